@@ -0,0 +1,100 @@
+//! Compares looking up the lessons of a room, a teacher, and a class using
+//! the indexes of a [`Timetable`] against scanning every entry.
+//!
+//! ```text
+//! cargo bench --bench lookup
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use timetableau::{
+    Activity, Class, Location, LocationFilter, RangedU8, Subject, Teacher, TimeSlot, Timetable,
+};
+
+// The number of lessons in the timetable (i.e., a whole-school timetable)
+const LESSONS: usize = 4000;
+
+// The number of times each lookup is repeated
+const ITERATIONS: u32 = 1000;
+
+fn timetable() -> Timetable {
+    let rooms: Vec<Location> = Location::all().existing().collect();
+    let mut timetable = Timetable::new();
+
+    for lesson in 0..LESSONS {
+        let slot = TimeSlot::with_index(RangedU8::new((lesson % 50) as u8).unwrap());
+
+        timetable.insert(
+            slot,
+            Activity::Lesson {
+                subject: Subject::new(format!("S{}", lesson % 30)).unwrap(),
+                class: Class::new(format!("C{}", lesson % 400)).unwrap(),
+                teacher: Some(Teacher::new(format!("T{}", lesson % 150)).unwrap()),
+                location: rooms[lesson % rooms.len()],
+            },
+        );
+    }
+
+    timetable
+}
+
+// Time `ITERATIONS` calls of `f`, returning the average duration of a call
+fn time(mut f: impl FnMut() -> usize) -> Duration {
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn report(name: &str, scan: Duration, indexed: Duration) {
+    println!(
+        "{:<8} scan: {:>10.2?}  indexed: {:>10.2?}  ({:.0}x)",
+        name,
+        scan,
+        indexed,
+        scan.as_secs_f64() / indexed.as_secs_f64()
+    );
+}
+
+fn main() {
+    let timetable = timetable();
+    let room: Location = "H201".parse().unwrap();
+
+    let scan = time(|| {
+        timetable
+            .entries()
+            .filter(|(_, entry)| entry.activity.location() == Some(&room))
+            .count()
+    });
+    let indexed = time(|| timetable.lessons().in_room(room).count());
+    report("room", scan, indexed);
+
+    let scan = time(|| {
+        timetable
+            .entries()
+            .filter(|(_, entry)| {
+                entry
+                    .activity
+                    .teacher()
+                    .is_some_and(|teacher| teacher.code() == "T42")
+            })
+            .count()
+    });
+    let indexed = time(|| timetable.lessons().taught_by("T42").count());
+    report("teacher", scan, indexed);
+
+    let scan = time(|| {
+        timetable
+            .entries()
+            .filter(|(_, entry)| match &entry.activity {
+                Activity::Lesson { class, .. } => class.reference() == "C42",
+                _ => false,
+            })
+            .count()
+    });
+    let indexed = time(|| timetable.lessons().for_class("C42").count());
+    report("class", scan, indexed);
+}
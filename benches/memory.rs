@@ -0,0 +1,80 @@
+//! Compares the memory occupied by a whole-school [`Timetable`] against that
+//! of the equivalent [`CompactTimetable`].
+//!
+//! ```text
+//! cargo bench --bench memory
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use timetableau::{
+    Activity, Class, CompactTimetable, Location, LocationFilter, RangedU8, Subject, Teacher,
+    TimeSlot, Timetable,
+};
+
+// The number of lessons in the timetable (i.e., a whole-school timetable)
+const LESSONS: usize = 4000;
+
+// An allocator which records the number of bytes currently allocated
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+// Measure the number of bytes allocated by the value `f` produces (which
+// remain allocated until the value is dropped)
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let value = f();
+
+    (value, ALLOCATED.load(Ordering::Relaxed) - before)
+}
+
+fn main() {
+    let rooms: Vec<Location> = Location::all().existing().collect();
+
+    // The timetable's index and history are included, as they are a part of
+    // every timetable
+    let (timetable, ergonomic) = measure(|| {
+        let mut timetable = Timetable::new();
+
+        for lesson in 0..LESSONS {
+            timetable.insert(
+                TimeSlot::with_index(RangedU8::new((lesson % 50) as u8).unwrap()),
+                Activity::Lesson {
+                    subject: Subject::new(format!("Subject {}", lesson % 30)).unwrap(),
+                    class: Class::new(format!("Class {}", lesson % 400)).unwrap(),
+                    teacher: Some(Teacher::new(format!("T{}", lesson % 150)).unwrap()),
+                    location: rooms[lesson % rooms.len()],
+                },
+            );
+        }
+
+        timetable
+    });
+
+    let (compact, compact_size) = measure(|| CompactTimetable::try_from(&timetable).unwrap());
+
+    println!("Timetable:        {:>8} bytes", ergonomic);
+    println!("CompactTimetable: {:>8} bytes", compact_size);
+    println!(
+        "                  ({:.1}x smaller)",
+        ergonomic as f64 / compact_size as f64
+    );
+
+    drop(compact);
+}
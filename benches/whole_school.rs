@@ -0,0 +1,72 @@
+//! Measures parsing, indexing, and clash detection against an anonymised
+//! whole-school [`Fixture`], such that changes to each can be compared.
+//!
+//! ```text
+//! cargo bench --bench whole_school
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use timetableau::fixtures::Fixture;
+use timetableau::import::sims::SimsImporter;
+use timetableau::validate::{NoClashes, Validator};
+use timetableau::{Dataset, Timetable};
+
+// The seed of the fixture (such that every run measures the same dataset)
+const SEED: u64 = 42;
+
+// The number of times each measurement is repeated
+const ITERATIONS: u32 = 50;
+
+// Time `ITERATIONS` calls of `f`, returning the average duration of a call
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn report(name: &str, duration: Duration) {
+    println!("{:<20} {:>10.2?}", name, duration);
+}
+
+fn main() {
+    let fixture = Fixture::whole_school(SEED);
+    let timetable = &fixture.dataset.timetable;
+    let csv = fixture.to_sims_csv();
+    let json = fixture.dataset.to_json();
+
+    println!(
+        "{} lessons, {} students\n",
+        timetable.lessons().count(),
+        fixture.roster.enrolled().len()
+    );
+
+    // Parsing
+    report(
+        "parse sims",
+        time(|| SimsImporter::new().import(&csv).unwrap()),
+    );
+    report("parse dataset", time(|| Dataset::from_json(&json).unwrap()));
+
+    // Indexing (i.e., inserting every lesson into an empty timetable)
+    report(
+        "index",
+        time(|| {
+            let mut indexed = Timetable::new();
+
+            for (_, entry) in timetable.entries() {
+                indexed.insert(entry.slot, entry.activity.clone());
+            }
+
+            indexed
+        }),
+    );
+
+    // Clash detection
+    let validator = Validator::new().rule(NoClashes);
+    report("detect clashes", time(|| validator.validate(timetable)));
+}
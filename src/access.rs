@@ -0,0 +1,564 @@
+//! Role-based access control of the mutations of timetables.
+//!
+//! Every member of staff edits the timetable in a [`Role`] (e.g., a teacher,
+//! or the cover manager), which grants them a set of [`Permission`]s -- a
+//! [`Session`] wraps the timetable being edited, refusing any mutation which
+//! the role does not permit with an [`AccessDenied`] error, and recording the
+//! role of each permitted change in the timetable's
+//! [history](crate::Timetable::history):
+//!
+//! ```
+//! use timetableau::access::{Permission, Role, Session};
+//! use timetableau::{timeslot, Activity, Timetable};
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.set_author(Some("JSM".to_string()));
+//!
+//! let mut session = Session::new(&mut timetable, Role::Teacher);
+//! let denied = session.insert(timeslot!(W1MP1), Activity::Registration).unwrap_err();
+//! assert_eq!(denied.permission, Permission::EditStructure);
+//! drop(session);
+//!
+//! let mut session = Session::new(&mut timetable, Role::Admin);
+//! session.insert(timeslot!(W1MP1), Activity::Registration).unwrap();
+//! drop(session);
+//!
+//! assert_eq!(timetable.history()[0].author.as_deref(), Some("JSM"));
+//! assert_eq!(timetable.history()[0].role, Some(Role::Admin));
+//! ```
+//!
+//! # Permissions
+//!
+//! | Role                     | Set homework | Propose swaps | Change rooms | Edit structure |
+//! |--------------------------|--------------|---------------|--------------|----------------|
+//! | [`Role::Admin`]          | Yes          | Yes           | Yes          | Yes            |
+//! | [`Role::CoverManager`]   | Yes          | Yes           | Yes          | No             |
+//! | [`Role::Teacher`]        | Yes          | Yes           | No           | No             |
+//! | [`Role::ReadOnly`]       | No           | No            | No           | No             |
+
+use crate::homework::{Due, Homework};
+use crate::swaps::{Issue, Resolution, Suggestion, SwapEngine};
+use crate::{
+    Activity, Change, Entry, EntryId, Location, Occurrence, RoomOverride, TimeSlot, Timetable,
+    TimetableError,
+};
+use chrono::NaiveDate;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// The role in which a member of staff edits a timetable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// An administrator of the timetable (e.g., the deputy head responsible
+    /// for timetabling), who may make any change.
+    Admin,
+
+    /// The member of staff who arranges cover, who may change the rooms of
+    /// lessons but not the structure of the timetable.
+    CoverManager,
+
+    /// A teacher, who may set homework and propose changes of room.
+    Teacher,
+
+    /// A member of staff who may view the timetable, but not change it.
+    ReadOnly,
+}
+
+impl Role {
+    /// Determines whether the `Role` grants the `permission` provided (*see
+    /// the [module](self#permissions) documentation*).
+    pub fn permits(self, permission: Permission) -> bool {
+        match self {
+            Self::Admin => true,
+            Self::CoverManager => permission != Permission::EditStructure,
+            Self::Teacher => matches!(
+                permission,
+                Permission::SetHomework | Permission::ProposeSwaps
+            ),
+            Self::ReadOnly => false,
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Admin => f.write_str("admin"),
+            Self::CoverManager => f.write_str("cover manager"),
+            Self::Teacher => f.write_str("teacher"),
+            Self::ReadOnly => f.write_str("read-only"),
+        }
+    }
+}
+
+/// A kind of action which a [`Role`] may be permitted to take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Setting homework during a lesson.
+    SetHomework,
+
+    /// Proposing changes of room (*see [`SwapEngine`]*), without making
+    /// them.
+    ProposeSwaps,
+
+    /// Changing the rooms of lessons, whether permanently or only for a
+    /// range of dates (e.g., to accommodate cover).
+    ChangeRooms,
+
+    /// Inserting, removing, and moving the entries of the timetable.
+    EditStructure,
+}
+
+impl Permission {
+    /// Determines the `Permission` required to make the `change` provided.
+    pub fn required_for<L>(change: &Change<L>) -> Self {
+        match change {
+            Change::Inserted { .. } | Change::Removed { .. } | Change::Moved { .. } => {
+                Self::EditStructure
+            }
+            Change::Relocated { .. }
+            | Change::OverrideAdded(_)
+            | Change::OverrideRemoved { .. } => Self::ChangeRooms,
+        }
+    }
+}
+
+impl Display for Permission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SetHomework => f.write_str("set homework"),
+            Self::ProposeSwaps => f.write_str("propose changes of room"),
+            Self::ChangeRooms => f.write_str("change the rooms of lessons"),
+            Self::EditStructure => f.write_str("edit the structure of the timetable"),
+        }
+    }
+}
+
+/// An error returned when a [`Session`] refuses an action which its role
+/// does not permit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessDenied {
+    /// The role of the session.
+    pub role: Role,
+
+    /// The permission which the action required.
+    pub permission: Permission,
+}
+
+impl Display for AccessDenied {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "the {} role may not {}", self.role, self.permission)
+    }
+}
+
+impl Error for AccessDenied {}
+
+/// A timetable being edited in a [`Role`], which only permits the mutations
+/// granted to the role.
+///
+/// The role is recorded alongside the [author](Timetable::set_author) of
+/// each change made through the `Session` (*see [`crate::Record::role`]*),
+/// and the timetable's previous role is restored once the `Session` is
+/// dropped.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug)]
+pub struct Session<'a> {
+    timetable: &'a mut Timetable,
+    role: Role,
+    previous: Option<Role>,
+}
+
+impl<'a> Session<'a> {
+    /// Creates a new `Session` editing the `timetable` in the `role`
+    /// provided.
+    pub fn new(timetable: &'a mut Timetable, role: Role) -> Self {
+        let previous = timetable.role();
+        timetable.set_role(Some(role));
+
+        Self {
+            timetable,
+            role,
+            previous,
+        }
+    }
+
+    /// Retrieves the role of the `Session`.
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Retrieves the timetable being edited.
+    pub fn timetable(&self) -> &Timetable {
+        self.timetable
+    }
+
+    // Retrieve the timetable being edited, mutably (for the callers which
+    // have already authorised their changes)
+    pub(crate) fn timetable_mut(&mut self) -> &mut Timetable {
+        self.timetable
+    }
+
+    /// Ensures the role of the `Session` grants the `permission` provided.
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not grant the
+    /// permission.
+    pub fn authorise(&self, permission: Permission) -> Result<(), AccessDenied> {
+        match self.role.permits(permission) {
+            true => Ok(()),
+            false => Err(AccessDenied {
+                role: self.role,
+                permission,
+            }),
+        }
+    }
+
+    /// Schedules an `activity` for the `slot` provided -- *see
+    /// [`Timetable::insert`]*.
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [editing the structure](Permission::EditStructure) of the timetable.
+    pub fn insert(&mut self, slot: TimeSlot, activity: Activity) -> Result<EntryId, AccessDenied> {
+        self.authorise(Permission::EditStructure)?;
+        Ok(self.timetable.insert(slot, activity))
+    }
+
+    /// Schedules an `activity` spanning the number of consecutive `periods`
+    /// provided (e.g., `2` for a double lesson), starting from the `slot`
+    /// provided -- *see [`Timetable::insert_spanning`]*.
+    ///
+    /// # Returns
+    ///
+    /// The result of the insertion (*see [`Timetable::insert_spanning`]*).
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [editing the structure](Permission::EditStructure) of the timetable.
+    pub fn insert_spanning(
+        &mut self,
+        slot: TimeSlot,
+        periods: u8,
+        activity: Activity,
+    ) -> Result<Result<EntryId, TimetableError>, AccessDenied> {
+        self.authorise(Permission::EditStructure)?;
+        Ok(self.timetable.insert_spanning(slot, periods, activity))
+    }
+
+    /// Schedules an `entry` (e.g., a team-taught lesson, or a lesson split
+    /// across multiple rooms) -- *see [`Timetable::insert_entry`]*.
+    ///
+    /// # Returns
+    ///
+    /// The result of the insertion (*see [`Timetable::insert_entry`]*).
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [editing the structure](Permission::EditStructure) of the timetable.
+    pub fn insert_entry(
+        &mut self,
+        entry: Entry,
+    ) -> Result<Result<EntryId, TimetableError>, AccessDenied> {
+        self.authorise(Permission::EditStructure)?;
+        Ok(self.timetable.insert_entry(entry))
+    }
+
+    /// Removes the entry with the identifier `id` -- *see
+    /// [`Timetable::remove`]*.
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [editing the structure](Permission::EditStructure) of the timetable.
+    pub fn remove(&mut self, id: EntryId) -> Result<Option<Entry>, AccessDenied> {
+        self.authorise(Permission::EditStructure)?;
+        Ok(self.timetable.remove(id))
+    }
+
+    /// Moves the entry with the identifier `id` to the `slot` provided --
+    /// *see [`Timetable::move_entry`]*.
+    ///
+    /// # Returns
+    ///
+    /// The result of the move (*see [`Timetable::move_entry`]*).
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [editing the structure](Permission::EditStructure) of the timetable.
+    pub fn move_entry(
+        &mut self,
+        id: EntryId,
+        slot: TimeSlot,
+    ) -> Result<Result<Option<TimeSlot>, TimetableError>, AccessDenied> {
+        self.authorise(Permission::EditStructure)?;
+        Ok(self.timetable.move_entry(id, slot))
+    }
+
+    /// Changes the location of the lesson with the identifier `id` -- *see
+    /// [`Timetable::relocate`]*.
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [changing the rooms](Permission::ChangeRooms) of lessons.
+    pub fn relocate(
+        &mut self,
+        id: EntryId,
+        location: Location,
+    ) -> Result<Option<Location>, AccessDenied> {
+        self.authorise(Permission::ChangeRooms)?;
+        Ok(self.timetable.relocate(id, location))
+    }
+
+    /// Temporarily moves the lessons taking place during the `slot`
+    /// provided to `location` for the range of `dates` provided -- *see
+    /// [`Timetable::override_room`]*.
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [changing the rooms](Permission::ChangeRooms) of lessons.
+    pub fn override_room(
+        &mut self,
+        slot: TimeSlot,
+        dates: RangeInclusive<NaiveDate>,
+        location: Location,
+    ) -> Result<bool, AccessDenied> {
+        self.authorise(Permission::ChangeRooms)?;
+        Ok(self.timetable.override_room(slot, dates, location))
+    }
+
+    /// Temporarily moves the lessons taking place in the `from` location
+    /// during the `slot` provided to `location` for the range of `dates`
+    /// provided -- *see [`Timetable::override_location`]*.
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [changing the rooms](Permission::ChangeRooms) of lessons.
+    pub fn override_location(
+        &mut self,
+        slot: TimeSlot,
+        dates: RangeInclusive<NaiveDate>,
+        from: Location,
+        location: Location,
+    ) -> Result<bool, AccessDenied> {
+        self.authorise(Permission::ChangeRooms)?;
+        Ok(self
+            .timetable
+            .override_location(slot, dates, from, location))
+    }
+
+    /// Removes (and returns) the room override at position `index` of
+    /// [`Timetable::overrides`] -- *see [`Timetable::remove_override`]*.
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [changing the rooms](Permission::ChangeRooms) of lessons.
+    pub fn remove_override(&mut self, index: usize) -> Result<Option<RoomOverride>, AccessDenied> {
+        self.authorise(Permission::ChangeRooms)?;
+        Ok(self.timetable.remove_override(index))
+    }
+
+    /// Suggests the changes of room which would resolve the `issue`
+    /// provided, without making them -- *see [`SwapEngine::suggest_swaps`]*.
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [proposing changes of room](Permission::ProposeSwaps).
+    pub fn propose_swaps(
+        &self,
+        engine: &SwapEngine,
+        issue: &Issue,
+    ) -> Result<Vec<Suggestion>, AccessDenied> {
+        self.authorise(Permission::ProposeSwaps)?;
+        Ok(engine.suggest_swaps(self.timetable, issue))
+    }
+
+    /// Makes the change of room of a proposed `resolution` (e.g., one
+    /// proposed by a teacher, and accepted by the cover manager) -- *see
+    /// [`Resolution::apply`]*.
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [changing the rooms](Permission::ChangeRooms) of lessons.
+    pub fn apply_swap(&mut self, resolution: &Resolution) -> Result<(), AccessDenied> {
+        self.authorise(Permission::ChangeRooms)?;
+        resolution.apply(self.timetable);
+        Ok(())
+    }
+
+    /// Sets homework during the `lesson` provided -- *see
+    /// [`Homework::new`]*.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the lesson is not a lesson of the timetable.
+    ///
+    /// # Errors
+    ///
+    /// [`AccessDenied`] is returned should the role not permit
+    /// [setting homework](Permission::SetHomework).
+    pub fn set_homework(
+        &self,
+        title: &str,
+        lesson: &Occurrence,
+        due: Due,
+    ) -> Result<Option<Homework>, AccessDenied> {
+        self.authorise(Permission::SetHomework)?;
+        Ok(Homework::new(title, lesson, due))
+    }
+}
+
+impl Drop for Session<'_> {
+    fn drop(&mut self) {
+        self.timetable.set_role(self.previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::{NoClashes, Rule};
+    use crate::{timeslot, Class, Subject};
+
+    #[test]
+    fn roles_enforced() {
+        let lesson = |class: &str| Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: None,
+            location: "H201".parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        let first = timetable.insert(timeslot!(W1MP1), lesson("10A"));
+        timetable.insert(timeslot!(W1MP1), lesson("10B"));
+
+        let clash = Issue::from(&NoClashes.check(&timetable)[0]);
+        let engine = SwapEngine::new(["H202".parse().unwrap()]);
+
+        // A teacher may propose a change of room, but not make it
+        let mut session = Session::new(&mut timetable, Role::Teacher);
+        let suggestions = session.propose_swaps(&engine, &clash).unwrap();
+        assert_eq!(
+            session.apply_swap(&suggestions[0].resolution),
+            Err(AccessDenied {
+                role: Role::Teacher,
+                permission: Permission::ChangeRooms
+            })
+        );
+        assert_eq!(
+            session
+                .move_entry(first, timeslot!(W1MP2))
+                .unwrap_err()
+                .to_string(),
+            "the teacher role may not edit the structure of the timetable"
+        );
+        drop(session);
+
+        let mut session = Session::new(&mut timetable, Role::CoverManager);
+        session.apply_swap(&suggestions[0].resolution).unwrap();
+        assert!(session.remove(first).is_err());
+        drop(session);
+
+        let session = Session::new(&mut timetable, Role::ReadOnly);
+        assert!(session.propose_swaps(&engine, &clash).is_err());
+        drop(session);
+
+        // Only the permitted change was made, and the role is no longer set
+        let record = &timetable.history()[2];
+        assert_eq!(record.role, Some(Role::CoverManager));
+        assert_eq!(
+            Permission::required_for(&record.change),
+            Permission::ChangeRooms
+        );
+        assert_eq!(timetable.history().len(), 3);
+        assert_eq!(timetable.role(), None);
+    }
+
+    #[test]
+    fn spans_and_overrides_enforced() {
+        let lesson = Activity::Lesson {
+            subject: Subject::new("PE".to_string()).unwrap(),
+            class: Class::new("10A".to_string()).unwrap(),
+            teacher: None,
+            location: "Field".parse().unwrap(),
+        };
+        let dates = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap()
+            ..=NaiveDate::from_ymd_opt(2023, 9, 8).unwrap();
+
+        let mut timetable = Timetable::new();
+
+        // A cover manager may change rooms, but not schedule lessons
+        let mut session = Session::new(&mut timetable, Role::CoverManager);
+        assert_eq!(
+            session.insert_spanning(timeslot!(W1MP3), 3, lesson.clone()),
+            Err(AccessDenied {
+                role: Role::CoverManager,
+                permission: Permission::EditStructure
+            })
+        );
+        assert!(session
+            .insert_entry(Entry::new(timeslot!(W1MP1), lesson.clone()))
+            .is_err());
+        assert_eq!(
+            session.override_location(
+                timeslot!(W1MP3),
+                dates.clone(),
+                "Field".parse().unwrap(),
+                "Sports Hall".parse().unwrap()
+            ),
+            Ok(true)
+        );
+        drop(session);
+
+        // A teacher may do neither
+        let mut session = Session::new(&mut timetable, Role::Teacher);
+        assert_eq!(
+            session.remove_override(0).unwrap_err().to_string(),
+            "the teacher role may not change the rooms of lessons"
+        );
+        assert!(session
+            .override_location(
+                timeslot!(W1MP4),
+                dates,
+                "Field".parse().unwrap(),
+                "Hall".parse().unwrap()
+            )
+            .is_err());
+        drop(session);
+
+        // An administrator may do both (should the span fit the day)
+        let mut session = Session::new(&mut timetable, Role::Admin);
+        let triple = session
+            .insert_spanning(timeslot!(W1MP3), 3, lesson.clone())
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            session.insert_spanning(timeslot!(W1MP5), 2, lesson.clone()),
+            Ok(Err(TimetableError::InvalidSpan { .. }))
+        ));
+        assert!(session
+            .insert_entry(Entry::new(timeslot!(W1TP1), lesson))
+            .unwrap()
+            .is_ok());
+        assert!(session.remove_override(0).unwrap().is_some());
+        drop(session);
+
+        assert_eq!(timetable.get(triple).unwrap().periods.get(), 3);
+        assert!(timetable.overrides().is_empty());
+        assert_eq!(timetable.history().len(), 4);
+        assert_eq!(timetable.history()[0].role, Some(Role::CoverManager));
+    }
+}
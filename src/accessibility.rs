@@ -0,0 +1,333 @@
+//! Accessibility profiles of students, against which timetables are checked.
+//!
+//! An [`AccessibilityProfile`] records the [`Need`]s of a student (e.g., a
+//! wheelchair user, or a student who requires extra time to move between
+//! lessons), and is attached to the student by their student identifier
+//! (*see [`Roster`](crate::attendance::Roster)*) -- the
+//! [`AccessibilityNeeds`](crate::validate::AccessibilityNeeds) rule then
+//! flags every lesson of the student in a room they cannot access, and
+//! every transition between lessons which leaves them too little time:
+//!
+//! ```
+//! use timetableau::accessibility::{AccessibilityProfile, AccessibilityProfiles, Need};
+//! use timetableau::attendance::Roster;
+//! use timetableau::validate::{AccessibilityNeeds, Validator};
+//! use timetableau::{timeslot, Activity, BellSchedules, Class, Subject, Timetable};
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new("10A".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "H201".parse().unwrap(),
+//! });
+//!
+//! let mut roster = Roster::new();
+//! roster.enrol("10A", "1001");
+//!
+//! let mut profiles = AccessibilityProfiles::new();
+//! profiles.insert("1001", AccessibilityProfile::new().need(Need::WheelchairUser));
+//!
+//! let report = Validator::new()
+//!     .rule(AccessibilityNeeds::new(roster, profiles, BellSchedules::default()))
+//!     .validate(&timetable);
+//!
+//! assert_eq!(report.findings[0].message, "1001 (wheelchair user) cannot access H201");
+//! ```
+//!
+//! # Remarks
+//!
+//! A room above the ground floor is only accessible to a wheelchair user (or
+//! a student who cannot use stairs) should it have a [`LIFT`] recorded in
+//! the [`RoomRegistry`] -- rooms whose floor is not known (e.g., the rooms of
+//! the Fearnhill school) are assumed to be on the ground floor.
+
+use crate::{HighfieldFloor, Location, Room, RoomKind, RoomRegistry};
+use chrono::Duration;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// The name of the facility recorded against rooms which are reached by a
+/// lift (*see [`RoomRegistry::add_facility`]*).
+pub const LIFT: &str = "Lift";
+
+/// A single accessibility need of a student.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Need {
+    /// The student uses a wheelchair, and so requires step-free access to
+    /// their rooms.
+    WheelchairUser,
+
+    /// The student cannot use the stairs, and so requires step-free access
+    /// to their rooms.
+    NoStairs,
+
+    /// The student requires the extra time provided to move between rooms
+    /// (in addition to the passing time tolerated by the school).
+    ExtraTransitionTime(Duration),
+
+    /// The student must be taught on the ground floor, even should a lift
+    /// be available.
+    GroundFloorOnly,
+}
+
+impl Display for Need {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WheelchairUser => f.write_str("wheelchair user"),
+            Self::NoStairs => f.write_str("cannot use stairs"),
+            Self::ExtraTransitionTime(extra) => {
+                write!(f, "needs {} extra minutes", extra.num_minutes())
+            }
+            Self::GroundFloorOnly => f.write_str("must be on the ground floor"),
+        }
+    }
+}
+
+/// The accessibility needs of a single student.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessibilityProfile {
+    needs: Vec<Need>,
+}
+
+impl AccessibilityProfile {
+    /// Creates a new `AccessibilityProfile` without any needs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `need` provided to the `AccessibilityProfile`.
+    pub fn need(mut self, need: Need) -> Self {
+        if !self.needs.contains(&need) {
+            self.needs.push(need);
+        }
+
+        self
+    }
+
+    /// Retrieves the needs of the `AccessibilityProfile` (in the order in
+    /// which they were added).
+    pub fn needs(&self) -> &[Need] {
+        &self.needs
+    }
+
+    /// Retrieves the extra time the student requires to move between rooms
+    /// (i.e., the sum of their [`Need::ExtraTransitionTime`]s).
+    pub fn extra_time(&self) -> Duration {
+        self.needs
+            .iter()
+            .filter_map(|need| match need {
+                Need::ExtraTransitionTime(extra) => Some(*extra),
+                _ => None,
+            })
+            .fold(Duration::zero(), |total, extra| total + extra)
+    }
+
+    /// Determines whether the student can access the `location` provided,
+    /// consulting the `registry` for the rooms which are reached by a
+    /// [`LIFT`].
+    ///
+    /// Remote lessons are accessible to every student.
+    pub fn can_access(&self, location: &Location, registry: &RoomRegistry) -> bool {
+        if location.kind() == RoomKind::Remote {
+            return true;
+        }
+
+        let ground = location
+            .parts()
+            .floor
+            .is_none_or(|floor| floor == HighfieldFloor::Ground);
+
+        self.needs.iter().all(|need| match need {
+            Need::WheelchairUser | Need::NoStairs => {
+                ground || registry.has_facility(location, LIFT)
+            }
+            Need::GroundFloorOnly => ground,
+            Need::ExtraTransitionTime(_) => true,
+        })
+    }
+}
+
+impl Display for AccessibilityProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, need) in self.needs.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{}", need)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The [`AccessibilityProfile`]s of the students of a school (identified by
+/// their student identifiers).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessibilityProfiles {
+    students: BTreeMap<String, AccessibilityProfile>,
+}
+
+impl AccessibilityProfiles {
+    /// Creates a new `AccessibilityProfiles` without any profiles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attaches the `profile` provided to the `student`, replacing their
+    /// previous profile (should they have one).
+    pub fn insert(&mut self, student: &str, profile: AccessibilityProfile) {
+        self.students.insert(student.to_string(), profile);
+    }
+
+    /// Retrieves the profile of the `student` provided.
+    pub fn get(&self, student: &str) -> Option<&AccessibilityProfile> {
+        self.students.get(student)
+    }
+
+    /// Creates an iterator over the students with a profile, and their
+    /// profiles (ordered by their student identifiers).
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &AccessibilityProfile)> + '_ {
+        self.students
+            .iter()
+            .map(|(student, profile)| (student.as_str(), profile))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attendance::Roster;
+    use crate::validate::{AccessibilityNeeds, Severity, Validator};
+    use crate::{timeslot, Activity, BellSchedules, Class, Subject, Timetable};
+
+    #[test]
+    fn needs_validated() {
+        let mut timetable = Timetable::new();
+        let mut lesson = |slot, location: &str| {
+            timetable.insert(
+                slot,
+                Activity::Lesson {
+                    subject: Subject::new("Ma".to_string()).unwrap(),
+                    class: Class::new("10A".to_string()).unwrap(),
+                    teacher: None,
+                    location: location.parse().unwrap(),
+                },
+            )
+        };
+
+        let upstairs = lesson(timeslot!(W1MP1), "H201");
+        let ground = lesson(timeslot!(W1MP2), "HG03");
+        let lifted = lesson(timeslot!(W1MP3), "P105");
+
+        let mut roster = Roster::new();
+        roster.enrol("10A", "1001");
+        roster.enrol("10A", "1002");
+        roster.enrol("10A", "1003");
+
+        let mut profiles = AccessibilityProfiles::new();
+        profiles.insert(
+            "1001",
+            AccessibilityProfile::new().need(Need::WheelchairUser),
+        );
+        profiles.insert(
+            "1002",
+            AccessibilityProfile::new().need(Need::ExtraTransitionTime(Duration::minutes(5))),
+        );
+        profiles.insert(
+            "1003",
+            AccessibilityProfile::new().need(Need::GroundFloorOnly),
+        );
+
+        let mut registry = RoomRegistry::new();
+        registry.add_facility("P105".parse().unwrap(), LIFT);
+
+        // Break leaves enough time to move from HG03 to P105
+        let report = Validator::new()
+            .rule(
+                AccessibilityNeeds::new(roster, profiles, BellSchedules::default())
+                    .registry(registry),
+            )
+            .validate(&timetable);
+
+        let findings: Vec<_> = report
+            .findings
+            .iter()
+            .map(|finding| {
+                (
+                    finding.severity,
+                    finding.entries.clone(),
+                    finding.message.as_str(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            findings,
+            vec![
+                (
+                    Severity::Error,
+                    vec![upstairs],
+                    "1001 (wheelchair user) cannot access H201"
+                ),
+                (
+                    Severity::Warning,
+                    vec![upstairs, ground],
+                    "1002 needs 7 minutes to move from H201 to HG03, but 2 are available"
+                ),
+                (
+                    Severity::Error,
+                    vec![upstairs],
+                    "1003 (must be on the ground floor) cannot access H201"
+                ),
+                (
+                    Severity::Error,
+                    vec![lifted],
+                    "1003 (must be on the ground floor) cannot access P105"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn needs_after_double() {
+        let lesson = |location: &str| Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new("10A".to_string()).unwrap(),
+            teacher: None,
+            location: location.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        let double = timetable
+            .insert_spanning(timeslot!(W1MP1), 2, lesson("H201"))
+            .unwrap();
+        let after = timetable.insert(timeslot!(W1MP3), lesson("HG03"));
+
+        let mut roster = Roster::new();
+        roster.enrol("10A", "1002");
+
+        let mut profiles = AccessibilityProfiles::new();
+        profiles.insert(
+            "1002",
+            AccessibilityProfile::new().need(Need::ExtraTransitionTime(Duration::minutes(60))),
+        );
+
+        // The move is from the end of the double (over break), rather than
+        // from its first period
+        let report = Validator::new()
+            .rule(AccessibilityNeeds::new(
+                roster,
+                profiles,
+                BellSchedules::default(),
+            ))
+            .validate(&timetable);
+
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].severity, Severity::Warning);
+        assert_eq!(report.findings[0].entries, [double, after]);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::Location;
+use crate::{Location, TimetableError};
+use chrono::Duration;
 use std::fmt::{self, Display, Formatter, Write};
 
 /// A subject/course which a student can undertake.
@@ -13,14 +14,16 @@ pub struct Subject(String);
 impl Subject {
     /// Creates a new `Subject`.
     ///
-    /// Returns [`Some(Subject)`](Some) if `name` is an ASCII string with a
-    /// length in the range `1..=16`, and [`None`] otherwise.
-    pub fn new(name: String) -> Option<Self> {
+    /// # Errors
+    ///
+    /// [`TimetableError::InvalidSubject`] is returned should `name` not be an
+    /// ASCII string with a length in the range `1..=16`.
+    pub fn new(name: String) -> Result<Self, TimetableError> {
         // Check the name's validity
-        if name.len() < 1 || name.len() > 16 || !name.is_ascii() {
-            None
+        if name.is_empty() || name.len() > 16 || !name.is_ascii() {
+            Err(TimetableError::InvalidSubject(name))
         } else {
-            Some(Self(name))
+            Ok(Self(name))
         }
     }
 
@@ -58,15 +61,16 @@ pub struct Class(String);
 impl Class {
     /// Create a new `Class`.
     ///
-    /// [`Some(Class)`](Some) is returned if `reference` is a valid ASCII
-    /// string with a length in the range `1..=32`, otherwise [`None`] is
-    /// returned.
-    pub fn new(reference: String) -> Option<Self> {
+    /// # Errors
+    ///
+    /// [`TimetableError::InvalidClass`] is returned should `reference` not be
+    /// an ASCII string with a length in the range `1..=32`.
+    pub fn new(reference: String) -> Result<Self, TimetableError> {
         // Check the reference's validity
-        if reference.len() < 1 || reference.len() > 32 || !reference.is_ascii() {
-            None
+        if reference.is_empty() || reference.len() > 32 || !reference.is_ascii() {
+            Err(TimetableError::InvalidClass(reference))
         } else {
-            Some(Self(reference))
+            Ok(Self(reference))
         }
     }
 
@@ -82,11 +86,57 @@ impl Display for Class {
     }
 }
 
+/// A teacher (or other member of staff), identified by their staff code.
+///
+/// Staff codes are the short identifiers schools assign to their staff
+/// (typically their initials, e.g., `JSM`) -- they are used, rather than the
+/// staff member's name, as they are unique within a school.
+///
+/// # Remarks
+///
+/// The staff code must be an ASCII string with a length in the range
+/// `1..=16`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Teacher(String);
+
+impl Teacher {
+    /// Creates a new `Teacher`.
+    ///
+    /// # Errors
+    ///
+    /// [`TimetableError::InvalidTeacher`] is returned should `code` not be an
+    /// ASCII string with a length in the range `1..=16`.
+    pub fn new(code: String) -> Result<Self, TimetableError> {
+        // Check the code's validity
+        if code.is_empty() || code.len() > 16 || !code.is_ascii() {
+            Err(TimetableError::InvalidTeacher(code))
+        } else {
+            Ok(Self(code))
+        }
+    }
+
+    /// Retrieves the staff code of the `Teacher`.
+    pub fn code(&self) -> &String {
+        &self.0
+    }
+}
+
+impl Display for Teacher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 /// An activity which can occur over one or more [`TimeSlot`](crate::TimeSlot)s.
 ///
 /// *See the [`crate`] documentation for more information*.
+///
+/// # Remarks
+///
+/// An `Activity` is generic over the [`Room`](crate::Room) in which a lesson
+/// takes place, defaulting to a [`Location`].
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Activity {
+pub enum Activity<L = Location> {
     /// A lesson.
     Lesson {
         /// The subject of the `Lesson`.
@@ -99,9 +149,17 @@ pub enum Activity {
         /// In most cases, this is the teacher who teaches the `Lesson`.
         class: Class,
 
+        /// The teacher who teaches the `Lesson` (if known).
+        ///
+        /// # Remarks
+        ///
+        /// Student timetables often omit the teacher (as the class is usually
+        /// sufficient for students to identify the teacher).
+        teacher: Option<Teacher>,
+
         /// The [`Location`] of the `Lesson` (i.e., the room in which the
         /// lesson takes place).
-        location: Location,
+        location: L,
     },
 
     /// Where the students are required to register their presence with a
@@ -150,9 +208,58 @@ pub enum Activity {
     ///
     /// *See the [`crate`] documentation for more information*.
     Miscellaneous(String),
+
+    /// The student travels between the sites of two consecutive lessons
+    /// (e.g., walking from Highfield to Fearnhill during a free period).
+    ///
+    /// # Remarks
+    ///
+    /// A `Transit` is not usually timetabled -- it is inserted into the free
+    /// periods of composite timetables (*see
+    /// [`Timetable::insert_transits`](crate::Timetable::insert_transits)*),
+    /// such that rendered timetables show the journey instead of a free
+    /// period.
+    Transit {
+        /// The location of the lesson from which the student departs.
+        from: L,
+
+        /// The location of the lesson at which the student arrives.
+        to: L,
+
+        /// The estimated duration of the journey.
+        duration: Duration,
+    },
+}
+
+impl<L> Activity<L> {
+    /// Retrieves the [`Location`] of the `Activity`.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if the `Activity` is a [lesson](Self::Lesson), or [`None`]
+    /// if the `Activity` does not take place in a specific location.
+    pub fn location(&self) -> Option<&L> {
+        match self {
+            Self::Lesson { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    /// Retrieves the [`Teacher`] of the `Activity`.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if the `Activity` is a [lesson](Self::Lesson) whose teacher
+    /// is known, or [`None`] otherwise.
+    pub fn teacher(&self) -> Option<&Teacher> {
+        match self {
+            Self::Lesson { teacher, .. } => teacher.as_ref(),
+            _ => None,
+        }
+    }
 }
 
-impl Display for Activity {
+impl<L: Display> Display for Activity<L> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         use Activity::*;
 
@@ -161,6 +268,7 @@ impl Display for Activity {
                 subject,
                 class,
                 location,
+                ..
             } => {
                 subject.fmt(f)?;
                 f.write_char(' ')?;
@@ -173,6 +281,7 @@ impl Display for Activity {
             SchoolStudy => f.write_str("Independent Study"),
             HomeStudy => f.write_str("Home Study"),
             Miscellaneous(description) => description.fmt(f),
+            Transit { from, to, .. } => write!(f, "Transit {} -> {}", from, to),
         }
     }
 }
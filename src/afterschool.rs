@@ -0,0 +1,372 @@
+use crate::{Location, Room, School, Subject, Teacher, TimeSlot, TimetableError, Week};
+use chrono::prelude::*;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+
+/// The after-school session of a particular day of the timetable (i.e., the
+/// equivalent of a seventh period), during which detentions, revision
+/// sessions, and rehearsals take place.
+///
+/// Each day of the timetable has a single after-school session, which takes
+/// place after [`Period::Fifth`](crate::Period::Fifth) at the times given by
+/// the [`AfterSchoolBells`] of the school.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AfterSchoolSlot {
+    pub week: Week,
+    pub day: Weekday,
+}
+
+impl AfterSchoolSlot {
+    /// Retrieves the `AfterSchoolSlot` of the day on which the `slot`
+    /// provided takes place.
+    pub fn after(slot: TimeSlot) -> Self {
+        Self {
+            week: slot.week,
+            day: slot.day,
+        }
+    }
+
+    /// Creates a new `AfterSchoolSlot` based on the `datetime` -- should the
+    /// `datetime` take place during the after-school session of the `school`
+    /// provided (*see [`AfterSchoolBells`]*), that session will be returned,
+    /// otherwise [`None`] will be returned instead.
+    ///
+    /// *See [`TimeSlot::from_datetime`] for more information*.
+    pub fn from_datetime<Tz>(
+        week: Week,
+        datetime: DateTime<Tz>,
+        bells: &AfterSchoolBells,
+        school: School,
+    ) -> Option<Self>
+    where
+        Tz: TimeZone,
+    {
+        let day = datetime.weekday();
+
+        // No after-school sessions take place over the weekend
+        if day == Weekday::Sat || day == Weekday::Sun {
+            return None;
+        }
+
+        let (start, end) = bells.get(school);
+        let time = datetime.time();
+
+        (time >= start && time < end).then_some(Self { week, day })
+    }
+}
+
+/// The times of the after-school session (*see [`AfterSchoolSlot`]*) at both
+/// the Highfield school and the Fearnhill school.
+///
+/// The default value contains the default times of each school:
+///
+/// | School    | Start Time | End Time |
+/// |-----------|------------|----------|
+/// | Highfield | 15:05      | 16:05    |
+/// | Fearnhill | 15:00      | 16:00    |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AfterSchoolBells {
+    highfield: (NaiveTime, NaiveTime),
+    fearnhill: (NaiveTime, NaiveTime),
+}
+
+impl AfterSchoolBells {
+    /// Retrieves the `(start, end)` times of the after-school session of the
+    /// `school` provided.
+    pub fn get(&self, school: School) -> (NaiveTime, NaiveTime) {
+        match school {
+            School::Highfield => self.highfield,
+            School::Fearnhill => self.fearnhill,
+        }
+    }
+
+    /// Replaces the `(start, end)` times of the after-school session of the
+    /// `school` provided.
+    ///
+    /// # Errors
+    ///
+    /// [`TimetableError::EmptyBellRange`] is returned should the session not
+    /// start before it ends.
+    pub fn set(
+        &mut self,
+        school: School,
+        (start, end): (NaiveTime, NaiveTime),
+    ) -> Result<(), TimetableError> {
+        if start >= end {
+            return Err(TimetableError::EmptyBellRange {
+                start: start.into(),
+                end: end.into(),
+            });
+        }
+
+        match school {
+            School::Highfield => self.highfield = (start, end),
+            School::Fearnhill => self.fearnhill = (start, end),
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for AfterSchoolBells {
+    fn default() -> Self {
+        let hm = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+
+        Self {
+            highfield: (hm(15, 5), hm(16, 5)),
+            fearnhill: (hm(15, 0), hm(16, 0)),
+        }
+    }
+}
+
+/// An event which takes place during an after-school session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AfterSchoolEvent {
+    /// A detention.
+    Detention,
+
+    /// A revision session of the subject provided.
+    Revision(Subject),
+
+    /// A rehearsal (e.g., of the school play).
+    Rehearsal(String),
+
+    /// Any other event (e.g., a club).
+    Other(String),
+}
+
+/// An [`AfterSchoolEvent`] which has been booked into a room for a particular
+/// [`AfterSchoolSlot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AfterSchoolBooking<L = Location> {
+    /// The after-school session during which the event takes place.
+    pub slot: AfterSchoolSlot,
+
+    /// The event which takes place.
+    pub event: AfterSchoolEvent,
+
+    /// The teacher supervising the event (if any).
+    pub teacher: Option<Teacher>,
+
+    /// The room in which the event takes place.
+    pub location: L,
+}
+
+/// A clash which prevents an [`AfterSchoolBooking`] from being made.
+///
+/// Existing bookings are identified by their index within
+/// [`AfterSchoolTimetable::bookings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AfterSchoolClash<L = Location> {
+    /// The teacher already supervises another event during the session.
+    Teacher {
+        /// The index of the existing booking.
+        booking: usize,
+
+        /// The teacher supervising both events.
+        teacher: Teacher,
+    },
+
+    /// The room is already booked by another event during the session.
+    Room {
+        /// The index of the existing booking.
+        booking: usize,
+
+        /// The room booked by both events.
+        location: L,
+    },
+}
+
+impl<L: Display> Display for AfterSchoolClash<L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Teacher { booking, teacher } => write!(
+                f,
+                "{} already supervises booking {} during the session",
+                teacher, booking
+            ),
+            Self::Room { booking, location } => write!(
+                f,
+                "{} is already used by booking {} during the session",
+                location, booking
+            ),
+        }
+    }
+}
+
+impl<L: Debug + Display> Error for AfterSchoolClash<L> {}
+
+/// The [`AfterSchoolBooking`]s of a school, which are checked for clashes in
+/// the same way as the lessons of a [`Timetable`](crate::Timetable) (*see
+/// [`NoClashes`](crate::validate::NoClashes)*):
+///
+/// ```
+/// use timetableau::{timeslot, AfterSchoolBooking, AfterSchoolEvent, AfterSchoolSlot};
+/// use timetableau::AfterSchoolTimetable;
+///
+/// let slot = AfterSchoolSlot::after(timeslot!(W1MP5));
+/// let detention = AfterSchoolBooking {
+///     slot,
+///     event: AfterSchoolEvent::Detention,
+///     teacher: None,
+///     location: "H201".parse().unwrap(),
+/// };
+///
+/// let mut bookings = AfterSchoolTimetable::new();
+///
+/// assert_eq!(bookings.book(detention.clone()), Ok(0));
+/// assert!(bookings.book(detention).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AfterSchoolTimetable<L = Location> {
+    bookings: Vec<AfterSchoolBooking<L>>,
+}
+
+impl<L> Default for AfterSchoolTimetable<L> {
+    fn default() -> Self {
+        Self {
+            bookings: Vec::new(),
+        }
+    }
+}
+
+impl AfterSchoolTimetable {
+    /// Creates a new `AfterSchoolTimetable` with no bookings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<L: Room> AfterSchoolTimetable<L> {
+    /// Books the `booking` provided, returning its index within
+    /// [`Self::bookings`].
+    ///
+    /// # Errors
+    ///
+    /// An [`AfterSchoolClash`] is returned (and the booking is not made)
+    /// should its teacher or its room already be booked during the same
+    /// session -- should both be, the teacher's clash is returned.
+    pub fn book(&mut self, booking: AfterSchoolBooking<L>) -> Result<usize, AfterSchoolClash<L>> {
+        if let Some(teacher) = &booking.teacher {
+            if let Some((index, _)) = self
+                .at(booking.slot)
+                .find(|(_, other)| other.teacher.as_ref() == Some(teacher))
+            {
+                return Err(AfterSchoolClash::Teacher {
+                    booking: index,
+                    teacher: teacher.clone(),
+                });
+            }
+        }
+
+        // Remote sessions (and sessions whose room is yet to be confirmed)
+        // occupy no room, and therefore never clash
+        let occupied = booking.location.kind().is_occupied();
+
+        if let Some((index, _)) = self
+            .at(booking.slot)
+            .find(|(_, other)| occupied && other.location == booking.location)
+        {
+            return Err(AfterSchoolClash::Room {
+                booking: index,
+                location: booking.location,
+            });
+        }
+
+        self.bookings.push(booking);
+
+        Ok(self.bookings.len() - 1)
+    }
+
+    /// Cancels the booking at the `index` provided, returning it.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if there is no booking at the `index` provided -- the indexes
+    /// of later bookings are decremented.
+    pub fn cancel(&mut self, index: usize) -> Option<AfterSchoolBooking<L>> {
+        (index < self.bookings.len()).then(|| self.bookings.remove(index))
+    }
+
+    /// Retrieves every booking (in the order in which they were booked).
+    pub fn bookings(&self) -> &[AfterSchoolBooking<L>] {
+        &self.bookings
+    }
+
+    /// Retrieves the bookings (and their indexes) of the `slot` provided.
+    pub fn at(
+        &self,
+        slot: AfterSchoolSlot,
+    ) -> impl Iterator<Item = (usize, &AfterSchoolBooking<L>)> + '_ {
+        self.bookings
+            .iter()
+            .enumerate()
+            .filter(move |(_, booking)| booking.slot == slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeslot;
+
+    #[test]
+    fn after_school_bookings() {
+        let teacher = Teacher::new("ABC".to_string()).unwrap();
+        let monday = AfterSchoolSlot::after(timeslot!(W1MP3));
+        let booking = |slot, teacher: Option<&Teacher>, room: &str| AfterSchoolBooking {
+            slot,
+            event: AfterSchoolEvent::Revision(Subject::new("Ma".to_string()).unwrap()),
+            teacher: teacher.cloned(),
+            location: room.parse().unwrap(),
+        };
+
+        let mut bookings = AfterSchoolTimetable::new();
+
+        assert_eq!(
+            bookings.book(booking(monday, Some(&teacher), "H201")),
+            Ok(0)
+        );
+        assert_eq!(
+            bookings.book(booking(monday, Some(&teacher), "H202")),
+            Err(AfterSchoolClash::Teacher {
+                booking: 0,
+                teacher: teacher.clone(),
+            })
+        );
+        assert_eq!(
+            bookings.book(booking(monday, None, "H201")),
+            Err(AfterSchoolClash::Room {
+                booking: 0,
+                location: "H201".parse().unwrap(),
+            })
+        );
+
+        // The same teacher and room are free during another session
+        let tuesday = AfterSchoolSlot::after(timeslot!(W1TP1));
+        assert_eq!(
+            bookings.book(booking(tuesday, Some(&teacher), "H201")),
+            Ok(1)
+        );
+        assert_eq!(bookings.at(monday).count(), 1);
+
+        assert!(bookings.cancel(0).is_some());
+        assert_eq!(bookings.book(booking(monday, None, "H201")), Ok(1));
+
+        // The session takes place at the times of the school
+        let mut bells = AfterSchoolBells::default();
+        let at = |hour, minute| Utc.with_ymd_and_hms(2023, 9, 4, hour, minute, 0).unwrap();
+
+        assert_eq!(
+            AfterSchoolSlot::from_datetime(Week::WeekOne, at(15, 30), &bells, School::Fearnhill),
+            Some(monday)
+        );
+        assert_eq!(
+            AfterSchoolSlot::from_datetime(Week::WeekOne, at(15, 2), &bells, School::Highfield),
+            None
+        );
+
+        let start = NaiveTime::from_hms_opt(16, 0, 0).unwrap();
+        assert!(bells.set(School::Highfield, (start, start)).is_err());
+    }
+}
@@ -0,0 +1,906 @@
+//! Automatic allocation of rooms to lessons.
+//!
+//! Given a set of [`Request`]s (i.e., lessons which have been scheduled but
+//! have not yet been assigned a room) and the [`Room`]s available, an
+//! [`Allocator`] assigns a [`Location`] to every request such that:
+//!
+//! * no room hosts multiple lessons during the same timeslot (including the
+//!   lessons already present in the timetable);
+//! * every room can seat the class of the lesson it hosts; and
+//! * every room has the equipment required by the lesson it hosts.
+//!
+//! Of the allocations satisfying these constraints, the allocator searches
+//! for the allocation which best respects the soft constraints -- lessons are
+//! preferably held in the [`Area`] preferred for their subject (e.g., maths
+//! lessons in the Parker block), and teachers and classes should not have to
+//! move between schools between consecutive lessons.
+//!
+//! ```
+//! use timetableau::allocate::{Allocator, Area, Request, Room};
+//! use timetableau::{timeslot, Class, HighfieldBlock, Subject, Timetable};
+//!
+//! let allocator = Allocator::new()
+//!     .room(Room::new("P101".parse().unwrap(), 30))
+//!     .room(Room::new("H101".parse().unwrap(), 30).equipment("Projector"))
+//!     .prefer(["Ma"], Area::Block(HighfieldBlock::Parker));
+//!
+//! let requests = [Request::new(
+//!     timeslot!(W1MP1),
+//!     Subject::new("Ma".to_string()).unwrap(),
+//!     Class::new("10A".to_string()).unwrap(),
+//!     None,
+//!     28,
+//! )];
+//!
+//! let allocation = allocator.allocate(&Timetable::new(), &requests).unwrap();
+//!
+//! assert_eq!(allocation.rooms, vec!["P101".parse().unwrap()]);
+//! ```
+//!
+//! # Remarks
+//!
+//! Allocation is performed by a branch and bound search (seeded by a
+//! per-timeslot matching, such that an allocation is always found when one
+//! exists) -- as the search is exponential in the worst case, it is bounded
+//! by a [limit](Allocator::limit) on the number of assignments explored,
+//! after which the best allocation found is returned.
+
+use crate::department::Department;
+use crate::{
+    Activity, Class, EntryId, FearnhillRoom, FearnhillSection, HighfieldBlock, HighfieldFloor,
+    HighfieldRoom, Location, RoomRegistry, School, Subject, Teacher, TimeSlot, Timetable,
+};
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// A room which can be allocated to lessons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Room {
+    /// The location of the room.
+    pub location: Location,
+
+    /// The number of students the room can seat.
+    pub capacity: u32,
+
+    /// The equipment available in the room (e.g., `Projector` or `Fume
+    /// Cupboard`).
+    pub equipment: HashSet<String>,
+}
+
+impl Room {
+    /// Creates a new `Room` (without any equipment) which can seat
+    /// `capacity` students.
+    pub fn new(location: Location, capacity: u32) -> Self {
+        Self {
+            location,
+            capacity,
+            equipment: HashSet::new(),
+        }
+    }
+
+    /// Records that the `Room` has the equipment with the `name` provided.
+    pub fn equipment(mut self, name: &str) -> Self {
+        self.equipment.insert(name.to_string());
+        self
+    }
+
+    /// Records that the `Room` has every facility recorded for its location
+    /// by the `registry` provided (e.g., `Gas Taps` for a laboratory), such
+    /// that requests [requiring](Request::requires) them can be allocated to
+    /// it.
+    pub fn facilities(mut self, registry: &RoomRegistry) -> Self {
+        self.equipment
+            .extend(registry.facilities(&self.location).map(str::to_string));
+        self
+    }
+}
+
+/// A lesson which requires a room.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// The timeslot during which the lesson takes place.
+    pub slot: TimeSlot,
+
+    /// The subject of the lesson.
+    pub subject: Subject,
+
+    /// The class of the lesson.
+    pub class: Class,
+
+    /// The teacher of the lesson (if known).
+    pub teacher: Option<Teacher>,
+
+    /// The number of students in the class.
+    pub size: u32,
+
+    /// The equipment the room must have.
+    pub equipment: Vec<String>,
+}
+
+impl Request {
+    /// Creates a new `Request` which requires no equipment.
+    pub fn new(
+        slot: TimeSlot,
+        subject: Subject,
+        class: Class,
+        teacher: Option<Teacher>,
+        size: u32,
+    ) -> Self {
+        Self {
+            slot,
+            subject,
+            class,
+            teacher,
+            size,
+            equipment: Vec::new(),
+        }
+    }
+
+    /// Records that the lesson requires the equipment with the `name`
+    /// provided.
+    pub fn requires(mut self, name: &str) -> Self {
+        self.equipment.push(name.to_string());
+        self
+    }
+
+    // Whether the lesson is taught by the same teacher (or to the same class)
+    // as a lesson with the `teacher` and `class` provided
+    fn shares(&self, teacher: Option<&Teacher>, class: &Class) -> bool {
+        (self.teacher.is_some() && self.teacher.as_ref() == teacher) || self.class == *class
+    }
+}
+
+/// An area of either school (in which a department's rooms are typically
+/// located).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Area {
+    /// A block of the Highfield school.
+    Block(HighfieldBlock),
+
+    /// A floor of a block of the Highfield school.
+    Floor {
+        /// The block of the floor.
+        block: HighfieldBlock,
+
+        /// The floor of the block.
+        floor: HighfieldFloor,
+    },
+
+    /// A section of the Fearnhill school.
+    Section(FearnhillSection),
+}
+
+impl Area {
+    /// Whether the `location` is located within the `Area`.
+    pub fn contains(self, location: Location) -> bool {
+        match (self, location) {
+            (Self::Block(area), Location::Highfield(HighfieldRoom::Classroom { block, .. })) => {
+                area == block
+            }
+            (
+                Self::Floor {
+                    block: area,
+                    floor: level,
+                },
+                Location::Highfield(HighfieldRoom::Classroom { block, floor, .. }),
+            ) => area == block && level == floor,
+            (
+                Self::Section(area),
+                Location::Fearnhill(FearnhillRoom::Classroom { section, .. }),
+            ) => area == section,
+            _ => false,
+        }
+    }
+}
+
+/// A constraint which prevents a set of [`Request`]s from being allocated a
+/// room.
+///
+/// Requests are identified by their index within the slice passed to
+/// [`Allocator::allocate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conflict {
+    /// No room can seat the class of the request.
+    Capacity {
+        /// The index of the request.
+        request: usize,
+    },
+
+    /// No room which can seat the class of the request has all of the
+    /// equipment required.
+    Equipment {
+        /// The index of the request.
+        request: usize,
+
+        /// The equipment required by the request.
+        equipment: Vec<String>,
+    },
+
+    /// There are fewer suitable rooms free during the timeslot than there
+    /// are requests competing for them.
+    Occupied {
+        /// The timeslot during which the requests take place.
+        slot: TimeSlot,
+
+        /// The indexes of the competing requests.
+        requests: Vec<usize>,
+    },
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Capacity { request } => {
+                write!(f, "no room can seat the class of request {}", request)
+            }
+            Self::Equipment { request, equipment } => write!(
+                f,
+                "no room seating the class of request {} has all of: {}",
+                request,
+                equipment.join(", ")
+            ),
+            Self::Occupied { slot, requests } => {
+                let requests: Vec<String> = requests.iter().map(ToString::to_string).collect();
+
+                write!(
+                    f,
+                    "too few suitable rooms are free during {:?} {:?} {:?} for requests {}",
+                    slot.week,
+                    slot.day,
+                    slot.period,
+                    requests.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// An error returned when no allocation satisfies the hard constraints.
+///
+/// Every conflict found is reported (rather than only the first), such that
+/// all of them can be resolved before allocating again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsatisfiable {
+    /// The conflicts preventing an allocation.
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Display for Unsatisfiable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("rooms cannot be allocated: ")?;
+
+        for (i, conflict) in self.conflicts.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            Display::fmt(conflict, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for Unsatisfiable {}
+
+/// The rooms allocated to a set of [`Request`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Allocation {
+    /// The room allocated to each request (in the order of the requests).
+    pub rooms: Vec<Location>,
+
+    /// The cost of the allocation (i.e., the extent to which it violates the
+    /// soft constraints) -- lower is better.
+    pub cost: u32,
+
+    /// Whether the allocation is known to have the lowest possible cost
+    /// (i.e., the search was not stopped by its [limit](Allocator::limit)).
+    pub optimal: bool,
+}
+
+impl Allocation {
+    /// Inserts a lesson into the `timetable` for each of the `requests` (which
+    /// must be the requests from which the `Allocation` was created).
+    ///
+    /// # Returns
+    ///
+    /// The identifiers of the inserted entries (in the order of the
+    /// requests).
+    pub fn apply(&self, timetable: &mut Timetable, requests: &[Request]) -> Vec<EntryId> {
+        requests
+            .iter()
+            .zip(&self.rooms)
+            .map(|(request, location)| {
+                timetable.insert(
+                    request.slot,
+                    Activity::Lesson {
+                        subject: request.subject.clone(),
+                        class: request.class.clone(),
+                        teacher: request.teacher.clone(),
+                        location: *location,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// Allocates [`Room`]s to [`Request`]s.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone)]
+pub struct Allocator {
+    rooms: Vec<Room>,
+    preferences: Vec<(String, Area, u32)>,
+    move_penalty: u32,
+    limit: usize,
+}
+
+impl Default for Allocator {
+    fn default() -> Self {
+        Self {
+            rooms: Vec::new(),
+            preferences: Vec::new(),
+            move_penalty: 3,
+            limit: 100_000,
+        }
+    }
+}
+
+impl Allocator {
+    /// Creates a new `Allocator` without any rooms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `room` which can be allocated.
+    pub fn room(mut self, room: Room) -> Self {
+        self.rooms.push(room);
+        self
+    }
+
+    /// Prefers lessons of the `subjects` provided (i.e., a department) to be
+    /// allocated rooms within the `area` provided.
+    ///
+    /// Each lesson allocated a room outside of its preferred area adds `1` to
+    /// the cost of the allocation.
+    pub fn prefer<S>(mut self, subjects: S, area: Area) -> Self
+    where
+        S: IntoIterator,
+        S::Item: Into<String>,
+    {
+        self.preferences.extend(
+            subjects
+                .into_iter()
+                .map(|subject| (subject.into(), area, 1)),
+        );
+        self
+    }
+
+    /// Prefers lessons of the subjects of the `department` provided to be
+    /// allocated rooms within its home areas (*see [`Department::area`]*).
+    ///
+    /// Each lesson allocated a room outside of its department's areas adds
+    /// the [weight](Department::weight) of the department to the cost of the
+    /// allocation.
+    pub fn department(mut self, department: &Department) -> Self {
+        for subject in &department.subjects {
+            self.preferences.extend(
+                department
+                    .areas
+                    .iter()
+                    .map(|area| (subject.clone(), *area, department.weight)),
+            );
+        }
+
+        self
+    }
+
+    /// Sets the cost of a teacher or class moving between schools between
+    /// consecutive lessons (`3` by default).
+    pub fn move_penalty(mut self, penalty: u32) -> Self {
+        self.move_penalty = penalty;
+        self
+    }
+
+    /// Sets the maximum number of assignments explored by the search
+    /// (`100000` by default).
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Allocates a room to each of the `requests`, treating the rooms of the
+    /// lessons already in the `timetable` as occupied.
+    ///
+    /// # Errors
+    ///
+    /// [`Unsatisfiable`] is returned should no allocation satisfy the hard
+    /// constraints -- *see the [module](self) documentation for more
+    /// information*.
+    pub fn allocate(
+        &self,
+        timetable: &Timetable,
+        requests: &[Request],
+    ) -> Result<Allocation, Unsatisfiable> {
+        let problem = Problem::new(self, timetable, requests)?;
+        let seed = problem.seed()?;
+
+        Ok(problem.search(seed))
+    }
+
+    /// Determines whether a room can be allocated to each of the `requests`
+    /// (e.g., to check whether a projected timetable could be hosted at
+    /// all), without searching for the allocation which best respects the
+    /// soft constraints.
+    ///
+    /// # Errors
+    ///
+    /// [`Unsatisfiable`] is returned should no allocation satisfy the hard
+    /// constraints (*see [`Self::allocate`]*).
+    pub fn check(&self, timetable: &Timetable, requests: &[Request]) -> Result<(), Unsatisfiable> {
+        Problem::new(self, timetable, requests)?.seed().map(|_| ())
+    }
+
+    /// Retrieves the rooms which can be allocated (in the order in which
+    /// they were added).
+    pub fn rooms(&self) -> &[Room] {
+        &self.rooms
+    }
+
+    // The cost of holding the lesson of the `request` in the `room` provided
+    // (ignoring movement between the requests themselves)
+    fn static_cost(&self, timetable: &Timetable, request: &Request, room: &Room) -> u32 {
+        let mut cost = 0;
+
+        let areas: Vec<(Area, u32)> = self
+            .preferences
+            .iter()
+            .filter(|(subject, _, _)| subject == request.subject.name())
+            .map(|(_, area, weight)| (*area, *weight))
+            .collect();
+
+        // A lesson outside of every preferred area costs the greatest weight
+        // of its preferences
+        if !areas.iter().any(|(area, _)| area.contains(room.location)) {
+            cost += areas.iter().map(|(_, weight)| *weight).max().unwrap_or(0);
+        }
+
+        // Movement to and from the lessons already in the timetable
+        for (_, entry) in timetable.lessons() {
+            if let Activity::Lesson {
+                class,
+                teacher,
+                location,
+                ..
+            } = &entry.activity
+            {
+                if adjacent(request.slot, entry.slot)
+                    && request.shares(teacher.as_ref(), class)
+                    && location.school() != room.location.school()
+                {
+                    cost += self.move_penalty;
+                }
+            }
+        }
+
+        cost
+    }
+}
+
+// Whether `a` and `b` are consecutive periods of the same day
+fn adjacent(a: TimeSlot, b: TimeSlot) -> bool {
+    a.week == b.week
+        && a.day == b.day
+        && (a.period.next() == Some(b.period) || b.period.next() == Some(a.period))
+}
+
+// A request's suitable rooms and their costs
+struct Variable {
+    // The indexes of the suitable rooms and the static cost of each (ordered
+    // by cost)
+    domain: Vec<(usize, u32)>,
+
+    // The requests with which movement between schools is penalised
+    neighbours: Vec<usize>,
+}
+
+struct Problem<'a> {
+    allocator: &'a Allocator,
+    requests: &'a [Request],
+    variables: Vec<Variable>,
+}
+
+impl<'a> Problem<'a> {
+    fn new(
+        allocator: &'a Allocator,
+        timetable: &Timetable,
+        requests: &'a [Request],
+    ) -> Result<Self, Unsatisfiable> {
+        let occupied: HashSet<(TimeSlot, Location)> = timetable
+            .lessons()
+            .flat_map(|(_, entry)| {
+                entry
+                    .locations()
+                    .flat_map(move |location| entry.slots().map(move |slot| (slot, *location)))
+            })
+            .collect();
+
+        let mut conflicts = Vec::new();
+        let mut variables = Vec::new();
+
+        for (i, request) in requests.iter().enumerate() {
+            let seats: Vec<(usize, &Room)> = allocator
+                .rooms
+                .iter()
+                .enumerate()
+                .filter(|(_, room)| room.capacity >= request.size)
+                .collect();
+
+            let equipped: Vec<(usize, &Room)> = seats
+                .iter()
+                .copied()
+                .filter(|(_, room)| {
+                    request
+                        .equipment
+                        .iter()
+                        .all(|name| room.equipment.contains(name))
+                })
+                .collect();
+
+            if seats.is_empty() {
+                conflicts.push(Conflict::Capacity { request: i });
+            } else if equipped.is_empty() {
+                conflicts.push(Conflict::Equipment {
+                    request: i,
+                    equipment: request.equipment.clone(),
+                });
+            }
+
+            let mut domain: Vec<(usize, u32)> = equipped
+                .into_iter()
+                .filter(|(_, room)| !occupied.contains(&(request.slot, room.location)))
+                .map(|(index, room)| (index, allocator.static_cost(timetable, request, room)))
+                .collect();
+            domain.sort_by_key(|(index, cost)| (*cost, *index));
+
+            let neighbours = requests
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| {
+                    *j != i
+                        && adjacent(request.slot, other.slot)
+                        && request.shares(other.teacher.as_ref(), &other.class)
+                })
+                .map(|(j, _)| j)
+                .collect();
+
+            variables.push(Variable { domain, neighbours });
+        }
+
+        if conflicts.is_empty() {
+            Ok(Self {
+                allocator,
+                requests,
+                variables,
+            })
+        } else {
+            Err(Unsatisfiable { conflicts })
+        }
+    }
+
+    // Find an allocation satisfying the hard constraints (ignoring cost) by
+    // matching the requests of each timeslot to its free rooms
+    fn seed(&self) -> Result<Vec<usize>, Unsatisfiable> {
+        let mut slots: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+
+        for (i, request) in self.requests.iter().enumerate() {
+            slots.entry(request.slot.index()).or_default().push(i);
+        }
+
+        let mut rooms = vec![0; self.requests.len()];
+        let mut conflicts = Vec::new();
+
+        for requests in slots.values() {
+            // The request to which each room is matched
+            let mut matched: Vec<Option<usize>> = vec![None; self.allocator.rooms.len()];
+
+            for &request in requests {
+                let mut visited = vec![false; matched.len()];
+
+                if !self.augment(request, &mut matched, &mut visited) {
+                    // The requests reachable from an unmatched request (via
+                    // alternating paths) compete for too few rooms
+                    let mut competing: Vec<usize> = visited
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, visited)| **visited)
+                        .filter_map(|(room, _)| matched[room])
+                        .chain([request])
+                        .collect();
+                    competing.sort_unstable();
+
+                    conflicts.push(Conflict::Occupied {
+                        slot: self.requests[request].slot,
+                        requests: competing,
+                    });
+                }
+            }
+
+            for (room, request) in matched.iter().enumerate() {
+                if let Some(request) = request {
+                    rooms[*request] = room;
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            Ok(rooms)
+        } else {
+            Err(Unsatisfiable { conflicts })
+        }
+    }
+
+    // Find an augmenting path from the `request` provided (Kuhn's algorithm)
+    fn augment(&self, request: usize, matched: &mut [Option<usize>], visited: &mut [bool]) -> bool {
+        for &(room, _) in &self.variables[request].domain {
+            if visited[room] {
+                continue;
+            }
+            visited[room] = true;
+
+            if matched[room].is_none_or(|other| self.augment(other, matched, visited)) {
+                matched[room] = Some(request);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // The cost of an allocation
+    fn cost(&self, rooms: &[usize]) -> u32 {
+        let mut cost = 0;
+
+        for (i, variable) in self.variables.iter().enumerate() {
+            cost += variable
+                .domain
+                .iter()
+                .find(|(room, _)| *room == rooms[i])
+                .map_or(0, |(_, cost)| *cost);
+
+            // Each pair of neighbours is only counted once
+            for &j in variable.neighbours.iter().filter(|j| **j > i) {
+                if self.school(rooms[i]) != self.school(rooms[j]) {
+                    cost += self.allocator.move_penalty;
+                }
+            }
+        }
+
+        cost
+    }
+
+    fn school(&self, room: usize) -> School {
+        self.allocator.rooms[room].location.school()
+    }
+
+    // Search for the allocation with the lowest cost (starting from the
+    // `seed` allocation)
+    fn search(&self, seed: Vec<usize>) -> Allocation {
+        // Assign the most constrained requests first
+        let mut order: Vec<usize> = (0..self.requests.len()).collect();
+        order.sort_by_key(|i| (self.variables[*i].domain.len(), *i));
+
+        // The lowest cost achievable by the requests from each position of
+        // the order onwards (ignoring movement between requests)
+        let mut bounds = vec![0; order.len() + 1];
+        for (position, i) in order.iter().enumerate().rev() {
+            let cheapest = self.variables[*i]
+                .domain
+                .first()
+                .map_or(0, |(_, cost)| *cost);
+            bounds[position] = bounds[position + 1] + cheapest;
+        }
+
+        let mut search = Search {
+            problem: self,
+            order,
+            bounds,
+            rooms: vec![None; self.requests.len()],
+            used: HashSet::new(),
+            best_cost: self.cost(&seed),
+            best: seed,
+            explored: 0,
+        };
+
+        search.explore(0, 0);
+
+        Allocation {
+            rooms: search
+                .best
+                .iter()
+                .map(|room| self.allocator.rooms[*room].location)
+                .collect(),
+            cost: search.best_cost,
+            optimal: search.explored <= self.allocator.limit,
+        }
+    }
+}
+
+struct Search<'a> {
+    problem: &'a Problem<'a>,
+    order: Vec<usize>,
+    bounds: Vec<u32>,
+    rooms: Vec<Option<usize>>,
+    used: HashSet<(usize, usize)>,
+    best: Vec<usize>,
+    best_cost: u32,
+    explored: usize,
+}
+
+impl Search<'_> {
+    fn explore(&mut self, position: usize, cost: u32) {
+        if self.explored > self.problem.allocator.limit {
+            return;
+        }
+
+        let i = match self.order.get(position) {
+            Some(i) => *i,
+            None => {
+                if cost < self.best_cost {
+                    self.best_cost = cost;
+                    self.best = self.rooms.iter().map(|room| room.unwrap()).collect();
+                }
+                return;
+            }
+        };
+
+        let slot = self.problem.requests[i].slot.index();
+        let variable = &self.problem.variables[i];
+
+        // The cost of each free room (including movement between the request
+        // and its assigned neighbours)
+        let mut candidates: Vec<(usize, u32)> = variable
+            .domain
+            .iter()
+            .filter(|(room, _)| !self.used.contains(&(slot, *room)))
+            .map(|&(room, cost)| {
+                let school = self.problem.school(room);
+                let moves = variable
+                    .neighbours
+                    .iter()
+                    .filter_map(|j| self.rooms[*j])
+                    .filter(|other| self.problem.school(*other) != school)
+                    .count() as u32;
+
+                (room, cost + moves * self.problem.allocator.move_penalty)
+            })
+            .collect();
+        candidates.sort_by_key(|(room, cost)| (*cost, *room));
+
+        for (room, added) in candidates {
+            if cost + added + self.bounds[position + 1] >= self.best_cost {
+                // Candidates are ordered by cost, so no later candidate can
+                // improve upon the best allocation either
+                break;
+            }
+
+            self.explored += 1;
+            self.rooms[i] = Some(room);
+            self.used.insert((slot, room));
+
+            self.explore(position + 1, cost + added);
+
+            self.rooms[i] = None;
+            self.used.remove(&(slot, room));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeslot;
+
+    fn request(slot: TimeSlot, subject: &str, class: &str, size: u32) -> Request {
+        Request::new(
+            slot,
+            Subject::new(subject.to_string()).unwrap(),
+            Class::new(class.to_string()).unwrap(),
+            Teacher::new("JSM".to_string()).ok(),
+            size,
+        )
+    }
+
+    fn room(code: &str, capacity: u32) -> Room {
+        Room::new(code.parse().unwrap(), capacity)
+    }
+
+    #[test]
+    fn allocate_preferences() {
+        let allocator = Allocator::new()
+            .room(room("H101", 20))
+            .room(room("P101", 32))
+            .room(room("FH S1", 32).equipment("Fume Cupboard"))
+            .room(room("FH M1", 32))
+            .prefer(["Ma"], Area::Block(HighfieldBlock::Parker))
+            .prefer(["Ch"], Area::Section(FearnhillSection::Science));
+
+        let requests = [
+            request(timeslot!(W1MP1), "Ch", "12C", 20).requires("Fume Cupboard"),
+            // Maths prefers the Parker block, but JSM would have to move
+            // between schools -- the movement penalty outweighs the
+            // preference (and FH S1 is free again by the second period)
+            request(timeslot!(W1MP2), "Ma", "12M", 30),
+            request(timeslot!(W1MP2), "En", "10E", 18),
+        ];
+
+        let allocation = allocator.allocate(&Timetable::new(), &requests).unwrap();
+        let rooms: Vec<String> = allocation.rooms.iter().map(ToString::to_string).collect();
+
+        assert_eq!(rooms, vec!["FH S1", "FH S1", "FH M1"]);
+        assert_eq!(allocation.cost, 1);
+        assert!(allocation.optimal);
+
+        // Allocations are only applied on request
+        let mut timetable = Timetable::new();
+        allocation.apply(&mut timetable, &requests);
+
+        assert_eq!(
+            timetable
+                .lessons()
+                .in_room("FH M1".parse().unwrap())
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn allocate_unsatisfiable() {
+        let allocator = Allocator::new()
+            .room(room("H101", 30))
+            .room(room("H102", 30).equipment("Projector"));
+
+        let mut timetable = Timetable::new();
+        timetable.insert(
+            timeslot!(W1TP1),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("9A".to_string()).unwrap(),
+                teacher: None,
+                location: "H102".parse().unwrap(),
+            },
+        );
+
+        let requests = [
+            request(timeslot!(W1MP1), "Ma", "10A", 31),
+            request(timeslot!(W1MP1), "Ma", "10B", 20).requires("Whiteboard"),
+            request(timeslot!(W1TP1), "Ma", "10C", 20),
+            request(timeslot!(W1TP1), "Ma", "10D", 20),
+        ];
+
+        let error = allocator.allocate(&timetable, &requests).unwrap_err();
+
+        assert_eq!(
+            error.conflicts,
+            vec![
+                Conflict::Capacity { request: 0 },
+                Conflict::Equipment {
+                    request: 1,
+                    equipment: vec!["Whiteboard".to_string()]
+                },
+            ]
+        );
+
+        // Occupancy is only checked once every request has a suitable room
+        let error = allocator.allocate(&timetable, &requests[2..]).unwrap_err();
+
+        assert_eq!(
+            error.conflicts,
+            vec![Conflict::Occupied {
+                slot: timeslot!(W1TP1),
+                requests: vec![0, 1]
+            }]
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use crate::random::Random;
+use crate::{
+    Activity, Change, Class, Dataset, DayException, Entry, Rotation, RotationStage, Teacher,
+    Timetable, YearGroup,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+// The characters from which pseudonyms are drawn (excluding those which are
+// easily confused, e.g., `O` and `0`)
+const LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const ALPHANUMERICS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// The personal data replaced by [`Dataset::anonymise`], and the key from
+/// which the pseudonyms are derived.
+///
+/// Each pseudonym is derived from the key and the value it replaces -- as a
+/// result, anonymising two datasets with the same key (e.g., successive
+/// exports of the same school) produces the same pseudonyms, such that the
+/// datasets can still be compared. The key should not be shared with the
+/// recipients of the datasets.
+///
+/// # Remarks
+///
+/// By default, teachers, classes, and the descriptions of miscellaneous
+/// activities are all replaced. Locations, timeslots, subjects, and the
+/// calendar are always preserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnonymisationPolicy {
+    key: u64,
+    teachers: bool,
+    classes: bool,
+    descriptions: bool,
+}
+
+impl AnonymisationPolicy {
+    /// Creates a new `AnonymisationPolicy` which derives its pseudonyms from
+    /// the `key` provided, replacing every kind of personal data.
+    pub fn new(key: u64) -> Self {
+        Self {
+            key,
+            teachers: true,
+            classes: true,
+            descriptions: true,
+        }
+    }
+
+    /// Sets whether the staff codes of teachers are replaced (by three
+    /// letters, e.g., `KRW`).
+    pub fn teachers(mut self, teachers: bool) -> Self {
+        self.teachers = teachers;
+        self
+    }
+
+    /// Sets whether the references of classes are replaced -- the year group
+    /// of each class is preserved (e.g., `10A/Ma1` is replaced by `10-QZ4D`),
+    /// such that [year group statistics](crate::Timetable::year_group_stats)
+    /// are unaffected.
+    pub fn classes(mut self, classes: bool) -> Self {
+        self.classes = classes;
+        self
+    }
+
+    /// Sets whether the descriptions of [miscellaneous](Activity::Miscellaneous)
+    /// activities (which often name people, e.g., `Meeting with Mrs Smith`)
+    /// are replaced.
+    pub fn descriptions(mut self, descriptions: bool) -> Self {
+        self.descriptions = descriptions;
+        self
+    }
+
+    /// Derives the pseudonym of the student with the identifier provided
+    /// (e.g., their admission number), such that the student identifiers
+    /// accompanying a dataset (e.g., the bookings of a
+    /// [`Spaces`](crate::booking::Spaces)) can be replaced consistently.
+    ///
+    /// # Remarks
+    ///
+    /// Unlike the pseudonyms of teachers and classes, student pseudonyms are
+    /// derived independently of each other -- two students could (with a
+    /// negligible probability) share a pseudonym.
+    pub fn student(&self, student: &str) -> String {
+        let mut random = self.random('S', student);
+
+        format!("S{}", draw(&mut random, ALPHANUMERICS, 8))
+    }
+
+    // Seed a generator from the key, the kind of value, and the value
+    fn random(&self, kind: char, value: &str) -> Random {
+        // FNV-1a, which is stable across releases (unlike the hasher of the
+        // standard library)
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325 ^ self.key;
+
+        for byte in kind.to_string().bytes().chain([0]).chain(value.bytes()) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+
+        Random::new(hash)
+    }
+
+    // Assign a distinct pseudonym to each of the values, drawing further
+    // pseudonyms from the same generator should two values collide (the
+    // values are visited in order, such that collisions are resolved
+    // reproducibly)
+    fn pseudonyms<F>(
+        &self,
+        kind: char,
+        values: BTreeSet<String>,
+        generate: F,
+    ) -> HashMap<String, String>
+    where
+        F: Fn(&str, &mut Random) -> String,
+    {
+        let mut used = HashSet::new();
+
+        values
+            .into_iter()
+            .map(|value| {
+                let mut random = self.random(kind, &value);
+                let mut pseudonym = generate(&value, &mut random);
+
+                while !used.insert(pseudonym.clone()) {
+                    pseudonym = generate(&value, &mut random);
+                }
+
+                (value, pseudonym)
+            })
+            .collect()
+    }
+}
+
+// Draw `length` characters from the alphabet provided
+fn draw(random: &mut Random, alphabet: &[u8], length: usize) -> String {
+    (0..length)
+        .map(|_| char::from(alphabet[random.below(alphabet.len())]))
+        .collect()
+}
+
+impl Dataset {
+    /// Creates a copy of the `Dataset` in which the personal data selected by
+    /// the `policy` (i.e., staff codes, class references, and the
+    /// descriptions of miscellaneous activities) are replaced by stable
+    /// pseudonyms, such that it can be shared (e.g., as the reproduction of a
+    /// bug, or as the input of a benchmark).
+    ///
+    /// The entries (and their [`EntryId`](crate::EntryId)s), room overrides,
+    /// locations, and calendar are otherwise preserved -- equal values are
+    /// replaced by equal pseudonyms, and distinct values by distinct
+    /// pseudonyms, such that clashes and other analyses are unaffected.
+    pub fn anonymise(&self, policy: &AnonymisationPolicy) -> Dataset {
+        let mut teachers = BTreeSet::new();
+        let mut classes = BTreeSet::new();
+        let mut descriptions = BTreeSet::new();
+
+        for activity in self.activities() {
+            match activity {
+                Activity::Lesson { class, teacher, .. } => {
+                    classes.insert(class.reference().to_string());
+                    teachers.extend(teacher.iter().map(|teacher| teacher.code().to_string()));
+                }
+                Activity::Miscellaneous(description) => {
+                    descriptions.insert(description.clone());
+                }
+                _ => {}
+            }
+        }
+
+        for (_, entry) in self.timetable.entries() {
+            let stages = entry.rotation.iter().flat_map(|rotation| &rotation.stages);
+
+            teachers.extend(
+                entry
+                    .co_teachers
+                    .iter()
+                    .chain(stages.filter_map(|stage| stage.teacher.as_ref()))
+                    .map(|teacher| teacher.code().to_string()),
+            );
+        }
+
+        let teachers = policy.pseudonyms('T', teachers, |_, random| draw(random, LETTERS, 3));
+        let classes = policy.pseudonyms('C', classes, |reference, random| {
+            let suffix = draw(random, ALPHANUMERICS, 4);
+
+            match YearGroup::of_class(reference) {
+                Some(year) => format!("{}-{}", year.get(), suffix),
+                None => suffix,
+            }
+        });
+        let descriptions = policy.pseudonyms('D', descriptions, |_, random| {
+            format!("Activity {}", draw(random, ALPHANUMERICS, 4))
+        });
+
+        let pseudonymise = |teacher: &Teacher| -> Teacher {
+            if policy.teachers {
+                Teacher::new(teachers[teacher.code()].clone())
+                    .expect("pseudonyms are valid staff codes")
+            } else {
+                teacher.clone()
+            }
+        };
+        let anonymise = |activity: &Activity| -> Activity {
+            match activity {
+                Activity::Lesson {
+                    subject,
+                    class,
+                    teacher,
+                    location,
+                } => Activity::Lesson {
+                    subject: subject.clone(),
+                    class: if policy.classes {
+                        Class::new(classes[class.reference()].clone())
+                            .expect("pseudonyms are valid class references")
+                    } else {
+                        class.clone()
+                    },
+                    teacher: teacher.as_ref().map(pseudonymise),
+                    location: *location,
+                },
+                Activity::Miscellaneous(description) if policy.descriptions => {
+                    Activity::Miscellaneous(descriptions[description].clone())
+                }
+                activity => activity.clone(),
+            }
+        };
+
+        let mut timetable = Timetable::new();
+
+        for (id, entry) in self.timetable.entries() {
+            timetable.apply(&Change::Inserted {
+                id,
+                entry: Entry {
+                    slot: entry.slot,
+                    activity: anonymise(&entry.activity),
+                    periods: entry.periods,
+                    co_teachers: entry.co_teachers.iter().map(pseudonymise).collect(),
+                    split_rooms: entry.split_rooms.clone(),
+                    rotation: entry.rotation.as_ref().map(|rotation| Rotation {
+                        start: rotation.start,
+                        stages: rotation
+                            .stages
+                            .iter()
+                            .map(|stage| RotationStage {
+                                teacher: stage.teacher.as_ref().map(pseudonymise),
+                                location: stage.location,
+                            })
+                            .collect(),
+                    }),
+                },
+            });
+        }
+
+        for room_override in self.timetable.overrides() {
+            timetable.apply(&Change::OverrideAdded(*room_override));
+        }
+
+        let mut calendar = self.calendar.clone();
+        let exceptions: Vec<_> = self
+            .calendar
+            .exceptions()
+            .map(|(date, exception)| (date, exception.clone()))
+            .collect();
+
+        for (date, exception) in exceptions {
+            if let DayException::Suspended { periods, activity } = exception {
+                calendar.add_exception(
+                    date,
+                    DayException::Suspended {
+                        periods,
+                        activity: anonymise(&activity),
+                    },
+                );
+            }
+        }
+
+        Dataset::new(timetable, calendar)
+    }
+
+    // Retrieve every activity of the dataset (i.e., of its entries and of the
+    // suspensions of its calendar)
+    fn activities(&self) -> impl Iterator<Item = &Activity> + '_ {
+        self.timetable
+            .entries()
+            .map(|(_, entry)| &entry.activity)
+            .chain(
+                self.calendar
+                    .exceptions()
+                    .filter_map(|(_, exception)| match exception {
+                        DayException::Suspended { activity, .. } => Some(activity),
+                        _ => None,
+                    }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::{NoClashes, Rule};
+    use crate::{timeslot, AcademicCalendar, Period, Subject};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn dataset_anonymised() {
+        let lesson = |class: &str, teacher: &str, room: &str| Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Some(Teacher::new(teacher.to_string()).unwrap()),
+            location: room.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("10A/Ma1", "JSM", "H201"));
+        timetable.insert(timeslot!(W1MP1), lesson("11B/Ma2", "JSM", "H202"));
+        let meeting = timetable.insert(
+            timeslot!(W1TP2),
+            Activity::Miscellaneous("Meeting with Mrs Smith".to_string()),
+        );
+
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        timetable.override_room(timeslot!(W1MP1), date..=date, "Hall".parse().unwrap());
+
+        let mut calendar = AcademicCalendar::default();
+        calendar.add_exception(
+            date,
+            DayException::Suspended {
+                periods: vec![Period::Third],
+                activity: lesson("Y9", "ABC", "H101"),
+            },
+        );
+
+        let dataset = Dataset::new(timetable, calendar);
+        let policy = AnonymisationPolicy::new(42);
+        let anonymised = dataset.anonymise(&policy);
+
+        // The pseudonyms are stable, and the structure is preserved
+        assert_eq!(dataset.anonymise(&policy), anonymised);
+        assert_ne!(dataset.anonymise(&AnonymisationPolicy::new(7)), anonymised);
+        assert_eq!(anonymised.locations(), dataset.locations());
+        assert_eq!(
+            anonymised.timetable.overrides(),
+            dataset.timetable.overrides()
+        );
+        assert_eq!(
+            NoClashes.check(&anonymised.timetable).len(),
+            NoClashes.check(&dataset.timetable).len()
+        );
+        assert_eq!(
+            anonymised
+                .timetable
+                .year_group_stats()
+                .into_keys()
+                .map(YearGroup::get)
+                .collect::<Vec<_>>(),
+            [10, 11]
+        );
+
+        let json = anonymised.to_json();
+
+        for personal in ["JSM", "ABC", "10A/Ma1", "11B/Ma2", "Y9", "Mrs Smith"] {
+            assert!(!json.contains(personal), "{} was not replaced", personal);
+        }
+
+        assert_ne!(
+            anonymised.timetable.get(meeting).unwrap().activity,
+            Activity::Miscellaneous("Meeting with Mrs Smith".to_string())
+        );
+
+        // The policy can preserve each kind of data
+        let preserved = dataset.anonymise(&policy.teachers(false).descriptions(false));
+
+        assert!(preserved.to_json().contains("\"JSM\""));
+        assert!(preserved.to_json().contains("Mrs Smith"));
+        assert_eq!(policy.student("123456"), policy.student("123456"));
+        assert_ne!(policy.student("123456"), policy.student("123457"));
+    }
+}
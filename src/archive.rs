@@ -0,0 +1,289 @@
+//! An archive of the complete datasets of previous academic years.
+//!
+//! Estates planning (e.g., deciding whether a block should be refurbished,
+//! or whether a new laboratory is required) should be based upon how the
+//! rooms were actually used over several years -- an [`Archive`] stores the
+//! [`Dataset`] of each [`AcademicYear`], and answers queries across the years
+//! (e.g., how a room was used in each year, or which rooms were added or
+//! retired between two years):
+//!
+//! ```
+//! use timetableau::archive::{AcademicYear, Archive};
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, Subject, Timetable};
+//!
+//! let dataset = |rooms: &[&str]| {
+//!     let mut timetable = Timetable::new();
+//!
+//!     for room in rooms {
+//!         timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+//!             subject: Subject::new("Ma".to_string()).unwrap(),
+//!             class: Class::new("10A".to_string()).unwrap(),
+//!             teacher: None,
+//!             location: room.parse().unwrap(),
+//!         });
+//!     }
+//!
+//!     Dataset::new(timetable, AcademicCalendar::default())
+//! };
+//!
+//! let archive = Archive::new()
+//!     .year(AcademicYear::new(2023), dataset(&["H201", "U101"]))
+//!     .year(AcademicYear::new(2024), dataset(&["H201", "H202"]));
+//!
+//! let changes = archive
+//!     .room_changes(AcademicYear::new(2023), AcademicYear::new(2024))
+//!     .unwrap();
+//!
+//! assert_eq!(changes.added, ["H202".parse().unwrap()]);
+//! assert_eq!(changes.retired, ["U101".parse().unwrap()]);
+//! ```
+
+use crate::{Activity, Dataset, Location};
+use chrono::{Datelike, NaiveDate};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::{self, Display, Formatter};
+
+// The month in which an academic year starts (i.e., September)
+const FIRST_MONTH: u32 = 9;
+
+// The number of periods of the two-week timetable
+const PERIODS: usize = 50;
+
+/// An academic year (e.g., `2023/24`), which starts in September.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AcademicYear(i32);
+
+impl AcademicYear {
+    /// Creates a new `AcademicYear` starting in September of the calendar
+    /// `year` provided (e.g., `2023` for `2023/24`).
+    pub fn new(year: i32) -> Self {
+        Self(year)
+    }
+
+    /// Retrieves the academic year during which the `date` provided falls
+    /// (e.g., `2023/24` for the 1st of March 2024).
+    pub fn of(date: NaiveDate) -> Self {
+        if date.month() >= FIRST_MONTH {
+            Self(date.year())
+        } else {
+            Self(date.year() - 1)
+        }
+    }
+
+    /// Retrieves the calendar year in which the academic year starts (e.g.,
+    /// `2023` for `2023/24`).
+    pub fn start_year(self) -> i32 {
+        self.0
+    }
+
+    /// Retrieves the academic year which follows the academic year.
+    pub fn next(self) -> Self {
+        Self(self.0 + 1)
+    }
+}
+
+impl Display for AcademicYear {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{:02}", self.0, (self.0 + 1).rem_euclid(100))
+    }
+}
+
+/// How a room was used during an academic year (*see
+/// [`Archive::room_usage`]*).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoomUsage {
+    /// The number of lessons scheduled in the room.
+    pub lessons: usize,
+
+    /// The number of periods (of the 50 periods of the two-week timetable)
+    /// during which the room was used.
+    pub periods: usize,
+
+    /// The number of lessons of each subject scheduled in the room.
+    pub subjects: BTreeMap<String, usize>,
+}
+
+impl RoomUsage {
+    /// Calculates the proportion of the periods of the timetable during
+    /// which the room was used (i.e., from `0.0` to `1.0`).
+    pub fn utilisation(&self) -> f64 {
+        self.periods as f64 / PERIODS as f64
+    }
+}
+
+/// The rooms added and retired between two academic years (*see
+/// [`Archive::room_changes`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoomChanges {
+    /// The rooms used during the later year, but not the earlier year
+    /// (ordered by their room codes).
+    pub added: Vec<Location>,
+
+    /// The rooms used during the earlier year, but not the later year
+    /// (ordered by their room codes).
+    pub retired: Vec<Location>,
+}
+
+/// The datasets of a number of academic years.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Archive {
+    years: BTreeMap<AcademicYear, Dataset>,
+}
+
+impl Archive {
+    /// Creates a new, empty `Archive`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `dataset` of the `year` provided to the `Archive`.
+    ///
+    /// Should the `Archive` already contain a dataset for the year, it is
+    /// replaced.
+    pub fn year(mut self, year: AcademicYear, dataset: Dataset) -> Self {
+        self.insert(year, dataset);
+        self
+    }
+
+    /// Inserts the `dataset` of the `year` provided into the `Archive`.
+    ///
+    /// # Returns
+    ///
+    /// The dataset previously archived for the year, should there be one.
+    pub fn insert(&mut self, year: AcademicYear, dataset: Dataset) -> Option<Dataset> {
+        self.years.insert(year, dataset)
+    }
+
+    /// Retrieves the dataset of the `year` provided, should it be archived.
+    pub fn get(&self, year: AcademicYear) -> Option<&Dataset> {
+        self.years.get(&year)
+    }
+
+    /// Retrieves the archived years, in chronological order.
+    pub fn years(&self) -> impl DoubleEndedIterator<Item = AcademicYear> + '_ {
+        self.years.keys().copied()
+    }
+
+    /// Retrieves the most recent archived year and its dataset.
+    pub fn latest(&self) -> Option<(AcademicYear, &Dataset)> {
+        self.years
+            .iter()
+            .next_back()
+            .map(|(year, dataset)| (*year, dataset))
+    }
+
+    /// Determines how the `location` provided was used during each archived
+    /// year (e.g., to compare the use of `H201` in `2023/24` with its use in
+    /// `2024/25`), in chronological order.
+    ///
+    /// # Remarks
+    ///
+    /// Only the lessons of each timetable are considered (i.e., room
+    /// overrides and one-off bookings are not), and years during which the
+    /// room was not used are included (with no lessons).
+    pub fn room_usage(&self, location: &Location) -> Vec<(AcademicYear, RoomUsage)> {
+        self.years
+            .iter()
+            .map(|(year, dataset)| {
+                let mut usage = RoomUsage {
+                    lessons: 0,
+                    periods: 0,
+                    subjects: BTreeMap::new(),
+                };
+                let mut periods = HashSet::new();
+
+                for (_, entry) in dataset.timetable.lessons().in_room(*location) {
+                    if let Activity::Lesson { subject, .. } = &entry.activity {
+                        usage.lessons += 1;
+                        *usage.subjects.entry(subject.name().clone()).or_default() += 1;
+                        periods.extend(entry.slots());
+                    }
+                }
+
+                usage.periods = periods.len();
+                (*year, usage)
+            })
+            .collect()
+    }
+
+    /// Determines which rooms were added and retired between the archived
+    /// years `from` and `to` (*see [`Dataset::locations`]*).
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should either year not be archived.
+    pub fn room_changes(&self, from: AcademicYear, to: AcademicYear) -> Option<RoomChanges> {
+        let before = self.get(from)?.locations();
+        let after = self.get(to)?.locations();
+
+        let codes = |locations: &[Location]| -> BTreeSet<String> {
+            locations.iter().map(Location::to_string).collect()
+        };
+        let (before_codes, after_codes) = (codes(&before), codes(&after));
+
+        // The locations are already ordered by their room codes
+        Some(RoomChanges {
+            added: after
+                .into_iter()
+                .filter(|location| !before_codes.contains(&location.to_string()))
+                .collect(),
+            retired: before
+                .into_iter()
+                .filter(|location| !after_codes.contains(&location.to_string()))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Subject, Timetable};
+
+    #[test]
+    fn usage_compared_across_years() {
+        let lesson = |subject: &str| Activity::Lesson {
+            subject: Subject::new(subject.to_string()).unwrap(),
+            class: Class::new("10A".to_string()).unwrap(),
+            teacher: None,
+            location: "H201".parse().unwrap(),
+        };
+
+        let mut earlier = Timetable::new();
+        earlier.insert(timeslot!(W1MP1), lesson("Ma"));
+        earlier.insert(timeslot!(W1MP2), lesson("Ma"));
+        earlier.insert(timeslot!(W2FP5), lesson("En"));
+
+        // A double lesson uses the room during both of its periods
+        let mut later = Timetable::new();
+        later
+            .insert_spanning(timeslot!(W1MP1), 2, lesson("Ma"))
+            .unwrap();
+
+        let (first, second) = (AcademicYear::new(2023), AcademicYear::new(2024));
+        let archive = Archive::new()
+            .year(second, Dataset::new(later, AcademicCalendar::default()))
+            .year(first, Dataset::new(earlier, AcademicCalendar::default()));
+
+        assert_eq!(first.to_string(), "2023/24");
+        assert_eq!(
+            AcademicYear::of(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()),
+            first
+        );
+        assert_eq!(archive.years().collect::<Vec<_>>(), [first, second]);
+        assert_eq!(archive.latest().map(|(year, _)| year), Some(second));
+
+        let usage = archive.room_usage(&"H201".parse().unwrap());
+        assert_eq!(usage[0].0, first);
+        assert_eq!(usage[0].1.lessons, 3);
+        assert_eq!(usage[0].1.subjects["En"], 1);
+        assert_eq!(usage[0].1.utilisation(), 0.06);
+        assert_eq!((usage[1].1.lessons, usage[1].1.periods), (1, 2));
+
+        assert!(archive.room_changes(first, second.next()).is_none());
+    }
+}
@@ -0,0 +1,368 @@
+use crate::{
+    Activity, DayException, EntryId, Location, Period, Timetable, Week, WeekCycle, YearGroup,
+};
+use chrono::prelude::*;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// The assembly of a year group, which takes place in a hall (e.g., the
+/// `Hall` or the `Sports Hall`) in place of period one registration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Assembly {
+    /// The year group attending the assembly (*see
+    /// [`YearGroup::of_class`] for how the year group of a class is
+    /// determined*).
+    pub year: YearGroup,
+
+    /// The day of the week on which the assembly takes place.
+    pub day: Weekday,
+
+    /// The week of the cycle during which the assembly takes place, or
+    /// [`None`] should it take place during both weeks.
+    pub week: Option<Week>,
+
+    /// The hall in which the assembly takes place.
+    pub venue: Location,
+}
+
+impl Assembly {
+    /// Whether the `Assembly` takes place during the `week` provided.
+    pub fn is_in(&self, week: Week) -> bool {
+        self.week.is_none_or(|own| own == week)
+    }
+
+    // Whether the assembly takes place during the same period as the other
+    fn overlaps(&self, other: &Assembly) -> bool {
+        self.day == other.day
+            && [Week::WeekOne, Week::WeekTwo]
+                .iter()
+                .any(|week| self.is_in(*week) && other.is_in(*week))
+    }
+}
+
+/// A conflict involving an [`Assembly`] of an [`AssemblyRotation`].
+///
+/// Assemblies are identified by their index within
+/// [`AssemblyRotation::assemblies`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssemblyConflict {
+    /// The assembly takes place in the same hall (or for the same year
+    /// group) as another assembly during the same period.
+    Assembly {
+        /// The index of the assembly (or, should it have been refused, the
+        /// index it would have been given).
+        assembly: usize,
+
+        /// The index of the other assembly.
+        other: usize,
+    },
+
+    /// The hall has been booked by a lesson of another year group during
+    /// the assembly.
+    Booking {
+        /// The index of the assembly.
+        assembly: usize,
+
+        /// The identifier of the lesson.
+        entry: EntryId,
+    },
+
+    /// Period one is suspended (e.g., for an exam) on a date on which the
+    /// assembly would take place.
+    Exam {
+        /// The index of the assembly.
+        assembly: usize,
+
+        /// The date on which period one is suspended.
+        date: NaiveDate,
+    },
+}
+
+impl Display for AssemblyConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Assembly { assembly, other } => write!(
+                f,
+                "assembly {} clashes with assembly {} during the same period",
+                assembly, other
+            ),
+            Self::Booking { assembly, entry } => write!(
+                f,
+                "the hall of assembly {} is booked by lesson {}",
+                assembly,
+                entry.get()
+            ),
+            Self::Exam { assembly, date } => write!(
+                f,
+                "period one is suspended on {}, when assembly {} takes place",
+                date, assembly
+            ),
+        }
+    }
+}
+
+impl Error for AssemblyConflict {}
+
+/// The weekly rotation of the [`Assembly`]s of each year group.
+///
+/// ```
+/// use chrono::Weekday;
+/// use timetableau::{
+///     timeslot, Activity, Assembly, AssemblyRotation, Class, Subject, Timetable, YearGroup,
+/// };
+///
+/// let mut timetable = Timetable::new();
+/// let registration = timetable.insert(
+///     timeslot!(W1MP1),
+///     Activity::Lesson {
+///         subject: Subject::new("Reg".to_string()).unwrap(),
+///         class: Class::new("10A".to_string()).unwrap(),
+///         teacher: None,
+///         location: "H201".parse().unwrap(),
+///     },
+/// );
+///
+/// let mut rotation = AssemblyRotation::new();
+/// rotation
+///     .add(Assembly {
+///         year: YearGroup::new(10).unwrap(),
+///         day: Weekday::Mon,
+///         week: None,
+///         venue: "Hall".parse().unwrap(),
+///     })
+///     .unwrap();
+///
+/// assert_eq!(rotation.apply(&mut timetable), [registration]);
+/// assert_eq!(
+///     timetable.get(registration).unwrap().activity.location(),
+///     Some(&"Hall".parse().unwrap())
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssemblyRotation {
+    assemblies: Vec<Assembly>,
+}
+
+impl AssemblyRotation {
+    /// Creates a new `AssemblyRotation` with no assemblies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `assembly` provided, returning its index within
+    /// [`Self::assemblies`].
+    ///
+    /// # Errors
+    ///
+    /// An [`AssemblyConflict::Assembly`] is returned (and the assembly is not
+    /// added) should another assembly take place in the same hall, or for
+    /// the same year group, during the same period.
+    pub fn add(&mut self, assembly: Assembly) -> Result<usize, AssemblyConflict> {
+        if let Some(other) = self.assemblies.iter().position(|other| {
+            other.overlaps(&assembly)
+                && (other.venue == assembly.venue || other.year == assembly.year)
+        }) {
+            return Err(AssemblyConflict::Assembly {
+                assembly: self.assemblies.len(),
+                other,
+            });
+        }
+
+        self.assemblies.push(assembly);
+
+        Ok(self.assemblies.len() - 1)
+    }
+
+    /// Removes the assembly at the `index` provided, returning it.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if there is no assembly at the `index` provided -- the
+    /// indexes of later assemblies are decremented.
+    pub fn remove(&mut self, index: usize) -> Option<Assembly> {
+        (index < self.assemblies.len()).then(|| self.assemblies.remove(index))
+    }
+
+    /// Retrieves every assembly (in the order in which they were added).
+    pub fn assemblies(&self) -> &[Assembly] {
+        &self.assemblies
+    }
+
+    /// Relocates the period one lessons (i.e., registration) of each year
+    /// group to the hall of its assembly, on the days on which it takes
+    /// place -- the identifiers of the relocated lessons are returned.
+    ///
+    /// Each relocation is [recorded](Timetable::history) as a separate
+    /// change, such that it can be undone.
+    pub fn apply(&self, timetable: &mut Timetable) -> Vec<EntryId> {
+        let mut relocated = Vec::new();
+
+        for assembly in &self.assemblies {
+            let lessons: Vec<EntryId> = timetable
+                .lessons()
+                .on(assembly.day)
+                .in_period(Period::First)
+                .filter(|(_, entry)| {
+                    assembly.is_in(entry.slot.week) && attends(assembly, &entry.activity)
+                })
+                .filter(|(_, entry)| entry.activity.location() != Some(&assembly.venue))
+                .map(|(id, _)| id)
+                .collect();
+
+            for id in lessons {
+                timetable.relocate(id, assembly.venue);
+                relocated.push(id);
+            }
+        }
+
+        relocated
+    }
+
+    /// Finds every conflict between an assembly and the lessons of the
+    /// `timetable` (i.e., bookings of its hall by another year group), or the
+    /// exceptions of the academic calendar of the `cycle` (i.e., suspensions
+    /// of period one, such as exams) within the range of `dates` provided.
+    pub fn conflicts(
+        &self,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        dates: RangeInclusive<NaiveDate>,
+    ) -> Vec<AssemblyConflict> {
+        let mut conflicts = Vec::new();
+
+        for (index, assembly) in self.assemblies.iter().enumerate() {
+            for (entry, _) in timetable
+                .lessons()
+                .on(assembly.day)
+                .in_period(Period::First)
+                .in_room(assembly.venue)
+                .filter(|(_, entry)| {
+                    assembly.is_in(entry.slot.week) && !attends(assembly, &entry.activity)
+                })
+            {
+                conflicts.push(AssemblyConflict::Booking {
+                    assembly: index,
+                    entry,
+                });
+            }
+        }
+
+        for (date, exception) in cycle.calendar().exceptions() {
+            if !dates.contains(&date) {
+                continue;
+            }
+
+            let suspended = match exception {
+                DayException::Suspended { periods, .. } => periods.contains(&Period::First),
+                _ => false,
+            };
+
+            let Some((week, day)) = cycle.day_for(date).filter(|_| suspended) else {
+                continue;
+            };
+
+            for (index, assembly) in self.assemblies.iter().enumerate() {
+                if assembly.day == day && assembly.is_in(week) {
+                    conflicts.push(AssemblyConflict::Exam {
+                        assembly: index,
+                        date,
+                    });
+                }
+            }
+        }
+
+        conflicts
+    }
+}
+
+// Whether the activity is a lesson of a class in the year group of the
+// assembly
+fn attends(assembly: &Assembly, activity: &Activity) -> bool {
+    match activity {
+        Activity::Lesson { class, .. } => {
+            YearGroup::of_class(class.reference()) == Some(assembly.year)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Subject, Term};
+
+    fn lesson(class: &str, room: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Reg".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: None,
+            location: room.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn assembly_rotation() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let hall: Location = "Hall".parse().unwrap();
+        let assembly = |year, day, week| Assembly {
+            year: YearGroup::new(year).unwrap(),
+            day,
+            week,
+            venue: hall,
+        };
+
+        let mut rotation = AssemblyRotation::new();
+
+        assert_eq!(
+            rotation.add(assembly(10, Weekday::Mon, Some(Week::WeekOne))),
+            Ok(0)
+        );
+        assert_eq!(
+            rotation.add(assembly(11, Weekday::Mon, Some(Week::WeekTwo))),
+            Ok(1)
+        );
+        assert_eq!(
+            rotation.add(assembly(9, Weekday::Mon, None)),
+            Err(AssemblyConflict::Assembly {
+                assembly: 2,
+                other: 0
+            })
+        );
+
+        let mut timetable = Timetable::new();
+        let ten = timetable.insert(timeslot!(W1MP1), lesson("10A", "H201"));
+        let eleven = timetable.insert(timeslot!(W1MP1), lesson("11A", "H202"));
+        let booking = timetable.insert(timeslot!(W2MP1), lesson("Y9", "Hall"));
+
+        assert_eq!(rotation.apply(&mut timetable), [ten]);
+        assert_eq!(
+            timetable.get(eleven).unwrap().activity.location(),
+            Some(&"H202".parse().unwrap())
+        );
+
+        let mut calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+        calendar.add_exception(
+            date(18),
+            DayException::Suspended {
+                periods: vec![Period::First, Period::Second],
+                activity: Activity::Miscellaneous("Mock Exams".to_string()),
+            },
+        );
+        let cycle = WeekCycle::new(date(4), calendar).unwrap();
+
+        assert_eq!(
+            rotation.conflicts(&timetable, &cycle, date(4)..=date(29)),
+            [
+                AssemblyConflict::Booking {
+                    assembly: 1,
+                    entry: booking
+                },
+                AssemblyConflict::Exam {
+                    assembly: 0,
+                    date: date(18)
+                },
+            ]
+        );
+    }
+}
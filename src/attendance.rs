@@ -0,0 +1,386 @@
+//! Attendance registers of lessons.
+//!
+//! A [`Register`] contains one [`RegisterEntry`] per student per lesson
+//! occurrence (*see [`Timetable::occurrences`]*), produced from the
+//! timetable and the [`Roster`] of each class -- marks are then recorded
+//! against each entry using the standard attendance [`Mark`] codes, and the
+//! register is exported as CSV for upload to the school management system:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::attendance::{Mark, Register, Roster};
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Period, Subject, Term};
+//! use timetableau::{Timetable, WeekCycle};
+//!
+//! let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+//! let calendar = AcademicCalendar::new(vec![Term::new(date, date).unwrap()]);
+//! let cycle = WeekCycle::new(date, calendar).unwrap();
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(
+//!     timeslot!(W1MP1),
+//!     Activity::Lesson {
+//!         subject: Subject::new("Ma".to_string()).unwrap(),
+//!         class: Class::new("10A".to_string()).unwrap(),
+//!         teacher: None,
+//!         location: "H201".parse().unwrap(),
+//!     },
+//! );
+//!
+//! let mut roster = Roster::new();
+//! roster.enrol("10A", "1001");
+//! roster.enrol("10A", "1002");
+//!
+//! let mut register = Register::new(&timetable, &cycle, date..=date, &roster);
+//! assert_eq!(register.entries().len(), 2);
+//!
+//! assert!(register.record(date, Period::First, "1002", Mark::Late));
+//! assert!(register.to_csv().ends_with("2023-09-04,P1,10A,Ma,,H201,1002,L\r\n"));
+//! ```
+
+use crate::csv::write_record;
+use crate::{Activity, Class, EntryId, Location, Period, Subject, Teacher, Timetable, WeekCycle};
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// The header of a register exported by [`Register::to_csv`].
+pub const CSV_HEADER: &str = "Date,Period,Class,Subject,Teacher,Room,Student,Mark";
+
+/// A standard attendance mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mark {
+    /// Present (`/`).
+    Present,
+
+    /// Late, but before the register closed (`L`).
+    Late,
+
+    /// Late, after the register closed (`U`).
+    LateAfterClose,
+
+    /// Educated off site (`B`).
+    OffSite,
+
+    /// Authorised absence for another reason (`C`).
+    Authorised,
+
+    /// Illness (`I`).
+    Illness,
+
+    /// Medical or dental appointment (`M`).
+    Medical,
+
+    /// Unauthorised absence (`O`).
+    Unauthorised,
+
+    /// No reason yet provided for the absence (`N`).
+    NoReason,
+}
+
+impl Mark {
+    /// Retrieves the code of the `Mark` (e.g., `/` for [`Mark::Present`]).
+    pub fn code(self) -> char {
+        match self {
+            Self::Present => '/',
+            Self::Late => 'L',
+            Self::LateAfterClose => 'U',
+            Self::OffSite => 'B',
+            Self::Authorised => 'C',
+            Self::Illness => 'I',
+            Self::Medical => 'M',
+            Self::Unauthorised => 'O',
+            Self::NoReason => 'N',
+        }
+    }
+
+    /// Retrieves the `Mark` with the `code` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the `code` is not the code of a mark.
+    pub fn from_code(code: char) -> Option<Self> {
+        [
+            Self::Present,
+            Self::Late,
+            Self::LateAfterClose,
+            Self::OffSite,
+            Self::Authorised,
+            Self::Illness,
+            Self::Medical,
+            Self::Unauthorised,
+            Self::NoReason,
+        ]
+        .into_iter()
+        .find(|mark| mark.code() == code)
+    }
+
+    /// Whether the student is counted as present (i.e., the student was
+    /// present, late, or educated off site).
+    pub fn is_present(self) -> bool {
+        matches!(
+            self,
+            Self::Present | Self::Late | Self::LateAfterClose | Self::OffSite
+        )
+    }
+}
+
+impl Display for Mark {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// The students of each class (identified by their student identifiers,
+/// e.g., the admission number used by the school management system).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Roster {
+    classes: BTreeMap<String, Vec<String>>,
+}
+
+impl Roster {
+    /// Creates a new `Roster` with no classes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enrols the `student` provided in the class with the `reference`
+    /// provided -- enrolling a student in a class twice has no effect.
+    pub fn enrol(&mut self, reference: &str, student: &str) {
+        let students = self.classes.entry(reference.to_string()).or_default();
+
+        if !students.iter().any(|other| other == student) {
+            students.push(student.to_string());
+        }
+    }
+
+    /// Retrieves the students of the class with the `reference` provided
+    /// (in the order in which they were enrolled).
+    pub fn students(&self, reference: &str) -> &[String] {
+        self.classes.get(reference).map_or(&[], Vec::as_slice)
+    }
+
+    /// Creates an iterator over the references of the classes in which the
+    /// `student` provided is enrolled (in alphabetical order).
+    pub fn classes_of<'a>(&'a self, student: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.classes
+            .iter()
+            .filter(move |(_, students)| students.iter().any(|other| other == student))
+            .map(|(reference, _)| reference.as_str())
+    }
+
+    /// Retrieves every student enrolled in any class (in alphabetical order).
+    pub fn enrolled(&self) -> BTreeSet<&str> {
+        self.classes
+            .values()
+            .flatten()
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// The attendance of a single student at a single lesson occurrence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterEntry {
+    /// The date of the lesson.
+    pub date: NaiveDate,
+
+    /// The period of the lesson.
+    pub period: Period,
+
+    /// The entry of the timetable from which the lesson was produced.
+    pub entry: EntryId,
+
+    /// The class of the lesson.
+    pub class: Class,
+
+    /// The subject of the lesson.
+    pub subject: Subject,
+
+    /// The teacher of the lesson (if known).
+    pub teacher: Option<Teacher>,
+
+    /// The room in which the lesson takes place (after any room overrides
+    /// have been applied).
+    pub location: Location,
+
+    /// The identifier of the student.
+    pub student: String,
+
+    /// The mark recorded for the student, or [`None`] should the register
+    /// not have been taken yet.
+    pub mark: Option<Mark>,
+}
+
+/// The attendance register of a range of dates.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Register {
+    entries: Vec<RegisterEntry>,
+}
+
+impl Register {
+    /// Creates the `Register` of every lesson of the `timetable` taking
+    /// place within the range of `dates` provided, with one (unmarked)
+    /// entry per student of the `roster` enrolled in the class of the lesson.
+    ///
+    /// The entries are ordered chronologically, then by the order in which
+    /// the students were enrolled.
+    pub fn new(
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        dates: RangeInclusive<NaiveDate>,
+        roster: &Roster,
+    ) -> Self {
+        let mut entries = Vec::new();
+
+        for date in dates
+            .start()
+            .iter_days()
+            .take_while(|date| date <= dates.end())
+        {
+            for occurrence in timetable.occurrences(cycle, date) {
+                let (
+                    Some(entry),
+                    Activity::Lesson {
+                        subject,
+                        class,
+                        teacher,
+                        location,
+                    },
+                ) = (occurrence.entry, occurrence.activity)
+                else {
+                    continue;
+                };
+
+                for student in roster.students(class.reference()) {
+                    entries.push(RegisterEntry {
+                        date,
+                        period: occurrence.slot.period,
+                        entry,
+                        class: class.clone(),
+                        subject: subject.clone(),
+                        teacher: teacher.clone(),
+                        location,
+                        student: student.clone(),
+                        mark: None,
+                    });
+                }
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Retrieves every entry of the `Register`.
+    pub fn entries(&self) -> &[RegisterEntry] {
+        &self.entries
+    }
+
+    /// Records the `mark` of the `student` provided, for their lesson during
+    /// the `period` of the `date` provided.
+    ///
+    /// # Returns
+    ///
+    /// Whether the register contains an entry for the student during that
+    /// period (i.e., whether the mark was recorded).
+    pub fn record(&mut self, date: NaiveDate, period: Period, student: &str, mark: Mark) -> bool {
+        let mut recorded = false;
+
+        for entry in self.entries.iter_mut().filter(|entry| {
+            entry.date == date && entry.period == period && entry.student == student
+        }) {
+            entry.mark = Some(mark);
+            recorded = true;
+        }
+
+        recorded
+    }
+
+    /// Retrieves the entries which have not yet been marked.
+    pub fn unmarked(&self) -> impl Iterator<Item = &RegisterEntry> + '_ {
+        self.entries.iter().filter(|entry| entry.mark.is_none())
+    }
+
+    /// Exports the `Register` as CSV, in the format uploaded to the school
+    /// management system -- a [header](CSV_HEADER) followed by one record per
+    /// entry, in which unmarked entries have an empty mark.
+    pub fn to_csv(&self) -> String {
+        let mut output = String::new();
+        output.push_str(CSV_HEADER);
+        output.push_str("\r\n");
+
+        for entry in &self.entries {
+            let mark = entry.mark.map(|mark| mark.code().to_string());
+
+            write_record(
+                &mut output,
+                &[
+                    &entry.date.format("%Y-%m-%d").to_string(),
+                    &format!("P{}", entry.period as u8 + 1),
+                    entry.class.reference(),
+                    entry.subject.name(),
+                    entry.teacher.as_ref().map_or("", |teacher| teacher.code()),
+                    &entry.location.to_string(),
+                    &entry.student,
+                    mark.as_deref().unwrap_or(""),
+                ],
+            );
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Term};
+
+    #[test]
+    fn attendance_register() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+        let cycle = WeekCycle::new(date(4), calendar).unwrap();
+
+        let lesson = |class: &str| Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Some(Teacher::new("ABC".to_string()).unwrap()),
+            location: "H201".parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("10A"));
+        timetable.insert(timeslot!(W1MP2), lesson("10B, Set 2"));
+        timetable.insert(timeslot!(W1MP3), Activity::Registration);
+        timetable.insert(timeslot!(W1TP1), lesson("10A"));
+
+        let mut roster = Roster::new();
+        roster.enrol("10A", "1001");
+        roster.enrol("10A", "1002");
+        roster.enrol("10A", "1001");
+        roster.enrol("10B, Set 2", "1003");
+
+        let mut register = Register::new(&timetable, &cycle, date(4)..=date(5), &roster);
+
+        assert_eq!(register.entries().len(), 5);
+        assert!(register.record(date(4), Period::First, "1001", Mark::Present));
+        assert!(register.record(date(4), Period::Second, "1003", Mark::Illness));
+        assert!(!register.record(date(4), Period::Third, "1001", Mark::Present));
+        assert_eq!(register.unmarked().count(), 3);
+
+        assert_eq!(Mark::from_code('I'), Some(Mark::Illness));
+        assert!(!Mark::Illness.is_present());
+
+        let csv = register.to_csv();
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "2023-09-04,P1,10A,Ma,ABC,H201,1001,/");
+        assert_eq!(lines[3], "2023-09-04,P2,\"10B, Set 2\",Ma,ABC,H201,1003,I");
+        assert_eq!(lines[5], "2023-09-05,P1,10A,Ma,ABC,H201,1002,");
+    }
+}
@@ -0,0 +1,320 @@
+//! The working patterns of staff (e.g., part-time teachers who only work
+//! from Monday to Wednesday).
+//!
+//! An [`Availability`] records the timeslots during which a teacher is
+//! available, and [`StaffAvailability`] records the availability of each
+//! member of staff (teachers without a recorded availability are available
+//! during every timeslot). The availability of staff is consumed by the
+//! [`WithinAvailability`](crate::validate::WithinAvailability) rule, by the
+//! [`Optimiser`](crate::optimise::Optimiser::availability), and when
+//! choosing [staff to provide cover](StaffAvailability::cover_candidates),
+//! such that part-time staff are never scheduled on their non-working days:
+//!
+//! ```
+//! use chrono::Weekday;
+//! use timetableau::availability::{Availability, StaffAvailability};
+//! use timetableau::{timeslot, Period, Teacher};
+//!
+//! let staff = StaffAvailability::new().teacher(
+//!     "JSM",
+//!     Availability::works(&[Weekday::Mon, Weekday::Tue, Weekday::Wed])
+//!         .unavailable(Weekday::Mon, Period::First),
+//! );
+//! let teacher = Teacher::new("JSM".to_string()).unwrap();
+//!
+//! assert!(staff.is_available(&teacher, timeslot!(W2TP1)));
+//! assert!(!staff.is_available(&teacher, timeslot!(W1MP1)));
+//! assert!(!staff.is_available(&teacher, timeslot!(W1FP3)));
+//! ```
+
+use crate::bulletin::Assignment;
+use crate::{Period, RangedU8, Teacher, TimeSlot, Timetable, Week};
+use chrono::Weekday;
+use std::collections::BTreeMap;
+
+// Every timeslot of the two-week timetable
+const FULL: u64 = (1 << 50) - 1;
+
+/// The timeslots during which a member of staff is available.
+///
+/// A new `Availability` is available during every timeslot -- *see the
+/// [module](self) documentation for more information*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Availability {
+    // The bit of each timeslot's index is set should the timeslot be
+    // available
+    mask: u64,
+}
+
+impl Default for Availability {
+    fn default() -> Self {
+        Self { mask: FULL }
+    }
+}
+
+impl Availability {
+    /// Creates a new `Availability` which is available during every
+    /// timeslot.
+    pub fn full() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `Availability` which is available during every period
+    /// of the `days` provided (of both weeks), and unavailable during every
+    /// other day.
+    pub fn works(days: &[Weekday]) -> Self {
+        let mask = slots()
+            .filter(|slot| days.contains(&slot.day))
+            .fold(0, |mask, slot| mask | bit(slot));
+
+        Self { mask }
+    }
+
+    /// Marks the `period` of the `day` provided (of both weeks) as
+    /// unavailable (e.g., a teacher who does not work during the first
+    /// period of a Friday).
+    pub fn unavailable(self, day: Weekday, period: Period) -> Self {
+        [Week::WeekOne, Week::WeekTwo]
+            .into_iter()
+            .fold(self, |availability, week| {
+                availability.unavailable_slot(TimeSlot { week, day, period })
+            })
+    }
+
+    /// Marks the `slot` provided as unavailable (e.g., a teacher who only
+    /// works on the Friday of week two).
+    pub fn unavailable_slot(mut self, slot: TimeSlot) -> Self {
+        self.mask &= !bit(slot);
+        self
+    }
+
+    /// Whether the member of staff is available during the `slot` provided.
+    pub fn is_available(&self, slot: TimeSlot) -> bool {
+        self.mask & bit(slot) != 0
+    }
+
+    /// Retrieves the number of timeslots (of the two-week timetable) during
+    /// which the member of staff is available.
+    pub fn periods(&self) -> u32 {
+        self.mask.count_ones()
+    }
+}
+
+/// The [`Availability`] of each member of staff.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StaffAvailability {
+    teachers: BTreeMap<String, Availability>,
+}
+
+impl StaffAvailability {
+    /// Creates a new `StaffAvailability` without any recorded availability
+    /// (i.e., every teacher is available during every timeslot).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the `availability` of the teacher with the staff `code`
+    /// provided (replacing any availability previously recorded for them).
+    pub fn teacher(mut self, code: &str, availability: Availability) -> Self {
+        self.teachers.insert(code.to_string(), availability);
+        self
+    }
+
+    /// Retrieves every teacher with a recorded availability (and their
+    /// availability), in order of their staff codes.
+    pub fn teachers(&self) -> impl Iterator<Item = (&str, &Availability)> + '_ {
+        self.teachers
+            .iter()
+            .map(|(code, availability)| (code.as_str(), availability))
+    }
+
+    /// Retrieves the availability of the `teacher` provided (which is
+    /// [full](Availability::full) should none have been recorded).
+    pub fn get(&self, teacher: &Teacher) -> Availability {
+        self.teachers
+            .get(teacher.code())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Whether the `teacher` provided is available during the `slot`
+    /// provided.
+    pub fn is_available(&self, teacher: &Teacher, slot: TimeSlot) -> bool {
+        self.get(teacher).is_available(slot)
+    }
+
+    /// Retrieves the staff codes of the teachers with a recorded
+    /// availability who could cover a lesson during the `slot` provided
+    /// (i.e., who are available, and who do not teach -- or co-teach -- a
+    /// lesson of the `timetable` provided during the slot, including the
+    /// later periods of a double lesson), in order of their staff codes.
+    pub fn cover_candidates(&self, timetable: &Timetable, slot: TimeSlot) -> Vec<&str> {
+        self.teachers()
+            .filter(|(code, availability)| {
+                availability.is_available(slot)
+                    && !timetable.lessons().any(|(_, entry)| {
+                        entry.covers(slot)
+                            && entry.teachers().any(|teacher| teacher.code() == *code)
+                    })
+            })
+            .map(|(code, _)| code)
+            .collect()
+    }
+
+    /// Retrieves the cover `assignments` (e.g., those produced by
+    /// [`Bulletin::apply`](crate::bulletin::Bulletin::apply)) whose covering
+    /// teacher is unavailable during any timeslot of the covered lesson.
+    ///
+    /// # Remarks
+    ///
+    /// Assignments whose entry is not a part of the `timetable` provided are
+    /// disregarded.
+    pub fn unavailable_cover<'a>(
+        &'a self,
+        timetable: &'a Timetable,
+        assignments: &'a [Assignment],
+    ) -> impl Iterator<Item = &'a Assignment> + 'a {
+        assignments.iter().filter(|assignment| {
+            timetable.get(assignment.entry).is_some_and(|entry| {
+                entry
+                    .slots()
+                    .any(|slot| !self.is_available(&assignment.teacher, slot))
+            })
+        })
+    }
+}
+
+// Retrieve the bit of the `slot` provided
+fn bit(slot: TimeSlot) -> u64 {
+    1 << slot.index()
+}
+
+// Retrieve every timeslot of the two-week timetable
+fn slots() -> impl Iterator<Item = TimeSlot> {
+    RangedU8::<0, 49>::all().map(TimeSlot::with_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bulletin;
+    use crate::{timeslot, AcademicCalendar, Activity, Class, Entry, Subject, Term, WeekCycle};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn availability_and_cover() {
+        let part_time = Availability::works(&[Weekday::Mon, Weekday::Tue, Weekday::Wed])
+            .unavailable(Weekday::Tue, Period::Fifth)
+            .unavailable_slot(timeslot!(W2WP1));
+
+        assert_eq!(part_time.periods(), 30 - 2 - 1);
+        assert!(part_time.is_available(timeslot!(W1WP1)));
+        assert!(!part_time.is_available(timeslot!(W2WP1)));
+        assert!(!part_time.is_available(timeslot!(W1TP5)));
+        assert!(!part_time.is_available(timeslot!(W2RP2)));
+        assert_eq!(Availability::full().periods(), 50);
+
+        let staff = StaffAvailability::new()
+            .teacher("ABC", part_time)
+            .teacher("XYZ", Availability::full())
+            .teacher(
+                "KLM",
+                Availability::full().unavailable(Weekday::Fri, Period::First),
+            );
+
+        let mut timetable = Timetable::new();
+        timetable.insert(
+            timeslot!(W1FP1),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("11C".to_string()).unwrap(),
+                teacher: Teacher::new("JSM".to_string()).ok(),
+                location: "H201".parse().unwrap(),
+            },
+        );
+        timetable.insert(
+            timeslot!(W1FP1),
+            Activity::Lesson {
+                subject: Subject::new("En".to_string()).unwrap(),
+                class: Class::new("10A".to_string()).unwrap(),
+                teacher: Teacher::new("XYZ".to_string()).ok(),
+                location: "H202".parse().unwrap(),
+            },
+        );
+
+        // ABC does not work on Fridays, KLM does not work during the first
+        // period, and XYZ is teaching
+        assert!(staff
+            .cover_candidates(&timetable, timeslot!(W1FP1))
+            .is_empty());
+        assert_eq!(
+            staff.cover_candidates(&timetable, timeslot!(W1FP2)),
+            ["KLM", "XYZ"]
+        );
+
+        // A co-teacher is teaching their lesson
+        timetable
+            .insert_entry(
+                Entry::new(
+                    timeslot!(W1FP3),
+                    Activity::Lesson {
+                        subject: Subject::new("Sc".to_string()).unwrap(),
+                        class: Class::new("11C".to_string()).unwrap(),
+                        teacher: Teacher::new("JSM".to_string()).ok(),
+                        location: "H203".parse().unwrap(),
+                    },
+                )
+                .co_teacher(Teacher::new("KLM".to_string()).unwrap()),
+            )
+            .unwrap();
+
+        assert_eq!(
+            staff.cover_candidates(&timetable, timeslot!(W1FP3)),
+            ["XYZ"]
+        );
+
+        // XYZ is teaching the second period of a double
+        timetable
+            .insert_spanning(
+                timeslot!(W1RP3),
+                2,
+                Activity::Lesson {
+                    subject: Subject::new("Sc".to_string()).unwrap(),
+                    class: Class::new("10A".to_string()).unwrap(),
+                    teacher: Teacher::new("XYZ".to_string()).ok(),
+                    location: "H204".parse().unwrap(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            staff.cover_candidates(&timetable, timeslot!(W1RP4)),
+            ["KLM"]
+        );
+
+        // A Friday of week one
+        let date = NaiveDate::from_ymd_opt(2023, 9, 8).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(
+            NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(),
+            NaiveDate::from_ymd_opt(2023, 9, 29).unwrap(),
+        )
+        .unwrap()]);
+        let cycle = WeekCycle::new(NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(), calendar).unwrap();
+
+        let applied = bulletin::parse("Cover: JSM absent, P1 11C covered by ABC").apply(
+            &mut timetable,
+            &cycle,
+            date,
+        );
+
+        assert_eq!(applied.assignments.len(), 1);
+        assert_eq!(
+            staff
+                .unavailable_cover(&timetable, &applied.assignments)
+                .count(),
+            1
+        );
+    }
+}
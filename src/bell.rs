@@ -0,0 +1,257 @@
+use crate::{Period, PeriodTime, School, TimeOfDay, TimetableError};
+
+/// The times at which the bells ring at a school (i.e., the start and end
+/// times of each [`Period`], break, and lunch).
+///
+/// # Remarks
+///
+/// The end time of a period is **not** included in that period (e.g., if the
+/// first period ends at `09:50`, `09:50` belongs to the second period).
+///
+/// *See the [`crate`] documentation for more information*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BellSchedule {
+    periods: [PeriodTime; 5],
+    break_time: PeriodTime,
+    lunch: PeriodTime,
+}
+
+impl BellSchedule {
+    /// Creates a new `BellSchedule`.
+    ///
+    /// The `periods` must be provided in chronological order (i.e., the first element is the time
+    /// range of [`Period::First`]).
+    ///
+    /// The break must take place between the second and third periods, and
+    /// lunch must take place between the fourth and fifth periods.
+    ///
+    /// # Errors
+    ///
+    /// [`TimetableError::EmptyBellRange`] is returned should a time range not
+    /// start before it ends, and [`TimetableError::OverlappingBellRanges`] is
+    /// returned should a time range end after the following one starts.
+    pub fn new(
+        periods: [PeriodTime; 5],
+        break_time: PeriodTime,
+        lunch: PeriodTime,
+    ) -> Result<Self, TimetableError> {
+        // Construct the order in which the time ranges must occur
+        let ranges = [
+            periods[0], periods[1], break_time, periods[2], periods[3], lunch, periods[4],
+        ];
+
+        // Each range must start before it ends
+        if let Some(&PeriodTime { start, end }) = ranges.iter().find(|range| range.is_empty()) {
+            return Err(TimetableError::EmptyBellRange { start, end });
+        }
+
+        // Each range must end before (or as) the next one starts
+        if let Some(pair) = ranges.windows(2).find(|pair| pair[0].end > pair[1].start) {
+            return Err(TimetableError::OverlappingBellRanges {
+                end: pair[0].end,
+                start: pair[1].start,
+            });
+        }
+
+        Ok(Self {
+            periods,
+            break_time,
+            lunch,
+        })
+    }
+
+    /// The `BellSchedule` of the Highfield school.
+    ///
+    /// | Period    | Start Time | End Time |
+    /// |-----------|------------|----------|
+    /// | First     | 08:50      | 09:50    |
+    /// | Second    | 09:50      | 10:50    |
+    /// | *Break*   | 10:50      | 11:10    |
+    /// | Third     | 11:10      | 12:10    |
+    /// | Fourth    | 12:10      | 13:10    |
+    /// | *Lunch*   | 13:10      | 13:55    |
+    /// | Fifth     | 13:55      | 14:55    |
+    pub fn highfield() -> Self {
+        Self {
+            periods: [
+                PeriodTime::new(hm(8, 50), hm(9, 50)),
+                PeriodTime::new(hm(9, 50), hm(10, 50)),
+                PeriodTime::new(hm(11, 10), hm(12, 10)),
+                PeriodTime::new(hm(12, 10), hm(13, 10)),
+                PeriodTime::new(hm(13, 55), hm(14, 55)),
+            ],
+            break_time: PeriodTime::new(hm(10, 50), hm(11, 10)),
+            lunch: PeriodTime::new(hm(13, 10), hm(13, 55)),
+        }
+    }
+
+    /// The `BellSchedule` of the Fearnhill school.
+    ///
+    /// | Period    | Start Time | End Time |
+    /// |-----------|------------|----------|
+    /// | First     | 08:45      | 09:45    |
+    /// | Second    | 09:45      | 10:45    |
+    /// | *Break*   | 10:45      | 11:05    |
+    /// | Third     | 11:05      | 12:05    |
+    /// | Fourth    | 12:05      | 13:05    |
+    /// | *Lunch*   | 13:05      | 13:50    |
+    /// | Fifth     | 13:50      | 14:50    |
+    pub fn fearnhill() -> Self {
+        Self {
+            periods: [
+                PeriodTime::new(hm(8, 45), hm(9, 45)),
+                PeriodTime::new(hm(9, 45), hm(10, 45)),
+                PeriodTime::new(hm(11, 5), hm(12, 5)),
+                PeriodTime::new(hm(12, 5), hm(13, 5)),
+                PeriodTime::new(hm(13, 50), hm(14, 50)),
+            ],
+            break_time: PeriodTime::new(hm(10, 45), hm(11, 5)),
+            lunch: PeriodTime::new(hm(13, 5), hm(13, 50)),
+        }
+    }
+
+    /// The default `BellSchedule` of the `school` provided.
+    pub fn for_school(school: School) -> Self {
+        match school {
+            School::Highfield => Self::highfield(),
+            School::Fearnhill => Self::fearnhill(),
+        }
+    }
+
+    /// Retrieves the [`PeriodTime`] of the `period` provided.
+    pub fn period(&self, period: Period) -> PeriodTime {
+        self.periods[period as usize]
+    }
+
+    /// Retrieves the time at which the `period` provided starts.
+    pub fn start(&self, period: Period) -> TimeOfDay {
+        self.period(period).start
+    }
+
+    /// Retrieves the time at which the `period` provided ends.
+    pub fn end(&self, period: Period) -> TimeOfDay {
+        self.period(period).end
+    }
+
+    /// Retrieves the [`PeriodTime`] of break.
+    pub fn break_time(&self) -> PeriodTime {
+        self.break_time
+    }
+
+    /// Retrieves the [`PeriodTime`] of lunch.
+    pub fn lunch(&self) -> PeriodTime {
+        self.lunch
+    }
+
+    /// Retrieves the [`Period`] taking place at the `time` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if the `time` falls within a period, or [`None`] if it
+    /// does not (e.g., during break).
+    ///
+    /// # Remarks
+    ///
+    /// Any [`TimeOfDay`] (or [`NaiveTime`](chrono::NaiveTime), whose seconds
+    /// are disregarded) can be provided.
+    pub fn period_at(&self, time: impl Into<TimeOfDay>) -> Option<Period> {
+        let time = time.into();
+
+        Period::ALL
+            .into_iter()
+            .find(|period| self.period(*period).contains(time))
+    }
+}
+
+/// The [`BellSchedule`]s of both the Highfield school and the Fearnhill
+/// school.
+///
+/// The default value contains the default schedule of each school (i.e.,
+/// [`BellSchedule::highfield`] and [`BellSchedule::fearnhill`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BellSchedules {
+    highfield: BellSchedule,
+    fearnhill: BellSchedule,
+}
+
+impl BellSchedules {
+    /// Retrieves the [`BellSchedule`] of the `school` provided.
+    pub fn get(&self, school: School) -> &BellSchedule {
+        match school {
+            School::Highfield => &self.highfield,
+            School::Fearnhill => &self.fearnhill,
+        }
+    }
+
+    /// Replaces the [`BellSchedule`] of the `school` provided.
+    pub fn set(&mut self, school: School, schedule: BellSchedule) {
+        match school {
+            School::Highfield => self.highfield = schedule,
+            School::Fearnhill => self.fearnhill = schedule,
+        }
+    }
+}
+
+impl Default for BellSchedules {
+    fn default() -> Self {
+        Self {
+            highfield: BellSchedule::highfield(),
+            fearnhill: BellSchedule::fearnhill(),
+        }
+    }
+}
+
+// A shorthand for creating a `TimeOfDay` from hardcoded hours and minutes
+fn hm(hour: u8, minute: u8) -> TimeOfDay {
+    TimeOfDay::new(hour, minute).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_default_valid() {
+        let highfield = BellSchedule::highfield();
+        let fearnhill = BellSchedule::fearnhill();
+
+        assert_eq!(
+            BellSchedule::new(highfield.periods, highfield.break_time, highfield.lunch),
+            Ok(highfield)
+        );
+        assert_eq!(
+            BellSchedule::new(fearnhill.periods, fearnhill.break_time, fearnhill.lunch),
+            Ok(fearnhill)
+        );
+    }
+
+    #[test]
+    fn schedule_overlapping() {
+        let mut periods = BellSchedule::highfield().periods;
+        periods[1].start = hm(9, 40);
+
+        assert_eq!(
+            BellSchedule::new(
+                periods,
+                PeriodTime::new(hm(10, 50), hm(11, 10)),
+                PeriodTime::new(hm(13, 10), hm(13, 55))
+            ),
+            Err(TimetableError::OverlappingBellRanges {
+                end: hm(9, 50),
+                start: hm(9, 40)
+            })
+        );
+    }
+
+    #[test]
+    fn schedule_period_at() {
+        let fearnhill = BellSchedule::fearnhill();
+
+        assert_eq!(fearnhill.period_at(hm(9, 45)), Some(Period::Second));
+        assert_eq!(fearnhill.period_at(hm(10, 50)), None);
+        assert_eq!(
+            BellSchedule::highfield().period_at(hm(9, 45)),
+            Some(Period::First)
+        );
+    }
+}
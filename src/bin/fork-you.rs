@@ -0,0 +1,460 @@
+//! A command-line interface for querying timetables.
+//!
+//! The timetable is loaded from a TOML definition (*see
+//! [`Timetable::from_toml_str`] for more information*) -- either the file
+//! provided by the `--timetable` option, the file named by the
+//! `FORK_YOU_TIMETABLE` environment variable, or `timetable.toml` (in that
+//! order of precedence).
+//!
+//! ```text
+//! fork-you next --for <class or teacher> --anchor <week one Monday>
+//! fork-you where <room> --at <slot>
+//! fork-you free-rooms --site <highfield|fearnhill> --slot <slot>
+//! fork-you serve --address <address>
+//! ```
+//!
+//! The `serve` command (which requires the `server` feature) answers the
+//! requests of the read-only HTTP API over the timetable -- *see the
+//! [`timetableau::server`] module for more information*.
+//!
+//! Slots consist of a day and a period, optionally accompanied by a week
+//! (e.g., `tue p4` or `wed B p2`) -- should the week be omitted, both weeks
+//! are queried.
+
+use chrono::prelude::*;
+use chrono::Duration;
+use std::collections::{BTreeSet, HashMap};
+use std::process::ExitCode;
+use timetableau::{
+    AcademicCalendar, Activity, Location, Period, School, Term, TimeSlot, Timetable, Week,
+    WeekCycle,
+};
+
+const USAGE: &str = "\
+usage: fork-you [--timetable <path>] <command>
+
+commands:
+    next --for <class or teacher> --anchor <week one Monday>
+    where <room> --at <slot>
+    free-rooms --site <highfield|fearnhill> --slot <slot>
+    serve --address <address>";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("fork-you: {}\n\n{}", message, USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// The positional arguments and `--name value` options provided
+struct Arguments {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Arguments {
+    fn parse(args: Vec<String>) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(name) => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("`--{}` requires a value", name))?;
+
+                    options.insert(name.to_string(), value);
+                }
+                None => positional.push(arg),
+            }
+        }
+
+        Ok(Self {
+            positional,
+            options,
+        })
+    }
+
+    fn option(&self, name: &str) -> Result<&str, String> {
+        self.options
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| format!("`--{}` is required", name))
+    }
+}
+
+fn run(args: Vec<String>) -> Result<String, String> {
+    let args = Arguments::parse(args)?;
+
+    let path = args
+        .options
+        .get("timetable")
+        .cloned()
+        .or_else(|| std::env::var("FORK_YOU_TIMETABLE").ok())
+        .unwrap_or_else(|| "timetable.toml".to_string());
+
+    let command = args.positional.first().ok_or("no command provided")?;
+
+    let load = || {
+        let definition = std::fs::read_to_string(&path)
+            .map_err(|error| format!("unable to read `{}`: {}", path, error))?;
+
+        Timetable::from_toml_str(&definition).map_err(|error| format!("{}: {}", path, error))
+    };
+
+    match command.as_str() {
+        "next" => next(&load()?, args.option("for")?, args.option("anchor")?),
+        "where" => {
+            let room = args.positional.get(1).ok_or("no room provided")?;
+            room_at(&load()?, room, args.option("at")?)
+        }
+        "free-rooms" => free_rooms(&load()?, args.option("site")?, args.option("slot")?),
+        #[cfg(feature = "server")]
+        "serve" => serve(load()?, args.option("address")?),
+        _ => Err(format!("unknown command `{}`", command)),
+    }
+}
+
+// Answer the requests of the HTTP API over the timetable until the server
+// fails
+#[cfg(feature = "server")]
+fn serve(timetable: Timetable, address: &str) -> Result<String, String> {
+    use std::net::TcpListener;
+    use timetableau::server::Api;
+    use timetableau::Dataset;
+
+    let listener = TcpListener::bind(address)
+        .map_err(|error| format!("unable to listen on `{}`: {}", address, error))?;
+    let api = Api::new(Dataset::new(timetable, AcademicCalendar::default()));
+
+    eprintln!("fork-you: listening on {}", address);
+
+    api.serve(listener)
+        .map(|()| String::new())
+        .map_err(|error| format!("the server failed: {}", error))
+}
+
+// Find the next lesson taught by (or to) the class or teacher provided
+fn next(timetable: &Timetable, reference: &str, anchor: &str) -> Result<String, String> {
+    next_after(timetable, reference, anchor, Local::now().naive_local())
+}
+
+// Find the next lesson taught by (or to) the class or teacher provided which
+// has not finished by `now`
+fn next_after(
+    timetable: &Timetable,
+    reference: &str,
+    anchor: &str,
+    now: NaiveDateTime,
+) -> Result<String, String> {
+    let anchor: NaiveDate = anchor
+        .parse()
+        .map_err(|_| format!("invalid anchor `{}`", anchor))?;
+
+    // Holidays are unknown to the command line interface, so every week is
+    // treated as a school week
+    let term = Term::new(anchor, anchor + Duration::days(365 * 5)).unwrap();
+    let cycle = WeekCycle::new(anchor, AcademicCalendar::new(vec![term]))
+        .map_err(|error| error.to_string())?;
+
+    for offset in 0..28 {
+        let date = now.date() + Duration::days(offset);
+
+        // A lesson spanning multiple periods is timed from the start of its
+        // first period to the end of its last period
+        let lesson = timetable
+            .timed_lessons(&cycle, date, now)
+            .into_iter()
+            .filter(|lesson| {
+                let Activity::Lesson { class, teacher, .. } = &lesson.occurrence.activity else {
+                    return false;
+                };
+
+                // The co-teachers of a lesson also teach it
+                let co_teachers = lesson
+                    .occurrence
+                    .entry
+                    .and_then(|id| timetable.get(id))
+                    .map_or(&[][..], |entry| &entry.co_teachers[..]);
+
+                let matches = class.reference().eq_ignore_ascii_case(reference)
+                    || teacher
+                        .iter()
+                        .chain(co_teachers)
+                        .any(|teacher| teacher.code().eq_ignore_ascii_case(reference));
+
+                // Lessons which have already finished are disregarded
+                matches && lesson.end > now
+            })
+            .min_by_key(|lesson| lesson.start);
+
+        if let Some(lesson) = lesson {
+            let occurrence = lesson.occurrence;
+
+            // The class of a split lesson is also in its split rooms
+            let split_rooms: Vec<String> = occurrence
+                .entry
+                .and_then(|id| timetable.get(id))
+                .map_or_else(Vec::new, |entry| {
+                    entry.split_rooms.iter().map(Location::to_string).collect()
+                });
+            let split = if split_rooms.is_empty() {
+                String::new()
+            } else {
+                format!(" (also in {})", split_rooms.join(", "))
+            };
+
+            return Ok(format!(
+                "{} {}, P{} ({}-{}): {}{}",
+                date.weekday(),
+                date.format("%d %b"),
+                occurrence.slot.period as usize + 1,
+                lesson.start.format("%H:%M"),
+                lesson.end.format("%H:%M"),
+                occurrence.activity,
+                split
+            ));
+        }
+    }
+
+    Err(format!("no lessons found for `{}`", reference))
+}
+
+// Describe what takes place in a room during the slot provided
+fn room_at(timetable: &Timetable, room: &str, slot: &str) -> Result<String, String> {
+    let location: Location = room
+        .parse()
+        .map_err(|_| format!("invalid room `{}`", room))?;
+
+    let lines: Vec<String> = parse_slots(slot)?
+        .into_iter()
+        .map(|slot| {
+            let activities: Vec<String> = timetable
+                .lessons()
+                .during(slot)
+                .in_room(location)
+                .map(|(_, entry)| entry.activity.to_string())
+                .collect();
+
+            let description = if activities.is_empty() {
+                "free".to_string()
+            } else {
+                activities.join(", ")
+            };
+
+            format!("{}: {}", describe(slot), description)
+        })
+        .collect();
+
+    Ok(lines.join("\n"))
+}
+
+// List the rooms at a site which are not in use during the slot provided
+//
+// Only the rooms which appear somewhere within the timetable are considered
+// (the timetable has no knowledge of rooms which are never used)
+fn free_rooms(timetable: &Timetable, site: &str, slot: &str) -> Result<String, String> {
+    let school = match site.to_ascii_lowercase().as_str() {
+        "highfield" | "hf" => School::Highfield,
+        "fearnhill" | "fh" => School::Fearnhill,
+        _ => return Err(format!("invalid site `{}`", site)),
+    };
+
+    let rooms: BTreeSet<String> = timetable
+        .entries()
+        .flat_map(|(_, entry)| entry.locations())
+        .filter(|location| location.school() == school)
+        .map(Location::to_string)
+        .collect();
+
+    let lines: Vec<String> = parse_slots(slot)?
+        .into_iter()
+        .map(|slot| {
+            let used: BTreeSet<String> = timetable
+                .at(slot)
+                .flat_map(|(_, entry)| entry.locations())
+                .map(Location::to_string)
+                .collect();
+
+            let free: Vec<&str> = rooms.difference(&used).map(String::as_str).collect();
+
+            format!("{}: {}", describe(slot), free.join(", "))
+        })
+        .collect();
+
+    Ok(lines.join("\n"))
+}
+
+// Parse a slot of the form `<day> <period>` (optionally accompanied by a
+// week) into every time slot it refers to
+fn parse_slots(s: &str) -> Result<Vec<TimeSlot>, String> {
+    let mut week = None;
+    let mut day = None;
+    let mut period = None;
+
+    for token in s.split_whitespace() {
+        let upper = token.to_ascii_uppercase();
+
+        if let Some(number) = upper.strip_prefix('P') {
+            period = Some(match number {
+                "1" => Period::First,
+                "2" => Period::Second,
+                "3" => Period::Third,
+                "4" => Period::Fourth,
+                "5" => Period::Fifth,
+                _ => return Err(format!("invalid period `{}`", token)),
+            });
+        } else if let Ok(weekday) = token.parse::<Weekday>() {
+            day = Some(weekday);
+        } else {
+            week = Some(
+                match upper.trim_start_matches("WEEK").trim_start_matches('W') {
+                    "1" | "A" => Week::WeekOne,
+                    "2" | "B" => Week::WeekTwo,
+                    _ => return Err(format!("invalid slot component `{}`", token)),
+                },
+            );
+        }
+    }
+
+    let day = day.ok_or_else(|| format!("the slot `{}` does not contain a day", s))?;
+    let period = period.ok_or_else(|| format!("the slot `{}` does not contain a period", s))?;
+
+    if matches!(day, Weekday::Sat | Weekday::Sun) {
+        return Err(format!("the slot `{}` falls on a weekend", s));
+    }
+
+    let weeks = match week {
+        Some(week) => vec![week],
+        None => vec![Week::WeekOne, Week::WeekTwo],
+    };
+
+    Ok(weeks
+        .into_iter()
+        .map(|week| TimeSlot { week, day, period })
+        .collect())
+}
+
+// Describe a time slot (e.g., `Week 1 Tue P4`)
+fn describe(slot: TimeSlot) -> String {
+    format!(
+        "Week {} {} P{}",
+        slot.week as usize + 1,
+        slot.day,
+        slot.period as usize + 1
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use timetableau::{timeslot, Entry};
+
+    #[test]
+    fn cli_parse_slots() {
+        assert_eq!(
+            parse_slots("wed B p2"),
+            Ok(vec![TimeSlot {
+                week: Week::WeekTwo,
+                day: Weekday::Wed,
+                period: Period::Second,
+            }])
+        );
+        assert_eq!(parse_slots("tue p4").map(|slots| slots.len()), Ok(2));
+        assert!(parse_slots("sat p1").is_err());
+        assert!(parse_slots("mon").is_err());
+    }
+
+    #[test]
+    fn cli_queries() {
+        let timetable = Timetable::from_toml_str(
+            "[week1]\ntue.p4 = { subject = 'Ma', class = '11JSM', room = 'H201' }\n\
+             [week2]\ntue.p4 = { subject = 'Ph', class = '11ABC', room = 'H202' }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            room_at(&timetable, "H201", "tue p4").unwrap(),
+            "Week 1 Tue P4: Ma 11JSM H201\nWeek 2 Tue P4: free"
+        );
+        assert_eq!(
+            free_rooms(&timetable, "highfield", "wed B p2").unwrap(),
+            "Week 2 Wed P2: H201, H202"
+        );
+        assert_eq!(
+            free_rooms(&timetable, "highfield", "tue 2 p4").unwrap(),
+            "Week 2 Tue P4: H201"
+        );
+    }
+
+    #[test]
+    fn cli_next_co_taught() {
+        let timetable = Timetable::from_toml_str(
+            "[week1]\nmon.p2 = { subject = 'PE', class = '10A', teacher = 'ABC', room = 'Hall', \
+             co_teachers = ['DEF'], split_rooms = ['Sports Hall'] }",
+        )
+        .unwrap();
+
+        // 4 September 2023 is the Monday of week one
+        let now = NaiveDate::from_ymd_opt(2023, 9, 4)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap();
+
+        assert_eq!(
+            next_after(&timetable, "def", "2023-09-04", now).unwrap(),
+            "Mon 04 Sep, P2 (09:50-10:50): PE 10A Hall (also in Sports Hall)"
+        );
+        assert!(next_after(&timetable, "GHI", "2023-09-04", now).is_err());
+    }
+
+    #[test]
+    fn cli_next_spanning() {
+        let timetable = Timetable::from_toml_str(
+            "[week1]\nmon.p3 = { subject = 'PE', class = '10A', teacher = 'ABC', room = 'Hall', \
+             periods = 3 }",
+        )
+        .unwrap();
+        let monday = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+
+        // The whole of a triple lesson is shown, including during its later
+        // periods
+        for (hour, minute) in [(8, 0), (12, 30), (14, 0)] {
+            let now = monday.and_hms_opt(hour, minute, 0).unwrap();
+
+            assert_eq!(
+                next_after(&timetable, "ABC", "2023-09-04", now).unwrap(),
+                "Mon 04 Sep, P3 (11:10-14:55): PE 10A Hall"
+            );
+        }
+    }
+
+    #[test]
+    fn cli_free_rooms_split() {
+        let mut timetable = Timetable::from_toml_str(
+            "[week1]\ntue.p4 = { subject = 'Ma', class = '11JSM', room = 'H201' }",
+        )
+        .unwrap();
+        let lesson = timetable.entries().next().unwrap().1.activity.clone();
+        timetable
+            .insert_entry(Entry::new(timeslot!(W1TP5), lesson).split_room("H203".parse().unwrap()))
+            .unwrap();
+
+        assert_eq!(
+            free_rooms(&timetable, "highfield", "tue 1 p4").unwrap(),
+            "Week 1 Tue P4: H203"
+        );
+        assert_eq!(
+            free_rooms(&timetable, "highfield", "tue 1 p5").unwrap(),
+            "Week 1 Tue P5: "
+        );
+    }
+}
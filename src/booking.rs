@@ -0,0 +1,427 @@
+//! Bookable spaces which are not timetabled by lesson (e.g., library tables,
+//! music practice rooms, or the recording booth).
+//!
+//! Unlike a room of the timetable, a [`Resource`] may be booked by multiple
+//! students during the same period (up to its capacity), and is booked for a
+//! specific date rather than for every iteration of the timetable. Bookings
+//! are only made on the school days of the calendar, and are checked for
+//! clashes in the same way as after-school bookings (*see
+//! [`AfterSchoolTimetable`](crate::AfterSchoolTimetable)*):
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::booking::{Booking, BookingClash, Resource, Spaces};
+//! use timetableau::{AcademicCalendar, Period, Term, WeekCycle};
+//!
+//! let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+//! let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+//! let cycle = WeekCycle::new(date(4), calendar).unwrap();
+//!
+//! let mut spaces = Spaces::new();
+//! let booth = spaces.add(Resource::new("Recording Booth", 1));
+//!
+//! let booking = |student: &str| Booking::new(booth, date(4), Period::Third, student);
+//!
+//! assert_eq!(spaces.book(&cycle, booking("ADA")), Ok(0));
+//! assert_eq!(
+//!     spaces.book(&cycle, booking("BOB")),
+//!     Err(BookingClash::Full { resource: booth, capacity: 1 })
+//! );
+//! ```
+//!
+//! Students who book a space but do not attend are recorded as no-shows
+//! (*see [`Spaces::record_attendance`]*) -- once a student reaches the
+//! [limit](Spaces::no_show_limit) of no-shows, they may no longer book.
+
+use crate::{Activity, EntryId, Location, Period, Timetable, WeekCycle};
+use chrono::NaiveDate;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// A space which is booked by students, rather than timetabled by lesson.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resource {
+    /// The name of the resource (e.g., `Library Table 3`).
+    pub name: String,
+
+    /// The room in which the resource is located (if known).
+    pub location: Option<Location>,
+
+    /// The number of students who may book the resource during the same
+    /// period.
+    pub capacity: usize,
+}
+
+impl Resource {
+    /// Creates a new `Resource` with the `name` and the `capacity`
+    /// provided, without a location.
+    pub fn new(name: &str, capacity: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            location: None,
+            capacity,
+        }
+    }
+
+    /// Sets the room in which the resource is located.
+    pub fn location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+}
+
+/// The booking of a [`Resource`] by a student for a period of a specific
+/// date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Booking {
+    /// The index of the resource (within [`Spaces::resources`]).
+    pub resource: usize,
+
+    /// The date of the booking.
+    pub date: NaiveDate,
+
+    /// The period of the booking.
+    pub period: Period,
+
+    /// The identifier of the student (e.g., their admission number).
+    pub student: String,
+
+    /// Whether the student attended the booking ([`None`] until their
+    /// attendance is recorded).
+    pub attended: Option<bool>,
+}
+
+impl Booking {
+    /// Creates a new `Booking` of the `resource` provided, whose attendance
+    /// is yet to be recorded.
+    pub fn new(resource: usize, date: NaiveDate, period: Period, student: &str) -> Self {
+        Self {
+            resource,
+            date,
+            period,
+            student: student.to_string(),
+            attended: None,
+        }
+    }
+}
+
+/// A clash which prevents a [`Booking`] from being made.
+///
+/// Existing bookings are identified by their index within
+/// [`Spaces::bookings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookingClash {
+    /// The resource does not exist.
+    UnknownResource(usize),
+
+    /// The date of the booking is not a school day (*see
+    /// [`AcademicCalendar::is_school_day`](crate::AcademicCalendar::is_school_day)*).
+    NotSchoolDay(NaiveDate),
+
+    /// The resource is already booked to its capacity during the period.
+    Full {
+        /// The index of the resource.
+        resource: usize,
+
+        /// The capacity of the resource.
+        capacity: usize,
+    },
+
+    /// The student has already booked a resource during the period.
+    Student {
+        /// The index of the existing booking.
+        booking: usize,
+    },
+
+    /// The student has a lesson during the period (*see
+    /// [`Spaces::book_around`]*).
+    Lesson(EntryId),
+
+    /// The student has reached the limit of no-shows.
+    Suspended {
+        /// The number of bookings the student did not attend.
+        no_shows: usize,
+    },
+}
+
+impl Display for BookingClash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownResource(resource) => write!(f, "resource {} does not exist", resource),
+            Self::NotSchoolDay(date) => write!(f, "{} is not a school day", date),
+            Self::Full { resource, capacity } => write!(
+                f,
+                "resource {} is already booked by {} students during the period",
+                resource, capacity
+            ),
+            Self::Student { booking } => write!(
+                f,
+                "the student has already made booking {} during the period",
+                booking
+            ),
+            Self::Lesson(id) => write!(
+                f,
+                "the student has a lesson (entry {}) during the period",
+                id.get()
+            ),
+            Self::Suspended { no_shows } => write!(
+                f,
+                "the student did not attend {} bookings, and may no longer book",
+                no_shows
+            ),
+        }
+    }
+}
+
+impl Error for BookingClash {}
+
+/// The bookable [`Resource`]s of a school, and their [`Booking`]s.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Spaces {
+    resources: Vec<Resource>,
+    bookings: Vec<Booking>,
+    no_show_limit: Option<usize>,
+}
+
+impl Spaces {
+    /// Creates a new `Spaces` without any resources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of no-shows after which a student may no longer book
+    /// (by default, there is no limit).
+    pub fn no_show_limit(mut self, limit: usize) -> Self {
+        self.no_show_limit = Some(limit);
+        self
+    }
+
+    /// Adds the `resource` provided, returning its index within
+    /// [`Self::resources`].
+    pub fn add(&mut self, resource: Resource) -> usize {
+        self.resources.push(resource);
+        self.resources.len() - 1
+    }
+
+    /// Retrieves every resource (in the order in which they were added).
+    pub fn resources(&self) -> &[Resource] {
+        &self.resources
+    }
+
+    /// Retrieves every booking (in the order in which they were booked).
+    pub fn bookings(&self) -> &[Booking] {
+        &self.bookings
+    }
+
+    /// Retrieves the bookings (and their indexes) of the `resource` provided
+    /// during the `period` of the `date` provided.
+    pub fn at(
+        &self,
+        resource: usize,
+        date: NaiveDate,
+        period: Period,
+    ) -> impl Iterator<Item = (usize, &Booking)> + '_ {
+        self.bookings
+            .iter()
+            .enumerate()
+            .filter(move |(_, booking)| {
+                booking.resource == resource && booking.date == date && booking.period == period
+            })
+    }
+
+    /// Retrieves the number of students who may still book the `resource`
+    /// provided during the `period` of the `date` provided.
+    pub fn remaining(&self, resource: usize, date: NaiveDate, period: Period) -> usize {
+        self.resources.get(resource).map_or(0, |details| {
+            details
+                .capacity
+                .saturating_sub(self.at(resource, date, period).count())
+        })
+    }
+
+    /// Books the `booking` provided, returning its index within
+    /// [`Self::bookings`].
+    ///
+    /// # Errors
+    ///
+    /// A [`BookingClash`] is returned (and the booking is not made) should
+    /// the resource not exist, the date not be a school day of the `cycle`'s
+    /// calendar, the resource be full, the student already have a booking
+    /// during the period, or the student have reached the limit of no-shows.
+    pub fn book(&mut self, cycle: &WeekCycle, booking: Booking) -> Result<usize, BookingClash> {
+        let resource = self
+            .resources
+            .get(booking.resource)
+            .ok_or(BookingClash::UnknownResource(booking.resource))?;
+
+        if !cycle.calendar().is_school_day(booking.date) {
+            return Err(BookingClash::NotSchoolDay(booking.date));
+        }
+
+        let no_shows = self.no_shows(&booking.student);
+
+        if self.no_show_limit.is_some_and(|limit| no_shows >= limit) {
+            return Err(BookingClash::Suspended { no_shows });
+        }
+
+        if let Some((index, _)) = self.bookings.iter().enumerate().find(|(_, other)| {
+            other.student == booking.student
+                && other.date == booking.date
+                && other.period == booking.period
+        }) {
+            return Err(BookingClash::Student { booking: index });
+        }
+
+        if self.remaining(booking.resource, booking.date, booking.period) == 0 {
+            return Err(BookingClash::Full {
+                resource: booking.resource,
+                capacity: resource.capacity,
+            });
+        }
+
+        self.bookings.push(booking);
+
+        Ok(self.bookings.len() - 1)
+    }
+
+    /// Books the `booking` provided (*see [`Self::book`]*), should the
+    /// student's `timetable` not have a lesson during the period of the
+    /// booking.
+    ///
+    /// # Errors
+    ///
+    /// [`BookingClash::Lesson`] should the student have a lesson during the
+    /// period (after any exceptions and overrides of the date are applied
+    /// -- *see [`Timetable::occurrences`]*), or any other [`BookingClash`]
+    /// returned by [`Self::book`].
+    pub fn book_around(
+        &mut self,
+        cycle: &WeekCycle,
+        timetable: &Timetable,
+        booking: Booking,
+    ) -> Result<usize, BookingClash> {
+        let lesson = timetable
+            .occurrences(cycle, booking.date)
+            .into_iter()
+            .filter(|occurrence| occurrence.slot.period == booking.period)
+            .find(|occurrence| matches!(occurrence.activity, Activity::Lesson { .. }));
+
+        match lesson.and_then(|occurrence| occurrence.entry) {
+            Some(id) => Err(BookingClash::Lesson(id)),
+            None => self.book(cycle, booking),
+        }
+    }
+
+    /// Cancels the booking at the `index` provided, returning it.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if there is no booking at the `index` provided -- the indexes
+    /// of later bookings are decremented.
+    pub fn cancel(&mut self, index: usize) -> Option<Booking> {
+        (index < self.bookings.len()).then(|| self.bookings.remove(index))
+    }
+
+    /// Records whether the student attended the booking at the `index`
+    /// provided.
+    ///
+    /// # Returns
+    ///
+    /// [`false`] if there is no booking at the `index` provided.
+    pub fn record_attendance(&mut self, index: usize, attended: bool) -> bool {
+        match self.bookings.get_mut(index) {
+            Some(booking) => {
+                booking.attended = Some(attended);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Retrieves the number of bookings which the student with the
+    /// identifier provided did not attend.
+    pub fn no_shows(&self, student: &str) -> usize {
+        self.bookings
+            .iter()
+            .filter(|booking| booking.student == student && booking.attended == Some(false))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Subject, Term};
+
+    #[test]
+    fn spaces_capacity_and_no_shows() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+        let cycle = WeekCycle::new(date(4), calendar).unwrap();
+
+        let mut spaces = Spaces::new().no_show_limit(2);
+        let tables =
+            spaces.add(Resource::new("Library Tables", 2).location("H101".parse().unwrap()));
+        let booking = |student: &str, day, period| Booking::new(tables, date(day), period, student);
+
+        assert_eq!(spaces.book(&cycle, booking("ADA", 4, Period::First)), Ok(0));
+        assert_eq!(spaces.book(&cycle, booking("BOB", 4, Period::First)), Ok(1));
+        assert_eq!(spaces.remaining(tables, date(4), Period::First), 0);
+        assert_eq!(
+            spaces.book(&cycle, booking("CAL", 4, Period::First)),
+            Err(BookingClash::Full {
+                resource: tables,
+                capacity: 2
+            })
+        );
+        assert_eq!(
+            spaces.book(&cycle, booking("ADA", 4, Period::First)),
+            Err(BookingClash::Student { booking: 0 })
+        );
+        assert_eq!(
+            spaces.book(&cycle, booking("ADA", 9, Period::First)),
+            Err(BookingClash::NotSchoolDay(date(9)))
+        );
+        assert_eq!(
+            spaces.book(&cycle, Booking::new(7, date(4), Period::First, "ADA")),
+            Err(BookingClash::UnknownResource(7))
+        );
+
+        // The student's lessons prevent them from booking
+        let mut timetable = Timetable::new();
+        let lesson = timetable.insert(
+            timeslot!(W1MP2),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("12A".to_string()).unwrap(),
+                teacher: None,
+                location: "H201".parse().unwrap(),
+            },
+        );
+
+        assert_eq!(
+            spaces.book_around(&cycle, &timetable, booking("ADA", 4, Period::Second)),
+            Err(BookingClash::Lesson(lesson))
+        );
+        assert_eq!(
+            spaces.book_around(&cycle, &timetable, booking("ADA", 4, Period::Third)),
+            Ok(2)
+        );
+
+        // Once the student has not attended twice, they may no longer book
+        assert!(spaces.record_attendance(0, false));
+        assert!(spaces.record_attendance(1, true));
+        assert!(spaces.record_attendance(2, false));
+        assert!(!spaces.record_attendance(3, false));
+
+        assert_eq!(spaces.no_shows("ADA"), 2);
+        assert_eq!(
+            spaces.book(&cycle, booking("ADA", 5, Period::First)),
+            Err(BookingClash::Suspended { no_shows: 2 })
+        );
+        assert!(spaces.cancel(1).is_some());
+        assert_eq!(spaces.book(&cycle, booking("BOB", 5, Period::First)), Ok(2));
+    }
+}
@@ -0,0 +1,478 @@
+//! Parsing of the room changes and cover listed by the daily staff bulletin.
+//!
+//! Every morning, the plain-text bulletin lists the lessons which have been
+//! moved to a different room, and the lessons of absent staff which are to
+//! be covered by another member of staff:
+//!
+//! ```text
+//! P3: 10B Geography moves to H105
+//! Cover: JSM absent, P2 11C covered by ABC in U G03, P4 10A covered by XYZ
+//! ```
+//!
+//! The bulletin is [parsed](parse) into a [`Bulletin`], which reports every
+//! line which could not be understood, and which is then
+//! [applied](Bulletin::apply) to the timetable for the date of the bulletin:
+//!
+//! ```
+//! use timetableau::bulletin;
+//!
+//! let bulletin = bulletin::parse(
+//!     "P3: 10B Geography moves to H105\n\
+//!      Staff briefing at 08:30\n\
+//!      Cover: JSM absent, P2 11C covered by ABC in U G03",
+//! );
+//!
+//! assert_eq!(bulletin.room_changes[0].to, "H105".parse().unwrap());
+//! assert_eq!(bulletin.covers[0].teacher.code(), "ABC");
+//! assert_eq!(bulletin.unrecognised[0].line, 2);
+//! ```
+
+use crate::timeslot::parse_period;
+use crate::{
+    Activity, Entry, EntryId, Location, Occurrence, Period, Teacher, TimeSlot, Timetable, WeekCycle,
+};
+use chrono::NaiveDate;
+
+/// A lesson which has been moved to a different room (e.g., `P3: 10B
+/// Geography moves to H105`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoomChange {
+    /// The line of the bulletin on which the room change is listed.
+    pub line: usize,
+
+    /// The period of the lesson.
+    pub period: Period,
+
+    /// The reference of the class of the lesson (e.g., `10B`), which also
+    /// matches the sets of the class (e.g., `10B/Gg1`).
+    pub class: String,
+
+    /// The subject of the lesson (if listed), which is matched
+    /// case-insensitively, by prefix, or as the abbreviation of the subject
+    /// of the lesson (e.g., `Geography` matches `Gg`).
+    pub subject: Option<String>,
+
+    /// The room to which the lesson has been moved.
+    pub to: Location,
+}
+
+/// A lesson of an absent member of staff which is to be covered (e.g.,
+/// `Cover: JSM absent, P2 11C covered by ABC in U G03`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cover {
+    /// The line of the bulletin on which the cover is listed.
+    pub line: usize,
+
+    /// The absent member of staff.
+    pub absent: Teacher,
+
+    /// The period of the lesson.
+    pub period: Period,
+
+    /// The reference of the class of the lesson (*see
+    /// [`RoomChange::class`]*).
+    pub class: String,
+
+    /// The member of staff covering the lesson.
+    pub teacher: Teacher,
+
+    /// The room to which the lesson has been moved (if listed).
+    pub location: Option<Location>,
+}
+
+/// A line of a bulletin which could not be understood.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnrecognisedLine {
+    /// The line of the bulletin.
+    pub line: usize,
+
+    /// The text of the line.
+    pub text: String,
+}
+
+/// The room changes and cover listed by a daily bulletin.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bulletin {
+    /// The lessons which have been moved to a different room.
+    pub room_changes: Vec<RoomChange>,
+
+    /// The lessons of absent staff which are to be covered.
+    pub covers: Vec<Cover>,
+
+    /// The lines of the bulletin which could not be understood (blank lines
+    /// are disregarded).
+    pub unrecognised: Vec<UnrecognisedLine>,
+}
+
+/// The assignment of a member of staff to cover a lesson on a specific date
+/// (*see [`Bulletin::apply`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    /// The entry of the lesson.
+    pub entry: EntryId,
+
+    /// The date on which the lesson is covered.
+    pub date: NaiveDate,
+
+    /// The absent member of staff.
+    pub absent: Teacher,
+
+    /// The member of staff covering the lesson.
+    pub teacher: Teacher,
+}
+
+impl Assignment {
+    /// Replaces the teacher of the covered lesson within the `occurrences`
+    /// provided (e.g., those produced by [`Timetable::occurrences`]) with the
+    /// member of staff covering it.
+    pub fn apply(&self, occurrences: &mut [Occurrence]) {
+        for occurrence in occurrences {
+            if occurrence.entry == Some(self.entry) && occurrence.date == self.date {
+                if let Activity::Lesson { teacher, .. } = &mut occurrence.activity {
+                    *teacher = Some(self.teacher.clone());
+                }
+            }
+        }
+    }
+}
+
+/// The result of applying a [`Bulletin`] to a timetable.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Applied {
+    /// The number of room overrides recorded.
+    pub overrides: usize,
+
+    /// The cover assignments of the lessons being covered.
+    pub assignments: Vec<Assignment>,
+
+    /// The lines of the room changes and cover which did not match any
+    /// lesson of the timetable.
+    pub unmatched: Vec<usize>,
+}
+
+impl Bulletin {
+    /// Applies the bulletin to the `timetable` provided, for the `date` of
+    /// the bulletin.
+    ///
+    /// Each room change (and each cover listing a room) is recorded as an
+    /// override of the matching lessons for the date (*see
+    /// [`Timetable::override_location`]*). Cover is returned as a set of
+    /// [`Assignment`]s, since the timetable does not record the staff of
+    /// individual occurrences -- the lesson covered is the lesson of the
+    /// class taught by the absent member of staff (or without a teacher).
+    ///
+    /// # Remarks
+    ///
+    /// Should the date not be a part of the cycle (e.g., during a holiday),
+    /// every room change and cover is unmatched.
+    pub fn apply(&self, timetable: &mut Timetable, cycle: &WeekCycle, date: NaiveDate) -> Applied {
+        let mut applied = Applied::default();
+
+        let Some((week, day)) = cycle.day_for(date) else {
+            applied
+                .unmatched
+                .extend(self.room_changes.iter().map(|change| change.line));
+            applied
+                .unmatched
+                .extend(self.covers.iter().map(|cover| cover.line));
+            applied.unmatched.sort_unstable();
+
+            return applied;
+        };
+
+        let slot = |period| TimeSlot { week, day, period };
+
+        for change in &self.room_changes {
+            let lessons = matching(timetable, slot(change.period), |entry| {
+                class_matches(entry, &change.class)
+                    && change
+                        .subject
+                        .as_deref()
+                        .is_none_or(|name| subject_matches(entry, name))
+            });
+
+            if lessons.is_empty() {
+                applied.unmatched.push(change.line);
+            }
+
+            for (_, location) in lessons {
+                timetable.override_location(slot(change.period), date..=date, location, change.to);
+                applied.overrides += 1;
+            }
+        }
+
+        for cover in &self.covers {
+            let lessons = matching(timetable, slot(cover.period), |entry| {
+                class_matches(entry, &cover.class)
+                    && entry
+                        .activity
+                        .teacher()
+                        .is_none_or(|teacher| *teacher == cover.absent)
+            });
+
+            if lessons.is_empty() {
+                applied.unmatched.push(cover.line);
+            }
+
+            for (entry, location) in lessons {
+                if let Some(to) = cover.location {
+                    timetable.override_location(slot(cover.period), date..=date, location, to);
+                    applied.overrides += 1;
+                }
+
+                applied.assignments.push(Assignment {
+                    entry,
+                    date,
+                    absent: cover.absent.clone(),
+                    teacher: cover.teacher.clone(),
+                });
+            }
+        }
+
+        applied.unmatched.sort_unstable();
+        applied
+    }
+}
+
+/// Parses the daily bulletin provided.
+///
+/// Room changes are listed in the form `P3: 10B Geography moves to H105`
+/// (the subject is optional, and `moved to` is also accepted), and cover in
+/// the form `Cover: JSM absent, P2 11C covered by ABC in U G03` (the room is
+/// optional, and any number of lessons may be listed). Every other line
+/// which is not blank is reported as [unrecognised](Bulletin::unrecognised).
+pub fn parse(text: &str) -> Bulletin {
+    let mut bulletin = Bulletin::default();
+
+    for (index, text) in text.lines().enumerate() {
+        let line = index + 1;
+        let text = text.trim();
+
+        if text.is_empty() {
+            continue;
+        }
+
+        let parsed = text.split_once(':').and_then(|(head, rest)| {
+            let head = head.trim();
+
+            if head.eq_ignore_ascii_case("cover") {
+                parse_cover(line, rest).map(|covers| bulletin.covers.extend(covers))
+            } else if head.starts_with(['P', 'p']) {
+                let period = parse_period(head)?;
+
+                parse_room_change(line, period, rest)
+                    .map(|change| bulletin.room_changes.push(change))
+            } else {
+                None
+            }
+        });
+
+        if parsed.is_none() {
+            bulletin.unrecognised.push(UnrecognisedLine {
+                line,
+                text: text.to_string(),
+            });
+        }
+    }
+
+    bulletin
+}
+
+// Parse a room change (following its period), e.g., `10B Geography moves to
+// H105`
+fn parse_room_change(line: usize, period: Period, text: &str) -> Option<RoomChange> {
+    let (lesson, room) = split_phrase(text, &[" moves to ", " moved to "])?;
+
+    let mut words = lesson.split_whitespace();
+    let class = words.next()?.to_string();
+    let subject = words.collect::<Vec<_>>().join(" ");
+
+    Some(RoomChange {
+        line,
+        period,
+        class,
+        subject: Some(subject).filter(|subject| !subject.is_empty()),
+        to: parse_room(room)?,
+    })
+}
+
+// Parse the cover of an absent member of staff (following `Cover:`), e.g.,
+// `JSM absent, P2 11C covered by ABC in U G03`
+fn parse_cover(line: usize, text: &str) -> Option<Vec<Cover>> {
+    let mut parts = text.split(',');
+
+    let absent = parts
+        .next()?
+        .trim()
+        .strip_suffix("absent")
+        .map(str::trim)
+        .and_then(|code| Teacher::new(code.to_string()).ok())?;
+
+    let covers = parts
+        .map(|part| {
+            let (lesson, cover) = split_phrase(part, &[" covered by "])?;
+            let (period, class) = lesson.trim().split_once(' ')?;
+            let (code, room) = match split_phrase(cover, &[" in "]) {
+                Some((code, room)) => (code, Some(parse_room(room)?)),
+                None => (cover, None),
+            };
+
+            Some(Cover {
+                line,
+                absent: absent.clone(),
+                period: parse_period(period)?,
+                class: class.trim().to_string(),
+                teacher: Teacher::new(code.trim().to_string()).ok()?,
+                location: room,
+            })
+        })
+        .collect::<Option<Vec<Cover>>>()?;
+
+    Some(covers).filter(|covers| !covers.is_empty())
+}
+
+// Parse a room, which bulletins often write with a space after the block
+// (e.g., `U G03`)
+fn parse_room(text: &str) -> Option<Location> {
+    let text = text.trim();
+
+    text.parse()
+        .ok()
+        .or_else(|| text.replace(' ', "").parse().ok())
+}
+
+// Split the text provided at the first of the phrases provided which it
+// contains (case-insensitively)
+fn split_phrase<'a>(text: &'a str, phrases: &[&str]) -> Option<(&'a str, &'a str)> {
+    let lower = text.to_ascii_lowercase();
+
+    phrases.iter().find_map(|phrase| {
+        let index = lower.find(phrase)?;
+        Some((&text[..index], &text[index + phrase.len()..]))
+    })
+}
+
+// Retrieve the entries (and locations) of the lessons of the slot provided
+// which satisfy the predicate provided
+fn matching<P>(timetable: &Timetable, slot: TimeSlot, predicate: P) -> Vec<(EntryId, Location)>
+where
+    P: Fn(&Entry) -> bool,
+{
+    timetable
+        .at(slot)
+        .filter(|(_, entry)| predicate(entry))
+        .filter_map(|(id, entry)| Some((id, *entry.activity.location()?)))
+        .collect()
+}
+
+// Determine whether a lesson is of the class (or a set of the class)
+// provided
+fn class_matches(entry: &Entry, reference: &str) -> bool {
+    let Activity::Lesson { class, .. } = &entry.activity else {
+        return false;
+    };
+
+    let class = class.reference().to_ascii_lowercase();
+    let reference = reference.to_ascii_lowercase();
+
+    class == reference || class.starts_with(&format!("{}/", reference))
+}
+
+// Determine whether a lesson is of the subject provided (matched by prefix,
+// or by abbreviation)
+fn subject_matches(entry: &Entry, name: &str) -> bool {
+    let Activity::Lesson { subject, .. } = &entry.activity else {
+        return false;
+    };
+
+    let subject = subject.name().to_ascii_lowercase();
+    let name = name.to_ascii_lowercase();
+
+    let mut letters = name.chars();
+    let abbreviates = subject.chars().next() == name.chars().next()
+        && subject
+            .chars()
+            .all(|letter| letters.any(|other| other == letter));
+
+    subject.starts_with(&name) || name.starts_with(&subject) || abbreviates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Subject, Term};
+
+    #[test]
+    fn bulletin_applied() {
+        let lesson = |subject: &str, class: &str, teacher: &str, room: &str| Activity::Lesson {
+            subject: Subject::new(subject.to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Some(Teacher::new(teacher.to_string()).unwrap()),
+            location: room.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        let maths = timetable.insert(timeslot!(W1MP2), lesson("Maths", "11C/Ma1", "JSM", "H201"));
+        timetable.insert(timeslot!(W1MP2), lesson("Art", "11C/Ar1", "DEF", "H202"));
+        timetable.insert(timeslot!(W1MP3), lesson("Gg", "10B/Gg1", "DEF", "H201"));
+
+        let monday = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(
+            monday,
+            NaiveDate::from_ymd_opt(2023, 12, 20).unwrap(),
+        )
+        .unwrap()]);
+        let cycle = WeekCycle::new(monday, calendar).unwrap();
+
+        let bulletin = parse(
+            "P3: 10B Geography moves to H105\n\
+             \n\
+             p4: 9Z moved to H101\n\
+             Cover: JSM absent, P2 11C covered by ABC in U G03\n\
+             Cover: JSM absent\n\
+             P9: 10B moves to H105",
+        );
+
+        assert_eq!(bulletin.room_changes.len(), 2);
+        assert_eq!(bulletin.covers[0].location, Some("UG03".parse().unwrap()));
+        assert_eq!(
+            bulletin
+                .unrecognised
+                .iter()
+                .map(|line| line.line)
+                .collect::<Vec<_>>(),
+            [5, 6]
+        );
+
+        let applied = bulletin.apply(&mut timetable, &cycle, monday);
+
+        assert_eq!(applied.overrides, 2);
+        assert_eq!(applied.unmatched, [3]);
+        assert_eq!(applied.assignments[0].entry, maths);
+
+        let mut occurrences = timetable.occurrences(&cycle, monday);
+        applied.assignments[0].apply(&mut occurrences);
+
+        let describe = |occurrence: &Occurrence| {
+            (
+                occurrence.activity.location().unwrap().to_string(),
+                occurrence.activity.teacher().unwrap().code().clone(),
+            )
+        };
+
+        assert_eq!(
+            occurrences.iter().map(describe).collect::<Vec<_>>(),
+            [
+                ("UG03".to_string(), "ABC".to_string()),
+                ("H202".to_string(), "DEF".to_string()),
+                ("H105".to_string(), "DEF".to_string()),
+            ]
+        );
+
+        // The overrides only apply to the date of the bulletin
+        let next = timetable.occurrences(&cycle, monday + chrono::Duration::days(14));
+        assert_eq!(next[0].activity.location().unwrap().to_string(), "H201");
+    }
+}
@@ -0,0 +1,262 @@
+//! Time-bounded caching of expensive derived results.
+//!
+//! A [`Cache`] memoises the results of expensive computations (e.g., the
+//! free rooms of each slot, the statistics of a year group, or a fetched
+//! feed) for a fixed time-to-live, such that interactive applications do not
+//! recompute them on every request. Results derived from a timetable are
+//! also invalidated by the changes made to the timetable -- each key decides
+//! which changes affect it (*see [`Invalidate`]*):
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use chrono::{Duration, Utc};
+//! use timetableau::cache::Cache;
+//! use timetableau::{timeslot, Activity, Location, Timetable};
+//!
+//! let mut timetable = Timetable::new();
+//! let changes = timetable.subscribe();
+//!
+//! // The rooms in use during each slot, kept for 5 minutes
+//! let mut used: Cache<_, Vec<Location>> = Cache::new(Duration::minutes(5));
+//! let now = Utc::now();
+//!
+//! let rooms = used.get_or_insert_with(timeslot!(W1MP1), now, || Vec::new());
+//! assert!(rooms.is_empty());
+//!
+//! timetable.insert(timeslot!(W1MP1), Activity::Registration);
+//!
+//! // The insertion affects the first slot, and invalidates its rooms
+//! for record in changes.try_iter() {
+//!     used.notify(&record);
+//! }
+//!
+//! assert!(used.get(&timeslot!(W1MP1), now).is_none());
+//! # }
+//! ```
+
+use crate::{Change, Location, TimeSlot};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A key of a [`Cache`] whose value may be invalidated by the changes made
+/// to a timetable.
+///
+/// # Remarks
+///
+/// The crate implements `Invalidate` for the following keys:
+///
+/// * [`TimeSlot`] -- the value is invalidated by any change to the slot
+///   (e.g., an entry being inserted into, or moved from, the slot).
+/// * `()` -- the value (e.g., the statistics of the whole timetable) is
+///   invalidated by every change.
+/// * [`String`] -- the value (e.g., a feed, keyed by its URL) is never
+///   invalidated by a change, only by its time-to-live.
+pub trait Invalidate<L = Location> {
+    /// Determines whether the `change` provided invalidates the value of the
+    /// key.
+    fn invalidated_by(&self, change: &Change<L>) -> bool;
+}
+
+impl<L> Invalidate<L> for TimeSlot {
+    fn invalidated_by(&self, change: &Change<L>) -> bool {
+        match change {
+            Change::Inserted { entry, .. } | Change::Removed { entry, .. } => entry.covers(*self),
+
+            // The span of a moved entry is not recorded in the change, so the
+            // rest of the day of each slot is invalidated
+            Change::Moved { from, to, .. } => [from, to].into_iter().any(|slot| {
+                slot.week == self.week && slot.day == self.day && slot.period <= self.period
+            }),
+            Change::OverrideAdded(room_override)
+            | Change::OverrideRemoved { room_override, .. } => room_override.slot == *self,
+
+            // The slot of a relocated entry is not recorded in the change
+            Change::Relocated { .. } => true,
+        }
+    }
+}
+
+impl<L> Invalidate<L> for () {
+    fn invalidated_by(&self, _: &Change<L>) -> bool {
+        true
+    }
+}
+
+impl<L> Invalidate<L> for String {
+    fn invalidated_by(&self, _: &Change<L>) -> bool {
+        false
+    }
+}
+
+/// A memoising cache whose values expire after a fixed time-to-live.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone)]
+pub struct Cache<K, V> {
+    values: HashMap<K, (DateTime<Utc>, V)>,
+    ttl: Duration,
+}
+
+impl<K: Eq + Hash, V> Cache<K, V> {
+    /// Creates a new, empty `Cache` whose values expire once the `ttl`
+    /// provided has elapsed after they were computed.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            values: HashMap::new(),
+            ttl,
+        }
+    }
+
+    /// Retrieves the value of the `key` provided, should it have been
+    /// computed and not yet have expired at the time provided (i.e., `now`).
+    pub fn get(&self, key: &K, now: DateTime<Utc>) -> Option<&V> {
+        self.values
+            .get(key)
+            .filter(|(computed, _)| now - *computed < self.ttl)
+            .map(|(_, value)| value)
+    }
+
+    /// Retrieves the value of the `key` provided, computing it using the
+    /// function provided (i.e., `compute`) should it not have been computed,
+    /// or should it have expired at the time provided (i.e., `now`).
+    pub fn get_or_insert_with<F>(&mut self, key: K, now: DateTime<Utc>, compute: F) -> &V
+    where
+        F: FnOnce() -> V,
+    {
+        use std::collections::hash_map::Entry;
+
+        let ttl = self.ttl;
+
+        match self.values.entry(key) {
+            Entry::Occupied(entry) => {
+                let (computed, value) = entry.into_mut();
+
+                if now - *computed >= ttl {
+                    *computed = now;
+                    *value = compute();
+                }
+
+                value
+            }
+            Entry::Vacant(entry) => &entry.insert((now, compute())).1,
+        }
+    }
+
+    /// Discards the value of the `key` provided (if any), returning it.
+    pub fn invalidate(&mut self, key: &K) -> Option<V> {
+        self.values.remove(key).map(|(_, value)| value)
+    }
+
+    /// Discards the value of every key which satisfies the `predicate`
+    /// provided.
+    pub fn invalidate_where<P: FnMut(&K) -> bool>(&mut self, mut predicate: P) {
+        self.values.retain(|key, _| !predicate(key));
+    }
+
+    /// Discards every value which has expired at the time provided (i.e.,
+    /// `now`), freeing the memory they occupy.
+    pub fn purge_expired(&mut self, now: DateTime<Utc>) {
+        let ttl = self.ttl;
+        self.values.retain(|_, (computed, _)| now - *computed < ttl);
+    }
+
+    /// Discards every value of the cache.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// Retrieves the number of values in the cache (including those which
+    /// have expired, but have not yet been purged).
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Determines whether the cache contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Discards the value of every key invalidated by the change of the
+    /// `record` provided (*see [`Invalidate`]*).
+    ///
+    /// This is intended to be called with each record received from
+    /// [`Timetable::subscribe`](crate::Timetable::subscribe), such that the
+    /// cache never returns a result derived from an outdated timetable.
+    pub fn notify<L>(&mut self, record: &crate::Record<L>)
+    where
+        K: Invalidate<L>,
+    {
+        self.values
+            .retain(|key, _| !key.invalidated_by(&record.change));
+    }
+}
+
+// The tests observe the subscribers of the timetable, which require `std`
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Activity, Timetable};
+
+    #[test]
+    fn cache_expires_and_invalidates() {
+        let now = Utc::now();
+        let mut calls = 0;
+        let mut cache: Cache<TimeSlot, usize> = Cache::new(Duration::seconds(60));
+
+        for offset in [0, 59, 60, 61] {
+            cache.get_or_insert_with(timeslot!(W1MP1), now + Duration::seconds(offset), || {
+                calls += 1;
+                calls
+            });
+        }
+
+        // The value is recomputed once it has expired (after 60 seconds)
+        assert_eq!(calls, 2);
+        assert_eq!(
+            cache.get(&timeslot!(W1MP1), now + Duration::seconds(119)),
+            Some(&2)
+        );
+        assert_eq!(
+            cache.get(&timeslot!(W1MP1), now + Duration::seconds(120)),
+            None
+        );
+
+        cache.get_or_insert_with(timeslot!(W1MP2), now, || 0);
+        cache.purge_expired(now + Duration::seconds(60));
+        assert_eq!(cache.len(), 1);
+
+        let mut timetable = Timetable::new();
+        let changes = timetable.subscribe();
+        let mut global: Cache<(), usize> = Cache::new(Duration::seconds(60));
+        let mut feeds: Cache<String, usize> = Cache::new(Duration::seconds(60));
+
+        cache.get_or_insert_with(timeslot!(W1MP2), now, || 0);
+        global.get_or_insert_with((), now, || 0);
+        feeds.get_or_insert_with("https://school.example".to_string(), now, || 0);
+
+        let id = timetable.insert(timeslot!(W1MP3), Activity::Break);
+        timetable.move_entry(id, timeslot!(W1MP2)).unwrap();
+
+        let records: Vec<_> = changes.try_iter().collect();
+
+        cache.notify(&records[0]);
+        global.notify(&records[0]);
+        feeds.notify(&records[0]);
+
+        assert_eq!(
+            cache.get(&timeslot!(W1MP1), now + Duration::seconds(61)),
+            Some(&2)
+        );
+        assert_eq!(cache.get(&timeslot!(W1MP2), now), Some(&0));
+        assert!(global.is_empty());
+        assert_eq!(feeds.len(), 1);
+
+        cache.notify(&records[1]);
+        assert_eq!(cache.get(&timeslot!(W1MP2), now), None);
+
+        cache.invalidate_where(|_| true);
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,673 @@
+use crate::{Activity, BankHoliday, BellSchedules, Period, TimetableError, Week};
+use chrono::prelude::*;
+use chrono::Duration;
+use num_traits::FromPrimitive;
+use std::collections::BTreeMap;
+
+/// A term (or half-term) of the academic year.
+///
+/// A term is a contiguous range of dates during which the school is open --
+/// the dates between two terms are referred to as a holiday.
+///
+/// # Remarks
+///
+/// Both the `start` and the `end` of the term are inclusive (i.e., a term
+/// starting on a Monday and ending on a Friday includes both the Monday and
+/// the Friday).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Term {
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+impl Term {
+    /// Creates a new `Term`.
+    ///
+    /// # Errors
+    ///
+    /// [`TimetableError::TermEndsBeforeStart`] is returned should `start`
+    /// occur after `end`.
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Result<Self, TimetableError> {
+        if start > end {
+            Err(TimetableError::TermEndsBeforeStart { start, end })
+        } else {
+            Ok(Self { start, end })
+        }
+    }
+
+    /// Retrieves the first day of the `Term`.
+    pub fn start(self) -> NaiveDate {
+        self.start
+    }
+
+    /// Retrieves the last day of the `Term`.
+    pub fn end(self) -> NaiveDate {
+        self.end
+    }
+
+    /// Whether the `date` provided falls within the `Term`.
+    pub fn contains(self, date: NaiveDate) -> bool {
+        date >= self.start && date <= self.end
+    }
+}
+
+/// A one-off deviation from the normal running of a school day.
+///
+/// Exceptions allow irregular days (e.g., a sports day, or a day on which the
+/// school closes early) to be represented without modifying the timetable
+/// itself -- *see the [`crate`] documentation for more information about
+/// deviations from the timetable*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DayException {
+    /// The school is closed for the entire day (e.g., due to snow).
+    Closed,
+
+    /// The school closes early -- no activities take place after the
+    /// `last_period`.
+    EarlyClosure {
+        /// The last `Period` which takes place on the day.
+        last_period: Period,
+    },
+
+    /// The timetabled activities for the `periods` provided are suspended
+    /// and replaced by `activity` (e.g., a mock exam day, or a sports day
+    /// where every period is suspended).
+    Suspended {
+        /// The periods during which the timetabled activities are suspended.
+        periods: Vec<Period>,
+
+        /// The activity which takes place instead of the timetabled
+        /// activities.
+        activity: Activity,
+    },
+
+    /// The school is open despite the day being a [`BankHoliday`] (e.g., in
+    /// exchange for a local occasional day taken at another time of the
+    /// year).
+    Open,
+
+    /// The bells ring at different times for the day (e.g., shortened
+    /// periods for a staff training afternoon, or a late start after an
+    /// open evening) -- the timetabled activities still take place, but
+    /// according to the [`BellSchedules`] provided instead of those of the
+    /// calendar (*see [`AcademicCalendar::bell_schedules_on`]*).
+    Schedule(BellSchedules),
+}
+
+/// The academic calendar of a school (i.e., the set of [`Term`]s during which
+/// the school is open).
+///
+/// # Bank Holidays
+///
+/// The school is closed on the [`BankHoliday`]s of England and Wales which
+/// fall within a term (e.g., the early May bank holiday), even should they
+/// not have been excluded from the term dates -- a school which opens on a
+/// bank holiday should record [`DayException::Open`] for that day, and the
+/// bank holidays can be disregarded entirely using
+/// [`Self::observe_bank_holidays`]. Local occasional days (on which the
+/// school closes) are recorded as [`DayException::Closed`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AcademicCalendar {
+    // Invariant: the terms are sorted by their start date
+    terms: Vec<Term>,
+    exceptions: BTreeMap<NaiveDate, DayException>,
+    bell_schedules: BellSchedules,
+    bank_holidays_disregarded: bool,
+}
+
+impl AcademicCalendar {
+    /// Creates a new `AcademicCalendar` from a set of [`Term`]s.
+    ///
+    /// The terms do not have to be provided in chronological order.
+    pub fn new(mut terms: Vec<Term>) -> Self {
+        terms.sort_by_key(|term| term.start);
+
+        Self {
+            terms,
+            exceptions: BTreeMap::new(),
+            bell_schedules: BellSchedules::default(),
+            bank_holidays_disregarded: false,
+        }
+    }
+
+    /// Retrieves the [`Term`]s of the `AcademicCalendar` in chronological
+    /// order.
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    /// Adds a [`Term`] to the `AcademicCalendar`.
+    pub fn add_term(&mut self, term: Term) {
+        let index = self
+            .terms
+            .partition_point(|other| other.start <= term.start);
+
+        self.terms.insert(index, term);
+    }
+
+    /// Whether the school is open on the `date` provided.
+    ///
+    /// A school day is an active day (i.e., Monday to Friday) which falls
+    /// within one of the calendar's [`Term`]s, which is not a [`BankHoliday`]
+    /// (unless the school is [open](DayException::Open)), and on which the
+    /// school has not been [closed](DayException::Closed).
+    ///
+    /// *See the [`crate`] documentation for more information*.
+    pub fn is_school_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+            && self.is_term_date(date)
+            && match self.exception(date) {
+                Some(DayException::Closed) => false,
+                Some(DayException::Open) => true,
+                _ => !self.is_bank_holiday(date),
+            }
+    }
+
+    /// Whether the `date` provided is a [`BankHoliday`] observed by the
+    /// calendar -- *see the [bank holiday
+    /// documentation](AcademicCalendar#bank-holidays) for more information*.
+    pub fn is_bank_holiday(&self, date: NaiveDate) -> bool {
+        !self.bank_holidays_disregarded && BankHoliday::on(date).is_some()
+    }
+
+    /// Sets whether the calendar observes [`BankHoliday`]s (which it does by
+    /// default).
+    pub fn observe_bank_holidays(&mut self, observe: bool) {
+        self.bank_holidays_disregarded = !observe;
+    }
+
+    /// Whether the calendar observes [`BankHoliday`]s.
+    pub fn observes_bank_holidays(&self) -> bool {
+        !self.bank_holidays_disregarded
+    }
+
+    /// Records a [`DayException`] for the `date` provided, replacing (and
+    /// returning) any exception previously recorded for that date.
+    pub fn add_exception(
+        &mut self,
+        date: NaiveDate,
+        exception: DayException,
+    ) -> Option<DayException> {
+        self.exceptions.insert(date, exception)
+    }
+
+    /// Removes (and returns) the [`DayException`] recorded for the `date`
+    /// provided.
+    pub fn remove_exception(&mut self, date: NaiveDate) -> Option<DayException> {
+        self.exceptions.remove(&date)
+    }
+
+    /// Retrieves the [`DayException`] recorded for the `date` provided.
+    pub fn exception(&self, date: NaiveDate) -> Option<&DayException> {
+        self.exceptions.get(&date)
+    }
+
+    /// Retrieves every [`DayException`] recorded for the `AcademicCalendar`
+    /// (in chronological order).
+    pub fn exceptions(&self) -> impl Iterator<Item = (NaiveDate, &DayException)> + '_ {
+        self.exceptions
+            .iter()
+            .map(|(date, exception)| (*date, exception))
+    }
+
+    /// Retrieves the [`BellSchedules`] of the schools using the calendar.
+    pub fn bell_schedules(&self) -> &BellSchedules {
+        &self.bell_schedules
+    }
+
+    /// Retrieves a mutable reference to the [`BellSchedules`] of the schools
+    /// using the calendar.
+    pub fn bell_schedules_mut(&mut self) -> &mut BellSchedules {
+        &mut self.bell_schedules
+    }
+
+    /// Retrieves the [`BellSchedules`] followed on the `date` provided (i.e.,
+    /// those of a [`DayException::Schedule`] recorded for the date, or else
+    /// those of the calendar).
+    pub fn bell_schedules_on(&self, date: NaiveDate) -> &BellSchedules {
+        match self.exception(date) {
+            Some(DayException::Schedule(schedules)) => schedules,
+            _ => &self.bell_schedules,
+        }
+    }
+
+    /// Whether any of the active days in the week containing `date` falls
+    /// within one of the calendar's [`Term`]s.
+    ///
+    /// # Remarks
+    ///
+    /// [Closures](DayException::Closed) are disregarded -- a week in which
+    /// the school is closed due to snow is still a part of the cycle.
+    pub fn is_school_week(&self, date: NaiveDate) -> bool {
+        let monday = monday_of(date);
+
+        (0..5).any(|offset| self.is_term_date(monday + Duration::days(offset)))
+    }
+
+    fn is_term_date(&self, date: NaiveDate) -> bool {
+        self.terms.iter().any(|term| term.contains(date))
+    }
+}
+
+/// The length of the cycle over which a timetable repeats.
+///
+/// Most schools run a two-week (i.e., an alternating week one and week two)
+/// timetable, however some run the same timetable every week, and others run
+/// a ten-day cycle which is counted in school days rather than in weeks.
+///
+/// *See [`WeekCycle::length`] for more information*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CycleLength {
+    /// The same timetable runs every week -- every week of the cycle is a
+    /// [`Week::WeekOne`] (and the entries of week two are never scheduled).
+    OneWeek,
+
+    /// The timetable alternates between [`Week::WeekOne`] and
+    /// [`Week::WeekTwo`] each week.
+    #[default]
+    TwoWeeks,
+
+    /// The timetable runs over ten *days* (day one being Monday of week one,
+    /// and day ten being Friday of week two), and the cycle only advances on
+    /// the days on which the timetable runs -- as a result, the timetabled
+    /// day of a date may differ from its day of the week (e.g., should the
+    /// school be closed on a Monday, the Tuesday is taught as the Monday).
+    TenDays,
+}
+
+impl CycleLength {
+    /// Retrieves the number of [`Week`]s of the timetable used by the cycle
+    /// (i.e., `1` for [`CycleLength::OneWeek`], and `2` otherwise).
+    pub fn weeks(self) -> usize {
+        match self {
+            Self::OneWeek => 1,
+            Self::TwoWeeks | Self::TenDays => 2,
+        }
+    }
+}
+
+/// Determines the [`Week`] of the two-week alternating timetable on which
+/// calendar dates fall.
+///
+/// A `WeekCycle` is anchored to a Monday which is known to be the start of
+/// [`Week::WeekOne`] -- the weeks then alternate from that anchor onwards
+/// (and backwards).
+///
+/// # Holidays
+///
+/// Schools disagree as to what happens to the cycle during a holiday: some
+/// schools continue to alternate the weeks during the holiday (such that the
+/// week after a one week holiday is the same as the week before it), whereas
+/// others pause the cycle (such that the week after a holiday is always the
+/// week following the last week of the previous term). By default, the cycle
+/// is paused during holidays -- this can be changed using
+/// [`Self::holidays_advance`].
+///
+/// A week is considered a holiday week if none of its active days fall within
+/// a [`Term`] -- a week containing at least one day of term (e.g., a week
+/// in which term starts on a Wednesday) is a part of the cycle.
+///
+/// # Cycle Lengths
+///
+/// By default, the weeks alternate between [`Week::WeekOne`] and
+/// [`Week::WeekTwo`] -- schools which run a one-week timetable, or a ten-day
+/// timetable, can change the [`CycleLength`] of the cycle using
+/// [`Self::length`]:
+///
+/// ```
+/// use chrono::{NaiveDate, Weekday};
+/// use timetableau::{AcademicCalendar, CycleLength, DayException, Term, Week, WeekCycle};
+///
+/// let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+/// let mut calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+/// calendar.add_exception(date(4), DayException::Closed);
+///
+/// let cycle = WeekCycle::new(date(4), calendar).unwrap().length(CycleLength::TenDays);
+///
+/// // The school was closed on the Monday, so the Tuesday is taught as day one
+/// assert_eq!(cycle.day_for(date(5)), Some((Week::WeekOne, Weekday::Mon)));
+/// assert_eq!(cycle.day_for(date(11)), Some((Week::WeekOne, Weekday::Fri)));
+/// assert_eq!(cycle.day_for(date(12)), Some((Week::WeekTwo, Weekday::Mon)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeekCycle {
+    anchor: NaiveDate,
+    calendar: AcademicCalendar,
+    holidays_advance: bool,
+    length: CycleLength,
+}
+
+impl WeekCycle {
+    /// Creates a new `WeekCycle`.
+    ///
+    /// # Errors
+    ///
+    /// [`TimetableError::AnchorNotMonday`] is returned should the `anchor`
+    /// provided not be a Monday.
+    ///
+    /// # Remarks
+    ///
+    /// The `anchor` **MUST** be the start of a week one -- if the `anchor`
+    /// is the start of a week two, every week produced by the cycle will be
+    /// the wrong way around.
+    pub fn new(anchor: NaiveDate, calendar: AcademicCalendar) -> Result<Self, TimetableError> {
+        if anchor.weekday() != Weekday::Mon {
+            return Err(TimetableError::AnchorNotMonday(anchor));
+        }
+
+        Ok(Self {
+            anchor,
+            calendar,
+            holidays_advance: false,
+            length: CycleLength::default(),
+        })
+    }
+
+    /// Sets whether holiday weeks advance the cycle.
+    ///
+    /// *See the [holiday documentation](WeekCycle#holidays) for more
+    /// information*.
+    pub fn holidays_advance(mut self, advance: bool) -> Self {
+        self.holidays_advance = advance;
+        self
+    }
+
+    /// Sets the [`CycleLength`] of the cycle (which defaults to
+    /// [`CycleLength::TwoWeeks`]).
+    ///
+    /// *See the [cycle length documentation](WeekCycle#cycle-lengths) for
+    /// more information*.
+    ///
+    /// # Remarks
+    ///
+    /// For a [`CycleLength::TenDays`] cycle, [holidays
+    /// advancing](Self::holidays_advance) the cycle causes every weekday
+    /// (rather than every school day) to advance the cycle.
+    pub fn length(mut self, length: CycleLength) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Retrieves the [`CycleLength`] of the cycle.
+    pub fn cycle_length(&self) -> CycleLength {
+        self.length
+    }
+
+    /// Retrieves the Monday from which the cycle is anchored.
+    pub fn anchor(&self) -> NaiveDate {
+        self.anchor
+    }
+
+    /// Retrieves the [`AcademicCalendar`] used by the cycle.
+    pub fn calendar(&self) -> &AcademicCalendar {
+        &self.calendar
+    }
+
+    /// Retrieves a mutable reference to the [`AcademicCalendar`] used by the
+    /// cycle (e.g., such that [`DayException`]s can be recorded).
+    pub fn calendar_mut(&mut self) -> &mut AcademicCalendar {
+        &mut self.calendar
+    }
+
+    /// Retrieves the [`Week`] on which the `date` provided falls.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if the week containing `date` is a part of the cycle, or
+    /// [`None`] if the week containing `date` is a holiday week.
+    ///
+    /// # Remarks
+    ///
+    /// Saturdays and Sundays belong to the week which precedes them (as each
+    /// week runs from Monday to Sunday) -- as a result, it is possible for
+    /// this function to return a week for a date on which the school is
+    /// closed. The exception is a [`CycleLength::TenDays`] cycle, in which
+    /// only the days on which the timetable runs have a week (*see
+    /// [`Self::day_for`]*).
+    pub fn week_for(&self, date: NaiveDate) -> Option<Week> {
+        if self.length == CycleLength::TenDays {
+            return self.day_for(date).map(|(week, _)| week);
+        }
+
+        if !self.calendar.is_school_week(date) {
+            return None;
+        }
+
+        if self.length == CycleLength::OneWeek {
+            return Some(Week::WeekOne);
+        }
+
+        let target = monday_of(date);
+
+        // The number of weeks into the cycle the target week is -- this can
+        // be negative should the date occur before the anchor
+        let offset = if self.holidays_advance {
+            (target - self.anchor).num_weeks()
+        } else {
+            // Only count the weeks which are a part of the cycle (i.e.,
+            // disregard any holiday weeks between the anchor and the target)
+            let (from, to, sign) = if target >= self.anchor {
+                (self.anchor, target, 1)
+            } else {
+                (target, self.anchor, -1)
+            };
+
+            let mut count = 0;
+            let mut monday = from;
+
+            while monday < to {
+                if self.calendar.is_school_week(monday) {
+                    count += 1;
+                }
+
+                monday += Duration::weeks(1);
+            }
+
+            count * sign
+        };
+
+        Some(if offset.rem_euclid(2) == 0 {
+            Week::WeekOne
+        } else {
+            Week::WeekTwo
+        })
+    }
+
+    /// Retrieves the [`Week`] and the day of the timetable on which the
+    /// `date` provided runs -- i.e., the [`TimeSlot`](crate::TimeSlot)s of
+    /// the timetable which take place on the date.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should the timetable not run on the `date` (e.g., during a
+    /// holiday, or at a weekend). For a cycle measured in weeks, the day is
+    /// always the day of the week of the `date` -- for a
+    /// [`CycleLength::TenDays`] cycle, it is the day of the cycle reached by
+    /// counting the school days since the anchor (*see the [cycle length
+    /// documentation](WeekCycle#cycle-lengths)*).
+    pub fn day_for(&self, date: NaiveDate) -> Option<(Week, Weekday)> {
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return None;
+        }
+
+        if self.length != CycleLength::TenDays {
+            return self.week_for(date).map(|week| (week, date.weekday()));
+        }
+
+        if !self.calendar.is_school_day(date) {
+            return None;
+        }
+
+        // Whether a day advances the cycle
+        let counts = |date: NaiveDate| {
+            if self.holidays_advance {
+                !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+            } else {
+                self.calendar.is_school_day(date)
+            }
+        };
+
+        // The number of days into the cycle the date is -- this can be
+        // negative should the date occur before the anchor
+        let (from, to, sign) = if date >= self.anchor {
+            (self.anchor, date, 1)
+        } else {
+            (date, self.anchor, -1)
+        };
+
+        let offset = from
+            .iter_days()
+            .take_while(|day| *day < to)
+            .filter(|day| counts(*day))
+            .count() as i64
+            * sign;
+        let day = offset.rem_euclid(10) as u8;
+
+        Some((
+            if day < 5 {
+                Week::WeekOne
+            } else {
+                Week::WeekTwo
+            },
+            Weekday::from_u8(day % 5).unwrap(),
+        ))
+    }
+}
+
+/// Retrieves the Monday of the week containing `date`.
+pub(crate) fn monday_of(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    // Autumn half-term one (with a one week half-term holiday) followed by
+    // autumn half-term two
+    fn cycle() -> WeekCycle {
+        let calendar = AcademicCalendar::new(vec![
+            Term::new(date(2023, 10, 30), date(2023, 12, 20)).unwrap(),
+            Term::new(date(2023, 9, 6), date(2023, 10, 20)).unwrap(),
+        ]);
+
+        WeekCycle::new(date(2023, 9, 4), calendar).unwrap()
+    }
+
+    #[test]
+    fn week_cycle_invalid_anchor() {
+        assert_eq!(
+            WeekCycle::new(date(2023, 9, 5), AcademicCalendar::default()),
+            Err(TimetableError::AnchorNotMonday(date(2023, 9, 5)))
+        );
+    }
+
+    #[test]
+    fn week_cycle_within_term() {
+        let cycle = cycle();
+
+        // First week of term starts on a Wednesday
+        assert_eq!(cycle.week_for(date(2023, 9, 6)), Some(Week::WeekOne));
+        assert_eq!(cycle.week_for(date(2023, 9, 15)), Some(Week::WeekTwo));
+        assert_eq!(cycle.week_for(date(2023, 10, 20)), Some(Week::WeekOne));
+    }
+
+    #[test]
+    fn week_cycle_holiday() {
+        let cycle = cycle();
+
+        assert_eq!(cycle.week_for(date(2023, 10, 25)), None);
+        assert_eq!(cycle.week_for(date(2023, 8, 28)), None);
+    }
+
+    #[test]
+    fn week_cycle_holiday_paused() {
+        // The last week before half-term is a week one, so the first week
+        // after half-term must be a week two
+        assert_eq!(cycle().week_for(date(2023, 10, 30)), Some(Week::WeekTwo));
+    }
+
+    #[test]
+    fn week_cycle_holiday_advances() {
+        let cycle = cycle().holidays_advance(true);
+
+        assert_eq!(cycle.week_for(date(2023, 10, 30)), Some(Week::WeekOne));
+    }
+
+    #[test]
+    fn week_cycle_lengths() {
+        let weekly = cycle().length(CycleLength::OneWeek);
+
+        assert_eq!(weekly.week_for(date(2023, 9, 15)), Some(Week::WeekOne));
+        assert_eq!(weekly.week_for(date(2023, 10, 25)), None);
+        assert_eq!(
+            weekly.day_for(date(2023, 9, 13)),
+            Some((Week::WeekOne, Weekday::Wed))
+        );
+
+        // Term starts on a Wednesday, so the Wednesday is taught as day one
+        let mut days = cycle().length(CycleLength::TenDays);
+        assert_eq!(days.day_for(date(2023, 9, 4)), None);
+        assert_eq!(days.day_for(date(2023, 9, 9)), None);
+        assert_eq!(
+            days.day_for(date(2023, 9, 6)),
+            Some((Week::WeekOne, Weekday::Mon))
+        );
+        assert_eq!(
+            days.day_for(date(2023, 9, 13)),
+            Some((Week::WeekTwo, Weekday::Mon))
+        );
+        assert_eq!(days.week_for(date(2023, 9, 13)), Some(Week::WeekTwo));
+
+        // Closures do not advance the cycle
+        days.calendar_mut()
+            .add_exception(date(2023, 9, 13), DayException::Closed);
+        assert_eq!(days.week_for(date(2023, 9, 13)), None);
+        assert_eq!(
+            days.day_for(date(2023, 9, 14)),
+            Some((Week::WeekTwo, Weekday::Mon))
+        );
+
+        // Unless every weekday advances the cycle
+        let days = days.holidays_advance(true);
+        assert_eq!(
+            days.day_for(date(2023, 9, 14)),
+            Some((Week::WeekTwo, Weekday::Thu))
+        );
+    }
+
+    #[test]
+    fn school_day_bank_holidays() {
+        let mut calendar =
+            AcademicCalendar::new(vec![
+                Term::new(date(2024, 4, 15), date(2024, 5, 24)).unwrap()
+            ]);
+
+        // The early May bank holiday falls within the term
+        assert!(!calendar.is_school_day(date(2024, 5, 6)));
+        assert!(calendar.is_bank_holiday(date(2024, 5, 6)));
+        assert!(calendar.is_school_day(date(2024, 5, 7)));
+        assert!(calendar.is_school_week(date(2024, 5, 6)));
+
+        calendar.add_exception(date(2024, 5, 6), DayException::Open);
+        assert!(calendar.is_school_day(date(2024, 5, 6)));
+
+        calendar.remove_exception(date(2024, 5, 6));
+        calendar.observe_bank_holidays(false);
+        assert!(!calendar.observes_bank_holidays());
+        assert!(calendar.is_school_day(date(2024, 5, 6)));
+    }
+
+    #[test]
+    fn school_day() {
+        let calendar = cycle().calendar().clone();
+
+        assert!(calendar.is_school_day(date(2023, 9, 6)));
+        assert!(!calendar.is_school_day(date(2023, 9, 9)));
+        assert!(!calendar.is_school_day(date(2023, 9, 5)));
+    }
+}
@@ -0,0 +1,512 @@
+//! Temporary closures of rooms (e.g., a flooded laboratory) and maintenance
+//! windows covering whole areas of a school (e.g., building works on a floor
+//! of a Highfield block).
+//!
+//! A [`Closure`] marks the rooms within its [`ClosureScope`] as unavailable
+//! for a range of dates. [`Closures`] surfaces the lessons which are
+//! scheduled in a closed room, lists the rooms which remain free, and
+//! proposes a room to which each affected lesson can be moved:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::closures::{Closure, ClosureScope, Closures};
+//! use timetableau::{
+//!     timeslot, AcademicCalendar, Activity, Class, HighfieldBlock, HighfieldFloor, Subject,
+//!     RangedU8, Term, Timetable, WeekCycle,
+//! };
+//!
+//! let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+//! let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+//! let cycle = WeekCycle::new(date(4), calendar).unwrap();
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(
+//!     timeslot!(W1MP1),
+//!     Activity::Lesson {
+//!         subject: Subject::new("Ma".to_string()).unwrap(),
+//!         class: Class::new("10A".to_string()).unwrap(),
+//!         teacher: None,
+//!         location: "H203".parse().unwrap(),
+//!     },
+//! );
+//!
+//! let mut closures = Closures::new();
+//! closures.add(Closure {
+//!     scope: ClosureScope::Floor(HighfieldBlock::Howard, HighfieldFloor::Level(RangedU8::new(2).unwrap())),
+//!     start: date(4),
+//!     end: date(8),
+//!     reason: "Roof repairs".to_string(),
+//! });
+//!
+//! let rooms = ["H204", "H101"].map(|code| code.parse().unwrap());
+//! let rehousings = closures.rehouse(&timetable, &cycle, date(4)..=date(8), rooms);
+//!
+//! assert_eq!(rehousings.len(), 1);
+//! assert_eq!(rehousings[0].to, Some("H101".parse().unwrap()));
+//! ```
+//!
+//! Rooms may also be in use without appearing on the timetable (e.g., an
+//! impromptu meeting) -- when live data is available from a sensor or
+//! booking system implementing [`Occupancy`], the rooms which are free on the
+//! timetable but actually occupied can be excluded using
+//! [`Closures::free_rooms_live`].
+
+use crate::{
+    Activity, FearnhillSection, HighfieldBlock, HighfieldFloor, Location, Occurrence, TimeSlot,
+    Timetable, WeekCycle,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// A source of live occupancy data (e.g., the occupancy sensors of rooms, or
+/// the booking system of a school).
+///
+/// *See the [module](self) documentation for more information*.
+pub trait Occupancy {
+    /// Determines whether the `location` provided is occupied at the time
+    /// provided.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should the source have no data for the room at that time
+    /// (e.g., the room has no sensor), in which case the timetable alone
+    /// determines whether the room is free.
+    fn is_occupied(&self, location: &Location, at: NaiveDateTime) -> Option<bool>;
+}
+
+/// An [`Occupancy`] without any data (i.e., the timetable alone determines
+/// whether each room is free).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoOccupancyData;
+
+impl Occupancy for NoOccupancyData {
+    fn is_occupied(&self, _: &Location, _: NaiveDateTime) -> Option<bool> {
+        None
+    }
+}
+
+// The latest reading of each room, regardless of the time at which it was
+// taken
+impl Occupancy for HashMap<Location, bool> {
+    fn is_occupied(&self, location: &Location, _: NaiveDateTime) -> Option<bool> {
+        self.get(location).copied()
+    }
+}
+
+/// The rooms which are closed by a [`Closure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClosureScope {
+    /// A single room (e.g., a flooded laboratory).
+    Room(Location),
+
+    /// Every room of a Highfield block.
+    Block(HighfieldBlock),
+
+    /// Every room on a floor of a Highfield block.
+    Floor(HighfieldBlock, HighfieldFloor),
+
+    /// Every room of a Fearnhill section.
+    Section(FearnhillSection),
+}
+
+impl ClosureScope {
+    /// Determines whether the `location` provided is within the
+    /// `ClosureScope`.
+    pub fn contains(&self, location: &Location) -> bool {
+        let parts = location.parts();
+
+        match self {
+            Self::Room(room) => room == location,
+            Self::Block(block) => parts.block == Some(*block),
+            Self::Floor(block, floor) => parts.block == Some(*block) && parts.floor == Some(*floor),
+            Self::Section(section) => parts.section == Some(*section),
+        }
+    }
+}
+
+/// A period of time during which a set of rooms is unavailable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Closure {
+    /// The rooms which are closed.
+    pub scope: ClosureScope,
+
+    /// The first date on which the rooms are closed.
+    pub start: NaiveDate,
+
+    /// The last date on which the rooms are closed.
+    pub end: NaiveDate,
+
+    /// The reason for which the rooms are closed (e.g., `Flooding`).
+    pub reason: String,
+}
+
+impl Closure {
+    /// Determines whether the `Closure` closes the `location` provided on the
+    /// `date` provided.
+    pub fn closes(&self, location: &Location, date: NaiveDate) -> bool {
+        date >= self.start && date <= self.end && self.scope.contains(location)
+    }
+}
+
+/// A lesson scheduled in a room which is closed on the date on which it
+/// takes place.
+///
+/// *See [`Closures::affected`] for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Affected {
+    /// The occurrence of the lesson.
+    pub occurrence: Occurrence,
+
+    /// The closed room in which the lesson is scheduled.
+    pub location: Location,
+
+    /// The index of the [`Closure`] which closes the room (within
+    /// [`Closures::closures`]).
+    pub closure: usize,
+}
+
+/// A proposal to move a lesson out of a closed room.
+///
+/// *See [`Closures::rehouse`] for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rehousing {
+    /// The lesson which is affected by a closure.
+    pub affected: Affected,
+
+    /// The free room nearest to the closed room, or [`None`] should no
+    /// candidate room be free.
+    pub to: Option<Location>,
+}
+
+/// A collection of [`Closure`]s.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Closures {
+    closures: Vec<Closure>,
+}
+
+impl Closures {
+    /// Creates a new `Closures` without any closures.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `closure` provided.
+    ///
+    /// # Returns
+    ///
+    /// The index of the closure (within [`Self::closures`]).
+    pub fn add(&mut self, closure: Closure) -> usize {
+        self.closures.push(closure);
+        self.closures.len() - 1
+    }
+
+    /// Removes (and returns) the closure at position `index` of
+    /// [`Self::closures`] (e.g., should building works finish early).
+    pub fn remove(&mut self, index: usize) -> Option<Closure> {
+        (index < self.closures.len()).then(|| self.closures.remove(index))
+    }
+
+    /// Retrieves every closure (in the order in which they were added).
+    pub fn closures(&self) -> &[Closure] {
+        &self.closures
+    }
+
+    /// Retrieves the index of the first closure which closes the `location`
+    /// provided on the `date` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the room is available on that date.
+    pub fn closure_of(&self, location: &Location, date: NaiveDate) -> Option<usize> {
+        self.closures
+            .iter()
+            .position(|closure| closure.closes(location, date))
+    }
+
+    /// Determines whether the `location` provided is closed on the `date`
+    /// provided.
+    pub fn is_closed(&self, location: &Location, date: NaiveDate) -> bool {
+        self.closure_of(location, date).is_some()
+    }
+
+    /// Finds every lesson of the `timetable` taking place within the range
+    /// of `dates` provided whose room is closed on the date on which it
+    /// takes place (in chronological order).
+    ///
+    /// # Remarks
+    ///
+    /// The room of each lesson is the room in which it actually takes place
+    /// (i.e., after any [`RoomOverride`](crate::RoomOverride) has been
+    /// applied) -- a lesson which has already been moved out of a closed
+    /// room is not affected.
+    pub fn affected(
+        &self,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        dates: RangeInclusive<NaiveDate>,
+    ) -> Vec<Affected> {
+        let mut affected = Vec::new();
+
+        for date in dates
+            .start()
+            .iter_days()
+            .take_while(|date| date <= dates.end())
+        {
+            for occurrence in timetable.occurrences(cycle, date) {
+                let Activity::Lesson { location, .. } = occurrence.activity else {
+                    continue;
+                };
+
+                if let Some(closure) = self.closure_of(&location, date) {
+                    affected.push(Affected {
+                        occurrence,
+                        location,
+                        closure,
+                    });
+                }
+            }
+        }
+
+        affected
+    }
+
+    /// Retrieves each of the `candidates` which is neither closed nor in use
+    /// by a lesson of the `timetable` during the `slot` provided on the
+    /// `date` provided (in the order of the candidates).
+    pub fn free_rooms<I>(
+        &self,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        date: NaiveDate,
+        slot: TimeSlot,
+        candidates: I,
+    ) -> Vec<Location>
+    where
+        I: IntoIterator<Item = Location>,
+    {
+        self.free_rooms_live(timetable, cycle, date, slot, candidates, &NoOccupancyData)
+    }
+
+    /// Retrieves each of the `candidates` which is [free](Self::free_rooms)
+    /// during the `slot` provided on the `date` provided, and which the
+    /// `occupancy` data does not report as occupied at the start of the
+    /// slot (in the order of the candidates).
+    ///
+    /// # Remarks
+    ///
+    /// The start of the slot is determined by the bell schedule of the
+    /// school of each candidate (*see [`AcademicCalendar::bell_schedules`](
+    /// crate::AcademicCalendar::bell_schedules)*). Candidates for which the
+    /// `occupancy` data has no reading are free should they be free on the
+    /// timetable.
+    pub fn free_rooms_live<I, O>(
+        &self,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        date: NaiveDate,
+        slot: TimeSlot,
+        candidates: I,
+        occupancy: &O,
+    ) -> Vec<Location>
+    where
+        I: IntoIterator<Item = Location>,
+        O: Occupancy + ?Sized,
+    {
+        let used: Vec<Location> = timetable
+            .occurrences(cycle, date)
+            .into_iter()
+            .filter(|occurrence| occurrence.slot == slot)
+            .flat_map(|occurrence| {
+                // The rooms across which a split lesson's class is split
+                let split = occurrence
+                    .entry
+                    .and_then(|id| timetable.get(id))
+                    .map_or(&[][..], |entry| &entry.split_rooms);
+
+                occurrence
+                    .activity
+                    .location()
+                    .copied()
+                    .into_iter()
+                    .chain(split.iter().copied())
+            })
+            .collect();
+
+        let schedules = cycle.calendar().bell_schedules_on(date);
+
+        candidates
+            .into_iter()
+            .filter(|location| !used.contains(location) && !self.is_closed(location, date))
+            .filter(|location| {
+                let start = schedules.get(location.school()).start(slot.period);
+                occupancy.is_occupied(location, date.and_time(start.into())) != Some(true)
+            })
+            .collect()
+    }
+
+    /// Proposes a room for each lesson [affected](Self::affected) by a
+    /// closure within the range of `dates` provided: the
+    /// [nearest](Location::nearest) of the `candidates` which is
+    /// [free](Self::free_rooms) on the date of the lesson, and which has not
+    /// been proposed for another affected lesson taking place at the same
+    /// time.
+    ///
+    /// # Remarks
+    ///
+    /// The proposals are not applied to the timetable -- a
+    /// [`RoomOverride`](crate::RoomOverride) moves every lesson of its
+    /// timeslot, so the proposals are intended to be reviewed (e.g., by a
+    /// cover manager) before the affected lessons are moved.
+    pub fn rehouse<I>(
+        &self,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        dates: RangeInclusive<NaiveDate>,
+        candidates: I,
+    ) -> Vec<Rehousing>
+    where
+        I: IntoIterator<Item = Location>,
+    {
+        let candidates: Vec<Location> = candidates.into_iter().collect();
+        let mut proposed: Vec<(NaiveDate, TimeSlot, Location)> = Vec::new();
+
+        self.affected(timetable, cycle, dates)
+            .into_iter()
+            .map(|affected| {
+                let date = affected.occurrence.date;
+                let slot = affected.occurrence.slot;
+
+                let free = self
+                    .free_rooms(timetable, cycle, date, slot, candidates.iter().copied())
+                    .into_iter()
+                    .filter(|location| !proposed.contains(&(date, slot, *location)));
+                let to = affected.location.nearest(free);
+
+                if let Some(to) = to {
+                    proposed.push((date, slot, to));
+                }
+
+                Rehousing { affected, to }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        timeslot, AcademicCalendar, BellSchedule, Class, Period, RangedU8, School, Subject, Term,
+        TimeOfDay,
+    };
+
+    fn lesson(class: &str, room: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Ch".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: None,
+            location: room.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn closures_affect_and_rehouse_lessons() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+        let cycle = WeekCycle::new(date(4), calendar).unwrap();
+        let room = |code: &str| code.parse::<Location>().unwrap();
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("10A", "FH S1"));
+        timetable.insert(timeslot!(W1MP1), lesson("10B", "FH S2"));
+        timetable.insert(timeslot!(W1MP1), lesson("10C", "FH S4"));
+        timetable.insert(timeslot!(W1TP1), lesson("10A", "FH S1"));
+
+        let mut closures = Closures::new();
+        closures.add(Closure {
+            scope: ClosureScope::Room(room("FH S1")),
+            start: date(4),
+            end: date(4),
+            reason: "Flooding".to_string(),
+        });
+        let works = closures.add(Closure {
+            scope: ClosureScope::Floor(
+                HighfieldBlock::Howard,
+                HighfieldFloor::Level(RangedU8::new(1).unwrap()),
+            ),
+            start: date(4),
+            end: date(15),
+            reason: "Building works".to_string(),
+        });
+
+        assert!(closures.is_closed(&room("H101"), date(15)));
+        assert!(!closures.is_closed(&room("H201"), date(15)));
+        assert!(!closures.is_closed(&room("FH S1"), date(5)));
+
+        let affected = closures.affected(&timetable, &cycle, date(4)..=date(8));
+
+        assert_eq!(affected.len(), 1);
+        assert_eq!(affected[0].location, room("FH S1"));
+
+        let candidates = ["H101", "FH S4", "FH S3", "FH S2"].map(room);
+
+        assert_eq!(
+            closures.free_rooms(&timetable, &cycle, date(4), timeslot!(W1MP1), candidates),
+            [room("FH S3")]
+        );
+
+        timetable.insert(timeslot!(W1MP1), lesson("10D", "FH S1"));
+        let rehousings = closures.rehouse(&timetable, &cycle, date(4)..=date(4), candidates);
+
+        assert_eq!(
+            rehousings.iter().map(|r| r.to).collect::<Vec<_>>(),
+            [Some(room("FH S3")), None]
+        );
+        assert_eq!(closures.remove(works).unwrap().reason, "Building works");
+        assert!(closures.remove(works).is_none());
+    }
+
+    #[test]
+    fn closures_exclude_occupied_rooms() {
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date, date).unwrap()]);
+        let cycle = WeekCycle::new(date, calendar).unwrap();
+        let rooms = ["H101", "H102", "FH S1"].map(|code| code.parse::<Location>().unwrap());
+
+        struct Sensors;
+
+        impl Occupancy for Sensors {
+            fn is_occupied(&self, location: &Location, at: NaiveDateTime) -> Option<bool> {
+                // Highfield's first period starts 5 minutes after Fearnhill's
+                let expected = BellSchedule::for_school(location.school()).start(Period::First);
+                assert_eq!(TimeOfDay::from(at.time()), expected);
+
+                (location.school() == School::Fearnhill).then_some(true)
+            }
+        }
+
+        let mut readings = HashMap::new();
+        readings.insert(rooms[0], true);
+        readings.insert(rooms[1], false);
+
+        let timetable = Timetable::new();
+        let closures = Closures::new();
+        let slot = timeslot!(W1MP1);
+
+        assert_eq!(
+            closures.free_rooms_live(&timetable, &cycle, date, slot, rooms, &readings),
+            [rooms[1], rooms[2]]
+        );
+        assert_eq!(
+            closures.free_rooms_live(&timetable, &cycle, date, slot, rooms, &Sensors),
+            [rooms[0], rooms[1]]
+        );
+        assert_eq!(
+            closures.free_rooms(&timetable, &cycle, date, slot, rooms),
+            rooms
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! Scheduling of clubs (and other extracurricular activities) which recur
+//! at lunchtime or after school.
+//!
+//! A [`Club`] meets in a venue, is led by a member of staff, and recurs on
+//! the same day of every week (or of every other week) of a [`Term`].
+//! Clubs are collected into [`Clubs`], which refuses clubs sharing a venue or
+//! a lead with another club meeting at the same time, and which detects
+//! clashes with [after-school bookings](AfterSchoolTimetable):
+//!
+//! ```
+//! use chrono::{NaiveDate, Weekday};
+//! use timetableau::clubs::{Club, Clubs, Session};
+//! use timetableau::{KeyStage, Teacher, Term};
+//!
+//! let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+//! let chess = Club {
+//!     name: "Chess Club".to_string(),
+//!     venue: "H201".parse().unwrap(),
+//!     lead: Teacher::new("ABC".to_string()).unwrap(),
+//!     years: KeyStage::Three.first()..=KeyStage::Three.last(),
+//!     session: Session::Lunch,
+//!     day: Weekday::Tue,
+//!     week: None,
+//!     term: Term::new(date(4), date(29)).unwrap(),
+//! };
+//!
+//! let mut clubs = Clubs::new();
+//!
+//! assert_eq!(clubs.add(chess.clone()), Ok(0));
+//! assert!(clubs.add(chess).is_err());
+//! ```
+//!
+//! Clubs can be exported alongside lessons using
+//! [`IcsExporter::export_clubs`](crate::export::ics::IcsExporter::export_clubs).
+
+use crate::{
+    AfterSchoolBells, AfterSchoolTimetable, Location, Teacher, Term, Week, WeekCycle, YearGroup,
+};
+use chrono::prelude::*;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// The part of the school day during which a [`Club`] meets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Session {
+    /// The club meets at lunch (*see
+    /// [`BellSchedule::lunch`](crate::BellSchedule::lunch)*).
+    Lunch,
+
+    /// The club meets during the after-school session (*see
+    /// [`AfterSchoolSlot`](crate::AfterSchoolSlot)*).
+    AfterSchool,
+}
+
+/// A recurring lunchtime or after-school activity.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Club {
+    /// The name of the club (e.g., `Chess Club`).
+    pub name: String,
+
+    /// The room in which the club meets.
+    pub venue: Location,
+
+    /// The member of staff who leads the club.
+    pub lead: Teacher,
+
+    /// The year groups whose students may attend the club.
+    pub years: RangeInclusive<YearGroup>,
+
+    /// The part of the day during which the club meets.
+    pub session: Session,
+
+    /// The day of the week on which the club meets.
+    pub day: Weekday,
+
+    /// The week of the cycle during which the club meets, or [`None`] should
+    /// it meet during both weeks.
+    pub week: Option<Week>,
+
+    /// The term during which the club meets.
+    pub term: Term,
+}
+
+impl Club {
+    /// Whether students of the `year` group provided may attend the `Club`.
+    pub fn is_eligible(&self, year: YearGroup) -> bool {
+        self.years.contains(&year)
+    }
+
+    /// Whether the `Club` meets on the `date` provided -- i.e., the date is
+    /// a school day of its term, falls on its day of the week, and falls in
+    /// its week of the `cycle`.
+    pub fn meets_on(&self, cycle: &WeekCycle, date: NaiveDate) -> bool {
+        self.term.contains(date)
+            && date.weekday() == self.day
+            && cycle.calendar().is_school_day(date)
+            && cycle
+                .week_for(date)
+                .is_some_and(|week| self.week.is_none_or(|own| own == week))
+    }
+
+    /// Retrieves every date on which the `Club` meets (in chronological
+    /// order).
+    pub fn dates<'a>(&'a self, cycle: &'a WeekCycle) -> impl Iterator<Item = NaiveDate> + 'a {
+        self.term
+            .start()
+            .iter_days()
+            .take_while(|date| *date <= self.term.end())
+            .filter(|date| self.meets_on(cycle, *date))
+    }
+
+    /// Retrieves the `(start, end)` times of the `Club` -- lunch (or the
+    /// after-school session) at the school of its venue.
+    pub fn times(&self, cycle: &WeekCycle, bells: &AfterSchoolBells) -> (NaiveTime, NaiveTime) {
+        let school = self.venue.school();
+
+        match self.session {
+            Session::Lunch => cycle
+                .calendar()
+                .bell_schedules()
+                .get(school)
+                .lunch()
+                .to_naive(),
+            Session::AfterSchool => bells.get(school),
+        }
+    }
+
+    // Whether the club could meet at the same time as the other club
+    fn overlaps(&self, other: &Club) -> bool {
+        self.session == other.session
+            && self.day == other.day
+            && (self.week.is_none() || other.week.is_none() || self.week == other.week)
+            && self.term.start() <= other.term.end()
+            && other.term.start() <= self.term.end()
+    }
+}
+
+/// A clash between a [`Club`] and another use of its venue or its lead.
+///
+/// Clubs are identified by their index within [`Clubs::clubs`], and bookings
+/// by their index within [`AfterSchoolTimetable::bookings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClubConflict {
+    /// The club shares its venue or its lead with another club meeting at
+    /// the same time.
+    Club {
+        /// The index of the club (or, should it have been refused, the index
+        /// it would have been given).
+        club: usize,
+
+        /// The index of the other club.
+        other: usize,
+    },
+
+    /// The club shares its venue or its lead with an after-school booking.
+    Booking {
+        /// The index of the club.
+        club: usize,
+
+        /// The index of the booking.
+        booking: usize,
+    },
+}
+
+impl Display for ClubConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Club { club, other } => write!(
+                f,
+                "club {} shares a venue or lead with club {} at the same time",
+                club, other
+            ),
+            Self::Booking { club, booking } => write!(
+                f,
+                "club {} shares a venue or lead with after-school booking {}",
+                club, booking
+            ),
+        }
+    }
+}
+
+impl Error for ClubConflict {}
+
+/// The [`Club`]s of a school.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Clubs {
+    clubs: Vec<Club>,
+}
+
+impl Clubs {
+    /// Creates a new `Clubs` with no clubs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `club` provided, returning its index within [`Self::clubs`].
+    ///
+    /// # Errors
+    ///
+    /// A [`ClubConflict::Club`] is returned (and the club is not added)
+    /// should another club with the same venue or the same lead meet at the
+    /// same time.
+    pub fn add(&mut self, club: Club) -> Result<usize, ClubConflict> {
+        if let Some(other) = self.clubs.iter().position(|other| {
+            other.overlaps(&club) && (other.venue == club.venue || other.lead == club.lead)
+        }) {
+            return Err(ClubConflict::Club {
+                club: self.clubs.len(),
+                other,
+            });
+        }
+
+        self.clubs.push(club);
+
+        Ok(self.clubs.len() - 1)
+    }
+
+    /// Removes the club at the `index` provided, returning it.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if there is no club at the `index` provided -- the indexes
+    /// of later clubs are decremented.
+    pub fn remove(&mut self, index: usize) -> Option<Club> {
+        (index < self.clubs.len()).then(|| self.clubs.remove(index))
+    }
+
+    /// Retrieves every club (in the order in which they were added).
+    pub fn clubs(&self) -> &[Club] {
+        &self.clubs
+    }
+
+    /// Retrieves the clubs (and their indexes) which students of the `year`
+    /// group provided may attend.
+    pub fn for_year(&self, year: YearGroup) -> impl Iterator<Item = (usize, &Club)> + '_ {
+        self.clubs
+            .iter()
+            .enumerate()
+            .filter(move |(_, club)| club.is_eligible(year))
+    }
+
+    /// Finds every clash between an after-school club and the `bookings`
+    /// provided (e.g., a detention booked into the venue of a club).
+    pub fn conflicts(&self, bookings: &AfterSchoolTimetable) -> Vec<ClubConflict> {
+        let mut conflicts = Vec::new();
+
+        for (club, details) in self.clubs.iter().enumerate() {
+            if details.session != Session::AfterSchool {
+                continue;
+            }
+
+            for (booking, booked) in bookings.bookings().iter().enumerate() {
+                if booked.slot.day == details.day
+                    && details.week.is_none_or(|week| week == booked.slot.week)
+                    && (booked.location == details.venue
+                        || booked.teacher.as_ref() == Some(&details.lead))
+                {
+                    conflicts.push(ClubConflict::Booking { club, booking });
+                }
+            }
+        }
+
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        timeslot, AcademicCalendar, AfterSchoolBooking, AfterSchoolEvent, AfterSchoolSlot,
+    };
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 9, day).unwrap()
+    }
+
+    fn club(venue: &str, lead: &str, session: Session, week: Option<Week>) -> Club {
+        Club {
+            name: "Drama Club".to_string(),
+            venue: venue.parse().unwrap(),
+            lead: Teacher::new(lead.to_string()).unwrap(),
+            years: YearGroup::new(7).unwrap()..=YearGroup::new(9).unwrap(),
+            session,
+            day: Weekday::Mon,
+            week,
+            term: Term::new(date(4), date(29)).unwrap(),
+        }
+    }
+
+    #[test]
+    fn club_recurrence_and_conflicts() {
+        let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+        let cycle = WeekCycle::new(date(4), calendar).unwrap();
+
+        let mut clubs = Clubs::new();
+        let fortnightly = club("H201", "ABC", Session::AfterSchool, Some(Week::WeekTwo));
+
+        assert_eq!(clubs.add(fortnightly.clone()), Ok(0));
+        assert_eq!(
+            fortnightly.dates(&cycle).collect::<Vec<_>>(),
+            [date(11), date(25)]
+        );
+        assert_eq!(
+            fortnightly.times(&cycle, &AfterSchoolBells::default()).0,
+            NaiveTime::from_hms_opt(15, 5, 0).unwrap()
+        );
+
+        // Clubs sharing a venue or a lead clash only when meeting together
+        assert_eq!(
+            clubs.add(club("H202", "ABC", Session::AfterSchool, None)),
+            Err(ClubConflict::Club { club: 1, other: 0 })
+        );
+        assert_eq!(
+            clubs.add(club(
+                "H201",
+                "DEF",
+                Session::AfterSchool,
+                Some(Week::WeekOne)
+            )),
+            Ok(1)
+        );
+        assert_eq!(clubs.add(club("H201", "ABC", Session::Lunch, None)), Ok(2));
+        assert_eq!(clubs.for_year(YearGroup::new(10).unwrap()).count(), 0);
+
+        let mut bookings = AfterSchoolTimetable::new();
+        bookings
+            .book(AfterSchoolBooking {
+                slot: AfterSchoolSlot::after(timeslot!(W2MP1)),
+                event: AfterSchoolEvent::Detention,
+                teacher: None,
+                location: "H201".parse().unwrap(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            clubs.conflicts(&bookings),
+            [ClubConflict::Booking {
+                club: 0,
+                booking: 0
+            }]
+        );
+    }
+}
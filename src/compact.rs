@@ -0,0 +1,446 @@
+use crate::{
+    Activity, Change, Class, Entry, EntryId, Interner, Location, RangeError, RangedU8,
+    RoomOverride, Rotation, RotationStage, Subject, Teacher, TimeSlot, Timetable,
+};
+use chrono::Duration;
+use std::mem;
+
+// The index of an interned string which is not present (e.g., the teacher of
+// a lesson whose teacher is unknown)
+const NONE: u16 = u16::MAX;
+
+// The kinds of a compact entry's activity
+const LESSON: u8 = 0;
+const REGISTRATION: u8 = 1;
+const BREAK: u8 = 2;
+const SCHOOL_STUDY: u8 = 3;
+const HOME_STUDY: u8 = 4;
+const MISCELLANEOUS: u8 = 5;
+const TRANSIT: u8 = 6;
+
+// The bits of a compact entry's kind which store the kind of its activity
+// (the remaining bits store the number of periods it spans, less one)
+const KIND: u8 = 0x0F;
+const SPAN_SHIFT: u8 = 4;
+
+/// A memory-compact representation of a [`Timetable`] (e.g., for embedding
+/// the timetable of an entire school within a WebAssembly bundle).
+///
+/// Each entry of a `CompactTimetable` occupies 16 bytes: its location is
+/// stored as its packed `u16` code (*see [`Location`]'s `From` implementation
+/// for `u16`*), and the names of its subject, class, and teacher are
+/// interned (i.e., each distinct name is only stored once). A whole-school
+/// timetable therefore occupies an order of magnitude less memory than the
+/// equivalent `Timetable` (*see the `memory` benchmark*). The co-teachers,
+/// split rooms, and rotations of the (comparatively rare) team-taught,
+/// split, and rotating lessons are stored separately.
+///
+/// A `CompactTimetable` cannot be modified or queried -- it is converted to
+/// and from the ergonomic representation:
+///
+/// ```
+/// use timetableau::{timeslot, Activity, Class, CompactTimetable, Subject, Timetable};
+///
+/// let mut timetable = Timetable::new();
+///
+/// timetable.insert(
+///     timeslot!(W1MP1),
+///     Activity::Lesson {
+///         subject: Subject::new("Ma".to_string()).unwrap(),
+///         class: Class::new("10A".to_string()).unwrap(),
+///         teacher: None,
+///         location: "H201".parse().unwrap(),
+///     },
+/// );
+///
+/// let compact = CompactTimetable::try_from(&timetable).unwrap();
+///
+/// assert_eq!(Timetable::from(&compact), timetable);
+/// ```
+///
+/// # Remarks
+///
+/// The entries retain their [`EntryId`]s, and the
+/// [room overrides](Timetable::override_room) of the timetable are retained
+/// -- its [history](Timetable::history) and author, however, are not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactTimetable {
+    entries: Vec<CompactEntry>,
+    strings: Vec<Box<str>>,
+    overrides: Vec<RoomOverride>,
+    next_id: u32,
+    unpacked: Vec<(u32, Location)>,
+    staffed: Vec<CompactStaffing>,
+}
+
+// The co-teachers (as interned staff codes), split rooms, and rotation of a
+// compact entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CompactStaffing {
+    id: u32,
+    co_teachers: Box<[u16]>,
+    split_rooms: Box<[Location]>,
+    rotation: Option<Box<Rotation>>,
+}
+
+// An entry of a compact timetable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CompactEntry {
+    id: u32,
+    slot: u8,
+    kind: u8,
+    location: u16,
+    subject: u16,
+    class: u16,
+    teacher: u16,
+}
+
+impl CompactTimetable {
+    /// The number of entries in the `CompactTimetable`.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the `CompactTimetable` has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Estimates the number of bytes of memory occupied by the
+    /// `CompactTimetable` (including the memory it allocates).
+    pub fn memory_usage(&self) -> usize {
+        let strings: usize = self.strings.iter().map(|string| string.len()).sum();
+
+        mem::size_of::<Self>()
+            + self.entries.capacity() * mem::size_of::<CompactEntry>()
+            + self.strings.capacity() * mem::size_of::<Box<str>>()
+            + strings
+            + self.overrides.capacity() * mem::size_of::<RoomOverride>()
+            + self.unpacked.capacity() * mem::size_of::<(u32, Location)>()
+            + self
+                .staffed
+                .iter()
+                .map(|staffing| {
+                    mem::size_of::<CompactStaffing>()
+                        + staffing.co_teachers.len() * mem::size_of::<u16>()
+                        + staffing.split_rooms.len() * mem::size_of::<Location>()
+                        + staffing.rotation.as_ref().map_or(0, |rotation| {
+                            mem::size_of::<Rotation>()
+                                + rotation.stages.len() * mem::size_of::<RotationStage>()
+                        })
+                })
+                .sum::<usize>()
+    }
+
+    /// Retrieves every [`Entry`] in the `CompactTimetable` (in the order in
+    /// which they were inserted into the original timetable).
+    pub fn entries(&self) -> impl Iterator<Item = (EntryId, Entry)> + '_ {
+        self.entries
+            .iter()
+            .map(|entry| (EntryId(entry.id), self.expand(entry)))
+    }
+
+    // Convert a compact entry back into an entry
+    fn expand(&self, entry: &CompactEntry) -> Entry {
+        let string = |index: u16| self.strings[index as usize].to_string();
+
+        let activity = match entry.kind & KIND {
+            LESSON => Activity::Lesson {
+                subject: Subject::new(string(entry.subject)).unwrap(),
+                class: Class::new(string(entry.class)).unwrap(),
+                teacher: (entry.teacher != NONE)
+                    .then(|| Teacher::new(string(entry.teacher)).unwrap()),
+                location: self.location(entry),
+            },
+            REGISTRATION => Activity::Registration,
+            BREAK => Activity::Break,
+            SCHOOL_STUDY => Activity::SchoolStudy,
+            HOME_STUDY => Activity::HomeStudy,
+            TRANSIT => {
+                // The locations of a transit are either both packed, or both
+                // stored separately
+                let (from, to) = match (entry.location, entry.class) {
+                    (0, _) | (_, 0) => (self.unpacked(entry)[0], self.unpacked(entry)[1]),
+                    (from, to) => (
+                        Location::try_from(from).unwrap(),
+                        Location::try_from(to).unwrap(),
+                    ),
+                };
+
+                Activity::Transit {
+                    from,
+                    to,
+                    duration: Duration::minutes(i64::from(entry.teacher)),
+                }
+            }
+            _ => Activity::Miscellaneous(string(entry.subject)),
+        };
+
+        let (co_teachers, split_rooms, rotation) = match self
+            .staffed
+            .binary_search_by_key(&entry.id, |staffing| staffing.id)
+        {
+            Ok(index) => {
+                let staffing = &self.staffed[index];
+                let co_teachers = staffing
+                    .co_teachers
+                    .iter()
+                    .map(|code| Teacher::new(string(*code)).unwrap())
+                    .collect();
+
+                (
+                    co_teachers,
+                    staffing.split_rooms.to_vec(),
+                    staffing.rotation.as_deref().cloned(),
+                )
+            }
+            Err(_) => (Vec::new(), Vec::new(), None),
+        };
+
+        Entry {
+            slot: TimeSlot::with_index(RangedU8::new(entry.slot).unwrap()),
+            activity,
+            periods: RangedU8::new((entry.kind >> SPAN_SHIFT) + 1).unwrap(),
+            co_teachers,
+            split_rooms,
+            rotation,
+        }
+    }
+
+    // Retrieve the location of a compact lesson
+    fn location(&self, entry: &CompactEntry) -> Location {
+        // The rooms without a packed code (i.e., off-site locations and other
+        // rooms) are stored separately
+        if entry.location == 0 {
+            return self.unpacked(entry)[0];
+        }
+
+        // The packed codes are produced by `Location`'s `From` implementation
+        Location::try_from(entry.location).unwrap()
+    }
+
+    // Retrieve the locations of a compact entry which are stored separately
+    fn unpacked(&self, entry: &CompactEntry) -> Vec<Location> {
+        let start = self.unpacked.partition_point(|(id, _)| *id < entry.id);
+
+        self.unpacked[start..]
+            .iter()
+            .take_while(|(id, _)| *id == entry.id)
+            .map(|(_, location)| *location)
+            .collect()
+    }
+}
+
+impl TryFrom<&Timetable> for CompactTimetable {
+    type Error = RangeError;
+
+    /// Converts the `timetable` provided into its compact representation.
+    ///
+    /// # Errors
+    ///
+    /// A [`RangeError`] is returned should the timetable contain more than
+    /// `65534` distinct subjects, classes, teachers, and miscellaneous
+    /// activities (i.e., more than can be interned).
+    fn try_from(timetable: &Timetable) -> Result<Self, Self::Error> {
+        let mut compact = Self {
+            entries: Vec::with_capacity(timetable.len()),
+            strings: Vec::new(),
+            overrides: timetable.overrides().to_vec(),
+            next_id: timetable.next_id,
+            unpacked: Vec::new(),
+            staffed: Vec::new(),
+        };
+        let mut interner = Interner::new();
+
+        let mut intern = |string: &str| -> Result<u16, RangeError> {
+            let index = interner.intern(string).index();
+
+            u16::try_from(index)
+                .ok()
+                .filter(|index| *index != NONE)
+                .ok_or(RangeError::new(index as i128, 0, NONE as i128 - 1))
+        };
+
+        for (id, entry) in timetable.entries() {
+            let mut compact_entry = CompactEntry {
+                id: id.get(),
+                slot: entry.slot.index() as u8,
+                kind: LESSON,
+                location: 0,
+                subject: NONE,
+                class: NONE,
+                teacher: NONE,
+            };
+
+            match &entry.activity {
+                Activity::Lesson {
+                    subject,
+                    class,
+                    teacher,
+                    location,
+                } => {
+                    compact_entry.location = u16::from(*location);
+                    compact_entry.subject = intern(subject.name())?;
+                    compact_entry.class = intern(class.reference())?;
+
+                    if let Some(teacher) = teacher {
+                        compact_entry.teacher = intern(teacher.code())?;
+                    }
+
+                    if compact_entry.location == 0 {
+                        compact.unpacked.push((id.get(), *location));
+                    }
+                }
+                Activity::Registration => compact_entry.kind = REGISTRATION,
+                Activity::Break => compact_entry.kind = BREAK,
+                Activity::SchoolStudy => compact_entry.kind = SCHOOL_STUDY,
+                Activity::HomeStudy => compact_entry.kind = HOME_STUDY,
+                Activity::Miscellaneous(description) => {
+                    compact_entry.kind = MISCELLANEOUS;
+                    compact_entry.subject = intern(description)?;
+                }
+                Activity::Transit { from, to, duration } => {
+                    // The destination is stored in place of the class, and
+                    // the duration (in minutes) in place of the teacher
+                    compact_entry.kind = TRANSIT;
+                    compact_entry.location = u16::from(*from);
+                    compact_entry.class = u16::from(*to);
+                    compact_entry.teacher =
+                        duration.num_minutes().clamp(0, i64::from(u16::MAX)) as u16;
+
+                    if compact_entry.location == 0 || compact_entry.class == 0 {
+                        compact.unpacked.push((id.get(), *from));
+                        compact.unpacked.push((id.get(), *to));
+                    }
+                }
+            }
+
+            if entry.is_staffed() {
+                compact.staffed.push(CompactStaffing {
+                    id: id.get(),
+                    co_teachers: entry
+                        .co_teachers
+                        .iter()
+                        .map(|teacher| intern(teacher.code()))
+                        .collect::<Result<_, _>>()?,
+                    split_rooms: entry.split_rooms.as_slice().into(),
+                    rotation: entry.rotation.clone().map(Box::new),
+                });
+            }
+
+            compact_entry.kind |= (entry.periods.get() - 1) << SPAN_SHIFT;
+            compact.entries.push(compact_entry);
+        }
+
+        compact.strings = interner.iter().map(Box::from).collect();
+        Ok(compact)
+    }
+}
+
+impl From<&CompactTimetable> for Timetable {
+    /// Converts the `compact` timetable provided back into a `Timetable`.
+    fn from(compact: &CompactTimetable) -> Self {
+        let mut timetable = Timetable::default();
+
+        for (id, entry) in compact.entries() {
+            timetable.apply(&Change::Inserted { id, entry });
+        }
+
+        for room_override in &compact.overrides {
+            timetable.apply(&Change::OverrideAdded(*room_override));
+        }
+
+        timetable.next_id = compact.next_id;
+        timetable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Location, LocationFilter};
+
+    #[test]
+    fn compact_round_trip() {
+        let rooms: Vec<Location> = Location::all().existing().collect();
+        let mut timetable = Timetable::new();
+
+        for lesson in 0..4000 {
+            timetable.insert(
+                TimeSlot::with_index(RangedU8::new((lesson % 50) as u8).unwrap()),
+                Activity::Lesson {
+                    subject: Subject::new(format!("Subject {}", lesson % 30)).unwrap(),
+                    class: Class::new(format!("Class {}", lesson % 400)).unwrap(),
+                    teacher: Some(Teacher::new(format!("T{}", lesson % 150)).unwrap()),
+                    location: rooms[lesson % rooms.len()],
+                },
+            );
+        }
+
+        let removed = timetable.insert(
+            TimeSlot::with_index(RangedU8::new(0).unwrap()),
+            Activity::Break,
+        );
+        timetable.remove(removed);
+        timetable.insert(
+            TimeSlot::with_index(RangedU8::new(1).unwrap()),
+            Activity::Miscellaneous("Choir".to_string()),
+        );
+        timetable.insert(
+            TimeSlot::with_index(RangedU8::new(2).unwrap()),
+            Activity::Transit {
+                from: "H201".parse().unwrap(),
+                to: "@Stevenage Pool".parse().unwrap(),
+                duration: Duration::minutes(25),
+            },
+        );
+
+        timetable
+            .insert_spanning(timeslot!(W1MP3), 2, Activity::SchoolStudy)
+            .unwrap();
+
+        // The staffing of team-taught and split lessons is retained
+        let shared = timetable.get(EntryId(7)).unwrap().clone();
+        timetable.remove(EntryId(7));
+        timetable
+            .insert_entry(
+                shared
+                    .co_teacher(Teacher::new("T999".to_string()).unwrap())
+                    .split_room("@Stevenage Pool".parse().unwrap()),
+            )
+            .unwrap();
+
+        let compact = CompactTimetable::try_from(&timetable).unwrap();
+        let expanded = Timetable::from(&compact);
+
+        assert_eq!(expanded, timetable);
+        assert_eq!(expanded.next_id, timetable.next_id);
+
+        // A lower bound of the memory occupied by the entries of the
+        // timetable (disregarding its index and the overhead of its map)
+        let ergonomic: usize = timetable
+            .entries()
+            .map(|(_, entry)| {
+                let strings = match &entry.activity {
+                    Activity::Lesson {
+                        subject,
+                        class,
+                        teacher,
+                        ..
+                    } => {
+                        subject.name().capacity()
+                            + class.reference().capacity()
+                            + teacher
+                                .as_ref()
+                                .map_or(0, |teacher| teacher.code().capacity())
+                    }
+                    _ => 0,
+                };
+
+                mem::size_of::<(EntryId, Entry)>() + strings
+            })
+            .sum();
+
+        assert!(compact.memory_usage() * 5 < ergonomic);
+    }
+}
@@ -0,0 +1,438 @@
+//! Simulation of the congestion of corridors and stairwells at the change
+//! of lessons.
+//!
+//! The movements of a [`MovementGraph`] show where classes go, but not how
+//! long it takes them to get there -- a [`Network`] describes the
+//! [`Segment`]s of the schools (i.e., their corridors and stairwells, each
+//! with the number of students which can pass through it per minute) and the
+//! route between each pair of [`Area`]s. A simulation then walks every
+//! moving student along their route one minute at a time, queueing students
+//! at any segment which is already at capacity, and reports the bottlenecks
+//! of each change of lessons:
+//!
+//! ```
+//! use timetableau::attendance::Roster;
+//! use timetableau::congestion::{Area, Network};
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, Subject, Timetable};
+//!
+//! let mut timetable = Timetable::new();
+//! for (slot, location) in [(timeslot!(W1MP1), "H201"), (timeslot!(W1MP2), "P105")] {
+//!     timetable.insert(slot, Activity::Lesson {
+//!         subject: Subject::new("Ma".to_string()).unwrap(),
+//!         class: Class::new("10A".to_string()).unwrap(),
+//!         teacher: None,
+//!         location: location.parse().unwrap(),
+//!     });
+//! }
+//!
+//! let mut roster = Roster::new();
+//! for student in 0..30 {
+//!     roster.enrol("10A", &student.to_string());
+//! }
+//!
+//! let (howard, parker) = (Area::of(&"H201".parse().unwrap()), Area::of(&"P105".parse().unwrap()));
+//! let network = Network::new()
+//!     .segment("Howard stairs", 20)
+//!     .segment("Link corridor", 40)
+//!     .route(howard, parker, &["Howard stairs", "Link corridor"]);
+//!
+//! let dataset = Dataset::new(timetable, AcademicCalendar::default());
+//! let report = network.simulate(&dataset.movement_graph(timeslot!(W1MP1)..=timeslot!(W1MP5)), &roster);
+//!
+//! assert_eq!(report.clearance_minutes, 3);
+//! assert_eq!(report.bottlenecks().next().unwrap().name, "Howard stairs");
+//! ```
+//!
+//! # Evaluating one-way systems
+//!
+//! A one-way system is evaluated by simulating the same movements on two
+//! networks -- the current network, and one whose routes follow the
+//! proposed one-way system (which are usually longer, but whose segments
+//! usually have a greater throughput, as nobody walks against the flow) --
+//! and comparing their [`CongestionReport`]s.
+
+use crate::attendance::Roster;
+use crate::occupancy::Zone;
+use crate::{HighfieldFloor, Location, Movement, MovementGraph, TimeSlot};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::{self, Display, Formatter};
+
+/// An area of a school between which routes are estimated (i.e., a floor of
+/// a block, or a [`Zone`] without floors).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Area {
+    /// The zone of the area.
+    pub zone: Zone,
+
+    /// The floor of the area (should its zone have floors).
+    pub floor: Option<HighfieldFloor>,
+}
+
+impl Area {
+    /// Creates a new `Area` of the `floor` of the `zone` provided.
+    pub fn new(zone: Zone, floor: Option<HighfieldFloor>) -> Self {
+        Self { zone, floor }
+    }
+
+    /// Determines the `Area` of the `location` provided.
+    pub fn of(location: &Location) -> Self {
+        Self::new(Zone::of(location), location.parts().floor)
+    }
+}
+
+impl Display for Area {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.floor {
+            Some(floor) => write!(f, "{} (floor {})", self.zone, floor),
+            None => write!(f, "{}", self.zone),
+        }
+    }
+}
+
+/// A corridor or stairwell of a [`Network`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    /// The name of the segment (e.g., `Howard stairs`).
+    pub name: String,
+
+    /// The number of students which can pass through the segment per
+    /// minute.
+    pub throughput: u32,
+}
+
+/// The corridors and stairwells of the schools, and the routes between
+/// their areas.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Network {
+    segments: Vec<Segment>,
+    routes: HashMap<(Area, Area), Vec<usize>>,
+}
+
+impl Network {
+    /// Creates a new `Network` without any segments or routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a segment with the `name` and `throughput` (in students per
+    /// minute) provided, replacing the throughput of the segment should it
+    /// already have been added.
+    pub fn segment(mut self, name: &str, throughput: u32) -> Self {
+        match self
+            .segments
+            .iter_mut()
+            .find(|segment| segment.name == name)
+        {
+            Some(segment) => segment.throughput = throughput,
+            None => self.segments.push(Segment {
+                name: name.to_string(),
+                throughput,
+            }),
+        }
+
+        self
+    }
+
+    /// Sets the route from the `from` area to the `to` area (i.e., the
+    /// `segments` through which the students pass, in order) -- students
+    /// moving within an area, or between areas without a route, pass
+    /// through no segments.
+    ///
+    /// # Panics
+    ///
+    /// Panics should any of the `segments` not have been
+    /// [added](Self::segment).
+    pub fn route(mut self, from: Area, to: Area, segments: &[&str]) -> Self {
+        let route = segments
+            .iter()
+            .map(|name| {
+                self.segments
+                    .iter()
+                    .position(|segment| segment.name == *name)
+                    .unwrap_or_else(|| panic!("unknown segment: {}", name))
+            })
+            .collect();
+
+        self.routes.insert((from, to), route);
+        self
+    }
+
+    /// Retrieves the segments of the `Network` (in the order in which they
+    /// were added).
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Simulates the movements of the `graph` provided, where the size of
+    /// each class is the number of its students in the `roster` -- the
+    /// movements leaving each timeslot (i.e., each change of lessons) are
+    /// simulated separately, and the report contains the worst of each.
+    pub fn simulate(&self, graph: &MovementGraph, roster: &Roster) -> CongestionReport {
+        let mut report = CongestionReport {
+            segments: self
+                .segments
+                .iter()
+                .map(|segment| SegmentLoad {
+                    name: segment.name.clone(),
+                    throughput: segment.throughput,
+                    peak: 0,
+                    slot: None,
+                })
+                .collect(),
+            clearance_minutes: 0,
+            unrouted: Vec::new(),
+        };
+
+        let mut changeovers: BTreeMap<usize, Vec<&Movement>> = BTreeMap::new();
+
+        for movement in graph.movements() {
+            changeovers
+                .entry(movement.slot.index())
+                .or_default()
+                .push(movement);
+        }
+
+        for movements in changeovers.values() {
+            let mut groups = Vec::new();
+
+            for movement in movements {
+                let (from, to) = (Area::of(&movement.from), Area::of(&movement.to));
+
+                if from == to {
+                    continue;
+                }
+
+                match self.routes.get(&(from, to)) {
+                    Some(route) => groups.push((
+                        route.as_slice(),
+                        roster.students(movement.class.reference()).len() as u32,
+                    )),
+                    None => report.unrouted.push((*movement).clone()),
+                }
+            }
+
+            let (minutes, peaks) = self.changeover(&groups);
+            report.clearance_minutes = report.clearance_minutes.max(minutes);
+
+            for (load, peak) in report.segments.iter_mut().zip(peaks) {
+                if peak > load.peak {
+                    load.peak = peak;
+                    load.slot = Some(movements[0].slot);
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Simulates a single change of lessons, in which each of the `groups`
+    /// of students sets off along its route at once.
+    ///
+    /// # Returns
+    ///
+    /// The number of minutes until every student has left their last
+    /// segment, and the peak number of students at each segment.
+    fn changeover(&self, groups: &[(&[usize], u32)]) -> (u32, Vec<u32>) {
+        let mut peaks = vec![0; self.segments.len()];
+
+        // The students waiting at each segment (by the index of their group,
+        // their position along its route, and their number), in the order
+        // in which they arrived
+        let mut queues: Vec<VecDeque<(usize, usize, u32)>> =
+            vec![VecDeque::new(); self.segments.len()];
+
+        for (group, (route, students)) in groups.iter().enumerate() {
+            if let (Some(first), true) = (route.first(), *students > 0) {
+                queues[*first].push_back((group, 0, *students));
+            }
+        }
+
+        let mut minutes = 0;
+
+        while queues.iter().any(|queue| !queue.is_empty()) {
+            minutes += 1;
+            let mut arrivals = Vec::new();
+
+            for (segment, queue) in queues.iter_mut().enumerate() {
+                let waiting: u32 = queue.iter().map(|(.., students)| students).sum();
+                peaks[segment] = peaks[segment].max(waiting);
+
+                let mut capacity = self.segments[segment].throughput;
+
+                while let Some((group, position, students)) = queue.front_mut() {
+                    if capacity == 0 {
+                        break;
+                    }
+
+                    let passed = (*students).min(capacity);
+                    capacity -= passed;
+                    *students -= passed;
+                    arrivals.push((*group, *position + 1, passed));
+
+                    if *students == 0 {
+                        queue.pop_front();
+                    }
+                }
+            }
+
+            // The students which passed through a segment reach the next
+            // segment of their route a minute later
+            for (group, position, students) in arrivals {
+                if let Some(next) = groups[group].0.get(position) {
+                    queues[*next].push_back((group, position, students));
+                }
+            }
+        }
+
+        (minutes, peaks)
+    }
+}
+
+/// The peak load of a [`Segment`] during a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentLoad {
+    /// The name of the segment.
+    pub name: String,
+
+    /// The number of students which can pass through the segment per
+    /// minute.
+    pub throughput: u32,
+
+    /// The greatest number of students at the segment at once (i.e., those
+    /// passing through it, and those queueing to do so).
+    pub peak: u32,
+
+    /// The timeslot left by the movements during which the peak occurred
+    /// (should any student pass through the segment).
+    pub slot: Option<TimeSlot>,
+}
+
+impl SegmentLoad {
+    /// Determines whether the segment is a bottleneck (i.e., whether any
+    /// student had to queue to pass through it).
+    pub fn is_bottleneck(&self) -> bool {
+        self.peak > self.throughput
+    }
+}
+
+/// The outcome of a simulation of the congestion of a [`Network`].
+///
+/// *See [`Network::simulate`] for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CongestionReport {
+    /// The peak load of each segment (in the order in which the segments
+    /// were added to the network).
+    pub segments: Vec<SegmentLoad>,
+
+    /// The number of minutes until every student has reached the end of
+    /// their route, during the slowest change of lessons.
+    pub clearance_minutes: u32,
+
+    /// The movements between areas without a route (which are therefore not
+    /// simulated).
+    pub unrouted: Vec<Movement>,
+}
+
+impl CongestionReport {
+    /// Creates an iterator over the segments which are bottlenecks (*see
+    /// [`SegmentLoad::is_bottleneck`]*), the most congested first (i.e., by
+    /// the number of students queueing at their peaks).
+    pub fn bottlenecks(&self) -> impl Iterator<Item = &SegmentLoad> + '_ {
+        let mut bottlenecks: Vec<_> = self
+            .segments
+            .iter()
+            .filter(|load| load.is_bottleneck())
+            .collect();
+
+        bottlenecks.sort_by_key(|load| std::cmp::Reverse(load.peak - load.throughput));
+        bottlenecks.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Activity, Class, Dataset, Subject, Timetable};
+
+    #[test]
+    fn bottlenecks_simulated() {
+        let mut timetable = Timetable::new();
+        let mut lesson = |slot, class: &str, location: &str| {
+            timetable.insert(
+                slot,
+                Activity::Lesson {
+                    subject: Subject::new("Ma".to_string()).unwrap(),
+                    class: Class::new(class.to_string()).unwrap(),
+                    teacher: None,
+                    location: location.parse().unwrap(),
+                },
+            );
+        };
+
+        lesson(timeslot!(W1MP1), "10A", "H201");
+        lesson(timeslot!(W1MP1), "10B", "H202");
+        lesson(timeslot!(W1MP2), "10A", "P105");
+        lesson(timeslot!(W1MP2), "10B", "P106");
+        lesson(timeslot!(W1MP3), "10B", "FH M1");
+
+        let mut roster = Roster::new();
+        for student in 0..50 {
+            let class = if student < 30 { "10A" } else { "10B" };
+            roster.enrol(class, &student.to_string());
+        }
+
+        let howard = Area::of(&"H201".parse().unwrap());
+        let parker = Area::of(&"P105".parse().unwrap());
+        let network = Network::new()
+            .segment("Howard stairs", 20)
+            .segment("Link corridor", 40)
+            .segment("Parker stairs", 20)
+            .route(
+                howard,
+                parker,
+                &["Howard stairs", "Link corridor", "Parker stairs"],
+            );
+
+        let dataset = Dataset::new(timetable, AcademicCalendar::default());
+        let graph = dataset.movement_graph(timeslot!(W1MP1)..=timeslot!(W1MP5));
+        let report = network.simulate(&graph, &roster);
+
+        // 50 students queue for the Howard stairs, then pass along the
+        // corridor and up the Parker stairs in waves of 20
+        assert_eq!(report.clearance_minutes, 5);
+        assert_eq!(
+            report
+                .segments
+                .iter()
+                .map(|load| (load.peak, load.slot))
+                .collect::<Vec<_>>(),
+            vec![
+                (50, Some(timeslot!(W1MP1))),
+                (20, Some(timeslot!(W1MP1))),
+                (20, Some(timeslot!(W1MP1)))
+            ]
+        );
+
+        let bottlenecks: Vec<_> = report
+            .bottlenecks()
+            .map(|load| load.name.as_str())
+            .collect();
+        assert_eq!(bottlenecks, vec!["Howard stairs"]);
+
+        // The walk to Fearnhill has no route
+        assert_eq!(report.unrouted.len(), 1);
+        assert_eq!(report.unrouted[0].slot, timeslot!(W1MP2));
+
+        // A second staircase halves the queue
+        let widened = network.segment("Howard east stairs", 20).route(
+            howard,
+            parker,
+            &["Howard east stairs", "Link corridor", "Parker stairs"],
+        );
+        let report = widened.simulate(&graph, &roster);
+        assert_eq!(report.segments[0].peak, 0);
+        assert_eq!(report.segments[3].peak, 50);
+    }
+}
@@ -0,0 +1,292 @@
+//! Joint sixth-form lessons shared between the Highfield school and the
+//! Fearnhill school.
+//!
+//! A [`JointLesson`] is hosted at a single site, but is attended by cohorts
+//! from both schools -- each school's management information system lists
+//! the lesson with its own class (and often without the room of the host
+//! school). A [`Consortium`] records the joint lessons, and produces the
+//! composite timetable of a consortium student (*see
+//! [`Consortium::composite`]*), in which each joint lesson replaces the home
+//! school's copy of it, such that clash detection and travel checks consider
+//! the site at which the lesson actually takes place:
+//!
+//! ```
+//! use timetableau::consortium::{Cohort, Consortium, JointLesson};
+//! use timetableau::{timeslot, Activity, BellSchedules, Class, School, Subject, Timetable};
+//! use chrono::Duration;
+//!
+//! // The student's Highfield timetable, which lists the joint lesson without a room
+//! let mut home = Timetable::new();
+//! home.insert(timeslot!(W1MP1), Activity::Lesson {
+//!     subject: Subject::new("Maths".to_string()).unwrap(),
+//!     class: Class::new("12A/Ma1".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "H201".parse().unwrap(),
+//! });
+//! home.insert(timeslot!(W1MP2), Activity::Lesson {
+//!     subject: Subject::new("Economics".to_string()).unwrap(),
+//!     class: Class::new("12A/Ec1".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "TBC".parse().unwrap(),
+//! });
+//!
+//! let mut consortium = Consortium::new();
+//! consortium.add(JointLesson {
+//!     slot: timeslot!(W1MP2),
+//!     subject: Subject::new("Economics".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "FH S12".parse().unwrap(),
+//!     cohorts: vec![
+//!         Cohort::new(School::Highfield, Class::new("12A/Ec1".to_string()).unwrap())
+//!             .student("ADA"),
+//!         Cohort::new(School::Fearnhill, Class::new("12F/Ec".to_string()).unwrap())
+//!             .student("BOB"),
+//!     ],
+//! });
+//!
+//! let composite = consortium.composite(&home, "ADA");
+//!
+//! // The student must travel to Fearnhill between the first and second periods
+//! let issues = composite.travel_issues(&BellSchedules::default(), Duration::minutes(15));
+//! assert_eq!(issues.len(), 1);
+//! ```
+
+use crate::{Activity, Class, Location, School, Subject, Teacher, TimeSlot, Timetable};
+
+/// The cohort of a [`JointLesson`] from one of the schools of the
+/// consortium.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cohort {
+    /// The home school of the students of the cohort.
+    pub school: School,
+
+    /// The class by which the home school refers to the lesson.
+    pub class: Class,
+
+    /// The identifiers (e.g., admission numbers) of the students of the
+    /// cohort.
+    pub students: Vec<String>,
+}
+
+impl Cohort {
+    /// Creates a new `Cohort` of the `class` provided, without any students.
+    pub fn new(school: School, class: Class) -> Self {
+        Self {
+            school,
+            class,
+            students: Vec::new(),
+        }
+    }
+
+    /// Adds the student with the identifier provided to the cohort.
+    pub fn student(mut self, student: &str) -> Self {
+        self.students.push(student.to_string());
+        self
+    }
+}
+
+/// A lesson hosted at one site which is attended by cohorts from multiple
+/// schools.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JointLesson {
+    /// The timeslot during which the lesson takes place.
+    pub slot: TimeSlot,
+
+    /// The subject of the lesson.
+    pub subject: Subject,
+
+    /// The teacher of the lesson (if known).
+    pub teacher: Option<Teacher>,
+
+    /// The location of the lesson at the host site.
+    pub location: Location,
+
+    /// The cohorts attending the lesson.
+    pub cohorts: Vec<Cohort>,
+}
+
+impl JointLesson {
+    /// Retrieves the school which hosts the lesson.
+    pub fn host(&self) -> School {
+        self.location.school()
+    }
+
+    /// Retrieves the home schools of the cohorts attending the lesson
+    /// (without duplicates).
+    pub fn home_schools(&self) -> Vec<School> {
+        let mut schools: Vec<School> = self.cohorts.iter().map(|cohort| cohort.school).collect();
+
+        schools.sort_unstable();
+        schools.dedup();
+        schools
+    }
+
+    /// Retrieves the combined class list of the lesson (i.e., the students
+    /// of every cohort, in the order of their cohorts).
+    pub fn class_list(&self) -> Vec<&str> {
+        self.cohorts
+            .iter()
+            .flat_map(|cohort| cohort.students.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Retrieves the cohort of the student with the identifier provided,
+    /// should they attend the lesson.
+    pub fn cohort_of(&self, student: &str) -> Option<&Cohort> {
+        self.cohorts
+            .iter()
+            .find(|cohort| cohort.students.iter().any(|other| other == student))
+    }
+
+    /// Creates the lesson as it is attended by the `cohort` provided (i.e.,
+    /// with the class of the cohort's home school, at the location of the
+    /// host site).
+    pub fn activity(&self, cohort: &Cohort) -> Activity {
+        Activity::Lesson {
+            subject: self.subject.clone(),
+            class: cohort.class.clone(),
+            teacher: self.teacher.clone(),
+            location: self.location,
+        }
+    }
+}
+
+/// The joint lessons of the consortium.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Consortium {
+    lessons: Vec<JointLesson>,
+}
+
+impl Consortium {
+    /// Creates a new `Consortium` without any joint lessons.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the joint `lesson` provided.
+    ///
+    /// # Returns
+    ///
+    /// The index of the lesson (within [`Self::lessons`]).
+    pub fn add(&mut self, lesson: JointLesson) -> usize {
+        self.lessons.push(lesson);
+        self.lessons.len() - 1
+    }
+
+    /// Retrieves every joint lesson (in the order in which they were added).
+    pub fn lessons(&self) -> &[JointLesson] {
+        &self.lessons
+    }
+
+    /// Retrieves the joint lessons attended by the student with the
+    /// identifier provided.
+    pub fn lessons_of<'a>(&'a self, student: &'a str) -> impl Iterator<Item = &'a JointLesson> {
+        self.lessons
+            .iter()
+            .filter(move |lesson| lesson.cohort_of(student).is_some())
+    }
+
+    /// Creates the composite timetable of the student with the identifier
+    /// provided from their `home` timetable (i.e., the timetable of the
+    /// student at their home school).
+    ///
+    /// Each joint lesson attended by the student replaces the home school's
+    /// copy of it (i.e., any lesson of the class of the student's cohort
+    /// during the slot of the joint lesson), such that the lesson is not
+    /// counted twice (e.g., as a teacher clash) and takes place at the host
+    /// site (e.g., for [`Timetable::travel_issues`]). A transit is then
+    /// inserted into the free periods between lessons at different sites
+    /// (*see [`Timetable::insert_transits`]*).
+    pub fn composite(&self, home: &Timetable, student: &str) -> Timetable {
+        let mut timetable = home.clone();
+
+        for lesson in &self.lessons {
+            let Some(cohort) = lesson.cohort_of(student) else {
+                continue;
+            };
+
+            let copies: Vec<_> = timetable
+                .at(lesson.slot)
+                .filter(|(_, entry)| match &entry.activity {
+                    Activity::Lesson { class, .. } => *class == cohort.class,
+                    _ => false,
+                })
+                .map(|(id, _)| id)
+                .collect();
+
+            for id in copies {
+                timetable.remove(id);
+            }
+
+            timetable.insert(lesson.slot, lesson.activity(cohort));
+        }
+
+        timetable.insert_transits();
+        timetable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeslot;
+    use crate::validate::{NoClashes, Rule};
+
+    #[test]
+    fn composite_replaces_home_copies() {
+        let teacher = Teacher::new("JSM".to_string()).unwrap();
+        let class = |reference: &str| Class::new(reference.to_string()).unwrap();
+
+        let mut home = Timetable::new();
+        home.insert(
+            timeslot!(W1TP3),
+            Activity::Lesson {
+                subject: Subject::new("Ec".to_string()).unwrap(),
+                class: class("12A/Ec1"),
+                teacher: Some(teacher.clone()),
+                location: "TBC".parse().unwrap(),
+            },
+        );
+        home.insert(timeslot!(W1TP4), Activity::SchoolStudy);
+
+        let lesson = JointLesson {
+            slot: timeslot!(W1TP3),
+            subject: Subject::new("Economics".to_string()).unwrap(),
+            teacher: Some(teacher),
+            location: "FH S12".parse().unwrap(),
+            cohorts: vec![
+                Cohort::new(School::Highfield, class("12A/Ec1"))
+                    .student("ADA")
+                    .student("CAL"),
+                Cohort::new(School::Fearnhill, class("12F/Ec")).student("BOB"),
+                Cohort::new(School::Highfield, class("13A/Ec1")).student("DEE"),
+            ],
+        };
+
+        assert_eq!(lesson.host(), School::Fearnhill);
+        assert_eq!(
+            lesson.home_schools(),
+            [School::Highfield, School::Fearnhill]
+        );
+        assert_eq!(lesson.class_list(), ["ADA", "CAL", "BOB", "DEE"]);
+
+        let mut consortium = Consortium::new();
+        consortium.add(lesson);
+
+        // The student's copy of the lesson does not clash with the joint lesson
+        let composite = consortium.composite(&home, "ADA");
+        let (_, entry) = composite.at(timeslot!(W1TP3)).next().unwrap();
+
+        assert_eq!(composite.len(), 2);
+        assert_eq!(entry.activity.location().unwrap().to_string(), "FH S12");
+        assert!(NoClashes.check(&composite).is_empty());
+
+        // Students who do not attend the lesson are unaffected
+        assert_eq!(consortium.composite(&home, "EVE"), home);
+        assert_eq!(consortium.lessons_of("BOB").count(), 1);
+    }
+}
@@ -0,0 +1,115 @@
+//! A minimal reader (and writer) for comma-separated values (as described by
+//! [RFC 4180]).
+//!
+//! Pulling in an entire crate for parsing CSV is overkill as the files
+//! produced by school management systems are simple -- this module handles
+//! quoted fields (including escaped quotes and line breaks within quotes)
+//! and both `\n` and `\r\n` line endings, which is all that is required.
+//!
+//! [RFC 4180]: https://www.rfc-editor.org/rfc/rfc4180
+
+/// A record of a CSV file: the line on which the record starts (starting from
+/// `1`) and the record's fields.
+pub(crate) type CsvRecord = (usize, Vec<String>);
+
+/// Parses the CSV `input` provided into its records.
+///
+/// Empty lines are skipped.
+pub(crate) fn parse(input: &str) -> Vec<CsvRecord> {
+    let mut records = Vec::new();
+
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                // A double quote within a quoted field is an escaped quote
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                fields.push(std::mem::take(&mut field));
+
+                // Skip empty lines
+                if fields.len() > 1 || !fields[0].is_empty() {
+                    records.push((start, std::mem::take(&mut fields)));
+                } else {
+                    fields.clear();
+                }
+
+                line += 1;
+                start = line;
+            }
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+
+                field.push(c);
+            }
+        }
+    }
+
+    // The final record may not be terminated by a line break
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((start, fields));
+    }
+
+    records
+}
+
+/// Writes a record with the `fields` provided to the `output`, terminated by
+/// a `\r\n` line break -- fields containing a comma, a double quote, or a
+/// line break are quoted.
+pub(crate) fn write_record(output: &mut String, fields: &[&str]) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            output.push(',');
+        }
+
+        if field.contains([',', '"', '\r', '\n']) {
+            output.push('"');
+            output.push_str(&field.replace('"', "\"\""));
+            output.push('"');
+        } else {
+            output.push_str(field);
+        }
+    }
+
+    output.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_parse_quoted() {
+        let records = parse("a,\"b,c\",\"d\"\"e\"\r\n\n\"f\ng\",h");
+
+        assert_eq!(
+            records,
+            vec![
+                (
+                    1,
+                    vec!["a".to_string(), "b,c".to_string(), "d\"e".to_string()]
+                ),
+                (3, vec!["f\ng".to_string(), "h".to_string()]),
+            ]
+        );
+    }
+}
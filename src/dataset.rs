@@ -0,0 +1,1316 @@
+use crate::json::{self, write_string, JsonValue};
+use crate::timeslot::{parse_slot_key, slot_key};
+use crate::timetable;
+use crate::{
+    AcademicCalendar, Activity, BellSchedule, BellSchedules, Change, Class, DayException, Entry,
+    EntryId, Location, Period, PeriodTime, RoomOverride, Rotation, School, Subject, Teacher, Term,
+    TimeOfDay, TimeSlot, Timetable, TimetableError,
+};
+use chrono::{Duration, NaiveDate, NaiveTime};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+
+/// The value of the `format` field of every dataset.
+const FORMAT: &str = "timetableau-dataset";
+
+/// The latest version of the dataset format (every earlier version can still
+/// be read).
+const VERSION: i64 = 7;
+
+/// A complete dataset of a school (i.e., its timetable and its academic
+/// calendar), which can be exchanged with third-party tools using its
+/// canonical JSON representation.
+///
+/// ```
+/// use timetableau::{timeslot, AcademicCalendar, Activity, Dataset, Timetable};
+///
+/// let mut timetable = Timetable::new();
+/// timetable.insert(timeslot!(W1MP1), Activity::Registration);
+///
+/// let dataset = Dataset::new(timetable, AcademicCalendar::default());
+/// let json = dataset.to_json();
+///
+/// assert_eq!(Dataset::from_json(&json), Ok(dataset));
+/// ```
+///
+/// # Format
+///
+/// A dataset is a JSON object with the following fields (the field names
+/// are stable -- any change to the format increments its `version`):
+///
+/// ```json
+/// {
+///   "format": "timetableau-dataset",
+///   "version": 1,
+///   "locations": ["H201", "Hall"],
+///   "entries": [
+///     {"id": 0, "slot": "week1.mon.p1", "activity": {"kind": "registration"}},
+///     {"id": 1, "slot": "week1.mon.p2", "activity": {"kind": "lesson", "subject": "Ma", "class": "10A", "teacher": "JSM", "room": "H201"}}
+///   ],
+///   "overrides": [
+///     {"slot": "week1.mon.p2", "start": "2023-09-04", "end": "2023-09-08", "room": "Hall"}
+///   ],
+///   "calendar": {
+///     "terms": [{"start": "2023-09-04", "end": "2023-10-20"}],
+///     "exceptions": [{"date": "2023-09-05", "kind": "early-closure", "last_period": 3}],
+///     "bells": {
+///       "highfield": {"periods": [["08:50", "09:50"], ...], "break": ["10:50", "11:10"], "lunch": ["13:10", "13:55"]},
+///       "fearnhill": { ... }
+///     }
+///   }
+/// }
+/// ```
+///
+/// * `locations` lists every room referenced by the dataset (as room codes,
+///   e.g., `"FH S13"`) -- every room referenced elsewhere must be listed.
+///
+/// * Slots are written as `<week>.<day>.<period>` (e.g., `week2.thu.p5`),
+///   dates as `YYYY-MM-DD`, times as `HH:MM`, and periods as their numbers
+///   (i.e., `1` to `5`).
+///
+/// * The `kind` of an activity is `lesson` (with a `subject`, a `class`, a
+///   `room`, and an optional `teacher`), `registration`, `break`,
+///   `independent-study`, `home-study`, or `miscellaneous` (with a
+///   `description`).
+///
+/// * The `kind` of an exception is `closed`, `early-closure` (with a
+///   `last_period`), `suspended` (with the suspended `periods` and the
+///   `activity` which replaces them), `open` (*see [`DayException::Open`]*),
+///   or `schedule` (with the `bells` of the day, in the format of those of
+///   the calendar) -- as readers of earlier versions would reject it, a
+///   dataset with a `schedule` exception is written as version `7`.
+///
+/// * The optional `from` of an override restricts the override to the
+///   lessons taking place in that room (*see
+///   [`Timetable::override_location`]*) -- as readers of version `1` would
+///   ignore it, a dataset with such an override is written as version `2`
+///   (and is otherwise identical).
+///
+/// * The optional `periods` of an entry is the number of consecutive periods
+///   it spans (e.g., `2` for a double lesson), and is omitted for entries
+///   spanning a single period -- as readers of earlier versions would ignore
+///   it, a dataset with such an entry is written as version `3`.
+///
+/// * The optional `co_teachers` (staff codes) and `split_rooms` (room codes)
+///   of a lesson's entry are its teachers and rooms besides those of its
+///   activity (*see [`Entry::co_teachers`] and [`Entry::split_rooms`]*), and
+///   are omitted should they be empty -- a dataset with such an entry is
+///   written as version `4`.
+///
+/// * The optional `rotation` of a lesson's entry is an object with the
+///   `start` date of the rotation and its `stages` (each with a `room` and
+///   an optional `teacher`) -- *see [`Rotation`]* -- and a dataset with
+///   such an entry is written as version `5`.
+///
+/// * The optional `bank_holidays` of the calendar is `false` should the
+///   calendar not observe bank holidays (*see
+///   [`AcademicCalendar::observe_bank_holidays`]*), and is omitted otherwise
+///   -- a dataset with such a calendar (or with an `open` exception) is
+///   written as version `6`.
+///
+/// * `overrides` and `calendar` may be omitted (as may `exceptions` and
+///   `bells`, in which case the default bell schedules are used), and
+///   unknown fields are ignored.
+///
+/// # Remarks
+///
+/// The entries of the timetable retain their [`EntryId`]s -- its history
+/// and author, however, are not included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dataset {
+    /// The timetable of the school.
+    pub timetable: Timetable,
+
+    /// The academic calendar of the school.
+    pub calendar: AcademicCalendar,
+}
+
+/// The reason a dataset could not be parsed.
+///
+/// *See [`DatasetError`] for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DatasetErrorKind {
+    /// The dataset is not valid JSON.
+    Syntax,
+
+    /// The dataset is not a `timetableau-dataset`.
+    InvalidFormat,
+
+    /// The dataset was written in a version of the format which is not
+    /// supported (e.g., by a newer version of the crate).
+    UnsupportedVersion(i64),
+
+    /// An object does not contain a required field.
+    MissingField(&'static str),
+
+    /// The value of a field is of the wrong type (e.g., a number rather than
+    /// a string).
+    UnexpectedValue(&'static str),
+
+    /// Two entries have the same `id`.
+    DuplicateId(u32),
+
+    /// A slot is not of the form `<week>.<day>.<period>`.
+    InvalidSlot(String),
+
+    /// A period is not a number from `1` to `5`.
+    InvalidPeriod,
+
+    /// A date is not of the form `YYYY-MM-DD`.
+    InvalidDate(String),
+
+    /// A time is not of the form `HH:MM`.
+    InvalidTime(String),
+
+    /// The kind of an activity is unknown.
+    UnknownActivity(String),
+
+    /// The kind of an exception is unknown.
+    UnknownException(String),
+
+    /// The subject of a lesson is invalid.
+    InvalidSubject(String),
+
+    /// The class of a lesson is invalid.
+    InvalidClass(String),
+
+    /// The teacher of a lesson is invalid.
+    InvalidTeacher(String),
+
+    /// A room is neither a Highfield room nor a Fearnhill room.
+    InvalidRoom(String),
+
+    /// A room is referenced, but is not listed in the `locations` of the
+    /// dataset.
+    UndeclaredRoom(String),
+
+    /// A room override ends before it starts.
+    EmptyOverride,
+
+    /// An entry does not span at least one period, or spans past the final
+    /// period of the day.
+    InvalidSpan,
+
+    /// An entry which is not a lesson has co-teachers, split rooms, or a
+    /// rotation.
+    NotALesson,
+
+    /// A term or a bell schedule of the calendar is invalid.
+    InvalidCalendar(TimetableError),
+}
+
+impl Display for DatasetErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax => f.write_str("invalid JSON"),
+            Self::InvalidFormat => write!(f, "expected a `{}`", FORMAT),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version `{}`", version)
+            }
+            Self::MissingField(field) => write!(f, "missing field `{}`", field),
+            Self::UnexpectedValue(field) => write!(f, "unexpected value for `{}`", field),
+            Self::DuplicateId(id) => write!(f, "entry `{}` defined more than once", id),
+            Self::InvalidSlot(slot) => write!(f, "invalid slot `{}`", slot),
+            Self::InvalidPeriod => f.write_str("expected a period from 1 to 5"),
+            Self::InvalidDate(date) => write!(f, "invalid date `{}`", date),
+            Self::InvalidTime(time) => write!(f, "invalid time `{}`", time),
+            Self::UnknownActivity(kind) => write!(f, "unknown activity `{}`", kind),
+            Self::UnknownException(kind) => write!(f, "unknown exception `{}`", kind),
+            Self::InvalidSubject(subject) => write!(f, "invalid subject `{}`", subject),
+            Self::InvalidClass(class) => write!(f, "invalid class `{}`", class),
+            Self::InvalidTeacher(teacher) => write!(f, "invalid teacher `{}`", teacher),
+            Self::InvalidRoom(room) => write!(f, "invalid room code `{}`", room),
+            Self::UndeclaredRoom(room) => write!(f, "room `{}` is not a listed location", room),
+            Self::EmptyOverride => f.write_str("room override ends before it starts"),
+            Self::InvalidSpan => f.write_str("entry spans past the final period of the day"),
+            Self::NotALesson => f.write_str(
+                "co-teachers, split rooms, or a rotation of an entry which is not a lesson",
+            ),
+            Self::InvalidCalendar(error) => write!(f, "invalid calendar ({})", error),
+        }
+    }
+}
+
+/// An error returned when a dataset cannot be parsed.
+///
+/// *See [`Dataset::from_json`] for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetError {
+    /// The reason the dataset could not be parsed.
+    pub kind: DatasetErrorKind,
+
+    /// The line (starting from `1`) on which the object at fault starts (or,
+    /// for syntax errors, the line on which the error occurred).
+    pub line: usize,
+}
+
+impl Display for DatasetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (line {})", self.kind, self.line)
+    }
+}
+
+impl Error for DatasetError {}
+
+impl Dataset {
+    /// Creates a new `Dataset`.
+    pub fn new(timetable: Timetable, calendar: AcademicCalendar) -> Self {
+        Self {
+            timetable,
+            calendar,
+        }
+    }
+
+    /// Retrieves every [`Location`] referenced by the `Dataset` (i.e., by its
+    /// lessons, their split rooms and rotations, its room overrides, and
+    /// its exceptions), ordered by their room codes.
+    pub fn locations(&self) -> Vec<Location> {
+        let lessons = self
+            .timetable
+            .entries()
+            .flat_map(|(_, entry)| {
+                let stages = entry.rotation.iter().flat_map(|rotation| &rotation.stages);
+
+                entry.locations().chain(stages.map(|stage| &stage.location))
+            })
+            .chain(
+                self.calendar
+                    .exceptions()
+                    .filter_map(|(_, exception)| match exception {
+                        DayException::Suspended { activity, .. } => activity.location(),
+                        _ => None,
+                    }),
+            );
+        let overrides = self
+            .timetable
+            .overrides()
+            .iter()
+            .flat_map(|room_override| [Some(&room_override.location), room_override.from.as_ref()])
+            .flatten();
+
+        let mut seen = HashSet::new();
+        let mut locations: Vec<Location> = lessons
+            .chain(overrides)
+            .filter(|location| seen.insert(**location))
+            .copied()
+            .collect();
+
+        locations.sort_by_cached_key(Location::to_string);
+        locations
+    }
+
+    /// Converts the `Dataset` into its canonical JSON representation.
+    ///
+    /// The representation is deterministic (i.e., equal datasets produce
+    /// identical documents) -- *see the [`Dataset`] documentation for a
+    /// description of the format*.
+    pub fn to_json(&self) -> String {
+        let mut output = String::from("{\n");
+
+        let _ = writeln!(output, "  \"format\": \"{}\",", FORMAT);
+        // Version 2 only differs from version 1 by restricted overrides,
+        // version 3 from version 2 by entries spanning multiple periods,
+        // version 4 from version 3 by team-taught and split lessons,
+        // version 5 from version 4 by rotating lessons, version 6 from
+        // version 5 by the observance of bank holidays, and version 7 from
+        // version 6 by days with their own bell schedules
+        let restricted = self
+            .timetable
+            .overrides()
+            .iter()
+            .any(|room_override| room_override.from.is_some());
+        let spanning = self
+            .timetable
+            .entries()
+            .any(|(_, entry)| entry.periods.get() > 1);
+        let staffed = self
+            .timetable
+            .entries()
+            .any(|(_, entry)| !(entry.co_teachers.is_empty() && entry.split_rooms.is_empty()));
+        let rotating = self
+            .timetable
+            .entries()
+            .any(|(_, entry)| entry.rotation.is_some());
+        let bank_holidays = !self.calendar.observes_bank_holidays()
+            || self
+                .calendar
+                .exceptions()
+                .any(|(_, exception)| *exception == DayException::Open);
+        let scheduled = self
+            .calendar
+            .exceptions()
+            .any(|(_, exception)| matches!(exception, DayException::Schedule(_)));
+        let version = if scheduled {
+            VERSION
+        } else if bank_holidays {
+            6
+        } else if rotating {
+            5
+        } else if staffed {
+            4
+        } else if spanning {
+            3
+        } else if restricted {
+            2
+        } else {
+            1
+        };
+
+        let _ = writeln!(output, "  \"version\": {},", version);
+
+        output.push_str("  \"locations\": [");
+
+        for (index, location) in self.locations().iter().enumerate() {
+            if index > 0 {
+                output.push_str(", ");
+            }
+
+            write_string(&mut output, &location.to_string());
+        }
+
+        output.push_str("],\n");
+
+        let entries = self.timetable.entries().map(|(id, entry)| {
+            let mut item = format!(
+                "{{\"id\": {}, \"slot\": \"{}\", ",
+                id.get(),
+                slot_key(entry.slot)
+            );
+
+            if entry.periods.get() > 1 {
+                let _ = write!(item, "\"periods\": {}, ", entry.periods);
+            }
+
+            let staffing = [
+                (
+                    "co_teachers",
+                    entry.co_teachers.iter().map(ToString::to_string).collect(),
+                ),
+                (
+                    "split_rooms",
+                    entry.split_rooms.iter().map(ToString::to_string).collect(),
+                ),
+            ];
+
+            for (name, codes) in staffing {
+                let codes: Vec<String> = codes;
+
+                if codes.is_empty() {
+                    continue;
+                }
+
+                let _ = write!(item, "\"{}\": [", name);
+
+                for (index, code) in codes.iter().enumerate() {
+                    if index > 0 {
+                        item.push_str(", ");
+                    }
+
+                    write_string(&mut item, code);
+                }
+
+                item.push_str("], ");
+            }
+
+            if let Some(rotation) = &entry.rotation {
+                let _ = write!(
+                    item,
+                    "\"rotation\": {{\"start\": \"{}\", \"stages\": [",
+                    rotation.start.format("%Y-%m-%d")
+                );
+
+                for (index, stage) in rotation.stages.iter().enumerate() {
+                    item.push_str(if index > 0 { ", {" } else { "{" });
+
+                    if let Some(teacher) = &stage.teacher {
+                        item.push_str("\"teacher\": ");
+                        write_string(&mut item, teacher.code());
+                        item.push_str(", ");
+                    }
+
+                    item.push_str("\"room\": ");
+                    write_string(&mut item, &stage.location.to_string());
+                    item.push('}');
+                }
+
+                item.push_str("]}, ");
+            }
+
+            item.push_str("\"activity\": ");
+            write_activity(&mut item, &entry.activity);
+            item.push('}');
+            item
+        });
+        write_array(&mut output, 1, "entries", entries);
+        output.push_str(",\n");
+
+        let overrides = self.timetable.overrides().iter().map(|room_override| {
+            let mut item = format!(
+                "{{\"slot\": \"{}\", \"start\": \"{}\", \"end\": \"{}\", \"room\": ",
+                slot_key(room_override.slot),
+                room_override.start.format("%Y-%m-%d"),
+                room_override.end.format("%Y-%m-%d"),
+            );
+
+            write_string(&mut item, &room_override.location.to_string());
+
+            if let Some(from) = room_override.from {
+                item.push_str(", \"from\": ");
+                write_string(&mut item, &from.to_string());
+            }
+
+            item.push('}');
+            item
+        });
+        write_array(&mut output, 1, "overrides", overrides);
+        output.push_str(",\n");
+
+        output.push_str("  \"calendar\": {\n");
+
+        let terms = self.calendar.terms().iter().map(|term| {
+            format!(
+                "{{\"start\": \"{}\", \"end\": \"{}\"}}",
+                term.start().format("%Y-%m-%d"),
+                term.end().format("%Y-%m-%d")
+            )
+        });
+        write_array(&mut output, 2, "terms", terms);
+        output.push_str(",\n");
+
+        let exceptions = self.calendar.exceptions().map(|(date, exception)| {
+            let mut item = format!("{{\"date\": \"{}\", ", date.format("%Y-%m-%d"));
+
+            match exception {
+                DayException::Closed => item.push_str("\"kind\": \"closed\""),
+                DayException::Open => item.push_str("\"kind\": \"open\""),
+                DayException::EarlyClosure { last_period } => {
+                    let _ = write!(
+                        item,
+                        "\"kind\": \"early-closure\", \"last_period\": {}",
+                        *last_period as usize + 1
+                    );
+                }
+                DayException::Suspended { periods, activity } => {
+                    let periods: Vec<String> = periods
+                        .iter()
+                        .map(|period| (*period as usize + 1).to_string())
+                        .collect();
+
+                    let _ = write!(
+                        item,
+                        "\"kind\": \"suspended\", \"periods\": [{}], \"activity\": ",
+                        periods.join(", ")
+                    );
+                    write_activity(&mut item, activity);
+                }
+                DayException::Schedule(schedules) => {
+                    let _ = write!(
+                        item,
+                        "\"kind\": \"schedule\", \"bells\": {{\"highfield\": {}, \"fearnhill\": {}}}",
+                        schedule_json(schedules.get(School::Highfield)),
+                        schedule_json(schedules.get(School::Fearnhill))
+                    );
+                }
+            }
+
+            item.push('}');
+            item
+        });
+        write_array(&mut output, 2, "exceptions", exceptions);
+        output.push_str(",\n");
+
+        if !self.calendar.observes_bank_holidays() {
+            output.push_str("    \"bank_holidays\": false,\n");
+        }
+
+        output.push_str("    \"bells\": {\n");
+
+        for (school, name, separator) in [
+            (School::Highfield, "highfield", ","),
+            (School::Fearnhill, "fearnhill", ""),
+        ] {
+            let schedule = self.calendar.bell_schedules().get(school);
+            let _ = writeln!(
+                output,
+                "      \"{}\": {}{}",
+                name,
+                schedule_json(schedule),
+                separator
+            );
+        }
+
+        output.push_str("    }\n  }\n}\n");
+        output
+    }
+
+    /// Parses a `Dataset` from its canonical JSON representation.
+    ///
+    /// *See the [`Dataset`] documentation for a description of the format*.
+    ///
+    /// # Errors
+    ///
+    /// A [`DatasetError`] describing the first problem found within the
+    /// dataset (e.g., an invalid room code).
+    pub fn from_json(input: &str) -> Result<Self, DatasetError> {
+        let document = json::parse(input).map_err(|error| DatasetError {
+            kind: DatasetErrorKind::Syntax,
+            line: error.line,
+        })?;
+        let root = Object::new(&document, 1, "dataset")?;
+
+        if root.string("format")? != FORMAT {
+            return Err(root.error(DatasetErrorKind::InvalidFormat));
+        }
+
+        let version = root.integer("version")?;
+
+        if !(1..=VERSION).contains(&version) {
+            return Err(root.error(DatasetErrorKind::UnsupportedVersion(version)));
+        }
+
+        let mut locations = HashSet::new();
+
+        for value in root.array("locations")? {
+            let room = value
+                .as_str()
+                .ok_or_else(|| root.error(DatasetErrorKind::UnexpectedValue("locations")))?;
+
+            locations.insert(
+                room.parse::<Location>()
+                    .map_err(|_| root.error(DatasetErrorKind::InvalidRoom(room.to_string())))?,
+            );
+        }
+
+        let parser = Parser { locations };
+        let mut timetable = Timetable::new();
+
+        for value in root.array("entries")? {
+            let object = Object::new(value, root.line, "entries")?;
+
+            let id = u32::try_from(object.integer("id")?)
+                .map_err(|_| object.error(DatasetErrorKind::UnexpectedValue("id")))?;
+            let slot = parser.slot(&object)?;
+            let periods = match object.get("periods") {
+                Some(_) => object.integer("periods")?,
+                None => 1,
+            };
+            let entry = Entry {
+                slot,
+                activity: parser.activity(&object.object("activity")?)?,
+                periods: u8::try_from(periods)
+                    .ok()
+                    .and_then(|periods| timetable::span(slot, periods).ok())
+                    .ok_or_else(|| object.error(DatasetErrorKind::InvalidSpan))?,
+                co_teachers: parser.co_teachers(&object)?,
+                split_rooms: parser.split_rooms(&object)?,
+                rotation: match object.get("rotation") {
+                    Some(_) => Some(parser.rotation(&object.object("rotation")?)?),
+                    None => None,
+                },
+            };
+
+            if entry.is_staffed() && !matches!(entry.activity, Activity::Lesson { .. }) {
+                return Err(object.error(DatasetErrorKind::NotALesson));
+            }
+
+            if timetable.get(EntryId(id)).is_some() {
+                return Err(object.error(DatasetErrorKind::DuplicateId(id)));
+            }
+
+            timetable.apply(&Change::Inserted {
+                id: EntryId(id),
+                entry,
+            });
+        }
+
+        for value in root.optional_array("overrides")? {
+            let object = Object::new(value, root.line, "overrides")?;
+
+            let room_override = RoomOverride {
+                slot: parser.slot(&object)?,
+                start: parser.date(&object, "start")?,
+                end: parser.date(&object, "end")?,
+                location: parser.room(&object, "room")?,
+                from: match object.get("from") {
+                    Some(_) if version >= 2 => Some(parser.room(&object, "from")?),
+                    _ => None,
+                },
+            };
+
+            if room_override.end < room_override.start {
+                return Err(object.error(DatasetErrorKind::EmptyOverride));
+            }
+
+            timetable.apply(&Change::OverrideAdded(room_override));
+        }
+
+        let calendar = match root.get("calendar") {
+            Some(value) => parser.calendar(&Object::new(value, root.line, "calendar")?)?,
+            None => AcademicCalendar::default(),
+        };
+
+        Ok(Self {
+            timetable,
+            calendar,
+        })
+    }
+}
+
+// Write an array of items (each on its own line) as the field `name`
+fn write_array(output: &mut String, depth: usize, name: &str, items: impl Iterator<Item = String>) {
+    let indent = "  ".repeat(depth);
+    let mut items = items.peekable();
+
+    let _ = write!(output, "{}\"{}\": [", indent, name);
+
+    if items.peek().is_none() {
+        output.push(']');
+        return;
+    }
+
+    output.push('\n');
+
+    while let Some(item) = items.next() {
+        let separator = if items.peek().is_some() { "," } else { "" };
+        let _ = writeln!(output, "{}  {}{}", indent, item, separator);
+    }
+
+    let _ = write!(output, "{}]", indent);
+}
+
+// Write an activity as a JSON object
+// Convert a bell schedule into its JSON representation (i.e., an object of
+// its `periods`, `break`, and `lunch`)
+fn schedule_json(schedule: &BellSchedule) -> String {
+    let range = |range: PeriodTime| format!("[\"{}\", \"{}\"]", range.start, range.end);
+    let periods: Vec<String> = Period::ALL
+        .iter()
+        .map(|period| range(schedule.period(*period)))
+        .collect();
+
+    format!(
+        "{{\"periods\": [{}], \"break\": {}, \"lunch\": {}}}",
+        periods.join(", "),
+        range(schedule.break_time()),
+        range(schedule.lunch())
+    )
+}
+
+fn write_activity(output: &mut String, activity: &Activity) {
+    let kind = match activity {
+        Activity::Lesson {
+            subject,
+            class,
+            teacher,
+            location,
+        } => {
+            output.push_str("{\"kind\": \"lesson\", \"subject\": ");
+            write_string(output, subject.name());
+            output.push_str(", \"class\": ");
+            write_string(output, class.reference());
+
+            if let Some(teacher) = teacher {
+                output.push_str(", \"teacher\": ");
+                write_string(output, teacher.code());
+            }
+
+            output.push_str(", \"room\": ");
+            write_string(output, &location.to_string());
+            output.push('}');
+            return;
+        }
+        Activity::Miscellaneous(description) => {
+            output.push_str("{\"kind\": \"miscellaneous\", \"description\": ");
+            write_string(output, description);
+            output.push('}');
+            return;
+        }
+        Activity::Transit { from, to, duration } => {
+            output.push_str("{\"kind\": \"transit\", \"from\": ");
+            write_string(output, &from.to_string());
+            output.push_str(", \"to\": ");
+            write_string(output, &to.to_string());
+            let _ = write!(output, ", \"minutes\": {}}}", duration.num_minutes());
+            return;
+        }
+        Activity::Registration => "registration",
+        Activity::Break => "break",
+        Activity::SchoolStudy => "independent-study",
+        Activity::HomeStudy => "home-study",
+    };
+
+    let _ = write!(output, "{{\"kind\": \"{}\"}}", kind);
+}
+
+// A JSON object of a dataset
+struct Object<'a> {
+    value: &'a JsonValue,
+    line: usize,
+}
+
+impl<'a> Object<'a> {
+    // Interpret the value of the field `name` as an object (the line of the
+    // enclosing object is used should the value not be an object)
+    fn new(value: &'a JsonValue, line: usize, name: &'static str) -> Result<Self, DatasetError> {
+        match value.line() {
+            Some(line) => Ok(Self { value, line }),
+            None => Err(DatasetError {
+                kind: DatasetErrorKind::UnexpectedValue(name),
+                line,
+            }),
+        }
+    }
+
+    fn error(&self, kind: DatasetErrorKind) -> DatasetError {
+        DatasetError {
+            kind,
+            line: self.line,
+        }
+    }
+
+    // Retrieve the value of an optional field (treating `null` as missing)
+    fn get(&self, name: &str) -> Option<&'a JsonValue> {
+        self.value
+            .get(name)
+            .filter(|value| **value != JsonValue::Null)
+    }
+
+    fn require(&self, name: &'static str) -> Result<&'a JsonValue, DatasetError> {
+        self.get(name)
+            .ok_or_else(|| self.error(DatasetErrorKind::MissingField(name)))
+    }
+
+    fn string(&self, name: &'static str) -> Result<&'a str, DatasetError> {
+        self.require(name)?
+            .as_str()
+            .ok_or_else(|| self.error(DatasetErrorKind::UnexpectedValue(name)))
+    }
+
+    fn integer(&self, name: &'static str) -> Result<i64, DatasetError> {
+        self.require(name)?
+            .as_f64()
+            .filter(|number| number.fract() == 0.0)
+            .map(|number| number as i64)
+            .ok_or_else(|| self.error(DatasetErrorKind::UnexpectedValue(name)))
+    }
+
+    fn array(&self, name: &'static str) -> Result<&'a [JsonValue], DatasetError> {
+        self.require(name)?
+            .as_array()
+            .ok_or_else(|| self.error(DatasetErrorKind::UnexpectedValue(name)))
+    }
+
+    fn optional_array(&self, name: &'static str) -> Result<&'a [JsonValue], DatasetError> {
+        match self.get(name) {
+            Some(_) => self.array(name),
+            None => Ok(&[]),
+        }
+    }
+
+    fn object(&self, name: &'static str) -> Result<Object<'a>, DatasetError> {
+        Object::new(self.require(name)?, self.line, name)
+    }
+}
+
+// Interprets the parts of a dataset which reference its locations
+struct Parser {
+    locations: HashSet<Location>,
+}
+
+impl Parser {
+    fn slot(&self, object: &Object) -> Result<TimeSlot, DatasetError> {
+        let slot = object.string("slot")?;
+
+        parse_slot_key(slot)
+            .ok_or_else(|| object.error(DatasetErrorKind::InvalidSlot(slot.to_string())))
+    }
+
+    fn date(&self, object: &Object, name: &'static str) -> Result<NaiveDate, DatasetError> {
+        let date = object.string(name)?;
+
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| object.error(DatasetErrorKind::InvalidDate(date.to_string())))
+    }
+
+    fn period(&self, object: &Object, value: &JsonValue) -> Result<Period, DatasetError> {
+        value
+            .as_f64()
+            .filter(|number| number.fract() == 0.0 && (1.0..=5.0).contains(number))
+            .map(|number| Period::ALL[number as usize - 1])
+            .ok_or_else(|| object.error(DatasetErrorKind::InvalidPeriod))
+    }
+
+    fn room(&self, object: &Object, name: &'static str) -> Result<Location, DatasetError> {
+        self.location(object, object.string(name)?)
+    }
+
+    fn location(&self, object: &Object, room: &str) -> Result<Location, DatasetError> {
+        let location = room
+            .parse()
+            .map_err(|_| object.error(DatasetErrorKind::InvalidRoom(room.to_string())))?;
+
+        if !self.locations.contains(&location) {
+            return Err(object.error(DatasetErrorKind::UndeclaredRoom(room.to_string())));
+        }
+
+        Ok(location)
+    }
+
+    // The co-teachers of an entry (should it be team-taught)
+    fn co_teachers(&self, object: &Object) -> Result<Vec<Teacher>, DatasetError> {
+        object
+            .optional_array("co_teachers")?
+            .iter()
+            .map(|value| {
+                let code = value.as_str().ok_or_else(|| {
+                    object.error(DatasetErrorKind::UnexpectedValue("co_teachers"))
+                })?;
+
+                Teacher::new(code.to_string())
+                    .map_err(|_| object.error(DatasetErrorKind::InvalidTeacher(code.to_string())))
+            })
+            .collect()
+    }
+
+    // The split rooms of an entry (should its class be split)
+    fn split_rooms(&self, object: &Object) -> Result<Vec<Location>, DatasetError> {
+        object
+            .optional_array("split_rooms")?
+            .iter()
+            .map(|value| {
+                let room = value.as_str().ok_or_else(|| {
+                    object.error(DatasetErrorKind::UnexpectedValue("split_rooms"))
+                })?;
+
+                self.location(object, room)
+            })
+            .collect()
+    }
+
+    // The rotation of an entry (should it rotate)
+    fn rotation(&self, object: &Object) -> Result<Rotation, DatasetError> {
+        let mut rotation = Rotation::new(self.date(object, "start")?);
+
+        for value in object.array("stages")? {
+            let stage = Object::new(value, object.line, "stages")?;
+
+            rotation = rotation.stage(self.teacher(&stage)?, self.room(&stage, "room")?);
+        }
+
+        Ok(rotation)
+    }
+
+    // The optional teacher of a lesson (or of a stage of its rotation)
+    fn teacher(&self, object: &Object) -> Result<Option<Teacher>, DatasetError> {
+        match object.get("teacher") {
+            Some(_) => {
+                let code = object.string("teacher")?;
+
+                Teacher::new(code.to_string())
+                    .map(Some)
+                    .map_err(|_| object.error(DatasetErrorKind::InvalidTeacher(code.to_string())))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn activity(&self, object: &Object) -> Result<Activity, DatasetError> {
+        let kind = object.string("kind")?;
+
+        Ok(match kind {
+            "lesson" => {
+                let subject = object.string("subject")?;
+                let class = object.string("class")?;
+
+                let teacher = self.teacher(object)?;
+
+                Activity::Lesson {
+                    subject: Subject::new(subject.to_string()).map_err(|_| {
+                        object.error(DatasetErrorKind::InvalidSubject(subject.to_string()))
+                    })?,
+                    class: Class::new(class.to_string()).map_err(|_| {
+                        object.error(DatasetErrorKind::InvalidClass(class.to_string()))
+                    })?,
+                    teacher,
+                    location: self.room(object, "room")?,
+                }
+            }
+            "registration" => Activity::Registration,
+            "break" => Activity::Break,
+            "independent-study" => Activity::SchoolStudy,
+            "home-study" => Activity::HomeStudy,
+            "miscellaneous" => Activity::Miscellaneous(object.string("description")?.to_string()),
+            "transit" => Activity::Transit {
+                from: self.room(object, "from")?,
+                to: self.room(object, "to")?,
+                duration: Duration::minutes(object.integer("minutes")?),
+            },
+            _ => return Err(object.error(DatasetErrorKind::UnknownActivity(kind.to_string()))),
+        })
+    }
+
+    fn calendar(&self, object: &Object) -> Result<AcademicCalendar, DatasetError> {
+        let invalid =
+            |object: &Object, error| object.error(DatasetErrorKind::InvalidCalendar(error));
+        let mut terms = Vec::new();
+
+        for value in object.array("terms")? {
+            let term = Object::new(value, object.line, "terms")?;
+            let (start, end) = (self.date(&term, "start")?, self.date(&term, "end")?);
+
+            terms.push(Term::new(start, end).map_err(|error| invalid(&term, error))?);
+        }
+
+        let mut calendar = AcademicCalendar::new(terms);
+
+        if let Some(value) = object.get("bells") {
+            let bells = Object::new(value, object.line, "bells")?;
+
+            self.bells(&bells, calendar.bell_schedules_mut())?;
+        }
+
+        for value in object.optional_array("exceptions")? {
+            let exception = Object::new(value, object.line, "exceptions")?;
+            let date = self.date(&exception, "date")?;
+            let kind = exception.string("kind")?;
+
+            let day = match kind {
+                "closed" => DayException::Closed,
+                "open" => DayException::Open,
+                "early-closure" => DayException::EarlyClosure {
+                    last_period: self.period(&exception, exception.require("last_period")?)?,
+                },
+                "suspended" => DayException::Suspended {
+                    periods: exception
+                        .array("periods")?
+                        .iter()
+                        .map(|period| self.period(&exception, period))
+                        .collect::<Result<_, _>>()?,
+                    activity: self.activity(&exception.object("activity")?)?,
+                },
+                "schedule" => {
+                    // The schools omitted from the day retain the schedules
+                    // of the calendar
+                    let mut schedules = *calendar.bell_schedules();
+
+                    self.bells(&exception.object("bells")?, &mut schedules)?;
+                    DayException::Schedule(schedules)
+                }
+                _ => {
+                    return Err(
+                        exception.error(DatasetErrorKind::UnknownException(kind.to_string()))
+                    )
+                }
+            };
+
+            calendar.add_exception(date, day);
+        }
+
+        match object.get("bank_holidays") {
+            Some(JsonValue::Bool(observe)) => calendar.observe_bank_holidays(*observe),
+            Some(_) => return Err(object.error(DatasetErrorKind::UnexpectedValue("bank_holidays"))),
+            None => {}
+        }
+
+        Ok(calendar)
+    }
+
+    // Parse the bell schedules of the `bells` object provided, replacing
+    // those of the `schedules` for the schools which are present
+    fn bells(&self, bells: &Object, schedules: &mut BellSchedules) -> Result<(), DatasetError> {
+        for (school, name) in [
+            (School::Highfield, "highfield"),
+            (School::Fearnhill, "fearnhill"),
+        ] {
+            if bells.get(name).is_none() {
+                continue;
+            }
+
+            let schedule = bells.object(name)?;
+            let range = |name: &'static str, value: &JsonValue| {
+                let error = || schedule.error(DatasetErrorKind::UnexpectedValue(name));
+                let time = |value: &JsonValue| {
+                    let time = value.as_str().ok_or_else(error)?;
+
+                    NaiveTime::parse_from_str(time, "%H:%M")
+                        .map(TimeOfDay::from)
+                        .map_err(|_| {
+                            schedule.error(DatasetErrorKind::InvalidTime(time.to_string()))
+                        })
+                };
+
+                match value.as_array() {
+                    Some([start, end]) => Ok(PeriodTime::new(time(start)?, time(end)?)),
+                    _ => Err(error()),
+                }
+            };
+
+            let periods = schedule.array("periods")?;
+            let periods: [_; 5] = match periods {
+                [a, b, c, d, e] => [
+                    range("periods", a)?,
+                    range("periods", b)?,
+                    range("periods", c)?,
+                    range("periods", d)?,
+                    range("periods", e)?,
+                ],
+                _ => return Err(schedule.error(DatasetErrorKind::UnexpectedValue("periods"))),
+            };
+
+            let bell_schedule = BellSchedule::new(
+                periods,
+                range("break", schedule.require("break")?)?,
+                range("lunch", schedule.require("lunch")?)?,
+            )
+            .map_err(|error| schedule.error(DatasetErrorKind::InvalidCalendar(error)))?;
+
+            schedules.set(school, bell_schedule);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeslot;
+
+    // The canonical representation of the dataset constructed by `dataset`
+    // (third-party tools rely upon this exact output)
+    const CANONICAL: &str = r#"{
+  "format": "timetableau-dataset",
+  "version": 1,
+  "locations": ["FH S13", "H201", "Hall"],
+  "entries": [
+    {"id": 0, "slot": "week1.mon.p1", "activity": {"kind": "registration"}},
+    {"id": 2, "slot": "week2.thu.p5", "activity": {"kind": "lesson", "subject": "Ma", "class": "10A/Ma1", "teacher": "JSM", "room": "H201"}},
+    {"id": 3, "slot": "week1.tue.p3", "activity": {"kind": "miscellaneous", "description": "Choir \"Tutti\""}}
+  ],
+  "overrides": [
+    {"slot": "week2.thu.p5", "start": "2023-09-04", "end": "2023-09-08", "room": "Hall"}
+  ],
+  "calendar": {
+    "terms": [
+      {"start": "2023-09-04", "end": "2023-10-20"}
+    ],
+    "exceptions": [
+      {"date": "2023-09-05", "kind": "early-closure", "last_period": 3},
+      {"date": "2023-09-06", "kind": "suspended", "periods": [1, 2], "activity": {"kind": "lesson", "subject": "Ex", "class": "Y11", "room": "FH S13"}}
+    ],
+    "bells": {
+      "highfield": {"periods": [["08:50", "09:50"], ["09:50", "10:50"], ["11:10", "12:10"], ["12:10", "13:10"], ["13:55", "14:55"]], "break": ["10:50", "11:10"], "lunch": ["13:10", "13:55"]},
+      "fearnhill": {"periods": [["08:45", "09:45"], ["09:45", "10:45"], ["11:05", "12:05"], ["12:05", "13:05"], ["13:50", "14:50"]], "break": ["10:45", "11:05"], "lunch": ["13:05", "13:50"]}
+    }
+  }
+}
+"#;
+
+    fn dataset() -> Dataset {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let lesson =
+            |subject: &str, class: &str, teacher: Option<&str>, room: &str| Activity::Lesson {
+                subject: Subject::new(subject.to_string()).unwrap(),
+                class: Class::new(class.to_string()).unwrap(),
+                teacher: teacher.map(|code| Teacher::new(code.to_string()).unwrap()),
+                location: room.parse().unwrap(),
+            };
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), Activity::Registration);
+        let removed = timetable.insert(timeslot!(W1MP2), Activity::Break);
+        timetable.insert(
+            timeslot!(W2RP5),
+            lesson("Ma", "10A/Ma1", Some("JSM"), "H201"),
+        );
+        timetable.insert(
+            timeslot!(W1TP3),
+            Activity::Miscellaneous("Choir \"Tutti\"".to_string()),
+        );
+        timetable.remove(removed);
+        timetable.override_room(timeslot!(W2RP5), date(4)..=date(8), "Hall".parse().unwrap());
+
+        let mut calendar = AcademicCalendar::new(vec![Term::new(
+            date(4),
+            NaiveDate::from_ymd_opt(2023, 10, 20).unwrap(),
+        )
+        .unwrap()]);
+        calendar.add_exception(
+            date(5),
+            DayException::EarlyClosure {
+                last_period: Period::Third,
+            },
+        );
+        calendar.add_exception(
+            date(6),
+            DayException::Suspended {
+                periods: vec![Period::First, Period::Second],
+                activity: lesson("Ex", "Y11", None, "FH S13"),
+            },
+        );
+
+        Dataset::new(timetable, calendar)
+    }
+
+    #[test]
+    fn dataset_conformance() {
+        let dataset = dataset();
+
+        assert_eq!(dataset.to_json(), CANONICAL);
+
+        let parsed = Dataset::from_json(CANONICAL).unwrap();
+
+        assert_eq!(parsed, dataset);
+        assert!(parsed.timetable.get(EntryId(1)).is_none());
+        assert_eq!(parsed.timetable.overrides(), dataset.timetable.overrides());
+
+        let mut restricted = dataset.clone();
+        restricted.timetable.override_location(
+            timeslot!(W1MP1),
+            NaiveDate::from_ymd_opt(2023, 9, 4).unwrap()
+                ..=NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(),
+            "FH S13".parse().unwrap(),
+            "Hall".parse().unwrap(),
+        );
+        let json = restricted.to_json();
+
+        assert!(json.contains("\"version\": 2,") && json.contains("\"from\": \"FH S13\""));
+        assert_eq!(Dataset::from_json(&json), Ok(restricted));
+
+        let mut spanning = dataset.clone();
+        spanning
+            .timetable
+            .insert_spanning(timeslot!(W1FP3), 2, Activity::SchoolStudy)
+            .unwrap();
+        let json = spanning.to_json();
+
+        assert!(json.contains("\"version\": 3,") && json.contains("\"periods\": 2, "));
+        assert_eq!(Dataset::from_json(&json), Ok(spanning));
+        assert_eq!(
+            Dataset::from_json(&json.replace("\"periods\": 2", "\"periods\": 4"))
+                .unwrap_err()
+                .kind,
+            DatasetErrorKind::InvalidSpan
+        );
+
+        // A double starting in the final period would span past the end of
+        // the day
+        assert_eq!(
+            Dataset::from_json(&json.replace("week1.fri.p3", "week1.fri.p5"))
+                .unwrap_err()
+                .kind,
+            DatasetErrorKind::InvalidSpan
+        );
+
+        let mut staffed = dataset.clone();
+        let lesson = Activity::Lesson {
+            subject: Subject::new("Sc".to_string()).unwrap(),
+            class: Class::new("10A".to_string()).unwrap(),
+            teacher: Some(Teacher::new("JSM".to_string()).unwrap()),
+            location: "H201".parse().unwrap(),
+        };
+        staffed
+            .timetable
+            .insert_entry(
+                Entry::new(timeslot!(W2MP1), lesson)
+                    .co_teacher(Teacher::new("ABC".to_string()).unwrap())
+                    .split_room("H202".parse().unwrap()),
+            )
+            .unwrap();
+        let json = staffed.to_json();
+
+        assert!(json.contains("\"version\": 4,"));
+        assert!(json.contains("\"co_teachers\": [\"ABC\"], \"split_rooms\": [\"H202\"], "));
+        assert!(staffed.locations().contains(&"H202".parse().unwrap()));
+        assert_eq!(Dataset::from_json(&json), Ok(staffed));
+        assert_eq!(
+            Dataset::from_json(&json.replace("[\"H202\"], ", "[\"H299\"], "))
+                .unwrap_err()
+                .kind,
+            DatasetErrorKind::UndeclaredRoom("H299".to_string())
+        );
+
+        let mut rotating = dataset.clone();
+        let rotation = Rotation::new(NaiveDate::from_ymd_opt(2023, 9, 4).unwrap())
+            .stage(None, "FH T1".parse().unwrap())
+            .stage(
+                Some(Teacher::new("ABC".to_string()).unwrap()),
+                "FH T2".parse().unwrap(),
+            );
+        let (id, entry) = rotating.timetable.lessons().next().unwrap();
+        let entry = entry.clone().rotating(rotation);
+        rotating.timetable.remove(id);
+        rotating.timetable.insert_entry(entry).unwrap();
+        let json = rotating.to_json();
+
+        assert!(json.contains("\"version\": 5,"));
+        assert!(json.contains(
+            "\"rotation\": {\"start\": \"2023-09-04\", \"stages\": [{\"room\": \"FH T1\"}, "
+        ));
+        assert!(rotating.locations().contains(&"FH T2".parse().unwrap()));
+        assert_eq!(Dataset::from_json(&json), Ok(rotating));
+
+        let mut holidays = dataset.clone();
+        holidays.calendar.observe_bank_holidays(false);
+        holidays.calendar.add_exception(
+            NaiveDate::from_ymd_opt(2023, 8, 28).unwrap(),
+            DayException::Open,
+        );
+        let json = holidays.to_json();
+
+        assert!(json.contains("\"version\": 6,"));
+        assert!(json.contains("\"kind\": \"open\"") && json.contains("\"bank_holidays\": false,"));
+        assert_eq!(Dataset::from_json(&json), Ok(holidays));
+
+        let mut scheduled = dataset.clone();
+        let mut schedules = BellSchedules::default();
+        schedules.set(School::Fearnhill, BellSchedule::highfield());
+        scheduled.calendar.add_exception(
+            NaiveDate::from_ymd_opt(2023, 9, 7).unwrap(),
+            DayException::Schedule(schedules),
+        );
+        let json = scheduled.to_json();
+
+        assert!(json.contains("\"version\": 7,"));
+        assert!(json.contains(
+            "\"kind\": \"schedule\", \"bells\": {\"highfield\": {\"periods\": [[\"08:50\", \"09:50\"], "
+        ));
+        assert_eq!(Dataset::from_json(&json), Ok(scheduled));
+
+        // Optional sections may be omitted, and unknown fields are ignored
+        let minimal = r#"{"format": "timetableau-dataset", "version": 1, "locations": [],
+            "entries": [], "generator": "another tool"}"#;
+
+        assert_eq!(
+            Dataset::from_json(minimal),
+            Ok(Dataset::new(Timetable::new(), AcademicCalendar::default()))
+        );
+    }
+
+    #[test]
+    fn dataset_errors() {
+        let error = |input: &str| Dataset::from_json(input).unwrap_err();
+
+        assert_eq!(
+            error(&CANONICAL.replace("\"version\": 1", "\"version\": 8")).kind,
+            DatasetErrorKind::UnsupportedVersion(8)
+        );
+        assert_eq!(
+            error(&CANONICAL.replace("\"FH S13\", ", "")),
+            DatasetError {
+                kind: DatasetErrorKind::UndeclaredRoom("FH S13".to_string()),
+                line: 19,
+            }
+        );
+        assert_eq!(
+            error(&CANONICAL.replace("week1.tue.p3", "week1.sat.p3")).kind,
+            DatasetErrorKind::InvalidSlot("week1.sat.p3".to_string())
+        );
+        assert_eq!(
+            error(&CANONICAL.replace("\"id\": 3", "\"id\": 0")).kind,
+            DatasetErrorKind::DuplicateId(0)
+        );
+        assert_eq!(error("{\"format\": ").kind, DatasetErrorKind::Syntax);
+    }
+}
@@ -0,0 +1,332 @@
+use crate::calendar::monday_of;
+use crate::render::travel_hint;
+use crate::{Activity, Dataset, Location, Period, School, Teacher, WeekCycle};
+use chrono::{NaiveDate, NaiveTime};
+use std::fmt::{self, Display, Formatter};
+
+// The indentation of the details of each item of a rendered day plan
+const INDENT: &str = "             ";
+
+/// The person for whom a [`DayPlan`] is produced (*see
+/// [`Dataset::day_plan_for`]*).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Attendee {
+    /// A member of staff, who follows their own timetable.
+    Teacher(Teacher),
+
+    /// A visitor (e.g., a supply teacher), who follows the timetable of the
+    /// `host` (e.g., the absent member of staff whose lessons they cover).
+    Visitor {
+        /// The name of the visitor (e.g., `Mr Jones (supply)`).
+        name: String,
+
+        /// The member of staff whose timetable the visitor follows.
+        host: Teacher,
+    },
+}
+
+impl Attendee {
+    /// Retrieves the name of the attendee (i.e., the staff code of a member
+    /// of staff, or the name of a visitor).
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Teacher(teacher) => teacher.code(),
+            Self::Visitor { name, .. } => name,
+        }
+    }
+
+    /// Retrieves the member of staff whose timetable the attendee follows.
+    pub fn teacher(&self) -> &Teacher {
+        match self {
+            Self::Teacher(teacher) | Self::Visitor { host: teacher, .. } => teacher,
+        }
+    }
+}
+
+/// An item of a [`DayPlan`] (i.e., a lesson, break, or lunch).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanItem {
+    /// The time at which the item starts.
+    pub start: NaiveTime,
+
+    /// The time at which the item ends.
+    pub end: NaiveTime,
+
+    /// The title of the item (e.g., `P1 Ma 10A`, or `Break`).
+    pub title: String,
+
+    /// Where the item takes place (e.g., the
+    /// [verbose name](Location::verbose_name) of the room of a lesson,
+    /// followed by the codes of its rooms -- including any split rooms).
+    pub location: String,
+
+    /// How to reach the item from the previous room (e.g., `walk to Parker
+    /// Block`), should it be in a different area or at the other site.
+    pub route: Option<String>,
+}
+
+/// The printable plan of the day of a member of staff or visitor (*see
+/// [`Dataset::day_plan_for`]*).
+///
+/// The plan is [displayed](Display) as a sheet -- a heading, followed by
+/// each item, its location, and the route to it (should there be one).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayPlan {
+    /// The name of the attendee (*see [`Attendee::name`]*).
+    pub name: String,
+
+    /// The date of the plan.
+    pub date: NaiveDate,
+
+    /// The items of the plan, in chronological order.
+    pub items: Vec<PlanItem>,
+}
+
+impl Display for DayPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Day plan for {} -- {}",
+            self.name,
+            self.date.format("%A %d %B")
+        )?;
+
+        if self.items.is_empty() {
+            return writeln!(f, "No lessons");
+        }
+
+        for item in &self.items {
+            writeln!(
+                f,
+                "{}-{}  {}",
+                item.start.format("%H:%M"),
+                item.end.format("%H:%M"),
+                item.title
+            )?;
+            writeln!(f, "{}{}", INDENT, item.location)?;
+
+            if let Some(route) = &item.route {
+                writeln!(f, "{}-> {}", INDENT, route)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Dataset {
+    /// Produces the plan of the `date` provided for the `attendee` provided
+    /// (e.g., the sheet handed to a supply teacher every morning): each of
+    /// their lessons, with the verbose name of its room and the route from
+    /// the previous room, and the staff room in which to spend break and
+    /// lunch.
+    ///
+    /// The lessons are those which actually take place (*see
+    /// [`Timetable::occurrences`](crate::Timetable::occurrences)*), at the
+    /// times of the [bell schedule](crate::BellSchedule) of the school of
+    /// each room. Break and lunch are spent at the school of the lesson
+    /// straight after them (or, should there be none, of the preceding
+    /// lesson), such that any travel between the sites takes place during
+    /// them.
+    ///
+    /// # Remarks
+    ///
+    /// The week of the date is determined by a [`WeekCycle`] anchored on the
+    /// Monday of the week in which the first term of the calendar starts.
+    /// Should the date not be a school day, or should the attendee have no
+    /// lessons, the plan has no items.
+    pub fn day_plan_for(&self, attendee: &Attendee, date: NaiveDate) -> DayPlan {
+        let mut plan = DayPlan {
+            name: attendee.name().to_string(),
+            date,
+            items: Vec::new(),
+        };
+
+        let Some(term) = self.calendar.terms().first() else {
+            return plan;
+        };
+        let Ok(cycle) = WeekCycle::new(monday_of(term.start()), self.calendar.clone()) else {
+            return plan;
+        };
+
+        let schedules = self.calendar.bell_schedules_on(date);
+        let teacher = attendee.teacher();
+        let mut lessons: Vec<(Period, Activity, Location, Vec<Location>)> = self
+            .timetable
+            .occurrences(&cycle, date)
+            .into_iter()
+            .filter_map(|occurrence| {
+                let entry = occurrence.entry.and_then(|id| self.timetable.get(id));
+
+                // The lessons the attendee co-teaches are also theirs
+                let teaches = occurrence.activity.teacher() == Some(teacher)
+                    || entry.is_some_and(|entry| entry.co_teachers.contains(teacher));
+
+                if !teaches {
+                    return None;
+                }
+
+                let location = *occurrence.activity.location()?;
+                let split_rooms = entry.map_or_else(Vec::new, |entry| entry.split_rooms.clone());
+
+                Some((
+                    occurrence.slot.period,
+                    occurrence.activity,
+                    location,
+                    split_rooms,
+                ))
+            })
+            .collect();
+
+        if lessons.is_empty() {
+            return plan;
+        }
+
+        lessons.sort_by_key(|(period, ..)| *period);
+
+        // The room and end of the previous lesson
+        let mut previous: Option<(Location, NaiveTime)> = None;
+
+        for (period, activity, location, split_rooms) in &lessons {
+            let (start, end) = schedules.get(location.school()).period(*period).to_naive();
+
+            let Activity::Lesson { subject, class, .. } = activity else {
+                continue;
+            };
+
+            // The split rooms of a lesson follow its room
+            let codes: Vec<String> = std::iter::once(location)
+                .chain(split_rooms)
+                .map(Location::to_string)
+                .collect();
+
+            plan.items.push(PlanItem {
+                start,
+                end,
+                title: format!("P{} {} {}", *period as usize + 1, subject, class),
+                location: format!("{} ({})", location.verbose_name(), codes.join(", ")),
+                route: previous
+                    .and_then(|(from, ended)| travel_hint(&from, location, start - ended)),
+            });
+
+            previous = Some((*location, end));
+        }
+
+        // Break follows the second period, and lunch the fourth
+        for (name, after) in [("Break", Period::Second), ("Lunch", Period::Fourth)] {
+            let preceding = lessons.iter().rev().find(|(period, ..)| *period <= after);
+            let following = lessons.iter().find(|(period, ..)| *period > after);
+            let next = following.filter(|(period, ..)| Some(*period) == after.next());
+            let school = next
+                .or(preceding)
+                .or(following)
+                .map_or(School::Highfield, |(_, _, location, _)| location.school());
+
+            let schedule = schedules.get(school);
+            let (start, end) = match after {
+                Period::Second => schedule.break_time(),
+                _ => schedule.lunch(),
+            }
+            .to_naive();
+
+            plan.items.push(PlanItem {
+                start,
+                end,
+                title: name.to_string(),
+                location: format!("Staff room, {}", school),
+                route: None,
+            });
+        }
+
+        plan.items.sort_by_key(|item| item.start);
+        plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Entry, Subject, Term, Timetable};
+
+    #[test]
+    fn supply_day_plan() {
+        let teacher = Teacher::new("JSM".to_string()).unwrap();
+        let lesson = |class: &str, room: &str| Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Some(teacher.clone()),
+            location: room.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("10A", "H201"));
+        timetable.insert(timeslot!(W1MP2), lesson("11B", "P105"));
+        timetable.insert(timeslot!(W1MP5), lesson("12C", "FH M1"));
+        timetable.insert(timeslot!(W1TP1), lesson("13D", "H201"));
+
+        // Term starts on a Wednesday, so the cycle is anchored on the Monday
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date(6), date(29)).unwrap()]);
+        let dataset = Dataset::new(timetable, calendar);
+
+        let visitor = Attendee::Visitor {
+            name: "Mr Jones (supply)".to_string(),
+            host: teacher.clone(),
+        };
+
+        assert_eq!(
+            dataset.day_plan_for(&visitor, date(18)).to_string(),
+            "Day plan for Mr Jones (supply) -- Monday 18 September\n\
+             08:50-09:50  P1 Ma 10A\n\
+             \x20            Howard Block, Floor 2, Room 01 (H201)\n\
+             09:50-10:50  P2 Ma 11B\n\
+             \x20            Parker Block, Floor 1, Room 05 (P105)\n\
+             \x20            -> walk to Parker Block\n\
+             10:50-11:10  Break\n\
+             \x20            Staff room, Highfield\n\
+             13:05-13:50  Lunch\n\
+             \x20            Staff room, Fearnhill\n\
+             13:50-14:50  P5 Ma 12C\n\
+             \x20            Fearnhill Mathematics, Room 1 (FH M1)\n\
+             \x20            -> travel to Fearnhill (180 minutes)\n"
+        );
+
+        // Before the first term, and on a day without lessons
+        assert!(dataset
+            .day_plan_for(&Attendee::Teacher(teacher.clone()), date(4))
+            .items
+            .is_empty());
+        assert_eq!(
+            dataset
+                .day_plan_for(&Attendee::Teacher(teacher.clone()), date(20))
+                .to_string(),
+            "Day plan for JSM -- Wednesday 20 September\nNo lessons\n"
+        );
+
+        // The lessons co-taught by a teacher are in their plan (with the
+        // split rooms of the lesson)
+        let mut timetable = Timetable::new();
+        timetable
+            .insert_entry(
+                Entry::new(
+                    timeslot!(W1MP1),
+                    Activity::Lesson {
+                        subject: Subject::new("PE".to_string()).unwrap(),
+                        class: Class::new("10A".to_string()).unwrap(),
+                        teacher: Some(Teacher::new("ABC".to_string()).unwrap()),
+                        location: "Hall".parse().unwrap(),
+                    },
+                )
+                .co_teacher(teacher.clone())
+                .split_room("Sports Hall".parse().unwrap()),
+            )
+            .unwrap();
+
+        let calendar = AcademicCalendar::new(vec![Term::new(date(6), date(29)).unwrap()]);
+        let plan =
+            Dataset::new(timetable, calendar).day_plan_for(&Attendee::Teacher(teacher), date(18));
+
+        assert_eq!(plan.items[0].title, "P1 PE 10A");
+        assert_eq!(plan.items[0].location, "Highfield Hall (Hall, Sports Hall)");
+    }
+}
@@ -0,0 +1,130 @@
+//! Coursework deadlines, which are exported alongside the lessons of a
+//! timetable.
+//!
+//! A [`Deadline`] is set for a class and subject on a fixed date (e.g., the
+//! submission of an NEA draft) -- unlike [homework](crate::homework), it is
+//! not relative to the lessons of the class. A [`Deadlines`] registry is
+//! merged with a timetable's iCalendar feed as all-day items (*see
+//! [`IcsExporter::export_with_deadlines`](crate::export::ics::IcsExporter::export_with_deadlines)*),
+//! such that the calendars subscribed to by students show their deadlines
+//! alongside their lessons:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::deadlines::{Deadline, Deadlines};
+//! use timetableau::{timeslot, Activity, Class, Subject, Timetable};
+//!
+//! let class = Class::new("13A/Hi1".to_string()).unwrap();
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+//!     subject: Subject::new("History".to_string()).unwrap(),
+//!     class: class.clone(),
+//!     teacher: None,
+//!     location: "H201".parse().unwrap(),
+//! });
+//!
+//! let mut deadlines = Deadlines::new();
+//! deadlines.add(Deadline {
+//!     subject: Subject::new("History".to_string()).unwrap(),
+//!     class,
+//!     title: "NEA first draft".to_string(),
+//!     due: NaiveDate::from_ymd_opt(2023, 10, 20).unwrap(),
+//! });
+//! deadlines.add(Deadline {
+//!     subject: Subject::new("Art".to_string()).unwrap(),
+//!     class: Class::new("11B/Ar1".to_string()).unwrap(),
+//!     title: "Final piece".to_string(),
+//!     due: NaiveDate::from_ymd_opt(2023, 10, 27).unwrap(),
+//! });
+//!
+//! // Only the deadlines of the classes of the timetable are relevant to it
+//! assert_eq!(deadlines.relevant_to(&timetable).count(), 1);
+//! ```
+
+use crate::{Activity, Class, Subject, Timetable};
+use chrono::NaiveDate;
+use std::ops::RangeInclusive;
+
+/// A coursework deadline of a class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deadline {
+    /// The subject of the coursework.
+    pub subject: Subject,
+
+    /// The class whose coursework is due.
+    pub class: Class,
+
+    /// The title of the coursework (e.g., `NEA first draft`).
+    pub title: String,
+
+    /// The date on which the coursework is due.
+    pub due: NaiveDate,
+}
+
+/// The registry of coursework [`Deadline`]s.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deadlines {
+    deadlines: Vec<Deadline>,
+}
+
+impl Deadlines {
+    /// Creates a new `Deadlines` registry without any deadlines.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `deadline` provided, returning its index within
+    /// [`Self::deadlines`].
+    pub fn add(&mut self, deadline: Deadline) -> usize {
+        self.deadlines.push(deadline);
+        self.deadlines.len() - 1
+    }
+
+    /// Removes the deadline at the `index` provided, returning it.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if there is no deadline at the `index` provided -- the
+    /// indexes of later deadlines are decremented.
+    pub fn remove(&mut self, index: usize) -> Option<Deadline> {
+        (index < self.deadlines.len()).then(|| self.deadlines.remove(index))
+    }
+
+    /// Retrieves every deadline (in the order in which they were added).
+    pub fn deadlines(&self) -> &[Deadline] {
+        &self.deadlines
+    }
+
+    /// Retrieves the deadlines (and their indexes) due within the range of
+    /// `dates` provided.
+    pub fn between(
+        &self,
+        dates: RangeInclusive<NaiveDate>,
+    ) -> impl Iterator<Item = (usize, &Deadline)> + '_ {
+        self.deadlines
+            .iter()
+            .enumerate()
+            .filter(move |(_, deadline)| dates.contains(&deadline.due))
+    }
+
+    /// Retrieves the deadlines (and their indexes) of the classes which have
+    /// lessons in the `timetable` provided (e.g., the deadlines of a
+    /// student, given their timetable).
+    pub fn relevant_to<'a>(
+        &'a self,
+        timetable: &'a Timetable,
+    ) -> impl Iterator<Item = (usize, &'a Deadline)> + 'a {
+        self.deadlines.iter().enumerate().filter(|(_, deadline)| {
+            timetable
+                .lessons()
+                .for_class(deadline.class.reference())
+                .any(|(_, entry)| match &entry.activity {
+                    Activity::Lesson { subject, .. } => *subject == deadline.subject,
+                    _ => false,
+                })
+        })
+    }
+}
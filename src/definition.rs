@@ -0,0 +1,1022 @@
+use crate::timeslot::{parse_day, parse_period, parse_week};
+use crate::timetable::{self, SINGLE};
+use crate::toml::{self, KeyValue, Spanned, SyntaxError, TomlValue};
+use crate::{
+    Activity, Class, Entry, Location, ParseOptions, Rotation, Subject, Teacher, TimeSlot,
+    Timetable, Week,
+};
+use chrono::{Duration, NaiveDate};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+use std::ops::Range;
+
+/// The reason a timetable definition could not be parsed.
+///
+/// *See [`TomlError`] for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TomlErrorKind {
+    /// The definition is not valid TOML (or uses a feature of TOML which is
+    /// not supported -- e.g., arrays of tables).
+    Syntax(&'static str),
+
+    /// A key does not take the form `<week>.<day>.<period>` (e.g.,
+    /// `week1.mon.p3`).
+    InvalidKey,
+
+    /// The week of a key is invalid.
+    InvalidWeek(String),
+
+    /// The day of a key is invalid (or is a Saturday or Sunday).
+    InvalidDay(String),
+
+    /// The period of a key is invalid.
+    InvalidPeriod(String),
+
+    /// The same [`TimeSlot`] is defined more than once -- a `TimeSlot` with
+    /// multiple activities must define them as an array instead.
+    DuplicateSlot,
+
+    /// A value is neither a string nor an inline table (or, within a lesson,
+    /// a field is not a string).
+    UnexpectedValue,
+
+    /// A lesson contains an unknown field.
+    UnknownField(String),
+
+    /// A lesson does not contain a required field.
+    MissingField(&'static str),
+
+    /// The subject of a lesson is invalid.
+    InvalidSubject(String),
+
+    /// The class of a lesson is invalid.
+    InvalidClass(String),
+
+    /// The teacher of a lesson is invalid.
+    InvalidTeacher(String),
+
+    /// The room of a lesson is neither a Highfield room nor a Fearnhill room.
+    InvalidRoom(String),
+
+    /// The room of a lesson is a valid room code, but is not in the registry
+    /// of known rooms (*see [`ParseOptions::strict`]*).
+    UnknownRoom(String),
+
+    /// A saved timetable was saved in a version of the format which is not
+    /// supported (e.g., by a newer version of the crate) -- *see
+    /// [`persist`](crate::persist) for more information*.
+    UnsupportedVersion(i64),
+
+    /// A date of a saved timetable is not of the form `YYYY-MM-DD`.
+    InvalidDate(String),
+
+    /// A room override of a saved timetable ends before it starts.
+    EmptyOverride,
+
+    /// An activity cannot span the number of periods provided (e.g., as it
+    /// would extend past the final period of the day).
+    InvalidPeriods(i64),
+
+    /// The label of a seat of a seating plan was invalid, or there is no
+    /// desk at that seat.
+    InvalidSeat(String),
+}
+
+impl Display for TomlErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax(message) => f.write_str(message),
+            Self::InvalidKey => f.write_str("expected a key of the form `<week>.<day>.<period>`"),
+            Self::InvalidWeek(week) => write!(f, "invalid week `{}`", week),
+            Self::InvalidDay(day) => write!(f, "invalid day `{}`", day),
+            Self::InvalidPeriod(period) => write!(f, "invalid period `{}`", period),
+            Self::DuplicateSlot => f.write_str("time slot defined more than once"),
+            Self::UnexpectedValue => f.write_str("expected a string or an inline table"),
+            Self::UnknownField(field) => write!(f, "unknown field `{}`", field),
+            Self::MissingField(field) => write!(f, "missing field `{}`", field),
+            Self::InvalidSubject(subject) => write!(f, "invalid subject `{}`", subject),
+            Self::InvalidClass(class) => write!(f, "invalid class `{}`", class),
+            Self::InvalidTeacher(teacher) => write!(f, "invalid teacher `{}`", teacher),
+            Self::InvalidRoom(room) => write!(f, "invalid room code `{}`", room),
+            Self::UnknownRoom(room) => write!(f, "unknown room `{}`", room),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version `{}`", version)
+            }
+            Self::InvalidDate(date) => write!(f, "invalid date `{}`", date),
+            Self::EmptyOverride => f.write_str("room override ends before it starts"),
+            Self::InvalidPeriods(periods) => {
+                write!(f, "the activity cannot span `{}` periods", periods)
+            }
+            Self::InvalidSeat(seat) => write!(f, "invalid seat `{}`", seat),
+        }
+    }
+}
+
+/// An error returned when a timetable definition cannot be parsed.
+///
+/// Each error points at the offending part of the definition -- `span` is
+/// the byte range of the definition at fault (e.g., the room code of a
+/// lesson whose room is invalid), whereas `line` and `column` (both starting
+/// from `1`) are the position at which that range starts.
+///
+/// *See [`Timetable::from_toml_str`] for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TomlError {
+    /// The reason the definition could not be parsed.
+    pub kind: TomlErrorKind,
+
+    /// The byte range of the definition at fault.
+    pub span: Range<usize>,
+
+    /// The line on which the `span` starts.
+    pub line: usize,
+
+    /// The column (in characters) at which the `span` starts.
+    pub column: usize,
+}
+
+impl TomlError {
+    pub(crate) fn new(input: &str, span: Range<usize>, kind: TomlErrorKind) -> Self {
+        let before = &input[..span.start];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        Self {
+            kind,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            span,
+        }
+    }
+
+    pub(crate) fn syntax(input: &str, syntax: SyntaxError) -> Self {
+        Self::new(input, syntax.span, TomlErrorKind::Syntax(syntax.message))
+    }
+}
+
+impl Display for TomlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (line {}, column {})",
+            self.kind, self.line, self.column
+        )
+    }
+}
+
+impl Error for TomlError {}
+
+impl Timetable {
+    /// Parses a `Timetable` from its human-editable TOML definition.
+    ///
+    /// Each activity is defined by a key of the form `<week>.<day>.<period>`
+    /// (usually within a `[week1]` or `[week2]` table):
+    ///
+    /// ```toml
+    /// [week1]
+    /// mon.p1 = "Registration"
+    /// mon.p3 = { subject = "Ma", class = "10A/Ma1", teacher = "JSM", room = "H201" }
+    ///
+    /// [week2]
+    /// tue.p5 = [
+    ///     { subject = "Chess Club", class = "Chess", room = "FH L3" },
+    ///     "Home Study",
+    /// ]
+    /// ```
+    ///
+    /// * Weeks, days, and periods accept the same notations as the importers
+    ///   (e.g., `weekA` for week one, `thursday` or `r`, and `p3` or `3`).
+    ///
+    /// * [Lessons](Activity::Lesson) are inline tables -- `subject`,
+    ///   `class`, and `room` are required, and `teacher` is optional.
+    ///
+    /// * [Transits](Activity::Transit) are inline tables of their `from` and
+    ///   `to` rooms, and their duration in `minutes`.
+    ///
+    /// * An activity spanning multiple periods (e.g., a double lesson) is an
+    ///   inline table with the number of `periods` it spans -- a lesson (or a
+    ///   transit) includes it as a field, whereas any other activity is
+    ///   written as `{ activity = "Independent Study", periods = 2 }`.
+    ///
+    /// * A team-taught lesson (or a lesson split across multiple rooms)
+    ///   includes arrays of its `co_teachers` (as staff codes) and its
+    ///   `split_rooms` (as room codes) -- *see [`Entry::co_teachers`] and
+    ///   [`Entry::split_rooms`]*.
+    ///
+    /// * A lesson which rotates between teachers and rooms includes its
+    ///   `rotation`, an inline table with the `start` date of the rotation
+    ///   and its `stages` (each with a `room` and an optional `teacher`) --
+    ///   *see [`Entry::rotation`]*:
+    ///
+    ///   ```toml
+    ///   [week1]
+    ///   mon.p1 = { subject = "DT", class = "9A", room = "FH T1", rotation = { start = "2023-09-04", stages = [{ room = "FH T1" }, { teacher = "ABC", room = "FH T2" }] } }
+    ///   ```
+    ///
+    /// * Any other activity is a string -- `Registration`, `Break`,
+    ///   `Independent Study`, and `Home Study` are their respective
+    ///   activities, whereas any other string is a
+    ///   [miscellaneous](Activity::Miscellaneous) activity.
+    ///
+    /// * A time slot with multiple activities is an array of activities.
+    ///
+    /// # Errors
+    ///
+    /// A [`TomlError`] pointing at the first problem found within the
+    /// definition (e.g., an invalid room code).
+    pub fn from_toml_str(input: &str) -> Result<Self, TomlError> {
+        Self::from_toml_str_with(input, &ParseOptions::default())
+    }
+
+    /// Parses a human-editable TOML timetable definition, using the
+    /// `options` provided to parse room codes.
+    ///
+    /// *See [`from_toml_str`](Self::from_toml_str) for more information*.
+    ///
+    /// # Errors
+    ///
+    /// A [`TomlError`] pointing at the first problem found within the
+    /// definition -- should the `options` be [strict](ParseOptions::strict),
+    /// this includes any room which is not in their registry.
+    pub fn from_toml_str_with(input: &str, options: &ParseOptions) -> Result<Self, TomlError> {
+        let pairs = toml::parse(input).map_err(|syntax| TomlError::syntax(input, syntax))?;
+        let mut timetable = Timetable::new();
+
+        for entry in entries(input, pairs, options, true)? {
+            timetable.push_entry(entry);
+        }
+
+        Ok(timetable)
+    }
+
+    /// Converts the `Timetable` into its human-editable TOML definition.
+    ///
+    /// The definition can be parsed back into an identical `Timetable` using
+    /// [`from_toml_str`](Self::from_toml_str) -- note that only the entries
+    /// of the `Timetable` are included (i.e., room overrides and the history
+    /// of the `Timetable` are not).
+    pub fn to_toml_string(&self) -> String {
+        let mut entries: Vec<_> = self.entries().collect();
+        entries.sort_by_key(|(id, entry)| (entry.slot.index(), *id));
+
+        let mut output = String::new();
+        let mut week = None;
+        let mut index = 0;
+
+        while index < entries.len() {
+            let slot = entries[index].1.slot;
+            let count = entries[index..]
+                .iter()
+                .take_while(|(_, entry)| entry.slot == slot)
+                .count();
+
+            if week != Some(slot.week) {
+                let header = match slot.week {
+                    Week::WeekOne => "[week1]",
+                    Week::WeekTwo => "[week2]",
+                };
+
+                if week.is_some() {
+                    output.push('\n');
+                }
+
+                output.push_str(header);
+                output.push('\n');
+                week = Some(slot.week);
+            }
+
+            let _ = write!(
+                output,
+                "{}.p{} = ",
+                slot.day.to_string().to_ascii_lowercase(),
+                slot.period as usize + 1
+            );
+
+            let activities = &entries[index..index + count];
+
+            if let [(_, entry)] = activities {
+                write_entry(&mut output, entry);
+            } else {
+                output.push_str("[\n");
+
+                for (_, entry) in activities {
+                    output.push_str("    ");
+                    write_entry(&mut output, entry);
+                    output.push_str(",\n");
+                }
+
+                output.push(']');
+            }
+
+            output.push('\n');
+            index += count;
+        }
+
+        output
+    }
+}
+
+/// Parses the entries defined by the key/value `pairs` of a TOML timetable
+/// definition (in the order in which they are defined) -- `rotating` being
+/// whether lessons may rotate, which version 4 of the
+/// [persisted format](crate::persist) could not record.
+pub(crate) fn entries(
+    input: &str,
+    pairs: Vec<KeyValue>,
+    options: &ParseOptions,
+    rotating: bool,
+) -> Result<Vec<Entry>, TomlError> {
+    parse_entries(input, pairs, options, true, rotating)
+}
+
+/// Parses the activities defined by the key/value `pairs` of a TOML timetable
+/// definition as it was before version 4 of the
+/// [persisted format](crate::persist) (i.e., without any activities spanning
+/// multiple periods).
+pub(crate) fn activities(
+    input: &str,
+    pairs: Vec<KeyValue>,
+    options: &ParseOptions,
+) -> Result<Vec<(TimeSlot, Activity)>, TomlError> {
+    let entries = parse_entries(input, pairs, options, false, false)?;
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| (entry.slot, entry.activity))
+        .collect())
+}
+
+// Parse the entries of a TOML timetable definition (`extended` being whether
+// the fields introduced by version 4 of the persisted format may be used, and
+// `rotating` whether the rotations introduced by version 5 may be used)
+fn parse_entries(
+    input: &str,
+    pairs: Vec<KeyValue>,
+    options: &ParseOptions,
+    extended: bool,
+    rotating: bool,
+) -> Result<Vec<Entry>, TomlError> {
+    let error = |span: &Range<usize>, kind| TomlError::new(input, span.clone(), kind);
+
+    let mut entries = Vec::new();
+    let mut defined = HashSet::new();
+
+    for (key, (span, value)) in pairs {
+        let slot = slot(input, &key)?;
+
+        if !defined.insert(slot) {
+            let span = key[0].0.start..key[key.len() - 1].0.end;
+            return Err(error(&span, TomlErrorKind::DuplicateSlot));
+        }
+
+        let activities = match value {
+            TomlValue::Array(values) => values,
+            value => vec![(span, value)],
+        };
+
+        for value in activities {
+            let entry = entry(slot, value, options, extended, rotating)
+                .map_err(|(span, kind)| error(&span, kind))?;
+            entries.push(entry);
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Parses a key of the form `<week>.<day>.<period>` (e.g., `week1.mon.p3`)
+/// into the [`TimeSlot`] it refers to.
+pub(crate) fn slot(input: &str, key: &[Spanned<String>]) -> Result<TimeSlot, TomlError> {
+    let error = |span: &Range<usize>, kind| TomlError::new(input, span.clone(), kind);
+
+    let ((week_span, week), (day_span, day), (period_span, period)) = match key {
+        [week, day, period] => (week, day, period),
+        // Point at the last part of the key (as the first part may be the
+        // name of a table on an earlier line)
+        _ => {
+            let (span, _) = &key[key.len() - 1];
+            return Err(error(span, TomlErrorKind::InvalidKey));
+        }
+    };
+
+    Ok(TimeSlot {
+        week: parse_week(week)
+            .ok_or_else(|| error(week_span, TomlErrorKind::InvalidWeek(week.clone())))?,
+        day: parse_day(day)
+            .ok_or_else(|| error(day_span, TomlErrorKind::InvalidDay(day.clone())))?,
+        period: parse_period(period)
+            .ok_or_else(|| error(period_span, TomlErrorKind::InvalidPeriod(period.clone())))?,
+    })
+}
+
+// Convert a single value of the definition into an entry starting during the
+// `slot` provided (`extended` being whether the value may span multiple
+// periods, and have co-teachers and split rooms, and `rotating` whether it may
+// have a rotation)
+fn entry(
+    slot: TimeSlot,
+    (span, value): Spanned<TomlValue>,
+    options: &ParseOptions,
+    extended: bool,
+    rotating: bool,
+) -> Result<Entry, Spanned<TomlErrorKind>> {
+    let mut members = match value {
+        TomlValue::String(name) => return Ok(Entry::new(slot, named(name))),
+        TomlValue::Table(members) => members,
+        _ => return Err((span, TomlErrorKind::UnexpectedValue)),
+    };
+
+    let mut periods = SINGLE;
+
+    if let Some(index) = members
+        .iter()
+        .position(|((_, key), _)| extended && key == "periods")
+    {
+        let (_, (value_span, value)) = members.remove(index);
+        let count = match value {
+            TomlValue::Integer(count) => count,
+            _ => return Err((value_span, TomlErrorKind::UnexpectedValue)),
+        };
+
+        periods = u8::try_from(count)
+            .ok()
+            .and_then(|count| timetable::span(slot, count).ok())
+            .ok_or((value_span, TomlErrorKind::InvalidPeriods(count)))?;
+    }
+
+    let mut co_teachers = Vec::new();
+    let mut split_rooms = Vec::new();
+    let mut staffing = None;
+
+    while let Some(index) = members
+        .iter()
+        .position(|((_, key), _)| extended && (key == "co_teachers" || key == "split_rooms"))
+    {
+        let ((key_span, key), (value_span, value)) = members.remove(index);
+        let values = match value {
+            TomlValue::Array(values) => values,
+            _ => return Err((value_span, TomlErrorKind::UnexpectedValue)),
+        };
+
+        for (span, value) in values {
+            let code = match value {
+                TomlValue::String(code) => code,
+                _ => return Err((span, TomlErrorKind::UnexpectedValue)),
+            };
+
+            if key == "co_teachers" {
+                co_teachers.push(
+                    Teacher::new(code.clone())
+                        .map_err(|_| (span, TomlErrorKind::InvalidTeacher(code)))?,
+                );
+            } else {
+                split_rooms.push(location((span, code), options)?);
+            }
+        }
+
+        staffing.get_or_insert((key_span, key));
+    }
+
+    let mut rotation = None;
+
+    if let Some(index) = members
+        .iter()
+        .position(|((_, key), _)| rotating && key == "rotation")
+    {
+        let ((key_span, key), value) = members.remove(index);
+
+        rotation = Some(parse_rotation(value, options)?);
+        staffing.get_or_insert((key_span, key));
+    }
+
+    // A table with only the name of an activity is a spanning activity
+    // other than a lesson (or a transit)
+    let activity = match &members[..] {
+        [((_, key), (_, TomlValue::String(_)))] if extended && key == "activity" => {
+            match members.pop() {
+                Some((_, (_, TomlValue::String(name)))) => named(name),
+                _ => unreachable!(),
+            }
+        }
+        _ => activity(span, members, options)?,
+    };
+
+    // Only lessons may be team-taught (or split across multiple rooms), or
+    // rotate
+    if let Some((key_span, key)) = staffing {
+        if !matches!(activity, Activity::Lesson { .. }) {
+            return Err((key_span, TomlErrorKind::UnknownField(key)));
+        }
+    }
+
+    Ok(Entry {
+        periods,
+        co_teachers,
+        split_rooms,
+        rotation,
+        ..Entry::new(slot, activity)
+    })
+}
+
+// Convert the inline table of a lesson's rotation into the rotation
+fn parse_rotation(
+    (span, value): Spanned<TomlValue>,
+    options: &ParseOptions,
+) -> Result<Rotation, Spanned<TomlErrorKind>> {
+    let mut start = None;
+    let mut stages = None;
+
+    let members = match value {
+        TomlValue::Table(members) => members,
+        _ => return Err((span, TomlErrorKind::UnexpectedValue)),
+    };
+
+    for ((key_span, key), (value_span, value)) in members {
+        match (key.as_str(), value) {
+            ("start", TomlValue::String(date)) => start = Some((value_span, date)),
+            ("stages", TomlValue::Array(values)) => stages = Some(values),
+            ("start" | "stages", _) => return Err((value_span, TomlErrorKind::UnexpectedValue)),
+            _ => return Err((key_span, TomlErrorKind::UnknownField(key))),
+        }
+    }
+
+    let (start_span, start) =
+        start.ok_or_else(|| (span.clone(), TomlErrorKind::MissingField("start")))?;
+    let start = NaiveDate::parse_from_str(&start, "%Y-%m-%d")
+        .map_err(|_| (start_span, TomlErrorKind::InvalidDate(start)))?;
+    let stages = stages.ok_or((span, TomlErrorKind::MissingField("stages")))?;
+
+    let mut rotation = Rotation::new(start);
+
+    for (span, value) in stages {
+        let mut teacher = None;
+        let mut room = None;
+
+        let members = match value {
+            TomlValue::Table(members) => members,
+            _ => return Err((span, TomlErrorKind::UnexpectedValue)),
+        };
+
+        for ((key_span, key), (value_span, value)) in members {
+            let field = match key.as_str() {
+                "teacher" => &mut teacher,
+                "room" => &mut room,
+                _ => return Err((key_span, TomlErrorKind::UnknownField(key))),
+            };
+
+            match value {
+                TomlValue::String(value) => *field = Some((value_span, value)),
+                _ => return Err((value_span, TomlErrorKind::UnexpectedValue)),
+            }
+        }
+
+        let teacher = match teacher {
+            Some((span, code)) => Some(
+                Teacher::new(code.clone())
+                    .map_err(|_| (span, TomlErrorKind::InvalidTeacher(code)))?,
+            ),
+            None => None,
+        };
+        let room = room.ok_or((span, TomlErrorKind::MissingField("room")))?;
+
+        rotation = rotation.stage(teacher, location(room, options)?);
+    }
+
+    Ok(rotation)
+}
+
+// Convert the name of an activity into the activity it refers to
+fn named(name: String) -> Activity {
+    match name.as_str() {
+        "Registration" => Activity::Registration,
+        "Break" => Activity::Break,
+        "Independent Study" => Activity::SchoolStudy,
+        "Home Study" => Activity::HomeStudy,
+        _ => Activity::Miscellaneous(name),
+    }
+}
+
+// Convert the members of an inline table of the definition into a lesson (or
+// a transit)
+fn activity(
+    span: Range<usize>,
+    members: Vec<(Spanned<String>, Spanned<TomlValue>)>,
+    options: &ParseOptions,
+) -> Result<Activity, Spanned<TomlErrorKind>> {
+    let mut subject = None;
+    let mut class = None;
+    let mut teacher = None;
+    let mut room = None;
+    let mut from = None;
+    let mut to = None;
+    let mut minutes = None;
+
+    for ((key_span, key), (value_span, value)) in members {
+        if key == "minutes" {
+            match value {
+                TomlValue::Integer(value) => minutes = Some(value),
+                _ => return Err((value_span, TomlErrorKind::UnexpectedValue)),
+            }
+
+            continue;
+        }
+
+        let field = match key.as_str() {
+            "subject" => &mut subject,
+            "class" => &mut class,
+            "teacher" => &mut teacher,
+            "room" => &mut room,
+            "from" => &mut from,
+            "to" => &mut to,
+            _ => return Err((key_span, TomlErrorKind::UnknownField(key))),
+        };
+
+        match value {
+            TomlValue::String(value) => *field = Some((value_span, value)),
+            _ => return Err((value_span, TomlErrorKind::UnexpectedValue)),
+        }
+    }
+
+    let require = |field: Option<Spanned<String>>, name| {
+        field.ok_or_else(|| (span.clone(), TomlErrorKind::MissingField(name)))
+    };
+
+    // A table with an origin is a transit, rather than a lesson
+    if let Some(from) = from {
+        let to = require(to, "to")?;
+        let minutes =
+            minutes.ok_or_else(|| (span.clone(), TomlErrorKind::MissingField("minutes")))?;
+
+        return Ok(Activity::Transit {
+            from: location(from, options)?,
+            to: location(to, options)?,
+            duration: Duration::minutes(minutes),
+        });
+    }
+
+    let (subject_span, subject) = require(subject, "subject")?;
+    let (class_span, class) = require(class, "class")?;
+    let (room_span, room) = require(room, "room")?;
+
+    let teacher = match teacher {
+        Some((span, code)) => Some(
+            Teacher::new(code.clone()).map_err(|_| (span, TomlErrorKind::InvalidTeacher(code)))?,
+        ),
+        None => None,
+    };
+
+    Ok(Activity::Lesson {
+        subject: Subject::new(subject.clone())
+            .map_err(|_| (subject_span, TomlErrorKind::InvalidSubject(subject)))?,
+        class: Class::new(class.clone())
+            .map_err(|_| (class_span, TomlErrorKind::InvalidClass(class)))?,
+        teacher,
+        location: location((room_span, room), options)?,
+    })
+}
+
+/// Parses the room code of a TOML timetable definition (e.g., the room of a
+/// lesson) into a [`Location`].
+pub(crate) fn location(
+    (span, room): Spanned<String>,
+    options: &ParseOptions,
+) -> Result<Location, Spanned<TomlErrorKind>> {
+    options.parse_location(&room).map_err(|error| {
+        let kind = match error.unknown() {
+            Some(_) => TomlErrorKind::UnknownRoom(room),
+            None => TomlErrorKind::InvalidRoom(room),
+        };
+
+        (span, kind)
+    })
+}
+
+// Write a single entry as a TOML value
+fn write_entry(output: &mut String, entry: &Entry) {
+    let mut fields = String::new();
+
+    if entry.periods != SINGLE {
+        let _ = write!(fields, ", periods = {}", entry.periods.get());
+    }
+
+    let codes: [(&str, Vec<String>); 2] = [
+        (
+            "co_teachers",
+            entry
+                .co_teachers
+                .iter()
+                .map(|teacher| teacher.code().clone())
+                .collect(),
+        ),
+        (
+            "split_rooms",
+            entry.split_rooms.iter().map(Location::to_string).collect(),
+        ),
+    ];
+
+    for (name, codes) in codes.iter().filter(|(_, codes)| !codes.is_empty()) {
+        let _ = write!(fields, ", {} = [", name);
+
+        for (index, code) in codes.iter().enumerate() {
+            if index > 0 {
+                fields.push_str(", ");
+            }
+
+            write_string(&mut fields, code);
+        }
+
+        fields.push(']');
+    }
+
+    if let Some(rotation) = &entry.rotation {
+        let _ = write!(
+            fields,
+            ", rotation = {{ start = \"{}\", stages = [",
+            rotation.start.format("%Y-%m-%d")
+        );
+
+        for (index, stage) in rotation.stages.iter().enumerate() {
+            if index > 0 {
+                fields.push_str(", ");
+            }
+
+            fields.push_str("{ ");
+
+            if let Some(teacher) = &stage.teacher {
+                fields.push_str("teacher = ");
+                write_string(&mut fields, teacher.code());
+                fields.push_str(", ");
+            }
+
+            fields.push_str("room = ");
+            write_string(&mut fields, &stage.location.to_string());
+            fields.push_str(" }");
+        }
+
+        fields.push_str("] }");
+    }
+
+    write_activity(output, &entry.activity, &fields);
+}
+
+// Write a single activity as a TOML value, followed by the `fields` of its
+// entry (should there be any)
+fn write_activity(output: &mut String, activity: &Activity, fields: &str) {
+    let name = match activity {
+        Activity::Lesson {
+            subject,
+            class,
+            teacher,
+            location,
+        } => {
+            output.push_str("{ subject = ");
+            write_string(output, subject.name());
+            output.push_str(", class = ");
+            write_string(output, class.reference());
+
+            if let Some(teacher) = teacher {
+                output.push_str(", teacher = ");
+                write_string(output, teacher.code());
+            }
+
+            output.push_str(", room = ");
+            write_string(output, &location.to_string());
+            output.push_str(fields);
+            output.push_str(" }");
+            return;
+        }
+        Activity::Transit { from, to, duration } => {
+            output.push_str("{ from = ");
+            write_string(output, &from.to_string());
+            output.push_str(", to = ");
+            write_string(output, &to.to_string());
+            let _ = write!(
+                output,
+                ", minutes = {}{} }}",
+                duration.num_minutes(),
+                fields
+            );
+            return;
+        }
+        Activity::Registration => "Registration",
+        Activity::Break => "Break",
+        Activity::SchoolStudy => "Independent Study",
+        Activity::HomeStudy => "Home Study",
+        Activity::Miscellaneous(description) => description,
+    };
+
+    if fields.is_empty() {
+        write_string(output, name);
+    } else {
+        output.push_str("{ activity = ");
+        write_string(output, name);
+        output.push_str(fields);
+        output.push_str(" }");
+    }
+}
+
+// Write a TOML basic string (escaping quotes, backslashes, and control
+// characters)
+pub(crate) fn write_string(output: &mut String, s: &str) {
+    output.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\t' => output.push_str("\\t"),
+            '\r' => output.push_str("\\r"),
+            c if c.is_control() => {
+                let _ = write!(output, "\\u{:04X}", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+
+    output.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, FearnhillRoom, HighfieldRoom, Location};
+
+    const DEFINITION: &str = r#"
+# Maths intervention timetable
+[week1]
+mon.p1 = "Registration"
+mon.p3 = { subject = 'Ma', room = 'H201', class = '10A/Ma1' }
+
+[weekB]
+r.p5 = [
+    { subject = "Chess", class = "Club", teacher = "JSM", room = "FH Gym" },
+    "Home Study",
+]
+"#;
+
+    #[test]
+    fn toml_definition() {
+        let timetable = Timetable::from_toml_str(DEFINITION).unwrap();
+
+        assert_eq!(timetable.len(), 4);
+        assert_eq!(
+            timetable
+                .at(timeslot!(W1MP3))
+                .next()
+                .unwrap()
+                .1
+                .activity
+                .location(),
+            Some(&Location::Highfield(
+                "H201".parse::<HighfieldRoom>().unwrap()
+            ))
+        );
+
+        let chess: Vec<_> = timetable.at(timeslot!(W2RP5)).collect();
+
+        assert_eq!(chess.len(), 2);
+        assert_eq!(
+            chess[0].1.activity.location(),
+            Some(&Location::Fearnhill(FearnhillRoom::Gym))
+        );
+        assert_eq!(chess[1].1.activity, Activity::HomeStudy);
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        let timetable = Timetable::from_toml_str(DEFINITION).unwrap();
+        let definition = timetable.to_toml_string();
+
+        assert_eq!(Timetable::from_toml_str(&definition), Ok(timetable));
+    }
+
+    #[test]
+    fn toml_spanning_entries() {
+        let definition = "[week1]\n\
+            mon.p3 = { subject = 'Sc', class = '10A', room = 'H201', periods = 2 }\n\
+            tue.p1 = [{ activity = 'Independent Study', periods = 2 }, 'Break']";
+        let timetable = Timetable::from_toml_str(definition).unwrap();
+
+        assert_eq!(timetable.at(timeslot!(W1MP4)).count(), 1);
+        assert_eq!(timetable.at(timeslot!(W1TP2)).count(), 1);
+        assert_eq!(
+            Timetable::from_toml_str(&timetable.to_toml_string()),
+            Ok(timetable)
+        );
+
+        let error = Timetable::from_toml_str(
+            "[week1]\nmon.p5 = { subject = 'Sc', class = '10A', room = 'H201', periods = 2 }",
+        )
+        .unwrap_err();
+
+        assert_eq!(error.kind, TomlErrorKind::InvalidPeriods(2));
+
+        let definition = "[week1]\n\
+            mon.p1 = { subject = 'Sc', class = '10A', teacher = 'ABC', room = 'H201', \
+            co_teachers = ['DEF'], split_rooms = ['H202', 'H203'] }";
+        let timetable = Timetable::from_toml_str(definition).unwrap();
+        let (_, entry) = timetable.entries().next().unwrap();
+
+        assert_eq!(entry.teachers().count(), 2);
+        assert_eq!(entry.locations().count(), 3);
+        assert_eq!(
+            Timetable::from_toml_str(&timetable.to_toml_string()),
+            Ok(timetable)
+        );
+        assert_eq!(
+            Timetable::from_toml_str("[week1]\nmon.p1 = { activity = 'Break', split_rooms = [] }")
+                .unwrap_err()
+                .kind,
+            TomlErrorKind::UnknownField("split_rooms".to_string())
+        );
+        assert_eq!(
+            error.to_string(),
+            "the activity cannot span `2` periods (line 2, column 68)"
+        );
+    }
+
+    #[test]
+    fn toml_rotating_entries() {
+        let definition = "[week1]\n\
+            mon.p1 = { subject = 'DT', class = '9A', room = 'FH T1', rotation = { \
+            start = '2023-09-04', stages = [{ room = 'FH T1' }, { teacher = 'ABC', room = 'FH T2' }] } }";
+        let timetable = Timetable::from_toml_str(definition).unwrap();
+        let (_, entry) = timetable.entries().next().unwrap();
+        let rotation = entry.rotation.as_ref().unwrap();
+
+        assert_eq!(rotation.start, NaiveDate::from_ymd_opt(2023, 9, 4).unwrap());
+        assert_eq!(rotation.stages.len(), 2);
+        assert_eq!(rotation.stages[0].teacher, None);
+        assert_eq!(rotation.stages[1].location, "FH T2".parse().unwrap());
+        assert_eq!(
+            Timetable::from_toml_str(&timetable.to_toml_string()),
+            Ok(timetable)
+        );
+
+        let error = Timetable::from_toml_str(
+            "[week1]\nmon.p1 = { subject = 'DT', class = '9A', room = 'FH T1', rotation = { \
+             start = '2023-09-04', stages = [{ teacher = 'ABC' }] } }",
+        )
+        .unwrap_err();
+
+        assert_eq!(error.kind, TomlErrorKind::MissingField("room"));
+        assert_eq!(
+            Timetable::from_toml_str(
+                "[week1]\nmon.p1 = { activity = 'Break', rotation = { start = '2023-09-04', \
+                 stages = [] } }"
+            )
+            .unwrap_err()
+            .kind,
+            TomlErrorKind::UnknownField("rotation".to_string())
+        );
+    }
+
+    #[test]
+    fn toml_invalid_room() {
+        let definition = "[week1]\nmon.p3 = { subject = 'Ma', room = 'H2O1', class = '10A' }";
+
+        assert_eq!(
+            Timetable::from_toml_str(definition),
+            Err(TomlError {
+                kind: TomlErrorKind::InvalidRoom("H2O1".to_string()),
+                span: 42..48,
+                line: 2,
+                column: 35,
+            })
+        );
+    }
+
+    #[test]
+    fn toml_unknown_room() {
+        let registry = ["H201", "FH Gym"]
+            .into_iter()
+            .map(|code| code.parse().unwrap());
+        let options = ParseOptions::strict(registry.collect());
+
+        assert!(Timetable::from_toml_str_with(DEFINITION, &options).is_ok());
+
+        let definition = "[week1]\nmon.p3 = { subject = 'Ma', room = 'H299', class = '10A' }";
+
+        assert!(Timetable::from_toml_str(definition).is_ok());
+        assert_eq!(
+            Timetable::from_toml_str_with(definition, &options)
+                .unwrap_err()
+                .kind,
+            TomlErrorKind::UnknownRoom("H299".to_string())
+        );
+    }
+
+    #[test]
+    fn toml_invalid_key() {
+        let error = Timetable::from_toml_str("week1.sat.p1 = 'Break'").unwrap_err();
+
+        assert_eq!(error.kind, TomlErrorKind::InvalidDay("sat".to_string()));
+        assert_eq!(error.column, 7);
+
+        let error = Timetable::from_toml_str("[week1]\nmon = 'Break'").unwrap_err();
+
+        assert_eq!(error.kind, TomlErrorKind::InvalidKey);
+        assert_eq!(error.line, 2);
+    }
+}
@@ -0,0 +1,316 @@
+//! Differential update bundles, which transform one dataset into another.
+//!
+//! Pushing the whole dataset to every device whenever three rooms change is
+//! wasteful -- [`diff_bundle`] instead produces a compact binary delta from
+//! the dataset a device already holds to the new dataset, which the device
+//! applies with [`apply_bundle`]:
+//!
+//! ```
+//! use timetableau::delta::{apply_bundle, diff_bundle};
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, Subject, Timetable};
+//!
+//! let mut timetable = Timetable::new();
+//! let lesson = timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new("10A".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "H201".parse().unwrap(),
+//! });
+//!
+//! let old = Dataset::new(timetable, AcademicCalendar::default());
+//! let mut new = old.clone();
+//! new.timetable.relocate(lesson, "H202".parse().unwrap());
+//!
+//! let bundle = diff_bundle(&old, &new);
+//!
+//! assert!(bundle.len() < new.to_json().len() / 2);
+//! assert_eq!(apply_bundle(&old, &bundle), Ok(new));
+//! ```
+//!
+//! # Format
+//!
+//! A bundle transforms the [canonical JSON representation](Dataset::to_json)
+//! of the old dataset into that of the new dataset, line by line. It starts
+//! with the magic bytes `TTDB` and the version of the format (`1`), followed
+//! by the SHA-256 digests of the old and the new representations, and then a
+//! sequence of operations (with every integer written as a big-endian
+//! `u32`):
+//!
+//! * `0x01`, the index of a line of the old representation, and a number of
+//!   lines -- copies the lines of the old representation.
+//!
+//! * `0x02` and a number of bytes, followed by the bytes -- inserts the
+//!   bytes (i.e., lines which are not in the old representation).
+//!
+//! # Integrity
+//!
+//! A bundle is only applied to the dataset from which it was produced (i.e.,
+//! whose representation has the old digest), and the dataset it produces is
+//! only returned should its representation have the new digest -- a bundle
+//! which has been truncated or corrupted in transit is therefore rejected,
+//! rather than producing a different dataset.
+
+use crate::hmac::sha256;
+use crate::{Dataset, DatasetError};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+// The magic bytes with which every bundle starts
+const MAGIC: &[u8; 4] = b"TTDB";
+
+// The version of the format of the bundles
+const VERSION: u8 = 1;
+
+// The operations of a bundle
+const COPY: u8 = 0x01;
+const INSERT: u8 = 0x02;
+
+/// An error returned when a bundle cannot be applied.
+///
+/// *See [`apply_bundle`] for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeltaError {
+    /// The bundle is not a differential update bundle (or was written in a
+    /// version of the format which is not supported).
+    InvalidFormat,
+
+    /// The bundle was produced from a different dataset.
+    WrongBase,
+
+    /// The bundle is truncated or corrupted (i.e., its operations are
+    /// malformed, or do not produce the new dataset).
+    Corrupted,
+
+    /// The dataset produced by the bundle cannot be parsed.
+    Dataset(DatasetError),
+}
+
+impl Display for DeltaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => f.write_str("not a differential update bundle"),
+            Self::WrongBase => f.write_str("bundle was produced from a different dataset"),
+            Self::Corrupted => f.write_str("bundle is truncated or corrupted"),
+            Self::Dataset(error) => write!(f, "invalid dataset: {}", error),
+        }
+    }
+}
+
+impl Error for DeltaError {}
+
+/// Produces a bundle transforming the `old` dataset into the `new` dataset
+/// (*see the [module](self#format) documentation for a description of the
+/// format*).
+///
+/// # Remarks
+///
+/// The bundle is deterministic (i.e., equal datasets produce identical
+/// bundles), as the representations of the datasets are canonical.
+pub fn diff_bundle(old: &Dataset, new: &Dataset) -> Vec<u8> {
+    let (old, new) = (old.to_json(), new.to_json());
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+
+    // The positions of each line of the old representation
+    let mut positions: HashMap<&str, Vec<usize>> = HashMap::new();
+
+    for (index, line) in old_lines.iter().enumerate() {
+        positions.entry(line).or_default().push(index);
+    }
+
+    let mut bundle = MAGIC.to_vec();
+    bundle.push(VERSION);
+    bundle.extend(sha256(old.as_bytes()));
+    bundle.extend(sha256(new.as_bytes()));
+
+    // The run of old lines being copied, and the bytes being inserted
+    let mut run: Option<(usize, usize)> = None;
+    let mut inserted = String::new();
+
+    for line in new.split_inclusive('\n') {
+        if let Some((start, count)) = &mut run {
+            if old_lines.get(*start + *count) == Some(&line) {
+                *count += 1;
+                continue;
+            }
+        }
+
+        // Prefer the first occurrence following the previous run (as the
+        // representations are both ordered)
+        let after = run.map_or(0, |(start, count)| start + count);
+        let position = positions.get(line).and_then(|indices| {
+            indices
+                .iter()
+                .find(|index| **index >= after)
+                .or(indices.first())
+        });
+
+        if let Some((start, count)) = run.take() {
+            push_copy(&mut bundle, start, count);
+        }
+
+        match position {
+            Some(index) => {
+                push_insert(&mut bundle, &mut inserted);
+                run = Some((*index, 1));
+            }
+            None => inserted.push_str(line),
+        }
+    }
+
+    if let Some((start, count)) = run {
+        push_copy(&mut bundle, start, count);
+    }
+
+    push_insert(&mut bundle, &mut inserted);
+    bundle
+}
+
+/// Applies the `bundle` provided to the `old` dataset, producing the new
+/// dataset (*see the [module](self#integrity) documentation*).
+///
+/// # Errors
+///
+/// [`DeltaError`] is returned should the bundle not be a differential
+/// update bundle, have been produced from a different dataset, or be
+/// truncated or corrupted.
+pub fn apply_bundle(old: &Dataset, bundle: &[u8]) -> Result<Dataset, DeltaError> {
+    let header = MAGIC.len() + 1 + 64;
+
+    if bundle.len() < header || &bundle[..MAGIC.len()] != MAGIC || bundle[MAGIC.len()] != VERSION {
+        return Err(DeltaError::InvalidFormat);
+    }
+
+    let old = old.to_json();
+
+    if sha256(old.as_bytes())[..] != bundle[MAGIC.len() + 1..MAGIC.len() + 33] {
+        return Err(DeltaError::WrongBase);
+    }
+
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let mut operations = &bundle[header..];
+    let mut new = Vec::new();
+
+    while let Some((&operation, rest)) = operations.split_first() {
+        operations = rest;
+
+        match operation {
+            COPY => {
+                let start = read_u32(&mut operations)? as usize;
+                let count = read_u32(&mut operations)? as usize;
+                let lines = start
+                    .checked_add(count)
+                    .and_then(|end| old_lines.get(start..end))
+                    .ok_or(DeltaError::Corrupted)?;
+
+                new.extend(lines.iter().flat_map(|line| line.bytes()));
+            }
+            INSERT => {
+                let length = read_u32(&mut operations)? as usize;
+
+                if length > operations.len() {
+                    return Err(DeltaError::Corrupted);
+                }
+
+                let (bytes, rest) = operations.split_at(length);
+                new.extend(bytes);
+                operations = rest;
+            }
+            _ => return Err(DeltaError::Corrupted),
+        }
+    }
+
+    if sha256(&new)[..] != bundle[MAGIC.len() + 33..header] {
+        return Err(DeltaError::Corrupted);
+    }
+
+    let new = String::from_utf8(new).map_err(|_| DeltaError::Corrupted)?;
+    Dataset::from_json(&new).map_err(DeltaError::Dataset)
+}
+
+/// Appends a copy of `count` old lines (starting from `start`) to the
+/// `bundle`.
+fn push_copy(bundle: &mut Vec<u8>, start: usize, count: usize) {
+    bundle.push(COPY);
+    bundle.extend((start as u32).to_be_bytes());
+    bundle.extend((count as u32).to_be_bytes());
+}
+
+/// Appends an insertion of the `inserted` bytes (should there be any) to the
+/// `bundle`, clearing them.
+fn push_insert(bundle: &mut Vec<u8>, inserted: &mut String) {
+    if inserted.is_empty() {
+        return;
+    }
+
+    bundle.push(INSERT);
+    bundle.extend((inserted.len() as u32).to_be_bytes());
+    bundle.extend(inserted.as_bytes());
+    inserted.clear();
+}
+
+/// Reads a big-endian `u32` from the start of the `input`, advancing it.
+fn read_u32(input: &mut &[u8]) -> Result<u32, DeltaError> {
+    let (bytes, rest) = input
+        .split_first_chunk::<4>()
+        .ok_or(DeltaError::Corrupted)?;
+
+    *input = rest;
+    Ok(u32::from_be_bytes(*bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        timeslot, AcademicCalendar, Activity, Class, RangedU8, Subject, TimeSlot, Timetable,
+    };
+
+    #[test]
+    fn bundle_applied_and_checked() {
+        let mut timetable = Timetable::new();
+        let lessons: Vec<_> = (0..50)
+            .map(|index| {
+                timetable.insert(
+                    TimeSlot::with_index(RangedU8::new(index).unwrap()),
+                    Activity::Lesson {
+                        subject: Subject::new("Ma".to_string()).unwrap(),
+                        class: Class::new(format!("10A/Ma{}", index)).unwrap(),
+                        teacher: None,
+                        location: "H201".parse().unwrap(),
+                    },
+                )
+            })
+            .collect();
+
+        let old = Dataset::new(timetable, AcademicCalendar::default());
+        let mut new = old.clone();
+
+        for lesson in &lessons[10..13] {
+            new.timetable.relocate(*lesson, "H202".parse().unwrap());
+        }
+        new.timetable.remove(lessons[40]);
+        new.timetable
+            .insert(timeslot!(W1MP1), Activity::Registration);
+
+        let bundle = diff_bundle(&old, &new);
+        assert!(bundle.len() * 5 < new.to_json().len());
+        assert_eq!(apply_bundle(&old, &bundle), Ok(new.clone()));
+        assert_eq!(
+            apply_bundle(&new, &diff_bundle(&new, &old)),
+            Ok(old.clone())
+        );
+
+        // The bundle is only applied to its own base, and must be intact
+        assert_eq!(apply_bundle(&new, &bundle), Err(DeltaError::WrongBase));
+        assert_eq!(
+            apply_bundle(&old, &bundle[..bundle.len() - 1]),
+            Err(DeltaError::Corrupted)
+        );
+
+        let mut corrupted = bundle.clone();
+        *corrupted.last_mut().unwrap() ^= 0x01;
+        assert_eq!(apply_bundle(&old, &corrupted), Err(DeltaError::Corrupted));
+        assert_eq!(apply_bundle(&old, b"TTDB"), Err(DeltaError::InvalidFormat));
+    }
+}
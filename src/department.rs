@@ -0,0 +1,268 @@
+//! Departments, which group subjects, their teachers, and the areas of the
+//! schools in which they are taught.
+//!
+//! A [`Department`] records the subjects it teaches, its member teachers,
+//! and its home areas (e.g., Maths on the second floor of the Howard block,
+//! or Science in the Fearnhill S-rooms). The allocator prefers to hold the
+//! lessons of a department within its home areas (*see
+//! [`Allocator::department`](crate::allocate::Allocator::department)*), and a
+//! [`DepartmentReport`] summarises the lessons of a department:
+//!
+//! ```
+//! use timetableau::allocate::Area;
+//! use timetableau::department::Department;
+//! use timetableau::{timeslot, Activity, Class, HighfieldBlock, HighfieldFloor, RangedU8};
+//! use timetableau::{Subject, Teacher, Timetable};
+//!
+//! let maths = Department::new("Mathematics")
+//!     .subject("Ma")
+//!     .subject("FM")
+//!     .teacher("JSM")
+//!     .area(Area::Floor {
+//!         block: HighfieldBlock::Howard,
+//!         floor: HighfieldFloor::Level(RangedU8::new(2).unwrap()),
+//!     });
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new("10A".to_string()).unwrap(),
+//!     teacher: Some(Teacher::new("JSM".to_string()).unwrap()),
+//!     location: "H201".parse().unwrap(),
+//! });
+//!
+//! let report = maths.report(&timetable);
+//!
+//! assert_eq!(report.lessons, 1);
+//! assert_eq!(report.in_area, 1);
+//! ```
+
+use crate::allocate::Area;
+use crate::{Activity, Entry, EntryId, Location, Teacher, Timetable};
+use std::collections::BTreeMap;
+
+/// A department of a school.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Department {
+    /// The name of the department (e.g., `Mathematics`).
+    pub name: String,
+
+    /// The subjects taught by the department (e.g., `Ma`).
+    pub subjects: Vec<String>,
+
+    /// The staff codes of the department's teachers.
+    pub teachers: Vec<String>,
+
+    /// The areas in which the department's rooms are located.
+    pub areas: Vec<Area>,
+
+    /// The cost of allocating a lesson of the department a room outside of
+    /// its areas (*see
+    /// [`Allocator::department`](crate::allocate::Allocator::department)*).
+    pub weight: u32,
+}
+
+impl Department {
+    /// Creates a new `Department` with the `name` provided, without any
+    /// subjects, teachers, or areas (and with a weight of `1`).
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            subjects: Vec::new(),
+            teachers: Vec::new(),
+            areas: Vec::new(),
+            weight: 1,
+        }
+    }
+
+    /// Adds the subject with the `name` provided.
+    pub fn subject(mut self, name: &str) -> Self {
+        self.subjects.push(name.to_string());
+        self
+    }
+
+    /// Adds the teacher with the staff `code` provided.
+    pub fn teacher(mut self, code: &str) -> Self {
+        self.teachers.push(code.to_string());
+        self
+    }
+
+    /// Adds a home `area` of the department.
+    pub fn area(mut self, area: Area) -> Self {
+        self.areas.push(area);
+        self
+    }
+
+    /// Sets the cost of allocating a lesson of the department a room outside
+    /// of its areas (`1` by default).
+    pub fn weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Whether the department teaches the subject with the `name` provided.
+    pub fn teaches(&self, name: &str) -> bool {
+        self.subjects.iter().any(|subject| subject == name)
+    }
+
+    /// Whether the `teacher` provided is a member of the department.
+    pub fn has_member(&self, teacher: &Teacher) -> bool {
+        self.teachers.iter().any(|code| code == teacher.code())
+    }
+
+    /// Whether the `location` provided is within one of the department's
+    /// areas.
+    pub fn contains(&self, location: Location) -> bool {
+        self.areas.iter().any(|area| area.contains(location))
+    }
+
+    /// Retrieves the lessons of the `timetable` provided which belong to the
+    /// department (i.e., lessons of its subjects, and lessons taught -- or
+    /// co-taught -- by its teachers).
+    pub fn lessons<'a>(
+        &'a self,
+        timetable: &'a Timetable,
+    ) -> impl Iterator<Item = (EntryId, &'a Entry)> + 'a {
+        timetable
+            .lessons()
+            .filter(|(_, entry)| match &entry.activity {
+                Activity::Lesson { subject, .. } => {
+                    self.teaches(subject.name())
+                        || entry.teachers().any(|teacher| self.has_member(teacher))
+                }
+                _ => false,
+            })
+    }
+
+    /// Summarises the lessons of the department within the `timetable`
+    /// provided (*see [`Self::lessons`]*).
+    pub fn report(&self, timetable: &Timetable) -> DepartmentReport {
+        let mut report = DepartmentReport {
+            teacher_periods: self.teachers.iter().map(|code| (code.clone(), 0)).collect(),
+            ..DepartmentReport::default()
+        };
+
+        for (_, entry) in self.lessons(timetable) {
+            let Activity::Lesson { subject, .. } = &entry.activity else {
+                continue;
+            };
+
+            report.lessons += 1;
+
+            if entry.locations().any(|location| self.contains(*location)) {
+                report.in_area += 1;
+            }
+
+            // Each member teaching a team-taught lesson is credited with it
+            let mut members = entry
+                .teachers()
+                .filter(|teacher| self.has_member(teacher))
+                .peekable();
+
+            if members.peek().is_none() && self.teaches(subject.name()) {
+                report.non_specialist += 1;
+            }
+
+            for teacher in members {
+                *report
+                    .teacher_periods
+                    .entry(teacher.code().to_string())
+                    .or_default() += 1;
+            }
+        }
+
+        report
+    }
+}
+
+/// A summary of the lessons of a [`Department`] (*see
+/// [`Department::report`]*).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DepartmentReport {
+    /// The number of lessons of the department.
+    pub lessons: usize,
+
+    /// The number of lessons of the department held within its areas.
+    pub in_area: usize,
+
+    /// The number of periods taught by each of the department's teachers
+    /// (including lessons of other departments' subjects).
+    pub teacher_periods: BTreeMap<String, usize>,
+
+    /// The number of lessons of the department's subjects which are taught
+    /// by teachers outside of the department (or by an unknown teacher).
+    pub non_specialist: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::allocate::{Allocator, Request, Room};
+    use crate::{
+        timeslot, Class, FearnhillSection, HighfieldBlock, HighfieldFloor, RangedU8, Subject,
+    };
+
+    fn lesson(subject: &str, teacher: Option<&str>, room: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new(subject.to_string()).unwrap(),
+            class: Class::new("10A".to_string()).unwrap(),
+            teacher: teacher.map(|code| Teacher::new(code.to_string()).unwrap()),
+            location: room.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn department_reports_and_allocation() {
+        let maths = Department::new("Mathematics")
+            .subject("Ma")
+            .teacher("JSM")
+            .teacher("ABC")
+            .area(Area::Floor {
+                block: HighfieldBlock::Howard,
+                floor: HighfieldFloor::Level(RangedU8::new(2).unwrap()),
+            })
+            .weight(5);
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("Ma", Some("JSM"), "H201"));
+        timetable.insert(timeslot!(W1MP2), lesson("Ma", None, "H101"));
+        timetable.insert(timeslot!(W1MP3), lesson("Ph", Some("JSM"), "FH S1"));
+        timetable.insert(timeslot!(W1MP4), lesson("En", Some("XYZ"), "H202"));
+
+        assert_eq!(
+            maths.report(&timetable),
+            DepartmentReport {
+                lessons: 3,
+                in_area: 1,
+                teacher_periods: BTreeMap::from([("ABC".to_string(), 0), ("JSM".to_string(), 2)]),
+                non_specialist: 1,
+            }
+        );
+
+        // The weight of the department outweighs the movement penalty
+        let science = Department::new("Science")
+            .subject("Ph")
+            .area(Area::Section(FearnhillSection::Science));
+        let allocator = Allocator::new()
+            .room(Room::new("H101".parse().unwrap(), 30))
+            .room(Room::new("H203".parse().unwrap(), 30))
+            .room(Room::new("FH S2".parse().unwrap(), 30))
+            .department(&maths)
+            .department(&science);
+
+        let requests = [Request::new(
+            timeslot!(W1MP4),
+            Subject::new("Ma".to_string()).unwrap(),
+            Class::new("11B".to_string()).unwrap(),
+            Teacher::new("JSM".to_string()).ok(),
+            28,
+        )];
+
+        let allocation = allocator.allocate(&timetable, &requests).unwrap();
+
+        assert_eq!(allocation.rooms, ["H203".parse().unwrap()]);
+        assert_eq!(allocation.cost, 3);
+    }
+}
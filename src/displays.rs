@@ -0,0 +1,465 @@
+//! Playlists for the display boards in the corridors of each block.
+//!
+//! A [`DisplayBoard`] is mounted in a block of the Highfield school (or a
+//! section of the Fearnhill school), and shows the lessons about to start in
+//! the rooms nearby, followed by the day's room changes affecting them. The
+//! [`Playlist`] of a board is what it should show during each period of a
+//! date, and can be [encoded](Playlist::to_json) for the signage firmware:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::displays::{DisplayBoard, Slide};
+//! use timetableau::occupancy::Zone;
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, HighfieldBlock};
+//! use timetableau::{Period, Subject, Term, Timetable};
+//!
+//! let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+//! let calendar = AcademicCalendar::new(vec![Term::new(date, date).unwrap()]);
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(timeslot!(W1MP2), Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new("10A".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "U101".parse().unwrap(),
+//! });
+//!
+//! let dataset = Dataset::new(timetable, calendar);
+//! let board = DisplayBoard::new("Unwin foyer".to_string(), Zone::Block(HighfieldBlock::Unwin));
+//! let playlist = board.playlist(&dataset, date);
+//!
+//! // The lesson is shown from the end of the first period
+//! let item = &playlist.items[1];
+//! assert_eq!((item.period, item.from.to_string()), (Period::Second, "09:50:00".to_string()));
+//! assert!(matches!(&item.slides[..], [Slide::Upcoming(lessons)] if lessons.len() == 1));
+//! ```
+//!
+//! # Remarks
+//!
+//! The lessons shown are those which actually take place (*see
+//! [`Timetable::occurrences`](crate::Timetable::occurrences)*), of a
+//! [`WeekCycle`] anchored on the Monday of the week in which the first term
+//! of the calendar starts -- a board shows nothing on a date which is not a
+//! school day.
+
+use crate::calendar::monday_of;
+use crate::json::write_string;
+use crate::occupancy::Zone;
+use crate::{Activity, Class, Dataset, Location, Period, School, Subject, Teacher, WeekCycle};
+use chrono::{NaiveDate, NaiveTime};
+use std::fmt::Write;
+
+/// A display board, showing the lessons in the rooms of one or more
+/// [`Zone`]s.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayBoard {
+    name: String,
+    zones: Vec<Zone>,
+}
+
+impl DisplayBoard {
+    /// Creates a new `DisplayBoard` with the `name` provided (e.g., `Unwin
+    /// foyer`), which shows the lessons in the rooms of the `zone` in which
+    /// it is mounted.
+    pub fn new(name: String, zone: Zone) -> Self {
+        Self {
+            name,
+            zones: vec![zone],
+        }
+    }
+
+    /// Adds a nearby `zone` whose lessons are also shown by the
+    /// `DisplayBoard` (e.g., the Parker block, for a board at the end of the
+    /// Howard block closest to it).
+    pub fn zone(mut self, zone: Zone) -> Self {
+        if !self.zones.contains(&zone) {
+            self.zones.push(zone);
+        }
+
+        self
+    }
+
+    /// Retrieves the name of the `DisplayBoard`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Retrieves the zones whose lessons are shown by the `DisplayBoard` (the
+    /// zone in which it is mounted first).
+    pub fn zones(&self) -> &[Zone] {
+        &self.zones
+    }
+
+    /// Determines whether the lessons in the `location` provided are shown by
+    /// the `DisplayBoard`.
+    pub fn shows(&self, location: &Location) -> bool {
+        self.zones.contains(&Zone::of(location))
+    }
+
+    /// Computes the [`Playlist`] of the `DisplayBoard` for the `date`
+    /// provided.
+    ///
+    /// The playlist has an item for every period of the day, which is shown
+    /// from the end of the previous period (or, for the first period, from
+    /// the start of the day) until the end of the period, according to the
+    /// [bell schedule](crate::BellSchedule) of the school of the board. Each
+    /// item has up to two slides:
+    ///
+    /// 1. The lessons of the period in the rooms shown by the board (should
+    ///    there be any), ordered by their rooms.
+    ///
+    /// 2. The room changes of the period and of the rest of the day (should
+    ///    there be any) -- *see [`RoomChange`]* -- which move a lesson into
+    ///    (or out of) a room shown by the board.
+    pub fn playlist(&self, dataset: &Dataset, date: NaiveDate) -> Playlist {
+        let mut playlist = Playlist {
+            board: self.name.clone(),
+            date,
+            items: Vec::new(),
+        };
+
+        let Some(term) = dataset.calendar.terms().first() else {
+            return playlist;
+        };
+        let Ok(cycle) = WeekCycle::new(monday_of(term.start()), dataset.calendar.clone()) else {
+            return playlist;
+        };
+
+        if !dataset.calendar.is_school_day(date) {
+            return playlist;
+        }
+
+        let occurrences = dataset.timetable.occurrences(&cycle, date);
+
+        let mut lessons: Vec<(Period, BoardLesson)> = Vec::new();
+        let mut changes: Vec<RoomChange> = Vec::new();
+
+        for occurrence in &occurrences {
+            let Activity::Lesson {
+                subject,
+                class,
+                teacher,
+                location,
+            } = &occurrence.activity
+            else {
+                continue;
+            };
+
+            if self.shows(location) {
+                lessons.push((
+                    occurrence.slot.period,
+                    BoardLesson {
+                        class: class.clone(),
+                        subject: subject.clone(),
+                        teacher: teacher.clone(),
+                        location: *location,
+                    },
+                ));
+            }
+
+            // The location of the lesson as scheduled for the date (i.e.,
+            // including its rotation, but not the room overrides)
+            let scheduled = occurrence
+                .entry
+                .and_then(|id| dataset.timetable.get(id))
+                .map(|entry| entry.activity_on(&dataset.calendar, date));
+
+            if let Some(from) = scheduled.as_ref().and_then(Activity::location) {
+                if from != location && (self.shows(from) || self.shows(location)) {
+                    changes.push(RoomChange {
+                        period: occurrence.slot.period,
+                        class: class.clone(),
+                        subject: subject.clone(),
+                        from: *from,
+                        to: *location,
+                    });
+                }
+            }
+        }
+
+        lessons.sort_by_cached_key(|(period, lesson)| (*period, lesson.location.to_string()));
+
+        let schedule = dataset.calendar.bell_schedules_on(date).get(self.school());
+        let mut from = NaiveTime::MIN;
+
+        for period in Period::ALL {
+            let until = schedule.end(period).into();
+            let mut slides = Vec::new();
+            let upcoming: Vec<BoardLesson> = lessons
+                .iter()
+                .filter(|(of, _)| *of == period)
+                .map(|(_, lesson)| lesson.clone())
+                .collect();
+            let remaining: Vec<RoomChange> = changes
+                .iter()
+                .filter(|change| change.period >= period)
+                .cloned()
+                .collect();
+
+            if !upcoming.is_empty() {
+                slides.push(Slide::Upcoming(upcoming));
+            }
+
+            if !remaining.is_empty() {
+                slides.push(Slide::RoomChanges(remaining));
+            }
+
+            playlist.items.push(PlaylistItem {
+                period,
+                from,
+                until,
+                slides,
+            });
+
+            from = until;
+        }
+
+        playlist
+    }
+
+    // The school whose bell schedule the board follows (i.e., the school of
+    // the zone in which it is mounted)
+    fn school(&self) -> School {
+        match self.zones[0] {
+            Zone::Block(_) => School::Highfield,
+            Zone::Section(_) => School::Fearnhill,
+            Zone::Elsewhere(school) => school,
+        }
+    }
+}
+
+/// A lesson shown by a [`DisplayBoard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardLesson {
+    /// The class attending the lesson.
+    pub class: Class,
+
+    /// The subject of the lesson.
+    pub subject: Subject,
+
+    /// The teacher of the lesson (if known).
+    pub teacher: Option<Teacher>,
+
+    /// The room in which the lesson takes place.
+    pub location: Location,
+}
+
+/// A lesson moved from its scheduled room for the day (i.e., by a
+/// [`RoomOverride`](crate::RoomOverride)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoomChange {
+    /// The period of the lesson.
+    pub period: Period,
+
+    /// The class attending the lesson.
+    pub class: Class,
+
+    /// The subject of the lesson.
+    pub subject: Subject,
+
+    /// The room in which the lesson is scheduled to take place.
+    pub from: Location,
+
+    /// The room in which the lesson takes place instead.
+    pub to: Location,
+}
+
+/// A slide of a [`PlaylistItem`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Slide {
+    /// The lessons of the period in the rooms shown by the board.
+    Upcoming(Vec<BoardLesson>),
+
+    /// The room changes of the period and of the rest of the day.
+    RoomChanges(Vec<RoomChange>),
+}
+
+/// What a [`DisplayBoard`] shows during a period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaylistItem {
+    /// The period of the lessons shown.
+    pub period: Period,
+
+    /// The time from which the item is shown.
+    pub from: NaiveTime,
+
+    /// The time until which the item is shown.
+    pub until: NaiveTime,
+
+    /// The slides of the item (in the order in which they are shown) --
+    /// should there be none, the board shows its default screen.
+    pub slides: Vec<Slide>,
+}
+
+/// What a [`DisplayBoard`] shows during each period of a date.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Playlist {
+    /// The name of the board.
+    pub board: String,
+
+    /// The date of the playlist.
+    pub date: NaiveDate,
+
+    /// The items of the playlist (one for each period, in chronological
+    /// order), which is empty should the date not be a school day.
+    pub items: Vec<PlaylistItem>,
+}
+
+impl Playlist {
+    /// Encodes the `Playlist` as a JSON document for the signage firmware.
+    ///
+    /// The document is an object with the `board` and `date` of the
+    /// playlist, and its `items` -- each with the `period` (starting from
+    /// `1`), the `from` and `until` times (formatted as `HH:MM`), and the
+    /// `slides` of the item. A slide has a `type` (either `upcoming` or
+    /// `room-changes`), and either the `lessons` (each with its `class`,
+    /// `subject`, `teacher` and `room`) or the `changes` (each with its
+    /// `period`, `class`, `subject`, and its `from` and `to` rooms).
+    pub fn to_json(&self) -> String {
+        let mut output = String::from("{\n  \"board\": ");
+
+        write_string(&mut output, &self.board);
+        let _ = write!(
+            output,
+            ",\n  \"date\": \"{}\",\n  \"items\": [",
+            self.date.format("%Y-%m-%d")
+        );
+
+        for (index, item) in self.items.iter().enumerate() {
+            output.push_str(if index == 0 { "\n" } else { ",\n" });
+            let _ = write!(
+                output,
+                "    {{\"period\": {}, \"from\": \"{}\", \"until\": \"{}\", \"slides\": [",
+                item.period as u8 + 1,
+                item.from.format("%H:%M"),
+                item.until.format("%H:%M")
+            );
+
+            for (index, slide) in item.slides.iter().enumerate() {
+                output.push_str(if index == 0 { "\n" } else { ",\n" });
+
+                match slide {
+                    Slide::Upcoming(lessons) => {
+                        output.push_str("      {\"type\": \"upcoming\", \"lessons\": [");
+
+                        for (index, lesson) in lessons.iter().enumerate() {
+                            output.push_str(if index == 0 { "\n" } else { ",\n" });
+                            output.push_str("        {\"class\": ");
+                            write_string(&mut output, &lesson.class.to_string());
+                            output.push_str(", \"subject\": ");
+                            write_string(&mut output, &lesson.subject.to_string());
+                            output.push_str(", \"teacher\": ");
+
+                            match &lesson.teacher {
+                                Some(teacher) => write_string(&mut output, &teacher.to_string()),
+                                None => output.push_str("null"),
+                            }
+
+                            output.push_str(", \"room\": ");
+                            write_string(&mut output, &lesson.location.to_string());
+                            output.push('}');
+                        }
+                    }
+                    Slide::RoomChanges(changes) => {
+                        output.push_str("      {\"type\": \"room-changes\", \"changes\": [");
+
+                        for (index, change) in changes.iter().enumerate() {
+                            output.push_str(if index == 0 { "\n" } else { ",\n" });
+                            let _ = write!(
+                                output,
+                                "        {{\"period\": {}, \"class\": ",
+                                change.period as u8 + 1
+                            );
+                            write_string(&mut output, &change.class.to_string());
+                            output.push_str(", \"subject\": ");
+                            write_string(&mut output, &change.subject.to_string());
+                            output.push_str(", \"from\": ");
+                            write_string(&mut output, &change.from.to_string());
+                            output.push_str(", \"to\": ");
+                            write_string(&mut output, &change.to.to_string());
+                            output.push('}');
+                        }
+                    }
+                }
+
+                output.push_str("\n      ]}");
+            }
+
+            if !item.slides.is_empty() {
+                output.push_str("\n    ");
+            }
+
+            output.push_str("]}");
+        }
+
+        if !self.items.is_empty() {
+            output.push_str("\n  ");
+        }
+
+        output.push_str("]\n}\n");
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, HighfieldBlock, Term, Timetable};
+
+    fn lesson(class: &str, code: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("En".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Teacher::new("ABC".to_string()).ok(),
+            location: code.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn display_board_playlist() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(8)).unwrap()]);
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("7A", "U101"));
+        timetable.insert(timeslot!(W1MP1), lesson("7B", "H201"));
+        timetable.insert(timeslot!(W1MP3), lesson("7C", "H202"));
+        timetable.override_room(timeslot!(W1MP3), date(4)..=date(4), "U102".parse().unwrap());
+
+        let dataset = Dataset::new(timetable, calendar);
+        let board = DisplayBoard::new(
+            "Unwin foyer".to_string(),
+            Zone::Block(HighfieldBlock::Unwin),
+        );
+        let playlist = board.playlist(&dataset, date(4));
+
+        assert_eq!(playlist.items.len(), 5);
+        assert!(board
+            .clone()
+            .zone(Zone::Block(HighfieldBlock::Howard))
+            .shows(&"H201".parse().unwrap()));
+
+        // The lesson moved into the block is shown as a change until it starts
+        let json = playlist.to_json();
+        assert!(json.starts_with(
+            "{\n  \"board\": \"Unwin foyer\",\n  \"date\": \"2023-09-04\",\n  \"items\": [\n    \
+             {\"period\": 1, \"from\": \"00:00\", \"until\": \"09:50\", \"slides\": [\n      \
+             {\"type\": \"upcoming\", \"lessons\": [\n        {\"class\": \"7A\", \"subject\": \
+             \"En\", \"teacher\": \"ABC\", \"room\": \"U101\"}\n      ]},\n      {\"type\": \
+             \"room-changes\", \"changes\": [\n        {\"period\": 3, \"class\": \"7C\", \
+             \"subject\": \"En\", \"from\": \"H202\", \"to\": \"U102\"}\n      ]}\n    ]},\n"
+        ));
+        assert_eq!(playlist.items[2].slides.len(), 2);
+        assert!(playlist.items[3].slides.is_empty());
+        assert!(json.ends_with("\"slides\": []}\n  ]\n}\n"));
+
+        // The change is only in effect on the day of the override
+        let tomorrow = board.playlist(&dataset, date(5));
+        assert!(tomorrow.items.iter().all(|item| item.slides.is_empty()));
+        assert!(board.playlist(&dataset, date(9)).items.is_empty());
+    }
+}
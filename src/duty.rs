@@ -0,0 +1,391 @@
+//! Generation of break and lunch duty rotas.
+//!
+//! Members of staff are allocated to [`DutyPoint`]s (e.g., the yard, the
+//! canteen, or the corridors of a block) during each break and lunch of the
+//! two-week cycle, such that:
+//!
+//! * no member of staff has a duty straight after a lesson at the other
+//!   school (as they would spend the duty travelling);
+//! * no member of staff has more than one duty per day, or more than the
+//!   [maximum](RotaGenerator::max_per_week) number of duties per week; and
+//! * duties are shared as evenly as possible -- each duty is given to the
+//!   eligible member of staff with the fewest duties so far.
+//!
+//! ```
+//! use timetableau::duty::{DutyPoint, RotaGenerator};
+//! use timetableau::{School, Teacher, Timetable, Week};
+//!
+//! let rota = RotaGenerator::new()
+//!     .point(DutyPoint::Yard(School::Highfield))
+//!     .staff(Teacher::new("ABC".to_string()).unwrap())
+//!     .staff(Teacher::new("DEF".to_string()).unwrap())
+//!     .generate(&Timetable::new());
+//!
+//! assert!(rota.unfilled.is_empty());
+//! assert_eq!(rota.duties_of("ABC").count(), 10);
+//!
+//! let bulletin = rota.bulletin(Week::WeekOne);
+//! assert!(bulletin.starts_with("DUTY ROTA -- WEEK 1"));
+//! ```
+
+use crate::{
+    Activity, FearnhillSection, HighfieldBlock, Period, School, Teacher, TimeSlot, Timetable, Week,
+};
+use chrono::Weekday;
+use std::fmt::{self, Display, Formatter, Write};
+
+/// A place which is supervised by a member of staff during break and lunch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DutyPoint {
+    /// The yard (or playground) of a school.
+    Yard(School),
+
+    /// The canteen of a school.
+    Canteen(School),
+
+    /// The corridors of a block of the Highfield school.
+    Block(HighfieldBlock),
+
+    /// The corridors of a section of the Fearnhill school.
+    Section(FearnhillSection),
+}
+
+impl DutyPoint {
+    /// Retrieves the school at which the `DutyPoint` is located.
+    pub fn school(self) -> School {
+        match self {
+            Self::Yard(school) | Self::Canteen(school) => school,
+            Self::Block(_) => School::Highfield,
+            Self::Section(_) => School::Fearnhill,
+        }
+    }
+}
+
+impl Display for DutyPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Yard(school) => write!(f, "Yard ({})", school),
+            Self::Canteen(school) => write!(f, "Canteen ({})", school),
+            Self::Block(block) => write!(f, "{} Block corridors", block.full_name()),
+            Self::Section(section) => write!(f, "{} corridors", section.full_name()),
+        }
+    }
+}
+
+/// The part of the day during which a duty takes place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DutyTime {
+    /// Break (i.e., after [`Period::Second`]).
+    Break,
+
+    /// Lunch (i.e., after [`Period::Fourth`]).
+    Lunch,
+}
+
+impl DutyTime {
+    /// Retrieves the [`Period`] which ends as the `DutyTime` starts.
+    pub fn after(self) -> Period {
+        match self {
+            Self::Break => Period::Second,
+            Self::Lunch => Period::Fourth,
+        }
+    }
+}
+
+impl Display for DutyTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Break => f.write_str("Break"),
+            Self::Lunch => f.write_str("Lunch"),
+        }
+    }
+}
+
+/// A duty allocated to a member of staff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duty {
+    /// The week of the cycle during which the duty takes place.
+    pub week: Week,
+
+    /// The day on which the duty takes place.
+    pub day: Weekday,
+
+    /// The part of the day during which the duty takes place.
+    pub time: DutyTime,
+
+    /// The place which is supervised.
+    pub point: DutyPoint,
+
+    /// The member of staff supervising the duty point.
+    pub teacher: Teacher,
+}
+
+/// A duty which could not be allocated to any member of staff, as produced
+/// by [`RotaGenerator::generate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unfilled {
+    /// The week of the cycle during which the duty takes place.
+    pub week: Week,
+
+    /// The day on which the duty takes place.
+    pub day: Weekday,
+
+    /// The part of the day during which the duty takes place.
+    pub time: DutyTime,
+
+    /// The place which is left unsupervised.
+    pub point: DutyPoint,
+}
+
+/// A duty rota, produced by a [`RotaGenerator`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rota {
+    /// Every duty allocated (in chronological order, then in the order in
+    /// which the duty points were added).
+    pub duties: Vec<Duty>,
+
+    /// The duties which could not be allocated (in the same order).
+    pub unfilled: Vec<Unfilled>,
+}
+
+impl Rota {
+    /// Retrieves the duties of the member of staff with the staff `code`
+    /// provided.
+    pub fn duties_of<'a>(&'a self, code: &'a str) -> impl Iterator<Item = &'a Duty> + 'a {
+        self.duties
+            .iter()
+            .filter(move |duty| duty.teacher.code() == code)
+    }
+
+    /// Formats the duties of the `week` provided for the weekly staff
+    /// bulletin -- a heading, followed by each day's duties (one per line).
+    /// Unfilled duties are listed as `UNFILLED`, such that volunteers can be
+    /// sought.
+    pub fn bulletin(&self, week: Week) -> String {
+        let mut output = format!("DUTY ROTA -- WEEK {}\n", week as u8 + 1);
+
+        for day in DAYS {
+            let _ = write!(output, "\n{}\n", weekday_name(day));
+
+            for time in [DutyTime::Break, DutyTime::Lunch] {
+                let duties = self
+                    .duties
+                    .iter()
+                    .filter(|duty| duty.week == week && duty.day == day && duty.time == time)
+                    .map(|duty| (duty.point, duty.teacher.code().as_str()));
+                let unfilled = self
+                    .unfilled
+                    .iter()
+                    .filter(|duty| duty.week == week && duty.day == day && duty.time == time)
+                    .map(|duty| (duty.point, "UNFILLED"));
+
+                for (point, teacher) in duties.chain(unfilled) {
+                    let _ = writeln!(
+                        output,
+                        "  {:<5}  {:<24}  {}",
+                        time.to_string(),
+                        point.to_string(),
+                        teacher
+                    );
+                }
+            }
+        }
+
+        output
+    }
+}
+
+/// Generates duty [`Rota`]s.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotaGenerator {
+    points: Vec<DutyPoint>,
+    staff: Vec<Teacher>,
+    max_per_week: usize,
+}
+
+impl Default for RotaGenerator {
+    fn default() -> Self {
+        Self {
+            points: Vec::new(),
+            staff: Vec::new(),
+            max_per_week: 5,
+        }
+    }
+}
+
+impl RotaGenerator {
+    /// Creates a new `RotaGenerator` with no duty points or staff, and a
+    /// maximum of five duties per member of staff per week.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a duty point, which is supervised during every break and lunch.
+    pub fn point(mut self, point: DutyPoint) -> Self {
+        self.points.push(point);
+        self
+    }
+
+    /// Adds a member of staff who can be allocated duties.
+    pub fn staff(mut self, teacher: Teacher) -> Self {
+        self.staff.push(teacher);
+        self
+    }
+
+    /// Sets the maximum number of duties allocated to each member of staff
+    /// per week.
+    pub fn max_per_week(mut self, max: usize) -> Self {
+        self.max_per_week = max;
+        self
+    }
+
+    /// Generates a [`Rota`] respecting the teaching `timetable` of each
+    /// member of staff.
+    ///
+    /// *See the [module](self) documentation for more information*.
+    pub fn generate(&self, timetable: &Timetable) -> Rota {
+        let mut rota = Rota::default();
+
+        // The number of duties of each member of staff (in total, and during
+        // the current week)
+        let mut total = vec![0usize; self.staff.len()];
+
+        for week in [Week::WeekOne, Week::WeekTwo] {
+            let mut weekly = vec![0usize; self.staff.len()];
+
+            for day in DAYS {
+                let mut today = vec![false; self.staff.len()];
+
+                for time in [DutyTime::Break, DutyTime::Lunch] {
+                    let slot = TimeSlot {
+                        week,
+                        day,
+                        period: time.after(),
+                    };
+
+                    for point in &self.points {
+                        let chosen = (0..self.staff.len())
+                            .filter(|&i| !today[i] && weekly[i] < self.max_per_week)
+                            .filter(|&i| !arrives_late(timetable, &self.staff[i], slot, *point))
+                            .min_by_key(|&i| total[i]);
+
+                        match chosen {
+                            Some(i) => {
+                                today[i] = true;
+                                weekly[i] += 1;
+                                total[i] += 1;
+
+                                rota.duties.push(Duty {
+                                    week,
+                                    day,
+                                    time,
+                                    point: *point,
+                                    teacher: self.staff[i].clone(),
+                                });
+                            }
+                            None => rota.unfilled.push(Unfilled {
+                                week,
+                                day,
+                                time,
+                                point: *point,
+                            }),
+                        }
+                    }
+                }
+            }
+        }
+
+        rota
+    }
+}
+
+// The days of the week on which duties take place
+const DAYS: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+
+// Whether the teacher's lesson during the slot (i.e., immediately before the
+// duty) takes place at the other school to the duty point
+fn arrives_late(
+    timetable: &Timetable,
+    teacher: &Teacher,
+    slot: TimeSlot,
+    point: DutyPoint,
+) -> bool {
+    timetable
+        .lessons()
+        .taught_by(teacher.code())
+        .during(slot)
+        .any(|(_, entry)| match &entry.activity {
+            Activity::Lesson { location, .. } => location.school() != point.school(),
+            _ => false,
+        })
+}
+
+// Retrieve the full name of a weekday (e.g., `Monday`)
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, Subject};
+
+    fn teacher(code: &str) -> Teacher {
+        Teacher::new(code.to_string()).unwrap()
+    }
+
+    #[test]
+    fn duty_rota_generation() {
+        let mut timetable = Timetable::new();
+        timetable.insert(
+            timeslot!(W1MP2),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("10A".to_string()).unwrap(),
+                teacher: Some(teacher("ABC")),
+                location: "FH S13".parse().unwrap(),
+            },
+        );
+
+        let rota = RotaGenerator::new()
+            .point(DutyPoint::Yard(School::Highfield))
+            .point(DutyPoint::Block(HighfieldBlock::Unwin))
+            .staff(teacher("ABC"))
+            .staff(teacher("DEF"))
+            .staff(teacher("GHI"))
+            .max_per_week(3)
+            .generate(&timetable);
+
+        // ABC teaches at Fearnhill before Monday's break
+        let monday = &rota.duties[..2];
+        assert!(monday.iter().all(|duty| duty.teacher != teacher("ABC")));
+        assert_eq!(monday[0].teacher, teacher("DEF"));
+
+        // Only nine of the twenty duties per week can be filled
+        assert_eq!(rota.duties.len(), 18);
+        assert_eq!(rota.unfilled.len(), 22);
+        for code in ["ABC", "DEF", "GHI"] {
+            assert_eq!(rota.duties_of(code).count(), 6);
+        }
+
+        let bulletin = rota.bulletin(Week::WeekOne);
+        assert!(bulletin.contains("\nMonday\n  Break  Yard (Highfield)          DEF\n"));
+        assert_eq!(bulletin.matches("UNFILLED").count(), 11);
+    }
+}
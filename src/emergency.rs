@@ -0,0 +1,309 @@
+//! Evacuation routes and assembly points, for fire drills (and real
+//! evacuations).
+//!
+//! Every [`Location`] is mapped to the [`EvacuationRoute`] taken from it
+//! (*see [`Location::evacuation_route`]*), ending at an [`AssemblyPoint`] --
+//! the [`DrillReport`] of a timeslot lists which classes should be found at
+//! each assembly point, as required by the drill paperwork:
+//!
+//! ```
+//! use timetableau::emergency::{AssemblyPoint, DrillReport};
+//! use timetableau::{timeslot, Activity, Class, Location, Subject, Timetable};
+//!
+//! let room: Location = "U101".parse().unwrap();
+//! assert_eq!(room.assembly_point(), AssemblyPoint::CarPark);
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(
+//!     timeslot!(W1MP3),
+//!     Activity::Lesson {
+//!         subject: Subject::new("Ma".to_string()).unwrap(),
+//!         class: Class::new("10A/Ma1".to_string()).unwrap(),
+//!         teacher: None,
+//!         location: room,
+//!     },
+//! );
+//!
+//! let report = DrillReport::new(&timetable, timeslot!(W1MP3));
+//!
+//! assert_eq!(report.points[&AssemblyPoint::CarPark].len(), 1);
+//! ```
+//!
+//! # Remarks
+//!
+//! The routes are those of the current fire procedures of each school:
+//!
+//! | Rooms                                     | Exit                    | Assembly Point |
+//! |-------------------------------------------|-------------------------|----------------|
+//! | Howard and Parker blocks                  | Main entrance           | Front Field    |
+//! | Unwin block and the Hall                  | Rear doors              | Car Park       |
+//! | Highfield Sports Hall                     | Sports Hall side doors  | Front Field    |
+//! | Fearnhill Science, Technology, IT, Maths  | Science corridor exit   | Playing Field  |
+//! | Other Fearnhill sections                  | Reception               | Playground     |
+//! | Fearnhill sports and performance rooms    | Sports centre doors     | Playing Field  |
+//!
+//! Rooms above the ground floor are evacuated via the nearest stairwell, and
+//! rooms whose position is not known use the nearest fire exit and the main
+//! assembly point of their school.
+
+use crate::{
+    Activity, Class, FearnhillSection, HighfieldBlock, HighfieldFloor, Location, School, Teacher,
+    TimeSlot, Timetable,
+};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// A place at which students and staff assemble after evacuating a school.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AssemblyPoint {
+    /// The front field of the Highfield school.
+    FrontField,
+
+    /// The staff car park of the Highfield school.
+    CarPark,
+
+    /// The playing field of the Fearnhill school.
+    PlayingField,
+
+    /// The playground of the Fearnhill school.
+    Playground,
+}
+
+impl AssemblyPoint {
+    /// Retrieves the name of the `AssemblyPoint` (e.g., `Front Field`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::FrontField => "Front Field",
+            Self::CarPark => "Car Park",
+            Self::PlayingField => "Playing Field",
+            Self::Playground => "Playground",
+        }
+    }
+
+    /// Retrieves the school at which the `AssemblyPoint` is located.
+    pub fn school(self) -> School {
+        match self {
+            Self::FrontField | Self::CarPark => School::Highfield,
+            Self::PlayingField | Self::Playground => School::Fearnhill,
+        }
+    }
+}
+
+impl Display for AssemblyPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name(), self.school())
+    }
+}
+
+/// The route taken to evacuate a [`Location`] (*see
+/// [`Location::evacuation_route`]*).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EvacuationRoute {
+    /// Whether the route starts by descending the nearest stairwell (i.e.,
+    /// the room is above the ground floor).
+    pub stairs: bool,
+
+    /// The exit through which the building is left (e.g., `Main entrance`).
+    pub exit: &'static str,
+
+    /// The assembly point at which the route ends.
+    pub assembly_point: AssemblyPoint,
+}
+
+impl Display for EvacuationRoute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.stairs {
+            f.write_str("nearest stairwell, then ")?;
+        }
+
+        write!(f, "{} to the {}", self.exit, self.assembly_point.name())
+    }
+}
+
+impl Location {
+    /// Retrieves the [`EvacuationRoute`] taken from the `Location`.
+    ///
+    /// *See the [module](crate::emergency) documentation for more information*.
+    pub fn evacuation_route(&self) -> EvacuationRoute {
+        let parts = self.parts();
+        let stairs = matches!(parts.floor, Some(HighfieldFloor::Level(_)));
+        let (exit, assembly_point) = match (parts.school, parts.block, parts.section, parts.named) {
+            (_, Some(HighfieldBlock::Howard | HighfieldBlock::Parker), ..) => {
+                ("Main entrance", AssemblyPoint::FrontField)
+            }
+            (_, Some(HighfieldBlock::Unwin), ..) | (School::Highfield, _, _, Some("Hall")) => {
+                ("Rear doors", AssemblyPoint::CarPark)
+            }
+            (School::Highfield, _, _, Some(_)) => {
+                ("Sports Hall side doors", AssemblyPoint::FrontField)
+            }
+            (
+                _,
+                _,
+                Some(
+                    FearnhillSection::Science
+                    | FearnhillSection::Technology
+                    | FearnhillSection::IT
+                    | FearnhillSection::Mathematics,
+                ),
+                _,
+            ) => ("Science corridor exit", AssemblyPoint::PlayingField),
+            (_, _, Some(_), _) => ("Reception", AssemblyPoint::Playground),
+            (School::Fearnhill, _, _, Some(_)) => {
+                ("Sports centre doors", AssemblyPoint::PlayingField)
+            }
+            (School::Highfield, ..) => ("Nearest fire exit", AssemblyPoint::FrontField),
+            (School::Fearnhill, ..) => ("Nearest fire exit", AssemblyPoint::Playground),
+        };
+
+        EvacuationRoute {
+            stairs,
+            exit,
+            assembly_point,
+        }
+    }
+
+    /// Retrieves the [`AssemblyPoint`] at which the occupants of the
+    /// `Location` assemble after evacuating it.
+    ///
+    /// *See [`Self::evacuation_route`] for more information*.
+    pub fn assembly_point(&self) -> AssemblyPoint {
+        self.evacuation_route().assembly_point
+    }
+}
+
+/// A class which should be found at an assembly point (*see
+/// [`DrillReport`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Muster {
+    /// The class.
+    pub class: Class,
+
+    /// The teacher of the class (if known).
+    pub teacher: Option<Teacher>,
+
+    /// The room which the class evacuated.
+    pub location: Location,
+}
+
+/// The classes which should be found at each [`AssemblyPoint`] following an
+/// evacuation during a particular [`TimeSlot`].
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrillReport {
+    /// The timeslot during which the evacuation takes place.
+    pub slot: TimeSlot,
+
+    /// The classes at each assembly point (in the order of their rooms) --
+    /// assembly points at which no class is expected are omitted.
+    pub points: BTreeMap<AssemblyPoint, Vec<Muster>>,
+}
+
+impl DrillReport {
+    /// Creates the `DrillReport` of the `timetable` provided, for an
+    /// evacuation during the `slot` provided.
+    pub fn new(timetable: &Timetable, slot: TimeSlot) -> Self {
+        let mut points: BTreeMap<AssemblyPoint, Vec<Muster>> = BTreeMap::new();
+
+        for (_, entry) in timetable.lessons().during(slot) {
+            if let Activity::Lesson {
+                class,
+                teacher,
+                location,
+                ..
+            } = &entry.activity
+            {
+                points
+                    .entry(location.assembly_point())
+                    .or_default()
+                    .push(Muster {
+                        class: class.clone(),
+                        teacher: teacher.clone(),
+                        location: *location,
+                    });
+            }
+        }
+
+        for musters in points.values_mut() {
+            musters.sort_by_key(|muster| muster.location.to_string());
+        }
+
+        Self { slot, points }
+    }
+}
+
+impl Display for DrillReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (point, musters) in &self.points {
+            writeln!(f, "{}:", point)?;
+
+            for muster in musters {
+                write!(f, "  {}", muster.class)?;
+
+                if let Some(teacher) = &muster.teacher {
+                    write!(f, " ({})", teacher)?;
+                }
+
+                writeln!(
+                    f,
+                    " from {} via {}",
+                    muster.location,
+                    muster.location.evacuation_route()
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Subject};
+
+    #[test]
+    fn evacuation_routes_and_drill_report() {
+        let route = |code: &str| code.parse::<Location>().unwrap().evacuation_route();
+
+        assert!(route("H201").stairs);
+        assert!(!route("HG01").stairs);
+        assert_eq!(route("P101").assembly_point, AssemblyPoint::FrontField);
+        assert_eq!(route("Hall").assembly_point, AssemblyPoint::CarPark);
+        assert_eq!(route("Sports Hall").exit, "Sports Hall side doors");
+        assert_eq!(route("FH S13").assembly_point, AssemblyPoint::PlayingField);
+        assert_eq!(route("FH E1").assembly_point, AssemblyPoint::Playground);
+        assert_eq!(route("FH Gym").exit, "Sports centre doors");
+
+        let mut timetable = Timetable::new();
+        let mut lesson = |class: &str, teacher: Option<&str>, room: &str| {
+            timetable.insert(
+                timeslot!(W1MP3),
+                Activity::Lesson {
+                    subject: Subject::new("Ma".to_string()).unwrap(),
+                    class: Class::new(class.to_string()).unwrap(),
+                    teacher: teacher.map(|code| Teacher::new(code.to_string()).unwrap()),
+                    location: room.parse().unwrap(),
+                },
+            );
+        };
+
+        lesson("10A", Some("ABC"), "U101");
+        lesson("9B", None, "Hall");
+        lesson("11C", None, "H201");
+        timetable.insert(timeslot!(W1MP4), Activity::Break);
+
+        let report = DrillReport::new(&timetable, timeslot!(W1MP3));
+
+        assert_eq!(report.points.len(), 2);
+        assert_eq!(
+            report.to_string(),
+            "Front Field (Highfield):\n  \
+             11C from H201 via nearest stairwell, then Main entrance to the Front Field\n\
+             Car Park (Highfield):\n  \
+             9B from Hall via Rear doors to the Car Park\n  \
+             10A (ABC) from U101 via nearest stairwell, then Rear doors to the Car Park\n"
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use crate::{Location, TimeOfDay, TimeSlot, TomlError};
+use chrono::NaiveDate;
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::num::ParseIntError;
+
+/// Any error returned by the crate.
+///
+/// Each of the crate's error types can be converted into an `Error` (using
+/// [`From`]), such that functions combining several of the crate's
+/// operations can use `?` with a single error type:
+///
+/// ```
+/// use timetableau::{Error, Location, Subject};
+///
+/// fn parse(subject: &str, room: &str) -> Result<(Subject, Location), Error> {
+///     Ok((Subject::new(subject.to_string())?, room.parse()?))
+/// }
+///
+/// assert!(parse("Ma", "H201").is_ok());
+/// assert!(parse("Ma", "H2O1").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A room identifier could not be parsed.
+    Location(ParseLocationError),
+
+    /// A value was outside of its permitted range.
+    Range(RangeError),
+
+    /// A ranged integer could not be parsed.
+    Ranged(ParseRangedError),
+
+    /// A database of known rooms could not be parsed.
+    Registry(ParseRegistryError),
+
+    /// A part of a timetable (e.g., a subject or a term) was invalid.
+    Timetable(TimetableError),
+
+    /// A TOML timetable definition was invalid.
+    Toml(TomlError),
+
+    /// A year group (or key stage) could not be parsed.
+    YearGroup(ParseYearGroupError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Location(error) => Display::fmt(error, f),
+            Self::Range(error) => Display::fmt(error, f),
+            Self::Ranged(error) => Display::fmt(error, f),
+            Self::Registry(error) => Display::fmt(error, f),
+            Self::Timetable(error) => Display::fmt(error, f),
+            Self::Toml(error) => Display::fmt(error, f),
+            Self::YearGroup(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Location(error) => Some(error),
+            Self::Range(error) => Some(error),
+            Self::Ranged(error) => Some(error),
+            Self::Registry(error) => Some(error),
+            Self::Timetable(error) => Some(error),
+            Self::Toml(error) => Some(error),
+            Self::YearGroup(error) => Some(error),
+        }
+    }
+}
+
+impl From<ParseLocationError> for Error {
+    fn from(error: ParseLocationError) -> Self {
+        Self::Location(error)
+    }
+}
+
+impl From<RangeError> for Error {
+    fn from(error: RangeError) -> Self {
+        Self::Range(error)
+    }
+}
+
+impl From<ParseRangedError> for Error {
+    fn from(error: ParseRangedError) -> Self {
+        Self::Ranged(error)
+    }
+}
+
+impl From<ParseRegistryError> for Error {
+    fn from(error: ParseRegistryError) -> Self {
+        Self::Registry(error)
+    }
+}
+
+impl From<TimetableError> for Error {
+    fn from(error: TimetableError) -> Self {
+        Self::Timetable(error)
+    }
+}
+
+impl From<TomlError> for Error {
+    fn from(error: TomlError) -> Self {
+        Self::Toml(error)
+    }
+}
+
+impl From<ParseYearGroupError> for Error {
+    fn from(error: ParseYearGroupError) -> Self {
+        Self::YearGroup(error)
+    }
+}
+
+/// An error returned when a room identifier cannot be parsed.
+///
+/// *See the [`crate`] documentation for more information about the room
+/// numbering schemes*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLocationError(pub(crate) Option<Location>);
+
+impl ParseLocationError {
+    /// Retrieves the room which was identified but rejected, as it is not in
+    /// the [`RoomRegistry`](crate::RoomRegistry) of a strict
+    /// [`ParseOptions`](crate::ParseOptions) (or [`None`] should the
+    /// identifier not be structurally valid).
+    pub fn unknown(&self) -> Option<Location> {
+        self.0
+    }
+}
+
+impl Display for ParseLocationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(location) => write!(f, "unknown room `{}`", location),
+            None => f.write_str("invalid room identifier"),
+        }
+    }
+}
+
+impl StdError for ParseLocationError {}
+
+/// An error returned when a database of known rooms (or a map of room
+/// renames) cannot be parsed.
+///
+/// *See [`RoomRegistry::from_data`](crate::RoomRegistry::from_data) and
+/// [`RoomMigration::from_data`](crate::RoomMigration::from_data) for more
+/// information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRegistryError {
+    line: usize,
+    code: String,
+}
+
+impl ParseRegistryError {
+    pub(crate) fn new(line: usize, code: String) -> Self {
+        Self { line, code }
+    }
+
+    /// Retrieves the line (starting from `1`) containing the invalid room
+    /// identifier.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Retrieves the invalid room identifier.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+}
+
+impl Display for ParseRegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: invalid room identifier `{}`",
+            self.line, self.code
+        )
+    }
+}
+
+impl StdError for ParseRegistryError {}
+
+/// An error returned when a value is outside of its permitted range.
+///
+/// The offending value and the (inclusive) bounds are retained, such that
+/// the error can describe exactly what was wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeError {
+    value: i128,
+    min: i128,
+    max: i128,
+}
+
+impl RangeError {
+    /// Creates a new `RangeError` for a `value` outside of the range
+    /// `min..=max`.
+    pub const fn new(value: i128, min: i128, max: i128) -> Self {
+        Self { value, min, max }
+    }
+
+    /// Retrieves the offending value.
+    pub fn value(&self) -> i128 {
+        self.value
+    }
+
+    /// Retrieves the lower bound (inclusive) of the permitted range.
+    pub fn min(&self) -> i128 {
+        self.min
+    }
+
+    /// Retrieves the upper bound (inclusive) of the permitted range.
+    pub fn max(&self) -> i128 {
+        self.max
+    }
+}
+
+impl Display for RangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is outside of the permitted range {}..={}",
+            self.value, self.min, self.max
+        )
+    }
+}
+
+impl StdError for RangeError {}
+
+/// An error returned when a ranged integer (e.g., a
+/// [`RangedU8`](crate::RangedU8)) cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRangedError {
+    /// The text was not an integer.
+    Invalid(ParseIntError),
+
+    /// The integer was outside of the permitted range.
+    Range(RangeError),
+}
+
+impl Display for ParseRangedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(error) => Display::fmt(error, f),
+            Self::Range(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl StdError for ParseRangedError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Invalid(error) => Some(error),
+            Self::Range(error) => Some(error),
+        }
+    }
+}
+
+/// An error returned when a [`YearGroup`](crate::YearGroup) or a
+/// [`KeyStage`](crate::KeyStage) cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseYearGroupError {
+    /// The text was not a year group (or a key stage).
+    Invalid(String),
+
+    /// The number of the year group was outside of the range `7..=13`.
+    Range(RangeError),
+}
+
+impl Display for ParseYearGroupError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(text) => write!(f, "`{}` is not a year group", text),
+            Self::Range(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl StdError for ParseYearGroupError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Invalid(_) => None,
+            Self::Range(error) => Some(error),
+        }
+    }
+}
+
+/// An error returned when a part of a timetable cannot be constructed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TimetableError {
+    /// The name of a [`Subject`](crate::Subject) was not an ASCII string with
+    /// a length in the range `1..=16`.
+    InvalidSubject(String),
+
+    /// The reference of a [`Class`](crate::Class) was not an ASCII string
+    /// with a length in the range `1..=32`.
+    InvalidClass(String),
+
+    /// The staff code of a [`Teacher`](crate::Teacher) was not an ASCII
+    /// string with a length in the range `1..=16`.
+    InvalidTeacher(String),
+
+    /// A [`Term`](crate::Term) started after it ended.
+    TermEndsBeforeStart {
+        /// The first day of the term.
+        start: NaiveDate,
+
+        /// The last day of the term.
+        end: NaiveDate,
+    },
+
+    /// The anchor of a [`WeekCycle`](crate::WeekCycle) was not a Monday.
+    AnchorNotMonday(NaiveDate),
+
+    /// A time range of a [`BellSchedule`](crate::BellSchedule) did not start
+    /// before it ended.
+    EmptyBellRange {
+        /// The start of the time range.
+        start: TimeOfDay,
+
+        /// The end of the time range.
+        end: TimeOfDay,
+    },
+
+    /// A time range of a [`BellSchedule`](crate::BellSchedule) ended after
+    /// the following time range started.
+    OverlappingBellRanges {
+        /// The end of the earlier time range.
+        end: TimeOfDay,
+
+        /// The start of the later time range.
+        start: TimeOfDay,
+    },
+
+    /// An [`Entry`](crate::Entry) did not span at least one period, or
+    /// spanned past the final period of the day.
+    InvalidSpan {
+        /// The timeslot during which the entry starts.
+        slot: TimeSlot,
+
+        /// The number of periods the entry spans.
+        periods: u8,
+    },
+
+    /// An [`Entry`](crate::Entry) which is not a lesson had co-teachers,
+    /// split rooms, or a rotation.
+    NotALesson {
+        /// The timeslot during which the entry starts.
+        slot: TimeSlot,
+    },
+}
+
+impl Display for TimetableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSubject(name) => write!(
+                f,
+                "invalid subject `{}` (must be 1 to 16 ASCII characters)",
+                name
+            ),
+            Self::InvalidClass(reference) => write!(
+                f,
+                "invalid class `{}` (must be 1 to 32 ASCII characters)",
+                reference
+            ),
+            Self::InvalidTeacher(code) => write!(
+                f,
+                "invalid staff code `{}` (must be 1 to 16 ASCII characters)",
+                code
+            ),
+            Self::TermEndsBeforeStart { start, end } => {
+                write!(f, "term starts on {} but ends earlier, on {}", start, end)
+            }
+            Self::AnchorNotMonday(anchor) => write!(
+                f,
+                "the anchor of a cycle must be a Monday, but {} is a {}",
+                anchor,
+                anchor.format("%A")
+            ),
+            Self::EmptyBellRange { start, end } => write!(
+                f,
+                "bell time range {}-{} does not start before it ends",
+                start, end
+            ),
+            Self::OverlappingBellRanges { end, start } => write!(
+                f,
+                "bell time range ending at {} overlaps the range starting at {}",
+                end, start
+            ),
+            Self::InvalidSpan { slot, periods } => write!(
+                f,
+                "an activity starting in period {} cannot span {} periods (must be 1 to {}, as it cannot span past the end of the day)",
+                slot.period as usize + 1,
+                periods,
+                crate::timetable::longest_span(slot.period)
+            ),
+            Self::NotALesson { slot } => write!(
+                f,
+                "the activity during {} has co-teachers, split rooms, or a rotation, but is not a lesson",
+                crate::timeslot::slot_key(*slot)
+            ),
+        }
+    }
+}
+
+impl StdError for TimetableError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_messages() {
+        let anchor = NaiveDate::from_ymd_opt(2023, 9, 5).unwrap();
+
+        assert_eq!(
+            Error::from(TimetableError::AnchorNotMonday(anchor)).to_string(),
+            "the anchor of a cycle must be a Monday, but 2023-09-05 is a Tuesday"
+        );
+        assert_eq!(
+            RangeError::new(0, 1, 99).to_string(),
+            "0 is outside of the permitted range 1..=99"
+        );
+    }
+}
@@ -0,0 +1,436 @@
+//! An event-sourced storage mode, in which a dataset is the fold of an
+//! append-only log of events.
+//!
+//! An [`EventStore`] records every change made to its dataset as an
+//! [`Event`] (e.g., a lesson being added or moved to a different room, or a
+//! closure being declared) -- the current state of the dataset is the result
+//! of applying every event to the dataset with which the store was created,
+//! such that the state at any point in time can be reconstructed by
+//! replaying a prefix of the log:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::closures::{Closure, ClosureScope};
+//! use timetableau::events::EventStore;
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, Subject, Timetable};
+//!
+//! let mut store = EventStore::new(Dataset::new(Timetable::new(), AcademicCalendar::default()));
+//!
+//! let lesson = store.insert(timeslot!(W1MP1), Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new("10A".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "H201".parse().unwrap(),
+//! });
+//! store.relocate(lesson, "H202".parse().unwrap());
+//! store.declare_closure(Closure {
+//!     scope: ClosureScope::Room("H201".parse().unwrap()),
+//!     start: NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(),
+//!     end: NaiveDate::from_ymd_opt(2023, 9, 8).unwrap(),
+//!     reason: "Flooding".to_string(),
+//! });
+//!
+//! assert_eq!(store.sequence(), 3);
+//!
+//! // Before the lesson was moved to H202 (or the closure was declared)
+//! let replayed = store.replay(1).unwrap();
+//!
+//! assert_eq!(
+//!     replayed.dataset.timetable.get(lesson).unwrap().activity.location(),
+//!     Some(&"H201".parse().unwrap())
+//! );
+//! assert!(replayed.closures.closures().is_empty());
+//! ```
+//!
+//! # Compaction
+//!
+//! The log of a long-lived store grows without bound -- [compacting](
+//! EventStore::compact) the store folds the oldest events into its base
+//! state, after which the states preceding the compacted events can no
+//! longer be replayed.
+//!
+//! # Remarks
+//!
+//! The changes made to the timetable are recorded as the same [`Change`]s
+//! as the timetable's [history](crate::Timetable::history) (and the edits of
+//! an [`EditStack`](crate::EditStack)), such that the timetable of every
+//! replayed state retains the history of the changes which led to it.
+
+use crate::closures::{Closure, Closures};
+use crate::{
+    Activity, Change, Dataset, EntryId, Location, Record, TimeSlot, Timetable, TimetableError,
+};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// A change made to the dataset of an [`EventStore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A change was made to the timetable (e.g., a lesson was added, or moved
+    /// to a different room).
+    Timetable(Change),
+
+    /// A [`Closure`] was declared.
+    ClosureDeclared(Closure),
+
+    /// A [`Closure`] was lifted (e.g., because building works finished
+    /// early).
+    ClosureLifted {
+        /// The position the closure occupied in [`Closures::closures`].
+        index: usize,
+
+        /// The closure which was lifted.
+        closure: Closure,
+    },
+}
+
+/// An [`Event`] in the log of an [`EventStore`]: its position in the log,
+/// who recorded it, and when they recorded it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorded {
+    /// The position of the event in the log (starting from `0`).
+    pub sequence: u64,
+
+    /// The time at which the event was recorded.
+    pub at: DateTime<Utc>,
+
+    /// The person who recorded the event (if known).
+    pub author: Option<String>,
+
+    /// The event which was recorded.
+    pub event: Event,
+}
+
+/// The state of the dataset of an [`EventStore`] (i.e., the fold of some
+/// prefix of its log).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Projection {
+    /// The dataset (i.e., the timetable and its calendar).
+    pub dataset: Dataset,
+
+    /// The closures which have been declared.
+    pub closures: Closures,
+}
+
+impl Projection {
+    /// Applies the `recorded` event to the projection.
+    fn apply(&mut self, recorded: &Recorded) {
+        match &recorded.event {
+            Event::Timetable(change) => {
+                let timetable = &mut self.dataset.timetable;
+
+                timetable.apply(change);
+                Arc::make_mut(&mut timetable.history).push(Record {
+                    at: recorded.at,
+                    author: recorded.author.clone(),
+                    role: None,
+                    change: change.clone(),
+                });
+            }
+            Event::ClosureDeclared(closure) => {
+                self.closures.add(closure.clone());
+            }
+            Event::ClosureLifted { index, .. } => {
+                self.closures.remove(*index);
+            }
+        }
+    }
+}
+
+/// A dataset stored as an append-only log of [`Event`]s.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone)]
+pub struct EventStore {
+    base: Projection,
+    compacted: u64,
+    compacted_at: Option<DateTime<Utc>>,
+    log: Vec<Recorded>,
+    current: Projection,
+    author: Option<String>,
+}
+
+impl EventStore {
+    /// Creates a new `EventStore` (without any events) whose base state is
+    /// the `dataset` provided.
+    pub fn new(dataset: Dataset) -> Self {
+        let base = Projection {
+            dataset,
+            closures: Closures::new(),
+        };
+
+        Self {
+            current: base.clone(),
+            base,
+            compacted: 0,
+            compacted_at: None,
+            log: Vec::new(),
+            author: None,
+        }
+    }
+
+    /// Sets the person to whom subsequently recorded events are attributed.
+    pub fn set_author(&mut self, author: Option<String>) {
+        self.current.dataset.timetable.set_author(author.clone());
+        self.author = author;
+    }
+
+    /// Retrieves the current state of the dataset (i.e., the fold of every
+    /// event).
+    pub fn current(&self) -> &Projection {
+        &self.current
+    }
+
+    /// Retrieves the number of events recorded by the `EventStore` (including
+    /// those which have been compacted).
+    pub fn sequence(&self) -> u64 {
+        self.compacted + self.log.len() as u64
+    }
+
+    /// Retrieves the events of the log which have not been compacted, in
+    /// the order in which they were recorded.
+    pub fn events(&self) -> &[Recorded] {
+        &self.log
+    }
+
+    /// Records an `event` (e.g., one received from another device),
+    /// applying it to the current state of the dataset.
+    ///
+    /// # Returns
+    ///
+    /// The sequence number of the event.
+    pub fn record(&mut self, event: Event) -> u64 {
+        self.push(Utc::now(), event)
+    }
+
+    /// Makes an edit to the timetable: every mutation made by `f` is recorded
+    /// as an [`Event::Timetable`].
+    ///
+    /// # Returns
+    ///
+    /// The value returned by `f`.
+    pub fn edit<T>(&mut self, f: impl FnOnce(&mut Timetable) -> T) -> T {
+        let timetable = &mut self.current.dataset.timetable;
+        let start = timetable.history.len();
+        let value = f(timetable);
+
+        // The mutations have already been applied to the current state
+        let records: Vec<Record> = timetable.history[start..].to_vec();
+
+        for record in records {
+            self.log.push(Recorded {
+                sequence: self.sequence(),
+                at: record.at,
+                author: record.author,
+                event: Event::Timetable(record.change),
+            });
+        }
+
+        value
+    }
+
+    /// Schedules an `activity` for the `slot` provided -- *see
+    /// [`Timetable::insert`] for more information*.
+    pub fn insert(&mut self, slot: TimeSlot, activity: Activity) -> EntryId {
+        self.edit(|timetable| timetable.insert(slot, activity))
+    }
+
+    /// Removes the entry with the identifier `id` -- *see
+    /// [`Timetable::remove`] for more information*.
+    pub fn remove(&mut self, id: EntryId) -> bool {
+        self.edit(|timetable| timetable.remove(id).is_some())
+    }
+
+    /// Moves the entry with the identifier `id` to the `slot` provided --
+    /// *see [`Timetable::move_entry`] for more information*.
+    pub fn move_entry(
+        &mut self,
+        id: EntryId,
+        slot: TimeSlot,
+    ) -> Result<Option<TimeSlot>, TimetableError> {
+        self.edit(|timetable| timetable.move_entry(id, slot))
+    }
+
+    /// Changes the location of the lesson with the identifier `id` -- *see
+    /// [`Timetable::relocate`] for more information*.
+    pub fn relocate(&mut self, id: EntryId, location: Location) -> Option<Location> {
+        self.edit(|timetable| timetable.relocate(id, location))
+    }
+
+    /// Declares the `closure` provided.
+    ///
+    /// # Returns
+    ///
+    /// The index of the closure (within [`Closures::closures`]).
+    pub fn declare_closure(&mut self, closure: Closure) -> usize {
+        self.record(Event::ClosureDeclared(closure));
+        self.current.closures.closures().len() - 1
+    }
+
+    /// Lifts (and returns) the closure at position `index` of
+    /// [`Closures::closures`].
+    pub fn lift_closure(&mut self, index: usize) -> Option<Closure> {
+        let closure = self.current.closures.closures().get(index)?.clone();
+
+        self.record(Event::ClosureLifted {
+            index,
+            closure: closure.clone(),
+        });
+        Some(closure)
+    }
+
+    /// Reconstructs the state of the dataset after the first `sequence`
+    /// events were recorded (e.g., `0` for its state before any event).
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should the state precede an event which has been
+    /// [compacted](Self::compact), or should fewer than `sequence` events
+    /// have been recorded.
+    pub fn replay(&self, sequence: u64) -> Option<Projection> {
+        if sequence < self.compacted || sequence > self.sequence() {
+            return None;
+        }
+
+        let mut projection = self.base.clone();
+
+        for recorded in &self.log[..(sequence - self.compacted) as usize] {
+            projection.apply(recorded);
+        }
+
+        Some(projection)
+    }
+
+    /// Reconstructs the state of the dataset as it was at the time provided
+    /// (i.e., disregarding every event recorded after `at`).
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should an event recorded after `at` have been
+    /// [compacted](Self::compact).
+    pub fn as_of(&self, at: DateTime<Utc>) -> Option<Projection> {
+        if self.compacted_at.is_some_and(|compacted| compacted > at) {
+            return None;
+        }
+
+        let retained = self.log.iter().take_while(|recorded| recorded.at <= at);
+        self.replay(self.compacted + retained.count() as u64)
+    }
+
+    /// Compacts the log, folding every event preceding the `sequence`
+    /// provided into the base state of the `EventStore` (*see the
+    /// [module](self#compaction) documentation*).
+    ///
+    /// # Returns
+    ///
+    /// The number of events which were compacted.
+    pub fn compact(&mut self, sequence: u64) -> usize {
+        let count = sequence
+            .saturating_sub(self.compacted)
+            .min(self.log.len() as u64) as usize;
+
+        for recorded in self.log.drain(..count) {
+            self.base.apply(&recorded);
+            self.compacted_at = Some(recorded.at);
+        }
+
+        self.compacted += count as u64;
+        count
+    }
+
+    /// Appends an `event` recorded at the time provided to the log, and
+    /// applies it to the current state.
+    fn push(&mut self, at: DateTime<Utc>, event: Event) -> u64 {
+        let recorded = Recorded {
+            sequence: self.sequence(),
+            at,
+            author: self.author.clone(),
+            event,
+        };
+
+        self.current.apply(&recorded);
+        self.log.push(recorded);
+        self.sequence() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::closures::ClosureScope;
+    use crate::{timeslot, AcademicCalendar, Class, Subject};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn replayed_and_compacted() {
+        let mut store =
+            EventStore::new(Dataset::new(Timetable::new(), AcademicCalendar::default()));
+        store.set_author(Some("JSM".to_string()));
+
+        let lesson = store.insert(
+            timeslot!(W1MP1),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("10A".to_string()).unwrap(),
+                teacher: None,
+                location: "H201".parse().unwrap(),
+            },
+        );
+        let registration = store.insert(timeslot!(W1MP2), Activity::Registration);
+        store.move_entry(lesson, timeslot!(W1TP3)).unwrap();
+        assert!(store.remove(registration));
+
+        let closure = store.declare_closure(Closure {
+            scope: ClosureScope::Room("H201".parse().unwrap()),
+            start: NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(),
+            end: NaiveDate::from_ymd_opt(2023, 9, 8).unwrap(),
+            reason: "Flooding".to_string(),
+        });
+        assert!(store.lift_closure(closure).is_some());
+        assert!(store.lift_closure(closure).is_none());
+
+        assert_eq!(store.sequence(), 6);
+        assert!(store
+            .events()
+            .iter()
+            .all(|recorded| recorded.author.as_deref() == Some("JSM")));
+
+        // The fold of the whole log is the current state
+        let replayed = store.replay(6).unwrap();
+        assert_eq!(&replayed, store.current());
+        assert_eq!(replayed.dataset.timetable.history().len(), 4);
+        assert_eq!(store.replay(5).unwrap().closures.closures().len(), 1);
+        assert_eq!(
+            store
+                .replay(2)
+                .unwrap()
+                .dataset
+                .timetable
+                .get(lesson)
+                .unwrap()
+                .slot,
+            timeslot!(W1MP1)
+        );
+        assert!(store.replay(7).is_none());
+        assert_eq!(store.as_of(Utc::now()).as_ref(), Some(store.current()));
+
+        // Compacted states can no longer be replayed
+        assert_eq!(store.compact(3), 3);
+        assert_eq!(store.events().len(), 3);
+        assert_eq!(store.events()[0].sequence, 3);
+        assert!(store.replay(2).is_none());
+        assert_eq!(
+            store
+                .replay(3)
+                .unwrap()
+                .dataset
+                .timetable
+                .get(lesson)
+                .unwrap()
+                .slot,
+            timeslot!(W1TP3)
+        );
+        assert_eq!(&store.replay(6).unwrap(), store.current());
+        assert!(store.as_of(DateTime::<Utc>::MIN_UTC).is_none());
+    }
+}
@@ -0,0 +1,761 @@
+//! Generation of mock exam timetables.
+//!
+//! Given the [`Paper`]s sat by each year group, the halls and rooms in which
+//! they can be sat (*see [`allocate::Room`](crate::allocate::Room)*), and a
+//! window of dates, a [`MockGenerator`] assigns each paper a [`Session`] (the
+//! morning or the afternoon of a school day) such that as few students as
+//! possible are entered for multiple papers during the same session, and such
+//! that every paper can be seated:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::allocate::Room;
+//! use timetableau::exam::{MockGenerator, Paper, Session};
+//! use timetableau::{AcademicCalendar, Subject, Term, WeekCycle, YearGroup};
+//!
+//! let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+//! let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+//! let cycle = WeekCycle::new(date(4), calendar).unwrap();
+//!
+//! let subject = |name: &str| Subject::new(name.to_string()).unwrap();
+//! let year = YearGroup::new(11).unwrap();
+//! let mocks = MockGenerator::new(date(4)..=date(5))
+//!     .room(Room::new("Hall".parse().unwrap(), 150))
+//!     .paper(Paper::new(subject("Maths"), year, 120))
+//!     .paper(Paper::new(subject("English"), year, 120))
+//!     .generate(&cycle);
+//!
+//! // Every Year 11 student sits both papers, so they are sat during different sessions
+//! assert_eq!(mocks.sittings[0].session, Session::Morning);
+//! assert_eq!(mocks.sittings[1].session, Session::Afternoon);
+//! assert!(mocks.clashes.is_empty());
+//! ```
+//!
+//! The lessons of each year group which are suspended by its papers are
+//! found using [`MockTimetable::suspensions`], and the candidates who are
+//! still entered for overlapping papers (and the arrangements to be made for
+//! them) using [`MockTimetable::overlaps`] and
+//! [`MockTimetable::overlap_report`].
+
+use crate::allocate::Room;
+use crate::{Activity, EntryId, Location, Period, Subject, Timetable, WeekCycle, YearGroup};
+use chrono::NaiveDate;
+use std::ops::RangeInclusive;
+
+/// A session of a school day during which papers are sat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Session {
+    /// The morning session, starting at period one (papers may last up to
+    /// three periods).
+    Morning,
+
+    /// The afternoon session, starting at period four (papers may last up to
+    /// two periods).
+    Afternoon,
+}
+
+impl Session {
+    /// The sessions of a school day, in chronological order.
+    pub const ALL: [Session; 2] = [Session::Morning, Session::Afternoon];
+
+    /// Retrieves the periods of the session (in chronological order).
+    pub fn periods(self) -> &'static [Period] {
+        match self {
+            Self::Morning => &[Period::First, Period::Second, Period::Third],
+            Self::Afternoon => &[Period::Fourth, Period::Fifth],
+        }
+    }
+}
+
+/// A paper sat by (some of) the students of a year group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paper {
+    /// The subject of the paper.
+    pub subject: Subject,
+
+    /// The year group sitting the paper.
+    pub year: YearGroup,
+
+    /// The number of candidates sitting the paper.
+    pub size: u32,
+
+    /// The number of periods for which the paper lasts.
+    pub periods: usize,
+
+    /// The identifiers of the candidates sitting the paper, or an empty list
+    /// should every student of the year group sit it.
+    pub students: Vec<String>,
+
+    /// The awarding body and component code of the paper (e.g.,
+    /// `AQA 8300/1H`), should it be known.
+    pub code: Option<String>,
+}
+
+impl Paper {
+    /// Creates a new `Paper` lasting two periods, which is sat by every
+    /// student of the year group (i.e., by `size` candidates).
+    pub fn new(subject: Subject, year: YearGroup, size: u32) -> Self {
+        Self {
+            subject,
+            year,
+            size,
+            periods: 2,
+            students: Vec::new(),
+            code: None,
+        }
+    }
+
+    /// Sets the number of periods for which the paper lasts.
+    pub fn periods(mut self, periods: usize) -> Self {
+        self.periods = periods;
+        self
+    }
+
+    /// Records that the student with the identifier provided sits the paper
+    /// (such that the paper is no longer sat by every student of the year
+    /// group).
+    pub fn student(mut self, student: &str) -> Self {
+        self.students.push(student.to_string());
+        self
+    }
+
+    /// Sets the awarding body and component code of the paper (as shown by
+    /// the [overlap report](MockTimetable::overlap_report)).
+    pub fn code(mut self, code: &str) -> Self {
+        self.code = Some(code.to_string());
+        self
+    }
+
+    /// Calculates the number of candidates who are entered for both this
+    /// paper and the `other` paper.
+    ///
+    /// # Remarks
+    ///
+    /// Should either paper be sat by every student of its year group, every
+    /// candidate of the other paper of the same year group is assumed to be
+    /// entered for both.
+    pub fn shared_candidates(&self, other: &Paper) -> u32 {
+        if self.year != other.year {
+            return 0;
+        }
+
+        match (self.students.is_empty(), other.students.is_empty()) {
+            (true, true) => self.size.min(other.size),
+            (true, false) => other.size,
+            (false, true) => self.size,
+            (false, false) => self
+                .students
+                .iter()
+                .filter(|student| other.students.contains(student))
+                .count() as u32,
+        }
+    }
+}
+
+/// The session during which a [`Paper`] is sat, and the rooms in which its
+/// candidates are seated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sitting {
+    /// The index of the paper (within [`MockGenerator::papers`]).
+    pub paper: usize,
+
+    /// The year group sitting the paper.
+    pub year: YearGroup,
+
+    /// The date on which the paper is sat.
+    pub date: NaiveDate,
+
+    /// The session during which the paper is sat.
+    pub session: Session,
+
+    /// The periods during which the paper is sat.
+    pub periods: Vec<Period>,
+
+    /// The rooms in which the candidates are seated, and the number of
+    /// candidates seated in each.
+    pub rooms: Vec<(Location, u32)>,
+}
+
+/// Two papers sat during the same session by some of the same candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExamClash {
+    /// The index of the first paper.
+    pub first: usize,
+
+    /// The index of the second paper.
+    pub second: usize,
+
+    /// The number of candidates entered for both papers.
+    pub candidates: u32,
+}
+
+/// A candidate who is entered for two [`Paper`]s sat during the same
+/// session, and the arrangement to be made for them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExamOverlap {
+    /// The identifier (i.e., the candidate number) of the candidate.
+    pub student: String,
+
+    /// The index of the first paper.
+    pub first: usize,
+
+    /// The index of the second paper.
+    pub second: usize,
+
+    /// The date on which both papers are sat.
+    pub date: NaiveDate,
+
+    /// The session during which both papers are sat.
+    pub session: Session,
+
+    /// The standard arrangement for the overlap.
+    pub resolution: Resolution,
+}
+
+/// The standard arrangement for a candidate entered for overlapping papers,
+/// under which the candidate is supervised from the start of the first paper
+/// until the start of the second (such that they cannot learn its content).
+///
+/// The paper lasting fewer periods (or, should both last as many periods,
+/// the paper added later) is the one sat later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// The `later` paper is sat immediately after the `earlier` paper.
+    Supervised {
+        /// The index of the paper sat as timetabled.
+        earlier: usize,
+
+        /// The index of the paper sat immediately afterwards.
+        later: usize,
+    },
+
+    /// The `paper` is sat during the following session (i.e., as both
+    /// papers would not fit within the remainder of the day).
+    Deferred {
+        /// The index of the paper sat during the following session.
+        paper: usize,
+    },
+}
+
+/// A lesson which is suspended as its class is sitting a paper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Suspension {
+    /// The date on which the lesson is suspended.
+    pub date: NaiveDate,
+
+    /// The identifier of the lesson.
+    pub entry: EntryId,
+
+    /// The index of the sitting (within [`MockTimetable::sittings`]) during
+    /// which the lesson is suspended.
+    pub sitting: usize,
+}
+
+/// A mock exam timetable produced by a [`MockGenerator`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MockTimetable {
+    /// The sittings of the papers, in chronological order.
+    pub sittings: Vec<Sitting>,
+
+    /// The clashes which could not be avoided.
+    pub clashes: Vec<ExamClash>,
+
+    /// The indexes of the papers which could not be scheduled (i.e., as
+    /// they last longer than a session, or as no session has enough seats).
+    pub unscheduled: Vec<usize>,
+}
+
+impl MockTimetable {
+    /// Finds the lessons of the `timetable` provided which are suspended by
+    /// the sittings (i.e., the lessons of each year group taking place
+    /// during the periods of its papers), in chronological order.
+    ///
+    /// Should the same lesson be suspended by multiple sittings (e.g., due
+    /// to a clash), it is only listed once (for the earliest sitting).
+    pub fn suspensions(&self, timetable: &Timetable, cycle: &WeekCycle) -> Vec<Suspension> {
+        let mut suspensions: Vec<Suspension> = Vec::new();
+
+        for (index, sitting) in self.sittings.iter().enumerate() {
+            for occurrence in timetable.occurrences(cycle, sitting.date) {
+                let Some(entry) = occurrence.entry else {
+                    continue;
+                };
+
+                let sat = match &occurrence.activity {
+                    Activity::Lesson { class, .. } => {
+                        YearGroup::of_class(class.reference()) == Some(sitting.year)
+                    }
+                    _ => false,
+                };
+
+                if sat
+                    && sitting.periods.contains(&occurrence.slot.period)
+                    && !suspensions
+                        .iter()
+                        .any(|other| other.date == sitting.date && other.entry == entry)
+                {
+                    suspensions.push(Suspension {
+                        date: sitting.date,
+                        entry,
+                        sitting: index,
+                    });
+                }
+            }
+        }
+
+        suspensions
+    }
+
+    /// Finds every candidate who is entered for two papers sat during the
+    /// same session, along with the standard [`Resolution`] of each overlap
+    /// (in chronological order).
+    ///
+    /// The `papers` must be those of the [`MockGenerator`] which produced
+    /// the timetable (*see [`MockGenerator::papers`]*).
+    ///
+    /// # Remarks
+    ///
+    /// Only named candidates can be reported -- two papers which are both
+    /// sat by every student of a year group produce an [`ExamClash`], but no
+    /// overlaps (and a paper sat by every student of a year group overlaps
+    /// with each named candidate of the other paper).
+    pub fn overlaps(&self, papers: &[Paper]) -> Vec<ExamOverlap> {
+        let mut overlaps = Vec::new();
+
+        for (index, sitting) in self.sittings.iter().enumerate() {
+            for other in &self.sittings[index + 1..] {
+                if other.date != sitting.date
+                    || !other
+                        .periods
+                        .iter()
+                        .any(|period| sitting.periods.contains(period))
+                {
+                    continue;
+                }
+
+                let (first, second) = (
+                    sitting.paper.min(other.paper),
+                    sitting.paper.max(other.paper),
+                );
+                let (a, b) = (&papers[first], &papers[second]);
+
+                if a.year != b.year {
+                    continue;
+                }
+
+                let students: Vec<&String> = match (a.students.is_empty(), b.students.is_empty()) {
+                    (true, true) => Vec::new(),
+                    (true, false) => b.students.iter().collect(),
+                    (false, true) => a.students.iter().collect(),
+                    (false, false) => a
+                        .students
+                        .iter()
+                        .filter(|student| b.students.contains(student))
+                        .collect(),
+                };
+
+                // The shorter paper (or the paper added later) is sat later
+                let (earlier, later) = if b.periods <= a.periods {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                let remaining = Period::ALL.len() - sitting.session.periods()[0] as usize;
+                let resolution = if a.periods + b.periods <= remaining {
+                    Resolution::Supervised { earlier, later }
+                } else {
+                    Resolution::Deferred { paper: later }
+                };
+
+                overlaps.extend(students.into_iter().map(|student| ExamOverlap {
+                    student: student.clone(),
+                    first,
+                    second,
+                    date: sitting.date,
+                    session: sitting.session,
+                    resolution,
+                }));
+            }
+        }
+
+        overlaps
+    }
+
+    /// Renders the overlap report filed with the awarding bodies, with a line
+    /// per overlap listing the candidate, both papers (by their codes, should
+    /// they be known), and the arrangement made -- *see [`Self::overlaps`]*.
+    pub fn overlap_report(&self, papers: &[Paper]) -> String {
+        let overlaps = self.overlaps(papers);
+        let mut output = String::from("Exam overlap report\n\n");
+
+        if overlaps.is_empty() {
+            output.push_str("No overlaps\n");
+            return output;
+        }
+
+        let name = |index: usize| {
+            let paper = &papers[index];
+
+            match &paper.code {
+                Some(code) => format!("{} ({})", paper.subject.name(), code),
+                None => paper.subject.name().clone(),
+            }
+        };
+
+        for overlap in overlaps {
+            let arrangement = match overlap.resolution {
+                Resolution::Supervised { earlier, later } => format!(
+                    "{} sat after {} under supervision",
+                    papers[later].subject.name(),
+                    papers[earlier].subject.name()
+                ),
+                Resolution::Deferred { paper } => format!(
+                    "{} sat during the following session under supervision",
+                    papers[paper].subject.name()
+                ),
+            };
+            let session = match overlap.session {
+                Session::Morning => "morning",
+                Session::Afternoon => "afternoon",
+            };
+
+            output.push_str(&format!(
+                "{}: {} and {}, {} ({}) -- {}\n",
+                overlap.student,
+                name(overlap.first),
+                name(overlap.second),
+                overlap.date.format("%A %d %B"),
+                session,
+                arrangement
+            ));
+        }
+
+        output
+    }
+}
+
+/// Generates a mock exam timetable.
+///
+/// *See the [module](self) documentation for more information*.
+///
+/// # Remarks
+///
+/// Papers are scheduled greedily, those sharing candidates with the most
+/// other papers (and then the largest papers) first -- each is given the
+/// earliest session with enough seats in which the fewest candidates are
+/// already sitting another paper. Should every session with enough seats
+/// have a clash, the clash is recorded rather than the paper being left
+/// unscheduled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockGenerator {
+    dates: RangeInclusive<NaiveDate>,
+    rooms: Vec<Room>,
+    papers: Vec<Paper>,
+}
+
+impl MockGenerator {
+    /// Creates a new `MockGenerator` which schedules papers on the school
+    /// days within the range of `dates` provided, without any rooms or
+    /// papers.
+    pub fn new(dates: RangeInclusive<NaiveDate>) -> Self {
+        Self {
+            dates,
+            rooms: Vec::new(),
+            papers: Vec::new(),
+        }
+    }
+
+    /// Adds a room in which papers can be sat (up to its capacity of
+    /// candidates during each session).
+    pub fn room(mut self, room: Room) -> Self {
+        self.rooms.push(room);
+        self
+    }
+
+    /// Adds a paper to be scheduled.
+    pub fn paper(mut self, paper: Paper) -> Self {
+        self.papers.push(paper);
+        self
+    }
+
+    /// Retrieves every paper (in the order in which they were added).
+    pub fn papers(&self) -> &[Paper] {
+        &self.papers
+    }
+
+    /// Generates the mock exam timetable, using the calendar of the `cycle`
+    /// provided to determine the school days within the window of dates.
+    pub fn generate(&self, cycle: &WeekCycle) -> MockTimetable {
+        let calendar = cycle.calendar();
+        let sessions: Vec<(NaiveDate, Session)> = self
+            .dates
+            .start()
+            .iter_days()
+            .take_while(|date| date <= self.dates.end())
+            .filter(|date| calendar.is_school_day(*date) && cycle.week_for(*date).is_some())
+            .flat_map(|date| Session::ALL.map(|session| (date, session)))
+            .collect();
+
+        let shared = |a: usize, b: usize| self.papers[a].shared_candidates(&self.papers[b]);
+
+        // Schedule the papers sharing candidates with the most other papers
+        // first, as they are the hardest to place
+        let mut order: Vec<usize> = (0..self.papers.len()).collect();
+        order.sort_by_key(|&index| {
+            let degree = (0..self.papers.len())
+                .filter(|&other| other != index && shared(index, other) > 0)
+                .count();
+
+            (
+                std::cmp::Reverse(degree),
+                std::cmp::Reverse(self.papers[index].size),
+            )
+        });
+
+        // The seats remaining in each room during each session, and the
+        // papers sat during each session
+        let mut seats: Vec<Vec<u32>> =
+            vec![self.rooms.iter().map(|room| room.capacity).collect(); sessions.len()];
+        let mut sat: Vec<Vec<usize>> = vec![Vec::new(); sessions.len()];
+        let mut mocks = MockTimetable::default();
+
+        for index in order {
+            let paper = &self.papers[index];
+
+            let best = (0..sessions.len())
+                .filter(|&session| {
+                    paper.periods > 0
+                        && paper.periods <= sessions[session].1.periods().len()
+                        && seats[session].iter().sum::<u32>() >= paper.size
+                })
+                .min_by_key(|&session| {
+                    sat[session]
+                        .iter()
+                        .map(|&other| shared(index, other))
+                        .sum::<u32>()
+                });
+
+            let Some(session) = best else {
+                mocks.unscheduled.push(index);
+                continue;
+            };
+
+            for &other in &sat[session] {
+                let candidates = shared(index, other);
+
+                if candidates > 0 {
+                    mocks.clashes.push(ExamClash {
+                        first: other.min(index),
+                        second: other.max(index),
+                        candidates,
+                    });
+                }
+            }
+
+            // Seat the candidates in the rooms with the most seats remaining
+            let mut rooms: Vec<usize> = (0..self.rooms.len()).collect();
+            rooms.sort_by_key(|&room| std::cmp::Reverse(seats[session][room]));
+
+            let mut remaining = paper.size;
+            let mut seated = Vec::new();
+
+            for room in rooms {
+                if remaining == 0 {
+                    break;
+                }
+
+                let candidates = remaining.min(seats[session][room]);
+
+                if candidates > 0 {
+                    seats[session][room] -= candidates;
+                    remaining -= candidates;
+                    seated.push((self.rooms[room].location, candidates));
+                }
+            }
+
+            sat[session].push(index);
+
+            let (date, time) = sessions[session];
+
+            mocks.sittings.push(Sitting {
+                paper: index,
+                year: paper.year,
+                date,
+                session: time,
+                periods: time.periods()[..paper.periods].to_vec(),
+                rooms: seated,
+            });
+        }
+
+        mocks
+            .sittings
+            .sort_by_key(|sitting| (sitting.date, sitting.session, sitting.paper));
+        mocks.unscheduled.sort_unstable();
+        mocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Term};
+
+    #[test]
+    fn mock_exams_generated() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+        let cycle = WeekCycle::new(date(4), calendar).unwrap();
+
+        let subject = |name: &str| Subject::new(name.to_string()).unwrap();
+        let year = |number| YearGroup::new(number).unwrap();
+        let generator = |end| {
+            MockGenerator::new(date(4)..=date(end))
+                .room(Room::new("Hall".parse().unwrap(), 150))
+                .room(Room::new("Sports Hall".parse().unwrap(), 120))
+                .paper(Paper::new(subject("Maths"), year(11), 100))
+                .paper(Paper::new(subject("English"), year(11), 100))
+                .paper(Paper::new(subject("Science"), year(11), 100).periods(3))
+                .paper(
+                    Paper::new(subject("Physics"), year(13), 2)
+                        .student("ADA")
+                        .student("BOB"),
+                )
+                .paper(Paper::new(subject("History"), year(10), 300))
+                .paper(Paper::new(subject("Drama"), year(10), 20).periods(4))
+        };
+
+        let mocks = generator(5).generate(&cycle);
+        let placed: Vec<(usize, NaiveDate, Session)> = mocks
+            .sittings
+            .iter()
+            .map(|sitting| (sitting.paper, sitting.date, sitting.session))
+            .collect();
+
+        // Papers which are too long, or too large, cannot be scheduled
+        assert_eq!(
+            placed,
+            [
+                (0, date(4), Session::Morning),
+                (3, date(4), Session::Morning),
+                (1, date(4), Session::Afternoon),
+                (2, date(5), Session::Morning),
+            ]
+        );
+        assert!(mocks.clashes.is_empty());
+        assert_eq!(mocks.unscheduled, [4, 5]);
+        assert_eq!(mocks.sittings[0].rooms, [("Hall".parse().unwrap(), 100)]);
+        assert_eq!(
+            mocks.sittings[1].rooms,
+            [("Sports Hall".parse().unwrap(), 2)]
+        );
+        assert_eq!(mocks.sittings[3].periods, Session::Morning.periods());
+
+        // Within a single day, one paper must clash
+        let mocks = generator(4).generate(&cycle);
+
+        assert_eq!(
+            mocks.clashes,
+            [ExamClash {
+                first: 0,
+                second: 2,
+                candidates: 100
+            }]
+        );
+
+        // Only the lessons of Year 11 during the papers are suspended
+        let lesson = |class: &str| Activity::Lesson {
+            subject: subject("Ma"),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: None,
+            location: "H201".parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        let first = timetable.insert(timeslot!(W1MP1), lesson("11A"));
+        timetable.insert(timeslot!(W1MP1), lesson("10A"));
+        let third = timetable.insert(timeslot!(W1MP3), lesson("11A"));
+        timetable.insert(timeslot!(W1TP3), lesson("11A"));
+
+        let entries: Vec<EntryId> = mocks
+            .suspensions(&timetable, &cycle)
+            .iter()
+            .map(|suspension| suspension.entry)
+            .collect();
+
+        assert_eq!(entries, [first, third]);
+    }
+
+    #[test]
+    fn exam_overlaps_reported() {
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let subject = |name: &str| Subject::new(name.to_string()).unwrap();
+        let year = YearGroup::new(13).unwrap();
+        let papers = [
+            Paper::new(subject("Physics"), year, 2)
+                .student("ADA")
+                .student("BOB")
+                .code("AQA 7408/1"),
+            Paper::new(subject("Chemistry"), year, 1)
+                .student("ADA")
+                .periods(3),
+            Paper::new(subject("Maths"), year, 1).student("ADA"),
+            Paper::new(subject("Further Maths"), year, 40),
+            Paper::new(subject("History"), year, 40),
+        ];
+        let sitting = |paper: usize, session: Session| Sitting {
+            paper,
+            year,
+            date,
+            session,
+            periods: session.periods()[..papers[paper].periods].to_vec(),
+            rooms: Vec::new(),
+        };
+
+        let mocks = MockTimetable {
+            sittings: vec![
+                sitting(0, Session::Morning),
+                sitting(1, Session::Morning),
+                sitting(2, Session::Afternoon),
+                sitting(3, Session::Afternoon),
+                sitting(4, Session::Afternoon),
+            ],
+            ..MockTimetable::default()
+        };
+
+        // Papers sat by the whole year group only overlap for named candidates
+        let overlaps = mocks.overlaps(&papers);
+        let resolutions: Vec<(&str, Resolution)> = overlaps
+            .iter()
+            .map(|overlap| (overlap.student.as_str(), overlap.resolution))
+            .collect();
+
+        assert_eq!(
+            resolutions,
+            [
+                (
+                    "ADA",
+                    Resolution::Supervised {
+                        earlier: 1,
+                        later: 0
+                    }
+                ),
+                ("ADA", Resolution::Deferred { paper: 3 }),
+                ("ADA", Resolution::Deferred { paper: 4 }),
+            ]
+        );
+        assert_eq!(
+            mocks.overlap_report(&papers),
+            "Exam overlap report\n\n\
+             ADA: Physics (AQA 7408/1) and Chemistry, Monday 04 September (morning) \
+             -- Physics sat after Chemistry under supervision\n\
+             ADA: Maths and Further Maths, Monday 04 September (afternoon) \
+             -- Further Maths sat during the following session under supervision\n\
+             ADA: Maths and History, Monday 04 September (afternoon) \
+             -- History sat during the following session under supervision\n"
+        );
+        assert_eq!(
+            MockTimetable::default().overlap_report(&papers),
+            "Exam overlap report\n\nNo overlaps\n"
+        );
+    }
+}
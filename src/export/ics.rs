@@ -0,0 +1,867 @@
+//! Exports timetables in the iCalendar (`.ics`) format described by
+//! [RFC 5545], allowing them to be subscribed to from phone calendars.
+//!
+//! [RFC 5545]: https://www.rfc-editor.org/rfc/rfc5545
+
+use crate::clubs::Clubs;
+use crate::deadlines::{Deadline, Deadlines};
+use crate::timetable::periods_of;
+use crate::{
+    Activity, AfterSchoolBells, Location, London, Occurrence, Period, Room, RoomKind, School,
+    Timetable, WeekCycle,
+};
+use chrono::prelude::*;
+use chrono::Duration;
+use std::ops::RangeInclusive;
+
+/// The default template of an event's `SUMMARY`.
+pub const DEFAULT_SUMMARY: &str = "{subject} ({location})";
+
+/// The default template of an event's `DESCRIPTION`.
+pub const DEFAULT_DESCRIPTION: &str = "{class} -- {location_name}{room_change}";
+
+/// The kind of iCalendar component as which coursework deadlines are
+/// exported (*see [`IcsExporter::export_with_deadlines`]*).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DeadlineItem {
+    /// An all-day event (`VEVENT`) on the due date, which every calendar
+    /// application displays.
+    #[default]
+    Event,
+
+    /// A task (`VTODO`) due on the due date, which calendar applications
+    /// supporting tasks allow to be marked as completed.
+    Todo,
+}
+
+/// Exports the [`Occurrence`]s of a [`Timetable`] as an iCalendar feed.
+///
+/// # Templates
+///
+/// The `SUMMARY` and `DESCRIPTION` of each lesson are produced from
+/// templates -- each template may contain any of the following placeholders,
+/// which are replaced with information about the lesson:
+///
+/// * `{subject}` -- the subject of the lesson (e.g., `Maths`).
+/// * `{class}` -- the class of the lesson (e.g., `10A/Ma1`).
+/// * `{teacher}` -- the staff code of the lesson's teacher (e.g., `JSM`), or
+///   nothing if the teacher is not known -- the staff codes of a team-taught
+///   lesson's teachers are separated by commas (e.g., `JSM, ABC`).
+/// * `{location}` -- the room identifier of the lesson (e.g., `H201`) -- the
+///   rooms of a split lesson are separated by commas (e.g., `H201, H202`),
+///   as are the rooms of its `LOCATION`.
+/// * `{location_name}` -- the [verbose name](Location::verbose_name) of the
+///   lesson's location (e.g., `Howard Block, Floor 2, Room 01`).
+/// * `{school}` -- the school at which the lesson takes place.
+/// * `{period}` -- the period of the lesson (e.g., `P3`), or the first and
+///   last periods of a lesson spanning multiple periods (e.g., `P3-P4`).
+/// * `{room_change}` -- an annotation (e.g., ` (moved from H201)`) if the
+///   lesson has been moved by a [`RoomOverride`](crate::RoomOverride), or
+///   nothing if it has not.
+///
+/// Activities which are not lessons use their [`Display`](std::fmt::Display)
+/// implementation as their `SUMMARY` and have no `DESCRIPTION`. Breaks are not
+/// exported, and an activity spanning multiple periods (e.g., a double
+/// lesson) is exported as a single event (with a single reminder), from the
+/// start of its first period to the end of its last.
+///
+/// # Alarms
+///
+/// A reminder (`VALARM`) can be attached to each lesson -- the reminder for a
+/// lesson which follows a lesson at a different school (i.e., a cross-site
+/// lesson) can be configured separately from the reminder for any other
+/// lesson, as travelling between the schools takes significantly longer. A
+/// [remote](crate::Remote) lesson is never cross-site, and does not change
+/// the school at which the previous lesson took place.
+///
+/// # Time Zones
+///
+/// By default, the times of each event are exported as *floating* times
+/// (e.g., `DTSTART:20231030T085000`), which calendar applications display in
+/// the time zone of the device -- a device set to another time zone (e.g.,
+/// on a school trip abroad) would display the lessons at the wrong times.
+/// Using [`Self::utc`], the times are instead converted from the time in
+/// London (*see [`London`]*) into UTC (e.g., `DTSTART:20231027T075000Z` for
+/// `08:50` BST), such that they are displayed correctly in any time zone and
+/// either side of the clocks changing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcsExporter {
+    same_site_alarm: Option<Duration>,
+    cross_site_alarm: Option<Duration>,
+    summary: String,
+    description: String,
+    stamp: DateTime<Utc>,
+    deadline_item: DeadlineItem,
+    utc: bool,
+}
+
+impl IcsExporter {
+    /// Creates a new `IcsExporter` with no alarms and the
+    /// [default summary](DEFAULT_SUMMARY) and
+    /// [default description](DEFAULT_DESCRIPTION) templates.
+    pub fn new() -> Self {
+        Self {
+            same_site_alarm: None,
+            cross_site_alarm: None,
+            summary: DEFAULT_SUMMARY.to_string(),
+            description: DEFAULT_DESCRIPTION.to_string(),
+            stamp: Utc::now(),
+            deadline_item: DeadlineItem::Event,
+            utc: false,
+        }
+    }
+
+    /// Sets the reminders attached to each lesson.
+    ///
+    /// `same_site` is the time before the start of a lesson at which a reminder
+    /// is given should the previous lesson be at the same school (or should
+    /// there be no previous lesson); `cross_site` is the time at which a
+    /// reminder is given should the previous lesson be at a different school.
+    /// [`None`] disables the corresponding reminder.
+    pub fn alarms(mut self, same_site: Option<Duration>, cross_site: Option<Duration>) -> Self {
+        self.same_site_alarm = same_site;
+        self.cross_site_alarm = cross_site;
+        self
+    }
+
+    /// Sets the template of each lesson's `SUMMARY`.
+    ///
+    /// *See the [template documentation](IcsExporter#templates) for more
+    /// information*.
+    pub fn summary(mut self, template: &str) -> Self {
+        self.summary = template.to_string();
+        self
+    }
+
+    /// Sets the template of each lesson's `DESCRIPTION`.
+    ///
+    /// *See the [template documentation](IcsExporter#templates) for more
+    /// information*.
+    pub fn description(mut self, template: &str) -> Self {
+        self.description = template.to_string();
+        self
+    }
+
+    /// Sets the time at which the feed is considered to have been created
+    /// (i.e., the `DTSTAMP` of each event).
+    ///
+    /// By default, this is the time at which the exporter was created --
+    /// setting a fixed time allows the output to be reproduced exactly.
+    pub fn stamp(mut self, stamp: DateTime<Utc>) -> Self {
+        self.stamp = stamp;
+        self
+    }
+
+    /// Sets whether the times of each event are exported in UTC (rather than
+    /// as floating times).
+    ///
+    /// *See the [time zone documentation](IcsExporter#time-zones) for more
+    /// information*.
+    pub fn utc(mut self, utc: bool) -> Self {
+        self.utc = utc;
+        self
+    }
+
+    /// Sets the kind of component as which coursework deadlines are exported
+    /// (by default, [`DeadlineItem::Event`]).
+    pub fn deadline_item(mut self, item: DeadlineItem) -> Self {
+        self.deadline_item = item;
+        self
+    }
+
+    /// Exports every [`Occurrence`] of the `timetable` taking place within the
+    /// range of `dates` provided.
+    ///
+    /// The occurrences are produced by [`Timetable::occurrences`] -- as a
+    /// result, any [`DayException`](crate::DayException)s and
+    /// [`RoomOverride`](crate::RoomOverride)s are reflected in the feed.
+    pub fn export(
+        &self,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        dates: RangeInclusive<NaiveDate>,
+    ) -> String {
+        self.export_with_deadlines(timetable, cycle, dates, &Deadlines::new())
+    }
+
+    /// Exports every [`Occurrence`] of the `timetable` taking place within the
+    /// range of `dates` provided (*see [`Self::export`]*), together with the
+    /// coursework `deadlines` due within the range which are relevant to the
+    /// timetable (*see [`Deadlines::relevant_to`]*) as all-day items.
+    ///
+    /// The `SUMMARY` of each deadline is its subject and title (e.g.,
+    /// `History: NEA first draft`), and its `DESCRIPTION` is its class --
+    /// *see [`Self::deadline_item`]* for the kind of component produced.
+    pub fn export_with_deadlines(
+        &self,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        dates: RangeInclusive<NaiveDate>,
+        deadlines: &Deadlines,
+    ) -> String {
+        let deadlines: Vec<(usize, &Deadline)> = deadlines.relevant_to(timetable).collect();
+        let mut output = String::new();
+
+        line(&mut output, "BEGIN:VCALENDAR");
+        line(&mut output, "VERSION:2.0");
+        line(&mut output, "PRODID:-//Timetableau//Timetableau//EN");
+        line(&mut output, "CALSCALE:GREGORIAN");
+
+        for date in dates
+            .start()
+            .iter_days()
+            .take_while(|date| date <= dates.end())
+        {
+            for (index, deadline) in deadlines
+                .iter()
+                .filter(|(_, deadline)| deadline.due == date)
+            {
+                self.deadline(&mut output, *index, deadline);
+            }
+
+            let mut previous: Option<School> = None;
+            let occurrences = timetable.occurrences(cycle, date);
+
+            for occurrence in &occurrences {
+                if occurrence.activity == Activity::Break {
+                    continue;
+                }
+
+                // An entry spanning multiple periods is exported as a single
+                // event, from the first to the last of its periods
+                let (first, last) = periods_of(&occurrences, occurrence);
+
+                if first != occurrence.slot.period {
+                    continue;
+                }
+
+                // Remote lessons take place at neither school
+                let school = occurrence
+                    .activity
+                    .location()
+                    .filter(|location| location.kind() != RoomKind::Remote)
+                    .map(Location::school);
+
+                // A lesson is cross-site if the previous lesson of the day
+                // took place at a different school
+                let cross_site = matches!((previous, school), (Some(a), Some(b)) if a != b);
+
+                if school.is_some() {
+                    previous = school;
+                }
+
+                self.event(&mut output, timetable, cycle, occurrence, last, cross_site);
+            }
+        }
+
+        line(&mut output, "END:VCALENDAR");
+        output
+    }
+
+    /// Exports every meeting of the `clubs` taking place within the range of
+    /// `dates` provided (*see [`Club::dates`](crate::clubs::Club::dates)*).
+    ///
+    /// The `SUMMARY` of each meeting is the name of its club, and its
+    /// `DESCRIPTION` lists the eligible year groups and the lead of the club
+    /// -- the templates and alarms of the exporter only apply to lessons.
+    pub fn export_clubs(
+        &self,
+        clubs: &Clubs,
+        cycle: &WeekCycle,
+        bells: &AfterSchoolBells,
+        dates: RangeInclusive<NaiveDate>,
+    ) -> String {
+        let mut output = String::new();
+
+        line(&mut output, "BEGIN:VCALENDAR");
+        line(&mut output, "VERSION:2.0");
+        line(&mut output, "PRODID:-//Timetableau//Timetableau//EN");
+        line(&mut output, "CALSCALE:GREGORIAN");
+
+        for date in dates
+            .start()
+            .iter_days()
+            .take_while(|date| date <= dates.end())
+        {
+            for (index, club) in clubs.clubs().iter().enumerate() {
+                if !club.meets_on(cycle, date) {
+                    continue;
+                }
+
+                let (start, end) = club.times(cycle, bells);
+                let description = format!(
+                    "Years {}-{} -- led by {}",
+                    club.years.start().get(),
+                    club.years.end().get(),
+                    club.lead
+                );
+
+                line(&mut output, "BEGIN:VEVENT");
+                line(
+                    &mut output,
+                    &format!("UID:{}-club{}@timetableau", date.format("%Y%m%d"), index),
+                );
+                line(
+                    &mut output,
+                    &format!("DTSTAMP:{}", self.stamp.format("%Y%m%dT%H%M%SZ")),
+                );
+                line(
+                    &mut output,
+                    &format!("DTSTART:{}", self.time(date.and_time(start))),
+                );
+                line(
+                    &mut output,
+                    &format!("DTEND:{}", self.time(date.and_time(end))),
+                );
+                line(&mut output, &format!("SUMMARY:{}", escape(&club.name)));
+                line(
+                    &mut output,
+                    &format!("DESCRIPTION:{}", escape(&description)),
+                );
+                line(
+                    &mut output,
+                    &format!("LOCATION:{}", escape(&club.venue.to_string())),
+                );
+                line(&mut output, "END:VEVENT");
+            }
+        }
+
+        line(&mut output, "END:VCALENDAR");
+        output
+    }
+
+    // Write a single all-day VEVENT (or VTODO) for the deadline provided
+    fn deadline(&self, output: &mut String, index: usize, deadline: &Deadline) {
+        let component = match self.deadline_item {
+            DeadlineItem::Event => "VEVENT",
+            DeadlineItem::Todo => "VTODO",
+        };
+
+        line(output, &format!("BEGIN:{}", component));
+        line(
+            output,
+            &format!(
+                "UID:{}-deadline{}@timetableau",
+                deadline.due.format("%Y%m%d"),
+                index
+            ),
+        );
+        line(
+            output,
+            &format!("DTSTAMP:{}", self.stamp.format("%Y%m%dT%H%M%SZ")),
+        );
+
+        match self.deadline_item {
+            DeadlineItem::Event => {
+                line(
+                    output,
+                    &format!("DTSTART;VALUE=DATE:{}", deadline.due.format("%Y%m%d")),
+                );
+                line(
+                    output,
+                    &format!(
+                        "DTEND;VALUE=DATE:{}",
+                        (deadline.due + Duration::days(1)).format("%Y%m%d")
+                    ),
+                );
+                line(output, "TRANSP:TRANSPARENT");
+            }
+            DeadlineItem::Todo => {
+                line(
+                    output,
+                    &format!("DUE;VALUE=DATE:{}", deadline.due.format("%Y%m%d")),
+                );
+            }
+        }
+
+        line(
+            output,
+            &format!(
+                "SUMMARY:{}",
+                escape(&format!("{}: {}", deadline.subject.name(), deadline.title))
+            ),
+        );
+        line(
+            output,
+            &format!("DESCRIPTION:{}", escape(deadline.class.reference())),
+        );
+        line(output, &format!("END:{}", component));
+    }
+
+    // Write a single VEVENT for the occurrence provided, which lasts until
+    // the end of the `last` period
+    fn event(
+        &self,
+        output: &mut String,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        occurrence: &Occurrence,
+        last: Period,
+        cross_site: bool,
+    ) {
+        let school = occurrence
+            .activity
+            .location()
+            .map_or(School::Highfield, Location::school);
+        let schedule = cycle
+            .calendar()
+            .bell_schedules_on(occurrence.date)
+            .get(school);
+        let (start, _) = schedule.period(occurrence.slot.period).to_naive();
+        let (_, end) = schedule.period(last).to_naive();
+
+        let (summary, description) = match &occurrence.activity {
+            Activity::Lesson { .. } => (
+                self.render(&self.summary, timetable, cycle, occurrence, last),
+                Some(self.render(&self.description, timetable, cycle, occurrence, last)),
+            ),
+            Activity::Transit { to, duration, .. } => (
+                occurrence.activity.to_string(),
+                Some(format!(
+                    "Travel to {} ({} minutes)",
+                    to,
+                    duration.num_minutes()
+                )),
+            ),
+            activity => (activity.to_string(), None),
+        };
+
+        let uid = match occurrence.entry {
+            Some(id) => id.get().to_string(),
+            None => "x".to_string(),
+        };
+
+        line(output, "BEGIN:VEVENT");
+        line(
+            output,
+            &format!(
+                "UID:{}-{}-{}@timetableau",
+                occurrence.date.format("%Y%m%d"),
+                occurrence.slot.index(),
+                uid
+            ),
+        );
+        line(
+            output,
+            &format!("DTSTAMP:{}", self.stamp.format("%Y%m%dT%H%M%SZ")),
+        );
+        line(
+            output,
+            &format!("DTSTART:{}", self.time(occurrence.date.and_time(start))),
+        );
+        line(
+            output,
+            &format!("DTEND:{}", self.time(occurrence.date.and_time(end))),
+        );
+        line(output, &format!("SUMMARY:{}", escape(&summary)));
+
+        if let Some(description) = description {
+            line(output, &format!("DESCRIPTION:{}", escape(&description)));
+        }
+
+        if let Some(location) = locations(timetable, occurrence) {
+            line(output, &format!("LOCATION:{}", escape(&location)));
+
+            let alarm = if cross_site {
+                self.cross_site_alarm
+            } else {
+                self.same_site_alarm
+            };
+
+            if let Some(alarm) = alarm {
+                line(output, "BEGIN:VALARM");
+                line(output, "ACTION:DISPLAY");
+                line(output, &format!("DESCRIPTION:{}", escape(&summary)));
+                line(output, &format!("TRIGGER:-PT{}M", alarm.num_minutes()));
+                line(output, "END:VALARM");
+            }
+        }
+
+        line(output, "END:VEVENT");
+    }
+
+    // Replace the placeholders of the template provided with information
+    // about the occurrence (which lasts until the end of the `last` period)
+    fn render(
+        &self,
+        template: &str,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        occurrence: &Occurrence,
+        last: Period,
+    ) -> String {
+        let (subject, class, teacher, location) = match &occurrence.activity {
+            Activity::Lesson {
+                subject,
+                class,
+                teacher,
+                location,
+            } => (subject, class, teacher, location),
+            _ => return template.to_string(),
+        };
+
+        let entry = occurrence.entry.and_then(|id| timetable.get(id));
+        let teachers: Vec<&str> = teacher
+            .iter()
+            .chain(entry.into_iter().flat_map(|entry| &entry.co_teachers))
+            .map(|teacher| teacher.code().as_str())
+            .collect();
+
+        // The location of the lesson in the base timetable (i.e., before any
+        // room overrides were applied, but after its rotation)
+        let original = entry
+            .map(|entry| entry.activity_on(cycle.calendar(), occurrence.date))
+            .and_then(|activity| activity.location().copied());
+
+        let room_change = match original {
+            Some(original) if original != *location => format!(" (moved from {})", original),
+            _ => String::new(),
+        };
+
+        let period = match occurrence.slot.period {
+            first if first == last => format!("P{}", first as u8 + 1),
+            first => format!("P{}-P{}", first as u8 + 1, last as u8 + 1),
+        };
+
+        template
+            .replace("{subject}", subject.name())
+            .replace("{class}", class.reference())
+            .replace("{teacher}", &teachers.join(", "))
+            .replace("{location_name}", &location.verbose_name())
+            .replace(
+                "{location}",
+                &locations(timetable, occurrence).unwrap_or_default(),
+            )
+            .replace("{school}", &location.school().to_string())
+            .replace("{period}", &period)
+            .replace("{room_change}", &room_change)
+    }
+
+    // Format the local time (in London) provided as the value of a
+    // `DATE-TIME` property
+    fn time(&self, local: NaiveDateTime) -> String {
+        if self.utc {
+            London.to_utc(local).format("%Y%m%dT%H%M%SZ").to_string()
+        } else {
+            local.format("%Y%m%dT%H%M%S").to_string()
+        }
+    }
+}
+
+impl Default for IcsExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The rooms of the occurrence provided (i.e., its location, followed by the
+// split rooms of its entry), separated by commas
+fn locations(timetable: &Timetable, occurrence: &Occurrence) -> Option<String> {
+    let location = occurrence.activity.location()?;
+    let split = occurrence
+        .entry
+        .and_then(|id| timetable.get(id))
+        .into_iter()
+        .flat_map(|entry| &entry.split_rooms);
+
+    Some(
+        std::iter::once(location)
+            .chain(split)
+            .map(Location::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// Escapes the `text` provided such that it can be used as the value of an
+/// iCalendar `TEXT` property.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+// Write a content line to the output, folding it such that no line exceeds
+// 75 octets (as required by RFC 5545)
+fn line(output: &mut String, content: &str) {
+    let mut length = 0;
+
+    for c in content.chars() {
+        if length + c.len_utf8() > 75 {
+            output.push_str("\r\n ");
+
+            // The leading space of the continuation counts towards its length
+            length = 1;
+        }
+
+        output.push(c);
+        length += c.len_utf8();
+    }
+
+    output.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clubs::{Club, Session};
+    use crate::{
+        timeslot, AcademicCalendar, BellSchedule, BellSchedules, Class, DayException,
+        FearnhillRoom, HighfieldRoom, PeriodTime, Subject, Teacher, Term, TimeOfDay, Week,
+        YearGroup,
+    };
+
+    fn lesson(location: Location) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Maths".to_string()).unwrap(),
+            class: Class::new("JSM".to_string()).unwrap(),
+            teacher: None,
+            location,
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 9, day).unwrap()
+    }
+
+    fn cycle() -> WeekCycle {
+        let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+
+        WeekCycle::new(date(4), calendar).unwrap()
+    }
+
+    #[test]
+    fn ics_escape() {
+        assert_eq!(escape("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+    }
+
+    #[test]
+    fn ics_fold() {
+        let mut output = String::new();
+        line(&mut output, &"a".repeat(100));
+
+        let lines: Vec<&str> = output.split("\r\n").collect();
+
+        assert_eq!(lines[0].len(), 75);
+        assert_eq!(lines[1], format!(" {}", "a".repeat(25)));
+    }
+
+    #[test]
+    fn ics_utc_clock_change() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2023, month, day).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date(10, 23), date(11, 3)).unwrap()]);
+        let cycle = WeekCycle::new(date(10, 23), calendar).unwrap();
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1FP1), lesson("H201".parse().unwrap()));
+        timetable.insert(timeslot!(W2MP1), lesson("H201".parse().unwrap()));
+
+        // The clocks go back on Sunday 2023-10-29
+        let dates = date(10, 27)..=date(10, 30);
+        let floating = IcsExporter::new().export(&timetable, &cycle, dates.clone());
+        let utc = IcsExporter::new()
+            .utc(true)
+            .export(&timetable, &cycle, dates);
+
+        assert!(floating.contains("DTSTART:20231027T085000\r\n"));
+        assert!(floating.contains("DTSTART:20231030T085000\r\n"));
+
+        assert!(utc.contains("DTSTART:20231027T075000Z\r\nDTEND:20231027T085000Z\r\n"));
+        assert!(utc.contains("DTSTART:20231030T085000Z\r\nDTEND:20231030T095000Z\r\n"));
+    }
+
+    #[test]
+    fn ics_day_schedule() {
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("H201".parse().unwrap()));
+        timetable.insert(timeslot!(W1TP1), lesson("H201".parse().unwrap()));
+
+        // A late start on the Monday (e.g., after an open evening)
+        let range = |start: (u8, u8), end: (u8, u8)| {
+            PeriodTime::new(
+                TimeOfDay::new(start.0, start.1).unwrap(),
+                TimeOfDay::new(end.0, end.1).unwrap(),
+            )
+        };
+        let late = BellSchedule::new(
+            [
+                range((10, 0), (10, 45)),
+                range((10, 45), (11, 30)),
+                range((11, 50), (12, 35)),
+                range((12, 35), (13, 20)),
+                range((14, 0), (14, 55)),
+            ],
+            range((11, 30), (11, 50)),
+            range((13, 20), (14, 0)),
+        )
+        .unwrap();
+        let mut schedules = BellSchedules::default();
+        schedules.set(School::Highfield, late);
+
+        let mut cycle = cycle();
+        cycle
+            .calendar_mut()
+            .add_exception(date(4), DayException::Schedule(schedules));
+
+        let output = IcsExporter::new().export(&timetable, &cycle, date(4)..=date(5));
+
+        assert!(output.contains("DTSTART:20230904T100000\r\nDTEND:20230904T104500\r\n"));
+        assert!(output.contains("DTSTART:20230905T085000\r\nDTEND:20230905T095000\r\n"));
+    }
+
+    #[test]
+    fn ics_double_lessons() {
+        let mut timetable = Timetable::new();
+        timetable
+            .insert_spanning(timeslot!(W1MP1), 2, lesson("H201".parse().unwrap()))
+            .unwrap();
+
+        let output = IcsExporter::new()
+            .summary("{subject} {period}")
+            .alarms(Some(Duration::minutes(5)), None)
+            .export(&timetable, &cycle(), date(4)..=date(4));
+
+        // A double is a single event (and alarm), lasting both periods
+        assert_eq!(output.matches("BEGIN:VEVENT").count(), 1);
+        assert_eq!(output.matches("BEGIN:VALARM").count(), 1);
+        assert!(output.contains("DTSTART:20230904T085000\r\nDTEND:20230904T105000\r\n"));
+        assert!(output.contains("SUMMARY:Maths P1-P2\r\n"));
+    }
+
+    #[test]
+    fn ics_alarms_and_room_change() {
+        let mut timetable = Timetable::new();
+
+        timetable.insert(
+            timeslot!(W1MP2),
+            lesson(Location::Fearnhill(FearnhillRoom::Gym)),
+        );
+        timetable.insert(
+            timeslot!(W1MP3),
+            lesson(Location::Highfield(HighfieldRoom::Hall)),
+        );
+        timetable.override_room(
+            timeslot!(W1MP3),
+            date(4)..=date(4),
+            Location::Highfield(HighfieldRoom::SportsHall),
+        );
+
+        let output = IcsExporter::new()
+            .alarms(Some(Duration::minutes(5)), Some(Duration::minutes(15)))
+            .export(&timetable, &cycle(), date(4)..=date(4));
+
+        assert_eq!(output.matches("BEGIN:VEVENT").count(), 2);
+        assert!(output.contains("TRIGGER:-PT5M"));
+        assert!(output.contains("TRIGGER:-PT15M"));
+        assert!(output.contains("DTSTART:20230904T094500"));
+        assert!(output.contains("DTSTART:20230904T111000"));
+        assert!(output.contains("(moved from Hall)"));
+        assert!(output.contains("LOCATION:Sports Hall"));
+    }
+
+    #[test]
+    fn ics_remote_lessons() {
+        let mut timetable = Timetable::new();
+
+        timetable.insert(
+            timeslot!(W1MP1),
+            lesson(Location::Fearnhill(FearnhillRoom::Gym)),
+        );
+        timetable.insert(timeslot!(W1MP2), lesson("Remote (Teams)".parse().unwrap()));
+        timetable.insert(
+            timeslot!(W1MP3),
+            lesson(Location::Highfield(HighfieldRoom::Hall)),
+        );
+
+        let output = IcsExporter::new()
+            .alarms(Some(Duration::minutes(5)), Some(Duration::minutes(15)))
+            .export(&timetable, &cycle(), date(4)..=date(4));
+
+        // The lesson following the remote lesson is still cross-site
+        assert!(output.contains("LOCATION:Remote (Teams)"));
+        assert_eq!(output.matches("TRIGGER:-PT5M").count(), 2);
+        assert_eq!(output.matches("TRIGGER:-PT15M").count(), 1);
+    }
+
+    #[test]
+    fn ics_deadlines() {
+        let mut timetable = Timetable::new();
+        timetable.insert(
+            timeslot!(W1MP1),
+            lesson(Location::Highfield(HighfieldRoom::Hall)),
+        );
+
+        let deadline = |class: &str, title: &str, day| Deadline {
+            subject: Subject::new("Maths".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            title: title.to_string(),
+            due: date(day),
+        };
+
+        let mut deadlines = Deadlines::new();
+        deadlines.add(deadline("JSM", "Statistics, Project", 5));
+        deadlines.add(deadline("10B", "Not Mine", 5));
+        deadlines.add(deadline("JSM", "Too Late", 30));
+
+        let events = IcsExporter::new().export_with_deadlines(
+            &timetable,
+            &cycle(),
+            date(4)..=date(8),
+            &deadlines,
+        );
+
+        assert_eq!(events.matches("BEGIN:VEVENT").count(), 2);
+        assert!(events.contains("UID:20230905-deadline0@timetableau"));
+        assert!(events.contains("DTSTART;VALUE=DATE:20230905"));
+        assert!(events.contains("DTEND;VALUE=DATE:20230906"));
+        assert!(events.contains("SUMMARY:Maths: Statistics\\, Project"));
+
+        let todos = IcsExporter::new()
+            .deadline_item(DeadlineItem::Todo)
+            .export_with_deadlines(&timetable, &cycle(), date(4)..=date(8), &deadlines);
+
+        assert_eq!(todos.matches("BEGIN:VTODO").count(), 1);
+        assert!(todos.contains("DUE;VALUE=DATE:20230905"));
+    }
+
+    #[test]
+    fn ics_clubs() {
+        let mut clubs = Clubs::new();
+        clubs
+            .add(Club {
+                name: "Chess, Draughts".to_string(),
+                venue: Location::Highfield(HighfieldRoom::Hall),
+                lead: Teacher::new("ABC".to_string()).unwrap(),
+                years: YearGroup::new(7).unwrap()..=YearGroup::new(9).unwrap(),
+                session: Session::Lunch,
+                day: Weekday::Tue,
+                week: Some(Week::WeekOne),
+                term: Term::new(date(4), date(29)).unwrap(),
+            })
+            .unwrap();
+
+        let output = IcsExporter::new().export_clubs(
+            &clubs,
+            &cycle(),
+            &AfterSchoolBells::default(),
+            date(4)..=date(18),
+        );
+
+        assert_eq!(output.matches("BEGIN:VEVENT").count(), 1);
+        assert!(output.contains("UID:20230905-club0@timetableau"));
+        assert!(output.contains("DTSTART:20230905T131000"));
+        assert!(output.contains("SUMMARY:Chess\\, Draughts"));
+        assert!(output.contains("DESCRIPTION:Years 7-9 -- led by ABC"));
+    }
+}
@@ -0,0 +1,7 @@
+//! Exporters which convert timetables into formats understood by other
+//! applications.
+
+pub mod ics;
+
+#[cfg(feature = "xml")]
+pub mod xml;
@@ -0,0 +1,556 @@
+//! Exports timetables as XML documents, allowing the lessons (and the room
+//! overrides) of a timetable to be bulk-imported by management information
+//! systems which only ingest XML.
+//!
+//! The names of the document's elements are configurable (*see
+//! [`XmlExporter::element`]*), such that the document can match the import
+//! schema of any system -- by default, the elements follow the common MIS
+//! import schema:
+//!
+//! ```xml
+//! <?xml version="1.0" encoding="UTF-8"?>
+//! <Timetable>
+//!   <Lessons>
+//!     <Lesson>
+//!       <Id>0</Id>
+//!       <Week>1</Week>
+//!       <Day>Monday</Day>
+//!       <Period>3</Period>
+//!       <Subject>Ma</Subject>
+//!       <Class>10A/Ma1</Class>
+//!       <Staff>JSM</Staff>
+//!       <Room>H201</Room>
+//!     </Lesson>
+//!   </Lessons>
+//!   <RoomAssignments>
+//!     <RoomAssignment>
+//!       <Week>1</Week>
+//!       <Day>Monday</Day>
+//!       <Period>3</Period>
+//!       <StartDate>2023-09-04</StartDate>
+//!       <EndDate>2023-09-08</EndDate>
+//!       <Room>Hall</Room>
+//!     </RoomAssignment>
+//!   </RoomAssignments>
+//! </Timetable>
+//! ```
+//!
+//! A lesson spanning multiple periods (e.g., a double lesson) has a
+//! `Periods` element giving the number of consecutive periods it spans
+//! (which is omitted for a lesson of a single period). A lesson without a
+//! known teacher has no staff element, whereas a team-taught lesson has a
+//! staff element for each of its teachers (and a split lesson a room element
+//! for each of its rooms). A room assignment which only moves the lessons of
+//! a single room (*see [`Timetable::override_location`]*) has a `FromRoom`
+//! element naming that room. The XML Schema which the documents conform to
+//! can be produced by [`XmlExporter::schema`].
+
+use crate::{Activity, TimeSlot, Timetable, Week};
+use chrono::Weekday;
+use std::fmt::Write;
+
+/// An element of an exported XML document (*see [`XmlExporter::element`]*).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Element {
+    /// The root element of the document (`Timetable` by default).
+    Root,
+
+    /// The element containing every lesson (`Lessons` by default).
+    Lessons,
+
+    /// The element of a single lesson (`Lesson` by default).
+    Lesson,
+
+    /// The identifier of a lesson (`Id` by default).
+    Id,
+
+    /// The week of a lesson or room assignment (`Week` by default).
+    Week,
+
+    /// The day of a lesson or room assignment (`Day` by default).
+    Day,
+
+    /// The period of a lesson or room assignment (`Period` by default).
+    Period,
+
+    /// The number of consecutive periods spanned by a lesson which spans
+    /// more than one period (`Periods` by default).
+    Periods,
+
+    /// The subject of a lesson (`Subject` by default).
+    Subject,
+
+    /// The class of a lesson (`Class` by default).
+    Class,
+
+    /// The staff code of a lesson's teacher (`Staff` by default).
+    Teacher,
+
+    /// The room of a lesson or room assignment (`Room` by default).
+    Room,
+
+    /// The element containing every room assignment (i.e., every
+    /// [`RoomOverride`](crate::RoomOverride)) -- `RoomAssignments` by
+    /// default.
+    Assignments,
+
+    /// The element of a single room assignment (`RoomAssignment` by
+    /// default).
+    Assignment,
+
+    /// The first date of a room assignment (`StartDate` by default).
+    Start,
+
+    /// The last date of a room assignment (`EndDate` by default).
+    End,
+
+    /// The room whose lessons are moved by a room assignment restricted to
+    /// a single room (`FromRoom` by default).
+    From,
+}
+
+impl Element {
+    // Every element (in the order of their default names)
+    const ALL: [Element; 17] = [
+        Element::Root,
+        Element::Lessons,
+        Element::Lesson,
+        Element::Id,
+        Element::Week,
+        Element::Day,
+        Element::Period,
+        Element::Periods,
+        Element::Subject,
+        Element::Class,
+        Element::Teacher,
+        Element::Room,
+        Element::Assignments,
+        Element::Assignment,
+        Element::Start,
+        Element::End,
+        Element::From,
+    ];
+
+    /// The name of the `Element` in the common MIS import schema.
+    pub fn default_name(self) -> &'static str {
+        match self {
+            Self::Root => "Timetable",
+            Self::Lessons => "Lessons",
+            Self::Lesson => "Lesson",
+            Self::Id => "Id",
+            Self::Week => "Week",
+            Self::Day => "Day",
+            Self::Period => "Period",
+            Self::Periods => "Periods",
+            Self::Subject => "Subject",
+            Self::Class => "Class",
+            Self::Teacher => "Staff",
+            Self::Room => "Room",
+            Self::Assignments => "RoomAssignments",
+            Self::Assignment => "RoomAssignment",
+            Self::Start => "StartDate",
+            Self::End => "EndDate",
+            Self::From => "FromRoom",
+        }
+    }
+}
+
+/// Exports the lessons and room assignments of a [`Timetable`] as an XML
+/// document.
+///
+/// ```
+/// use timetableau::export::xml::{Element, XmlExporter};
+/// use timetableau::{timeslot, Activity, Class, Subject, Timetable};
+///
+/// let mut timetable = Timetable::new();
+///
+/// timetable.insert(
+///     timeslot!(W1MP3),
+///     Activity::Lesson {
+///         subject: Subject::new("Ma".to_string()).unwrap(),
+///         class: Class::new("10A/Ma1".to_string()).unwrap(),
+///         teacher: None,
+///         location: "H201".parse().unwrap(),
+///     },
+/// );
+///
+/// let xml = XmlExporter::new()
+///     .element(Element::Class, "TeachingGroup")
+///     .export(&timetable);
+///
+/// assert!(xml.contains("<TeachingGroup>10A/Ma1</TeachingGroup>"));
+/// ```
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmlExporter {
+    names: [String; 17],
+}
+
+impl XmlExporter {
+    /// Creates a new `XmlExporter` using the names of the common MIS import
+    /// schema (*see [`Element::default_name`]*).
+    pub fn new() -> Self {
+        Self {
+            names: Element::ALL.map(|element| element.default_name().to_string()),
+        }
+    }
+
+    /// Sets the `name` of an `element` of the exported documents.
+    ///
+    /// # Panics
+    ///
+    /// This function panics should the `name` not be a valid XML element
+    /// name (e.g., should it contain a space, or start with a digit).
+    pub fn element(mut self, element: Element, name: &str) -> Self {
+        assert!(is_name(name), "`{}` is not a valid XML element name", name);
+
+        self.names[element as usize] = name.to_string();
+        self
+    }
+
+    // Retrieve the name of an element
+    fn name(&self, element: Element) -> &str {
+        &self.names[element as usize]
+    }
+
+    /// Exports every lesson and room override of the `timetable` provided.
+    ///
+    /// Lessons are ordered by their [`TimeSlot`](crate::TimeSlot)s (and then
+    /// by the order in which they were inserted), whereas room assignments
+    /// are ordered by the order in which they were recorded. Activities
+    /// which are not lessons (e.g., registration) are not exported.
+    ///
+    /// # Remarks
+    ///
+    /// Lessons which [rotate](crate::Rotation) are exported as scheduled --
+    /// the lessons of a given term can be exported from the timetable
+    /// [effective](Timetable::effective_on) during that term.
+    pub fn export(&self, timetable: &Timetable) -> String {
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+        let mut lessons: Vec<_> = timetable
+            .entries()
+            .filter(|(_, entry)| matches!(entry.activity, Activity::Lesson { .. }))
+            .collect();
+        lessons.sort_by_key(|(id, entry)| (entry.slot.index(), *id));
+
+        self.open(&mut output, 0, Element::Root);
+        self.open(&mut output, 1, Element::Lessons);
+
+        for (id, entry) in lessons {
+            let Activity::Lesson { subject, class, .. } = &entry.activity else {
+                continue;
+            };
+
+            self.open(&mut output, 2, Element::Lesson);
+            self.leaf(&mut output, Element::Id, &id.get().to_string());
+            self.slot(&mut output, entry.slot);
+
+            if entry.periods.get() > 1 {
+                self.leaf(&mut output, Element::Periods, &entry.periods.to_string());
+            }
+
+            self.leaf(&mut output, Element::Subject, subject.name());
+            self.leaf(&mut output, Element::Class, class.reference());
+
+            for teacher in entry.teachers() {
+                self.leaf(&mut output, Element::Teacher, teacher.code());
+            }
+
+            for location in entry.locations() {
+                self.leaf(&mut output, Element::Room, &location.to_string());
+            }
+            self.close(&mut output, 2, Element::Lesson);
+        }
+
+        self.close(&mut output, 1, Element::Lessons);
+        self.open(&mut output, 1, Element::Assignments);
+
+        for room_override in timetable.overrides() {
+            self.open(&mut output, 2, Element::Assignment);
+            self.slot(&mut output, room_override.slot);
+            self.leaf(
+                &mut output,
+                Element::Start,
+                &room_override.start.format("%Y-%m-%d").to_string(),
+            );
+            self.leaf(
+                &mut output,
+                Element::End,
+                &room_override.end.format("%Y-%m-%d").to_string(),
+            );
+            self.leaf(
+                &mut output,
+                Element::Room,
+                &room_override.location.to_string(),
+            );
+
+            if let Some(from) = room_override.from {
+                self.leaf(&mut output, Element::From, &from.to_string());
+            }
+
+            self.close(&mut output, 2, Element::Assignment);
+        }
+
+        self.close(&mut output, 1, Element::Assignments);
+        self.close(&mut output, 0, Element::Root);
+        output
+    }
+
+    /// Produces the XML Schema (XSD) to which the documents exported by the
+    /// `XmlExporter` conform (e.g., for validating the documents before they
+    /// are imported).
+    pub fn schema(&self) -> String {
+        let mut output = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<xs:schema xmlns:xs=\"http://www.w3.org/2001/XMLSchema\">\n",
+        ));
+
+        // The type, minimum, and maximum occurrences of each field
+        let slot = [
+            (Element::Week, "xs:positiveInteger", 1, "1"),
+            (Element::Day, "xs:string", 1, "1"),
+            (Element::Period, "xs:positiveInteger", 1, "1"),
+        ];
+        let lesson = [
+            &[(Element::Id, "xs:nonNegativeInteger", 1, "1")][..],
+            &slot,
+            &[
+                (Element::Periods, "xs:positiveInteger", 0, "1"),
+                (Element::Subject, "xs:string", 1, "1"),
+                (Element::Class, "xs:string", 1, "1"),
+                (Element::Teacher, "xs:string", 0, "unbounded"),
+                (Element::Room, "xs:string", 1, "unbounded"),
+            ],
+        ]
+        .concat();
+        let assignment = [
+            &slot[..],
+            &[
+                (Element::Start, "xs:date", 1, "1"),
+                (Element::End, "xs:date", 1, "1"),
+                (Element::Room, "xs:string", 1, "1"),
+                (Element::From, "xs:string", 0, "1"),
+            ],
+        ]
+        .concat();
+
+        let _ = writeln!(
+            output,
+            "  <xs:element name=\"{}\">",
+            self.name(Element::Root)
+        );
+        output.push_str("    <xs:complexType>\n      <xs:sequence>\n");
+
+        for (list, item, fields) in [
+            (Element::Lessons, Element::Lesson, &lesson),
+            (Element::Assignments, Element::Assignment, &assignment),
+        ] {
+            let _ = writeln!(output, "        <xs:element name=\"{}\">", self.name(list));
+            output.push_str("          <xs:complexType>\n            <xs:sequence>\n");
+            let _ = writeln!(
+                output,
+                "              <xs:element name=\"{}\" minOccurs=\"0\" maxOccurs=\"unbounded\">",
+                self.name(item)
+            );
+            output.push_str("                <xs:complexType>\n                  <xs:sequence>\n");
+
+            for (field, kind, minimum, maximum) in fields {
+                let _ = write!(
+                    output,
+                    "                    <xs:element name=\"{}\" type=\"{}\" minOccurs=\"{}\"",
+                    self.name(*field),
+                    kind,
+                    minimum
+                );
+
+                if *maximum != "1" {
+                    let _ = write!(output, " maxOccurs=\"{}\"", maximum);
+                }
+
+                output.push_str("/>\n");
+            }
+
+            output
+                .push_str("                  </xs:sequence>\n                </xs:complexType>\n");
+            output.push_str("              </xs:element>\n");
+            output.push_str("            </xs:sequence>\n          </xs:complexType>\n");
+            output.push_str("        </xs:element>\n");
+        }
+
+        output.push_str("      </xs:sequence>\n    </xs:complexType>\n  </xs:element>\n");
+        output.push_str("</xs:schema>\n");
+        output
+    }
+
+    // Write the opening tag of an element containing other elements
+    fn open(&self, output: &mut String, depth: usize, element: Element) {
+        let _ = writeln!(output, "{}<{}>", "  ".repeat(depth), self.name(element));
+    }
+
+    // Write the closing tag of an element containing other elements
+    fn close(&self, output: &mut String, depth: usize, element: Element) {
+        let _ = writeln!(output, "{}</{}>", "  ".repeat(depth), self.name(element));
+    }
+
+    // Write an element containing text (within a lesson or room assignment)
+    fn leaf(&self, output: &mut String, element: Element, text: &str) {
+        let name = self.name(element);
+        let _ = writeln!(output, "      <{}>{}</{}>", name, escape(text), name);
+    }
+
+    // Write the elements of a timeslot
+    fn slot(&self, output: &mut String, slot: TimeSlot) {
+        let week = match slot.week {
+            Week::WeekOne => "1",
+            Week::WeekTwo => "2",
+        };
+        let day = match slot.day {
+            Weekday::Mon => "Monday",
+            Weekday::Tue => "Tuesday",
+            Weekday::Wed => "Wednesday",
+            Weekday::Thu => "Thursday",
+            Weekday::Fri => "Friday",
+            Weekday::Sat => "Saturday",
+            Weekday::Sun => "Sunday",
+        };
+
+        self.leaf(output, Element::Week, week);
+        self.leaf(output, Element::Day, day);
+        self.leaf(
+            output,
+            Element::Period,
+            &(slot.period as usize + 1).to_string(),
+        );
+    }
+}
+
+impl Default for XmlExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Escapes the `text` provided such that it can be used as the content (or
+/// an attribute value) of an XML element.
+///
+/// Control characters which cannot be represented in XML 1.0 are removed.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+// Whether the `name` provided is a valid XML element name (names containing
+// a colon are rejected, as the documents do not declare any namespaces)
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && !name.to_ascii_lowercase().starts_with("xml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, Subject, Teacher};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn xml_export() {
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let mut timetable = Timetable::new();
+
+        timetable.insert(
+            timeslot!(W2FP2),
+            Activity::Lesson {
+                subject: Subject::new("D&T <RM>".to_string()).unwrap(),
+                class: Class::new("10A".to_string()).unwrap(),
+                teacher: Some(Teacher::new("JSM".to_string()).unwrap()),
+                location: "FH S13".parse().unwrap(),
+            },
+        );
+        timetable.insert(timeslot!(W1MP1), Activity::Registration);
+        timetable.override_room(timeslot!(W2FP2), date..=date, "Hall".parse().unwrap());
+
+        assert_eq!(
+            XmlExporter::new()
+                .element(Element::Teacher, "Teacher")
+                .export(&timetable),
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<Timetable>\n",
+                "  <Lessons>\n",
+                "    <Lesson>\n",
+                "      <Id>0</Id>\n",
+                "      <Week>2</Week>\n",
+                "      <Day>Friday</Day>\n",
+                "      <Period>2</Period>\n",
+                "      <Subject>D&amp;T &lt;RM&gt;</Subject>\n",
+                "      <Class>10A</Class>\n",
+                "      <Teacher>JSM</Teacher>\n",
+                "      <Room>FH S13</Room>\n",
+                "    </Lesson>\n",
+                "  </Lessons>\n",
+                "  <RoomAssignments>\n",
+                "    <RoomAssignment>\n",
+                "      <Week>2</Week>\n",
+                "      <Day>Friday</Day>\n",
+                "      <Period>2</Period>\n",
+                "      <StartDate>2023-09-04</StartDate>\n",
+                "      <EndDate>2023-09-04</EndDate>\n",
+                "      <Room>Hall</Room>\n",
+                "    </RoomAssignment>\n",
+                "  </RoomAssignments>\n",
+                "</Timetable>\n",
+            )
+        );
+
+        let schema = XmlExporter::new().element(Element::Root, "Bulk").schema();
+
+        assert!(schema.contains("<xs:element name=\"Bulk\">"));
+        assert!(schema.contains(
+            "<xs:element name=\"Staff\" type=\"xs:string\" minOccurs=\"0\" maxOccurs=\"unbounded\"/>"
+        ));
+        // A double lesson records the number of periods it spans
+        let mut timetable = Timetable::new();
+        timetable
+            .insert_spanning(
+                timeslot!(W1TP3),
+                2,
+                Activity::Lesson {
+                    subject: Subject::new("Sc".to_string()).unwrap(),
+                    class: Class::new("10A".to_string()).unwrap(),
+                    teacher: None,
+                    location: "H201".parse().unwrap(),
+                },
+            )
+            .unwrap();
+
+        assert!(XmlExporter::new().export(&timetable).contains(concat!(
+            "      <Period>3</Period>\n",
+            "      <Periods>2</Periods>\n",
+            "      <Subject>Sc</Subject>\n",
+        )));
+        assert!(schema.contains(
+            "<xs:element name=\"Periods\" type=\"xs:positiveInteger\" minOccurs=\"0\"/>"
+        ));
+
+        assert!(!is_name("Room Number") && !is_name("1Room") && is_name("Room_1"));
+    }
+}
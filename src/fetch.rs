@@ -0,0 +1,886 @@
+//! Fetches the timetable feeds published on the schools' websites.
+//!
+//! A [`Feed`] downloads and parses a published feed -- either the JSON
+//! payload of the parent/pupil apps (*see [`JsonAppImporter`]*) or an
+//! iCalendar feed -- normalising its rooms into [`Location`]s. Refreshing a
+//! feed is conditional: the `ETag` and `Last-Modified` validators of the
+//! previous response are sent with the next request, such that a feed which
+//! has not changed is neither downloaded nor parsed again.
+//!
+//! The crate does not contain an HTTP client -- requests are sent using a
+//! [`Transport`], which is implemented for the HTTP client the application
+//! already uses (e.g., `reqwest`):
+//!
+//! ```
+//! use std::future::Future;
+//! use timetableau::fetch::{Feed, FeedContent, FetchError, Request, Response, Transport};
+//!
+//! struct Website;
+//!
+//! impl Transport for Website {
+//!     fn get(&self, request: &Request) -> impl Future<Output = Result<Response, FetchError>> {
+//!         // A real transport would send the request (and its headers)
+//!         let body = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nDTSTART:20230904T085000\r\n\
+//!                     SUMMARY:Maths\r\nLOCATION:H201\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+//!
+//!         std::future::ready(Ok(Response {
+//!             status: 200,
+//!             headers: vec![("ETag".to_string(), "\"v1\"".to_string())],
+//!             body: body.to_string(),
+//!         }))
+//!     }
+//! }
+//!
+//! # async fn example() -> Result<(), FetchError> {
+//! let mut feed = Feed::ics("https://school.example/timetable.ics".to_string());
+//!
+//! if feed.refresh(&Website).await? {
+//!     if let Some(FeedContent::Events { events, .. }) = feed.content() {
+//!         assert_eq!(events[0].location, Some("H201".parse().unwrap()));
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Applications which refresh many feeds (or serve many users) should refresh
+//! them through a [`Client`], which shares a single transport between every
+//! feed, and applies a [`FetchPolicy`]: the number of requests sent is rate
+//! limited, the number of concurrent connections is bounded, and a feed
+//! which fails is only retried after a jittered, exponential backoff -- such
+//! that the school's website is not overwhelmed when every device refreshes
+//! at once (e.g., before registration).
+
+use crate::import::json_app::{JsonAppError, JsonAppImporter};
+use crate::import::{Import, ImportIssue};
+use crate::random::Random;
+use crate::{Location, London, ParseOptions};
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::future::Future;
+use std::sync::Mutex;
+use std::task::{Poll, Waker};
+
+/// An HTTP `GET` request sent by a [`Feed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// The URL of the feed.
+    pub url: String,
+
+    /// The headers of the request (e.g., `If-None-Match`).
+    pub headers: Vec<(String, String)>,
+}
+
+/// The HTTP response to a [`Request`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    /// The status code of the response (e.g., `200`, or `304` should the
+    /// feed not have been modified).
+    pub status: u16,
+
+    /// The headers of the response (whose names are not case-sensitive).
+    pub headers: Vec<(String, String)>,
+
+    /// The body of the response.
+    pub body: String,
+}
+
+impl Response {
+    /// Retrieves the value of the header with the `name` provided (compared
+    /// case-insensitively), should the response have such a header.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// The means by which a [`Feed`] sends its requests (i.e., an HTTP client).
+///
+/// *See the [module](self) documentation for more information*.
+pub trait Transport {
+    /// Sends the `request` provided, returning the response of the server.
+    ///
+    /// # Errors
+    ///
+    /// A [`FetchError::Transport`] should the request not have been sent or
+    /// no response have been received (e.g., the server is unreachable).
+    fn get(&self, request: &Request) -> impl Future<Output = Result<Response, FetchError>>;
+}
+
+/// An error which prevents a [`Feed`] from being refreshed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// The request could not be sent (or no response was received).
+    Transport(String),
+
+    /// The server responded with a status code other than `200` or `304`.
+    Status(u16),
+
+    /// The feed was not a valid JSON payload.
+    JsonApp(JsonAppError),
+
+    /// The request was not sent by a [`Client`]: its rate limit (or the
+    /// backoff of the feed) permits no further requests until the time
+    /// provided.
+    RateLimited(DateTime<Utc>),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(reason) => write!(f, "the feed could not be fetched: {}", reason),
+            Self::Status(status) => write!(f, "the server responded with status {}", status),
+            Self::JsonApp(error) => Display::fmt(error, f),
+            Self::RateLimited(until) => {
+                write!(f, "the feed may not be fetched again until {}", until)
+            }
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+impl From<JsonAppError> for FetchError {
+    fn from(error: JsonAppError) -> Self {
+        Self::JsonApp(error)
+    }
+}
+
+/// The format of a [`Feed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedFormat {
+    /// The JSON payload of the parent/pupil apps, imported using the
+    /// importer provided.
+    JsonApp(JsonAppImporter),
+
+    /// An iCalendar feed, whose rooms are parsed using the options provided.
+    Ics(ParseOptions),
+}
+
+/// An event of an iCalendar [`Feed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedEvent {
+    /// The date and time at which the event starts.
+    pub start: NaiveDateTime,
+
+    /// The summary of the event (e.g., `Maths (H201)`).
+    pub summary: String,
+
+    /// The room in which the event takes place, or [`None`] should the
+    /// event have no room (or should its room not have been mapped -- *see
+    /// [`FeedContent::Events`]*).
+    pub location: Option<Location>,
+}
+
+/// The parsed contents of a [`Feed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedContent {
+    /// The timetable imported from a JSON app payload.
+    Timetable(Import),
+
+    /// The events of an iCalendar feed (in the order of the feed).
+    Events {
+        /// The events of the feed.
+        events: Vec<FeedEvent>,
+
+        /// The rooms which could not be mapped to a [`Location`], and the
+        /// events which could not be understood (e.g., without a start).
+        issues: Vec<ImportIssue>,
+    },
+}
+
+/// A timetable feed published on a school's website.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feed {
+    url: String,
+    format: FeedFormat,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content: Option<FeedContent>,
+}
+
+impl Feed {
+    /// Creates a new `Feed` of the `format` provided, published at the `url`
+    /// provided, which has not yet been fetched.
+    pub fn new(url: String, format: FeedFormat) -> Self {
+        Self {
+            url,
+            format,
+            etag: None,
+            last_modified: None,
+            content: None,
+        }
+    }
+
+    /// Creates a new iCalendar `Feed`, whose rooms are parsed using the
+    /// default [`ParseOptions`].
+    pub fn ics(url: String) -> Self {
+        Self::new(url, FeedFormat::Ics(ParseOptions::default()))
+    }
+
+    /// Creates a new JSON app `Feed`, imported using the default
+    /// [`JsonAppImporter`].
+    pub fn json_app(url: String) -> Self {
+        Self::new(url, FeedFormat::JsonApp(JsonAppImporter::new()))
+    }
+
+    /// Retrieves the URL at which the feed is published.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Retrieves the parsed contents of the feed, or [`None`] should the
+    /// feed not yet have been fetched.
+    pub fn content(&self) -> Option<&FeedContent> {
+        self.content.as_ref()
+    }
+
+    /// Retrieves the `ETag` of the latest response (if any).
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Retrieves the `Last-Modified` date of the latest response (if any).
+    pub fn last_modified(&self) -> Option<&str> {
+        self.last_modified.as_deref()
+    }
+
+    /// Fetches the feed using the `transport` provided, should it have been
+    /// modified since it was last fetched.
+    ///
+    /// # Returns
+    ///
+    /// `true` should the feed have been downloaded and parsed again, or
+    /// `false` should the server have reported that it has not been modified
+    /// (i.e., responded with `304 Not Modified`).
+    ///
+    /// # Errors
+    ///
+    /// A [`FetchError`] should the feed not have been fetched, or should it
+    /// be impossible to parse -- the previous contents of the feed are kept.
+    pub async fn refresh<T: Transport + ?Sized>(
+        &mut self,
+        transport: &T,
+    ) -> Result<bool, FetchError> {
+        let mut request = Request {
+            url: self.url.clone(),
+            headers: Vec::new(),
+        };
+
+        // The validators are only sent once the feed has been parsed, such
+        // that a failed parse is retried in full
+        if self.content.is_some() {
+            if let Some(etag) = &self.etag {
+                request
+                    .headers
+                    .push(("If-None-Match".to_string(), etag.clone()));
+            }
+            if let Some(last_modified) = &self.last_modified {
+                request
+                    .headers
+                    .push(("If-Modified-Since".to_string(), last_modified.clone()));
+            }
+        }
+
+        let response = transport.get(&request).await?;
+
+        match response.status {
+            304 if self.content.is_some() => return Ok(false),
+            200 => {}
+            status => return Err(FetchError::Status(status)),
+        }
+
+        let content = match &self.format {
+            FeedFormat::JsonApp(importer) => {
+                FeedContent::Timetable(importer.import(&response.body)?)
+            }
+            FeedFormat::Ics(options) => parse_ics(&response.body, options),
+        };
+
+        self.etag = response.header("ETag").map(str::to_string);
+        self.last_modified = response.header("Last-Modified").map(str::to_string);
+        self.content = Some(content);
+
+        Ok(true)
+    }
+}
+
+/// The policy with which a [`Client`] sends requests.
+///
+/// # Remarks
+///
+/// By default, at most 60 requests are sent each minute over at most 4
+/// connections, and a feed which fails is retried after a backoff of 30
+/// seconds (doubled after each consecutive failure, up to an hour), which is
+/// shortened by up to half at random (*see [`Self::jitter`]*).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FetchPolicy {
+    max_requests: usize,
+    per: Duration,
+    max_connections: usize,
+    backoff: Duration,
+    max_backoff: Duration,
+    jitter: f64,
+}
+
+impl FetchPolicy {
+    /// Creates a new `FetchPolicy` with the default limits.
+    pub fn new() -> Self {
+        Self {
+            max_requests: 60,
+            per: Duration::minutes(1),
+            max_connections: 4,
+            backoff: Duration::seconds(30),
+            max_backoff: Duration::hours(1),
+            jitter: 0.5,
+        }
+    }
+
+    /// Sets the number of requests which may be sent during any period of
+    /// the length provided (i.e., `per`).
+    ///
+    /// # Panics
+    ///
+    /// Panics should `max_requests` be `0`.
+    pub fn rate_limit(mut self, max_requests: usize, per: Duration) -> Self {
+        assert!(
+            max_requests > 0,
+            "at least one request must be permitted during each period"
+        );
+
+        self.max_requests = max_requests;
+        self.per = per;
+        self
+    }
+
+    /// Sets the number of requests which may be in progress at once (i.e.,
+    /// the size of the shared connection pool).
+    ///
+    /// # Panics
+    ///
+    /// Panics should `max_connections` be `0`.
+    pub fn max_connections(mut self, max_connections: usize) -> Self {
+        assert!(max_connections > 0, "at least one connection is required");
+
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// Sets the backoff after the first failure of a feed, which is doubled
+    /// after each consecutive failure up to the maximum provided (i.e.,
+    /// `max_backoff`).
+    pub fn backoff(mut self, backoff: Duration, max_backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the fraction (in the range `0.0..=1.0`) by which each backoff may
+    /// be shortened at random, such that devices whose requests failed at
+    /// the same time do not retry at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics should `jitter` not be in the range `0.0..=1.0`.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&jitter),
+            "the jitter must be between 0 and 1"
+        );
+
+        self.jitter = jitter;
+        self
+    }
+
+    // Calculate the backoff after the consecutive failures provided
+    fn delay(&self, failures: u32, random: &mut Random) -> Duration {
+        let doubled = self
+            .backoff
+            .checked_mul(2i32.saturating_pow(failures.saturating_sub(1)))
+            .unwrap_or(self.max_backoff);
+        let delay = doubled.min(self.max_backoff);
+
+        let jitter = (delay.num_milliseconds() as f64 * self.jitter * random.unit()) as i64;
+        delay - Duration::milliseconds(jitter)
+    }
+}
+
+impl Default for FetchPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The state shared by the refreshes of a client
+#[derive(Debug)]
+struct ClientState {
+    sent: VecDeque<DateTime<Utc>>,
+    failures: HashMap<String, (u32, DateTime<Utc>)>,
+    connections: usize,
+    waiting: Vec<Waker>,
+    random: Random,
+}
+
+/// A [`Transport`] shared between feeds, which sends their requests
+/// according to a [`FetchPolicy`].
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug)]
+pub struct Client<T> {
+    transport: T,
+    policy: FetchPolicy,
+    state: Mutex<ClientState>,
+}
+
+impl<T: Transport> Client<T> {
+    /// Creates a new `Client` which sends requests using the `transport`
+    /// provided, according to the `policy` provided.
+    pub fn new(transport: T, policy: FetchPolicy) -> Self {
+        Self {
+            transport,
+            policy,
+            state: Mutex::new(ClientState {
+                sent: VecDeque::new(),
+                failures: HashMap::new(),
+                connections: 0,
+                waiting: Vec::new(),
+                random: Random::from_clock(),
+            }),
+        }
+    }
+
+    /// Retrieves the transport of the client.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Retrieves the policy of the client.
+    pub fn policy(&self) -> &FetchPolicy {
+        &self.policy
+    }
+
+    /// Retrieves the time before which the feed published at the `url`
+    /// provided will not be retried, should its latest refresh have failed.
+    pub fn retry_at(&self, url: &str) -> Option<DateTime<Utc>> {
+        let state = self.state.lock().unwrap();
+        state.failures.get(url).map(|(_, retry_at)| *retry_at)
+    }
+
+    /// Refreshes the `feed` provided (*see [`Feed::refresh`]*) at the time
+    /// provided (i.e., `now`), once a connection is available.
+    ///
+    /// # Remarks
+    ///
+    /// A feed whose request could not be sent, or to which the server
+    /// responded with `429 Too Many Requests` or a server error (e.g., `503
+    /// Service Unavailable`), is not retried until its backoff has elapsed
+    /// -- a feed which could not be parsed will not parse when retried, and
+    /// so is not backed off.
+    ///
+    /// # Errors
+    ///
+    /// A [`FetchError::RateLimited`] should the rate limit of the client (or
+    /// the backoff of the feed) not permit a request at the time provided,
+    /// or any other [`FetchError`] should the feed not have been refreshed.
+    pub async fn refresh(&self, feed: &mut Feed, now: DateTime<Utc>) -> Result<bool, FetchError> {
+        {
+            let mut state = self.state.lock().unwrap();
+
+            if let Some((_, retry_at)) = state.failures.get(feed.url()) {
+                if *retry_at > now {
+                    return Err(FetchError::RateLimited(*retry_at));
+                }
+            }
+
+            while state
+                .sent
+                .front()
+                .is_some_and(|sent| *sent + self.policy.per <= now)
+            {
+                state.sent.pop_front();
+            }
+
+            if state.sent.len() >= self.policy.max_requests {
+                return Err(FetchError::RateLimited(state.sent[0] + self.policy.per));
+            }
+
+            state.sent.push_back(now);
+        }
+
+        // The connection is released (and the next refresh woken) once the
+        // response has been received, even should the refresh be cancelled
+        let connection = self.connect().await;
+        let result = feed.refresh(&self.transport).await;
+        drop(connection);
+
+        let retry = matches!(
+            result,
+            Err(FetchError::Transport(_) | FetchError::Status(429 | 500..))
+        );
+
+        let mut state = self.state.lock().unwrap();
+
+        if retry {
+            let failures = state
+                .failures
+                .get(feed.url())
+                .map_or(0, |(count, _)| *count)
+                + 1;
+            let delay = self.policy.delay(failures, &mut state.random);
+
+            state
+                .failures
+                .insert(feed.url().to_string(), (failures, now + delay));
+        } else {
+            state.failures.remove(feed.url());
+        }
+
+        result
+    }
+
+    // Wait for a connection of the pool to be available
+    async fn connect(&self) -> Connection<'_> {
+        std::future::poll_fn(|cx| {
+            let mut state = self.state.lock().unwrap();
+
+            if state.connections < self.policy.max_connections {
+                state.connections += 1;
+                Poll::Ready(())
+            } else {
+                state.waiting.push(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+        .await;
+
+        Connection(&self.state)
+    }
+}
+
+// A connection of the pool of a client, released when dropped
+struct Connection<'a>(&'a Mutex<ClientState>);
+
+impl Drop for Connection<'_> {
+    fn drop(&mut self) {
+        let mut state = self.0.lock().unwrap();
+        state.connections -= 1;
+
+        // Each waiting refresh competes for the connection when woken
+        for waker in state.waiting.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+// Parse the events of an iCalendar feed
+fn parse_ics(feed: &str, options: &ParseOptions) -> FeedContent {
+    let mut events = Vec::new();
+    let mut issues = Vec::new();
+
+    // The current event: its first line, start, summary, and room
+    let mut event: Option<(usize, Option<NaiveDateTime>, String, Option<String>)> = None;
+
+    for (line, content) in unfold(feed) {
+        let Some((name, value)) = content.split_once(':') else {
+            continue;
+        };
+
+        // Parameters (e.g., `DTSTART;TZID=Europe/London`) are disregarded
+        let name = name.split(';').next().unwrap_or(name);
+
+        match (name, &mut event) {
+            ("BEGIN", None) if value == "VEVENT" => event = Some((line, None, String::new(), None)),
+            ("END", Some(_)) if value == "VEVENT" => {
+                let (line, start, summary, room) = event.take().unwrap();
+
+                let Some(start) = start else {
+                    issues.push(ImportIssue::InvalidRow {
+                        line,
+                        reason: "the event has no valid start".to_string(),
+                    });
+                    continue;
+                };
+
+                let location = match room {
+                    Some(code) => match options.parse_location(&code) {
+                        Ok(location) => Some(location),
+                        Err(_) => {
+                            issues.push(ImportIssue::UnmappedRoom { line, code });
+                            None
+                        }
+                    },
+                    None => None,
+                };
+
+                events.push(FeedEvent {
+                    start,
+                    summary,
+                    location,
+                });
+            }
+            ("DTSTART", Some((_, start, _, _))) => {
+                // Times in UTC are converted into the time in London (which
+                // differs by an hour during British Summer Time)
+                *start = match value.strip_suffix('Z') {
+                    Some(value) => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+                        .ok()
+                        .map(|utc| London.from_utc_datetime(&utc).naive_local()),
+                    None => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok(),
+                };
+            }
+            ("SUMMARY", Some((_, _, summary, _))) => *summary = unescape(value),
+            ("LOCATION", Some((_, _, _, room))) => {
+                *room = Some(unescape(value)).filter(|room| !room.is_empty())
+            }
+            _ => {}
+        }
+    }
+
+    FeedContent::Events { events, issues }
+}
+
+// Unfold the content lines of an iCalendar feed (i.e., join the lines which
+// start with whitespace onto the previous line), numbering each content
+// line by the line on which it starts
+fn unfold(feed: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+
+    for (index, line) in feed.lines().enumerate() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some((_, previous))) => previous.push_str(continuation),
+            _ => lines.push((index + 1, line.to_string())),
+        }
+    }
+
+    lines
+}
+
+// Reverse the escaping of an iCalendar `TEXT` value (*see
+// `export::ics::escape`*)
+fn unescape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n' | 'N')) => {
+                output.push('\n');
+                chars.next();
+            }
+            ('\\', Some(escaped @ ('\\' | ';' | ','))) => {
+                output.push(escaped);
+                chars.next();
+            }
+            (c, _) => output.push(c),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    // A transport which records each request and responds with the next of
+    // its responses
+    struct Recorded {
+        requests: RefCell<Vec<Request>>,
+        responses: RefCell<Vec<Response>>,
+    }
+
+    impl Transport for Recorded {
+        fn get(&self, request: &Request) -> impl Future<Output = Result<Response, FetchError>> {
+            self.requests.borrow_mut().push(request.clone());
+            std::future::ready(Ok(self.responses.borrow_mut().remove(0)))
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("the future was not ready"),
+        }
+    }
+
+    fn response(status: u16, headers: &[(&str, &str)], body: &str) -> Response {
+        Response {
+            status,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn feed_conditional_refresh() {
+        let ics = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nDTSTART:20230904T085000\r\n\
+                   SUMMARY:Maths (H2\r\n 01\\, Howard)\r\nLOCATION:H201\r\nEND:VEVENT\r\n\
+                   BEGIN:VEVENT\r\nDTSTART:20230904T095000Z\r\nLOCATION:Lab 3\r\n\
+                   END:VEVENT\r\nBEGIN:VEVENT\r\nSUMMARY:Undated\r\nEND:VEVENT\r\n\
+                   END:VCALENDAR\r\n";
+        let transport = Recorded {
+            requests: RefCell::new(Vec::new()),
+            responses: RefCell::new(vec![
+                response(500, &[], ""),
+                response(200, &[("etag", "\"v1\""), ("Last-Modified", "Mon")], ics),
+                response(304, &[], ""),
+            ]),
+        };
+
+        let mut feed = Feed::ics("https://school.example/feed.ics".to_string());
+
+        assert_eq!(
+            block_on(feed.refresh(&transport)),
+            Err(FetchError::Status(500))
+        );
+        assert_eq!(block_on(feed.refresh(&transport)), Ok(true));
+        assert_eq!(feed.etag(), Some("\"v1\""));
+
+        let Some(FeedContent::Events { events, issues }) = feed.content() else {
+            panic!("the feed was not parsed");
+        };
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].summary, "Maths (H201, Howard)");
+        assert_eq!(events[0].location, Some("H201".parse().unwrap()));
+        assert_eq!(events[1].location, None);
+
+        // Times in UTC are converted into the time in London (i.e., BST)
+        assert_eq!(events[1].start.format("%H:%M").to_string(), "10:50");
+        assert_eq!(
+            issues,
+            &[
+                ImportIssue::UnmappedRoom {
+                    line: 8,
+                    code: "Lab 3".to_string()
+                },
+                ImportIssue::InvalidRow {
+                    line: 12,
+                    reason: "the event has no valid start".to_string()
+                }
+            ]
+        );
+
+        assert_eq!(block_on(feed.refresh(&transport)), Ok(false));
+
+        let requests = transport.requests.borrow();
+
+        assert!(requests[1].headers.is_empty());
+        assert_eq!(
+            requests[2].headers,
+            [
+                ("If-None-Match".to_string(), "\"v1\"".to_string()),
+                ("If-Modified-Since".to_string(), "Mon".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn client_rate_limit_and_backoff() {
+        let now = Utc::now();
+        let transport = Recorded {
+            requests: RefCell::new(Vec::new()),
+            responses: RefCell::new(vec![
+                response(503, &[], ""),
+                response(503, &[], ""),
+                response(200, &[], ""),
+                response(200, &[], ""),
+            ]),
+        };
+        let policy = FetchPolicy::new()
+            .rate_limit(2, Duration::minutes(1))
+            .backoff(Duration::seconds(30), Duration::minutes(5))
+            .jitter(0.0);
+
+        let client = Client::new(transport, policy);
+        let mut feed = Feed::ics("https://school.example/feed.ics".to_string());
+        let url = feed.url().to_string();
+
+        assert_eq!(
+            block_on(client.refresh(&mut feed, now)),
+            Err(FetchError::Status(503))
+        );
+        assert_eq!(client.retry_at(&url), Some(now + Duration::seconds(30)));
+
+        // The feed is not retried until its backoff has elapsed
+        assert_eq!(
+            block_on(client.refresh(&mut feed, now + Duration::seconds(10))),
+            Err(FetchError::RateLimited(now + Duration::seconds(30)))
+        );
+
+        // The backoff is doubled after each consecutive failure
+        let retried = now + Duration::seconds(30);
+
+        assert_eq!(
+            block_on(client.refresh(&mut feed, retried)),
+            Err(FetchError::Status(503))
+        );
+        assert_eq!(client.retry_at(&url), Some(retried + Duration::minutes(1)));
+
+        let recovered = retried + Duration::minutes(1);
+        assert_eq!(block_on(client.refresh(&mut feed, recovered)), Ok(true));
+        assert_eq!(client.retry_at(&url), None);
+
+        // Only 2 requests may be sent each minute
+        let mut other = Feed::ics("https://school.example/other.ics".to_string());
+
+        assert_eq!(block_on(client.refresh(&mut other, recovered)), Ok(true));
+        assert_eq!(
+            block_on(client.refresh(&mut other, recovered + Duration::seconds(1))),
+            Err(FetchError::RateLimited(recovered + Duration::minutes(1)))
+        );
+        assert_eq!(client.transport().requests.borrow().len(), 4);
+
+        // Each backoff is shortened by at most the jitter
+        let policy = FetchPolicy::new().jitter(0.5);
+        let mut random = Random::new(7);
+
+        for failures in 1..=10 {
+            let delay = policy.delay(failures, &mut random);
+            let maximum = Duration::seconds(30 << (failures - 1).min(7)).min(Duration::hours(1));
+
+            assert!(delay <= maximum && delay >= maximum / 2, "{}", failures);
+        }
+    }
+
+    #[test]
+    fn feed_json_app() {
+        let payload = r#"{ "weeks": [{ "name": "Week 1", "days": [{ "name": "Monday",
+            "lessons": [{ "period": 1, "subject": "Maths", "teacher": "JSM",
+            "room": "FH S12" }] }] }] }"#;
+        let transport = Recorded {
+            requests: RefCell::new(Vec::new()),
+            responses: RefCell::new(vec![response(200, &[], payload), response(200, &[], "{")]),
+        };
+
+        let mut feed = Feed::json_app("https://school.example/feed.json".to_string());
+        assert_eq!(block_on(feed.refresh(&transport)), Ok(true));
+
+        let Some(FeedContent::Timetable(import)) = feed.content() else {
+            panic!("the feed was not imported");
+        };
+        assert_eq!(import.timetable.entries().count(), 1);
+
+        // A feed which cannot be parsed keeps its previous contents
+        assert!(matches!(
+            block_on(feed.refresh(&transport)),
+            Err(FetchError::JsonApp(_))
+        ));
+        assert!(feed.content().is_some());
+    }
+}
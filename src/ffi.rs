@@ -0,0 +1,177 @@
+//! A C interface to the room numbering schemes (e.g., for the digital signage
+//! firmware).
+//!
+//! Locations are passed across the interface in their packed `u16`
+//! representation (*see the `From<Location>` implementation of `u16` for
+//! more information*) -- `0` ([`LOCATION_NONE`]) never represents a valid
+//! location, and is returned whenever an operation fails.
+//!
+//! The interface only uses C-compatible types, such that a header can be
+//! generated using [cbindgen]:
+//!
+//! ```text
+//! cbindgen --lang c --crate timetableau --output timetableau.h
+//! ```
+//!
+//! [cbindgen]: https://github.com/mozilla/cbindgen
+
+use crate::Location;
+use std::ffi::{c_char, CStr};
+
+/// The packed representation of the absence of a location.
+pub const LOCATION_NONE: u16 = 0;
+
+/// Parses the NUL-terminated room identifier `code` (e.g., `H201` or
+/// `FH Gym`) into its packed representation.
+///
+/// # Returns
+///
+/// The packed representation of the location, or [`LOCATION_NONE`] should
+/// `code` be null, not valid UTF-8, or not a valid room identifier.
+///
+/// # Safety
+///
+/// `code` must either be null or point to a NUL-terminated string which
+/// remains valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn location_parse(code: *const c_char) -> u16 {
+    if code.is_null() {
+        return LOCATION_NONE;
+    }
+
+    // SAFETY: the caller guarantees `code` points to a NUL-terminated string
+    let code = unsafe { CStr::from_ptr(code) };
+
+    code.to_str()
+        .ok()
+        .and_then(|code| code.parse::<Location>().ok())
+        .map_or(LOCATION_NONE, u16::from)
+}
+
+/// Whether `location` is the packed representation of a valid location.
+#[no_mangle]
+pub extern "C" fn location_is_valid(location: u16) -> bool {
+    Location::try_from(location).is_ok()
+}
+
+/// Writes the room identifier (e.g., `H201`) of the packed `location` into
+/// `buffer` as a NUL-terminated string.
+///
+/// Should the identifier (and its NUL terminator) not fit within `length`
+/// bytes, the identifier is truncated (in the same manner as `snprintf`).
+///
+/// # Returns
+///
+/// The length of the identifier (excluding the NUL terminator) -- a value
+/// greater than or equal to `length` indicates the identifier was truncated.
+/// `0` is returned should `location` not be valid.
+///
+/// # Safety
+///
+/// `buffer` must either be null (in which case `length` must be `0`) or
+/// point to at least `length` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn location_format(
+    location: u16,
+    buffer: *mut c_char,
+    length: usize,
+) -> usize {
+    match Location::try_from(location) {
+        // SAFETY: the caller upholds the requirements of `write`
+        Ok(location) => unsafe { write(&location.to_string(), buffer, length) },
+        Err(()) => 0,
+    }
+}
+
+/// Writes the verbose name (e.g., `Howard Block, Floor 2, Room 01`) of the
+/// packed `location` into `buffer` as a NUL-terminated string.
+///
+/// *See [`location_format`] for more information*.
+///
+/// # Safety
+///
+/// `buffer` must either be null (in which case `length` must be `0`) or
+/// point to at least `length` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn location_verbose_name(
+    location: u16,
+    buffer: *mut c_char,
+    length: usize,
+) -> usize {
+    match Location::try_from(location) {
+        // SAFETY: the caller upholds the requirements of `write`
+        Ok(location) => unsafe { write(&location.verbose_name(), buffer, length) },
+        Err(()) => 0,
+    }
+}
+
+/// Whether the packed `location` is located at the Fearnhill school (rather
+/// than the Highfield school).
+///
+/// `false` is returned should `location` not be valid.
+#[no_mangle]
+pub extern "C" fn location_is_fearnhill(location: u16) -> bool {
+    matches!(Location::try_from(location), Ok(Location::Fearnhill(_)))
+}
+
+// Write `s` into `buffer` as a (possibly truncated) NUL-terminated string,
+// returning the length of `s`
+//
+// Safety: `buffer` must either be null (with a `length` of `0`) or point to at
+// least `length` writable bytes
+unsafe fn write(s: &str, buffer: *mut c_char, length: usize) -> usize {
+    if !buffer.is_null() && length > 0 {
+        let count = s.len().min(length - 1);
+
+        // SAFETY: `count + 1` does not exceed `length`, and the caller
+        // guarantees `buffer` points to at least `length` writable bytes
+        unsafe {
+            std::ptr::copy_nonoverlapping(s.as_ptr().cast(), buffer, count);
+            *buffer.add(count) = 0;
+        }
+    }
+
+    s.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ffi_round_trip() {
+        let location = unsafe { location_parse(c"FH Mu12".as_ptr()) };
+        let mut buffer = [0 as c_char; 16];
+
+        assert!(location_is_valid(location));
+        assert!(location_is_fearnhill(location));
+        assert_eq!(
+            unsafe { location_format(location, buffer.as_mut_ptr(), buffer.len()) },
+            7
+        );
+        assert_eq!(
+            unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_str(),
+            Ok("FH Mu12")
+        );
+    }
+
+    #[test]
+    fn ffi_invalid() {
+        let mut buffer = [0 as c_char; 8];
+
+        assert_eq!(unsafe { location_parse(c"H2O1".as_ptr()) }, LOCATION_NONE);
+        assert_eq!(unsafe { location_parse(std::ptr::null()) }, LOCATION_NONE);
+
+        // Verbose names are truncated to fit the buffer
+        let location = unsafe { location_parse(c"H201".as_ptr()) };
+
+        assert_eq!(
+            unsafe { location_verbose_name(location, buffer.as_mut_ptr(), buffer.len()) },
+            30
+        );
+        assert_eq!(
+            unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_str(),
+            Ok("Howard ")
+        );
+    }
+}
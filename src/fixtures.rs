@@ -0,0 +1,172 @@
+//! Anonymised whole-school datasets, for benchmarks and for the tests of
+//! downstream applications.
+//!
+//! A [`Fixture`] is a [`Dataset`] (generated by
+//! [`Timetable::generate_random`]) together with a [`Roster`] of the students
+//! enrolled in each of its classes. Fixtures contain no personal data --
+//! teachers are identified by generated staff codes (`AAA`, `AAB`, ...),
+//! classes by their year groups (`7A`, `7B`, ...), and students by sequential
+//! identifiers (`100000`, `100001`, ...) -- such that they can be shared and
+//! committed freely.
+//!
+//! ```
+//! use timetableau::fixtures::Fixture;
+//! use timetableau::validate::{NoClashes, Validator};
+//!
+//! let fixture = Fixture::whole_school(42);
+//!
+//! assert!(Validator::new().rule(NoClashes).validate(&fixture.dataset.timetable).is_valid());
+//! assert_eq!(fixture.roster.students("7A").len(), 28);
+//! ```
+//!
+//! # Remarks
+//!
+//! Generating a fixture with the same seed (and shape) always produces the
+//! same fixture -- as a result, the lessons of a fixture can be asserted upon
+//! (e.g., the number of lessons of a class).
+
+use crate::attendance::Roster;
+use crate::csv;
+use crate::{AcademicCalendar, Activity, Dataset, Profile, Timetable};
+
+/// The number of students enrolled into each class of
+/// [`Fixture::whole_school`] (i.e., a typical class size).
+const CLASS_SIZE: usize = 28;
+
+/// The identifier of the first student of a fixture.
+const FIRST_STUDENT: usize = 100_000;
+
+/// An anonymised dataset and the students enrolled in each of its classes.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    /// The generated dataset (with the default academic calendar).
+    pub dataset: Dataset,
+
+    /// The students enrolled in each class of the dataset.
+    pub roster: Roster,
+}
+
+impl Fixture {
+    /// Generates a `Fixture` with the shape described by the `profile`, with
+    /// `students` students enrolled into each of its classes (each student
+    /// being enrolled into exactly one class).
+    pub fn generate(seed: u64, profile: &Profile, students: usize) -> Self {
+        let timetable = Timetable::generate_random(seed, profile);
+        let mut roster = Roster::new();
+
+        let mut classes: Vec<&str> = timetable
+            .entries()
+            .filter_map(|(_, entry)| match &entry.activity {
+                Activity::Lesson { class, .. } => Some(class.reference().as_str()),
+                _ => None,
+            })
+            .collect();
+        classes.sort();
+        classes.dedup();
+
+        for (i, class) in classes.iter().enumerate() {
+            for student in 0..students {
+                roster.enrol(class, &(FIRST_STUDENT + i * students + student).to_string());
+            }
+        }
+
+        Self {
+            dataset: Dataset::new(timetable, AcademicCalendar::default()),
+            roster,
+        }
+    }
+
+    /// Generates a `Fixture` the size of the whole Highfield school (*see
+    /// [`Profile::whole_school`]*), with `28` students enrolled into each of
+    /// its classes.
+    pub fn whole_school(seed: u64) -> Self {
+        Self::generate(seed, &Profile::whole_school(), CLASS_SIZE)
+    }
+
+    /// Exports the timetable of the `Fixture` in the CSV format of SIMS (*see
+    /// the [`import::sims`](crate::import::sims) module*), such that the
+    /// importer can be benchmarked (or tested) against a whole-school export.
+    ///
+    /// # Remarks
+    ///
+    /// The lessons are exported in the order of their timeslots, and each
+    /// class code takes the form `<class>/<subject>` (e.g., `10A/Ma`).
+    pub fn to_sims_csv(&self) -> String {
+        let mut output = String::new();
+        csv::write_record(
+            &mut output,
+            &["Week", "Day", "Period", "Class", "Subject", "Staff", "Room"],
+        );
+
+        let mut lessons: Vec<_> = self.dataset.timetable.entries().collect();
+        lessons.sort_by_key(|(_, entry)| entry.slot.index());
+
+        for (_, entry) in lessons {
+            let Activity::Lesson {
+                subject,
+                class,
+                teacher,
+                location,
+            } = &entry.activity
+            else {
+                continue;
+            };
+
+            let slot = entry.slot;
+            let teacher = teacher
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+
+            csv::write_record(
+                &mut output,
+                &[
+                    &(slot.week as u8 + 1).to_string(),
+                    &slot.day.to_string(),
+                    &(slot.period as u8 + 1).to_string(),
+                    &format!("{}/{}", class, subject),
+                    &subject.to_string(),
+                    &teacher,
+                    &location.to_string(),
+                ],
+            );
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::sims::SimsImporter;
+
+    #[test]
+    fn fixture_generated() {
+        let fixture = Fixture::whole_school(7);
+        let timetable = &fixture.dataset.timetable;
+        let students = fixture.roster.enrolled();
+
+        // Every class has its own students
+        assert_eq!(students.len(), 42 * CLASS_SIZE);
+        assert!(students.contains("100000"));
+        assert_eq!(
+            fixture.roster.classes_of("100000").collect::<Vec<_>>(),
+            ["10A"]
+        );
+
+        // The same seed always produces the same fixture
+        let again = Fixture::whole_school(7);
+        assert_eq!(fixture.to_sims_csv(), again.to_sims_csv());
+
+        // The SIMS export can be imported without issues
+        let import = SimsImporter::new().import(&fixture.to_sims_csv()).unwrap();
+        assert!(import.issues.is_empty());
+        assert_eq!(
+            import.timetable.lessons().count(),
+            timetable.lessons().count()
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Fuzzy resolution of mistyped subjects and teachers.
+//!
+//! A [`Resolver`] knows the subjects taught at a school and its staff list,
+//! and resolves the names typed by people (e.g., `phyics`, or `Mr Smth`) to
+//! the closest subject or staff code within a configurable edit distance
+//! (*see [`edit_distance`]*):
+//!
+//! ```
+//! use timetableau::fuzzy::Resolver;
+//!
+//! let resolver = Resolver::new()
+//!     .subjects(["Physics", "Chemistry", "Biology"])
+//!     .staff_member("JSM", "John Smith")
+//!     .staff_member("ABC", "Alice Bond");
+//!
+//! assert_eq!(resolver.resolve_subject("phyics"), Some("Physics"));
+//! assert_eq!(resolver.resolve_teacher("Mr Smth"), Some("JSM"));
+//! assert_eq!(resolver.resolve_subject("history"), None);
+//! ```
+//!
+//! The importers correct the subjects and teachers of the lessons they
+//! import using a resolver (*see
+//! [`SimsImporter::resolver`](crate::import::sims::SimsImporter::resolver)*),
+//! as does the natural-language query parser (*see
+//! [`query::parse_with`](crate::query::parse_with)*).
+
+// The titles which may precede the name of a member of staff
+const TITLES: &[&str] = &["mr", "mrs", "ms", "miss", "mx", "dr", "sir"];
+
+/// Calculates the edit distance between `a` and `b` (compared
+/// case-insensitively): the number of characters which must be inserted,
+/// removed, replaced, or swapped with an adjacent character to transform one
+/// into the other.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().flat_map(char::to_lowercase).collect();
+    let b: Vec<char> = b.chars().flat_map(char::to_lowercase).collect();
+
+    // The distances between the prefixes of `a` and `b`, for the previous
+    // two characters of `a` and the current character
+    let mut before: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+
+        before = std::mem::replace(&mut previous, current);
+    }
+
+    previous[b.len()]
+}
+
+/// A candidate matched by a [`Resolver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<'a> {
+    /// The candidate (i.e., the name of a subject, or a staff code).
+    pub value: &'a str,
+
+    /// The edit distance between the input and the candidate (*see
+    /// [`edit_distance`]*).
+    pub distance: usize,
+}
+
+/// The subjects and staff against which mistyped names are resolved.
+///
+/// *See the [module](self) documentation for more information*.
+///
+/// # Remarks
+///
+/// By default, an input may be up to 2 edits away from a candidate, and at
+/// most one edit is permitted for every 3 characters of the input (such that
+/// short inputs are not matched to unrelated candidates).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolver {
+    subjects: Vec<String>,
+    staff: Vec<(String, String)>,
+    max_distance: usize,
+    characters_per_edit: usize,
+}
+
+impl Resolver {
+    /// Creates a new `Resolver` without any subjects or staff.
+    pub fn new() -> Self {
+        Self {
+            subjects: Vec::new(),
+            staff: Vec::new(),
+            max_distance: 2,
+            characters_per_edit: 3,
+        }
+    }
+
+    /// Adds the subjects with the `names` provided.
+    pub fn subjects<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.subjects.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds the member of staff with the staff `code` (e.g., `JSM`) and the
+    /// `name` (e.g., `John Smith`) provided.
+    pub fn staff_member(mut self, code: &str, name: &str) -> Self {
+        self.staff.push((code.to_string(), name.to_string()));
+        self
+    }
+
+    /// Sets the largest edit distance at which a candidate is matched.
+    pub fn max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Sets the number of characters of the input for which each edit is
+    /// permitted (at least one edit is always permitted, should the
+    /// [maximum distance](Self::max_distance) not be `0`).
+    ///
+    /// # Panics
+    ///
+    /// Panics should `characters_per_edit` be `0`.
+    pub fn characters_per_edit(mut self, characters_per_edit: usize) -> Self {
+        assert!(
+            characters_per_edit > 0,
+            "at least one character is required for each edit"
+        );
+
+        self.characters_per_edit = characters_per_edit;
+        self
+    }
+
+    /// Determines whether the resolver knows any subjects.
+    pub fn has_subjects(&self) -> bool {
+        !self.subjects.is_empty()
+    }
+
+    /// Determines whether the resolver knows any staff.
+    pub fn has_staff(&self) -> bool {
+        !self.staff.is_empty()
+    }
+
+    /// Retrieves the subjects matching the `input` provided, ranked by their
+    /// edit distance (closest first).
+    pub fn subjects_matching(&self, input: &str) -> Vec<Match<'_>> {
+        self.rank(
+            input,
+            self.subjects
+                .iter()
+                .map(|subject| (subject.as_str(), edit_distance(input, subject))),
+        )
+    }
+
+    /// Retrieves the staff codes of the members of staff matching the
+    /// `input` provided, ranked by their edit distance (closest first).
+    ///
+    /// The input is compared to the staff code, the surname, and the full
+    /// name of each member of staff -- any title (e.g., `Mr`) is
+    /// disregarded.
+    pub fn teachers_matching(&self, input: &str) -> Vec<Match<'_>> {
+        let input = input.trim();
+        let input = match input.split_once(' ') {
+            Some((title, name)) if is_title(title) => name.trim(),
+            _ => input,
+        };
+
+        self.rank(
+            input,
+            self.staff.iter().map(|(code, name)| {
+                let surname = name.split_whitespace().last().unwrap_or(name);
+                let distance = [code, surname, name]
+                    .into_iter()
+                    .map(|candidate| edit_distance(input, candidate))
+                    .min()
+                    .unwrap_or(usize::MAX);
+
+                (code.as_str(), distance)
+            }),
+        )
+    }
+
+    /// Resolves the `input` provided to a subject.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] should a single subject be closest to the input (*see
+    /// [`Self::subjects_matching`]*), or [`None`] should no subject match
+    /// (or should multiple subjects be equally close).
+    pub fn resolve_subject(&self, input: &str) -> Option<&str> {
+        best(self.subjects_matching(input))
+    }
+
+    /// Resolves the `input` provided to the staff code of a member of staff
+    /// (*see [`Self::resolve_subject`] for more information*).
+    pub fn resolve_teacher(&self, input: &str) -> Option<&str> {
+        best(self.teachers_matching(input))
+    }
+
+    // Rank the candidates within the permitted distance of the input
+    fn rank<'a, I>(&self, input: &str, candidates: I) -> Vec<Match<'a>>
+    where
+        I: Iterator<Item = (&'a str, usize)>,
+    {
+        let length = input.chars().count();
+        let permitted = self
+            .max_distance
+            .min((length / self.characters_per_edit).max(1));
+
+        let mut matches: Vec<Match<'a>> = candidates
+            .filter(|(_, distance)| *distance <= permitted)
+            .map(|(value, distance)| Match { value, distance })
+            .collect();
+
+        matches.sort_by_key(|candidate| candidate.distance);
+        matches
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Retrieve the closest match, should it be closer than every other match
+fn best(matches: Vec<Match<'_>>) -> Option<&str> {
+    match matches[..] {
+        [first] => Some(first.value),
+        [first, second, ..] if first.distance < second.distance => Some(first.value),
+        _ => None,
+    }
+}
+
+/// Determines whether the `word` provided is a title (e.g., `Mr`) which
+/// may precede the name of a member of staff.
+pub(crate) fn is_title(word: &str) -> bool {
+    TITLES.contains(&word.to_lowercase().trim_end_matches('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_resolution() {
+        assert_eq!(edit_distance("kitten", "Sitting"), 3);
+        assert_eq!(edit_distance("phyics", "Physics"), 1);
+        assert_eq!(edit_distance("hsitory", "history"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+
+        let resolver = Resolver::new()
+            .subjects(["Art", "Maths", "Music", "Physics"])
+            .staff_member("JSM", "John Smith")
+            .staff_member("JSN", "Jane Smyth");
+
+        // Short inputs only permit a single edit
+        assert_eq!(resolver.resolve_subject("Mths"), Some("Maths"));
+        assert_eq!(resolver.resolve_subject("Mus"), None);
+        assert_eq!(resolver.resolve_subject("Rat"), Some("Art"));
+
+        // Ties are ambiguous, but are still ranked
+        assert_eq!(resolver.resolve_teacher("Smth"), None);
+        assert_eq!(
+            resolver.teachers_matching("Dr Smth"),
+            [
+                Match {
+                    value: "JSM",
+                    distance: 1
+                },
+                Match {
+                    value: "JSN",
+                    distance: 1
+                }
+            ]
+        );
+        assert_eq!(resolver.resolve_teacher("Mrs Smyth"), Some("JSN"));
+        assert_eq!(resolver.resolve_teacher("jsm"), Some("JSM"));
+        assert_eq!(resolver.max_distance(0).resolve_subject("phyics"), None);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::random::Random;
+use crate::{
+    Activity, Class, FearnhillRoom, FearnhillSection, HighfieldBlock, HighfieldFloor,
+    HighfieldRoom, Location, RangedU8, Subject, Teacher, TimeSlot, Timetable,
+};
+use std::collections::HashSet;
+
+/// The shape of a timetable produced by [`Timetable::generate_random`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    /// The number of classes (named `7A`, `7B`, ..., `13F`).
+    pub classes: usize,
+
+    /// The number of teachers (with staff codes `AAA`, `AAB`, ...).
+    pub teachers: usize,
+
+    /// The number of lessons each class has per cycle (at most `50`).
+    pub lessons_per_class: usize,
+
+    /// The number of consortium lessons (i.e., lessons taking place at the
+    /// Fearnhill school) in addition to the lessons of each class.
+    pub consortium: usize,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            classes: 12,
+            teachers: 15,
+            lessons_per_class: 25,
+            consortium: 10,
+        }
+    }
+}
+
+impl Profile {
+    /// The shape of a timetable the size of the whole Highfield school (i.e.,
+    /// six classes in each of the year groups `7` to `13`, each with `40`
+    /// lessons per cycle, and `60` consortium lessons).
+    pub fn whole_school() -> Self {
+        Self {
+            classes: 42,
+            teachers: 60,
+            lessons_per_class: 40,
+            consortium: 60,
+        }
+    }
+}
+
+// The subjects taught at the Highfield school
+const SUBJECTS: [&str; 10] = ["Ma", "En", "Sc", "Hi", "Gg", "Fr", "Ar", "Mu", "PE", "Co"];
+
+// The subjects taught at the Fearnhill school as part of the consortium
+const CONSORTIUM: [&str; 4] = ["Ps", "Ec", "Lw", "So"];
+
+impl Timetable {
+    /// Generates a random (but plausible) `Timetable` with the shape
+    /// described by the `profile`.
+    ///
+    /// The timetable never contains clashes (i.e., no teacher, class, or room
+    /// is used by multiple lessons during the same timeslot), and generating
+    /// a timetable with the same `seed` and `profile` always produces the
+    /// same timetable -- this is intended for benchmarks and property-based
+    /// tests.
+    ///
+    /// # Remarks
+    ///
+    /// Should the profile require more lessons than can be scheduled without
+    /// clashes (e.g., more classes than teachers), the lessons which cannot
+    /// be scheduled are omitted.
+    pub fn generate_random(seed: u64, profile: &Profile) -> Self {
+        let mut random = Random::new(seed);
+        let mut timetable = Self::new();
+
+        let highfield = highfield_rooms(profile.classes);
+        let fearnhill = fearnhill_rooms(profile.classes.max(1));
+        let teachers: Vec<Teacher> = (0..profile.teachers).filter_map(teacher).collect();
+        let classes: Vec<Class> = (0..profile.classes).filter_map(class).collect();
+
+        // The teachers, classes, and rooms in use during each timeslot
+        let mut used: HashSet<(usize, String)> = HashSet::new();
+
+        let mut schedule = |random: &mut Random,
+                            timetable: &mut Timetable,
+                            class: &Class,
+                            subjects: &[&str],
+                            rooms: &[Location]| {
+            // Attempt a bounded number of timeslots before giving up
+            for _ in 0..100 {
+                let slot = TimeSlot::with_index(RangedU8::new(random.below(50) as u8).unwrap());
+                let index = slot.index();
+
+                if used.contains(&(index, class.to_string())) {
+                    continue;
+                }
+
+                let free = |candidates: Vec<String>| {
+                    candidates
+                        .into_iter()
+                        .position(|candidate| !used.contains(&(index, candidate)))
+                };
+
+                // Start from a random teacher (and room) such that the
+                // lessons are spread evenly
+                let offset = random.below(teachers.len().max(1));
+                let teacher = free(
+                    (0..teachers.len())
+                        .map(|i| teachers[(i + offset) % teachers.len()].to_string())
+                        .collect(),
+                )
+                .map(|i| teachers[(i + offset) % teachers.len()].clone());
+
+                let offset = random.below(rooms.len());
+                let location = free(
+                    (0..rooms.len())
+                        .map(|i| rooms[(i + offset) % rooms.len()].to_string())
+                        .collect(),
+                )
+                .map(|i| rooms[(i + offset) % rooms.len()]);
+
+                if let (Some(teacher), Some(location)) = (teacher, location) {
+                    let subject = subjects[random.below(subjects.len())];
+
+                    used.insert((index, class.to_string()));
+                    used.insert((index, teacher.to_string()));
+                    used.insert((index, location.to_string()));
+
+                    timetable.insert(
+                        slot,
+                        Activity::Lesson {
+                            subject: Subject::new(subject.to_string()).unwrap(),
+                            class: class.clone(),
+                            teacher: Some(teacher),
+                            location,
+                        },
+                    );
+                    return;
+                }
+            }
+        };
+
+        for class in &classes {
+            for _ in 0..profile.lessons_per_class.min(50) {
+                schedule(&mut random, &mut timetable, class, &SUBJECTS, &highfield);
+            }
+        }
+
+        if !classes.is_empty() {
+            for _ in 0..profile.consortium {
+                let class = &classes[random.below(classes.len())];
+                schedule(&mut random, &mut timetable, class, &CONSORTIUM, &fearnhill);
+            }
+        }
+
+        timetable
+    }
+}
+
+// The staff code of the nth teacher (`AAA`, `AAB`, ...)
+fn teacher(n: usize) -> Option<Teacher> {
+    let letter = |i: usize| char::from(b'A' + (i % 26) as u8);
+    let code: String = [n / 676, n / 26, n].into_iter().map(letter).collect();
+
+    Teacher::new(code).ok()
+}
+
+// The reference of the nth class (`7A` to `7F`, then `8A`, ...)
+fn class(n: usize) -> Option<Class> {
+    Class::new(format!("{}{}", 7 + n / 6, char::from(b'A' + (n % 6) as u8))).ok()
+}
+
+// At least `count` classrooms at the Highfield school
+fn highfield_rooms(count: usize) -> Vec<Location> {
+    let blocks = [
+        HighfieldBlock::Howard,
+        HighfieldBlock::Parker,
+        HighfieldBlock::Unwin,
+    ];
+
+    (1..=99)
+        .flat_map(|discriminator| {
+            blocks.into_iter().flat_map(move |block| {
+                [
+                    HighfieldFloor::Ground,
+                    HighfieldFloor::Level(RangedU8::new(1).unwrap()),
+                ]
+                .into_iter()
+                .map(move |floor| {
+                    Location::Highfield(HighfieldRoom::Classroom {
+                        block,
+                        floor,
+                        discriminator: RangedU8::new(discriminator).unwrap(),
+                    })
+                })
+            })
+        })
+        .take(count.max(1))
+        .collect()
+}
+
+// At least `count` classrooms at the Fearnhill school
+fn fearnhill_rooms(count: usize) -> Vec<Location> {
+    (1..=99)
+        .flat_map(|discriminator| {
+            [FearnhillSection::Humanities, FearnhillSection::Business]
+                .into_iter()
+                .map(move |section| {
+                    Location::Fearnhill(FearnhillRoom::Classroom {
+                        section,
+                        discriminator: RangedU8::new(discriminator).unwrap(),
+                    })
+                })
+        })
+        .take(count)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::{NoClashes, Validator};
+    use crate::School;
+
+    #[test]
+    fn generate_random_valid() {
+        let profile = Profile::default();
+        let timetable = Timetable::generate_random(42, &profile);
+
+        let report = Validator::new().rule(NoClashes).validate(&timetable);
+
+        assert!(report.is_valid());
+        assert_eq!(timetable.lessons().count(), 12 * 25 + 10);
+        assert_eq!(timetable.lessons().at_school(School::Fearnhill).count(), 10);
+        assert_eq!(
+            timetable
+                .lessons()
+                .for_class("8F")
+                .at_school(School::Highfield)
+                .count(),
+            25
+        );
+
+        // The same seed always produces the same timetable
+        let again = Timetable::generate_random(42, &profile);
+
+        assert!(timetable
+            .entries()
+            .zip(again.entries())
+            .all(|((_, a), (_, b))| a == b));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::json::write_string;
+use crate::occupancy::Zone;
+use crate::{Coordinates, Dataset, Location, Room, RoomKind};
+use std::fmt::Write;
+
+// The centroids of the blocks, sections and sites of the schools
+const CENTROIDS: &str = include_str!("../data/centroids.txt");
+
+impl Dataset {
+    /// Exports every room used by the `Dataset` (*see [`Self::locations`]*)
+    /// as a GeoJSON ([RFC 7946]) `FeatureCollection`, such that the estate
+    /// of the schools can be consumed by mapping tools.
+    ///
+    /// Each room is a `Feature` whose geometry is a `Point` at the centroid
+    /// of its block (or section), or of its school should the centroid of
+    /// its block not be known -- an [off-site location](crate::OffSite) is
+    /// placed at its own coordinates. The properties of each feature are the
+    /// `code` of the room (e.g., `H201`), its verbose `name` (*see
+    /// [`Location::verbose_name`]*), and its `kind` (e.g., `classroom`):
+    ///
+    /// ```
+    /// use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, Subject};
+    /// use timetableau::Timetable;
+    ///
+    /// let mut timetable = Timetable::new();
+    /// timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+    ///     subject: Subject::new("Ma".to_string()).unwrap(),
+    ///     class: Class::new("10A".to_string()).unwrap(),
+    ///     teacher: None,
+    ///     location: "H201".parse().unwrap(),
+    /// });
+    ///
+    /// let geojson = Dataset::new(timetable, AcademicCalendar::default()).locations_geojson();
+    ///
+    /// assert!(geojson.starts_with("{\n  \"type\": \"FeatureCollection\","));
+    /// assert!(geojson.contains("\"name\": \"Howard Block, Floor 2, Room 01\""));
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// Remote lessons and rooms which are yet to be confirmed occupy no room,
+    /// and are therefore not exported. An off-site location without
+    /// coordinates is exported with a `null` geometry.
+    ///
+    /// [RFC 7946]: https://www.rfc-editor.org/rfc/rfc7946
+    pub fn locations_geojson(&self) -> String {
+        let centroids = centroids();
+        let features: Vec<Location> = self
+            .locations()
+            .into_iter()
+            .filter(|location| location.kind().is_occupied())
+            .collect();
+
+        let mut output = String::from("{\n  \"type\": \"FeatureCollection\",\n");
+
+        output.push_str("  \"features\": [");
+
+        for (index, location) in features.iter().enumerate() {
+            output.push_str(if index == 0 { "\n" } else { ",\n" });
+            output.push_str("    {\n      \"type\": \"Feature\",\n      \"geometry\": ");
+
+            match position(&centroids, location) {
+                Some(coordinates) => {
+                    let _ = write!(
+                        output,
+                        "{{\"type\": \"Point\", \"coordinates\": [{:.6}, {:.6}]}}",
+                        coordinates.longitude(),
+                        coordinates.latitude()
+                    );
+                }
+                None => output.push_str("null"),
+            }
+
+            output.push_str(",\n      \"properties\": {\"code\": ");
+            write_string(&mut output, &location.to_string());
+            output.push_str(", \"name\": ");
+            write_string(&mut output, &location.verbose_name());
+            output.push_str(", \"kind\": ");
+            write_string(&mut output, kind_name(location.kind()));
+            output.push_str("}\n    }");
+        }
+
+        if !features.is_empty() {
+            output.push_str("\n  ");
+        }
+
+        output.push_str("]\n}\n");
+        output
+    }
+}
+
+// Parse the embedded centroids, as `(area, coordinates)`
+fn centroids() -> Vec<(&'static str, Coordinates)> {
+    CENTROIDS
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (area, coordinates) = line.split_once(':')?;
+
+            Some((area.trim(), coordinates.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+// Determine the position of the location provided: its own coordinates
+// (should it be off-site), or the centroid of its zone or school
+fn position(centroids: &[(&str, Coordinates)], location: &Location) -> Option<Coordinates> {
+    if let Some(site) = location.off_site() {
+        return site.coordinates();
+    }
+
+    let centroid = |area: &str| {
+        centroids
+            .iter()
+            .find(|(name, _)| *name == area)
+            .map(|(_, coordinates)| *coordinates)
+    };
+
+    let zone = match Zone::of(location) {
+        Zone::Block(block) => Some(block.full_name()),
+        Zone::Section(section) => Some(section.full_name()),
+        Zone::Elsewhere(_) => None,
+    };
+
+    zone.and_then(centroid)
+        .or_else(|| centroid(location.school().name()))
+}
+
+fn kind_name(kind: RoomKind) -> &'static str {
+    match kind {
+        RoomKind::Classroom => "classroom",
+        RoomKind::Lab => "lab",
+        RoomKind::Hall => "hall",
+        RoomKind::Sports => "sports",
+        RoomKind::Studio => "studio",
+        RoomKind::Outdoor => "outdoor",
+        RoomKind::OffSite => "off-site",
+        RoomKind::Remote => "remote",
+        RoomKind::ToBeConfirmed => "to-be-confirmed",
+        RoomKind::Other => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Activity, Class, Subject, Timetable};
+
+    #[test]
+    fn locations_geojson_features() {
+        let mut timetable = Timetable::new();
+
+        for (slot, code) in [
+            (timeslot!(W1MP1), "FH S1"),
+            (timeslot!(W1MP2), "@Stevenage Pool (51.901200, -0.202100)"),
+            (timeslot!(W1MP3), "@Museum"),
+            (timeslot!(W1MP4), "TBC"),
+        ] {
+            timetable.insert(
+                slot,
+                Activity::Lesson {
+                    subject: Subject::new("Sc".to_string()).unwrap(),
+                    class: Class::new("10A".to_string()).unwrap(),
+                    teacher: None,
+                    location: code.parse().unwrap(),
+                },
+            );
+        }
+
+        let geojson = Dataset::new(timetable, AcademicCalendar::default()).locations_geojson();
+
+        assert_eq!(
+            geojson,
+            "{\n  \"type\": \"FeatureCollection\",\n  \"features\": [\n    {\n      \
+             \"type\": \"Feature\",\n      \"geometry\": null,\n      \"properties\": \
+             {\"code\": \"@Museum\", \"name\": \"Museum (Off-Site)\", \"kind\": \"off-site\"}\n    \
+             },\n    {\n      \"type\": \"Feature\",\n      \"geometry\": {\"type\": \"Point\", \
+             \"coordinates\": [-0.202100, 51.901200]},\n      \"properties\": {\"code\": \
+             \"@Stevenage Pool (51.901200, -0.202100)\", \"name\": \"Stevenage Pool (Off-Site)\", \
+             \"kind\": \"off-site\"}\n    },\n    {\n      \"type\": \"Feature\",\n      \
+             \"geometry\": {\"type\": \"Point\", \"coordinates\": [-0.252900, 51.983700]},\n      \
+             \"properties\": {\"code\": \"FH S1\", \"name\": \"Fearnhill Science, Room 1\", \
+             \"kind\": \"lab\"}\n    }\n  ]\n}\n"
+        );
+
+        // Every embedded centroid is valid
+        let areas = CENTROIDS
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'));
+        assert_eq!(centroids().len(), areas.count());
+
+        let empty = Dataset::new(Timetable::new(), AcademicCalendar::default());
+        assert_eq!(
+            empty.locations_geojson(),
+            "{\n  \"type\": \"FeatureCollection\",\n  \"features\": []\n}\n"
+        );
+    }
+}
@@ -0,0 +1,405 @@
+//! Heatmaps of the use of rooms, for the floor plans of the estates
+//! dashboard.
+//!
+//! A [`Heatmap`] measures each room of the timetable by a [`Metric`] (i.e.,
+//! its utilisation, or its footfall), producing one [`HeatmapCell`] per room
+//! -- grouped by the [`Zone`] and floor in which the room is located, and
+//! positioned on the floor plan should its position be known. The cells are
+//! [exported](Heatmap::to_json) as JSON, which the dashboard binds to the
+//! rooms of an SVG floor plan:
+//!
+//! ```
+//! use timetableau::attendance::Roster;
+//! use timetableau::heatmap::Heatmap;
+//! use timetableau::{timeslot, Activity, Class, Subject, Timetable};
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new("10A".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "H201".parse().unwrap(),
+//! });
+//!
+//! let mut roster = Roster::new();
+//! roster.enrol("10A", "1001");
+//! roster.enrol("10A", "1002");
+//!
+//! let heatmap = Heatmap::footfall(&roster).position("H201".parse().unwrap(), 120.0, 40.0);
+//! let cells = heatmap.cells(&timetable);
+//!
+//! assert_eq!(cells[0].value, 2.0);
+//! assert!(heatmap.to_json(&timetable).contains(
+//!     r#""rooms":[{"room":"H201","value":2,"intensity":1,"position":{"x":120,"y":40}}]"#
+//! ));
+//! ```
+//!
+//! # Format
+//!
+//! The JSON document contains the `metric` (`utilisation` or `footfall`),
+//! the greatest `value` of any room (`max`), and the `zones` -- each with
+//! its `zone` (*see [`Zone`]'s `Display`*), its `school`, and its `floors`
+//! (the floor being `G`, a level, or `null` for the rooms without a floor),
+//! each of which contains its `rooms`: the `room`, its `value`, its
+//! `intensity` (the value relative to `max`, between `0` and `1`), and its
+//! `position` and `coordinates` should they be known.
+
+use crate::attendance::Roster;
+use crate::json::write_string;
+use crate::occupancy::Zone;
+use crate::{
+    Activity, Coordinates, HighfieldFloor, Location, Room, RoomKind, RoomRegistry, TimeSlot,
+    Timetable,
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+// The number of timeslots of the fortnight
+const SLOTS: f64 = 50.0;
+
+/// The measure of the use of a room shown by a [`Heatmap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// The proportion of the timeslots of the fortnight during which the
+    /// room hosts a lesson (between `0` and `1`).
+    Utilisation,
+
+    /// The number of students the room hosts during the fortnight (i.e.,
+    /// the sum of the sizes of the classes of its lessons).
+    Footfall,
+}
+
+impl Metric {
+    /// Retrieves the name of the `Metric` (e.g., `footfall`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Utilisation => "utilisation",
+            Self::Footfall => "footfall",
+        }
+    }
+}
+
+/// The use of a single room of a [`Heatmap`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeatmapCell {
+    /// The room.
+    pub location: Location,
+
+    /// The zone in which the room is located.
+    pub zone: Zone,
+
+    /// The floor of the room (should it be a Highfield classroom).
+    pub floor: Option<HighfieldFloor>,
+
+    /// The measure of the room's use (*see [`Metric`]*).
+    pub value: f64,
+
+    /// The value of the room relative to the greatest value of any room of
+    /// the heatmap (between `0` and `1`).
+    pub intensity: f64,
+
+    /// The position of the room on the floor plan (should it have been
+    /// provided -- *see [`Heatmap::position`]*).
+    pub position: Option<(f64, f64)>,
+
+    /// The geographic coordinates of the room (should it be an off-site
+    /// location with coordinates).
+    pub coordinates: Option<Coordinates>,
+}
+
+/// Produces the heatmap of the rooms of a timetable.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone)]
+pub struct Heatmap<'a> {
+    roster: Option<&'a Roster>,
+    rooms: Option<&'a RoomRegistry>,
+    positions: HashMap<Location, (f64, f64)>,
+}
+
+impl<'a> Heatmap<'a> {
+    /// Creates a new `Heatmap` of the [utilisation](Metric::Utilisation) of
+    /// each room.
+    pub fn utilisation() -> Self {
+        Self {
+            roster: None,
+            rooms: None,
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Creates a new `Heatmap` of the [footfall](Metric::Footfall) of each
+    /// room, where the size of each class is the number of its students in
+    /// the `roster` provided.
+    pub fn footfall(roster: &'a Roster) -> Self {
+        Self {
+            roster: Some(roster),
+            ..Self::utilisation()
+        }
+    }
+
+    /// Sets the registry of the `rooms` to include -- the rooms which host
+    /// no lessons are then included (with a value of `0`), such that every
+    /// room of the floor plan is coloured.
+    pub fn rooms(mut self, rooms: &'a RoomRegistry) -> Self {
+        self.rooms = Some(rooms);
+        self
+    }
+
+    /// Sets the position of the `location` provided on the floor plan (in
+    /// the units of the plan, e.g., the coordinates of the SVG element of the
+    /// room).
+    pub fn position(mut self, location: Location, x: f64, y: f64) -> Self {
+        self.positions.insert(location, (x, y));
+        self
+    }
+
+    /// Retrieves the [`Metric`] of the `Heatmap`.
+    pub fn metric(&self) -> Metric {
+        match self.roster {
+            Some(_) => Metric::Footfall,
+            None => Metric::Utilisation,
+        }
+    }
+
+    /// Measures each room of the `timetable` provided (disregarding remote
+    /// lessons), ordered by their zones, then their floors, and then their
+    /// identifiers.
+    pub fn cells(&self, timetable: &Timetable) -> Vec<HeatmapCell> {
+        let mut footfall: HashMap<Location, usize> = self
+            .rooms
+            .into_iter()
+            .flat_map(RoomRegistry::iter)
+            .filter(|location| location.kind() != RoomKind::Remote)
+            .map(|location| (*location, 0))
+            .collect();
+
+        // Lessons sharing a timeslot (e.g., combined classes) occupy their
+        // room once
+        let mut occupied: HashSet<(Location, TimeSlot)> = HashSet::new();
+
+        for (_, entry) in timetable.entries() {
+            let Activity::Lesson { class, .. } = &entry.activity else {
+                continue;
+            };
+
+            let students = self
+                .roster
+                .map_or(0, |roster| roster.students(class.reference()).len());
+            let rooms: Vec<Location> = entry
+                .locations()
+                .copied()
+                .filter(|location| location.kind() != RoomKind::Remote)
+                .collect();
+
+            // A split class's students are divided evenly across its rooms
+            // (the first rooms receiving any remainder)
+            for (index, location) in rooms.iter().enumerate() {
+                *footfall.entry(*location).or_default() +=
+                    students / rooms.len() + usize::from(index < students % rooms.len());
+                occupied.extend(entry.slots().map(|slot| (*location, slot)));
+            }
+        }
+
+        let values: Vec<(Location, f64)> = footfall
+            .into_iter()
+            .map(|(location, students)| match self.roster {
+                Some(_) => (location, students as f64),
+                None => {
+                    let slots = occupied.iter().filter(|(other, _)| *other == location);
+                    (location, slots.count() as f64 / SLOTS)
+                }
+            })
+            .collect();
+
+        let max = values.iter().map(|(_, value)| *value).fold(0.0, f64::max);
+        let mut cells: Vec<_> = values
+            .into_iter()
+            .map(|(location, value)| HeatmapCell {
+                location,
+                zone: Zone::of(&location),
+                floor: location.parts().floor,
+                value,
+                intensity: if max > 0.0 { value / max } else { 0.0 },
+                position: self.positions.get(&location).copied(),
+                coordinates: location.off_site().and_then(|site| site.coordinates()),
+            })
+            .collect();
+
+        cells.sort_by_cached_key(|cell| {
+            (
+                cell.zone.rank(),
+                cell.floor.map(floor_rank),
+                cell.location.to_string(),
+            )
+        });
+        cells
+    }
+
+    /// Exports the heatmap of the `timetable` provided as JSON (*see the
+    /// [module](self#format) documentation for a description of the
+    /// format*).
+    pub fn to_json(&self, timetable: &Timetable) -> String {
+        let cells = self.cells(timetable);
+        let max = cells.iter().map(|cell| cell.value).fold(0.0, f64::max);
+
+        let mut output = String::from("{\"metric\":");
+        write_string(&mut output, self.metric().name());
+        let _ = write!(output, ",\"max\":{},\"zones\":[", max);
+
+        let mut previous: Option<&HeatmapCell> = None;
+
+        for cell in &cells {
+            match previous {
+                Some(other) if other.zone == cell.zone && other.floor == cell.floor => {
+                    output.push(',');
+                }
+                Some(other) => {
+                    output.push_str("]}");
+
+                    if other.zone == cell.zone {
+                        output.push(',');
+                        write_floor(&mut output, cell.floor);
+                    } else {
+                        output.push_str("]},");
+                        write_zone(&mut output, cell);
+                    }
+                }
+                None => write_zone(&mut output, cell),
+            }
+
+            output.push_str("{\"room\":");
+            write_string(&mut output, &cell.location.to_string());
+            let _ = write!(
+                output,
+                ",\"value\":{},\"intensity\":{}",
+                cell.value, cell.intensity
+            );
+
+            if let Some((x, y)) = cell.position {
+                let _ = write!(output, ",\"position\":{{\"x\":{},\"y\":{}}}", x, y);
+            }
+
+            if let Some(coordinates) = cell.coordinates {
+                let _ = write!(
+                    output,
+                    ",\"coordinates\":{{\"latitude\":{},\"longitude\":{}}}",
+                    coordinates.latitude(),
+                    coordinates.longitude()
+                );
+            }
+
+            output.push('}');
+            previous = Some(cell);
+        }
+
+        if previous.is_some() {
+            output.push_str("]}]}");
+        }
+
+        output.push_str("]}");
+        output
+    }
+}
+
+/// The position of the `floor` provided within its zone (from the ground
+/// floor upwards).
+fn floor_rank(floor: HighfieldFloor) -> u8 {
+    match floor {
+        HighfieldFloor::Ground => 0,
+        HighfieldFloor::Level(level) => level.get(),
+    }
+}
+
+/// Writes the opening of the zone of the `cell` provided (and of its floor)
+/// to the `output`.
+fn write_zone(output: &mut String, cell: &HeatmapCell) {
+    output.push_str("{\"zone\":");
+    write_string(output, &cell.zone.to_string());
+    output.push_str(",\"school\":");
+    write_string(output, &cell.location.school().to_string());
+    output.push_str(",\"floors\":[");
+    write_floor(output, cell.floor);
+}
+
+/// Writes the opening of the `floor` provided to the `output`.
+fn write_floor(output: &mut String, floor: Option<HighfieldFloor>) {
+    output.push_str("{\"floor\":");
+
+    match floor {
+        Some(floor) => write_string(output, &floor.to_string()),
+        None => output.push_str("null"),
+    }
+
+    output.push_str(",\"rooms\":[");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, Subject};
+
+    #[test]
+    fn heatmap_grouped() {
+        let mut timetable = Timetable::new();
+        let mut lesson = |slot, class: &str, location: &str| {
+            timetable.insert(
+                slot,
+                Activity::Lesson {
+                    subject: Subject::new("Ma".to_string()).unwrap(),
+                    class: Class::new(class.to_string()).unwrap(),
+                    teacher: None,
+                    location: location.parse().unwrap(),
+                },
+            );
+        };
+
+        lesson(timeslot!(W1MP1), "10A", "H201");
+        lesson(timeslot!(W1MP1), "10B", "H201");
+        lesson(timeslot!(W1MP2), "10A", "H201");
+        lesson(timeslot!(W1MP1), "10C", "HG03");
+        lesson(timeslot!(W1MP3), "10C", "Remote (Teams)");
+
+        let mut rooms = RoomRegistry::new();
+        rooms.insert("P105".parse().unwrap());
+
+        let utilisation = Heatmap::utilisation().rooms(&rooms).cells(&timetable);
+        let values: Vec<_> = utilisation
+            .iter()
+            .map(|cell| (cell.location.to_string(), cell.value, cell.intensity))
+            .collect();
+
+        // Combined lessons occupy the room once, and the unused room is cold
+        assert_eq!(
+            values,
+            vec![
+                ("HG03".to_string(), 0.02, 0.5),
+                ("H201".to_string(), 0.04, 1.0),
+                ("P105".to_string(), 0.0, 0.0),
+            ]
+        );
+
+        let mut roster = Roster::new();
+        roster.enrol("10A", "1001");
+        roster.enrol("10A", "1002");
+        roster.enrol("10B", "1003");
+
+        let json = Heatmap::footfall(&roster)
+            .position("H201".parse().unwrap(), 10.0, 20.5)
+            .rooms(&rooms)
+            .to_json(&timetable);
+
+        assert!(json.starts_with(r#"{"metric":"footfall","max":5,"zones":[{"zone":"#));
+        assert!(json.contains(concat!(
+            r#""floors":[{"floor":"G","rooms":[{"room":"HG03","value":0,"intensity":0}]},"#,
+            r#"{"floor":"2","rooms":[{"room":"H201","value":5,"intensity":1,"#,
+            r#""position":{"x":10,"y":20.5}}]}]},{"zone":"#
+        )));
+        assert!(json.ends_with(r#"{"room":"P105","value":0,"intensity":0}]}]}]}"#));
+        assert!(crate::json::parse(&json).is_ok());
+        assert_eq!(
+            Heatmap::utilisation().to_json(&Timetable::new()),
+            r#"{"metric":"utilisation","max":0,"zones":[]}"#
+        );
+    }
+}
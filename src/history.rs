@@ -0,0 +1,270 @@
+use crate::access::Role;
+use crate::{Activity, Entry, EntryId, Location, Room, RoomOverride, TimeSlot, Timetable};
+use chrono::prelude::*;
+use std::sync::Arc;
+
+/// A mutation made to a [`Timetable`].
+///
+/// Each change contains both the old and the new values of anything it
+/// modifies, such that it is always possible to determine exactly what a
+/// change did (and, consequently, how it can be reversed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change<L = Location> {
+    /// An [`Entry`] was inserted into the timetable.
+    Inserted {
+        /// The identifier assigned to the entry.
+        id: EntryId,
+
+        /// The entry which was inserted.
+        entry: Entry<L>,
+    },
+
+    /// An [`Entry`] was removed from the timetable.
+    Removed {
+        /// The identifier of the entry.
+        id: EntryId,
+
+        /// The entry which was removed.
+        entry: Entry<L>,
+    },
+
+    /// An [`Entry`] was moved to a different [`TimeSlot`].
+    Moved {
+        /// The identifier of the entry.
+        id: EntryId,
+
+        /// The slot in which the entry was previously scheduled.
+        from: TimeSlot,
+
+        /// The slot in which the entry is now scheduled.
+        to: TimeSlot,
+    },
+
+    /// A lesson was moved to a different [`Location`].
+    Relocated {
+        /// The identifier of the lesson's entry.
+        id: EntryId,
+
+        /// The previous location of the lesson.
+        from: L,
+
+        /// The new location of the lesson.
+        to: L,
+    },
+
+    /// A [`RoomOverride`] was recorded.
+    OverrideAdded(RoomOverride<L>),
+
+    /// A [`RoomOverride`] was removed.
+    OverrideRemoved {
+        /// The position the override occupied in
+        /// [`Timetable::overrides`].
+        index: usize,
+
+        /// The override which was removed.
+        room_override: RoomOverride<L>,
+    },
+}
+
+/// An entry in the history of a [`Timetable`]: who made a [`Change`] and
+/// when they made it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record<L = Location> {
+    /// The time at which the change was made.
+    pub at: DateTime<Utc>,
+
+    /// The person who made the change (if known).
+    ///
+    /// *See [`Timetable::set_author`] for more information*.
+    pub author: Option<String>,
+
+    /// The role in which the author made the change (if known).
+    ///
+    /// *See [`Session`](crate::access::Session) for more information*.
+    pub role: Option<Role>,
+
+    /// The change which was made.
+    pub change: Change<L>,
+}
+
+impl<L: Room> Timetable<L> {
+    /// Sets the person to whom subsequent changes are attributed in the
+    /// timetable's history.
+    ///
+    /// # Remarks
+    ///
+    /// The author is not a part of the timetable itself -- it is neither
+    /// compared by [`PartialEq`] nor retained by [`Self::as_of`].
+    pub fn set_author(&mut self, author: Option<String>) {
+        self.author = author;
+    }
+
+    /// Retrieves the person to whom subsequent changes are attributed.
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// Sets the role in which subsequent changes are made (i.e., the role
+    /// recorded in the timetable's history alongside the author).
+    ///
+    /// # Remarks
+    ///
+    /// Setting the role grants no permissions -- changes are only checked
+    /// against the role when made through a
+    /// [`Session`](crate::access::Session), which sets the role itself.
+    pub fn set_role(&mut self, role: Option<Role>) {
+        self.role = role;
+    }
+
+    /// Retrieves the role in which subsequent changes are made.
+    pub fn role(&self) -> Option<Role> {
+        self.role
+    }
+
+    /// Retrieves the history of the `Timetable`: every [`Change`] made to the
+    /// timetable since its creation (in the order in which they were made).
+    pub fn history(&self) -> &[Record<L>] {
+        &self.history
+    }
+
+    /// Retrieves every [`Record`] of a change made after `at`.
+    pub fn changes_since(&self, at: DateTime<Utc>) -> impl Iterator<Item = &Record<L>> + '_ {
+        self.history.iter().filter(move |record| record.at > at)
+    }
+
+    /// Reconstructs the `Timetable` as it was at the time provided (i.e.,
+    /// disregarding every change made after `at`).
+    ///
+    /// The history of the reconstructed timetable only contains the changes
+    /// which were made at or before `at`.
+    pub fn as_of(&self, at: DateTime<Utc>) -> Self {
+        let mut timetable = Self::default();
+
+        for record in self.history.iter().filter(|record| record.at <= at) {
+            timetable.apply(&record.change);
+            Arc::make_mut(&mut timetable.history).push(record.clone());
+        }
+
+        timetable
+    }
+
+    /// Applies a `change` to the timetable and records it in the timetable's
+    /// history.
+    pub(crate) fn commit(&mut self, change: Change<L>) {
+        self.apply(&change);
+
+        let record = Record {
+            at: Utc::now(),
+            author: self.author.clone(),
+            role: self.role,
+            change,
+        };
+
+        #[cfg(feature = "std")]
+        self.subscribers.notify(&record);
+
+        Arc::make_mut(&mut self.history).push(record);
+    }
+
+    /// Applies a `change` to the timetable without recording it.
+    pub(crate) fn apply(&mut self, change: &Change<L>) {
+        // The collections may be shared with snapshots of the timetable, in
+        // which case they are copied before being modified
+        match change {
+            Change::Inserted { id, entry } => {
+                if let Some(previous) = Arc::make_mut(&mut self.entries).insert(*id, entry.clone())
+                {
+                    Arc::make_mut(&mut self.index).remove(*id, &previous);
+                }
+
+                Arc::make_mut(&mut self.index).insert(*id, entry);
+
+                // Ensure identifiers are never reused
+                self.next_id = self.next_id.max(id.get() + 1);
+            }
+            Change::Removed { id, .. } => {
+                if let Some(entry) = Arc::make_mut(&mut self.entries).remove(id) {
+                    Arc::make_mut(&mut self.index).remove(*id, &entry);
+                }
+            }
+            Change::Moved { id, to, .. } => {
+                if let Some(entry) = Arc::make_mut(&mut self.entries).get_mut(id) {
+                    entry.slot = *to;
+                }
+            }
+            Change::Relocated { id, to, .. } => {
+                if let Some(entry) = Arc::make_mut(&mut self.entries).get_mut(id) {
+                    Arc::make_mut(&mut self.index).remove(*id, entry);
+
+                    if let Activity::Lesson { location, .. } = &mut entry.activity {
+                        location.clone_from(to);
+                    }
+
+                    Arc::make_mut(&mut self.index).insert(*id, entry);
+                }
+            }
+            Change::OverrideAdded(room_override) => {
+                Arc::make_mut(&mut self.overrides).push(room_override.clone());
+            }
+            Change::OverrideRemoved { index, .. } => {
+                if *index < self.overrides.len() {
+                    Arc::make_mut(&mut self.overrides).remove(*index);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, HighfieldRoom, Subject};
+    use std::thread;
+    use std::time::Duration;
+
+    fn lesson() -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Maths".to_string()).unwrap(),
+            class: Class::new("JSM".to_string()).unwrap(),
+            teacher: None,
+            location: Location::Highfield(HighfieldRoom::Hall),
+        }
+    }
+
+    #[test]
+    fn history_records_author() {
+        let mut timetable = Timetable::new();
+
+        timetable.set_author(Some("JSM".to_string()));
+        let id = timetable.insert(timeslot!(W1MP1), lesson());
+
+        let record = &timetable.history()[0];
+
+        assert_eq!(record.author.as_deref(), Some("JSM"));
+        assert_eq!(
+            record.change,
+            Change::Inserted {
+                id,
+                entry: Entry::new(timeslot!(W1MP1), lesson()),
+            }
+        );
+    }
+
+    #[test]
+    fn history_as_of() {
+        let mut timetable = Timetable::new();
+        let id = timetable.insert(timeslot!(W1MP1), lesson());
+        let before = timetable.clone();
+
+        thread::sleep(Duration::from_millis(2));
+        let checkpoint = Utc::now();
+        thread::sleep(Duration::from_millis(2));
+
+        timetable.relocate(id, Location::Highfield(HighfieldRoom::SportsHall));
+        timetable.move_entry(id, timeslot!(W2FP5)).unwrap();
+
+        assert_eq!(timetable.changes_since(checkpoint).count(), 2);
+        assert_eq!(timetable.as_of(checkpoint), before);
+        assert_ne!(timetable, before);
+    }
+}
@@ -0,0 +1,209 @@
+use chrono::prelude::*;
+use chrono::Duration;
+
+/// A date, as `(year, month, day)`.
+type Ymd = (i32, u32, u32);
+
+/// The bank holidays which were moved from their usual date by royal
+/// proclamation, as `(usual date, actual date)`.
+const MOVED: [(Ymd, Ymd); 4] = [
+    ((2002, 5, 27), (2002, 6, 4)),
+    ((2012, 5, 28), (2012, 6, 4)),
+    ((2020, 5, 4), (2020, 5, 8)),
+    ((2022, 5, 30), (2022, 6, 2)),
+];
+
+/// The additional (one-off) bank holidays declared by royal proclamation.
+const ADDITIONAL: [(Ymd, &str); 6] = [
+    ((2002, 6, 3), "Golden Jubilee"),
+    ((2011, 4, 29), "Royal Wedding"),
+    ((2012, 6, 5), "Diamond Jubilee"),
+    ((2022, 6, 3), "Platinum Jubilee"),
+    ((2022, 9, 19), "State Funeral of Queen Elizabeth II"),
+    ((2023, 5, 8), "Coronation of King Charles III"),
+];
+
+/// A bank holiday in England and Wales, on which both schools are closed.
+///
+/// The bank holidays of a year are calculated from the rules set out by the
+/// Banking and Financial Dealings Act 1971 (including the substitute days
+/// given when a holiday falls at a weekend), together with the holidays
+/// which have been moved (or added) by royal proclamation since 2000:
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use timetableau::BankHoliday;
+///
+/// let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+/// let holidays = BankHoliday::in_year(2024);
+///
+/// assert_eq!(holidays.len(), 8);
+/// assert_eq!(BankHoliday::on(date(4, 1)).map(|holiday| holiday.name), Some("Easter Monday"));
+/// assert_eq!(BankHoliday::on(date(4, 2)), None);
+/// ```
+///
+/// An [`AcademicCalendar`](crate::AcademicCalendar) observes the bank
+/// holidays by default (*see
+/// [`AcademicCalendar::is_school_day`](crate::AcademicCalendar::is_school_day)*),
+/// such that they need not be excluded from its terms.
+///
+/// # Remarks
+///
+/// Proclamations are made at short notice -- a bank holiday proclaimed after
+/// the release of this crate should be recorded as a
+/// [closure](crate::DayException::Closed) until the crate is updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BankHoliday {
+    /// The date on which the holiday is observed (i.e., the substitute day
+    /// should the holiday itself fall at a weekend).
+    pub date: NaiveDate,
+
+    /// The name of the holiday (e.g., `Easter Monday`).
+    pub name: &'static str,
+}
+
+impl BankHoliday {
+    /// Retrieves the bank holidays of the `year` provided (in chronological
+    /// order).
+    pub fn in_year(year: i32) -> Vec<BankHoliday> {
+        let date = |month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let easter = easter_sunday(year);
+
+        let mut holidays = vec![
+            (substitute(date(1, 1)), "New Year's Day"),
+            (easter - Duration::days(2), "Good Friday"),
+            (easter + Duration::days(1), "Easter Monday"),
+            (first_monday(year, 5), "Early May Bank Holiday"),
+            (last_monday(year, 5), "Spring Bank Holiday"),
+            (last_monday(year, 8), "Summer Bank Holiday"),
+        ];
+
+        // Should Christmas Day fall at a weekend, it is substituted by the
+        // first weekday not already taken by Boxing Day
+        let christmas = date(12, 25);
+        let (christmas, boxing) = match christmas.weekday() {
+            Weekday::Fri => (christmas, date(12, 28)),
+            Weekday::Sat => (date(12, 27), date(12, 28)),
+            Weekday::Sun => (date(12, 27), date(12, 26)),
+            _ => (christmas, christmas + Duration::days(1)),
+        };
+
+        holidays.push((christmas, "Christmas Day"));
+        holidays.push((boxing, "Boxing Day"));
+
+        for (usual, actual) in MOVED {
+            for holiday in &mut holidays {
+                if holiday.0 == ymd(usual) {
+                    holiday.0 = ymd(actual);
+                }
+            }
+        }
+
+        holidays.extend(
+            ADDITIONAL
+                .iter()
+                .filter(|(date, _)| date.0 == year)
+                .map(|(date, name)| (ymd(*date), *name)),
+        );
+
+        let mut holidays: Vec<BankHoliday> = holidays
+            .into_iter()
+            .map(|(date, name)| BankHoliday { date, name })
+            .collect();
+        holidays.sort_by_key(|holiday| holiday.date);
+
+        holidays
+    }
+
+    /// Retrieves the bank holiday observed on the `date` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if the `date` is a bank holiday, or [`None`] otherwise.
+    pub fn on(date: NaiveDate) -> Option<BankHoliday> {
+        Self::in_year(date.year())
+            .into_iter()
+            .find(|holiday| holiday.date == date)
+    }
+}
+
+// Retrieves the date of Easter Sunday (using the anonymous Gregorian
+// algorithm)
+fn easter_sunday(year: i32) -> NaiveDate {
+    let (a, b, c) = (year % 19, year / 100, year % 100);
+    let (d, e) = (b / 4, b % 4);
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let n = h + l - 7 * m + 114;
+
+    NaiveDate::from_ymd_opt(year, (n / 31) as u32, (n % 31 + 1) as u32).unwrap()
+}
+
+// Retrieves the first weekday on or after the date provided (i.e., the
+// substitute day of a holiday falling at a weekend)
+fn substitute(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Sat => date + Duration::days(2),
+        Weekday::Sun => date + Duration::days(1),
+        _ => date,
+    }
+}
+
+fn first_monday(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Mon, 1).unwrap()
+}
+
+fn last_monday(year: i32, month: u32) -> NaiveDate {
+    let last = NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap() - Duration::days(1);
+
+    last - Duration::days(last.weekday().num_days_from_monday() as i64)
+}
+
+fn ymd((year, month, day): Ymd) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dates(year: i32) -> Vec<String> {
+        BankHoliday::in_year(year)
+            .iter()
+            .map(|holiday| holiday.date.format("%m-%d").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn bank_holidays_calculated() {
+        assert_eq!(
+            dates(2024),
+            ["01-01", "03-29", "04-01", "05-06", "05-27", "08-26", "12-25", "12-26"]
+        );
+
+        // Christmas Day on a Sunday (and New Year's Day on a Saturday)
+        assert_eq!(
+            dates(2022),
+            [
+                "01-03", "04-15", "04-18", "05-02", "06-02", "06-03", "08-29", "09-19", "12-26",
+                "12-27"
+            ]
+        );
+
+        // Christmas Day on a Saturday (and the coronation)
+        assert_eq!(dates(2021)[6..], ["12-27", "12-28"]);
+        assert_eq!(dates(2023).len(), 9);
+        assert_eq!(
+            BankHoliday::on(ymd((2023, 5, 8))).unwrap().name,
+            "Coronation of King Charles III"
+        );
+
+        // VE Day (the early May bank holiday moved to a Friday)
+        assert_eq!(dates(2020)[3], "05-08");
+        assert_eq!(easter_sunday(2025), ymd((2025, 4, 20)));
+    }
+}
@@ -0,0 +1,247 @@
+//! Homework tasks, set during a lesson and due at a later date.
+//!
+//! A [`Homework`] is attached to the [`Occurrence`] of the lesson during
+//! which it was set, and its [`Due`] date may be relative to the lessons of
+//! the same class and subject (e.g., "due next Maths lesson") -- as a
+//! result, its real due date is resolved against the timetable and the
+//! academic calendar (i.e., holidays and [`DayException`](crate::DayException)s
+//! are taken into account), rather than being hard-coded:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::homework::{Due, Homework};
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Subject, Term, Timetable};
+//! use timetableau::WeekCycle;
+//!
+//! let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+//! let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+//! let cycle = WeekCycle::new(date(4), calendar).unwrap();
+//!
+//! let mut timetable = Timetable::new();
+//! for slot in [timeslot!(W1MP1), timeslot!(W2TP3)] {
+//!     timetable.insert(
+//!         slot,
+//!         Activity::Lesson {
+//!             subject: Subject::new("Maths".to_string()).unwrap(),
+//!             class: Class::new("10A/Ma1".to_string()).unwrap(),
+//!             teacher: None,
+//!             location: "P101".parse().unwrap(),
+//!         },
+//!     );
+//! }
+//!
+//! // Set during Monday's lesson, due by the next Maths lesson (a week later)
+//! let lesson = &timetable.occurrences(&cycle, date(4))[0];
+//! let homework = Homework::new("Exercise 4B", lesson, Due::NextLesson).unwrap();
+//!
+//! let deadline = homework.deadline(&timetable, &cycle).unwrap();
+//! assert_eq!(deadline.date, date(12));
+//! ```
+
+use crate::{Activity, Class, EntryId, Occurrence, Subject, TimeSlot, Timetable, WeekCycle};
+use chrono::NaiveDate;
+
+/// When a [`Homework`] is due.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Due {
+    /// The homework is due by the next lesson of the same class and subject.
+    NextLesson,
+
+    /// The homework is due by the `n`th following lesson of the same class
+    /// and subject (i.e., `Lessons(1)` is the same as [`Due::NextLesson`]).
+    Lessons(u8),
+
+    /// The homework is due on the date provided.
+    Date(NaiveDate),
+}
+
+/// The resolved due date of a [`Homework`] (*see [`Homework::deadline`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deadline {
+    /// The date on which the homework is due.
+    pub date: NaiveDate,
+
+    /// The occurrence of the lesson by which the homework is due, or
+    /// [`None`] should the homework be due on a [fixed date](Due::Date).
+    pub lesson: Option<Occurrence>,
+}
+
+/// A homework task set during a lesson.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Homework {
+    /// The title of the task (e.g., `Exercise 4B`).
+    pub title: String,
+
+    /// The date on which the task was set.
+    pub set_on: NaiveDate,
+
+    /// The timeslot of the lesson during which the task was set.
+    pub slot: TimeSlot,
+
+    /// The entry of the lesson during which the task was set.
+    pub entry: EntryId,
+
+    /// The subject of the lesson during which the task was set.
+    pub subject: Subject,
+
+    /// The class to which the task was set.
+    pub class: Class,
+
+    /// When the task is due.
+    pub due: Due,
+}
+
+impl Homework {
+    /// Creates a new `Homework` set during the `lesson` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the occurrence is not a [lesson](Activity::Lesson) of the
+    /// timetable (e.g., it replaces a suspended period).
+    pub fn new(title: &str, lesson: &Occurrence, due: Due) -> Option<Self> {
+        let (subject, class) = match &lesson.activity {
+            Activity::Lesson { subject, class, .. } => (subject, class),
+            _ => return None,
+        };
+
+        Some(Self {
+            title: title.to_string(),
+            set_on: lesson.date,
+            slot: lesson.slot,
+            entry: lesson.entry?,
+            subject: subject.clone(),
+            class: class.clone(),
+            due,
+        })
+    }
+
+    /// Resolves the date on which the `Homework` is due, using the lessons
+    /// of the `timetable` and the calendar of the `cycle` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the homework is due by a lesson which does not take place
+    /// before the end of the last [`Term`](crate::Term) of the calendar.
+    pub fn deadline(&self, timetable: &Timetable, cycle: &WeekCycle) -> Option<Deadline> {
+        let mut remaining = match self.due {
+            Due::Date(date) => return Some(Deadline { date, lesson: None }),
+            Due::NextLesson => 1,
+            Due::Lessons(n) => n.max(1),
+        };
+
+        let last = cycle
+            .calendar()
+            .terms()
+            .iter()
+            .map(|term| term.end())
+            .max()?;
+
+        let mut date = self.set_on;
+
+        while date <= last {
+            for occurrence in timetable.occurrences(cycle, date) {
+                // Only lessons after the one during which the task was set
+                if date == self.set_on && occurrence.slot.period <= self.slot.period {
+                    continue;
+                }
+
+                let matches = match &occurrence.activity {
+                    Activity::Lesson { subject, class, .. } => {
+                        subject == &self.subject && class == &self.class
+                    }
+                    _ => false,
+                };
+
+                if matches {
+                    remaining -= 1;
+
+                    if remaining == 0 {
+                        return Some(Deadline {
+                            date,
+                            lesson: Some(occurrence),
+                        });
+                    }
+                }
+            }
+
+            date = date.succ_opt()?;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, DayException, Period, Term};
+
+    #[test]
+    fn homework_deadlines() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let mut calendar = AcademicCalendar::new(vec![Term::new(date(4), date(15)).unwrap()]);
+        calendar.add_exception(
+            date(5),
+            DayException::Suspended {
+                periods: vec![Period::Second],
+                activity: Activity::Miscellaneous("Mock Exams".to_string()),
+            },
+        );
+        let cycle = WeekCycle::new(date(4), calendar).unwrap();
+
+        let lesson = |subject: &str| Activity::Lesson {
+            subject: Subject::new(subject.to_string()).unwrap(),
+            class: Class::new("10A".to_string()).unwrap(),
+            teacher: None,
+            location: "H201".parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("Ma"));
+        timetable.insert(timeslot!(W1MP2), lesson("Ma"));
+        timetable.insert(timeslot!(W1MP3), lesson("En"));
+
+        // Suspended by the exception, so the homework is due a week later
+        timetable.insert(timeslot!(W1TP2), lesson("Ma"));
+        timetable.insert(timeslot!(W2TP2), lesson("Ma"));
+
+        let occurrences = timetable.occurrences(&cycle, date(4));
+        let homework = |due| Homework::new("Exercise 4B", &occurrences[0], due).unwrap();
+
+        assert_eq!(
+            homework(Due::NextLesson)
+                .deadline(&timetable, &cycle)
+                .unwrap()
+                .date,
+            date(4)
+        );
+
+        let deadline = homework(Due::Lessons(2))
+            .deadline(&timetable, &cycle)
+            .unwrap();
+        assert_eq!(deadline.date, date(12));
+        assert_eq!(deadline.lesson.unwrap().slot, timeslot!(W2TP2));
+
+        assert_eq!(homework(Due::Lessons(3)).deadline(&timetable, &cycle), None);
+        assert_eq!(
+            homework(Due::Date(date(8))).deadline(&timetable, &cycle),
+            Some(Deadline {
+                date: date(8),
+                lesson: None
+            })
+        );
+
+        // Only lessons can have homework set
+        assert!(Homework::new("Reading", &occurrences[2], Due::NextLesson).is_some());
+        assert_eq!(
+            Homework::new(
+                "Revision",
+                &timetable.occurrences(&cycle, date(5))[0],
+                Due::NextLesson
+            ),
+            None
+        );
+    }
+}
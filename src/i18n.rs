@@ -0,0 +1,383 @@
+//! Localisation of the verbose names displayed to persons (e.g., parents)
+//! who read another language better than English.
+//!
+//! Only verbose names are localised -- the compact room identifiers (e.g.,
+//! `H201`) and staff/class/subject codes are never translated, as they are
+//! printed on doors and shared between the schools' systems.
+//!
+//! Translations are provided by a [`Catalogue`], which maps stable keys onto
+//! translated text. Any key missing from a catalogue falls back to English,
+//! so a catalogue may be incomplete:
+//!
+//! | Key                                   | English                                |
+//! |---------------------------------------|----------------------------------------|
+//! | `block.howard`, ...                   | `Howard`                               |
+//! | `section.science`, ...                | `Science`                              |
+//! | `day.monday`, ...                     | `Monday`                               |
+//! | `floor.ground`                        | `Ground Floor`                         |
+//! | `floor.level`                         | `Floor {level}`                        |
+//! | `room.highfield.hall`                 | `Highfield Hall`                       |
+//! | `room.highfield.sports-hall`          | `Highfield Sports Hall`                |
+//! | `room.highfield.field`, ...           | `Highfield Field`                      |
+//! | `room.fearnhill.sports-hall`, ...     | `Fearnhill Sports Hall`                |
+//! | `location.highfield.classroom`        | `{block} Block, {floor}, Room {room}`  |
+//! | `location.fearnhill.classroom`        | `Fearnhill {section}, Room {room}`     |
+//! | `location.highfield.temporary`        | `Highfield Temporary Classroom {room}` |
+//! | `room.highfield.other`                | `Highfield {room}`                     |
+//! | `room.fearnhill.other`                | `Fearnhill {room}`                     |
+//! | `location.off-site`                   | `{name} (Off-Site)`                    |
+//! | `location.remote`                     | `Remote Lesson`                        |
+//! | `room.highfield.tbc`                  | `Highfield, Room To Be Confirmed`      |
+//! | `room.fearnhill.tbc`                  | `Fearnhill, Room To Be Confirmed`      |
+//! | `location.remote.labelled`            | `Remote Lesson ({label})`              |
+//! | `subject.<code>` (e.g., `subject.Ma`) | the subject's code                     |
+//!
+//! Placeholders (e.g., `{block}`) are substituted with the (localised) parts
+//! of the name, such that translations can reorder them:
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use timetableau::i18n::Localise;
+//! use timetableau::Location;
+//!
+//! let mut polish = HashMap::new();
+//! polish.insert("floor.level".to_string(), "Piętro {level}".to_string());
+//! polish.insert(
+//!     "location.highfield.classroom".to_string(),
+//!     "Budynek {block}, {floor}, Sala {room}".to_string(),
+//! );
+//!
+//! let room: Location = "H201".parse().unwrap();
+//!
+//! assert_eq!(room.localise(&polish), "Budynek Howard, Piętro 2, Sala 01");
+//! ```
+
+use crate::{
+    FearnhillRoom, FearnhillSection, HighfieldBlock, HighfieldFloor, HighfieldRoom, Location,
+    Subject,
+};
+use chrono::Weekday;
+use std::collections::{BTreeMap, HashMap};
+
+/// A catalogue of translations.
+///
+/// *See the [module](self) documentation for more information*.
+pub trait Catalogue {
+    /// Retrieves the translation of the `key` provided (or [`None`] should
+    /// the catalogue not contain a translation).
+    fn lookup(&self, key: &str) -> Option<&str>;
+}
+
+/// A [`Catalogue`] without any translations (i.e., every name is displayed
+/// in English).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct English;
+
+impl Catalogue for English {
+    fn lookup(&self, _: &str) -> Option<&str> {
+        None
+    }
+}
+
+impl Catalogue for HashMap<String, String> {
+    fn lookup(&self, key: &str) -> Option<&str> {
+        self.get(key).map(String::as_str)
+    }
+}
+
+impl Catalogue for BTreeMap<String, String> {
+    fn lookup(&self, key: &str) -> Option<&str> {
+        self.get(key).map(String::as_str)
+    }
+}
+
+/// A value which can be displayed in the language of a [`Catalogue`].
+pub trait Localise {
+    /// Retrieves the name of the value in the language of the `catalogue`.
+    fn localise<C>(&self, catalogue: &C) -> String
+    where
+        C: Catalogue + ?Sized;
+}
+
+impl Localise for HighfieldBlock {
+    fn localise<C>(&self, catalogue: &C) -> String
+    where
+        C: Catalogue + ?Sized,
+    {
+        let key = format!("block.{}", self.full_name().to_ascii_lowercase());
+
+        catalogue
+            .lookup(&key)
+            .unwrap_or(self.full_name())
+            .to_string()
+    }
+}
+
+impl Localise for FearnhillSection {
+    fn localise<C>(&self, catalogue: &C) -> String
+    where
+        C: Catalogue + ?Sized,
+    {
+        let key = format!("section.{}", self.full_name().to_ascii_lowercase());
+
+        catalogue
+            .lookup(&key)
+            .unwrap_or(self.full_name())
+            .to_string()
+    }
+}
+
+impl Localise for HighfieldFloor {
+    fn localise<C>(&self, catalogue: &C) -> String
+    where
+        C: Catalogue + ?Sized,
+    {
+        match self {
+            Self::Ground => text(catalogue, "floor.ground", &[]),
+            Self::Level(level) => text(catalogue, "floor.level", &[("level", level.to_string())]),
+        }
+    }
+}
+
+impl Localise for Weekday {
+    fn localise<C>(&self, catalogue: &C) -> String
+    where
+        C: Catalogue + ?Sized,
+    {
+        let key = match self {
+            Weekday::Mon => "day.monday",
+            Weekday::Tue => "day.tuesday",
+            Weekday::Wed => "day.wednesday",
+            Weekday::Thu => "day.thursday",
+            Weekday::Fri => "day.friday",
+            Weekday::Sat => "day.saturday",
+            Weekday::Sun => "day.sunday",
+        };
+
+        text(catalogue, key, &[])
+    }
+}
+
+impl Localise for Subject {
+    fn localise<C>(&self, catalogue: &C) -> String
+    where
+        C: Catalogue + ?Sized,
+    {
+        catalogue
+            .lookup(&format!("subject.{}", self.name()))
+            .map_or_else(|| self.name().clone(), str::to_string)
+    }
+}
+
+impl Localise for Location {
+    fn localise<C>(&self, catalogue: &C) -> String
+    where
+        C: Catalogue + ?Sized,
+    {
+        match self {
+            Self::Highfield(HighfieldRoom::Hall) => text(catalogue, "room.highfield.hall", &[]),
+            Self::Highfield(HighfieldRoom::SportsHall) => {
+                text(catalogue, "room.highfield.sports-hall", &[])
+            }
+            Self::Highfield(HighfieldRoom::Field) => text(catalogue, "room.highfield.field", &[]),
+            Self::Highfield(HighfieldRoom::AstroPitch) => {
+                text(catalogue, "room.highfield.astro-pitch", &[])
+            }
+            Self::Highfield(HighfieldRoom::TennisCourts) => {
+                text(catalogue, "room.highfield.tennis-courts", &[])
+            }
+            Self::Highfield(HighfieldRoom::Playground) => {
+                text(catalogue, "room.highfield.playground", &[])
+            }
+            Self::Highfield(HighfieldRoom::Classroom {
+                block,
+                floor,
+                discriminator,
+            }) => text(
+                catalogue,
+                "location.highfield.classroom",
+                &[
+                    ("block", block.localise(catalogue)),
+                    ("floor", floor.localise(catalogue)),
+                    ("room", format!("{:0>2}", discriminator.get())),
+                ],
+            ),
+            Self::Highfield(HighfieldRoom::Temporary(number)) => text(
+                catalogue,
+                "location.highfield.temporary",
+                &[("room", number.get().to_string())],
+            ),
+            Self::Fearnhill(FearnhillRoom::SportsHall) => {
+                text(catalogue, "room.fearnhill.sports-hall", &[])
+            }
+            Self::Fearnhill(FearnhillRoom::Gym) => text(catalogue, "room.fearnhill.gym", &[]),
+            Self::Fearnhill(FearnhillRoom::DanceStudio) => {
+                text(catalogue, "room.fearnhill.dance-studio", &[])
+            }
+            Self::Fearnhill(FearnhillRoom::DramaStudio) => {
+                text(catalogue, "room.fearnhill.drama-studio", &[])
+            }
+            Self::Fearnhill(FearnhillRoom::Field) => text(catalogue, "room.fearnhill.field", &[]),
+            Self::Fearnhill(FearnhillRoom::AstroPitch) => {
+                text(catalogue, "room.fearnhill.astro-pitch", &[])
+            }
+            Self::Fearnhill(FearnhillRoom::TennisCourts) => {
+                text(catalogue, "room.fearnhill.tennis-courts", &[])
+            }
+            Self::Fearnhill(FearnhillRoom::Playground) => {
+                text(catalogue, "room.fearnhill.playground", &[])
+            }
+            Self::Fearnhill(FearnhillRoom::Classroom {
+                section,
+                discriminator,
+            }) => text(
+                catalogue,
+                "location.fearnhill.classroom",
+                &[
+                    ("section", section.localise(catalogue)),
+                    ("room", discriminator.to_string()),
+                ],
+            ),
+            Self::Highfield(HighfieldRoom::OffSite(site))
+            | Self::Fearnhill(FearnhillRoom::OffSite(site)) => text(
+                catalogue,
+                "location.off-site",
+                &[("name", site.name().to_string())],
+            ),
+            Self::Highfield(HighfieldRoom::Remote(remote))
+            | Self::Fearnhill(FearnhillRoom::Remote(remote)) => match remote.label() {
+                Some(label) => text(
+                    catalogue,
+                    "location.remote.labelled",
+                    &[("label", label.to_string())],
+                ),
+                None => text(catalogue, "location.remote", &[]),
+            },
+            Self::Highfield(HighfieldRoom::ToBeConfirmed) => {
+                text(catalogue, "room.highfield.tbc", &[])
+            }
+            Self::Fearnhill(FearnhillRoom::ToBeConfirmed) => {
+                text(catalogue, "room.fearnhill.tbc", &[])
+            }
+            #[cfg(feature = "other-rooms")]
+            Self::Highfield(HighfieldRoom::Other(room)) => text(
+                catalogue,
+                "room.highfield.other",
+                &[("room", room.to_string())],
+            ),
+            #[cfg(feature = "other-rooms")]
+            Self::Fearnhill(FearnhillRoom::Other(room)) => text(
+                catalogue,
+                "room.fearnhill.other",
+                &[("room", room.to_string())],
+            ),
+        }
+    }
+}
+
+// Translate the `key` provided (falling back to English), substituting each
+// of the `arguments` into its placeholder
+fn text<C>(catalogue: &C, key: &str, arguments: &[(&str, String)]) -> String
+where
+    C: Catalogue + ?Sized,
+{
+    let mut text = catalogue
+        .lookup(key)
+        .or_else(|| english(key))
+        .unwrap_or(key)
+        .to_string();
+
+    for (name, value) in arguments {
+        text = text.replace(&format!("{{{}}}", name), value);
+    }
+
+    text
+}
+
+// The English text of each key
+fn english(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "day.monday" => "Monday",
+        "day.tuesday" => "Tuesday",
+        "day.wednesday" => "Wednesday",
+        "day.thursday" => "Thursday",
+        "day.friday" => "Friday",
+        "day.saturday" => "Saturday",
+        "day.sunday" => "Sunday",
+        "floor.ground" => "Ground Floor",
+        "floor.level" => "Floor {level}",
+        "room.highfield.hall" => "Highfield Hall",
+        "room.highfield.sports-hall" => "Highfield Sports Hall",
+        "room.highfield.field" => "Highfield Field",
+        "room.highfield.astro-pitch" => "Highfield Astro Pitch",
+        "room.highfield.tennis-courts" => "Highfield Tennis Courts",
+        "room.highfield.playground" => "Highfield Playground",
+        "room.fearnhill.sports-hall" => "Fearnhill Sports Hall",
+        "room.fearnhill.gym" => "Fearnhill Gym",
+        "room.fearnhill.dance-studio" => "Fearnhill Dance Studio",
+        "room.fearnhill.drama-studio" => "Fearnhill Drama Studio",
+        "room.fearnhill.field" => "Fearnhill Field",
+        "room.fearnhill.astro-pitch" => "Fearnhill Astro Pitch",
+        "room.fearnhill.tennis-courts" => "Fearnhill Tennis Courts",
+        "room.fearnhill.playground" => "Fearnhill Playground",
+        "location.highfield.classroom" => "{block} Block, {floor}, Room {room}",
+        "location.fearnhill.classroom" => "Fearnhill {section}, Room {room}",
+        "location.highfield.temporary" => "Highfield Temporary Classroom {room}",
+        "room.highfield.other" => "Highfield {room}",
+        "room.fearnhill.other" => "Fearnhill {room}",
+        "location.off-site" => "{name} (Off-Site)",
+        "location.remote" => "Remote Lesson",
+        "room.highfield.tbc" => "Highfield, Room To Be Confirmed",
+        "room.fearnhill.tbc" => "Fearnhill, Room To Be Confirmed",
+        "location.remote.labelled" => "Remote Lesson ({label})",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localise_english_matches_verbose_name() {
+        for code in [
+            "H201",
+            "UG03",
+            "Hall",
+            "Sports Hall",
+            "T1",
+            "Astro Pitch",
+            "FH Tennis Courts",
+            "FH S13",
+            "FH Mu2",
+            "FH Gym",
+            "FH @Stevenage Pool",
+            "Remote",
+            "FH Remote (Teams)",
+            "TBC",
+            "FH TBC",
+        ] {
+            let location: Location = code.parse().unwrap();
+
+            assert_eq!(location.localise(&English), location.verbose_name());
+        }
+    }
+
+    #[test]
+    fn localise_fallback() {
+        let mut urdu = HashMap::new();
+        urdu.insert("day.monday".to_string(), "پیر".to_string());
+        urdu.insert("subject.Ma".to_string(), "ریاضی".to_string());
+
+        assert_eq!(Weekday::Mon.localise(&urdu), "پیر");
+        assert_eq!(Weekday::Tue.localise(&urdu), "Tuesday");
+        assert_eq!(
+            Subject::new("Ma".to_string()).unwrap().localise(&urdu),
+            "ریاضی"
+        );
+        assert_eq!(
+            Subject::new("En".to_string()).unwrap().localise(&urdu),
+            "En"
+        );
+    }
+}
@@ -0,0 +1,446 @@
+//! Room identifiers in the conventions of other consortium tools.
+//!
+//! The tools shared with the consortium's other schools identify rooms using
+//! their own conventions (e.g., `HF-H2-01` or `FHS012`, rather than `H201` or
+//! `FH S12`). Each convention is an [`IdentifierScheme`], which parses and
+//! formats identifiers in its own convention whilst the rooms themselves
+//! remain [`Location`]s -- the canonical identifiers are unaffected.
+//!
+//! A [`SchemeRegistry`] looks schemes up by their name, and contains the
+//! built-in schemes by default:
+//!
+//! | Scheme                        | Highfield classroom | Fearnhill classroom | Temporary classroom |
+//! |-------------------------------|---------------------|---------------------|---------------------|
+//! | [`Canonical`] (`canonical`)   | `H201`              | `FH S12`            | `T7`                |
+//! | [`Hyphenated`] (`hyphenated`) | `HF-H2-01`          | `FH-S-12`           | `HF-T-07`           |
+//! | [`Packed`] (`packed`)         | `HFH201`            | `FHS012`            | `HFT07`             |
+//!
+//! ```
+//! use timetableau::identifiers::SchemeRegistry;
+//! use timetableau::Location;
+//!
+//! let registry = SchemeRegistry::new();
+//! let room: Location = "FH S12".parse().unwrap();
+//!
+//! assert_eq!(registry.format("packed", &room).unwrap(), "FHS012");
+//! assert_eq!(registry.parse("hyphenated", "FH-S-12").unwrap(), room);
+//! assert_eq!(registry.translate("hyphenated", "packed", "HF-H2-01").unwrap(), "HFH201");
+//! ```
+//!
+//! # Remarks
+//!
+//! Only the classrooms (including the temporary classrooms) of the schools
+//! are represented by the [`Hyphenated`] and [`Packed`] schemes -- the other
+//! rooms (e.g., halls, or off-site locations) must be exchanged using their
+//! [`Canonical`] identifiers.
+
+use crate::{FearnhillRoom, HighfieldRoom, Location};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// A convention for the identifiers of rooms.
+///
+/// *See the [module](self) documentation for more information*.
+pub trait IdentifierScheme {
+    /// Retrieves the name of the scheme (e.g., `packed`), by which it is
+    /// looked up in a [`SchemeRegistry`].
+    fn name(&self) -> &str;
+
+    /// Parses a room from its `code` in the scheme.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should the code not be a valid identifier in the scheme.
+    fn parse(&self, code: &str) -> Option<Location>;
+
+    /// Formats the identifier of the `location` in the scheme.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should the location not be represented by the scheme.
+    fn format(&self, location: &Location) -> Option<String>;
+}
+
+/// The canonical identifiers of rooms (e.g., `H201` or `FH S12`) -- *see the
+/// [`crate`] documentation for more information*.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Canonical;
+
+impl IdentifierScheme for Canonical {
+    fn name(&self) -> &str {
+        "canonical"
+    }
+
+    fn parse(&self, code: &str) -> Option<Location> {
+        code.parse().ok()
+    }
+
+    fn format(&self, location: &Location) -> Option<String> {
+        Some(location.to_string())
+    }
+}
+
+/// Hyphen-separated identifiers prefixed by the code of the school (e.g.,
+/// `HF-H2-01` or `FH-S-12`), with the room numbers padded to two digits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Hyphenated;
+
+impl IdentifierScheme for Hyphenated {
+    fn name(&self) -> &str {
+        "hyphenated"
+    }
+
+    fn parse(&self, code: &str) -> Option<Location> {
+        let mut parts = code.split('-');
+        let (school, area, number) = (parts.next()?, parts.next()?, parts.next()?);
+
+        if parts.next().is_some() || !is_digits(number, 2) {
+            return None;
+        }
+
+        let number: u8 = number.parse().ok()?;
+
+        match (school, area) {
+            ("HF", "T") => format!("T{}", number).parse().ok(),
+            ("HF", _) if area.len() == 2 => format!("{}{:0>2}", area, number).parse().ok(),
+            ("FH", _) => format!("FH {}{}", area, number).parse().ok(),
+            _ => None,
+        }
+        .filter(is_classroom)
+    }
+
+    fn format(&self, location: &Location) -> Option<String> {
+        match location {
+            Location::Highfield(HighfieldRoom::Classroom {
+                block,
+                floor,
+                discriminator,
+            }) => Some(format!("HF-{}{}-{:0>2}", block, floor, discriminator.get())),
+            Location::Highfield(HighfieldRoom::Temporary(number)) => {
+                Some(format!("HF-T-{:0>2}", number.get()))
+            }
+            Location::Fearnhill(FearnhillRoom::Classroom {
+                section,
+                discriminator,
+            }) => Some(format!("FH-{}-{:0>2}", section, discriminator.get())),
+            _ => None,
+        }
+    }
+}
+
+/// Identifiers without separators, prefixed by the code of the school (e.g.,
+/// `HFH201` or `FHS012`), with the room numbers of Fearnhill padded to three
+/// digits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Packed;
+
+impl IdentifierScheme for Packed {
+    fn name(&self) -> &str {
+        "packed"
+    }
+
+    fn parse(&self, code: &str) -> Option<Location> {
+        if !code.is_ascii() {
+            return None;
+        }
+
+        if let Some(room) = code.strip_prefix("HF") {
+            // Either a temporary classroom (`T07`), or a block, floor and
+            // room number (`H201`)
+            let (area, number) = room.split_at(room.len().checked_sub(2)?);
+
+            if !is_digits(number, 2) || area.is_empty() || area.len() > 2 {
+                return None;
+            }
+
+            return match area {
+                "T" => format!("T{}", number.parse::<u8>().ok()?).parse().ok(),
+                _ if area.len() == 2 => room.parse().ok(),
+                _ => None,
+            }
+            .filter(is_classroom);
+        }
+
+        let room = code.strip_prefix("FH")?;
+        let (section, number) = room.split_at(room.len().checked_sub(3)?);
+
+        if !is_digits(number, 3) || section.is_empty() {
+            return None;
+        }
+
+        format!("FH {}{}", section, number.parse::<u8>().ok()?)
+            .parse()
+            .ok()
+            .filter(is_classroom)
+    }
+
+    fn format(&self, location: &Location) -> Option<String> {
+        match location {
+            Location::Highfield(HighfieldRoom::Classroom {
+                block,
+                floor,
+                discriminator,
+            }) => Some(format!("HF{}{}{:0>2}", block, floor, discriminator.get())),
+            Location::Highfield(HighfieldRoom::Temporary(number)) => {
+                Some(format!("HFT{:0>2}", number.get()))
+            }
+            Location::Fearnhill(FearnhillRoom::Classroom {
+                section,
+                discriminator,
+            }) => Some(format!("FH{}{:0>3}", section, discriminator.get())),
+            _ => None,
+        }
+    }
+}
+
+/// The [`IdentifierScheme`]s available to parse and format identifiers, by
+/// name.
+///
+/// *See the [module](self) documentation for more information*.
+pub struct SchemeRegistry {
+    schemes: Vec<Box<dyn IdentifierScheme>>,
+}
+
+impl Default for SchemeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SchemeRegistry {
+    /// Creates a new `SchemeRegistry` containing the built-in schemes (i.e.,
+    /// [`Canonical`], [`Hyphenated`], and [`Packed`]).
+    pub fn new() -> Self {
+        Self {
+            schemes: vec![Box::new(Canonical), Box::new(Hyphenated), Box::new(Packed)],
+        }
+    }
+
+    /// Adds a `scheme` to the `SchemeRegistry`, replacing any scheme of the
+    /// same name.
+    pub fn scheme<S>(mut self, scheme: S) -> Self
+    where
+        S: IdentifierScheme + 'static,
+    {
+        self.schemes
+            .retain(|existing| existing.name() != scheme.name());
+        self.schemes.push(Box::new(scheme));
+        self
+    }
+
+    /// Retrieves the scheme with the `name` provided.
+    pub fn get(&self, name: &str) -> Option<&dyn IdentifierScheme> {
+        self.schemes
+            .iter()
+            .find(|scheme| scheme.name() == name)
+            .map(|scheme| scheme.as_ref())
+    }
+
+    /// Retrieves the names of the schemes of the `SchemeRegistry` (in the
+    /// order in which they were added).
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.schemes.iter().map(|scheme| scheme.name())
+    }
+
+    /// Parses a room from its `code` in the scheme named `scheme`.
+    ///
+    /// # Errors
+    ///
+    /// - [`IdentifierError::UnknownScheme`] is returned should the
+    ///   `SchemeRegistry` not contain the scheme.
+    /// - [`IdentifierError::InvalidCode`] is returned should the code not be
+    ///   a valid identifier in the scheme.
+    pub fn parse(&self, scheme: &str, code: &str) -> Result<Location, IdentifierError> {
+        self.find(scheme)?
+            .parse(code)
+            .ok_or_else(|| IdentifierError::InvalidCode {
+                scheme: scheme.to_string(),
+                code: code.to_string(),
+            })
+    }
+
+    /// Formats the identifier of the `location` in the scheme named
+    /// `scheme`.
+    ///
+    /// # Errors
+    ///
+    /// - [`IdentifierError::UnknownScheme`] is returned should the
+    ///   `SchemeRegistry` not contain the scheme.
+    /// - [`IdentifierError::Unrepresentable`] is returned should the
+    ///   location not be represented by the scheme.
+    pub fn format(&self, scheme: &str, location: &Location) -> Result<String, IdentifierError> {
+        self.find(scheme)?
+            .format(location)
+            .ok_or_else(|| IdentifierError::Unrepresentable {
+                scheme: scheme.to_string(),
+                location: *location,
+            })
+    }
+
+    /// Translates a `code` from the scheme named `from` into the scheme
+    /// named `to` (e.g., from `HF-H2-01` into `HFH201`).
+    ///
+    /// # Errors
+    ///
+    /// Any error of [`Self::parse`] or [`Self::format`] is returned.
+    pub fn translate(&self, from: &str, to: &str, code: &str) -> Result<String, IdentifierError> {
+        self.format(to, &self.parse(from, code)?)
+    }
+
+    fn find(&self, name: &str) -> Result<&dyn IdentifierScheme, IdentifierError> {
+        self.get(name)
+            .ok_or_else(|| IdentifierError::UnknownScheme(name.to_string()))
+    }
+}
+
+/// An error returned when an identifier cannot be parsed or formatted using
+/// a [`SchemeRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentifierError {
+    /// No scheme of the registry has the name provided.
+    UnknownScheme(String),
+
+    /// The code is not a valid identifier in the scheme.
+    InvalidCode {
+        /// The name of the scheme.
+        scheme: String,
+
+        /// The code which could not be parsed.
+        code: String,
+    },
+
+    /// The location has no identifier in the scheme.
+    Unrepresentable {
+        /// The name of the scheme.
+        scheme: String,
+
+        /// The location which could not be formatted.
+        location: Location,
+    },
+}
+
+impl Display for IdentifierError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownScheme(name) => write!(f, "unknown identifier scheme `{}`", name),
+            Self::InvalidCode { scheme, code } => {
+                write!(f, "`{}` is not a valid {} identifier", code, scheme)
+            }
+            Self::Unrepresentable { scheme, location } => {
+                write!(f, "{} has no {} identifier", location, scheme)
+            }
+        }
+    }
+}
+
+impl Error for IdentifierError {}
+
+// Whether the string consists of exactly `count` ASCII digits
+fn is_digits(s: &str, count: usize) -> bool {
+    s.len() == count && s.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+// Whether the location is a classroom (the only rooms represented by the
+// non-canonical built-in schemes)
+fn is_classroom(location: &Location) -> bool {
+    matches!(
+        location,
+        Location::Highfield(HighfieldRoom::Classroom { .. } | HighfieldRoom::Temporary(_))
+            | Location::Fearnhill(FearnhillRoom::Classroom { .. })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A scheme which prefixes canonical identifiers with `ROOM:`
+    struct Prefixed;
+
+    impl IdentifierScheme for Prefixed {
+        fn name(&self) -> &str {
+            "prefixed"
+        }
+
+        fn parse(&self, code: &str) -> Option<Location> {
+            code.strip_prefix("ROOM:")?.parse().ok()
+        }
+
+        fn format(&self, location: &Location) -> Option<String> {
+            Some(format!("ROOM:{}", location))
+        }
+    }
+
+    #[test]
+    fn identifier_schemes() {
+        let registry = SchemeRegistry::new().scheme(Prefixed);
+        let room = |code: &str| code.parse::<Location>().unwrap();
+
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["canonical", "hyphenated", "packed", "prefixed"]
+        );
+
+        // Every built-in scheme round-trips the classrooms
+        for code in ["H201", "HG01", "U312", "T7", "FH S12", "FH Mu3", "FH I99"] {
+            for scheme in ["canonical", "hyphenated", "packed", "prefixed"] {
+                let formatted = registry.format(scheme, &room(code)).unwrap();
+                assert_eq!(registry.parse(scheme, &formatted), Ok(room(code)));
+            }
+        }
+
+        assert_eq!(
+            registry.format("hyphenated", &room("HG01")).unwrap(),
+            "HF-HG-01"
+        );
+        assert_eq!(
+            registry.format("packed", &room("FH Mu3")).unwrap(),
+            "FHMu003"
+        );
+        assert_eq!(registry.format("packed", &room("T7")).unwrap(), "HFT07");
+        assert_eq!(
+            registry.format("prefixed", &room("Hall")).unwrap(),
+            "ROOM:Hall"
+        );
+
+        // Invalid (or unpadded) identifiers are rejected
+        for code in [
+            "HF-H2-1",
+            "HF-H2-01-02",
+            "HF-Hall-01",
+            "XX-H2-01",
+            "FH-S-00",
+            "HF-X2-01",
+        ] {
+            assert!(matches!(
+                registry.parse("hyphenated", code),
+                Err(IdentifierError::InvalidCode { .. })
+            ));
+        }
+        for code in ["FHS12", "HFH2001", "HF01", "FH001", "HFA201"] {
+            assert!(registry.parse("packed", code).is_err(), "{}", code);
+        }
+
+        assert_eq!(
+            registry
+                .format("packed", &room("Hall"))
+                .unwrap_err()
+                .to_string(),
+            "Hall has no packed identifier"
+        );
+        assert_eq!(
+            registry.parse("legacy", "H201"),
+            Err(IdentifierError::UnknownScheme("legacy".to_string()))
+        );
+        assert_eq!(
+            registry
+                .translate("packed", "hyphenated", "FHS012")
+                .unwrap(),
+            "FH-S-12"
+        );
+
+        // A scheme replaces any existing scheme of the same name
+        let registry = registry.scheme(Prefixed).scheme(Prefixed);
+        assert_eq!(registry.names().count(), 4);
+    }
+}
@@ -0,0 +1,373 @@
+//! Imports the JSON timetable payloads produced by parent/pupil apps (e.g.,
+//! EduLink One).
+//!
+//! The payload must take the following shape (the payload may also be
+//! wrapped in a `result` object, as returned by the apps' APIs):
+//!
+//! ```json
+//! {
+//!     "weeks": [
+//!         {
+//!             "name": "Week A",
+//!             "days": [
+//!                 {
+//!                     "name": "Monday",
+//!                     "periods": [{ "id": "7", "name": "1" }],
+//!                     "lessons": [
+//!                         {
+//!                             "period_id": "7",
+//!                             "teaching_group": { "name": "10A/Ma1", "subject": "Maths" },
+//!                             "teacher": "JSM",
+//!                             "room": { "name": "H201" }
+//!                         }
+//!                     ]
+//!                 }
+//!             ]
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! * The name of each week must be a valid week (e.g., `Week A`, `Week 1`).
+//!
+//! * The period of each lesson is either given directly (as `period`, e.g.,
+//!   `"period": 1`) or by referencing one of the day's `periods` (using
+//!   `period_id`), whose `name` is the number of the period.
+//!
+//! * The subject of each lesson is taken from `subject` or, should it be
+//!   missing, from `teaching_group.subject`. The class is taken from
+//!   `teaching_group.name` or, should it be missing, the teacher (as is
+//!   common for student timetables -- *see the [`crate`] documentation for
+//!   more information*).
+//!
+//! * Both `teacher` and `room` may either be a string or an object with a
+//!   `name` (or, for teachers, a `code`).
+//!
+//! Unknown fields are ignored, such that the payloads of newer versions of
+//! the apps can still be imported.
+
+use crate::fuzzy::Resolver;
+use crate::import::{self, Import, ImportIssue};
+use crate::json::{self, JsonValue};
+use crate::timeslot::{parse_day, parse_period, parse_week};
+use crate::{
+    Activity, Class, Location, ParseOptions, Period, Subject, Teacher, TimeSlot, Timetable,
+};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// An error which prevents a payload from being imported at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonAppError {
+    /// The payload is not valid JSON.
+    Syntax {
+        /// The line on which the syntax error occurred.
+        line: usize,
+    },
+
+    /// The payload does not contain a `weeks` array.
+    MissingWeeks,
+}
+
+impl Display for JsonAppError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax { line } => write!(f, "invalid JSON on line {}", line),
+            Self::MissingWeeks => f.write_str("the payload does not contain a `weeks` array"),
+        }
+    }
+}
+
+impl Error for JsonAppError {}
+
+/// Imports the JSON timetable payloads of parent/pupil apps.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonAppImporter {
+    aliases: HashMap<String, Location>,
+    options: ParseOptions,
+    resolver: Option<Resolver>,
+}
+
+impl JsonAppImporter {
+    /// Creates a new `JsonAppImporter` with no aliases.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the room identifier `code` to `location`.
+    ///
+    /// *See [`SimsImporter::alias`](crate::import::sims::SimsImporter::alias)
+    /// for more information*.
+    pub fn alias(mut self, code: &str, location: Location) -> Self {
+        self.aliases.insert(code.to_string(), location);
+        self
+    }
+
+    /// Sets the options used to parse room identifiers.
+    ///
+    /// *See [`SimsImporter::options`](crate::import::sims::SimsImporter::options)
+    /// for more information*.
+    pub fn options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the resolver used to correct mistyped subjects and teachers.
+    ///
+    /// *See [`SimsImporter::resolver`](crate::import::sims::SimsImporter::resolver)
+    /// for more information*.
+    pub fn resolver(mut self, resolver: Resolver) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    /// Imports the payload provided.
+    ///
+    /// # Errors
+    ///
+    /// An error is only returned should the payload be impossible to import
+    /// (e.g., if it is not valid JSON) -- problems with individual weeks,
+    /// days, or lessons are reported as [`ImportIssue`]s instead.
+    pub fn import(&self, payload: &str) -> Result<Import, JsonAppError> {
+        let root =
+            json::parse(payload).map_err(|error| JsonAppError::Syntax { line: error.line })?;
+        let root = root.get("result").unwrap_or(&root);
+
+        let weeks = root
+            .get("weeks")
+            .and_then(JsonValue::as_array)
+            .ok_or(JsonAppError::MissingWeeks)?;
+
+        let mut timetable = Timetable::new();
+        let mut issues = Vec::new();
+
+        for week_value in weeks {
+            let line = week_value.line().unwrap_or(0);
+            let name = week_value.get("name").and_then(text).unwrap_or_default();
+
+            let week = match parse_week(&name) {
+                Some(week) => week,
+                None => {
+                    issues.push(ImportIssue::InvalidRow {
+                        line,
+                        reason: format!("invalid week `{}`", name),
+                    });
+                    continue;
+                }
+            };
+
+            for day_value in week_value
+                .get("days")
+                .and_then(JsonValue::as_array)
+                .unwrap_or_default()
+            {
+                let line = day_value.line().unwrap_or(0);
+                let name = day_value.get("name").and_then(text).unwrap_or_default();
+
+                let day = match parse_day(&name) {
+                    Some(day) => day,
+                    None => {
+                        issues.push(ImportIssue::InvalidRow {
+                            line,
+                            reason: format!("invalid day `{}`", name),
+                        });
+                        continue;
+                    }
+                };
+
+                let periods = day_value
+                    .get("periods")
+                    .and_then(JsonValue::as_array)
+                    .unwrap_or_default();
+
+                for lesson in day_value
+                    .get("lessons")
+                    .and_then(JsonValue::as_array)
+                    .unwrap_or_default()
+                {
+                    match self.lesson(lesson, periods) {
+                        Ok((period, mut activity)) => {
+                            let line = lesson.line().unwrap_or(0);
+
+                            if let Some(resolver) = &self.resolver {
+                                import::correct(resolver, line, &mut activity, &mut issues);
+                            }
+
+                            if let Some(&location) = activity.location() {
+                                if !self.options.is_known(&location) {
+                                    issues.push(ImportIssue::NonexistentRoom { line, location });
+                                }
+                            }
+
+                            timetable.insert(TimeSlot { week, day, period }, activity);
+                        }
+                        Err(issue) => issues.push(issue),
+                    }
+                }
+            }
+        }
+
+        Ok(Import { timetable, issues })
+    }
+
+    // Convert a single lesson of the payload into an activity
+    fn lesson(
+        &self,
+        lesson: &JsonValue,
+        periods: &[JsonValue],
+    ) -> Result<(Period, Activity), ImportIssue> {
+        let line = lesson.line().unwrap_or(0);
+        let invalid = |reason: String| ImportIssue::InvalidRow { line, reason };
+
+        // Resolve the period either directly or via the day's periods
+        let period_name = match lesson.get("period").and_then(text) {
+            Some(period) => period,
+            None => {
+                let id = lesson.get("period_id").and_then(text).unwrap_or_default();
+
+                periods
+                    .iter()
+                    .find(|period| period.get("id").and_then(text).as_deref() == Some(&id))
+                    .and_then(|period| period.get("name"))
+                    .and_then(text)
+                    .ok_or_else(|| invalid(format!("unknown period `{}`", id)))?
+            }
+        };
+        let period = parse_period(&period_name)
+            .ok_or_else(|| invalid(format!("invalid period `{}`", period_name)))?;
+
+        let group = lesson.get("teaching_group");
+
+        let subject_name = lesson
+            .get("subject")
+            .or_else(|| group.and_then(|group| group.get("subject")))
+            .and_then(named)
+            .unwrap_or_default();
+        let subject =
+            Subject::new(subject_name.clone()).map_err(|error| invalid(error.to_string()))?;
+
+        let teacher_code = lesson.get("teacher").and_then(|teacher| {
+            teacher
+                .get("code")
+                .and_then(text)
+                .or_else(|| named(teacher))
+        });
+        let teacher = match &teacher_code {
+            Some(code) => {
+                Some(Teacher::new(code.clone()).map_err(|error| invalid(error.to_string()))?)
+            }
+            None => None,
+        };
+
+        let reference = group
+            .and_then(|group| group.get("name"))
+            .and_then(text)
+            .or(teacher_code)
+            .unwrap_or_default();
+        let class = Class::new(reference.clone()).map_err(|error| invalid(error.to_string()))?;
+
+        let code = lesson.get("room").and_then(named).unwrap_or_default();
+        let location = match self.aliases.get(&code) {
+            Some(location) => self.options.check(*location),
+            None => self.options.parse_location(&code),
+        };
+        let location = location
+            .ok()
+            .ok_or(ImportIssue::UnmappedRoom { line, code })?;
+
+        Ok((
+            period,
+            Activity::Lesson {
+                subject,
+                class,
+                teacher,
+                location,
+            },
+        ))
+    }
+}
+
+// Retrieve the textual representation of a value (the apps are inconsistent
+// as to whether identifiers are strings or numbers)
+fn text(value: &JsonValue) -> Option<String> {
+    value.as_str().map(|s| s.trim().to_string()).or_else(|| {
+        value
+            .as_f64()
+            .filter(|n| n.fract() == 0.0)
+            .map(|n| format!("{}", n as i64))
+    })
+}
+
+// Retrieve either the textual representation of a value, or the name of an
+// object
+fn named(value: &JsonValue) -> Option<String> {
+    text(value).or_else(|| value.get("name").and_then(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeslot;
+
+    const PAYLOAD: &str = r#"{
+        "result": {
+            "success": true,
+            "weeks": [
+                {
+                    "name": "Week B",
+                    "is_current": false,
+                    "days": [
+                        {
+                            "name": "Tuesday",
+                            "periods": [{ "id": "12", "name": "4" }],
+                            "lessons": [
+                                {
+                                    "period_id": "12",
+                                    "teaching_group": { "id": 3, "name": "10A/Ma1", "subject": "Maths" },
+                                    "teacher": { "title": "Mr", "name": "JSM" },
+                                    "room": { "id": 9, "name": "H201" }
+                                },
+                                {
+                                    "period": 5,
+                                    "subject": "Physics",
+                                    "teacher": "ABC",
+                                    "room": "Lab Z"
+                                }
+                            ]
+                        }
+                    ]
+                },
+                { "name": "Holiday", "days": [] }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn json_app_import() {
+        let import = JsonAppImporter::new().import(PAYLOAD).unwrap();
+
+        assert_eq!(import.timetable.len(), 1);
+        assert_eq!(import.unmapped_rooms(), vec!["Lab Z"]);
+        assert_eq!(import.issues.len(), 2);
+
+        let (_, entry) = import.timetable.at(timeslot!(W2TP4)).next().unwrap();
+
+        assert_eq!(entry.activity.to_string(), "Maths 10A/Ma1 H201");
+        assert_eq!(entry.activity.teacher().unwrap().code(), "JSM");
+    }
+
+    #[test]
+    fn json_app_invalid() {
+        assert_eq!(
+            JsonAppImporter::new().import("{\n\"weeks\": [}"),
+            Err(JsonAppError::Syntax { line: 2 })
+        );
+        assert_eq!(
+            JsonAppImporter::new().import("{}"),
+            Err(JsonAppError::MissingWeeks)
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Importers which convert timetables exported by other applications into
+//! [`Timetable`](crate::Timetable)s.
+//!
+//! Importers never silently drop data -- anything an importer is unable to
+//! understand is reported as an [`ImportIssue`].
+
+use crate::fuzzy::Resolver;
+use crate::{Activity, Location, Subject, Teacher, Timetable};
+use std::fmt::{self, Display, Formatter};
+
+pub mod json_app;
+pub mod sims;
+
+/// The result of importing a timetable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    /// The timetable containing everything which was successfully imported.
+    pub timetable: Timetable,
+
+    /// Every problem encountered whilst importing the timetable (i.e.,
+    /// everything which was not imported and the reason it was not
+    /// imported, as well as anything imported which should be reviewed).
+    pub issues: Vec<ImportIssue>,
+}
+
+impl Import {
+    /// Retrieves every distinct room identifier which could not be mapped to
+    /// a [`Location`](crate::Location) (in the order in which they were first
+    /// encountered).
+    pub fn unmapped_rooms(&self) -> Vec<&str> {
+        let mut rooms: Vec<&str> = Vec::new();
+
+        for issue in &self.issues {
+            if let ImportIssue::UnmappedRoom { code, .. } = issue {
+                if !rooms.contains(&code.as_str()) {
+                    rooms.push(code);
+                }
+            }
+        }
+
+        rooms
+    }
+}
+
+/// A problem encountered whilst importing a timetable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportIssue {
+    /// A room identifier could not be mapped to a
+    /// [`Location`](crate::Location) -- the row containing the room was not
+    /// imported.
+    UnmappedRoom {
+        /// The line on which the room identifier was found.
+        line: usize,
+
+        /// The room identifier which could not be mapped.
+        code: String,
+    },
+
+    /// A room is not in the registry of the importer's flagging
+    /// [`ParseOptions`](crate::ParseOptions) (e.g., a mistyped room which
+    /// does not exist) -- unlike the other issues, the row containing the
+    /// room was still imported.
+    NonexistentRoom {
+        /// The line on which the room identifier was found.
+        line: usize,
+
+        /// The room which does not exist.
+        location: Location,
+    },
+
+    /// The subject or teacher of a lesson was mistyped, and was corrected by
+    /// the importer's [`Resolver`] (*see
+    /// [`SimsImporter::resolver`](sims::SimsImporter::resolver)*) -- the row
+    /// was imported with the correction.
+    Corrected {
+        /// The line on which the mistyped name was found.
+        line: usize,
+
+        /// The name as it was found.
+        from: String,
+
+        /// The name to which it was corrected.
+        to: String,
+    },
+
+    /// A row (or record) could not be understood -- the row was not
+    /// imported.
+    InvalidRow {
+        /// The line on which the row starts.
+        line: usize,
+
+        /// A description of the problem.
+        reason: String,
+    },
+}
+
+impl Display for ImportIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnmappedRoom { line, code } => {
+                write!(f, "line {}: unknown room identifier `{}`", line, code)
+            }
+            Self::NonexistentRoom { line, location } => {
+                write!(f, "line {}: room `{}` does not exist", line, location)
+            }
+            Self::Corrected { line, from, to } => {
+                write!(f, "line {}: corrected `{}` to `{}`", line, from, to)
+            }
+            Self::InvalidRow { line, reason } => write!(f, "line {}: {}", line, reason),
+        }
+    }
+}
+
+// Correct the mistyped subject and teacher of an imported lesson, reporting
+// each correction
+pub(crate) fn correct(
+    resolver: &Resolver,
+    line: usize,
+    activity: &mut Activity,
+    issues: &mut Vec<ImportIssue>,
+) {
+    let Activity::Lesson {
+        subject, teacher, ..
+    } = activity
+    else {
+        return;
+    };
+
+    if let Some(name) = resolver.resolve_subject(subject.name()) {
+        if name != subject.name() {
+            if let Ok(corrected) = Subject::new(name.to_string()) {
+                issues.push(ImportIssue::Corrected {
+                    line,
+                    from: subject.name().clone(),
+                    to: name.to_string(),
+                });
+                *subject = corrected;
+            }
+        }
+    }
+
+    if let Some(teacher) = teacher {
+        if let Some(code) = resolver.resolve_teacher(teacher.code()) {
+            if code != teacher.code() {
+                if let Ok(corrected) = Teacher::new(code.to_string()) {
+                    issues.push(ImportIssue::Corrected {
+                        line,
+                        from: teacher.code().clone(),
+                        to: code.to_string(),
+                    });
+                    *teacher = corrected;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,389 @@
+//! Imports the CSV timetable exports produced by SIMS (and similar management
+//! information systems).
+//!
+//! The export must contain a header row naming (at least) the following
+//! columns, in any order (the names are case-insensitive and may contain
+//! spaces or underscores):
+//!
+//! | Column      | Example   | Remarks                                       |
+//! |-------------|-----------|-----------------------------------------------|
+//! | `Class`     | `10A/Ma1` |                                               |
+//! | `Staff`     | `JSM`     | Also accepted as `Staff Code` or `Teacher`.   |
+//! | `Room`      | `H201`    | Also accepted as `Room Code`.                 |
+//! | `Day`       | `Tue`     | A day name or its WDF notation letter.        |
+//! | `Period`    | `4`       | Also accepted in the form `P4`.               |
+//! | `Week`      | `1`       | Also accepted as `A`/`B` or `W1`/`W2`.        |
+//!
+//! A `Subject` column may also be provided -- should it be missing, the
+//! subject is derived from the class code (e.g., `Ma` for `10A/Ma1`). Any
+//! other columns are ignored.
+
+use crate::csv;
+use crate::fuzzy::Resolver;
+use crate::import::{self, Import, ImportIssue};
+use crate::timeslot::{parse_day, parse_period, parse_week};
+use crate::{Activity, Class, Location, ParseOptions, Subject, Teacher, TimeSlot, Timetable};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// An error which prevents a SIMS export from being imported at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimsError {
+    /// The export does not contain a header row.
+    Empty,
+
+    /// The header row does not contain a required column.
+    MissingColumn(&'static str),
+}
+
+impl Display for SimsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("the export does not contain a header row"),
+            Self::MissingColumn(column) => {
+                write!(f, "the export does not contain a `{}` column", column)
+            }
+        }
+    }
+}
+
+impl Error for SimsError {}
+
+/// Imports SIMS timetable exports.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimsImporter {
+    aliases: HashMap<String, Location>,
+    options: ParseOptions,
+    resolver: Option<Resolver>,
+}
+
+impl SimsImporter {
+    /// Creates a new `SimsImporter` with no aliases.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the room identifier `code` to `location`.
+    ///
+    /// This allows legacy room identifiers (which are still used by the
+    /// management information system, but are no longer a part of either
+    /// school's room numbering scheme) to be imported -- aliases take
+    /// precedence over the room numbering schemes.
+    pub fn alias(mut self, code: &str, location: Location) -> Self {
+        self.aliases.insert(code.to_string(), location);
+        self
+    }
+
+    /// Sets the options used to parse room identifiers.
+    ///
+    /// Should the options be [strict](ParseOptions::strict), any room which
+    /// is not in their registry (including the target of an alias) is
+    /// reported as an [`ImportIssue::UnmappedRoom`]. With the `other-rooms`
+    /// feature, rooms which are not modelled by either school's room
+    /// numbering scheme can instead be retained (*see
+    /// `ParseOptions::other_rooms`*). Should the options be
+    /// [flagging](ParseOptions::flagging), such rooms are imported but also
+    /// reported as an [`ImportIssue::NonexistentRoom`].
+    pub fn options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the resolver used to correct mistyped subjects and staff codes
+    /// (e.g., `Phyics`, or a surname in place of a staff code).
+    ///
+    /// Each correction is reported as an [`ImportIssue::Corrected`] -- any
+    /// subject or member of staff which the resolver is unable to resolve is
+    /// imported unchanged.
+    pub fn resolver(mut self, resolver: Resolver) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    /// Imports the SIMS export provided.
+    ///
+    /// # Errors
+    ///
+    /// An error is only returned should the export be impossible to import
+    /// (e.g., if a required column is missing) -- problems with individual
+    /// rows are reported as [`ImportIssue`]s instead.
+    pub fn import(&self, export: &str) -> Result<Import, SimsError> {
+        let mut records = csv::parse(export).into_iter();
+        let (_, header) = records.next().ok_or(SimsError::Empty)?;
+
+        let columns = Columns::new(&header)?;
+
+        let mut timetable = Timetable::new();
+        let mut issues = Vec::new();
+
+        for (line, record) in records {
+            match self.row(&columns, line, &record) {
+                Ok((slot, mut activity)) => {
+                    if let Some(resolver) = &self.resolver {
+                        import::correct(resolver, line, &mut activity, &mut issues);
+                    }
+
+                    if let Some(&location) = activity.location() {
+                        if !self.options.is_known(&location) {
+                            issues.push(ImportIssue::NonexistentRoom { line, location });
+                        }
+                    }
+
+                    timetable.insert(slot, activity);
+                }
+                Err(issue) => issues.push(issue),
+            }
+        }
+
+        Ok(Import { timetable, issues })
+    }
+
+    // Convert a single row of the export into an activity
+    fn row(
+        &self,
+        columns: &Columns,
+        line: usize,
+        record: &[String],
+    ) -> Result<(TimeSlot, Activity), ImportIssue> {
+        let field = |index: usize| record.get(index).map_or("", |field| field.trim());
+        let invalid = |reason: String| ImportIssue::InvalidRow { line, reason };
+
+        let week = parse_week(field(columns.week))
+            .ok_or_else(|| invalid(format!("invalid week `{}`", field(columns.week))))?;
+        let day = parse_day(field(columns.day))
+            .ok_or_else(|| invalid(format!("invalid day `{}`", field(columns.day))))?;
+        let period = parse_period(field(columns.period))
+            .ok_or_else(|| invalid(format!("invalid period `{}`", field(columns.period))))?;
+
+        let class_code = field(columns.class);
+        let class =
+            Class::new(class_code.to_string()).map_err(|error| invalid(error.to_string()))?;
+
+        let subject_name = match columns.subject {
+            Some(index) => field(index),
+            None => derive_subject(class_code),
+        };
+        let subject =
+            Subject::new(subject_name.to_string()).map_err(|error| invalid(error.to_string()))?;
+
+        // Lessons without a member of staff (e.g., supervised study) are
+        // permitted
+        let teacher = match field(columns.staff) {
+            "" => None,
+            code => {
+                Some(Teacher::new(code.to_string()).map_err(|error| invalid(error.to_string()))?)
+            }
+        };
+
+        let code = field(columns.room);
+        let location = match self.aliases.get(code) {
+            Some(location) => self.options.check(*location),
+            None => self.options.parse_location(code),
+        };
+        let location = location.ok().ok_or_else(|| ImportIssue::UnmappedRoom {
+            line,
+            code: code.to_string(),
+        })?;
+
+        Ok((
+            TimeSlot { week, day, period },
+            Activity::Lesson {
+                subject,
+                class,
+                teacher,
+                location,
+            },
+        ))
+    }
+}
+
+// The positions of the columns within the export
+struct Columns {
+    class: usize,
+    staff: usize,
+    room: usize,
+    day: usize,
+    period: usize,
+    week: usize,
+    subject: Option<usize>,
+}
+
+impl Columns {
+    fn new(header: &[String]) -> Result<Self, SimsError> {
+        // Normalise the column names such that `Staff Code`, `staff_code`,
+        // and `STAFFCODE` all refer to the same column
+        let names: Vec<String> = header
+            .iter()
+            .map(|name| {
+                name.chars()
+                    .filter(|c| !c.is_whitespace() && *c != '_')
+                    .collect::<String>()
+                    .to_ascii_lowercase()
+            })
+            .collect();
+
+        let find = |aliases: &[&str]| {
+            names
+                .iter()
+                .position(|name| aliases.contains(&name.as_str()))
+        };
+        let require = |column: &'static str, aliases: &[&str]| {
+            find(aliases).ok_or(SimsError::MissingColumn(column))
+        };
+
+        Ok(Self {
+            class: require("Class", &["class"])?,
+            staff: require("Staff", &["staff", "staffcode", "teacher"])?,
+            room: require("Room", &["room", "roomcode"])?,
+            day: require("Day", &["day"])?,
+            period: require("Period", &["period"])?,
+            week: require("Week", &["week"])?,
+            subject: find(&["subject"]),
+        })
+    }
+}
+
+// Derive the subject from a class code -- SIMS class codes typically take the
+// form `<year group>/<subject><set>` (e.g., `10A/Ma1`)
+fn derive_subject(class: &str) -> &str {
+    match class.rsplit_once('/') {
+        Some((_, subject)) => subject.trim_end_matches(|c: char| c.is_ascii_digit()),
+        None => class,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, HighfieldRoom, RoomRegistry};
+
+    const EXPORT: &str = "\
+Week,Day,Period,Class,Staff Code,Room Code
+1,Mon,1,10A/Ma1,JSM,H201
+A,Tue,P2,10A/Ma1,JSM,OLDLAB
+2,Fri,5,12B/Ph,ABC,FH S12
+1,Sat,1,10A/Ma1,JSM,H201
+1,Mon,2,10A/Ma1,JSM,XYZ
+";
+
+    #[test]
+    fn sims_import() {
+        let import = SimsImporter::new()
+            .alias("OLDLAB", Location::Highfield(HighfieldRoom::Hall))
+            .import(EXPORT)
+            .unwrap();
+
+        assert_eq!(import.timetable.len(), 3);
+        assert_eq!(import.unmapped_rooms(), vec!["XYZ"]);
+        assert!(matches!(
+            import.issues[0],
+            ImportIssue::InvalidRow { line: 5, .. }
+        ));
+
+        let (_, entry) = import.timetable.at(timeslot!(W1TP2)).next().unwrap();
+
+        assert_eq!(
+            entry.activity.location(),
+            Some(&Location::Highfield(HighfieldRoom::Hall))
+        );
+
+        let (_, entry) = import.timetable.at(timeslot!(W2FP5)).next().unwrap();
+
+        match &entry.activity {
+            Activity::Lesson {
+                subject, teacher, ..
+            } => {
+                assert_eq!(subject.name(), "Ph");
+                assert_eq!(teacher.as_ref().unwrap().code(), "ABC");
+            }
+            _ => panic!("expected a lesson"),
+        }
+    }
+
+    #[test]
+    fn sims_import_strict() {
+        let registry = ["H201", "FH S12"]
+            .into_iter()
+            .map(|code| code.parse().unwrap());
+        let import = SimsImporter::new()
+            .alias("OLDLAB", Location::Highfield(HighfieldRoom::Hall))
+            .options(ParseOptions::strict(registry.collect()))
+            .import(EXPORT)
+            .unwrap();
+
+        assert_eq!(import.timetable.len(), 2);
+        assert_eq!(import.unmapped_rooms(), vec!["OLDLAB", "XYZ"]);
+    }
+
+    #[cfg(feature = "other-rooms")]
+    #[test]
+    fn sims_import_other_rooms() {
+        let import = SimsImporter::new()
+            .options(ParseOptions::lenient().other_rooms(true))
+            .import(EXPORT)
+            .unwrap();
+
+        assert_eq!(import.timetable.len(), 4);
+        assert!(import.unmapped_rooms().is_empty());
+
+        let (_, entry) = import.timetable.at(timeslot!(W1TP2)).next().unwrap();
+
+        assert_eq!(entry.activity.location().unwrap().to_string(), "OLDLAB");
+    }
+
+    #[test]
+    fn sims_import_flagging() {
+        let import = SimsImporter::new()
+            .options(ParseOptions::flagging(RoomRegistry::curated()))
+            .import("Week,Day,Period,Class,Staff,Room\n1,Mon,1,10A/Ma1,JSM,H201\n1,Mon,2,10A/Ma1,JSM,H299\n")
+            .unwrap();
+
+        assert_eq!(import.timetable.len(), 2);
+        assert_eq!(
+            import.issues,
+            vec![ImportIssue::NonexistentRoom {
+                line: 3,
+                location: "H299".parse().unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn sims_import_corrected() {
+        let resolver = Resolver::new()
+            .subjects(["Maths", "Physics"])
+            .staff_member("JSM", "John Smith");
+        let import = SimsImporter::new()
+            .resolver(resolver)
+            .import("Week,Day,Period,Class,Subject,Staff,Room\n1,Mon,1,12B/Ph,Phyics,Smith,H201\n1,Mon,2,10A/Ma1,Maths,ABC,H201\n")
+            .unwrap();
+
+        assert_eq!(import.timetable.len(), 2);
+        assert_eq!(
+            import.issues,
+            vec![
+                ImportIssue::Corrected {
+                    line: 2,
+                    from: "Phyics".to_string(),
+                    to: "Physics".to_string(),
+                },
+                ImportIssue::Corrected {
+                    line: 2,
+                    from: "Smith".to_string(),
+                    to: "JSM".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn sims_missing_column() {
+        assert_eq!(
+            SimsImporter::new().import("Week,Day,Period,Class,Room\n"),
+            Err(SimsError::MissingColumn("Staff"))
+        );
+    }
+}
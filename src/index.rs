@@ -0,0 +1,147 @@
+use crate::{Activity, Entry, EntryId, Room};
+use std::collections::{BTreeSet, HashMap};
+use std::hash::Hash;
+
+/// The indexes of the lessons of a [`Timetable`](crate::Timetable) by their
+/// rooms, teachers, and classes.
+///
+/// The indexes are maintained whenever a change is applied to the timetable,
+/// such that [queries](crate::Lessons) filtering by a room, teacher, or class
+/// do not need to scan every entry of the timetable.
+#[derive(Debug, Clone)]
+pub(crate) struct Index<L> {
+    rooms: HashMap<L, BTreeSet<EntryId>>,
+    teachers: HashMap<String, BTreeSet<EntryId>>,
+    classes: HashMap<String, BTreeSet<EntryId>>,
+}
+
+impl<L> Default for Index<L> {
+    fn default() -> Self {
+        Self {
+            rooms: HashMap::new(),
+            teachers: HashMap::new(),
+            classes: HashMap::new(),
+        }
+    }
+}
+
+impl<L: Room> Index<L> {
+    /// Indexes the `entry` with the identifier `id`.
+    pub(crate) fn insert(&mut self, id: EntryId, entry: &Entry<L>) {
+        if let Activity::Lesson { class, .. } = &entry.activity {
+            self.classes
+                .entry(class.reference().clone())
+                .or_default()
+                .insert(id);
+
+            for location in entry.locations() {
+                self.rooms.entry(location.clone()).or_default().insert(id);
+            }
+
+            for teacher in entry.teachers() {
+                self.teachers
+                    .entry(teacher.code().clone())
+                    .or_default()
+                    .insert(id);
+            }
+        }
+    }
+
+    /// Removes the `entry` with the identifier `id` from the indexes.
+    pub(crate) fn remove(&mut self, id: EntryId, entry: &Entry<L>) {
+        if let Activity::Lesson { class, .. } = &entry.activity {
+            unindex(&mut self.classes, class.reference().as_str(), id);
+
+            for location in entry.locations() {
+                unindex(&mut self.rooms, location, id);
+            }
+
+            for teacher in entry.teachers() {
+                unindex(&mut self.teachers, teacher.code().as_str(), id);
+            }
+        }
+    }
+
+    /// Retrieves the identifiers of the lessons taking place in the
+    /// `location` provided.
+    pub(crate) fn room(&self, location: &L) -> Option<&BTreeSet<EntryId>> {
+        self.rooms.get(location)
+    }
+
+    /// Retrieves the identifiers of the lessons taught by the teacher with
+    /// the staff `code` provided.
+    pub(crate) fn teacher(&self, code: &str) -> Option<&BTreeSet<EntryId>> {
+        self.teachers.get(code)
+    }
+
+    /// Retrieves the identifiers of the lessons of the class with the
+    /// `reference` provided.
+    pub(crate) fn class(&self, reference: &str) -> Option<&BTreeSet<EntryId>> {
+        self.classes.get(reference)
+    }
+}
+
+// Remove the identifier `id` from the set of `key`, removing the set should it
+// become empty (such that the index does not grow as lessons are moved about)
+fn unindex<K, Q>(index: &mut HashMap<K, BTreeSet<EntryId>>, key: &Q, id: EntryId)
+where
+    K: Eq + Hash + std::borrow::Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+{
+    if let Some(ids) = index.get_mut(key) {
+        ids.remove(&id);
+
+        if ids.is_empty() {
+            index.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{timeslot, Activity, Class, Location, Subject, Teacher, Timetable};
+
+    fn lesson(class: &str, teacher: &str, location: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Some(Teacher::new(teacher.to_string()).unwrap()),
+            location: location.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn index_maintained() {
+        let h201: Location = "H201".parse().unwrap();
+        let u101: Location = "U101".parse().unwrap();
+
+        let mut timetable = Timetable::new();
+        let first = timetable.insert(timeslot!(W1MP1), lesson("10A", "JSM", "H201"));
+        let second = timetable.insert(timeslot!(W1MP2), lesson("10B", "JSM", "H201"));
+
+        timetable.relocate(second, u101);
+        timetable.remove(first);
+
+        assert_eq!(timetable.index.room(&h201), None);
+        assert_eq!(timetable.index.room(&u101).unwrap().len(), 1);
+        assert_eq!(timetable.index.teacher("JSM").unwrap().len(), 1);
+        assert_eq!(timetable.index.class("10A"), None);
+
+        let ids: Vec<_> = timetable
+            .lessons()
+            .in_room(u101)
+            .map(|(id, _)| id)
+            .collect();
+
+        assert_eq!(ids, [second]);
+        assert_eq!(timetable.lessons().in_room(h201).count(), 0);
+        assert_eq!(
+            timetable
+                .lessons()
+                .taught_by("JSM")
+                .for_class("10B")
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,622 @@
+use crate::booking::Spaces;
+use crate::{
+    Dataset, EntryId, Location, Room, RoomKind, RoomMigration, RoomRegistry, Teacher, Term,
+};
+use chrono::{Duration, NaiveDate};
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+// The longest break between two terms (in days) which is not reported as a
+// gap in the calendar -- longer than any school holiday (e.g., the summer)
+const LONGEST_HOLIDAY: i64 = 56;
+
+/// A problem with the integrity of a [`Dataset`] (*see
+/// [`Dataset::check_integrity`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityIssue {
+    /// A lesson takes place in a room which is not in the registry of the
+    /// check (e.g., a mistyped room in a hand-edited file).
+    UnknownRoom {
+        /// The entry of the lesson.
+        entry: EntryId,
+
+        /// The unknown room.
+        location: Location,
+    },
+
+    /// A lesson is taught by a teacher who is not a member of the staff of
+    /// the check (be they its teacher, a co-teacher, or the teacher of a
+    /// stage of its rotation).
+    UnknownTeacher {
+        /// The entry of the lesson.
+        entry: EntryId,
+
+        /// The unknown teacher.
+        teacher: Teacher,
+    },
+
+    /// Two room overrides move the same lessons during overlapping dates
+    /// (in which case the `later` override takes precedence), identified
+    /// by their indexes within [`Timetable::overrides`](crate::Timetable::overrides).
+    OverlappingOverrides {
+        /// The index of the override recorded first.
+        earlier: usize,
+
+        /// The index of the override recorded last.
+        later: usize,
+    },
+
+    /// Two terms of the calendar overlap.
+    OverlappingTerms {
+        /// The earlier term.
+        first: Term,
+
+        /// The later term.
+        second: Term,
+    },
+
+    /// Two consecutive terms of the calendar are separated by a break longer
+    /// than any school holiday (e.g., should a term be missing).
+    CalendarGap {
+        /// The first day of the break.
+        from: NaiveDate,
+
+        /// The last day of the break.
+        until: NaiveDate,
+    },
+
+    /// A booking is of a resource located in a room which is not in the
+    /// registry of the check (e.g., a room which has been demolished),
+    /// identified by its index within [`Spaces::bookings`].
+    BookingInUnknownRoom {
+        /// The index of the booking.
+        booking: usize,
+
+        /// The room of the resource.
+        location: Location,
+    },
+
+    /// A booking is made on a date which is not a school day of the calendar
+    /// (e.g., after the term dates have been changed), identified by its
+    /// index within [`Spaces::bookings`].
+    BookingOnClosedDay {
+        /// The index of the booking.
+        booking: usize,
+
+        /// The date of the booking.
+        date: NaiveDate,
+    },
+}
+
+impl Display for IntegrityIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownRoom { entry, location } => {
+                write!(
+                    f,
+                    "lesson {} uses the unknown room {}",
+                    entry.get(),
+                    location
+                )
+            }
+            Self::UnknownTeacher { entry, teacher } => {
+                write!(
+                    f,
+                    "lesson {} is taught by the unknown teacher {}",
+                    entry.get(),
+                    teacher
+                )
+            }
+            Self::OverlappingOverrides { earlier, later } => {
+                write!(
+                    f,
+                    "room override {} overlaps room override {}",
+                    later, earlier
+                )
+            }
+            Self::OverlappingTerms { first, second } => write!(
+                f,
+                "the term starting on {} overlaps the term starting on {}",
+                second.start(),
+                first.start()
+            ),
+            Self::CalendarGap { from, until } => {
+                write!(f, "there is no term from {} until {}", from, until)
+            }
+            Self::BookingInUnknownRoom { booking, location } => {
+                write!(f, "booking {} is in the unknown room {}", booking, location)
+            }
+            Self::BookingOnClosedDay { booking, date } => {
+                write!(
+                    f,
+                    "booking {} is on {}, which is not a school day",
+                    booking, date
+                )
+            }
+        }
+    }
+}
+
+/// An automated repair of an [`IntegrityIssue`], which is only offered
+/// should it be safe (i.e., should it not change the intended timetable).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repair {
+    /// The lesson is moved to the room which its unknown room was renamed to
+    /// (*see [`IntegrityCheck::migration`]*).
+    Relocate {
+        /// The entry of the lesson.
+        entry: EntryId,
+
+        /// The room to which the lesson is moved.
+        location: Location,
+    },
+
+    /// The override is removed, as it is entirely superseded by a later
+    /// override (and therefore has no effect).
+    RemoveOverride(usize),
+}
+
+/// An [`IntegrityIssue`], accompanied by its [`Repair`] (if it can be
+/// repaired safely).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityFinding {
+    /// The problem.
+    pub issue: IntegrityIssue,
+
+    /// The repair of the problem (if any).
+    pub repair: Option<Repair>,
+}
+
+/// The [`IntegrityFinding`]s of a [`Dataset`] (*see
+/// [`Dataset::check_integrity`]*).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Every finding (lessons first, followed by overrides, the calendar,
+    /// and bookings).
+    pub findings: Vec<IntegrityFinding>,
+}
+
+impl IntegrityReport {
+    /// Whether the dataset has no integrity issues.
+    pub fn is_sound(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Retrieves the repairs offered by the findings.
+    pub fn repairs(&self) -> impl Iterator<Item = Repair> + '_ {
+        self.findings.iter().filter_map(|finding| finding.repair)
+    }
+
+    /// Makes every repair of the `IntegrityReport` to the `dataset` provided
+    /// (from which the report was produced), recording each change in the
+    /// [history](crate::Timetable::history) of its timetable.
+    ///
+    /// # Returns
+    ///
+    /// The number of repairs made.
+    pub fn repair(&self, dataset: &mut Dataset) -> usize {
+        let mut repaired = 0;
+        let mut removed: Vec<usize> = Vec::new();
+
+        for repair in self.repairs() {
+            match repair {
+                Repair::Relocate { entry, location } => {
+                    if dataset.timetable.relocate(entry, location).is_some() {
+                        repaired += 1;
+                    }
+                }
+                Repair::RemoveOverride(index) => removed.push(index),
+            }
+        }
+
+        // The overrides are removed last-first, such that the indexes of the
+        // other overrides are unaffected
+        removed.sort_unstable();
+        removed.dedup();
+
+        for index in removed.into_iter().rev() {
+            if dataset.timetable.remove_override(index).is_some() {
+                repaired += 1;
+            }
+        }
+
+        repaired
+    }
+}
+
+/// The configuration of [`Dataset::check_integrity_with`]: the rooms, staff,
+/// and bookings against which a dataset is checked.
+#[derive(Debug, Clone)]
+pub struct IntegrityCheck<'a> {
+    registry: RoomRegistry,
+    migration: RoomMigration,
+    staff: Option<HashSet<Teacher>>,
+    spaces: Option<&'a Spaces>,
+}
+
+impl Default for IntegrityCheck<'_> {
+    fn default() -> Self {
+        Self {
+            registry: RoomRegistry::known(),
+            migration: RoomMigration::known(),
+            staff: None,
+            spaces: None,
+        }
+    }
+}
+
+impl<'a> IntegrityCheck<'a> {
+    /// Creates a new `IntegrityCheck` against the [known](RoomRegistry::known)
+    /// rooms and [renames](RoomMigration::known), which does not check the
+    /// teachers or bookings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the registry of the rooms which the lessons may use.
+    pub fn registry(mut self, registry: RoomRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Sets the renames from which unknown rooms are repaired (*see
+    /// [`Repair::Relocate`]*).
+    pub fn migration(mut self, migration: RoomMigration) -> Self {
+        self.migration = migration;
+        self
+    }
+
+    /// Sets the members of staff who may teach the lessons.
+    pub fn staff<I>(mut self, staff: I) -> Self
+    where
+        I: IntoIterator<Item = Teacher>,
+    {
+        self.staff = Some(staff.into_iter().collect());
+        self
+    }
+
+    /// Sets the bookings of spaces made against the calendar of the dataset.
+    pub fn spaces(mut self, spaces: &'a Spaces) -> Self {
+        self.spaces = Some(spaces);
+        self
+    }
+
+    fn check(&self, dataset: &Dataset) -> IntegrityReport {
+        let mut findings = Vec::new();
+        let mut push = |issue, repair| findings.push(IntegrityFinding { issue, repair });
+
+        // Dangling references of the lessons
+        for (id, entry) in dataset.timetable.lessons() {
+            let stages = entry.rotation.iter().flat_map(|rotation| &rotation.stages);
+            let mut seen = HashSet::new();
+
+            for location in entry
+                .locations()
+                .chain(stages.map(|stage| &stage.location))
+                .filter(|location| seen.insert(**location))
+            {
+                let unregistered = !matches!(
+                    location.kind(),
+                    RoomKind::OffSite | RoomKind::Remote | RoomKind::ToBeConfirmed
+                ) && !self.registry.contains(location);
+
+                if unregistered {
+                    // Only the lesson's own room can be relocated
+                    let repair = self
+                        .migration
+                        .get(location)
+                        .filter(|renamed| {
+                            entry.activity.location() == Some(location)
+                                && self.registry.contains(renamed)
+                        })
+                        .map(|renamed| Repair::Relocate {
+                            entry: id,
+                            location: renamed,
+                        });
+
+                    push(
+                        IntegrityIssue::UnknownRoom {
+                            entry: id,
+                            location: *location,
+                        },
+                        repair,
+                    );
+                }
+            }
+
+            if let Some(staff) = &self.staff {
+                let stages = entry.rotation.iter().flat_map(|rotation| &rotation.stages);
+                let mut seen = HashSet::new();
+
+                // Every teacher of the lesson (including its co-teachers, and
+                // the teachers of the stages of its rotation)
+                for teacher in entry
+                    .teachers()
+                    .chain(stages.filter_map(|stage| stage.teacher.as_ref()))
+                    .filter(|teacher| seen.insert(*teacher))
+                {
+                    if !staff.contains(teacher) {
+                        push(
+                            IntegrityIssue::UnknownTeacher {
+                                entry: id,
+                                teacher: teacher.clone(),
+                            },
+                            None,
+                        );
+                    }
+                }
+            }
+        }
+
+        // Overrides which move the same lessons during overlapping dates
+        let overrides = dataset.timetable.overrides();
+
+        for (later, second) in overrides.iter().enumerate() {
+            for (earlier, first) in overrides[..later].iter().enumerate() {
+                let overlapping = first.slot == second.slot
+                    && first.from == second.from
+                    && first.start <= second.end
+                    && second.start <= first.end;
+
+                if overlapping {
+                    let superseded = second.start <= first.start && first.end <= second.end;
+
+                    push(
+                        IntegrityIssue::OverlappingOverrides { earlier, later },
+                        superseded.then_some(Repair::RemoveOverride(earlier)),
+                    );
+                }
+            }
+        }
+
+        // Overlapping terms, and breaks longer than any holiday
+        for pair in dataset.calendar.terms().windows(2) {
+            let (first, second) = (pair[0], pair[1]);
+
+            if second.start() <= first.end() {
+                push(IntegrityIssue::OverlappingTerms { first, second }, None);
+            } else if (second.start() - first.end()).num_days() - 1 > LONGEST_HOLIDAY {
+                push(
+                    IntegrityIssue::CalendarGap {
+                        from: first.end() + Duration::days(1),
+                        until: second.start() - Duration::days(1),
+                    },
+                    None,
+                );
+            }
+        }
+
+        // Orphaned bookings
+        if let Some(spaces) = self.spaces {
+            for (index, booking) in spaces.bookings().iter().enumerate() {
+                let location = spaces
+                    .resources()
+                    .get(booking.resource)
+                    .and_then(|resource| resource.location);
+
+                if let Some(location) = location.filter(|location| {
+                    location.kind().is_occupied() && !self.registry.contains(location)
+                }) {
+                    push(
+                        IntegrityIssue::BookingInUnknownRoom {
+                            booking: index,
+                            location,
+                        },
+                        None,
+                    );
+                } else if !dataset.calendar.is_school_day(booking.date) {
+                    push(
+                        IntegrityIssue::BookingOnClosedDay {
+                            booking: index,
+                            date: booking.date,
+                        },
+                        None,
+                    );
+                }
+            }
+        }
+
+        IntegrityReport { findings }
+    }
+}
+
+impl Dataset {
+    /// Checks the integrity of the `Dataset` (e.g., after a hand-edited file
+    /// has been loaded) against the [known](RoomRegistry::known) rooms --
+    /// *see [`Self::check_integrity_with`]*.
+    pub fn check_integrity(&self) -> IntegrityReport {
+        self.check_integrity_with(&IntegrityCheck::new())
+    }
+
+    /// Checks the integrity of the `Dataset` against the rooms, staff, and
+    /// bookings of the `check` provided, detecting:
+    ///
+    /// * lessons which use rooms missing from the registry (or which are
+    ///   taught by teachers missing from the staff);
+    /// * room overrides which move the same lessons during overlapping dates;
+    /// * terms which overlap, or which are separated by a break longer than
+    ///   any school holiday (i.e., eight weeks); and
+    /// * bookings of resources located in rooms missing from the registry,
+    ///   or which are made on days which are no longer school days.
+    ///
+    /// A [`Repair`] is offered where it is safe -- a lesson whose room has
+    /// been renamed is moved to the new room, and an override entirely
+    /// superseded by a later override is removed:
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, Subject};
+    /// use timetableau::{IntegrityIssue, Timetable};
+    ///
+    /// let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+    ///
+    /// let mut timetable = Timetable::new();
+    /// timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+    ///     subject: Subject::new("Ma".to_string()).unwrap(),
+    ///     class: Class::new("10A".to_string()).unwrap(),
+    ///     teacher: None,
+    ///     location: "H999".parse().unwrap(),
+    /// });
+    /// timetable.override_room(timeslot!(W1MP1), date(5)..=date(6), "H202".parse().unwrap());
+    /// timetable.override_room(timeslot!(W1MP1), date(4)..=date(8), "H203".parse().unwrap());
+    ///
+    /// let mut dataset = Dataset::new(timetable, AcademicCalendar::default());
+    /// let report = dataset.check_integrity();
+    ///
+    /// assert!(matches!(report.findings[0].issue, IntegrityIssue::UnknownRoom { .. }));
+    /// assert_eq!(report.findings[1].to_string(), "room override 1 overlaps room override 0");
+    ///
+    /// assert_eq!(report.repair(&mut dataset), 1);
+    /// assert_eq!(dataset.timetable.overrides().len(), 1);
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// The repairs are not made until [`IntegrityReport::repair`] is called.
+    /// Off-site locations, remote lessons, and rooms which are yet to be
+    /// confirmed are never unknown.
+    pub fn check_integrity_with(&self, check: &IntegrityCheck) -> IntegrityReport {
+        check.check(self)
+    }
+}
+
+impl Display for IntegrityFinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.issue, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booking::{Booking, Resource};
+    use crate::{
+        timeslot, AcademicCalendar, Activity, Class, DayException, Entry, Period, Rotation,
+        Subject, Timetable, WeekCycle,
+    };
+
+    #[test]
+    fn integrity_checked() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2023, month, day).unwrap();
+        let teacher = |code: &str| Teacher::new(code.to_string()).unwrap();
+        let lesson = |code: &str, location: &str| Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new("10A".to_string()).unwrap(),
+            teacher: Some(teacher(code)),
+            location: location.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        let renamed = timetable.insert(timeslot!(W1MP1), lesson("JSM", "U190"));
+        let unknown = timetable.insert(timeslot!(W1MP2), lesson("XYZ", "H201"));
+        timetable.insert(timeslot!(W1MP3), lesson("JSM", "@Museum"));
+        let staffed = timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP4), lesson("JSM", "H201"))
+                    .co_teacher(teacher("ZZZ"))
+                    .rotating(
+                        Rotation::new(date(9, 4))
+                            .stage(Some(teacher("YYY")), "H201".parse().unwrap()),
+                    ),
+            )
+            .unwrap();
+
+        // Partially overlapping overrides cannot be repaired
+        let slot = timeslot!(W1TP1);
+        timetable.override_room(slot, date(9, 4)..=date(9, 8), "H202".parse().unwrap());
+        timetable.override_room(slot, date(9, 6)..=date(9, 12), "H203".parse().unwrap());
+        timetable.override_location(
+            slot,
+            date(9, 6)..=date(9, 7),
+            "H201".parse().unwrap(),
+            "H204".parse().unwrap(),
+        );
+
+        let calendar = AcademicCalendar::new(vec![
+            Term::new(date(1, 3), date(2, 10)).unwrap(),
+            Term::new(date(2, 1), date(3, 31)).unwrap(),
+            Term::new(date(9, 4), date(12, 20)).unwrap(),
+        ]);
+
+        // The bookings were made before the school closed on the 6th
+        let cycle = WeekCycle::new(date(9, 4), calendar.clone()).unwrap();
+        let mut spaces = Spaces::new();
+        let booth =
+            spaces.add(Resource::new("Recording Booth", 1).location("U190".parse().unwrap()));
+        let piano = spaces.add(Resource::new("Piano", 1).location("H202".parse().unwrap()));
+        for booking in [
+            Booking::new(booth, date(9, 4), Period::First, "ADA"),
+            Booking::new(piano, date(9, 5), Period::First, "BOB"),
+            Booking::new(piano, date(9, 6), Period::First, "EVE"),
+        ] {
+            spaces.book(&cycle, booking).unwrap();
+        }
+
+        let mut calendar = calendar;
+        calendar.add_exception(date(9, 6), DayException::Closed);
+
+        let mut dataset = Dataset::new(timetable, calendar);
+        let check = IntegrityCheck::new()
+            .registry(RoomRegistry::from_data("H201\nU201\nH202\nH203\nH204").unwrap())
+            .migration(RoomMigration::from_data("U190 -> U201").unwrap())
+            .staff([teacher("JSM")])
+            .spaces(&spaces);
+        let report = dataset.check_integrity_with(&check);
+
+        assert_eq!(
+            report
+                .findings
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "lesson 0 uses the unknown room U190",
+                "lesson 1 is taught by the unknown teacher XYZ",
+                "lesson 3 is taught by the unknown teacher ZZZ",
+                "lesson 3 is taught by the unknown teacher YYY",
+                "room override 1 overlaps room override 0",
+                "the term starting on 2023-02-01 overlaps the term starting on 2023-01-03",
+                "there is no term from 2023-04-01 until 2023-09-03",
+                "booking 0 is in the unknown room U190",
+                "booking 2 is on 2023-09-06, which is not a school day"
+            ]
+        );
+        assert_eq!(
+            report.repairs().collect::<Vec<_>>(),
+            [Repair::Relocate {
+                entry: renamed,
+                location: "U201".parse().unwrap()
+            }]
+        );
+        assert!(!report.is_sound());
+
+        assert_eq!(report.repair(&mut dataset), 1);
+        let report = dataset.check_integrity_with(&check);
+        assert_eq!(report.findings.len(), 8);
+        assert!(report
+            .findings
+            .iter()
+            .all(|finding| finding.repair.is_none()));
+        assert_eq!(
+            report.findings[0].issue,
+            IntegrityIssue::UnknownTeacher {
+                entry: unknown,
+                teacher: teacher("XYZ")
+            }
+        );
+        assert_eq!(
+            report.findings[1].issue,
+            IntegrityIssue::UnknownTeacher {
+                entry: staffed,
+                teacher: teacher("ZZZ")
+            }
+        );
+
+        // Without any configuration, only the known rooms are checked
+        assert!(dataset
+            .check_integrity()
+            .findings
+            .iter()
+            .all(|finding| { !matches!(finding.issue, IntegrityIssue::UnknownTeacher { .. }) }));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{Class, Subject, Teacher};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A handle to a string stored within an [`Interner`].
+///
+/// Symbols are cheap to copy, and two symbols produced by the same interner
+/// are equal if (and only if) their strings are equal -- as a result,
+/// analyses can compare and hash symbols rather than strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Retrieves the index of the `Symbol` (i.e., the number of distinct
+    /// strings interned before it).
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+// Declare a handle to the name of a part of a lesson
+macro_rules! handle {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(Symbol);
+
+        impl $name {
+            /// Retrieves the [`Symbol`] of the name.
+            pub fn symbol(self) -> Symbol {
+                self.0
+            }
+        }
+    };
+}
+
+handle! {
+    /// A handle to the name of a [`Subject`] stored within an [`Interner`].
+    SubjectId
+}
+
+handle! {
+    /// A handle to the reference of a [`Class`] stored within an
+    /// [`Interner`].
+    ClassId
+}
+
+handle! {
+    /// A handle to the staff code of a [`Teacher`] stored within an
+    /// [`Interner`].
+    TeacherId
+}
+
+/// An arena of strings (e.g., the names of subjects, classes, and teachers),
+/// in which each distinct string is only stored once.
+///
+/// Thousands of lessons typically share a small number of teachers, subjects,
+/// and classes -- interning their names allows them to share storage and to
+/// be compared by their handles (which are [`Copy`]):
+///
+/// ```
+/// use timetableau::{Interner, Teacher};
+///
+/// let mut interner = Interner::new();
+///
+/// let a = interner.teacher(&Teacher::new("JSM".to_string()).unwrap());
+/// let b = interner.teacher(&Teacher::new("JSM".to_string()).unwrap());
+///
+/// assert_eq!(a, b);
+/// assert_eq!(interner.resolve(a.symbol()), "JSM");
+/// assert_eq!(interner.len(), 1);
+/// ```
+///
+/// # Remarks
+///
+/// A handle must only be resolved by the interner which produced it --
+/// resolving a handle produced by another interner retrieves an unrelated
+/// string (or panics).
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    strings: Vec<Arc<str>>,
+    symbols: HashMap<Arc<str>, Symbol>,
+}
+
+impl Interner {
+    /// Creates a new, empty `Interner`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interns the `string` provided (only storing it, should it not already
+    /// be stored).
+    ///
+    /// # Panics
+    ///
+    /// This function panics should more than [`u32::MAX`] distinct strings
+    /// be interned.
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(string) {
+            return *symbol;
+        }
+
+        let symbol = Symbol(u32::try_from(self.strings.len()).expect("the interner is full"));
+        let string: Arc<str> = Arc::from(string);
+
+        self.strings.push(string.clone());
+        self.symbols.insert(string, symbol);
+        symbol
+    }
+
+    /// Retrieves the [`Symbol`] of the `string` provided (or [`None`] should
+    /// the string not have been interned).
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        self.symbols.get(string).copied()
+    }
+
+    /// Retrieves the string of the `symbol` provided.
+    ///
+    /// # Panics
+    ///
+    /// This function panics should the symbol not have been produced by the
+    /// `Interner`.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol.index()]
+    }
+
+    /// Interns the name of the `subject` provided.
+    pub fn subject(&mut self, subject: &Subject) -> SubjectId {
+        SubjectId(self.intern(subject.name()))
+    }
+
+    /// Interns the reference of the `class` provided.
+    pub fn class(&mut self, class: &Class) -> ClassId {
+        ClassId(self.intern(class.reference()))
+    }
+
+    /// Interns the staff code of the `teacher` provided.
+    pub fn teacher(&mut self, teacher: &Teacher) -> TeacherId {
+        TeacherId(self.intern(teacher.code()))
+    }
+
+    /// The number of distinct strings stored within the `Interner`.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Whether no strings are stored within the `Interner`.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Creates an iterator over the strings stored within the `Interner` (in
+    /// the order of their symbols).
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.strings.iter().map(|string| &**string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interner_deduplicates() {
+        let mut interner = Interner::new();
+
+        let maths = interner.subject(&Subject::new("Ma".to_string()).unwrap());
+        let class = interner.class(&Class::new("Ma".to_string()).unwrap());
+        let english = interner.intern("En");
+
+        assert_eq!(maths.symbol(), class.symbol());
+        assert_eq!(interner.intern("Ma"), maths.symbol());
+        assert_ne!(english, maths.symbol());
+        assert_eq!(interner.get("En"), Some(english));
+        assert_eq!(interner.get("Fr"), None);
+        assert_eq!(interner.iter().collect::<Vec<_>>(), ["Ma", "En"]);
+    }
+}
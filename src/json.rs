@@ -0,0 +1,404 @@
+//! A minimal JSON (as described by [RFC 8259]) parser.
+//!
+//! Pulling in an entire serialisation framework to read a handful of JSON
+//! payloads is overkill -- this module provides a simple tree
+//! representation of JSON documents, which the importers then interpret.
+//! Documents are written directly (*see [`write_string`]*).
+//!
+//! [RFC 8259]: https://www.rfc-editor.org/rfc/rfc8259
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+
+// The deepest nesting of arrays and objects which is parsed -- deeper
+// documents are rejected (rather than overflowing the stack, as the parser is
+// recursive and parses untrusted payloads)
+const MAX_DEPTH: usize = 128;
+
+/// A JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(JsonObject),
+}
+
+/// A JSON object.
+///
+/// The members of the object are stored in the order in which they appear
+/// in the document.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct JsonObject {
+    /// The line (starting from `1`) on which the object starts.
+    pub(crate) line: usize,
+
+    /// The members of the object.
+    pub(crate) members: Vec<(String, JsonValue)>,
+}
+
+impl JsonValue {
+    /// Retrieves the value of the member `key` (should the value be an
+    /// object containing that member).
+    pub(crate) fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            Self::Object(object) => object
+                .members
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Retrieves the line on which the value starts (should the value be an
+    /// object).
+    pub(crate) fn line(&self) -> Option<usize> {
+        match self {
+            Self::Object(object) => Some(object.line),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// An error returned when a JSON document cannot be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct JsonError {
+    /// The line (starting from `1`) on which the error occurred.
+    pub(crate) line: usize,
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid JSON on line {}", self.line)
+    }
+}
+
+impl Error for JsonError {}
+
+/// Parses the JSON document provided.
+pub(crate) fn parse(input: &str) -> Result<JsonValue, JsonError> {
+    let mut parser = Parser {
+        input: input.as_bytes(),
+        position: 0,
+        line: 1,
+        depth: 0,
+    };
+
+    let value = parser.value()?;
+    parser.whitespace();
+
+    // Nothing but whitespace may follow the value
+    if parser.position != parser.input.len() {
+        return Err(parser.error());
+    }
+
+    Ok(value)
+}
+
+/// Writes the string `s` as a JSON string (escaping quotes, backslashes, and
+/// control characters).
+pub(crate) fn write_string(output: &mut String, s: &str) {
+    output.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(output, "\\u{:04x}", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+
+    output.push('"');
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    position: usize,
+    line: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self) -> JsonError {
+        JsonError { line: self.line }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        if self.next() == Some(byte) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(byte) = self.peek() {
+            match byte {
+                b'\n' => self.line += 1,
+                b' ' | b'\t' | b'\r' => {}
+                _ => break,
+            }
+
+            self.position += 1;
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        if self.input[self.position..].starts_with(literal.as_bytes()) {
+            self.position += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn value(&mut self) -> Result<JsonValue, JsonError> {
+        self.whitespace();
+
+        match self.peek() {
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => self.string().map(JsonValue::String),
+            Some(b't') => self.literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.literal("false", JsonValue::Bool(false)),
+            Some(b'n') => self.literal("null", JsonValue::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err(self.error()),
+        }
+    }
+
+    // Parse an array or object, one level deeper than the current value
+    fn nested(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<JsonValue, JsonError>,
+    ) -> Result<JsonValue, JsonError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error());
+        }
+
+        self.depth += 1;
+        let value = f(self);
+        self.depth -= 1;
+
+        value
+    }
+
+    fn object(&mut self) -> Result<JsonValue, JsonError> {
+        let line = self.line;
+        let mut members = Vec::new();
+
+        self.expect(b'{')?;
+        self.whitespace();
+
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(JsonValue::Object(JsonObject { line, members }));
+        }
+
+        loop {
+            self.whitespace();
+            let key = self.string()?;
+
+            self.whitespace();
+            self.expect(b':')?;
+
+            members.push((key, self.value()?));
+
+            self.whitespace();
+
+            match self.next() {
+                Some(b',') => continue,
+                Some(b'}') => return Ok(JsonValue::Object(JsonObject { line, members })),
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<JsonValue, JsonError> {
+        let mut values = Vec::new();
+
+        self.expect(b'[')?;
+        self.whitespace();
+
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(JsonValue::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+            self.whitespace();
+
+            match self.next() {
+                Some(b',') => continue,
+                Some(b']') => return Ok(JsonValue::Array(values)),
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect(b'"')?;
+
+        let mut bytes = Vec::new();
+
+        loop {
+            match self.next().ok_or_else(|| self.error())? {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = match self.next().ok_or_else(|| self.error())? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode_escape()?,
+                        _ => return Err(self.error()),
+                    };
+
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut buffer).as_bytes());
+                }
+                // Control characters must be escaped
+                0x00..=0x1f => return Err(self.error()),
+                byte => bytes.push(byte),
+            }
+        }
+
+        // The input is a `str`, so any unescaped bytes are valid UTF-8
+        String::from_utf8(bytes).map_err(|_| self.error())
+    }
+
+    fn hex4(&mut self) -> Result<u32, JsonError> {
+        let digits = self
+            .input
+            .get(self.position..self.position + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error())?;
+
+        self.position += 4;
+        Ok(digits)
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, JsonError> {
+        let high = self.hex4()?;
+
+        // Characters outside the basic multilingual plane are encoded as a
+        // surrogate pair (e.g., `\ud83d\ude00`)
+        let code = if (0xd800..0xdc00).contains(&high) {
+            self.expect(b'\\')?;
+            self.expect(b'u')?;
+
+            let low = self.hex4()?;
+
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error());
+            }
+
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+
+        char::from_u32(code).ok_or_else(|| self.error())
+    }
+
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.position;
+
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+
+        std::str::from_utf8(&self.input[start..self.position])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .map(JsonValue::Number)
+            .ok_or_else(|| self.error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_parse_valid() {
+        let value =
+            parse("{\"a\": [1, -2.5e1, true, null],\n \"b\": \"x\\u00e9\\ud83d\\ude00\\n\"}")
+                .unwrap();
+
+        assert_eq!(
+            value.get("a"),
+            Some(&JsonValue::Array(vec![
+                JsonValue::Number(1.0),
+                JsonValue::Number(-25.0),
+                JsonValue::Bool(true),
+                JsonValue::Null,
+            ]))
+        );
+        assert_eq!(
+            value.get("b").and_then(JsonValue::as_str),
+            Some("x\u{e9}\u{1f600}\n")
+        );
+    }
+
+    #[test]
+    fn json_parse_invalid() {
+        assert_eq!(parse("{\"a\": 1,\n}"), Err(JsonError { line: 2 }));
+        assert!(parse("[1] 2").is_err());
+        assert!(parse("\"\\x\"").is_err());
+
+        // Deeply nested documents are rejected, rather than overflowing the
+        // stack
+        let nested = |depth| "[".repeat(depth) + &"]".repeat(depth);
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(parse(&nested(MAX_DEPTH + 1)), Err(JsonError { line: 1 }));
+        assert!(parse(&"[".repeat(200_000)).is_err());
+    }
+}
@@ -68,6 +68,17 @@
 //!
 //! Any room that does use Highfield's RNS is referred to as a classroom.
 //!
+//! Highfield also has temporary (i.e., demountable) classrooms, which are not
+//! part of any block -- these are identified by a `T` followed by their
+//! number (e.g., `T1` and `T2`), which is not padded.
+//!
+//! Lessons which take place away from both schools' sites (e.g., at a
+//! swimming pool) are held at an off-site location, identified by its name
+//! prefixed with `@` (e.g., `@Stevenage Pool`) -- *see [`OffSite`] for more
+//! information*. Lessons taught online occupy no room at all, and are held
+//! at a remote lesson location (e.g., `Remote (Teams)`) -- *see [`Remote`]
+//! for more information*.
+//!
 //! ### Fearnhill's Room Numbering Scheme
 //!
 //! Fearnhill has the following room numbering scheme for its classrooms:
@@ -238,9 +249,10 @@
 //! * A break (from work).
 //!
 //! Each activity must span one (or more) periods, and must not partially
-//! utilise a periods (e.g., an activity may not use `1.5` periods), or
-//! span several broken-up periods (e.g., an activity cannot span over
-//! periods two and three as they are broken up by a twenty-minute break).
+//! utilise a periods (e.g., an activity may not use `1.5` periods). An
+//! activity may span periods which are broken up by break or lunch (e.g.,
+//! triple P.E. from period three to period five), but not past the final
+//! period of the day.
 //!
 //! ### Classes
 //!
@@ -269,12 +281,56 @@
 //! [the Highfield school]: https://highfield.herts.sch.uk/
 //! [the Fearnhill school]: https://fearnhill.herts.sch.uk/
 
-pub use activity::{Activity, Class, Subject};
+pub use activity::{Activity, Class, Subject, Teacher};
+pub use afterschool::{
+    AfterSchoolBells, AfterSchoolBooking, AfterSchoolClash, AfterSchoolEvent, AfterSchoolSlot,
+    AfterSchoolTimetable,
+};
+pub use anonymise::AnonymisationPolicy;
+pub use assembly::{Assembly, AssemblyConflict, AssemblyRotation};
+pub use bell::{BellSchedule, BellSchedules};
+pub use calendar::{AcademicCalendar, CycleLength, DayException, Term, WeekCycle};
+pub use compact::CompactTimetable;
+pub use dataset::{Dataset, DatasetError, DatasetErrorKind};
+pub use dayplan::{Attendee, DayPlan, PlanItem};
+pub use definition::{TomlError, TomlErrorKind};
+pub use error::{
+    Error, ParseLocationError, ParseRangedError, ParseRegistryError, ParseYearGroupError,
+    RangeError, TimetableError,
+};
+pub use generate::Profile;
+pub use history::{Change, Record};
+pub use holiday::BankHoliday;
+pub use integrity::{IntegrityCheck, IntegrityFinding, IntegrityIssue, IntegrityReport, Repair};
+pub use intern::{ClassId, Interner, SubjectId, Symbol, TeacherId};
+#[cfg(feature = "other-rooms")]
+pub use location::OtherRoom;
 pub use location::{
-    FearnhillRoom, FearnhillSection, HighfieldBlock, HighfieldFloor, HighfieldRoom, Location,
+    Coordinates, FearnhillRoom, FearnhillSection, HighfieldBlock, HighfieldFloor, HighfieldRoom,
+    Location, LocationParts, OffSite, Remote, School,
 };
+pub use migration::RoomMigration;
+pub use movement::{Movement, MovementGraph};
+#[cfg(feature = "async")]
+pub use observer::ChangeStream;
+pub use proximity::TransitPolicy;
+pub use query::Lessons;
 pub use ranged::*;
+pub use registry::{ParseOptions, Retirement, RoomRegistry};
+pub use reminder::{Reminder, ReminderPolicy};
+pub use render::{Clock, SystemClock};
+pub use room::{LocationFilter, Room, RoomKind};
+pub use rotation::{Rotation, RotationStage};
+pub use scoring::ScoringWeights;
+pub use snapshot::Snapshot;
+pub use stats::{StudentStats, WeekStats, YearGroupStats};
+pub use time::{PeriodTime, TimeOfDay};
 pub use timeslot::{Period, TimeSlot, Week};
+pub use timetable::{Entry, EntryId, Occurrence, RoomOverride, TimedLesson, Timetable};
+pub use travel::TravelIssue;
+pub use undo::EditStack;
+pub use year::{KeyStage, YearGroup};
+pub use zone::London;
 
 mod ranged;
 
@@ -282,6 +338,271 @@ mod ranged;
 /// within the Highfield school and the Fearnhill school.
 mod location;
 
+/// This module contains the error types returned by the crate.
+mod error;
+
+/// This module contains the trait implemented by the rooms which can be
+/// scheduled by a timetable.
+mod room;
+
+/// This module contains the registry of known rooms and the options which
+/// control how strictly rooms are parsed.
+mod registry;
+
+/// This module contains the maps of room renames which migrate stored
+/// locations to their new identifiers.
+mod migration;
+
 mod timeslot;
 
+/// This module contains the year groups and key stages of students.
+mod year;
+
 mod activity;
+
+/// This module contains data structures which map calendar dates onto the
+/// two-week alternating timetable.
+mod calendar;
+
+/// This module contains the data structures used to store timetables and
+/// query the activities which take place on specific dates.
+mod timetable;
+
+/// This module contains the after-school sessions (e.g., detentions) which
+/// take place after the teaching day.
+mod afterschool;
+
+/// This module contains the weekly rotation of year group assemblies.
+mod assembly;
+
+/// This module contains the bell schedules (i.e., the times of each period)
+/// of the Highfield school and the Fearnhill school.
+mod bell;
+
+/// This module contains the times of day (and time ranges) used by the bell
+/// schedules.
+mod time;
+
+/// This module contains the bank holidays of England and Wales (on which the
+/// schools are closed).
+mod holiday;
+
+/// This module contains the time zone of the schools (i.e., `Europe/London`),
+/// such that instants can be converted to and from the times of the bells.
+mod zone;
+
+/// This module contains the gap and contact-time statistics of students'
+/// timetables.
+mod stats;
+
+/// This module contains the room-stickiness scoring of timetables.
+mod scoring;
+
+/// This module contains checks which ensure students and teachers are able to
+/// travel between the schools of consecutive lessons.
+mod travel;
+
+/// This module contains the data structures used to record the history of
+/// changes made to a timetable.
+mod history;
+
+/// This module contains the arena in which the names of subjects, classes,
+/// and teachers can be interned.
+mod intern;
+
+/// This module contains the memory-compact representation of a timetable.
+mod compact;
+
+/// This module contains the canonical JSON interchange format of a complete
+/// dataset.
+mod dataset;
+
+/// This module contains the day plans of staff and visitors (e.g., supply
+/// teachers).
+mod dayplan;
+
+/// This module contains the integrity checks of complete datasets (e.g.,
+/// after hand-edited files are loaded), and their repairs.
+mod integrity;
+
+/// This module contains the anonymisation of datasets, such that they can be
+/// shared without personal data.
+mod anonymise;
+
+/// This module contains the indexes used to look up the lessons of a
+/// timetable by their rooms, teachers, and classes.
+mod index;
+
+/// This module contains the snapshots from which the state of a timetable
+/// can be restored.
+mod snapshot;
+
+/// This module contains the transactions in which batches of edits are
+/// validated before being committed.
+mod transaction;
+
+/// This module contains the stack of edits which allows changes made to a
+/// timetable to be undone and redone.
+mod undo;
+
+/// This module contains the reminders of upcoming lessons.
+mod reminder;
+
+/// This module contains the plain-text renderings of timetables (e.g., the
+/// current week for terminals, and the agenda of a single day).
+mod render;
+
+/// This module allows changes made to a timetable to be observed.
+#[cfg(feature = "std")]
+mod observer;
+
+/// This module contains the rotations of lessons between teachers and rooms
+/// (e.g., half-termly carousels).
+mod rotation;
+
+pub mod export;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+pub mod import;
+
+#[cfg(feature = "fetch")]
+pub mod fetch;
+
+pub mod i18n;
+
+pub mod validate;
+
+pub mod access;
+
+pub mod accessibility;
+
+pub mod allocate;
+
+pub mod archive;
+
+pub mod attendance;
+
+pub mod availability;
+
+pub mod booking;
+
+pub mod bulletin;
+
+pub mod cache;
+
+pub mod closures;
+
+pub mod clubs;
+
+pub mod congestion;
+
+pub mod consortium;
+
+pub mod deadlines;
+
+pub mod delta;
+
+pub mod department;
+
+pub mod displays;
+
+pub mod duty;
+
+pub mod emergency;
+
+pub mod events;
+
+pub mod exam;
+
+pub mod fixtures;
+
+pub mod fuzzy;
+
+pub mod heatmap;
+
+pub mod homework;
+
+pub mod identifiers;
+
+pub mod lint;
+
+pub mod locator;
+
+pub mod occupancy;
+
+pub mod optimise;
+
+pub mod parents_evening;
+
+pub mod persist;
+
+pub mod proposals;
+
+pub mod query;
+
+pub mod seating;
+
+#[cfg(feature = "server")]
+pub mod server;
+
+pub mod signage;
+
+pub mod simulate;
+
+pub mod study;
+
+pub mod swaps;
+
+pub mod sync;
+
+pub mod tenancy;
+
+pub mod testkit;
+
+#[cfg(feature = "webhooks")]
+pub mod webhook;
+
+#[cfg(feature = "serde")]
+pub mod serialise;
+
+/// This module contains the generator of random (but valid) timetables.
+mod generate;
+
+/// This module contains the estimation of the distances between rooms, and
+/// of the times taken to travel between them.
+mod proximity;
+
+/// This module contains the movements of classes between rooms at the change
+/// of lessons.
+mod movement;
+
+/// This module contains the GeoJSON export of the rooms used by a dataset.
+mod geojson;
+
+/// This module contains the seedable random number generator used by the
+/// randomised algorithms.
+mod random;
+
+/// This module contains the SHA-256 and HMAC-SHA-256 implementations used to
+/// check the integrity of update bundles and to sign webhook deliveries.
+mod hmac;
+
+/// This module contains a minimal CSV reader (used by the importers) and
+/// writer.
+mod csv;
+
+/// This module contains a minimal JSON parser used by the importers.
+mod json;
+
+/// This module contains the human-editable TOML format in which timetables
+/// can be defined by hand.
+mod definition;
+
+/// This module contains a minimal TOML parser used by the timetable
+/// definition format.
+mod toml;
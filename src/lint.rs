@@ -0,0 +1,308 @@
+//! Feasibility linting of draft timetables (e.g., those imported from a
+//! school management system).
+//!
+//! Whereas a [`Validator`](crate::validate::Validator) enforces the rules a deployment chooses, a
+//! [`Linter`] runs every rule which can be checked without further
+//! configuration, followed by heuristic checks for "smells" -- timetables
+//! which are feasible, but which are unlikely to be intended:
+//!
+//! * a teacher who teaches every period of a day (*see [`MaxConsecutive`]*);
+//! * a class which uses three (or more) rooms in one morning (*see
+//!   [`RoomHopping`]*); and
+//! * a P.E. lesson scheduled in a classroom (*see [`SportInClassroom`]*).
+//!
+//! The findings are sorted by severity (most severe first), and the
+//! [`Report`] can be converted into JSON for CI-style checking:
+//!
+//! ```
+//! use timetableau::lint;
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, Subject, Timetable};
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+//!     subject: Subject::new("PE".to_string()).unwrap(),
+//!     class: Class::new("10A".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "H201".parse().unwrap(),
+//! });
+//!
+//! let report = lint::lint(&Dataset::new(timetable, AcademicCalendar::default()));
+//!
+//! assert!(report.is_valid());
+//! assert_eq!(report.findings[0].rule, "sport-in-classroom");
+//! assert!(report.to_json().starts_with("{\"valid\":true,\"findings\":[{\"rule\":\"sport-in-classroom\""));
+//! ```
+
+use crate::validate::{
+    Finding, MaxConsecutive, NoClashes, Report, RoomsNeeded, Rule, Severity, TravelTime,
+};
+use crate::{
+    Activity, Dataset, EntryId, Location, Period, Room, RoomKind, Timetable, TransitPolicy,
+};
+use std::collections::{BTreeMap, HashSet};
+
+// The number of periods of a day (taught consecutively by a teacher who
+// teaches every period of a day)
+const PERIODS: usize = 5;
+
+/// Reports each class which uses at least a number of distinct rooms (`3`
+/// by default) during the morning of a day (i.e., the first three periods).
+///
+/// Each such morning is reported as a [`Severity::Warning`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoomHopping {
+    rooms: usize,
+}
+
+impl Default for RoomHopping {
+    fn default() -> Self {
+        Self { rooms: 3 }
+    }
+}
+
+impl RoomHopping {
+    /// Creates a new `RoomHopping` rule reporting classes which use at least
+    /// `rooms` distinct rooms in one morning.
+    pub fn new(rooms: usize) -> Self {
+        Self { rooms }
+    }
+}
+
+impl Rule for RoomHopping {
+    fn name(&self) -> &'static str {
+        "room-hopping"
+    }
+
+    fn check(&self, timetable: &Timetable) -> Vec<Finding> {
+        // The lessons (and rooms) of each class during each morning
+        let mut mornings: BTreeMap<(&str, usize), Vec<(EntryId, Location)>> = BTreeMap::new();
+
+        for (id, entry) in timetable.lessons() {
+            if let Activity::Lesson {
+                class, location, ..
+            } = &entry.activity
+            {
+                // The first slot of a lesson spanning multiple periods is
+                // its earliest, so it suffices to determine whether the lesson
+                // takes place during the morning
+                if entry.slot.period <= Period::Third {
+                    mornings
+                        .entry((class.reference(), entry.slot.index() / 5))
+                        .or_default()
+                        .push((id, *location));
+                }
+            }
+        }
+
+        mornings
+            .into_iter()
+            .filter_map(|((class, _), lessons)| {
+                let rooms: HashSet<Location> =
+                    lessons.iter().map(|(_, location)| *location).collect();
+
+                (rooms.len() >= self.rooms).then(|| Finding {
+                    rule: self.name(),
+                    severity: Severity::Warning,
+                    entries: lessons.iter().map(|(id, _)| *id).collect(),
+                    message: format!("{} uses {} rooms in one morning", class, rooms.len()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reports each lesson of a sport subject (e.g., `PE`) which takes place in
+/// a [classroom](RoomKind::Classroom).
+///
+/// Each such lesson is reported as a [`Severity::Warning`] -- subjects are
+/// matched ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SportInClassroom {
+    subjects: HashSet<String>,
+}
+
+impl Default for SportInClassroom {
+    fn default() -> Self {
+        Self::new(["PE", "P.E.", "Games", "Physical Education"])
+    }
+}
+
+impl SportInClassroom {
+    /// Creates a new `SportInClassroom` rule, where `subjects` are the names
+    /// of the sport subjects (`PE`, `P.E.`, `Games`, and `Physical
+    /// Education` by default).
+    pub fn new<S>(subjects: S) -> Self
+    where
+        S: IntoIterator,
+        S::Item: Into<String>,
+    {
+        Self {
+            subjects: subjects
+                .into_iter()
+                .map(|subject| subject.into().to_lowercase())
+                .collect(),
+        }
+    }
+}
+
+impl Rule for SportInClassroom {
+    fn name(&self) -> &'static str {
+        "sport-in-classroom"
+    }
+
+    fn check(&self, timetable: &Timetable) -> Vec<Finding> {
+        timetable
+            .lessons()
+            .filter_map(|(id, entry)| match &entry.activity {
+                Activity::Lesson {
+                    subject, location, ..
+                } if location.kind() == RoomKind::Classroom
+                    && self.subjects.contains(&subject.name().to_lowercase()) =>
+                {
+                    Some(Finding {
+                        rule: self.name(),
+                        severity: Severity::Warning,
+                        entries: vec![id],
+                        message: format!("{} takes place in the classroom {}", subject, location),
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Lints datasets against a set of rules.
+///
+/// A new `Linter` contains the default rules -- *see the [module](self)
+/// documentation for more information*.
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    transit: TransitPolicy,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            transit: TransitPolicy::default(),
+        }
+        .rule(NoClashes)
+        .rule(RoomsNeeded)
+        .rule(MaxConsecutive::new(PERIODS - 1))
+        .rule(RoomHopping::default())
+        .rule(SportInClassroom::default())
+    }
+}
+
+impl Linter {
+    /// Creates a new `Linter` with the default rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `rule` to the `Linter` (e.g., a
+    /// [`RoomCapacity`](crate::validate::RoomCapacity) rule, which requires
+    /// the sizes of the classes).
+    pub fn rule<R>(mut self, rule: R) -> Self
+    where
+        R: Rule + 'static,
+    {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Sets the policy whose time to travel between the sites is required
+    /// by the [`TravelTime`] rule (the [default](TransitPolicy::default)
+    /// policy by default).
+    pub fn transit(mut self, transit: TransitPolicy) -> Self {
+        self.transit = transit;
+        self
+    }
+
+    /// Lints the `dataset` provided, returning the findings of every rule
+    /// sorted by severity (most severe first, and otherwise in the order in
+    /// which the rules were added).
+    ///
+    /// In addition to the rules of the `Linter`, the timetable is checked
+    /// against the [`TravelTime`] rule (*see [`Self::transit`]*), using the
+    /// bell schedules of the dataset's calendar.
+    pub fn lint(&self, dataset: &Dataset) -> Report {
+        let travel = TravelTime::with_policy(*dataset.calendar.bell_schedules(), &self.transit);
+
+        let mut findings: Vec<Finding> = self
+            .rules
+            .iter()
+            .map(Box::as_ref)
+            .chain([&travel as &dyn Rule])
+            .flat_map(|rule| rule.check(&dataset.timetable))
+            .collect();
+
+        // The sort is stable, so the findings of each severity remain in the
+        // order of their rules
+        findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+
+        Report { findings }
+    }
+}
+
+/// Lints the `dataset` provided with the default rules (*see
+/// [`Linter::lint`]*).
+pub fn lint(dataset: &Dataset) -> Report {
+    Linter::new().lint(dataset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Subject, Teacher};
+
+    fn lesson(subject: &str, class: &str, teacher: &str, room: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new(subject.to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Teacher::new(teacher.to_string()).ok(),
+            location: room.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn draft_linted() {
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("Ma", "10A", "JSM", "H201"));
+        timetable.insert(timeslot!(W1MP2), lesson("En", "10A", "JSM", "H202"));
+        timetable.insert(timeslot!(W1MP3), lesson("Games", "10A", "JSM", "P105"));
+        timetable.insert(timeslot!(W1MP4), lesson("Ma", "11B", "JSM", "H201"));
+        timetable.insert(timeslot!(W1MP5), lesson("Ma", "11C", "JSM", "H201"));
+        timetable.insert(timeslot!(W1MP5), lesson("Ma", "11D", "ABC", "H201"));
+
+        let report = lint(&Dataset::new(timetable, AcademicCalendar::default()));
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            report
+                .findings
+                .iter()
+                .map(|finding| (finding.severity, finding.rule))
+                .collect::<Vec<_>>(),
+            [
+                (Severity::Error, "no-clashes"),
+                (Severity::Warning, "max-consecutive"),
+                (Severity::Warning, "room-hopping"),
+                (Severity::Warning, "sport-in-classroom"),
+            ]
+        );
+        assert_eq!(
+            report.to_json(),
+            "{\"valid\":false,\"findings\":[\
+             {\"rule\":\"no-clashes\",\"severity\":\"error\",\"entries\":[4,5],\
+             \"message\":\"H201 hosts 2 lessons at once\"},\
+             {\"rule\":\"max-consecutive\",\"severity\":\"warning\",\"entries\":[0,1,2,3,4],\
+             \"message\":\"JSM teaches 5 consecutive periods (at most 4 are permitted)\"},\
+             {\"rule\":\"room-hopping\",\"severity\":\"warning\",\"entries\":[0,1,2],\
+             \"message\":\"10A uses 3 rooms in one morning\"},\
+             {\"rule\":\"sport-in-classroom\",\"severity\":\"warning\",\"entries\":[2],\
+             \"message\":\"Games takes place in the classroom P105\"}]}"
+        );
+    }
+}
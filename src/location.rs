@@ -1,31 +1,64 @@
-use crate::RangedU8;
+use crate::{ParseLocationError, RangedU8};
 use std::fmt::{self, Debug, Display, Formatter, Write};
+use std::str::FromStr;
 
 /// A block at the Highfield school.
 ///
 /// *See the [`crate`] documentation for more information*
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HighfieldBlock {
     Howard,
     Parker,
     Unwin,
 }
 
+impl HighfieldBlock {
+    /// Retrieves the full name of the `HighfieldBlock` (e.g., `Howard`).
+    pub fn full_name(self) -> &'static str {
+        match self {
+            Self::Howard => "Howard",
+            Self::Parker => "Parker",
+            Self::Unwin => "Unwin",
+        }
+    }
+
+    /// Retrieves the code of the `HighfieldBlock` (e.g., `H`) -- this is the
+    /// first character of the identifier of each classroom in the block.
+    pub fn code(self) -> char {
+        match self {
+            Self::Howard => 'H',
+            Self::Parker => 'P',
+            Self::Unwin => 'U',
+        }
+    }
+}
+
 impl Display for HighfieldBlock {
     // Format the HighfieldBlock (use that block's identifier)
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        use HighfieldBlock::*;
+        f.write_char(self.code())
+    }
+}
 
-        match self {
-            Howard => f.write_char('H'),
-            Parker => f.write_char('P'),
-            Unwin => f.write_char('U'),
-        }
+impl FromStr for HighfieldBlock {
+    type Err = ParseLocationError;
+
+    /// Parses a `HighfieldBlock` from either its [code](Self::code) (e.g.,
+    /// `H`) or its [full name](Self::full_name) (e.g., `Howard`, ignoring
+    /// case).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Self::Howard, Self::Parker, Self::Unwin]
+            .into_iter()
+            .find(|block| {
+                s.len() == 1 && s.starts_with(block.code())
+                    || s.eq_ignore_ascii_case(block.full_name())
+            })
+            .ok_or(ParseLocationError(None))
     }
 }
 
 /// A floor of a [`HighfieldBlock`].
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HighfieldFloor {
     /// The ground floor of a block.
     Ground,
@@ -51,8 +84,74 @@ impl Display for HighfieldFloor {
     }
 }
 
+impl HighfieldFloor {
+    /// Retrieves the number of the `HighfieldFloor` (`0` for the ground
+    /// floor).
+    pub fn number(self) -> u8 {
+        match self {
+            Self::Ground => 0,
+            Self::Level(level) => level.get(),
+        }
+    }
+
+    /// Creates an iterator over the upper levels of a block (i.e., every
+    /// floor other than the ground floor), from the lowest upwards:
+    ///
+    /// ```
+    /// use timetableau::HighfieldFloor;
+    ///
+    /// for floor in HighfieldFloor::levels() {
+    ///     assert_ne!(floor, HighfieldFloor::Ground);
+    /// }
+    ///
+    /// assert_eq!(HighfieldFloor::levels().count(), 9);
+    /// ```
+    pub fn levels() -> impl DoubleEndedIterator<Item = Self> + Clone {
+        RangedU8::all().map(Self::Level)
+    }
+
+    /// Creates an iterator over every floor of a block, from the ground
+    /// floor upwards (*see [`Self::levels`]*).
+    pub fn all() -> impl DoubleEndedIterator<Item = Self> + Clone {
+        std::iter::once(Self::Ground).chain(Self::levels())
+    }
+}
+
+/// Converts a floor number into a `HighfieldFloor` (`0` being the ground
+/// floor).
+///
+/// An error is returned should the number be greater than `9`.
+impl TryFrom<u8> for HighfieldFloor {
+    type Error = ();
+
+    fn try_from(number: u8) -> Result<Self, Self::Error> {
+        match number {
+            0 => Ok(Self::Ground),
+            number => RangedU8::new(number).map(Self::Level).ok_or(()),
+        }
+    }
+}
+
+impl FromStr for HighfieldFloor {
+    type Err = ParseLocationError;
+
+    /// Parses a `HighfieldFloor` from either the form produced by `Display`
+    /// (e.g., `G` or `3`) or its [number](Self::number) (e.g., `0`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "G" => Ok(Self::Ground),
+            _ if s.len() == 1 => s
+                .parse::<u8>()
+                .ok()
+                .and_then(|number| Self::try_from(number).ok())
+                .ok_or(ParseLocationError(None)),
+            _ => Err(ParseLocationError(None)),
+        }
+    }
+}
+
 /// A room at the Highfield school.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 // non_exhaustive is used for two reasons:
 //  1. An exhaustive list of all of Highfield's rooms has not yet been
 //     obtained
@@ -65,6 +164,18 @@ pub enum HighfieldRoom {
     /// The sports hall (generally used for P.E.).
     SportsHall,
 
+    /// The playing field at Highfield (used for outdoor P.E.).
+    Field,
+
+    /// The artificial turf (astro) pitch at Highfield.
+    AstroPitch,
+
+    /// The tennis courts at Highfield.
+    TennisCourts,
+
+    /// The playground at Highfield.
+    Playground,
+
     /// A classroom at the Highfield school.
     Classroom {
         /// The block in which the room is located.
@@ -82,6 +193,27 @@ pub enum HighfieldRoom {
         /// *See the [`crate`] documentation for more information*.
         discriminator: RangedU8<1, 99>,
     },
+
+    /// A temporary (i.e., demountable) classroom at the Highfield school,
+    /// which is not part of any block -- its identifier is `T` followed by
+    /// its number (e.g., `T1`).
+    Temporary(RangedU8<1, 99>),
+
+    /// A location away from the sites of both schools at which the Highfield
+    /// school holds lessons (e.g., a swimming pool).
+    OffSite(OffSite),
+
+    /// A lesson taught online by the Highfield school (*see [`Remote`]*).
+    Remote(Remote),
+
+    /// A room at the Highfield school which is yet to be decided (e.g., in
+    /// a draft timetable) -- its identifier is `TBC`.
+    ToBeConfirmed,
+
+    /// A room which is not modelled by the room numbering scheme of the
+    /// Highfield school (e.g., a legacy room identifier).
+    #[cfg(feature = "other-rooms")]
+    Other(OtherRoom),
 }
 
 impl Display for HighfieldRoom {
@@ -94,6 +226,10 @@ impl Display for HighfieldRoom {
         match self {
             Hall => f.write_str("Hall"),
             SportsHall => f.write_str("Sports Hall"),
+            Field => f.write_str("Field"),
+            AstroPitch => f.write_str("Astro Pitch"),
+            TennisCourts => f.write_str("Tennis Courts"),
+            Playground => f.write_str("Playground"),
             Classroom {
                 block,
                 floor,
@@ -111,6 +247,12 @@ impl Display for HighfieldRoom {
                 // have to worry about it
                 write!(f, "{:0>2}", discriminator.get())
             }
+            Temporary(number) => write!(f, "T{}", number.get()),
+            OffSite(site) => Display::fmt(site, f),
+            Remote(remote) => Display::fmt(remote, f),
+            ToBeConfirmed => f.write_str("TBC"),
+            #[cfg(feature = "other-rooms")]
+            Other(room) => Display::fmt(room, f),
         }
     }
 }
@@ -118,7 +260,7 @@ impl Display for HighfieldRoom {
 /// A section at the Fearnhill school.
 ///
 /// *See the [`crate`] documentation for more information*.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FearnhillSection {
     Science,
     Business,
@@ -132,29 +274,70 @@ pub enum FearnhillSection {
     IT,
 }
 
-impl Display for FearnhillSection {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+impl FearnhillSection {
+    /// Retrieves the full name of the `FearnhillSection` (e.g.,
+    /// `Mathematics`).
+    pub fn full_name(self) -> &'static str {
         use FearnhillSection::*;
 
         match self {
-            Science => f.write_str("S"),
-            Business => f.write_str("B"),
-            PSHE => f.write_str("P"),
-            Languages => f.write_str("L"),
-            Technology => f.write_str("T"),
-            Mathematics => f.write_str("M"),
-            English => f.write_str("E"),
-            Music => f.write_str("Mu"),
-            Humanities => f.write_str("H"),
-            IT => f.write_str("I"),
+            Science => "Science",
+            Business => "Business",
+            PSHE => "PSHE",
+            Languages => "Languages",
+            Technology => "Technology",
+            Mathematics => "Mathematics",
+            English => "English",
+            Music => "Music",
+            Humanities => "Humanities",
+            IT => "IT",
         }
     }
+
+    /// Retrieves the code of the `FearnhillSection` (e.g., `M`) -- this is
+    /// the prefix of the identifier of each classroom in the section.
+    pub fn code(self) -> &'static str {
+        use FearnhillSection::*;
+
+        match self {
+            Science => "S",
+            Business => "B",
+            PSHE => "P",
+            Languages => "L",
+            Technology => "T",
+            Mathematics => "M",
+            English => "E",
+            Music => "Mu",
+            Humanities => "H",
+            IT => "I",
+        }
+    }
+}
+
+impl Display for FearnhillSection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for FearnhillSection {
+    type Err = ParseLocationError;
+
+    /// Parses a `FearnhillSection` from either its [code](Self::code) (e.g.,
+    /// `M`) or its [full name](Self::full_name) (e.g., `Mathematics`,
+    /// ignoring case).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FEARNHILL_SECTIONS
+            .into_iter()
+            .find(|section| s == section.code() || s.eq_ignore_ascii_case(section.full_name()))
+            .ok_or(ParseLocationError(None))
+    }
 }
 
 /// A room at the Fearnhill school.
 ///
 /// *See the [`crate`] documentation for more information*.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 // non_exhaustive is used for two reasons:
 //  1. An exhaustive list of all Fearnhill's rooms has yet to be obtained
 //  2. Fearnhill may add additional rooms at any time (and, as a result,
@@ -173,6 +356,18 @@ pub enum FearnhillRoom {
     /// The drama studio at Fearnhill.
     DramaStudio,
 
+    /// The playing field at Fearnhill (used for outdoor P.E.).
+    Field,
+
+    /// The artificial turf (astro) pitch at Fearnhill.
+    AstroPitch,
+
+    /// The tennis courts at Fearnhill.
+    TennisCourts,
+
+    /// The playground at Fearnhill.
+    Playground,
+
     /// A classroom at Fearnhill.
     ///
     /// *See the [`crate`] documentation for more information*.
@@ -187,6 +382,23 @@ pub enum FearnhillRoom {
         /// identifiers).
         discriminator: RangedU8<1, 99>,
     },
+
+    /// A location away from the sites of both schools at which the Fearnhill
+    /// school holds lessons (e.g., a partner school of the sixth-form
+    /// consortium).
+    OffSite(OffSite),
+
+    /// A lesson taught online by the Fearnhill school (*see [`Remote`]*).
+    Remote(Remote),
+
+    /// A room at the Fearnhill school which is yet to be decided (e.g., in
+    /// a draft timetable) -- its identifier is `FH TBC`.
+    ToBeConfirmed,
+
+    /// A room which is not modelled by the room numbering scheme of the
+    /// Fearnhill school (e.g., a legacy room identifier).
+    #[cfg(feature = "other-rooms")]
+    Other(OtherRoom),
 }
 
 impl Display for FearnhillRoom {
@@ -198,6 +410,10 @@ impl Display for FearnhillRoom {
             Gym => f.write_str("Gym"),
             DanceStudio => f.write_str("Dance Studio"),
             DramaStudio => f.write_str("Drama Studio"),
+            Field => f.write_str("Field"),
+            AstroPitch => f.write_str("Astro Pitch"),
+            TennisCourts => f.write_str("Tennis Courts"),
+            Playground => f.write_str("Playground"),
             Classroom {
                 section,
                 discriminator,
@@ -205,13 +421,426 @@ impl Display for FearnhillRoom {
                 Display::fmt(section, f)?;
                 Display::fmt(&discriminator.get(), f)
             }
+            OffSite(site) => Display::fmt(site, f),
+            Remote(remote) => Display::fmt(remote, f),
+            ToBeConfirmed => f.write_str("TBC"),
+            #[cfg(feature = "other-rooms")]
+            Other(room) => Display::fmt(room, f),
+        }
+    }
+}
+
+/// The identifier of a room which is not modelled by either school's room
+/// numbering scheme.
+///
+/// Identifiers are stored inline (rather than in a [`String`]), such that
+/// rooms remain [`Copy`] -- an identifier must consist of between `1` and
+/// `23` printable ASCII characters.
+///
+/// # Remarks
+///
+/// `OtherRoom`s are only produced when explicitly requested (*see
+/// [`Location::parse_or_other`]*) -- parsing a `Location` using [`FromStr`]
+/// never produces one, and an identifier which is valid under a room
+/// numbering scheme is always parsed as that room instead.
+#[cfg(feature = "other-rooms")]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OtherRoom {
+    len: u8,
+    bytes: [u8; 23],
+}
+
+#[cfg(feature = "other-rooms")]
+impl OtherRoom {
+    /// Creates a new `OtherRoom` with the identifier `code`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should the identifier be empty, longer than `23`
+    /// characters, or contain a character which is not printable ASCII.
+    pub fn new(code: &str) -> Result<Self, ParseLocationError> {
+        let valid = (1..=23).contains(&code.len())
+            && code
+                .bytes()
+                .all(|byte| byte.is_ascii_graphic() || byte == b' ');
+
+        if !valid {
+            return Err(ParseLocationError(None));
+        }
+
+        let mut bytes = [0; 23];
+        bytes[..code.len()].copy_from_slice(code.as_bytes());
+
+        Ok(Self {
+            len: code.len() as u8,
+            bytes,
+        })
+    }
+
+    /// Retrieves the identifier of the `OtherRoom`.
+    pub fn as_str(&self) -> &str {
+        // The identifier is validated as ASCII upon construction
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap()
+    }
+}
+
+#[cfg(feature = "other-rooms")]
+impl Debug for OtherRoom {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OtherRoom").field(&self.as_str()).finish()
+    }
+}
+
+#[cfg(feature = "other-rooms")]
+impl Display for OtherRoom {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A location away from the sites of both schools at which lessons take
+/// place (e.g., a swimming pool, a partner school of the sixth-form
+/// consortium, or a work experience placement).
+///
+/// As with the identifier of an `OtherRoom`, the name of an off-site
+/// location is stored inline (rather than in a [`String`]), such that rooms
+/// remain [`Copy`] -- a name must consist of between `1` and `23` printable
+/// ASCII characters, and must not contain parentheses. An off-site location
+/// may also carry its [`Coordinates`], whereas its address (which does not fit
+/// inline) is recorded in a [`RoomRegistry`](crate::RoomRegistry) -- *see
+/// [`RoomRegistry::set_address`](crate::RoomRegistry::set_address)*.
+///
+/// The identifier of an off-site location is its name prefixed with `@`,
+/// followed by its coordinates (should it have any) in parentheses:
+///
+/// ```
+/// use timetableau::{Coordinates, Location, OffSite};
+///
+/// let pool = OffSite::new("Stevenage Pool")
+///     .unwrap()
+///     .at(Coordinates::new(51.9012, -0.2021).unwrap());
+///
+/// assert_eq!(pool.to_string(), "@Stevenage Pool (51.901200, -0.202100)");
+///
+/// // As with rooms, the off-site locations of the Fearnhill school are
+/// // prefixed with `FH `
+/// let consortium: Location = "FH @Barclay Academy".parse().unwrap();
+///
+/// assert_eq!(consortium.off_site().unwrap().name(), "Barclay Academy");
+/// ```
+///
+/// # Remarks
+///
+/// An off-site location is a [site](crate::Room::site) of its own (named
+/// after the location), such that the [travel
+/// checks](crate::Timetable::travel_issues) require time to travel to and
+/// from it -- even from a room of the school which holds the lesson.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OffSite {
+    len: u8,
+    bytes: [u8; 23],
+    coordinates: Option<Coordinates>,
+}
+
+impl OffSite {
+    /// Creates a new `OffSite` location with the `name` provided (and without
+    /// any coordinates).
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should the name be empty, longer than `23`
+    /// characters, or contain a character which is not printable ASCII (or
+    /// is a parenthesis).
+    pub fn new(name: &str) -> Result<Self, ParseLocationError> {
+        let valid = (1..=23).contains(&name.len())
+            && !name.starts_with(' ')
+            && !name.ends_with(' ')
+            && name.bytes().all(|byte| {
+                (byte.is_ascii_graphic() || byte == b' ') && byte != b'(' && byte != b')'
+            });
+
+        if !valid {
+            return Err(ParseLocationError(None));
+        }
+
+        let mut bytes = [0; 23];
+        bytes[..name.len()].copy_from_slice(name.as_bytes());
+
+        Ok(Self {
+            len: name.len() as u8,
+            bytes,
+            coordinates: None,
+        })
+    }
+
+    /// Sets the [`Coordinates`] of the `OffSite` location.
+    pub fn at(mut self, coordinates: Coordinates) -> Self {
+        self.coordinates = Some(coordinates);
+        self
+    }
+
+    /// Retrieves the name of the `OffSite` location (e.g., `Stevenage
+    /// Pool`).
+    pub fn name(&self) -> &str {
+        // The name is validated as ASCII upon construction
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap()
+    }
+
+    /// Retrieves the [`Coordinates`] of the `OffSite` location (if any).
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        self.coordinates
+    }
+}
+
+impl Debug for OffSite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OffSite")
+            .field("name", &self.name())
+            .field("coordinates", &self.coordinates)
+            .finish()
+    }
+}
+
+impl Display for OffSite {
+    // Format the OffSite such that it prints its identifier (e.g.,
+    // `@Stevenage Pool (51.901200, -0.202100)`)
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}", self.name())?;
+
+        if let Some(coordinates) = self.coordinates {
+            write!(f, " ({})", coordinates)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for OffSite {
+    type Err = ParseLocationError;
+
+    // Parse an identifier in the same format as produced by `Display`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix('@').ok_or(ParseLocationError(None))?;
+
+        match s.strip_suffix(')').and_then(|s| s.split_once(" (")) {
+            Some((name, coordinates)) => Ok(Self::new(name)?.at(coordinates.parse()?)),
+            None => Self::new(s),
+        }
+    }
+}
+
+/// The location of a lesson which is taught online (rather than in a room),
+/// optionally labelled with the platform on which it is taught (e.g.,
+/// `Teams`).
+///
+/// A remote lesson occupies no room, so any number of remote lessons can be
+/// taught during the same period without clashing (*see
+/// [`NoClashes`](crate::validate::NoClashes)*), and no time is required to
+/// travel to or from one (*see
+/// [`Timetable::travel_issues`](crate::Timetable::travel_issues)*).
+///
+/// The identifier of a remote lesson is `Remote`, followed by its label (should
+/// it have one) in parentheses -- as with the rooms of the Fearnhill school,
+/// the remote lessons of the Fearnhill school are prefixed with `FH `:
+///
+/// ```
+/// use timetableau::{Location, Remote, RoomKind, Room};
+///
+/// let remote: Location = "FH Remote (Teams)".parse().unwrap();
+///
+/// assert_eq!(remote.kind(), RoomKind::Remote);
+/// assert_eq!(Remote::new(Some("Teams")).unwrap().to_string(), "Remote (Teams)");
+/// assert_eq!(Remote::new(None).unwrap().label(), None);
+/// ```
+///
+/// # Remarks
+///
+/// As with the name of an [`OffSite`] location, the label is stored inline
+/// -- a label must consist of between `1` and `23` printable ASCII
+/// characters, and must not contain parentheses (a link to the lesson, which
+/// rarely fits, is better shared with the class directly).
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Remote {
+    // A length of `0` represents the absence of a label
+    len: u8,
+    bytes: [u8; 23],
+}
+
+impl Remote {
+    /// Creates a new `Remote` lesson location with the (optional) `label`
+    /// provided.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should the label be empty, longer than `23`
+    /// characters, or contain a character which is not printable ASCII (or
+    /// is a parenthesis).
+    pub fn new(label: Option<&str>) -> Result<Self, ParseLocationError> {
+        let mut remote = Self {
+            len: 0,
+            bytes: [0; 23],
+        };
+
+        if let Some(label) = label {
+            // Labels follow the same rules as the names of off-site locations
+            let name = OffSite::new(label)?;
+
+            remote.len = name.len;
+            remote.bytes = name.bytes;
+        }
+
+        Ok(remote)
+    }
+
+    /// Retrieves the label of the `Remote` lesson location (e.g., `Teams`).
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the location has no label.
+    pub fn label(&self) -> Option<&str> {
+        // The label is validated as ASCII upon construction
+        (self.len > 0).then(|| std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap())
+    }
+}
+
+impl Debug for Remote {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Remote").field(&self.label()).finish()
+    }
+}
+
+impl Display for Remote {
+    // Format the Remote such that it prints its identifier (e.g.,
+    // `Remote (Teams)`)
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Remote")?;
+
+        if let Some(label) = self.label() {
+            write!(f, " ({})", label)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Remote {
+    type Err = ParseLocationError;
+
+    // Parse an identifier in the same format as produced by `Display`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("Remote").ok_or(ParseLocationError(None))?;
+
+        if s.is_empty() {
+            return Self::new(None);
+        }
+
+        s.strip_prefix(" (")
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or(ParseLocationError(None))
+            .and_then(|label| Self::new(Some(label)))
+    }
+}
+
+/// The geographic coordinates (i.e., the latitude and longitude, in degrees)
+/// of an [`OffSite`] location.
+///
+/// Coordinates are stored to the nearest millionth of a degree (roughly ten
+/// centimetres), such that they can be compared and hashed exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Coordinates {
+    // Both in millionths of a degree
+    latitude: i32,
+    longitude: i32,
+}
+
+impl Coordinates {
+    /// Creates new `Coordinates` from a `latitude` and `longitude` (in
+    /// degrees).
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the latitude is not between `-90` and `90`, or the
+    /// longitude is not between `-180` and `180`.
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return None;
+        }
+
+        Some(Self {
+            latitude: (latitude * 1e6).round() as i32,
+            longitude: (longitude * 1e6).round() as i32,
+        })
+    }
+
+    /// Retrieves the latitude of the `Coordinates` (in degrees).
+    pub fn latitude(self) -> f64 {
+        self.latitude as f64 / 1e6
+    }
+
+    /// Retrieves the longitude of the `Coordinates` (in degrees).
+    pub fn longitude(self) -> f64 {
+        self.longitude as f64 / 1e6
+    }
+}
+
+impl Display for Coordinates {
+    // Format the Coordinates as `latitude, longitude`, each to six decimal
+    // places (i.e., exactly as stored)
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let degrees = |value: i32| {
+            let sign = if value < 0 { "-" } else { "" };
+            let value = value.unsigned_abs();
+
+            format!("{}{}.{:0>6}", sign, value / 1_000_000, value % 1_000_000)
+        };
+
+        write!(f, "{}, {}", degrees(self.latitude), degrees(self.longitude))
+    }
+}
+
+impl FromStr for Coordinates {
+    type Err = ParseLocationError;
+
+    // Parse coordinates in the same format as produced by `Display`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (latitude, longitude) = s.split_once(", ").ok_or(ParseLocationError(None))?;
+        let degrees = |value: &str| value.parse::<f64>().map_err(|_| ParseLocationError(None));
+
+        Self::new(degrees(latitude)?, degrees(longitude)?).ok_or(ParseLocationError(None))
+    }
+}
+
+/// A school within the consortium.
+///
+/// *See the [`crate`] documentation for more information*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum School {
+    /// The Highfield school.
+    Highfield,
+
+    /// The Fearnhill school.
+    Fearnhill,
+}
+
+impl School {
+    /// Retrieves the name of the `School` (e.g., `Highfield`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Highfield => "Highfield",
+            Self::Fearnhill => "Fearnhill",
         }
     }
 }
 
+impl Display for School {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// A location of a room (in which a lesson can take place) in either the
 /// Highfield school or the Fearnhill school.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Location {
     /// The location of a room at the Highfield school.
     Highfield(HighfieldRoom),
@@ -236,3 +865,1420 @@ impl Display for Location {
         }
     }
 }
+
+impl Location {
+    /// Retrieves the verbose name of the `Location` (e.g., `Howard Block,
+    /// Floor 2, Room 01` rather than `H201`).
+    ///
+    /// Verbose names are intended for persons unfamiliar with the room
+    /// numbering schemes of the schools -- *see the [`crate`] documentation
+    /// for more information*.
+    pub fn verbose_name(&self) -> String {
+        match self {
+            Self::Highfield(HighfieldRoom::Hall) => "Highfield Hall".to_string(),
+            Self::Highfield(HighfieldRoom::SportsHall) => "Highfield Sports Hall".to_string(),
+            Self::Highfield(
+                room @ (HighfieldRoom::Field
+                | HighfieldRoom::AstroPitch
+                | HighfieldRoom::TennisCourts
+                | HighfieldRoom::Playground),
+            ) => format!("Highfield {}", room),
+            Self::Highfield(HighfieldRoom::Classroom {
+                block,
+                floor,
+                discriminator,
+            }) => {
+                let floor = match floor {
+                    HighfieldFloor::Ground => "Ground Floor".to_string(),
+                    HighfieldFloor::Level(level) => format!("Floor {}", level.get()),
+                };
+
+                format!(
+                    "{} Block, {}, Room {:0>2}",
+                    block.full_name(),
+                    floor,
+                    discriminator.get()
+                )
+            }
+            Self::Highfield(HighfieldRoom::Temporary(number)) => {
+                format!("Highfield Temporary Classroom {}", number.get())
+            }
+            Self::Fearnhill(FearnhillRoom::Classroom {
+                section,
+                discriminator,
+            }) => format!(
+                "Fearnhill {}, Room {}",
+                section.full_name(),
+                discriminator.get()
+            ),
+            Self::Highfield(HighfieldRoom::OffSite(site))
+            | Self::Fearnhill(FearnhillRoom::OffSite(site)) => {
+                format!("{} (Off-Site)", site.name())
+            }
+            Self::Highfield(HighfieldRoom::Remote(remote))
+            | Self::Fearnhill(FearnhillRoom::Remote(remote)) => match remote.label() {
+                Some(label) => format!("Remote Lesson ({})", label),
+                None => "Remote Lesson".to_string(),
+            },
+            Self::Highfield(HighfieldRoom::ToBeConfirmed) => {
+                "Highfield, Room To Be Confirmed".to_string()
+            }
+            Self::Fearnhill(FearnhillRoom::ToBeConfirmed) => {
+                "Fearnhill, Room To Be Confirmed".to_string()
+            }
+            #[cfg(feature = "other-rooms")]
+            Self::Highfield(HighfieldRoom::Other(room)) => format!("Highfield {}", room),
+            Self::Fearnhill(room) => format!("Fearnhill {}", room),
+        }
+    }
+
+    /// Retrieves a description of the `Location` which reads naturally when
+    /// spoken (e.g., by a screen reader, or by an announcement system), in
+    /// which room numbers are written as words and abbreviations are spelt
+    /// out letter by letter.
+    ///
+    /// ```
+    /// use timetableau::Location;
+    ///
+    /// let spoken = |code: &str| code.parse::<Location>().unwrap().spoken();
+    ///
+    /// assert_eq!(spoken("H201"), "Highfield, Howard block, floor two, room one");
+    /// assert_eq!(spoken("UG12"), "Highfield, Unwin block, ground floor, room twelve");
+    /// assert_eq!(spoken("FH S12"), "Fearnhill, Science room twelve");
+    /// assert_eq!(spoken("FH I3"), "Fearnhill, I T room three");
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// Unlike a [verbose name](Self::verbose_name), the description is not
+    /// intended to be displayed -- *see [`Self::verbose_name`]* for a
+    /// written description of the `Location`.
+    pub fn spoken(&self) -> String {
+        let school = self.school().name();
+
+        match self {
+            Self::Highfield(HighfieldRoom::Classroom {
+                block,
+                floor,
+                discriminator,
+            }) => {
+                let floor = match floor {
+                    HighfieldFloor::Ground => "ground floor".to_string(),
+                    HighfieldFloor::Level(level) => format!("floor {}", number_words(level.get())),
+                };
+
+                format!(
+                    "{}, {} block, {}, room {}",
+                    school,
+                    block.full_name(),
+                    floor,
+                    number_words(discriminator.get())
+                )
+            }
+            Self::Fearnhill(FearnhillRoom::Classroom {
+                section,
+                discriminator,
+            }) => {
+                let section = match section {
+                    FearnhillSection::PSHE => "P S H E",
+                    FearnhillSection::IT => "I T",
+                    section => section.full_name(),
+                };
+
+                format!(
+                    "{}, {} room {}",
+                    school,
+                    section,
+                    number_words(discriminator.get())
+                )
+            }
+            Self::Highfield(HighfieldRoom::Temporary(number)) => format!(
+                "{}, temporary classroom {}",
+                school,
+                number_words(number.get())
+            ),
+            Self::Highfield(HighfieldRoom::OffSite(site))
+            | Self::Fearnhill(FearnhillRoom::OffSite(site)) => {
+                format!("{}, off site", site.name())
+            }
+            Self::Highfield(HighfieldRoom::Remote(remote))
+            | Self::Fearnhill(FearnhillRoom::Remote(remote)) => match remote.label() {
+                Some(label) => format!("remote lesson, {}", label),
+                None => "remote lesson".to_string(),
+            },
+            Self::Highfield(HighfieldRoom::ToBeConfirmed)
+            | Self::Fearnhill(FearnhillRoom::ToBeConfirmed) => {
+                format!("{}, room to be confirmed", school)
+            }
+            #[cfg(feature = "other-rooms")]
+            Self::Highfield(HighfieldRoom::Other(room))
+            | Self::Fearnhill(FearnhillRoom::Other(room)) => format!("{}, room {}", school, room),
+            Self::Highfield(room) => format!("{}, {}", school, room),
+            Self::Fearnhill(room) => format!("{}, {}", school, room),
+        }
+    }
+
+    /// Formats the `Location` as the most descriptive identifier which fits
+    /// within `max_chars` characters (e.g., for the LED matrices and e-ink
+    /// signs outside of each room).
+    ///
+    /// ```
+    /// use timetableau::Location;
+    ///
+    /// let room: Location = "FH S12".parse().unwrap();
+    ///
+    /// assert_eq!(room.format_abbrev(6), "FH S12");
+    /// assert_eq!(room.format_abbrev(5), "FHS12");
+    /// assert_eq!(room.format_abbrev(4), "S12*");
+    /// assert_eq!(room.format_abbrev(3), "S12");
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// Each room has one or more forms, from the longest to the shortest:
+    ///
+    /// * Classrooms, temporary classrooms, and other rooms have a single
+    ///   form -- their room identifier (e.g., `H201`, `T7`, or `S12`).
+    /// * Named rooms are formed by their name, their name without spaces, and
+    ///   a three-letter code (e.g., `Sports Hall`, `SportsHall`, and `SPH`).
+    /// * Rooms which are yet to be confirmed are formed by `TBC` and `?`.
+    /// * Off-site locations are formed by their identifier, and by their
+    ///   identifier without coordinates (e.g., `@Museum`).
+    /// * Remote lessons are formed by their identifier, `Remote`, and `RMT`.
+    ///
+    /// The first of the following which fits within `max_chars` characters
+    /// is returned:
+    ///
+    /// 1. The room identifier of the `Location` (e.g., `FH Sports Hall`).
+    /// 2. For Fearnhill rooms, the room identifier without the space after
+    ///    `FH` (e.g., `FHSports Hall`).
+    /// 3. For Fearnhill rooms, each form followed by an asterisk, which
+    ///    indicates the Fearnhill site (e.g., `SPH*`).
+    /// 4. Each form (e.g., `SPH`), unless it is the identifier of another room
+    ///    (e.g., `T1` for `FH T1`), or a form of the Highfield room of the
+    ///    same name (e.g., `SPH` for `FH Sports Hall`).
+    /// 5. The shortest form, truncated to `max_chars` characters, the last of
+    ///    which is an ellipsis (e.g., `H2…`) -- such that a truncated form is
+    ///    never mistaken for the identifier of another room (e.g., `H20`).
+    ///
+    /// As such, no two rooms share an abbreviation unless it is truncated
+    /// (i.e., ends with an ellipsis, such as `H2…` for both `H201` and
+    /// `H202`).
+    pub fn format_abbrev(&self, max_chars: usize) -> String {
+        let fits = |s: &String| s.chars().count() <= max_chars;
+
+        let identifier = self.to_string();
+
+        if fits(&identifier) {
+            return identifier;
+        }
+
+        let forms = self.abbreviated_forms();
+
+        if self.school() == School::Fearnhill {
+            let mut candidates = std::iter::once(format!("FH{}", forms[0]))
+                .chain(forms.iter().map(|form| format!("{}*", form)));
+
+            if let Some(candidate) = candidates.find(fits) {
+                return candidate;
+            }
+        }
+
+        // Without the site indicator, the form of a Fearnhill room may be the
+        // identifier of a Highfield room (e.g., `T1` for `FH T1`), or a form
+        // of the Highfield room of the same name (e.g., `SPH` for `FH Sports
+        // Hall`)
+        let namesake = match self.school() {
+            School::Fearnhill => forms[0]
+                .parse::<Location>()
+                .map_or_else(|_| Vec::new(), |other| other.abbreviated_forms()),
+            School::Highfield => Vec::new(),
+        };
+        let ambiguous = |form: &String| {
+            self.school() == School::Fearnhill
+                && (form.parse::<Location>().is_ok() || namesake.contains(form))
+        };
+
+        if let Some(form) = forms.iter().find(|form| fits(form) && !ambiguous(form)) {
+            return form.clone();
+        }
+
+        if max_chars == 0 {
+            return String::new();
+        }
+
+        let mut truncated: String = forms[forms.len() - 1].chars().take(max_chars - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+
+    // Retrieve the forms of the room (without the `FH ` prefix), from the
+    // longest to the shortest (*see `Location::format_abbrev`*)
+    fn abbreviated_forms(&self) -> Vec<String> {
+        let room = match self {
+            Self::Highfield(room) => room.to_string(),
+            Self::Fearnhill(room) => room.to_string(),
+        };
+
+        if let Some(name) = self.parts().named {
+            let code = match name {
+                "Hall" => "HAL",
+                "Sports Hall" => "SPH",
+                "Field" => "FLD",
+                "Astro Pitch" => "AST",
+                "Tennis Courts" => "TEN",
+                "Playground" => "PLY",
+                "Gym" => "GYM",
+                "Dance Studio" => "DAN",
+                "Drama Studio" => "DRA",
+                "TBC" => return vec![room, "?".to_string()],
+                _ => unreachable!("named room without a code"),
+            };
+            let joined = name.replace(' ', "");
+
+            let mut forms = vec![room];
+
+            if joined != forms[0] {
+                forms.push(joined);
+            }
+
+            forms.push(code.to_string());
+            return forms;
+        }
+
+        if let Some(site) = self.off_site() {
+            let name = format!("@{}", site.name());
+            return if name == room {
+                vec![room]
+            } else {
+                vec![room, name]
+            };
+        }
+
+        if let Self::Highfield(HighfieldRoom::Remote(_))
+        | Self::Fearnhill(FearnhillRoom::Remote(_)) = self
+        {
+            let mut forms = vec![room];
+
+            if forms[0] != "Remote" {
+                forms.push("Remote".to_string());
+            }
+
+            forms.push("RMT".to_string());
+            return forms;
+        }
+
+        vec![room]
+    }
+
+    /// Parses a room identifier, falling back to an [`OtherRoom`] should the
+    /// identifier not be valid under the room numbering scheme of either
+    /// school (as with [`FromStr`], identifiers prefixed with `FH ` are
+    /// Fearnhill rooms).
+    ///
+    /// This allows importers to retain rooms which the crate does not model,
+    /// such that they can be exported again unchanged.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should the identifier not be a valid
+    /// [`OtherRoom`] either.
+    #[cfg(feature = "other-rooms")]
+    pub fn parse_or_other(s: &str) -> Result<Self, ParseLocationError> {
+        if let Ok(location) = s.parse() {
+            return Ok(location);
+        }
+
+        match s.strip_prefix("FH ") {
+            Some(room) => {
+                OtherRoom::new(room).map(|room| Self::Fearnhill(FearnhillRoom::Other(room)))
+            }
+            None => OtherRoom::new(s).map(|room| Self::Highfield(HighfieldRoom::Other(room))),
+        }
+    }
+
+    /// Retrieves the [`School`] at which the `Location` is located.
+    pub fn school(&self) -> School {
+        match self {
+            Self::Highfield(_) => School::Highfield,
+            Self::Fearnhill(_) => School::Fearnhill,
+        }
+    }
+
+    /// Retrieves the [`OffSite`] location of the `Location` (i.e., should
+    /// the lesson take place away from the sites of both schools).
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the `Location` is a room at either school.
+    pub fn off_site(&self) -> Option<OffSite> {
+        match self {
+            Self::Highfield(HighfieldRoom::OffSite(site))
+            | Self::Fearnhill(FearnhillRoom::OffSite(site)) => Some(*site),
+            _ => None,
+        }
+    }
+
+    /// Retrieves the structured breakdown of the `Location` -- *see
+    /// [`LocationParts`] for more information*.
+    pub fn parts(&self) -> LocationParts {
+        let mut parts = LocationParts {
+            school: self.school(),
+            block: None,
+            section: None,
+            floor: None,
+            discriminator: None,
+            named: None,
+            off_site: None,
+            remote: None,
+        };
+
+        match self {
+            Self::Highfield(HighfieldRoom::Classroom {
+                block,
+                floor,
+                discriminator,
+            }) => {
+                parts.block = Some(*block);
+                parts.floor = Some(*floor);
+                parts.discriminator = Some(discriminator.get());
+            }
+            Self::Fearnhill(FearnhillRoom::Classroom {
+                section,
+                discriminator,
+            }) => {
+                parts.section = Some(*section);
+                parts.discriminator = Some(discriminator.get());
+            }
+            Self::Highfield(HighfieldRoom::Temporary(number)) => {
+                parts.discriminator = Some(number.get());
+            }
+            Self::Highfield(HighfieldRoom::Hall) => parts.named = Some("Hall"),
+            Self::Highfield(HighfieldRoom::SportsHall) => parts.named = Some("Sports Hall"),
+            Self::Highfield(HighfieldRoom::Field) | Self::Fearnhill(FearnhillRoom::Field) => {
+                parts.named = Some("Field")
+            }
+            Self::Highfield(HighfieldRoom::AstroPitch)
+            | Self::Fearnhill(FearnhillRoom::AstroPitch) => parts.named = Some("Astro Pitch"),
+            Self::Highfield(HighfieldRoom::TennisCourts)
+            | Self::Fearnhill(FearnhillRoom::TennisCourts) => parts.named = Some("Tennis Courts"),
+            Self::Highfield(HighfieldRoom::Playground)
+            | Self::Fearnhill(FearnhillRoom::Playground) => parts.named = Some("Playground"),
+            Self::Fearnhill(FearnhillRoom::SportsHall) => parts.named = Some("Sports Hall"),
+            Self::Fearnhill(FearnhillRoom::Gym) => parts.named = Some("Gym"),
+            Self::Fearnhill(FearnhillRoom::DanceStudio) => parts.named = Some("Dance Studio"),
+            Self::Fearnhill(FearnhillRoom::DramaStudio) => parts.named = Some("Drama Studio"),
+            Self::Highfield(HighfieldRoom::ToBeConfirmed)
+            | Self::Fearnhill(FearnhillRoom::ToBeConfirmed) => parts.named = Some("TBC"),
+            Self::Highfield(HighfieldRoom::OffSite(site))
+            | Self::Fearnhill(FearnhillRoom::OffSite(site)) => parts.off_site = Some(*site),
+            Self::Highfield(HighfieldRoom::Remote(remote))
+            | Self::Fearnhill(FearnhillRoom::Remote(remote)) => parts.remote = Some(*remote),
+            #[cfg(feature = "other-rooms")]
+            Self::Highfield(HighfieldRoom::Other(_)) | Self::Fearnhill(FearnhillRoom::Other(_)) => {
+            }
+        }
+
+        parts
+    }
+}
+
+/// The structured breakdown of a [`Location`], retrieved using
+/// [`Location::parts`].
+///
+/// This allows a location to be grouped by its block, section, or floor
+/// without matching the (non-exhaustive) room enumerations -- any part which
+/// does not apply to the location is [`None`]:
+///
+/// ```
+/// use timetableau::{HighfieldBlock, Location};
+///
+/// let parts = "H201".parse::<Location>().unwrap().parts();
+///
+/// assert_eq!(parts.block, Some(HighfieldBlock::Howard));
+/// assert_eq!(parts.discriminator, Some(1));
+/// assert_eq!(parts.section, None);
+///
+/// let parts = "FH Gym".parse::<Location>().unwrap().parts();
+///
+/// assert_eq!(parts.named, Some("Gym"));
+/// assert_eq!(parts.floor, None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct LocationParts {
+    /// The school at which the location is located.
+    pub school: School,
+
+    /// The block of a Highfield classroom.
+    pub block: Option<HighfieldBlock>,
+
+    /// The section of a Fearnhill classroom.
+    pub section: Option<FearnhillSection>,
+
+    /// The floor of a Highfield classroom.
+    pub floor: Option<HighfieldFloor>,
+
+    /// The discriminator of a classroom at either school (or the number of a
+    /// temporary classroom at the Highfield school, which has neither a
+    /// block nor a floor).
+    pub discriminator: Option<u8>,
+
+    /// The name of a named room (e.g., `Sports Hall`) -- this is the room's
+    /// identifier without the `FH ` prefix.
+    pub named: Option<&'static str>,
+
+    /// The off-site location at which the school holds lessons.
+    pub off_site: Option<OffSite>,
+
+    /// The remote (i.e., online) lesson location of the school.
+    pub remote: Option<Remote>,
+}
+
+impl FromStr for HighfieldRoom {
+    type Err = ParseLocationError;
+
+    // Parse a room identifier in the same format as produced by `Display`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Hall" => return Ok(Self::Hall),
+            "Sports Hall" => return Ok(Self::SportsHall),
+            "Field" => return Ok(Self::Field),
+            "Astro Pitch" => return Ok(Self::AstroPitch),
+            "Tennis Courts" => return Ok(Self::TennisCourts),
+            "Playground" => return Ok(Self::Playground),
+            "TBC" => return Ok(Self::ToBeConfirmed),
+            _ => {}
+        }
+
+        if s.starts_with('@') {
+            return s.parse().map(Self::OffSite);
+        }
+
+        if s.starts_with("Remote") {
+            return s.parse().map(Self::Remote);
+        }
+
+        // Temporary classrooms take the form `T#` (and are not padded)
+        if let Some(number) = s.strip_prefix('T') {
+            if number.starts_with('0') || number.is_empty() || number.len() > 2 {
+                return Err(ParseLocationError(None));
+            }
+
+            return parse_discriminator(number)
+                .map(Self::Temporary)
+                .ok_or(ParseLocationError(None));
+        }
+
+        // Classrooms take the form `BF##`
+        let bytes = s.as_bytes();
+
+        if bytes.len() != 4 {
+            return Err(ParseLocationError(None));
+        }
+
+        let block = match bytes[0] {
+            b'H' => HighfieldBlock::Howard,
+            b'P' => HighfieldBlock::Parker,
+            b'U' => HighfieldBlock::Unwin,
+            _ => return Err(ParseLocationError(None)),
+        };
+
+        // Room identifiers always use `G` for the ground floor (never `0`)
+        let floor = match bytes[1] {
+            b'G' => HighfieldFloor::Ground,
+            level @ b'1'..=b'9' => HighfieldFloor::try_from(level - b'0').unwrap(),
+            _ => return Err(ParseLocationError(None)),
+        };
+
+        // The discriminator is always padded to two digits
+        let discriminator = parse_discriminator(&s[2..]).ok_or(ParseLocationError(None))?;
+
+        Ok(Self::Classroom {
+            block,
+            floor,
+            discriminator,
+        })
+    }
+}
+
+impl FromStr for FearnhillRoom {
+    type Err = ParseLocationError;
+
+    // Parse a room identifier in the same format as produced by `Display`
+    // (i.e., without the `FH ` prefix)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use FearnhillSection::*;
+
+        match s {
+            "Sports Hall" => return Ok(Self::SportsHall),
+            "Gym" => return Ok(Self::Gym),
+            "Dance Studio" => return Ok(Self::DanceStudio),
+            "Drama Studio" => return Ok(Self::DramaStudio),
+            "Field" => return Ok(Self::Field),
+            "Astro Pitch" => return Ok(Self::AstroPitch),
+            "Tennis Courts" => return Ok(Self::TennisCourts),
+            "Playground" => return Ok(Self::Playground),
+            "TBC" => return Ok(Self::ToBeConfirmed),
+            _ => {}
+        }
+
+        if s.starts_with('@') {
+            return s.parse().map(Self::OffSite);
+        }
+
+        if s.starts_with("Remote") {
+            return s.parse().map(Self::Remote);
+        }
+
+        // `Mu` must be checked before `M` as both are valid section
+        // identifiers
+        let (section, rest) = if let Some(rest) = s.strip_prefix("Mu") {
+            (Music, rest)
+        } else {
+            let mut chars = s.chars();
+
+            let section = match chars.next() {
+                Some('S') => Science,
+                Some('B') => Business,
+                Some('P') => PSHE,
+                Some('L') => Languages,
+                Some('T') => Technology,
+                Some('M') => Mathematics,
+                Some('E') => English,
+                Some('H') => Humanities,
+                Some('I') => IT,
+                _ => return Err(ParseLocationError(None)),
+            };
+
+            (section, chars.as_str())
+        };
+
+        // Fearnhill discriminators are not padded (i.e., `S1` and not `S01`)
+        if rest.starts_with('0') || rest.is_empty() || rest.len() > 2 {
+            return Err(ParseLocationError(None));
+        }
+
+        let discriminator = parse_discriminator(rest).ok_or(ParseLocationError(None))?;
+
+        Ok(Self::Classroom {
+            section,
+            discriminator,
+        })
+    }
+}
+
+/// Reassembles a `Location` from its [parts](Location::parts).
+///
+/// An error is returned should the parts not describe a room (e.g., should
+/// a Highfield classroom be missing its floor, or should a Fearnhill
+/// classroom have a block).
+impl TryFrom<LocationParts> for Location {
+    type Error = ParseLocationError;
+
+    fn try_from(parts: LocationParts) -> Result<Self, Self::Error> {
+        let discriminator = parts
+            .discriminator
+            .and_then(RangedU8::new)
+            .ok_or(ParseLocationError(None));
+
+        let location = match (parts.school, parts.named, parts.off_site, parts.remote) {
+            (School::Highfield, _, Some(site), _) => Self::Highfield(HighfieldRoom::OffSite(site)),
+            (School::Fearnhill, _, Some(site), _) => Self::Fearnhill(FearnhillRoom::OffSite(site)),
+            (School::Highfield, _, _, Some(remote)) => {
+                Self::Highfield(HighfieldRoom::Remote(remote))
+            }
+            (School::Fearnhill, _, _, Some(remote)) => {
+                Self::Fearnhill(FearnhillRoom::Remote(remote))
+            }
+            (School::Highfield, Some(name), ..) => Self::Highfield(name.parse()?),
+            (School::Fearnhill, Some(name), ..) => Self::Fearnhill(name.parse()?),
+            (School::Highfield, None, ..) if parts.block.is_none() && parts.floor.is_none() => {
+                Self::Highfield(HighfieldRoom::Temporary(discriminator?))
+            }
+            (School::Highfield, None, ..) => Self::Highfield(HighfieldRoom::Classroom {
+                block: parts.block.ok_or(ParseLocationError(None))?,
+                floor: parts.floor.ok_or(ParseLocationError(None))?,
+                discriminator: discriminator?,
+            }),
+            (School::Fearnhill, None, ..) => Self::Fearnhill(FearnhillRoom::Classroom {
+                section: parts.section.ok_or(ParseLocationError(None))?,
+                discriminator: discriminator?,
+            }),
+        };
+
+        // Reject any part which does not apply to the room (this also
+        // rejects a name which is the identifier of a classroom)
+        if location.parts() == parts {
+            Ok(location)
+        } else {
+            Err(ParseLocationError(None))
+        }
+    }
+}
+
+impl FromStr for Location {
+    type Err = ParseLocationError;
+
+    /// Parses a room identifier (in the same format as produced by the
+    /// `Display` implementation of `Location`).
+    ///
+    /// Room identifiers prefixed with `FH ` are parsed as Fearnhill rooms,
+    /// and any other room identifier is parsed as a Highfield room -- *see
+    /// the [`crate`] documentation for more information*.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("FH ") {
+            Some(room) => room.parse().map(Self::Fearnhill),
+            None => s.parse().map(Self::Highfield),
+        }
+    }
+}
+
+impl Location {
+    /// Parses a room identifier (in the same format as produced by the
+    /// `Display` implementation of `Location`) in a `const` context, such as
+    /// the [`rooms!`](crate::rooms) macro.
+    ///
+    /// ```
+    /// use timetableau::Location;
+    ///
+    /// const ROOM: Option<Location> = Location::parse_const("FH S12");
+    ///
+    /// assert_eq!(ROOM, "FH S12".parse().ok());
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// The room with the identifier provided, or [`None`] should the
+    /// identifier not be valid.
+    ///
+    /// # Remarks
+    ///
+    /// Only the rooms of the room numbering schemes (i.e., classrooms,
+    /// temporary classrooms, and named rooms) and rooms which are yet to be
+    /// confirmed can be parsed -- off-site locations, remote lessons, and
+    /// other rooms always return [`None`] (*see [`FromStr`] instead*).
+    pub const fn parse_const(s: &str) -> Option<Self> {
+        match strip_prefix_const(s.as_bytes(), b"FH ") {
+            Some(room) => match parse_fearnhill_const(room) {
+                Some(room) => Some(Self::Fearnhill(room)),
+                None => None,
+            },
+            None => match parse_highfield_const(s.as_bytes()) {
+                Some(room) => Some(Self::Highfield(room)),
+                None => None,
+            },
+        }
+    }
+}
+
+// The `const` equivalent of `HighfieldRoom::from_str` (*see
+// `Location::parse_const`*)
+const fn parse_highfield_const(s: &[u8]) -> Option<HighfieldRoom> {
+    const NAMED: [(&[u8], HighfieldRoom); 7] = [
+        (b"Hall", HighfieldRoom::Hall),
+        (b"Sports Hall", HighfieldRoom::SportsHall),
+        (b"Field", HighfieldRoom::Field),
+        (b"Astro Pitch", HighfieldRoom::AstroPitch),
+        (b"Tennis Courts", HighfieldRoom::TennisCourts),
+        (b"Playground", HighfieldRoom::Playground),
+        (b"TBC", HighfieldRoom::ToBeConfirmed),
+    ];
+
+    let mut index = 0;
+
+    while index < NAMED.len() {
+        if eq_const(s, NAMED[index].0) {
+            return Some(NAMED[index].1);
+        }
+
+        index += 1;
+    }
+
+    // Temporary classrooms take the form `T#` (and are not padded)
+    if let Some(number) = strip_prefix_const(s, b"T") {
+        return match parse_unpadded_const(number) {
+            Some(number) => Some(HighfieldRoom::Temporary(number)),
+            None => None,
+        };
+    }
+
+    // Classrooms take the form `BF##`
+    let [block, floor, tens, ones] = *s else {
+        return None;
+    };
+
+    let block = match block {
+        b'H' => HighfieldBlock::Howard,
+        b'P' => HighfieldBlock::Parker,
+        b'U' => HighfieldBlock::Unwin,
+        _ => return None,
+    };
+
+    let floor = match floor {
+        b'G' => HighfieldFloor::Ground,
+        b'1'..=b'9' => match RangedU8::new(floor - b'0') {
+            Some(level) => HighfieldFloor::Level(level),
+            None => return None,
+        },
+        _ => return None,
+    };
+
+    if !tens.is_ascii_digit() || !ones.is_ascii_digit() {
+        return None;
+    }
+
+    match RangedU8::new((tens - b'0') * 10 + (ones - b'0')) {
+        Some(discriminator) => Some(HighfieldRoom::Classroom {
+            block,
+            floor,
+            discriminator,
+        }),
+        None => None,
+    }
+}
+
+// The `const` equivalent of `FearnhillRoom::from_str` (*see
+// `Location::parse_const`*)
+const fn parse_fearnhill_const(s: &[u8]) -> Option<FearnhillRoom> {
+    use FearnhillSection::*;
+
+    const NAMED: [(&[u8], FearnhillRoom); 9] = [
+        (b"Sports Hall", FearnhillRoom::SportsHall),
+        (b"Gym", FearnhillRoom::Gym),
+        (b"Dance Studio", FearnhillRoom::DanceStudio),
+        (b"Drama Studio", FearnhillRoom::DramaStudio),
+        (b"Field", FearnhillRoom::Field),
+        (b"Astro Pitch", FearnhillRoom::AstroPitch),
+        (b"Tennis Courts", FearnhillRoom::TennisCourts),
+        (b"Playground", FearnhillRoom::Playground),
+        (b"TBC", FearnhillRoom::ToBeConfirmed),
+    ];
+
+    let mut index = 0;
+
+    while index < NAMED.len() {
+        if eq_const(s, NAMED[index].0) {
+            return Some(NAMED[index].1);
+        }
+
+        index += 1;
+    }
+
+    // `Mu` must be checked before `M` as both are valid section identifiers
+    let (section, rest) = match strip_prefix_const(s, b"Mu") {
+        Some(rest) => (Music, rest),
+        None => {
+            let [section, rest @ ..] = s else {
+                return None;
+            };
+
+            let section = match section {
+                b'S' => Science,
+                b'B' => Business,
+                b'P' => PSHE,
+                b'L' => Languages,
+                b'T' => Technology,
+                b'M' => Mathematics,
+                b'E' => English,
+                b'H' => Humanities,
+                b'I' => IT,
+                _ => return None,
+            };
+
+            (section, rest)
+        }
+    };
+
+    match parse_unpadded_const(rest) {
+        Some(discriminator) => Some(FearnhillRoom::Classroom {
+            section,
+            discriminator,
+        }),
+        None => None,
+    }
+}
+
+// Parse a discriminator of one or two digits without a leading zero (e.g.,
+// `7` or `12`, but never `07`)
+const fn parse_unpadded_const(s: &[u8]) -> Option<RangedU8<1, 99>> {
+    match *s {
+        [ones @ b'1'..=b'9'] => RangedU8::new(ones - b'0'),
+        [tens @ b'1'..=b'9', ones @ b'0'..=b'9'] => {
+            RangedU8::new((tens - b'0') * 10 + (ones - b'0'))
+        }
+        _ => None,
+    }
+}
+
+// The `const` equivalent of `<[u8]>::strip_prefix`
+const fn strip_prefix_const<'a>(s: &'a [u8], prefix: &[u8]) -> Option<&'a [u8]> {
+    if s.len() < prefix.len() {
+        return None;
+    }
+
+    let (start, rest) = s.split_at(prefix.len());
+
+    if eq_const(start, prefix) {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+// The `const` equivalent of `<[u8]>::eq`
+const fn eq_const(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut index = 0;
+
+    while index < a.len() {
+        if a[index] != b[index] {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+/// Creates a `&'static` slice of [`Location`]s from their room identifiers,
+/// each of which is validated at compile time.
+///
+/// *See [`Location::parse_const`] for more information*.
+///
+/// # Examples
+///
+/// ```
+/// # use timetableau::{rooms, Location};
+/// #
+/// # fn main() {
+/// // The rooms of the mathematics department
+/// const MATHEMATICS: &[Location] = rooms!["H201", "H202", "FH M3"];
+///
+/// assert_eq!(MATHEMATICS.len(), 3);
+/// assert_eq!(MATHEMATICS[2], "FH M3".parse().unwrap());
+/// # }
+/// ```
+///
+/// Invalid room identifiers fail to compile:
+///
+/// ```compile_fail
+/// # use timetableau::{rooms, Location};
+/// const ROOMS: &[Location] = rooms!["H201", "H2001"];
+/// ```
+///
+/// # Remarks
+///
+/// Off-site locations, remote lessons, and other rooms cannot be parsed at
+/// compile time, and therefore cannot be provided.
+#[macro_export]
+macro_rules! rooms {
+    ($($room:literal),* $(,)?) => {{
+        const ROOMS: &[$crate::Location] = &[$(
+            match $crate::Location::parse_const($room) {
+                ::core::option::Option::Some(location) => location,
+                ::core::option::Option::None => {
+                    ::core::panic!(::core::concat!("invalid room identifier: ", $room))
+                }
+            }
+        ),*];
+
+        ROOMS
+    }};
+}
+
+// The packed representation of a `Location`:
+//
+// | Bits   | Highfield                   | Fearnhill                      |
+// |--------|-----------------------------|--------------------------------|
+// | 15     | `0`                         | `1`                            |
+// | 13..15 | Reserved (must be `0`)      | Reserved (must be `0`)         |
+// | 11..13 | Block (`3` for named rooms) | Reserved (must be `0`)         |
+// | 7..11  | Floor (`0` for ground)      | Section (`15` for named rooms) |
+// | 0..7   | Discriminator (or room)     | Discriminator (or room)        |
+//
+// Temporary classrooms at Highfield are packed as named rooms on floor `1`,
+// whose discriminator is the number of the classroom.
+const FEARNHILL_BIT: u16 = 1 << 15;
+const NAMED_BLOCK: u16 = 3;
+const NAMED_SECTION: u16 = 15;
+const TEMPORARY_FLOOR: u16 = 1;
+
+const FEARNHILL_SECTIONS: [FearnhillSection; 10] = [
+    FearnhillSection::Science,
+    FearnhillSection::Business,
+    FearnhillSection::PSHE,
+    FearnhillSection::Languages,
+    FearnhillSection::Technology,
+    FearnhillSection::Mathematics,
+    FearnhillSection::English,
+    FearnhillSection::Music,
+    FearnhillSection::Humanities,
+    FearnhillSection::IT,
+];
+
+/// Packs a `Location` into a `u16`.
+///
+/// The packed representation is stable (i.e., it will not change between
+/// versions of the library), such that it can be stored or transmitted to
+/// systems which cannot parse room identifiers (e.g., the digital signage
+/// firmware) -- `0` never represents a valid `Location`, and can therefore be
+/// used to represent the absence of a room.
+///
+/// As neither an [`OffSite`] location, a [`Remote`] lesson location, a room
+/// which is yet to be confirmed, nor an [`OtherRoom`] can be packed, each is
+/// represented by `0` (i.e., as the absence of a room).
+impl From<Location> for u16 {
+    fn from(location: Location) -> Self {
+        match location {
+            Location::Highfield(room) => {
+                let (block, floor, discriminator) = match room {
+                    HighfieldRoom::Hall => (NAMED_BLOCK, 0, 0),
+                    HighfieldRoom::SportsHall => (NAMED_BLOCK, 0, 1),
+                    HighfieldRoom::Field => (NAMED_BLOCK, 0, 2),
+                    HighfieldRoom::AstroPitch => (NAMED_BLOCK, 0, 3),
+                    HighfieldRoom::TennisCourts => (NAMED_BLOCK, 0, 4),
+                    HighfieldRoom::Playground => (NAMED_BLOCK, 0, 5),
+                    HighfieldRoom::Temporary(number) => {
+                        (NAMED_BLOCK, TEMPORARY_FLOOR, number.get() as u16)
+                    }
+                    HighfieldRoom::Classroom {
+                        block,
+                        floor,
+                        discriminator,
+                    } => (
+                        block as u16,
+                        floor.number() as u16,
+                        discriminator.get() as u16,
+                    ),
+                    HighfieldRoom::OffSite(_)
+                    | HighfieldRoom::Remote(_)
+                    | HighfieldRoom::ToBeConfirmed => return 0,
+                    #[cfg(feature = "other-rooms")]
+                    HighfieldRoom::Other(_) => return 0,
+                };
+
+                block << 11 | floor << 7 | discriminator
+            }
+            Location::Fearnhill(room) => {
+                let (section, discriminator) = match room {
+                    FearnhillRoom::SportsHall => (NAMED_SECTION, 0),
+                    FearnhillRoom::Gym => (NAMED_SECTION, 1),
+                    FearnhillRoom::DanceStudio => (NAMED_SECTION, 2),
+                    FearnhillRoom::DramaStudio => (NAMED_SECTION, 3),
+                    FearnhillRoom::Field => (NAMED_SECTION, 4),
+                    FearnhillRoom::AstroPitch => (NAMED_SECTION, 5),
+                    FearnhillRoom::TennisCourts => (NAMED_SECTION, 6),
+                    FearnhillRoom::Playground => (NAMED_SECTION, 7),
+                    FearnhillRoom::Classroom {
+                        section,
+                        discriminator,
+                    } => (section as u16, discriminator.get() as u16),
+                    FearnhillRoom::OffSite(_)
+                    | FearnhillRoom::Remote(_)
+                    | FearnhillRoom::ToBeConfirmed => return 0,
+                    #[cfg(feature = "other-rooms")]
+                    FearnhillRoom::Other(_) => return 0,
+                };
+
+                FEARNHILL_BIT | section << 7 | discriminator
+            }
+        }
+    }
+}
+
+/// Unpacks a `Location` from a `u16`.
+///
+/// An error is returned should the `u16` not be the packed representation
+/// of a `Location` -- *see the `From<Location>` implementation of `u16` for
+/// more information*.
+impl TryFrom<u16> for Location {
+    type Error = ();
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        let discriminator = (value & 0x7f) as u8;
+
+        if value & FEARNHILL_BIT == 0 {
+            if value >> 13 != 0 {
+                return Err(());
+            }
+
+            let room = match (value >> 11 & 0x3, value >> 7 & 0xf) {
+                (NAMED_BLOCK, 0) => match discriminator {
+                    0 => HighfieldRoom::Hall,
+                    1 => HighfieldRoom::SportsHall,
+                    2 => HighfieldRoom::Field,
+                    3 => HighfieldRoom::AstroPitch,
+                    4 => HighfieldRoom::TennisCourts,
+                    5 => HighfieldRoom::Playground,
+                    _ => return Err(()),
+                },
+                (NAMED_BLOCK, TEMPORARY_FLOOR) => {
+                    HighfieldRoom::Temporary(RangedU8::new(discriminator).ok_or(())?)
+                }
+                (NAMED_BLOCK, _) => return Err(()),
+                (block, floor) => HighfieldRoom::Classroom {
+                    block: match block {
+                        0 => HighfieldBlock::Howard,
+                        1 => HighfieldBlock::Parker,
+                        _ => HighfieldBlock::Unwin,
+                    },
+                    floor: match floor {
+                        0 => HighfieldFloor::Ground,
+                        level => HighfieldFloor::Level(RangedU8::new(level as u8).ok_or(())?),
+                    },
+                    discriminator: RangedU8::new(discriminator).ok_or(())?,
+                },
+            };
+
+            Ok(Self::Highfield(room))
+        } else {
+            if value >> 11 & 0xf != 0 {
+                return Err(());
+            }
+
+            let room = match value >> 7 & 0xf {
+                NAMED_SECTION => match discriminator {
+                    0 => FearnhillRoom::SportsHall,
+                    1 => FearnhillRoom::Gym,
+                    2 => FearnhillRoom::DanceStudio,
+                    3 => FearnhillRoom::DramaStudio,
+                    4 => FearnhillRoom::Field,
+                    5 => FearnhillRoom::AstroPitch,
+                    6 => FearnhillRoom::TennisCourts,
+                    7 => FearnhillRoom::Playground,
+                    _ => return Err(()),
+                },
+                section => FearnhillRoom::Classroom {
+                    section: *FEARNHILL_SECTIONS.get(section as usize).ok_or(())?,
+                    discriminator: RangedU8::new(discriminator).ok_or(())?,
+                },
+            };
+
+            Ok(Self::Fearnhill(room))
+        }
+    }
+}
+
+// Parse a string of ASCII digits into a discriminator (i.e., a number in the
+// range `1..=99`)
+fn parse_discriminator(s: &str) -> Option<RangedU8<1, 99>> {
+    // `u8::from_str` accepts a leading `+`, which is not a part of any room
+    // identifier
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    RangedU8::new(s.parse().ok()?)
+}
+
+// Free slots are commonly represented by `Option<Location>`, which must use a
+// niche of the room enumerations (rather than an additional tag)
+const _: () = assert!(size_of::<Option<Location>>() == size_of::<Location>());
+const _: () = assert!(size_of::<Option<HighfieldRoom>>() == size_of::<HighfieldRoom>());
+const _: () = assert!(size_of::<Option<FearnhillRoom>>() == size_of::<FearnhillRoom>());
+
+// Write the number provided (which is less than 100) in words (e.g., `42`
+// is written as `forty-two`)
+fn number_words(number: u8) -> String {
+    const UNITS: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+
+    match (number / 10, number % 10) {
+        _ if number < 20 => UNITS[number as usize].to_string(),
+        (tens, 0) => TENS[tens as usize].to_string(),
+        (tens, units) => format!("{}-{}", TENS[tens as usize], UNITS[units as usize]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn location_packed_round_trip() {
+        let mut count = 0;
+
+        for packed in 0..=u16::MAX {
+            if let Ok(location) = Location::try_from(packed) {
+                assert_eq!(u16::from(location), packed);
+                assert_eq!(location.to_string().parse(), Ok(location));
+                count += 1;
+            }
+        }
+
+        // Every Highfield room (3 blocks of 10 floors, the 6 named rooms, and
+        // the temporary classrooms) and Fearnhill room (10 sections, and the
+        // 8 named rooms)
+        assert_eq!(count, 3 * 10 * 99 + 6 + 99 + 10 * 99 + 8);
+        assert_eq!(Location::try_from(0), Err(()));
+    }
+
+    #[test]
+    fn location_parts_round_trip() {
+        for code in [
+            "H201",
+            "UG03",
+            "Hall",
+            "T2",
+            "FH Sports Hall",
+            "FH Mu2",
+            "@Stevenage Pool (51.901200, -0.202100)",
+            "FH Remote (Teams)",
+            "FH TBC",
+        ] {
+            let location: Location = code.parse().unwrap();
+
+            assert_eq!(Location::try_from(location.parts()), Ok(location));
+        }
+
+        let mut parts = "FH S1".parse::<Location>().unwrap().parts();
+        parts.floor = Some(HighfieldFloor::Ground);
+
+        assert!(Location::try_from(parts).is_err());
+    }
+
+    #[test]
+    fn location_parse_valid() {
+        let parsed: Location = "P212".parse().unwrap();
+
+        assert_eq!(
+            parsed,
+            Location::Highfield(HighfieldRoom::Classroom {
+                block: HighfieldBlock::Parker,
+                floor: HighfieldFloor::Level(RangedU8::new(2).unwrap()),
+                discriminator: RangedU8::new(12).unwrap(),
+            })
+        );
+
+        assert_eq!(
+            "FH Mu2".parse::<Location>(),
+            Ok(Location::Fearnhill(FearnhillRoom::Classroom {
+                section: FearnhillSection::Music,
+                discriminator: RangedU8::new(2).unwrap(),
+            }))
+        );
+    }
+
+    #[test]
+    fn location_parse_round_trip() {
+        for code in [
+            "HG01",
+            "U111",
+            "P999",
+            "Hall",
+            "Sports Hall",
+            "T1",
+            "T42",
+            "FH S13",
+            "FH M1",
+            "FH Gym",
+            "FH Sports Hall",
+            "Tennis Courts",
+            "FH Astro Pitch",
+            "FH I99",
+            "@Stevenage Pool",
+            "FH @Barclay Academy (51.914000, -0.190123)",
+            "@Work Experience (-33.856000, 151.215000)",
+            "Remote",
+            "Remote (Google Meet)",
+            "FH Remote",
+            "TBC",
+            "FH TBC",
+        ] {
+            assert_eq!(code.parse::<Location>().unwrap().to_string(), code);
+        }
+    }
+
+    #[test]
+    fn location_parse_invalid() {
+        for code in [
+            "",
+            "H201 ",
+            "HG00",
+            "HG1",
+            "XG01",
+            "H0 01",
+            "FH S01",
+            "FH S",
+            "FH X1",
+            "S13",
+            "FH S+1",
+            "FH Hall",
+            "T0",
+            "T01",
+            "T100",
+            "@",
+            "@ Pool",
+            "@Pool (1.0)",
+            "@Pool (91.0, 0.0)",
+            "@Stevenage Swimming Centre",
+            "Remote ()",
+            "Remote(Teams)",
+        ] {
+            assert!(code.parse::<Location>().is_err(), "{} parsed", code);
+        }
+    }
+
+    #[test]
+    fn block_and_section_names() {
+        assert_eq!(HighfieldBlock::Howard.full_name(), "Howard");
+        assert_eq!(HighfieldBlock::Howard.code(), 'H');
+        assert_eq!(FearnhillSection::Mathematics.full_name(), "Mathematics");
+        assert_eq!(FearnhillSection::Music.code(), "Mu");
+
+        for s in ["P", "Parker", "parker"] {
+            assert_eq!(s.parse(), Ok(HighfieldBlock::Parker));
+        }
+        for s in ["Mu", "Music", "MUSIC"] {
+            assert_eq!(s.parse(), Ok(FearnhillSection::Music));
+        }
+        for s in ["", "p", "Parkers"] {
+            assert!(s.parse::<HighfieldBlock>().is_err(), "{} parsed", s);
+        }
+        assert!("mu".parse::<FearnhillSection>().is_err());
+    }
+
+    #[test]
+    fn floor_parse() {
+        assert_eq!("G".parse(), Ok(HighfieldFloor::Ground));
+        assert_eq!("0".parse(), Ok(HighfieldFloor::Ground));
+        assert_eq!(
+            "3".parse::<HighfieldFloor>().map(HighfieldFloor::number),
+            Ok(3)
+        );
+        assert_eq!(HighfieldFloor::try_from(0), Ok(HighfieldFloor::Ground));
+        assert_eq!(HighfieldFloor::try_from(10), Err(()));
+
+        for s in ["", "g", "10", "-1"] {
+            assert!(s.parse::<HighfieldFloor>().is_err(), "{} parsed", s);
+        }
+    }
+
+    #[test]
+    fn location_parse_const() {
+        for packed in 0..=u16::MAX {
+            if let Ok(location) = Location::try_from(packed) {
+                let identifier = location.to_string();
+                assert_eq!(Location::parse_const(&identifier), Some(location));
+            }
+        }
+
+        for s in ["TBC", "FH TBC", "T7", "FH Mu3", "UG01"] {
+            assert_eq!(Location::parse_const(s), s.parse().ok(), "{}", s);
+        }
+        for s in [
+            "", "H2001", "H200", "H0 01", "T0", "T07", "T100", "FH S01", "FH S", "FH Mu", "FH X1",
+            "FH Hall", "fh S1", "@Museum", "Remote", "H2+1",
+        ] {
+            assert_eq!(Location::parse_const(s), None, "{} parsed", s);
+        }
+
+        const ROOMS: &[Location] = rooms!["H201", "FH S12", "Hall"];
+        assert_eq!(ROOMS[1], "FH S12".parse().unwrap());
+    }
+
+    #[test]
+    fn location_format_abbrev() {
+        let abbrev = |s: &str, max_chars| s.parse::<Location>().unwrap().format_abbrev(max_chars);
+
+        assert_eq!(abbrev("H201", 4), "H201");
+        assert_eq!(abbrev("H201", 3), "H2…");
+        assert_eq!(abbrev("T12", 2), "T…");
+        assert_eq!(abbrev("FH Mu12", 5), "Mu12*");
+        assert_eq!(abbrev("FH Mu12", 4), "Mu12");
+        assert_eq!(abbrev("FH Mu12", 2), "M…");
+        assert_eq!(abbrev("Sports Hall", 11), "Sports Hall");
+        assert_eq!(abbrev("Sports Hall", 10), "SportsHall");
+        assert_eq!(abbrev("Sports Hall", 4), "SPH");
+        assert_eq!(abbrev("Hall", 3), "HAL");
+        assert_eq!(abbrev("FH Sports Hall", 13), "FHSports Hall");
+        assert_eq!(abbrev("FH Sports Hall", 11), "SportsHall*");
+        assert_eq!(abbrev("FH Gym", 5), "FHGym");
+        assert_eq!(abbrev("FH Gym", 4), "Gym*");
+        assert_eq!(abbrev("FH Gym", 0), "");
+        assert_eq!(abbrev("FH TBC", 2), "?*");
+        assert_eq!(abbrev("TBC", 2), "?");
+        assert_eq!(abbrev("@Museum (51.752, -0.34)", 10), "@Museum");
+        assert_eq!(abbrev("@Museum", 4), "@Mu…");
+        assert_eq!(abbrev("H201", 1), "…");
+        assert_eq!(abbrev("Remote (Teams)", 6), "Remote");
+        assert_eq!(abbrev("FH Remote", 4), "RMT*");
+        assert_eq!(abbrev("FH T1", 2), "T…");
+        assert_eq!(abbrev("FH Sports Hall", 3), "SP…");
+        assert_eq!(abbrev("FH Remote", 3), "RM…");
+        assert_eq!(abbrev("FH TBC", 1), "…");
+
+        for max_chars in 0..16 {
+            let mut abbreviations: HashMap<String, Location> = HashMap::new();
+
+            for location in Location::all() {
+                let abbrev = location.format_abbrev(max_chars);
+
+                // An abbreviation is never the identifier of another room
+                assert!(abbrev.chars().count() <= max_chars);
+                assert!(abbrev
+                    .parse::<Location>()
+                    .map_or(true, |other| other == location));
+
+                // Nor is it the abbreviation of another room, unless it is
+                // truncated
+                if !abbrev.is_empty() && !abbrev.ends_with('…') {
+                    if let Some(other) = abbreviations.insert(abbrev.clone(), location) {
+                        panic!(
+                            "{} and {} are both abbreviated to {}",
+                            other, location, abbrev
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn location_spoken() {
+        let spoken = |code: &str| code.parse::<Location>().unwrap().spoken();
+
+        assert_eq!(
+            spoken("P110"),
+            "Highfield, Parker block, floor one, room ten"
+        );
+        assert_eq!(spoken("FH Mu42"), "Fearnhill, Music room forty-two");
+        assert_eq!(spoken("FH P3"), "Fearnhill, P S H E room three");
+        assert_eq!(spoken("T7"), "Highfield, temporary classroom seven");
+        assert_eq!(spoken("Sports Hall"), "Highfield, Sports Hall");
+        assert_eq!(spoken("FH Dance Studio"), "Fearnhill, Dance Studio");
+        assert_eq!(spoken("FH TBC"), "Fearnhill, room to be confirmed");
+        assert_eq!(spoken("@Museum"), "Museum, off site");
+        assert_eq!(number_words(90), "ninety");
+        assert_eq!(number_words(99), "ninety-nine");
+    }
+}
@@ -0,0 +1,468 @@
+//! Locating the members of staff who hold specific roles (e.g., first aiders
+//! and designated safeguarding leads).
+//!
+//! Roles are modelled as [`StaffTags`] (e.g., `first-aider` or `dsl`), and a
+//! [`Locator`] determines where each member of staff with a tag is at a
+//! particular time -- teaching a lesson of the timetable, supervising a
+//! point of the duty [`Rota`], or unaccounted for (e.g., during a free
+//! period). Reception can find the nearest first aider in one call:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::locator::{Locator, StaffTags, Whereabouts};
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Subject, Teacher};
+//! use timetableau::{Term, Timetable, WeekCycle};
+//!
+//! let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+//! let calendar = AcademicCalendar::new(vec![Term::new(date, date).unwrap()]);
+//! let cycle = WeekCycle::new(date, calendar).unwrap();
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new("10A".to_string()).unwrap(),
+//!     teacher: Some(Teacher::new("JSM".to_string()).unwrap()),
+//!     location: "H201".parse().unwrap(),
+//! });
+//!
+//! let tags = StaffTags::new().tag("JSM", "first-aider").tag("ABC", "first-aider");
+//! let at = date.and_hms_opt(9, 0, 0).unwrap();
+//!
+//! let nearest = Locator::new(&tags)
+//!     .nearest("first-aider", &timetable, &cycle, at, &"H105".parse().unwrap())
+//!     .unwrap();
+//!
+//! assert_eq!(nearest.teacher, "JSM");
+//! assert!(matches!(nearest.whereabouts, Whereabouts::Teaching { .. }));
+//! ```
+
+use crate::duty::{DutyPoint, DutyTime, Rota};
+use crate::{EntryId, Location, Timetable, WeekCycle};
+use chrono::NaiveDateTime;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+
+// The distances (*see `Location::distance`*) to a duty point within the same
+// block (or section) as a room, and to any other duty point at the same
+// school as the room
+const DUTY_WITHIN_BLOCK: u32 = 10;
+const DUTY_WITHIN_SCHOOL: u32 = 60;
+const DUTY_ACROSS_SITES: u32 = 1000;
+
+/// The tags (i.e., roles) of members of staff (e.g., `first-aider` or
+/// `dsl`).
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StaffTags {
+    tags: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl StaffTags {
+    /// Creates a new `StaffTags` without any tags.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tags the member of staff with the staff `code` provided with the
+    /// `tag` provided.
+    pub fn tag(mut self, code: &str, tag: &str) -> Self {
+        self.tags
+            .entry(code.to_string())
+            .or_default()
+            .insert(tag.to_string());
+        self
+    }
+
+    /// Whether the member of staff with the staff `code` provided has the
+    /// `tag` provided.
+    pub fn has_tag(&self, code: &str, tag: &str) -> bool {
+        self.tags.get(code).is_some_and(|tags| tags.contains(tag))
+    }
+
+    /// Retrieves the tags of the member of staff with the staff `code`
+    /// provided (in alphabetical order).
+    pub fn tags_of<'a>(&'a self, code: &str) -> impl Iterator<Item = &'a str> + 'a {
+        self.tags
+            .get(code)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Retrieves the staff codes of the members of staff with the `tag`
+    /// provided (in alphabetical order).
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.tags
+            .iter()
+            .filter(move |(_, tags)| tags.contains(tag))
+            .map(|(code, _)| code.as_str())
+    }
+}
+
+/// Where a member of staff is at a particular time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Whereabouts {
+    /// The member of staff is teaching a lesson.
+    Teaching {
+        /// The entry of the lesson, or [`None`] should the lesson be a
+        /// suspension of the timetable (*see
+        /// [`DayException::Suspended`](crate::DayException::Suspended)*).
+        entry: Option<EntryId>,
+
+        /// The room in which the lesson takes place.
+        location: Location,
+    },
+
+    /// The member of staff is supervising a duty point.
+    OnDuty(DutyPoint),
+
+    /// The member of staff is neither teaching nor on duty (e.g., during a
+    /// free period, or outside of the school day).
+    Unaccounted,
+}
+
+impl Display for Whereabouts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Teaching { location, .. } => write!(f, "teaching in {}", location),
+            Self::OnDuty(point) => write!(f, "on duty at {}", point),
+            Self::Unaccounted => f.write_str("unaccounted for"),
+        }
+    }
+}
+
+/// A member of staff located by a [`Locator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Located {
+    /// The staff code of the member of staff.
+    pub teacher: String,
+
+    /// Where the member of staff is.
+    pub whereabouts: Whereabouts,
+}
+
+/// Locates the members of staff with a tag.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Copy)]
+pub struct Locator<'a> {
+    tags: &'a StaffTags,
+    rota: Option<&'a Rota>,
+}
+
+impl<'a> Locator<'a> {
+    /// Creates a new `Locator` of the members of staff with the `tags`
+    /// provided, without a duty rota.
+    pub fn new(tags: &'a StaffTags) -> Self {
+        Self { tags, rota: None }
+    }
+
+    /// Sets the duty `rota` consulted during break and lunch.
+    pub fn rota(mut self, rota: &'a Rota) -> Self {
+        self.rota = Some(rota);
+        self
+    }
+
+    /// Determines where each member of staff with the `tag` provided is at
+    /// the time provided (in order of their staff codes).
+    ///
+    /// The lessons of each member of staff are those which actually take
+    /// place on the date (*see [`Timetable::occurrences`]*) which they teach
+    /// or co-teach (*see [`Entry::co_teachers`](crate::Entry::co_teachers)*),
+    /// at the times of the [bell schedule](crate::BellSchedule) of the school
+    /// of each lesson's room.
+    ///
+    /// # Remarks
+    ///
+    /// Cover is not recorded by the timetable, so a member of staff who is
+    /// covering a lesson is located by their own lessons (*see
+    /// [`Assignment`](crate::bulletin::Assignment)*).
+    pub fn locate(
+        &self,
+        tag: &str,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        at: NaiveDateTime,
+    ) -> Vec<Located> {
+        let date = at.date();
+        let time = at.time();
+        let schedules = cycle.calendar().bell_schedules_on(date);
+        let occurrences = timetable.occurrences(cycle, date);
+
+        self.tags
+            .tagged(tag)
+            .map(|code| {
+                let lesson = occurrences.iter().find_map(|occurrence| {
+                    let location = *occurrence.activity.location()?;
+                    let (start, end) = schedules
+                        .get(location.school())
+                        .period(occurrence.slot.period)
+                        .to_naive();
+
+                    // A co-teacher of a lesson is also teaching it
+                    let teaches = occurrence
+                        .activity
+                        .teacher()
+                        .is_some_and(|teacher| teacher.code() == code)
+                        || occurrence
+                            .entry
+                            .and_then(|id| timetable.get(id))
+                            .is_some_and(|entry| {
+                                entry
+                                    .co_teachers
+                                    .iter()
+                                    .any(|teacher| teacher.code() == code)
+                            });
+
+                    (teaches && time >= start && time < end).then_some(Whereabouts::Teaching {
+                        entry: occurrence.entry,
+                        location,
+                    })
+                });
+
+                let duty = || {
+                    let (week, day) = cycle.day_for(date)?;
+
+                    self.rota?
+                        .duties_of(code)
+                        .find(|duty| {
+                            let schedule = schedules.get(duty.point.school());
+                            let (start, end) = match duty.time {
+                                DutyTime::Break => schedule.break_time(),
+                                DutyTime::Lunch => schedule.lunch(),
+                            }
+                            .to_naive();
+
+                            duty.week == week && duty.day == day && time >= start && time < end
+                        })
+                        .map(|duty| Whereabouts::OnDuty(duty.point))
+                };
+
+                Located {
+                    teacher: code.to_string(),
+                    whereabouts: lesson.or_else(duty).unwrap_or(Whereabouts::Unaccounted),
+                }
+            })
+            .collect()
+    }
+
+    /// Finds the member of staff with the `tag` provided who is nearest to
+    /// the `location` provided at the time provided (*see [`Self::locate`]*)
+    /// -- members of staff who are unaccounted for are disregarded, and a
+    /// member of staff teaching a lesson split across multiple rooms is
+    /// considered to be in whichever of its rooms is nearest.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should no member of staff with the tag be teaching or on
+    /// duty at the time provided.
+    pub fn nearest(
+        &self,
+        tag: &str,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        at: NaiveDateTime,
+        location: &Location,
+    ) -> Option<Located> {
+        self.locate(tag, timetable, cycle, at)
+            .into_iter()
+            .filter_map(|located| {
+                let distance = match located.whereabouts {
+                    Whereabouts::Teaching {
+                        entry,
+                        location: room,
+                    } => entry
+                        .and_then(|id| timetable.get(id))
+                        .and_then(|entry| {
+                            entry
+                                .split_rooms
+                                .iter()
+                                .map(|split| location.distance(split))
+                                .min()
+                        })
+                        .map_or(location.distance(&room), |split| {
+                            split.min(location.distance(&room))
+                        }),
+                    Whereabouts::OnDuty(point) => duty_distance(location, point),
+                    Whereabouts::Unaccounted => return None,
+                };
+
+                Some((distance, located))
+            })
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, located)| located)
+    }
+}
+
+// Estimate the distance between the `location` provided and a duty point
+fn duty_distance(location: &Location, point: DutyPoint) -> u32 {
+    let parts = location.parts();
+
+    match point {
+        _ if parts.school != point.school() => DUTY_ACROSS_SITES,
+        DutyPoint::Block(block) if parts.block == Some(block) => DUTY_WITHIN_BLOCK,
+        DutyPoint::Section(section) if parts.section == Some(section) => DUTY_WITHIN_BLOCK,
+        _ => DUTY_WITHIN_SCHOOL,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::duty::Duty;
+    use crate::{timeslot, AcademicCalendar, Activity, Class, Entry, HighfieldBlock, Subject};
+    use crate::{School, Teacher, Term, Week};
+    use chrono::{NaiveDate, NaiveTime, Weekday};
+
+    #[test]
+    fn staff_located() {
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date, date).unwrap()]);
+        let cycle = WeekCycle::new(date, calendar).unwrap();
+        let teacher = |code: &str| Teacher::new(code.to_string()).unwrap();
+
+        let mut timetable = Timetable::new();
+        let lesson = timetable.insert(
+            timeslot!(W1MP3),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("10A".to_string()).unwrap(),
+                teacher: Some(teacher("JSM")),
+                location: "FH M1".parse().unwrap(),
+            },
+        );
+
+        let rota = Rota {
+            duties: vec![Duty {
+                week: Week::WeekOne,
+                day: Weekday::Mon,
+                time: DutyTime::Break,
+                point: DutyPoint::Block(HighfieldBlock::Parker),
+                teacher: teacher("ABC"),
+            }],
+            unfilled: Vec::new(),
+        };
+
+        let tags = StaffTags::new()
+            .tag("JSM", "first-aider")
+            .tag("ABC", "first-aider")
+            .tag("ABC", "dsl")
+            .tag("XYZ", "dsl")
+            .tag("XYZ", "first-aider");
+
+        assert_eq!(
+            tags.tags_of("ABC").collect::<Vec<_>>(),
+            ["dsl", "first-aider"]
+        );
+        assert!(tags.has_tag("XYZ", "dsl"));
+
+        let locator = Locator::new(&tags).rota(&rota);
+        let break_time = cycle
+            .calendar()
+            .bell_schedules()
+            .get(School::Highfield)
+            .break_time()
+            .start
+            .into();
+        let third = NaiveTime::from_hms_opt(11, 30, 0).unwrap();
+
+        assert_eq!(
+            locator.locate("dsl", &timetable, &cycle, date.and_time(break_time)),
+            [
+                Located {
+                    teacher: "ABC".to_string(),
+                    whereabouts: Whereabouts::OnDuty(DutyPoint::Block(HighfieldBlock::Parker)),
+                },
+                Located {
+                    teacher: "XYZ".to_string(),
+                    whereabouts: Whereabouts::Unaccounted,
+                },
+            ]
+        );
+
+        // During break, the first aider on duty in the Parker block is
+        // nearest to a Parker room, whereas the other is at Fearnhill during
+        // the third period
+        let room: Location = "P105".parse().unwrap();
+
+        assert_eq!(
+            locator
+                .nearest(
+                    "first-aider",
+                    &timetable,
+                    &cycle,
+                    date.and_time(break_time),
+                    &room
+                )
+                .unwrap()
+                .teacher,
+            "ABC"
+        );
+        assert_eq!(
+            locator
+                .nearest(
+                    "first-aider",
+                    &timetable,
+                    &cycle,
+                    date.and_time(third),
+                    &room
+                )
+                .unwrap()
+                .whereabouts,
+            Whereabouts::Teaching {
+                entry: Some(lesson),
+                location: "FH M1".parse().unwrap(),
+            }
+        );
+        assert_eq!(
+            locator.nearest("dsl", &timetable, &cycle, date.and_time(third), &room),
+            None
+        );
+
+        // A co-teacher is teaching the lesson, and is as near as the nearest
+        // of its rooms (the Parker room into which it is split, rather than
+        // the first aider at Fearnhill)
+        let team = timetable
+            .insert_entry(
+                Entry::new(
+                    timeslot!(W1MP3),
+                    Activity::Lesson {
+                        subject: Subject::new("PE".to_string()).unwrap(),
+                        class: Class::new("10B".to_string()).unwrap(),
+                        teacher: None,
+                        location: "FH Gym".parse().unwrap(),
+                    },
+                )
+                .co_teacher(teacher("XYZ"))
+                .split_room("P104".parse().unwrap()),
+            )
+            .unwrap();
+
+        assert_eq!(
+            locator
+                .nearest("dsl", &timetable, &cycle, date.and_time(third), &room)
+                .unwrap(),
+            Located {
+                teacher: "XYZ".to_string(),
+                whereabouts: Whereabouts::Teaching {
+                    entry: Some(team),
+                    location: "FH Gym".parse().unwrap(),
+                },
+            }
+        );
+        assert_eq!(
+            locator
+                .nearest(
+                    "first-aider",
+                    &timetable,
+                    &cycle,
+                    date.and_time(third),
+                    &room
+                )
+                .unwrap()
+                .teacher,
+            "XYZ"
+        );
+    }
+}
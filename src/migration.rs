@@ -0,0 +1,407 @@
+use crate::{
+    Activity, Change, Dataset, DayException, Entry, EntryId, Location, ParseRegistryError,
+};
+use std::collections::HashMap;
+
+// The renames of rooms which have been officially announced by the schools
+const KNOWN_RENAMES: &str = include_str!("../data/renames.txt");
+
+/// A map of room renames (e.g., the renumbering of a block), which migrates
+/// the locations stored by a [`Dataset`] from their old identifiers to their
+/// new ones.
+///
+/// Every rename of a migration takes place at once, such that rooms can
+/// exchange identifiers (e.g., `U101` becoming `U102` whilst `U102` becomes
+/// `U101`) -- a location is never renamed twice by the same migration:
+///
+/// ```
+/// use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, Location};
+/// use timetableau::{RoomMigration, Subject, Timetable};
+///
+/// let room = |code: &str| code.parse::<Location>().unwrap();
+/// let lesson = |code| Activity::Lesson {
+///     subject: Subject::new("En".to_string()).unwrap(),
+///     class: Class::new("10A".to_string()).unwrap(),
+///     teacher: None,
+///     location: room(code),
+/// };
+///
+/// let mut dataset = Dataset::new(Timetable::new(), AcademicCalendar::default());
+/// let first = dataset.timetable.insert(timeslot!(W1MP1), lesson("U101"));
+/// let second = dataset.timetable.insert(timeslot!(W1MP2), lesson("U102"));
+///
+/// let migration = RoomMigration::from_data("U101 -> U102\nU102 -> U101\n").unwrap();
+/// assert_eq!(migration.apply(&mut dataset), 2);
+///
+/// let location = |id| dataset.timetable.get(id).unwrap().activity.location().copied();
+/// assert_eq!(location(first), Some(room("U102")));
+/// assert_eq!(location(second), Some(room("U101")));
+/// ```
+///
+/// The crate ships the renames which have been officially announced by the
+/// schools (*see [`RoomMigration::known`]*), which should be applied to any
+/// dataset stored before they took effect.
+///
+/// # History
+///
+/// The changes made to the timetable of the dataset are recorded in its
+/// history (attributed to its [author](crate::Timetable::set_author), if
+/// any), such that the timetable as it was before the migration can still be
+/// [reconstructed](crate::Timetable::as_of):
+///
+/// * A lesson which only takes place in its own location is
+///   [relocated](Change::Relocated).
+///
+/// * A lesson whose split rooms or rotation are renamed is
+///   [removed](Change::Removed) and [reinserted](Change::Inserted) with the
+///   same identifier.
+///
+/// * The room overrides from the first one which is renamed onwards are
+///   [removed](Change::OverrideRemoved) and [recorded
+///   again](Change::OverrideAdded) in their original order (such that the
+///   precedence of the overrides is retained).
+///
+/// The exceptions of the academic calendar have no history, and are
+/// rewritten in place.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoomMigration {
+    renames: HashMap<Location, Location>,
+}
+
+impl RoomMigration {
+    /// Creates a new `RoomMigration` without any renames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that the room identified by `from` is now identified by `to`.
+    ///
+    /// # Returns
+    ///
+    /// The identifier to which `from` was previously renamed (if any).
+    pub fn insert(&mut self, from: Location, to: Location) -> Option<Location> {
+        self.renames.insert(from, to)
+    }
+
+    /// Retrieves the new identifier of the room identified by `location`.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the room is not renamed by the `RoomMigration`.
+    pub fn get(&self, location: &Location) -> Option<Location> {
+        self.renames.get(location).copied()
+    }
+
+    /// Migrates the `location` provided (which is returned unchanged should
+    /// it not be renamed by the `RoomMigration`).
+    pub fn migrate(&self, location: Location) -> Location {
+        self.get(&location).unwrap_or(location)
+    }
+
+    /// Retrieves the number of renamed rooms.
+    pub fn len(&self) -> usize {
+        self.renames.len()
+    }
+
+    /// Determines whether no rooms are renamed.
+    pub fn is_empty(&self) -> bool {
+        self.renames.is_empty()
+    }
+
+    /// Creates an iterator over the renames, as `(from, to)` (in an arbitrary
+    /// order).
+    pub fn iter(&self) -> impl Iterator<Item = (&Location, &Location)> {
+        self.renames.iter()
+    }
+
+    /// Parses a map of renames: one rename per line, as the old room
+    /// identifier followed by `->` and the new room identifier (e.g.,
+    /// `U101 -> U201`) -- blank lines and lines starting with `#` are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should a line not contain two structurally valid
+    /// room identifiers (a line without `->` has an empty, and therefore
+    /// invalid, new identifier).
+    pub fn from_data(data: &str) -> Result<Self, ParseRegistryError> {
+        let mut migration = Self::new();
+
+        for (line, rename) in data
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        {
+            let parse = |code: &str| {
+                code.trim()
+                    .parse()
+                    .map_err(|_| ParseRegistryError::new(line, code.trim().to_string()))
+            };
+
+            let (from, to) = rename.split_once("->").unwrap_or((rename, ""));
+
+            migration.insert(parse(from)?, parse(to)?);
+        }
+
+        Ok(migration)
+    }
+
+    /// Retrieves the renames which have been officially announced by the
+    /// schools (as shipped with the crate).
+    pub fn known() -> Self {
+        // The embedded map is checked by the crate's tests
+        Self::from_data(KNOWN_RENAMES).unwrap()
+    }
+
+    /// Rewrites every [`Location`] stored by the `dataset` (i.e., by its
+    /// lessons, their split rooms and rotations, its room overrides, and its
+    /// exceptions) which is renamed by the `RoomMigration`.
+    ///
+    /// *See the [`RoomMigration`] documentation for how the changes are
+    /// recorded*.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries, room overrides and exceptions which were
+    /// rewritten.
+    pub fn apply(&self, dataset: &mut Dataset) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+
+        let timetable = &mut dataset.timetable;
+        let mut rewritten = 0;
+
+        let ids: Vec<EntryId> = timetable.entries().map(|(id, _)| id).collect();
+
+        for id in ids {
+            let Some(entry) = timetable.get(id) else {
+                continue;
+            };
+            let migrated = self.migrate_entry(entry);
+
+            if migrated == *entry {
+                continue;
+            }
+
+            rewritten += 1;
+
+            if migrated.split_rooms == entry.split_rooms && migrated.rotation == entry.rotation {
+                if let Some(location) = migrated.activity.location() {
+                    timetable.relocate(id, *location);
+                }
+            } else {
+                let entry = entry.clone();
+
+                timetable.commit(Change::Removed { id, entry });
+                timetable.commit(Change::Inserted {
+                    id,
+                    entry: migrated,
+                });
+            }
+        }
+
+        let overrides: Vec<_> = timetable
+            .overrides()
+            .iter()
+            .map(|room_override| {
+                let mut migrated = *room_override;
+
+                migrated.location = self.migrate(migrated.location);
+                migrated.from = migrated.from.map(|from| self.migrate(from));
+                (migrated != *room_override, migrated)
+            })
+            .collect();
+
+        // The overrides are recorded again from the first one renamed, such
+        // that the later overrides still take precedence over the earlier
+        if let Some(first) = overrides.iter().position(|(renamed, _)| *renamed) {
+            for index in (first..overrides.len()).rev() {
+                timetable.remove_override(index);
+            }
+
+            for (renamed, room_override) in overrides.into_iter().skip(first) {
+                rewritten += renamed as usize;
+                timetable.commit(Change::OverrideAdded(room_override));
+            }
+        }
+
+        let exceptions: Vec<_> = dataset
+            .calendar
+            .exceptions()
+            .filter_map(|(date, exception)| match exception {
+                DayException::Suspended { periods, activity } => {
+                    let migrated = self.migrate_activity(activity.clone());
+
+                    (migrated != *activity).then(|| {
+                        let periods = periods.clone();
+
+                        (
+                            date,
+                            DayException::Suspended {
+                                periods,
+                                activity: migrated,
+                            },
+                        )
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+
+        rewritten += exceptions.len();
+
+        for (date, exception) in exceptions {
+            dataset.calendar.add_exception(date, exception);
+        }
+
+        rewritten
+    }
+
+    // Migrate every location of the entry provided
+    fn migrate_entry(&self, entry: &Entry) -> Entry {
+        let mut migrated = entry.clone();
+
+        migrated.activity = self.migrate_activity(migrated.activity);
+
+        for room in &mut migrated.split_rooms {
+            *room = self.migrate(*room);
+        }
+
+        for stage in migrated
+            .rotation
+            .iter_mut()
+            .flat_map(|rotation| &mut rotation.stages)
+        {
+            stage.location = self.migrate(stage.location);
+        }
+
+        migrated
+    }
+
+    // Migrate the location of the activity provided (should it be a lesson)
+    fn migrate_activity(&self, mut activity: Activity) -> Activity {
+        if let Activity::Lesson { location, .. } = &mut activity {
+            *location = self.migrate(*location);
+        }
+
+        activity
+    }
+}
+
+impl FromIterator<(Location, Location)> for RoomMigration {
+    fn from_iter<I: IntoIterator<Item = (Location, Location)>>(iter: I) -> Self {
+        Self {
+            renames: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Rotation, Subject, Timetable};
+    use chrono::NaiveDate;
+
+    fn room(code: &str) -> Location {
+        code.parse().unwrap()
+    }
+
+    fn lesson(code: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new("9B".to_string()).unwrap(),
+            teacher: None,
+            location: room(code),
+        }
+    }
+
+    #[test]
+    fn room_migration_applied() {
+        assert!(RoomMigration::known().iter().all(|(from, to)| from != to));
+        assert_eq!(
+            RoomMigration::from_data("# Renames\nU101 -> X\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2: invalid room identifier `X`"
+        );
+        assert_eq!(RoomMigration::from_data("U101\n").unwrap_err().code(), "");
+
+        let date = |day| NaiveDate::from_ymd_opt(2024, 9, day).unwrap();
+        let migration: RoomMigration = [(room("U101"), room("U201")), (room("U102"), room("U202"))]
+            .into_iter()
+            .collect();
+
+        let mut dataset = Dataset::new(Timetable::new(), AcademicCalendar::default());
+        let timetable = &mut dataset.timetable;
+        let relocated = timetable.insert(timeslot!(W1MP1), lesson("U101"));
+        let untouched = timetable.insert(timeslot!(W1MP2), lesson("H201"));
+        let split = timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP3), lesson("H201"))
+                    .split_room(room("U102"))
+                    .rotating(Rotation::new(date(2)).stage(None, room("U101"))),
+            )
+            .unwrap();
+
+        timetable.override_room(timeslot!(W1MP4), date(2)..=date(6), room("U102"));
+        timetable.override_room(timeslot!(W1MP4), date(2)..=date(6), room("H202"));
+        dataset.calendar.add_exception(
+            date(9),
+            DayException::Suspended {
+                periods: vec![],
+                activity: lesson("U101"),
+            },
+        );
+
+        let before = dataset.timetable.history().len();
+        assert_eq!(migration.apply(&mut dataset), 4);
+
+        let timetable = &dataset.timetable;
+        let location = |id| timetable.get(id).unwrap().activity.location().copied();
+        assert_eq!(location(relocated), Some(room("U201")));
+        assert_eq!(location(untouched), Some(room("H201")));
+
+        let entry = timetable.get(split).unwrap();
+        assert_eq!(entry.split_rooms, [room("U202")]);
+        assert_eq!(
+            entry.rotation.as_ref().unwrap().stages[0].location,
+            room("U201")
+        );
+
+        // The later override still takes precedence over the earlier one
+        assert_eq!(
+            timetable.overridden_location(timeslot!(W1MP4), date(2)),
+            Some(room("H202"))
+        );
+        assert_eq!(timetable.overrides()[0].location, room("U202"));
+
+        // One relocation, the reinsertion, and both overrides recorded again
+        let changes: Vec<_> = timetable.history()[before..]
+            .iter()
+            .map(|record| &record.change)
+            .collect();
+        assert_eq!(changes.len(), 7);
+        assert_eq!(
+            changes[0],
+            &Change::Relocated {
+                id: relocated,
+                from: room("U101"),
+                to: room("U201")
+            }
+        );
+        assert!(matches!(changes[2], Change::Inserted { id, .. } if *id == split));
+
+        assert_eq!(
+            dataset.calendar.exception(date(9)),
+            Some(&DayException::Suspended {
+                periods: vec![],
+                activity: lesson("U201"),
+            })
+        );
+
+        // Migrations are not applied twice
+        assert_eq!(migration.apply(&mut dataset), 0);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::attendance::Roster;
+use crate::occupancy::Zone;
+use crate::{Activity, Class, Dataset, Location, Room, RoomKind, TimeSlot};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// A class moving between rooms at the change of lessons (*see
+/// [`Dataset::movement_graph`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Movement {
+    /// The class which moves.
+    pub class: Class,
+
+    /// The timeslot of the lesson the class leaves (i.e., the last timeslot
+    /// of a lesson spanning multiple periods, at the end of which the class
+    /// moves).
+    pub slot: TimeSlot,
+
+    /// The room of the lesson the class leaves.
+    pub from: Location,
+
+    /// The room of the lesson the class moves to (in the following period).
+    pub to: Location,
+}
+
+/// The movements of classes between rooms during a range of timeslots,
+/// which can be exported as a [DOT](https://graphviz.org/doc/info/lang.html)
+/// graph (e.g., to visualise the congestion of the corridors at the change
+/// of lessons).
+///
+/// *See [`Dataset::movement_graph`] for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MovementGraph {
+    movements: Vec<Movement>,
+    zones: bool,
+}
+
+impl MovementGraph {
+    /// Groups the rooms of the `MovementGraph` by their [zones](Zone) (i.e.,
+    /// the blocks and sections of the schools), such that only the movements
+    /// between zones are exported.
+    pub fn zones(mut self) -> Self {
+        self.zones = true;
+        self
+    }
+
+    /// Retrieves the movements of the `MovementGraph` (ordered by their
+    /// timeslots, and then by the order of the lessons in the timetable).
+    pub fn movements(&self) -> &[Movement] {
+        &self.movements
+    }
+
+    /// Exports the `MovementGraph` as a directed DOT graph, with an edge
+    /// between each pair of rooms (or zones) between which a class moves --
+    /// weighted by the number of students who move, as enrolled in each
+    /// class by the `roster` provided.
+    ///
+    /// # Remarks
+    ///
+    /// The nodes and edges are sorted by their names, such that the graph is
+    /// deterministic, and the width of each edge is proportional to its
+    /// weight (the heaviest edge being `8` points wide).
+    pub fn to_dot(&self, roster: &Roster) -> String {
+        let node = |location: &Location| match self.zones {
+            true => Zone::of(location).to_string(),
+            false => location.to_string(),
+        };
+
+        // The headcount and number of classes of each edge
+        let mut edges: BTreeMap<(String, String), (usize, usize)> = BTreeMap::new();
+
+        for movement in &self.movements {
+            let (from, to) = (node(&movement.from), node(&movement.to));
+
+            if from == to {
+                continue;
+            }
+
+            let edge = edges.entry((from, to)).or_default();
+            edge.0 += roster.students(movement.class.reference()).len();
+            edge.1 += 1;
+        }
+
+        let mut nodes: Vec<&String> = edges.keys().flat_map(|(from, to)| [from, to]).collect();
+        nodes.sort();
+        nodes.dedup();
+
+        let heaviest = edges.values().map(|(students, _)| *students).max();
+        let mut output = String::from("digraph movements {\n");
+
+        for node in nodes {
+            let _ = writeln!(output, "    {};", quote(node));
+        }
+
+        for ((from, to), (students, classes)) in &edges {
+            let width = match heaviest {
+                Some(heaviest) if heaviest > 0 => 1.0 + 7.0 * *students as f64 / heaviest as f64,
+                _ => 1.0,
+            };
+
+            let _ = writeln!(
+                output,
+                "    {} -> {} [weight={}, label=\"{} ({} {})\", penwidth={:.1}];",
+                quote(from),
+                quote(to),
+                students,
+                students,
+                classes,
+                if *classes == 1 { "class" } else { "classes" },
+                width
+            );
+        }
+
+        output.push_str("}\n");
+        output
+    }
+}
+
+impl Dataset {
+    /// Determines the movements of classes between rooms at the change of
+    /// lessons during the `slots` provided (i.e., each class whose lesson
+    /// is followed, in the next period of the same day, by a lesson of the
+    /// same class in a different room).
+    ///
+    /// ```
+    /// use timetableau::attendance::Roster;
+    /// use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, Subject};
+    /// use timetableau::Timetable;
+    ///
+    /// let mut timetable = Timetable::new();
+    /// for (slot, location) in [(timeslot!(W1MP1), "H201"), (timeslot!(W1MP2), "P105")] {
+    ///     timetable.insert(slot, Activity::Lesson {
+    ///         subject: Subject::new("Ma".to_string()).unwrap(),
+    ///         class: Class::new("10A".to_string()).unwrap(),
+    ///         teacher: None,
+    ///         location: location.parse().unwrap(),
+    ///     });
+    /// }
+    ///
+    /// let mut roster = Roster::new();
+    /// roster.enrol("10A", "1001");
+    ///
+    /// let dataset = Dataset::new(timetable, AcademicCalendar::default());
+    /// let graph = dataset.movement_graph(timeslot!(W1MP1)..=timeslot!(W1MP5));
+    ///
+    /// assert!(graph.to_dot(&roster).contains("\"H201\" -> \"P105\" [weight=1"));
+    /// assert!(graph.zones().to_dot(&roster).contains("\"Howard\" -> \"Parker\""));
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// Both lessons must take place during the `slots` (ordered by their
+    /// [indices](TimeSlot::index)), and lessons which take place remotely
+    /// are disregarded.
+    pub fn movement_graph(&self, slots: RangeInclusive<TimeSlot>) -> MovementGraph {
+        let range = slots.start().index()..=slots.end().index();
+        // The first and last timeslots of each lesson (i.e., a lesson spanning
+        // multiple periods is left after its last period)
+        let lessons: Vec<(TimeSlot, TimeSlot, &Class, &Location)> = self
+            .timetable
+            .entries()
+            .filter(|(_, entry)| range.contains(&entry.slot.index()))
+            .filter_map(|(_, entry)| match &entry.activity {
+                Activity::Lesson {
+                    class, location, ..
+                } if location.kind() != RoomKind::Remote => {
+                    let last = entry.slots().last().unwrap_or(entry.slot);
+
+                    Some((entry.slot, last, class, location))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut movements = Vec::new();
+
+        for (_, slot, class, from) in &lessons {
+            let Some(next) = slot.period.next() else {
+                continue;
+            };
+
+            for (to_slot, _, other, to) in &lessons {
+                if to_slot.week == slot.week
+                    && to_slot.day == slot.day
+                    && to_slot.period == next
+                    && other == class
+                    && to != from
+                {
+                    movements.push(Movement {
+                        class: (*class).clone(),
+                        slot: *slot,
+                        from: **from,
+                        to: **to,
+                    });
+                }
+            }
+        }
+
+        movements.sort_by_key(|movement| movement.slot.index());
+
+        MovementGraph {
+            movements,
+            zones: false,
+        }
+    }
+}
+
+/// Quotes the `name` provided as a DOT identifier.
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Subject, Timetable};
+
+    #[test]
+    fn movements_exported() {
+        let mut timetable = Timetable::new();
+        let mut lesson = |slot, class: &str, location: &str| {
+            timetable.insert(
+                slot,
+                Activity::Lesson {
+                    subject: Subject::new("Ma".to_string()).unwrap(),
+                    class: Class::new(class.to_string()).unwrap(),
+                    teacher: None,
+                    location: location.parse().unwrap(),
+                },
+            );
+        };
+
+        lesson(timeslot!(W1MP1), "10A", "H201");
+        lesson(timeslot!(W1MP2), "10A", "P105");
+        lesson(timeslot!(W1MP1), "10B", "H202");
+        lesson(timeslot!(W1MP2), "10B", "P105");
+        lesson(timeslot!(W1MP3), "10B", "P105");
+        lesson(timeslot!(W1MP4), "10B", "Remote (Teams)");
+        lesson(timeslot!(W1TP1), "10A", "H201");
+
+        let mut roster = Roster::new();
+        for student in ["1001", "1002", "1003"] {
+            roster.enrol("10A", student);
+        }
+        roster.enrol("10B", "1004");
+
+        let dataset = Dataset::new(timetable, AcademicCalendar::default());
+        let graph = dataset.movement_graph(timeslot!(W1MP1)..=timeslot!(W1TP5));
+
+        // Staying in a room, moving online, or moving across days is not a movement
+        assert_eq!(graph.movements().len(), 2);
+        assert_eq!(
+            graph.to_dot(&roster),
+            concat!(
+                "digraph movements {\n",
+                "    \"H201\";\n",
+                "    \"H202\";\n",
+                "    \"P105\";\n",
+                "    \"H201\" -> \"P105\" [weight=3, label=\"3 (1 class)\", penwidth=8.0];\n",
+                "    \"H202\" -> \"P105\" [weight=1, label=\"1 (1 class)\", penwidth=3.3];\n",
+                "}\n"
+            )
+        );
+        assert!(graph.clone().zones().to_dot(&roster).contains(
+            "\"Howard\" -> \"Parker\" [weight=4, label=\"4 (2 classes)\", penwidth=8.0];"
+        ));
+
+        // Movements out of the range of timeslots are disregarded
+        let graph = dataset.movement_graph(timeslot!(W1MP2)..=timeslot!(W1MP5));
+        assert!(graph.movements().is_empty());
+        assert_eq!(graph.to_dot(&roster), "digraph movements {\n}\n");
+    }
+
+    #[test]
+    fn movements_after_double() {
+        let lesson = |location: &str| Activity::Lesson {
+            subject: Subject::new("Sc".to_string()).unwrap(),
+            class: Class::new("10C".to_string()).unwrap(),
+            teacher: None,
+            location: location.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        timetable
+            .insert_spanning(timeslot!(W1MP3), 2, lesson("H201"))
+            .unwrap();
+        timetable.insert(timeslot!(W1MP5), lesson("P105"));
+
+        // The class moves at the end of the double, rather than after its
+        // first period
+        let dataset = Dataset::new(timetable, AcademicCalendar::default());
+        let graph = dataset.movement_graph(timeslot!(W1MP1)..=timeslot!(W1MP5));
+
+        assert_eq!(
+            graph.movements(),
+            [Movement {
+                class: Class::new("10C".to_string()).unwrap(),
+                slot: timeslot!(W1MP4),
+                from: "H201".parse().unwrap(),
+                to: "P105".parse().unwrap(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use crate::{Record, Room, Timetable};
+#[cfg(feature = "async")]
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Formatter};
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, Sender};
+#[cfg(feature = "async")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "async")]
+use std::task::{Context, Poll, Waker};
+
+/// The set of channels to which the changes made to a [`Timetable`] are
+/// sent.
+///
+/// # Remarks
+///
+/// Cloning a `Subscribers` produces an empty set of subscribers -- a clone of
+/// a timetable (e.g., one used for "what-if" planning) is a separate
+/// timetable, and changes made to it should not be reported to the
+/// subscribers of the original.
+pub(crate) struct Subscribers<L> {
+    senders: Vec<Sender<Record<L>>>,
+    #[cfg(feature = "async")]
+    streams: Vec<Arc<Mutex<StreamState<L>>>>,
+}
+
+impl<L: Clone> Subscribers<L> {
+    /// Sends the `record` provided to every subscriber, disregarding any
+    /// subscriber whose receiver has been dropped.
+    pub(crate) fn notify(&mut self, record: &Record<L>) {
+        self.senders
+            .retain(|sender| sender.send(record.clone()).is_ok());
+
+        // A stream is only referenced by the subscribers once it has been
+        // dropped
+        #[cfg(feature = "async")]
+        self.streams.retain(|state| {
+            if Arc::strong_count(state) == 1 {
+                return false;
+            }
+
+            state.lock().unwrap().push(record.clone());
+            true
+        });
+    }
+}
+
+impl<L> Default for Subscribers<L> {
+    fn default() -> Self {
+        Self {
+            senders: Vec::new(),
+            #[cfg(feature = "async")]
+            streams: Vec::new(),
+        }
+    }
+}
+
+impl<L> Clone for Subscribers<L> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<L> Debug for Subscribers<L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "async")]
+        let count = self.senders.len() + self.streams.len();
+        #[cfg(not(feature = "async"))]
+        let count = self.senders.len();
+
+        f.debug_struct("Subscribers")
+            .field("count", &count)
+            .finish()
+    }
+}
+
+// The streams of a timetable end once the timetable has been dropped (as
+// no further changes can be made to it)
+#[cfg(feature = "async")]
+impl<L> Drop for Subscribers<L> {
+    fn drop(&mut self) {
+        for state in &self.streams {
+            let mut state = state.lock().unwrap();
+            state.closed = true;
+
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+// The buffer of records shared between a timetable and a `ChangeStream`
+#[cfg(feature = "async")]
+struct StreamState<L> {
+    buffer: VecDeque<Record<L>>,
+    capacity: usize,
+    missed: usize,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+#[cfg(feature = "async")]
+impl<L> StreamState<L> {
+    // Buffer a record, discarding the oldest buffered record should the
+    // buffer be full, and wake the task awaiting the stream (if any)
+    fn push(&mut self, record: Record<L>) {
+        if self.buffer.len() == self.capacity {
+            self.buffer.pop_front();
+            self.missed += 1;
+        }
+
+        self.buffer.push_back(record);
+
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// An asynchronous stream of the changes made to a [`Timetable`].
+///
+/// *See [`Timetable::stream`] for more information*.
+#[cfg(feature = "async")]
+pub struct ChangeStream<L> {
+    state: Arc<Mutex<StreamState<L>>>,
+}
+
+#[cfg(feature = "async")]
+impl<L> ChangeStream<L> {
+    /// Attempts to retrieve the next [`Record`] of the stream, registering
+    /// the current task to be woken once a change is made should no record
+    /// be buffered.
+    ///
+    /// # Returns
+    ///
+    /// [`Poll::Ready`] with the next record, [`Poll::Ready`] with [`None`]
+    /// should the timetable have been dropped (and every buffered record
+    /// been retrieved), or [`Poll::Pending`] otherwise.
+    ///
+    /// # Remarks
+    ///
+    /// This has the same signature as `futures::Stream::poll_next`, such
+    /// that the stream can be adapted to any executor's stream trait with a
+    /// single line.
+    pub fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Record<L>>> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(record) = state.buffer.pop_front() {
+            return Poll::Ready(Some(record));
+        }
+
+        if state.closed {
+            return Poll::Ready(None);
+        }
+
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Waits for the next [`Record`] of the stream.
+    ///
+    /// # Returns
+    ///
+    /// The next record, or [`None`] should the timetable have been dropped
+    /// (and every buffered record been retrieved).
+    pub async fn next(&mut self) -> Option<Record<L>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    /// Retrieves the number of records which have been discarded because the
+    /// buffer of the stream was full.
+    ///
+    /// # Remarks
+    ///
+    /// Should any record have been discarded, the consumer has missed a
+    /// change, and should reload the timetable rather than applying the
+    /// remaining records.
+    pub fn missed(&self) -> usize {
+        self.state.lock().unwrap().missed
+    }
+}
+
+#[cfg(feature = "async")]
+impl<L> Debug for ChangeStream<L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+
+        f.debug_struct("ChangeStream")
+            .field("buffered", &state.buffer.len())
+            .field("missed", &state.missed)
+            .field("closed", &state.closed)
+            .finish()
+    }
+}
+
+impl<L: Room> Timetable<L> {
+    /// Subscribes to the changes made to the `Timetable`.
+    ///
+    /// A [`Record`] of every subsequent change made to the timetable (e.g., a
+    /// lesson being added, moved, or relocated) is sent to the returned
+    /// receiver -- this allows applications to notify affected students
+    /// without having to poll and compare the timetable.
+    ///
+    /// # Remarks
+    ///
+    /// Subscriptions are not carried over to clones of the timetable. To
+    /// unsubscribe, drop the receiver.
+    pub fn subscribe(&mut self) -> Receiver<Record<L>> {
+        let (sender, receiver) = mpsc::channel();
+
+        self.subscribers.senders.push(sender);
+        receiver
+    }
+
+    /// Subscribes to the changes made to the `Timetable` as an asynchronous
+    /// [`ChangeStream`], buffering at most `capacity` records.
+    ///
+    /// This allows server applications to await the changes made to the
+    /// timetable (rather than receiving them on a blocking channel):
+    ///
+    /// ```
+    /// # use timetableau::{timeslot, Activity, Timetable};
+    /// # async fn example() {
+    /// let mut timetable: Timetable = Timetable::new();
+    /// let mut changes = timetable.stream(64);
+    ///
+    /// timetable.insert(timeslot!(W1MP1), Activity::Break);
+    /// drop(timetable);
+    ///
+    /// while let Some(record) = changes.next().await {
+    ///     println!("{:?}", record.change);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// Changes are never blocked by a slow consumer -- should a burst of
+    /// edits fill the buffer, the oldest records are discarded and counted
+    /// (*see [`ChangeStream::missed`]*). The stream ends once the timetable
+    /// has been dropped, and, as with [`Self::subscribe`], is not carried
+    /// over to clones of the timetable.
+    ///
+    /// # Panics
+    ///
+    /// Panics should the `capacity` be `0`.
+    #[cfg(feature = "async")]
+    pub fn stream(&mut self, capacity: usize) -> ChangeStream<L> {
+        assert!(capacity > 0, "the capacity of a stream must not be 0");
+
+        let state = Arc::new(Mutex::new(StreamState {
+            buffer: VecDeque::new(),
+            capacity,
+            missed: 0,
+            waker: None,
+            closed: false,
+        }));
+
+        self.subscribers.streams.push(Arc::clone(&state));
+        ChangeStream { state }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Activity, Change};
+
+    #[test]
+    fn subscribe_receives_changes() {
+        let mut timetable = Timetable::new();
+        let receiver = timetable.subscribe();
+
+        let id = timetable.insert(timeslot!(W1MP1), Activity::Break);
+        timetable.move_entry(id, timeslot!(W1MP2)).unwrap();
+
+        let changes: Vec<Change> = receiver.try_iter().map(|record| record.change).collect();
+
+        assert_eq!(changes.len(), 2);
+        assert!(matches!(changes[1], Change::Moved { .. }));
+    }
+
+    #[test]
+    fn subscribe_not_cloned() {
+        let mut timetable = Timetable::new();
+        let receiver = timetable.subscribe();
+
+        timetable.clone().insert(timeslot!(W1MP1), Activity::Break);
+
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn stream_receives_changes() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::task::Wake;
+
+        struct Counter(AtomicUsize);
+
+        impl Wake for Counter {
+            fn wake(self: Arc<Self>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let counter = Arc::new(Counter(AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&counter));
+        let mut cx = Context::from_waker(&waker);
+
+        let mut timetable = Timetable::new();
+        let mut stream = timetable.stream(2);
+        let mut poll = |stream: &mut ChangeStream<_>| Pin::new(stream).poll_next(&mut cx);
+
+        assert!(poll(&mut stream).is_pending());
+
+        let id = timetable.insert(timeslot!(W1MP1), Activity::Break);
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+        // A burst of edits discards the oldest records
+        timetable.move_entry(id, timeslot!(W1MP2)).unwrap();
+        timetable.move_entry(id, timeslot!(W1MP3)).unwrap();
+        assert_eq!(stream.missed(), 1);
+
+        let Poll::Ready(Some(record)) = poll(&mut stream) else {
+            panic!("no record was buffered");
+        };
+        assert!(matches!(record.change, Change::Moved { .. }));
+
+        drop(timetable);
+
+        assert!(matches!(poll(&mut stream), Poll::Ready(Some(_))));
+        assert!(matches!(poll(&mut stream), Poll::Ready(None)));
+    }
+}
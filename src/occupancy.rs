@@ -0,0 +1,462 @@
+//! Whole-school maps of what is happening in every room during a period.
+//!
+//! An [`OccupancyBoard`] combines the timetable with the rooms of the
+//! schools (*see [`RoomRegistry`]*), their [closures](Closures), and the
+//! [bookings](Spaces) of bookable spaces, producing an [`OccupancyMap`] of
+//! every room during a period of a particular date -- grouped by the block
+//! (or section) in which each room is located. The map can be
+//! [rendered](OccupancyMap::render) for the "where is everyone" board used
+//! by reception and duty staff:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::occupancy::{OccupancyBoard, RoomUse};
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Period, RoomRegistry};
+//! use timetableau::{Subject, Term, Timetable, WeekCycle};
+//!
+//! let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+//! let calendar = AcademicCalendar::new(vec![Term::new(date, date).unwrap()]);
+//! let cycle = WeekCycle::new(date, calendar).unwrap();
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(timeslot!(W1MP1), Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new("10A".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "H201".parse().unwrap(),
+//! });
+//!
+//! let mut rooms = RoomRegistry::new();
+//! rooms.insert("H202".parse().unwrap());
+//!
+//! let map = OccupancyBoard::new(&rooms).map(&timetable, &cycle, date, Period::First);
+//!
+//! assert!(matches!(map.get(&"H201".parse().unwrap()), Some(RoomUse::Lessons(_))));
+//! assert_eq!(map.get(&"H202".parse().unwrap()), Some(&RoomUse::Free));
+//! assert!(map.render().contains("H202    Free"));
+//! ```
+
+use crate::booking::Spaces;
+use crate::closures::Closures;
+use crate::{
+    Activity, FearnhillSection, HighfieldBlock, Location, Occurrence, Period, Room, RoomRegistry,
+    School, Timetable, WeekCycle,
+};
+use chrono::NaiveDate;
+use std::fmt::{self, Display, Formatter, Write};
+
+// The width of the column of room identifiers of a rendered map
+const ROOM_WIDTH: usize = 8;
+
+/// The area of a school by which the rooms of an [`OccupancyMap`] are
+/// grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Zone {
+    /// A block of the Highfield school.
+    Block(HighfieldBlock),
+
+    /// A section of the Fearnhill school.
+    Section(FearnhillSection),
+
+    /// The rooms of a school which are neither in a block nor in a section
+    /// (e.g., halls, temporary classrooms, and off-site locations).
+    Elsewhere(School),
+}
+
+impl Zone {
+    /// Determines the `Zone` of the `location` provided.
+    pub fn of(location: &Location) -> Self {
+        let parts = location.parts();
+
+        match (parts.block, parts.section) {
+            (Some(block), _) => Self::Block(block),
+            (_, Some(section)) => Self::Section(section),
+            _ => Self::Elsewhere(parts.school),
+        }
+    }
+
+    // The position of the zone on a rendered map: the blocks of Highfield,
+    // the rest of Highfield, the sections of Fearnhill, and then the rest of
+    // Fearnhill
+    pub(crate) fn rank(self) -> (School, u8, u8) {
+        match self {
+            Self::Block(block) => (School::Highfield, 0, block as u8),
+            Self::Section(section) => (School::Fearnhill, 0, section as u8),
+            Self::Elsewhere(school) => (school, 1, 0),
+        }
+    }
+}
+
+impl Display for Zone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Block(block) => f.write_str(block.full_name()),
+            Self::Section(section) => f.write_str(section.full_name()),
+            Self::Elsewhere(school) => write!(f, "Elsewhere at {}", school),
+        }
+    }
+}
+
+/// What is happening in a room during a period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoomUse {
+    /// The room is hosting lessons (ordinarily one, unless lessons are
+    /// combined or clash).
+    Lessons(Vec<Occurrence>),
+
+    /// The room is a bookable space (*see [`Spaces`]*) with bookings.
+    Booked {
+        /// The name of the space.
+        name: String,
+
+        /// The number of students who booked the space.
+        bookings: usize,
+    },
+
+    /// The room is closed (*see [`Closures`]*), for the reason provided.
+    Closed(String),
+
+    /// The room is free.
+    Free,
+}
+
+impl Display for RoomUse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lessons(occurrences) => {
+                for (index, occurrence) in occurrences.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    match &occurrence.activity {
+                        Activity::Lesson {
+                            subject,
+                            class,
+                            teacher: Some(teacher),
+                            ..
+                        } => write!(f, "{} {} ({})", subject, class, teacher.code())?,
+                        Activity::Lesson { subject, class, .. } => {
+                            write!(f, "{} {}", subject, class)?
+                        }
+                        activity => write!(f, "{}", activity)?,
+                    }
+                }
+
+                Ok(())
+            }
+            Self::Booked { name, bookings } => write!(f, "{}: {} booked", name, bookings),
+            Self::Closed(reason) => write!(f, "Closed ({})", reason),
+            Self::Free => f.write_str("Free"),
+        }
+    }
+}
+
+/// What is happening in every room during a period of a date, grouped by
+/// [`Zone`].
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OccupancyMap {
+    /// The date of the map.
+    pub date: NaiveDate,
+
+    /// The period of the map.
+    pub period: Period,
+
+    /// The rooms of each zone (and what is happening within them), in order
+    /// of zone and then of room identifier.
+    pub zones: Vec<(Zone, Vec<(Location, RoomUse)>)>,
+}
+
+impl OccupancyMap {
+    /// Retrieves what is happening in the `location` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should the location be neither registered nor in use.
+    pub fn get(&self, location: &Location) -> Option<&RoomUse> {
+        self.rooms()
+            .find(|(room, _)| room == location)
+            .map(|(_, usage)| usage)
+    }
+
+    /// Creates an iterator over every room of the map (and what is happening
+    /// within it).
+    pub fn rooms(&self) -> impl Iterator<Item = (Location, &RoomUse)> + '_ {
+        self.zones
+            .iter()
+            .flat_map(|(_, rooms)| rooms.iter().map(|(room, usage)| (*room, usage)))
+    }
+
+    /// Creates an iterator over the rooms of the map which are free.
+    pub fn free(&self) -> impl Iterator<Item = Location> + '_ {
+        self.rooms()
+            .filter(|(_, usage)| **usage == RoomUse::Free)
+            .map(|(room, _)| room)
+    }
+
+    /// Renders the map as plain text for a wall display: a heading for the
+    /// date and period, followed by each zone and its rooms (e.g.,
+    /// `  H201    Ma 10A (JSM)`).
+    pub fn render(&self) -> String {
+        let mut output = format!(
+            "Where is everyone: {} (P{})\n",
+            self.date.format("%A %d %B"),
+            self.period as usize + 1
+        );
+
+        for (zone, rooms) in &self.zones {
+            let _ = writeln!(output, "\n{}", zone);
+
+            for (room, usage) in rooms {
+                let _ = writeln!(
+                    output,
+                    "  {:width$}{}",
+                    room.format_abbrev(ROOM_WIDTH - 1),
+                    usage,
+                    width = ROOM_WIDTH
+                );
+            }
+        }
+
+        output
+    }
+}
+
+/// Produces [`OccupancyMap`]s of the rooms of a [`RoomRegistry`].
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Copy)]
+pub struct OccupancyBoard<'a> {
+    rooms: &'a RoomRegistry,
+    closures: Option<&'a Closures>,
+    spaces: Option<&'a Spaces>,
+}
+
+impl<'a> OccupancyBoard<'a> {
+    /// Creates a new `OccupancyBoard` of the `rooms` provided, without any
+    /// closures or bookable spaces.
+    pub fn new(rooms: &'a RoomRegistry) -> Self {
+        Self {
+            rooms,
+            closures: None,
+            spaces: None,
+        }
+    }
+
+    /// Sets the `closures` of the rooms.
+    pub fn closures(mut self, closures: &'a Closures) -> Self {
+        self.closures = Some(closures);
+        self
+    }
+
+    /// Sets the bookable `spaces` whose bookings are shown (the spaces
+    /// without a location are disregarded).
+    pub fn spaces(mut self, spaces: &'a Spaces) -> Self {
+        self.spaces = Some(spaces);
+        self
+    }
+
+    /// Maps what is happening in every room during the `period` of the
+    /// `date` provided.
+    ///
+    /// The rooms of the map are the registered rooms, and any other room in
+    /// which a lesson takes place (excluding online lessons and rooms which
+    /// are yet to be confirmed). The lessons of each room are those which
+    /// actually take place (*see [`Timetable::occurrences`]*).
+    ///
+    /// # Remarks
+    ///
+    /// A closed room is shown as closed regardless of its lessons or
+    /// bookings (i.e., the lessons are yet to be moved out of it -- *see
+    /// [`Closures::rehouse`]*), and a room with lessons is shown as hosting
+    /// them regardless of its bookings.
+    pub fn map(
+        &self,
+        timetable: &Timetable,
+        cycle: &WeekCycle,
+        date: NaiveDate,
+        period: Period,
+    ) -> OccupancyMap {
+        let occurrences: Vec<Occurrence> = timetable
+            .occurrences(cycle, date)
+            .into_iter()
+            .filter(|occurrence| occurrence.slot.period == period)
+            .collect();
+
+        let mut locations: Vec<Location> = self.rooms.iter().copied().collect();
+        locations.extend(
+            occurrences
+                .iter()
+                .flat_map(|occurrence| locations_of(timetable, occurrence))
+                .filter(|location| location.kind().is_occupied()),
+        );
+        locations.sort_by_key(|location| (Zone::of(location).rank(), location.to_string()));
+        locations.dedup();
+
+        let mut zones: Vec<(Zone, Vec<(Location, RoomUse)>)> = Vec::new();
+
+        for location in locations {
+            let usage = self.usage(timetable, &occurrences, location, date, period);
+            let zone = Zone::of(&location);
+
+            match zones.last_mut() {
+                Some((last, rooms)) if *last == zone => rooms.push((location, usage)),
+                _ => zones.push((zone, vec![(location, usage)])),
+            }
+        }
+
+        OccupancyMap {
+            date,
+            period,
+            zones,
+        }
+    }
+
+    // Determine what is happening in the `location` provided
+    fn usage(
+        &self,
+        timetable: &Timetable,
+        occurrences: &[Occurrence],
+        location: Location,
+        date: NaiveDate,
+        period: Period,
+    ) -> RoomUse {
+        if let Some(closures) = self.closures {
+            if let Some(index) = closures.closure_of(&location, date) {
+                return RoomUse::Closed(closures.closures()[index].reason.clone());
+            }
+        }
+
+        let lessons: Vec<Occurrence> = occurrences
+            .iter()
+            .filter(|occurrence| locations_of(timetable, occurrence).contains(&location))
+            .cloned()
+            .collect();
+
+        if !lessons.is_empty() {
+            return RoomUse::Lessons(lessons);
+        }
+
+        let booked = self.spaces.and_then(|spaces| {
+            spaces
+                .resources()
+                .iter()
+                .enumerate()
+                .filter(|(_, resource)| resource.location == Some(location))
+                .map(|(index, resource)| (resource, spaces.at(index, date, period).count()))
+                .find(|(_, bookings)| *bookings > 0)
+        });
+
+        match booked {
+            Some((resource, bookings)) => RoomUse::Booked {
+                name: resource.name.clone(),
+                bookings,
+            },
+            None => RoomUse::Free,
+        }
+    }
+}
+
+// Retrieve every room in which an `occurrence` takes place (i.e., including
+// the rooms across which the class of a split lesson is split)
+fn locations_of(timetable: &Timetable, occurrence: &Occurrence) -> Vec<Location> {
+    let split = occurrence
+        .entry
+        .and_then(|id| timetable.get(id))
+        .map_or(&[][..], |entry| &entry.split_rooms);
+
+    occurrence
+        .activity
+        .location()
+        .into_iter()
+        .chain(split)
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booking::{Booking, Resource};
+    use crate::closures::{Closure, ClosureScope};
+    use crate::{timeslot, AcademicCalendar, Class, Entry, Subject, Teacher, Term};
+
+    #[test]
+    fn occupancy_mapped_and_rendered() {
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date, date).unwrap()]);
+        let cycle = WeekCycle::new(date, calendar).unwrap();
+        let room = |code: &str| -> Location { code.parse().unwrap() };
+
+        let mut timetable = Timetable::new();
+        timetable
+            .insert_entry(
+                Entry::new(
+                    timeslot!(W1MP2),
+                    Activity::Lesson {
+                        subject: Subject::new("Ma".to_string()).unwrap(),
+                        class: Class::new("10A".to_string()).unwrap(),
+                        teacher: Teacher::new("JSM".to_string()).ok(),
+                        location: room("FH M1"),
+                    },
+                )
+                .split_room(room("H201")),
+            )
+            .unwrap();
+
+        let mut rooms = RoomRegistry::new();
+        for code in ["H202", "Hall", "H201", "H203", "P105"] {
+            rooms.insert(room(code));
+        }
+
+        let mut closures = Closures::new();
+        closures.add(Closure {
+            scope: ClosureScope::Room(room("P105")),
+            start: date,
+            end: date,
+            reason: "Flooding".to_string(),
+        });
+
+        let mut spaces = Spaces::new();
+        let library = spaces.add(Resource::new("Library", 20).location(room("H202")));
+        spaces
+            .book(&cycle, Booking::new(library, date, Period::Second, "123"))
+            .unwrap();
+
+        let map = OccupancyBoard::new(&rooms)
+            .closures(&closures)
+            .spaces(&spaces)
+            .map(&timetable, &cycle, date, Period::Second);
+
+        assert_eq!(
+            map.zones.iter().map(|(zone, _)| *zone).collect::<Vec<_>>(),
+            [
+                Zone::Block(HighfieldBlock::Howard),
+                Zone::Block(HighfieldBlock::Parker),
+                Zone::Elsewhere(School::Highfield),
+                Zone::Section(FearnhillSection::Mathematics),
+            ]
+        );
+        assert_eq!(map.free().collect::<Vec<_>>(), [room("H203"), room("Hall")]);
+        assert_eq!(
+            map.render(),
+            "Where is everyone: Monday 04 September (P2)\n\
+             \n\
+             Howard\n  \
+             H201    Ma 10A (JSM)\n  \
+             H202    Library: 1 booked\n  \
+             H203    Free\n\
+             \n\
+             Parker\n  \
+             P105    Closed (Flooding)\n\
+             \n\
+             Elsewhere at Highfield\n  \
+             Hall    Free\n\
+             \n\
+             Mathematics\n  \
+             FH M1   Ma 10A (JSM)\n"
+        );
+    }
+}
@@ -0,0 +1,623 @@
+//! Heuristic improvement of valid [`Timetable`]s.
+//!
+//! Whereas the [`allocate`](crate::allocate) and [`validate`](crate::validate)
+//! modules are concerned with whether a timetable is feasible, an
+//! [`Optimiser`] improves a feasible timetable against a weighted objective
+//! (*see [`Weights`] for more information*) by simulated annealing: lessons
+//! are repeatedly moved to another timeslot, swapped with another lesson, or
+//! swapped into another lesson's room, and each change is kept should it
+//! improve the objective (or, occasionally, should it not, such that the
+//! search can escape local minima).
+//!
+//! No change ever causes a teacher, class, or room to be used by multiple
+//! lessons during the same timeslot -- a timetable without clashes remains
+//! without clashes.
+//!
+//! ```
+//! use timetableau::optimise::Optimiser;
+//! use timetableau::Timetable;
+//!
+//! let mut timetable = Timetable::new();
+//! let outcome = Optimiser::new().seed(42).optimise(&mut timetable);
+//!
+//! assert!(outcome.score.cost(&outcome.weights) <= outcome.initial.cost(&outcome.weights));
+//! ```
+
+use crate::availability::StaffAvailability;
+use crate::random::Random;
+use crate::timetable::span;
+use crate::{Activity, Class, EntryId, Location, Period, RangedU8, Teacher, TimeSlot, Timetable};
+use std::collections::{BTreeMap, HashSet};
+
+/// The weight of each term of the objective minimised by an [`Optimiser`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Weights {
+    /// The weight of each free period between a class's first and last
+    /// lessons of a day.
+    pub gaps: u32,
+
+    /// The weight of each change of room between a teacher's consecutive
+    /// lessons.
+    pub room_moves: u32,
+
+    /// The weight of each change of school between the consecutive lessons
+    /// of a teacher or class.
+    pub cross_site: u32,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            gaps: 2,
+            room_moves: 1,
+            cross_site: 5,
+        }
+    }
+}
+
+/// The terms of the objective for a particular [`Timetable`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Score {
+    /// The number of free periods between the first and last lessons of each
+    /// class's days.
+    pub gaps: u32,
+
+    /// The number of changes of room between teachers' consecutive lessons.
+    pub room_moves: u32,
+
+    /// The number of changes of school between the consecutive lessons of
+    /// teachers and classes.
+    pub cross_site: u32,
+}
+
+impl Score {
+    /// Computes the (weighted) cost of the `Score` -- lower is better.
+    pub fn cost(&self, weights: &Weights) -> u32 {
+        self.gaps * weights.gaps
+            + self.room_moves * weights.room_moves
+            + self.cross_site * weights.cross_site
+    }
+}
+
+/// The result of [optimising](Optimiser::optimise) a [`Timetable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The weights used by the optimiser.
+    pub weights: Weights,
+
+    /// The score of the timetable before it was optimised.
+    pub initial: Score,
+
+    /// The score of the timetable after it was optimised.
+    pub score: Score,
+
+    /// The number of lessons which were moved or relocated.
+    pub changed: usize,
+}
+
+/// Improves a [`Timetable`] by simulated annealing.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone)]
+pub struct Optimiser {
+    weights: Weights,
+    iterations: usize,
+    seed: Option<u64>,
+    pinned: HashSet<EntryId>,
+    availability: StaffAvailability,
+}
+
+impl Default for Optimiser {
+    fn default() -> Self {
+        Self {
+            weights: Weights::default(),
+            iterations: 20_000,
+            seed: None,
+            pinned: HashSet::new(),
+            availability: StaffAvailability::new(),
+        }
+    }
+}
+
+impl Optimiser {
+    /// Creates a new `Optimiser` with the default [`Weights`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `weights` of the objective.
+    pub fn weights(mut self, weights: Weights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Sets the number of changes attempted (`20000` by default).
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Seeds the random number generator, such that optimising the same
+    /// timetable always produces the same result.
+    ///
+    /// Without a seed, the generator is seeded from the system clock.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Prevents the lesson with the identifier `id` from being moved or
+    /// relocated (e.g., a consortium lesson whose timeslot is fixed by the
+    /// other school).
+    pub fn pin(mut self, id: EntryId) -> Self {
+        self.pinned.insert(id);
+        self
+    }
+
+    /// Prevents lessons from being moved to a timeslot during which their
+    /// teacher is unavailable (*see
+    /// [`StaffAvailability`](crate::availability::StaffAvailability)*).
+    ///
+    /// # Remarks
+    ///
+    /// Lessons which are already scheduled outside of their teacher's
+    /// availability are not moved for that reason alone -- use the
+    /// [`WithinAvailability`](crate::validate::WithinAvailability) rule to
+    /// find them.
+    pub fn availability(mut self, availability: StaffAvailability) -> Self {
+        self.availability = availability;
+        self
+    }
+
+    /// Optimises the lessons of the `timetable` in place.
+    ///
+    /// Only the best timetable found is applied (through
+    /// [`Timetable::move_entry`] and [`Timetable::relocate`], such that each
+    /// change is recorded in the timetable's history) -- the timetable is
+    /// never made worse.
+    pub fn optimise(&self, timetable: &mut Timetable) -> Outcome {
+        let mut lessons = lessons(timetable);
+
+        let movable: Vec<usize> = (0..lessons.len())
+            .filter(|i| !self.pinned.contains(&lessons[*i].id))
+            .collect();
+
+        let initial = score(&lessons);
+        let mut cost = initial.cost(&self.weights);
+        let mut best = (cost, lessons.clone());
+
+        let mut random = self.seed.map_or_else(Random::from_clock, Random::new);
+
+        for iteration in 0..self.iterations {
+            if movable.is_empty() || lessons.len() < 2 {
+                break;
+            }
+
+            // Cool geometrically from 2.0 to 0.05
+            let progress = iteration as f64 / self.iterations as f64;
+            let temperature = 2.0 * 0.025f64.powf(progress);
+
+            let i = movable[random.below(movable.len())];
+            let j = random.below(lessons.len());
+            let previous = (lessons[i].clone(), lessons[j].clone());
+
+            match random.below(3) {
+                // Move the lesson to another timeslot
+                0 => {
+                    let index = RangedU8::new(random.below(50) as u8).unwrap();
+                    lessons[i].slot = TimeSlot::with_index(index);
+                }
+
+                // Swap the timeslots of two lessons
+                1 if i != j && !self.pinned.contains(&lessons[j].id) => {
+                    let slot = lessons[i].slot;
+                    lessons[i].slot = lessons[j].slot;
+                    lessons[j].slot = slot;
+                }
+
+                // Swap the rooms of two lessons
+                2 if i != j
+                    && lessons[i].slot == lessons[j].slot
+                    && !self.pinned.contains(&lessons[j].id) =>
+                {
+                    let location = lessons[i].location;
+                    lessons[i].location = lessons[j].location;
+                    lessons[j].location = location;
+                }
+
+                _ => continue,
+            }
+
+            let candidate = score(&lessons).cost(&self.weights);
+            let accepted = fits(&lessons, i)
+                && fits(&lessons, j)
+                && self.available(&lessons[i])
+                && self.available(&lessons[j])
+                && (candidate <= cost
+                    || random.unit() < (-f64::from(candidate - cost) / temperature).exp());
+
+            if accepted {
+                cost = candidate;
+
+                if cost < best.0 {
+                    best = (cost, lessons.clone());
+                }
+            } else {
+                lessons[i] = previous.0;
+                lessons[j] = previous.1;
+            }
+        }
+
+        // Apply the best timetable found
+        let (_, best) = best;
+        let mut changed = 0;
+
+        for lesson in &best {
+            let entry = match timetable.get(lesson.id) {
+                Some(entry) => entry,
+                None => continue,
+            };
+            let moved = entry.slot != lesson.slot;
+            let relocated = entry.activity.location() != Some(&lesson.location);
+
+            // Each lesson of the best timetable fits its timeslot (*see
+            // `fits`*), and so can always be moved
+            if moved && timetable.move_entry(lesson.id, lesson.slot).is_err() {
+                continue;
+            }
+            if relocated {
+                timetable.relocate(lesson.id, lesson.location);
+            }
+            if moved || relocated {
+                changed += 1;
+            }
+        }
+
+        Outcome {
+            weights: self.weights,
+            initial,
+            score: score(&best),
+            changed,
+        }
+    }
+}
+
+impl Optimiser {
+    // Whether every teacher of the lesson is available during each of its
+    // timeslots
+    fn available(&self, lesson: &Lesson) -> bool {
+        lesson.teachers().all(|teacher| {
+            lesson
+                .slots()
+                .all(|slot| self.availability.is_available(teacher, slot))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Lesson {
+    id: EntryId,
+    slot: TimeSlot,
+    periods: u8,
+    location: Location,
+    teacher: Option<Teacher>,
+    class: Class,
+    co_teachers: Vec<Teacher>,
+    split_rooms: Vec<Location>,
+}
+
+impl Lesson {
+    // The teachers of the lesson (*see `Entry::teachers`*)
+    fn teachers(&self) -> impl Iterator<Item = &Teacher> + '_ {
+        self.teacher.iter().chain(&self.co_teachers)
+    }
+
+    // The rooms of the lesson (*see `Entry::locations`*)
+    fn locations(&self) -> impl Iterator<Item = &Location> + '_ {
+        std::iter::once(&self.location).chain(&self.split_rooms)
+    }
+
+    // The timeslots occupied by the lesson (*see `Entry::slots`*)
+    fn slots(&self) -> impl Iterator<Item = TimeSlot> + '_ {
+        let TimeSlot { week, day, period } = self.slot;
+
+        Period::ALL
+            .into_iter()
+            .skip(period as usize)
+            .take(usize::from(self.periods))
+            .map(move |period| TimeSlot { week, day, period })
+    }
+
+    // Whether the lesson occupies any of the timeslots of the `other` lesson
+    fn overlaps(&self, other: &Lesson) -> bool {
+        self.slots()
+            .any(|slot| other.slots().any(|occupied| occupied == slot))
+    }
+}
+
+// Collect the lessons of the timetable
+fn lessons(timetable: &Timetable) -> Vec<Lesson> {
+    timetable
+        .lessons()
+        .filter_map(|(id, entry)| match &entry.activity {
+            Activity::Lesson {
+                class,
+                teacher,
+                location,
+                ..
+            } => Some(Lesson {
+                id,
+                slot: entry.slot,
+                periods: entry.periods.get(),
+                location: *location,
+                teacher: teacher.clone(),
+                class: class.clone(),
+                co_teachers: entry.co_teachers.clone(),
+                split_rooms: entry.split_rooms.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+// Whether the lesson at index `i` fits its timeslot (i.e., its span does not
+// extend past the end of the day), and does not clash
+// with any other lesson during any of the periods it spans (i.e., shares
+// none of its teachers, rooms, or class -- including co-teachers and split
+// rooms)
+fn fits(lessons: &[Lesson], i: usize) -> bool {
+    let lesson = &lessons[i];
+
+    span(lesson.slot, lesson.periods).is_ok()
+        && lessons.iter().enumerate().all(|(j, other)| {
+            j == i
+                || !other.overlaps(lesson)
+                || (other.class != lesson.class
+                    && other
+                        .locations()
+                        .all(|location| lesson.locations().all(|room| room != location))
+                    && other
+                        .teachers()
+                        .all(|teacher| lesson.teachers().all(|code| code != teacher)))
+        })
+}
+
+// Compute the score of a set of lessons
+fn score(lessons: &[Lesson]) -> Score {
+    // The periods (and locations) of each teacher and class on each day
+    let mut teachers: BTreeMap<(&str, usize), Vec<(Period, Location)>> = BTreeMap::new();
+    let mut classes: BTreeMap<(&str, usize), Vec<(Period, Location)>> = BTreeMap::new();
+
+    for lesson in lessons {
+        let day = lesson.slot.index() / 5;
+
+        for slot in lesson.slots() {
+            if let Some(teacher) = &lesson.teacher {
+                teachers
+                    .entry((teacher.code(), day))
+                    .or_default()
+                    .push((slot.period, lesson.location));
+            }
+            classes
+                .entry((lesson.class.reference(), day))
+                .or_default()
+                .push((slot.period, lesson.location));
+        }
+    }
+
+    let mut score = Score::default();
+
+    // Count the transitions between consecutive periods of a day
+    let consecutive = |day: &mut Vec<(Period, Location)>| {
+        day.sort_by_key(|(period, _)| *period);
+        day.windows(2)
+            .filter(|pair| pair[0].0.next() == Some(pair[1].0))
+            .map(|pair| (pair[0].1, pair[1].1))
+            .collect::<Vec<_>>()
+    };
+
+    for day in teachers.values_mut() {
+        for (from, to) in consecutive(day) {
+            score.room_moves += u32::from(from != to);
+            score.cross_site += u32::from(from.school() != to.school());
+        }
+    }
+
+    for day in classes.values_mut() {
+        for (from, to) in consecutive(day) {
+            score.cross_site += u32::from(from.school() != to.school());
+        }
+
+        let first = day.first().map_or(0, |(period, _)| *period as u32);
+        let last = day.last().map_or(0, |(period, _)| *period as u32);
+        let mut periods: Vec<Period> = day.iter().map(|(period, _)| *period).collect();
+        periods.dedup();
+
+        score.gaps += last - first + 1 - periods.len() as u32;
+    }
+
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Subject};
+
+    fn lesson(class: &str, teacher: &str, location: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Teacher::new(teacher.to_string()).ok(),
+            location: location.parse().unwrap(),
+        }
+    }
+
+    fn timetable() -> Timetable {
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("10A", "JSM", "H101"));
+        timetable.insert(timeslot!(W1MP2), lesson("10A", "ABC", "FH M1"));
+        timetable.insert(timeslot!(W1MP5), lesson("10A", "JSM", "H102"));
+        timetable.insert(timeslot!(W1TP1), lesson("10B", "ABC", "FH M1"));
+        timetable.insert(timeslot!(W1TP2), lesson("10B", "JSM", "H101"));
+        timetable
+    }
+
+    #[test]
+    fn optimise_improves() {
+        let mut timetable = timetable();
+        let outcome = Optimiser::new().seed(7).optimise(&mut timetable);
+        let weights = Weights::default();
+
+        assert_eq!(
+            outcome.initial,
+            Score {
+                gaps: 2,
+                room_moves: 0,
+                cross_site: 2
+            }
+        );
+        assert!(outcome.score.cost(&weights) < outcome.initial.cost(&weights));
+        assert!(outcome.changed > 0);
+
+        // The timetable remains without clashes
+        let report = crate::validate::Validator::new()
+            .rule(crate::validate::NoClashes)
+            .validate(&timetable);
+
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn optimise_reproducible() {
+        let (mut a, mut b) = (timetable(), timetable());
+        let pinned = a.entries().next().unwrap().0;
+
+        Optimiser::new().seed(1).pin(pinned).optimise(&mut a);
+        Optimiser::new().seed(1).pin(pinned).optimise(&mut b);
+
+        let slots = |timetable: &Timetable| -> Vec<TimeSlot> {
+            timetable.entries().map(|(_, entry)| entry.slot).collect()
+        };
+
+        assert_eq!(slots(&a), slots(&b));
+        assert_eq!(a.get(pinned).unwrap().slot, timeslot!(W1MP1));
+    }
+
+    #[test]
+    fn optimise_respects_availability() {
+        use crate::availability::Availability;
+        use chrono::Weekday;
+
+        let mut timetable = timetable();
+        let staff = StaffAvailability::new()
+            .teacher("JSM", Availability::works(&[Weekday::Mon, Weekday::Tue]))
+            .teacher("ABC", Availability::works(&[Weekday::Mon, Weekday::Tue]));
+
+        Optimiser::new()
+            .seed(3)
+            .availability(staff.clone())
+            .optimise(&mut timetable);
+
+        let report = crate::validate::Validator::new()
+            .rule(crate::validate::WithinAvailability::new(staff))
+            .validate(&timetable);
+
+        assert!(report.findings.is_empty());
+    }
+
+    #[test]
+    fn optimise_double_lessons() {
+        use crate::availability::Availability;
+        use chrono::Weekday;
+
+        let lesson = |slot, periods, teacher: &str, location: &str| Lesson {
+            id: EntryId(0),
+            slot,
+            periods,
+            location: location.parse().unwrap(),
+            teacher: Teacher::new(teacher.to_string()).ok(),
+            class: Class::new("10A".to_string()).unwrap(),
+            co_teachers: Vec::new(),
+            split_rooms: Vec::new(),
+        };
+
+        // A lesson during the second period of a double clashes with it
+        let mut lessons = vec![
+            lesson(timeslot!(W1MP1), 2, "JSM", "H101"),
+            lesson(timeslot!(W1MP2), 1, "JSM", "H101"),
+        ];
+        assert!(!fits(&lessons, 0) && !fits(&lessons, 1));
+
+        lessons[1].slot = timeslot!(W1MP3);
+        assert!(fits(&lessons, 0) && fits(&lessons, 1));
+
+        // Nor can a double be moved past the end of the day
+        lessons[0].slot = timeslot!(W1MP5);
+        assert!(!fits(&lessons, 0));
+
+        // A double cannot be taught should its teacher be unavailable for
+        // its second period
+        let optimiser = Optimiser::new().availability(StaffAvailability::new().teacher(
+            "JSM",
+            Availability::full().unavailable(Weekday::Mon, Period::Second),
+        ));
+
+        assert!(!optimiser.available(&lesson(timeslot!(W1MP1), 2, "JSM", "H101")));
+        assert!(optimiser.available(&lesson(timeslot!(W1MP3), 2, "JSM", "H101")));
+    }
+
+    #[test]
+    fn optimise_staffed_lessons() {
+        use crate::availability::Availability;
+        use crate::validate::{NoClashes, Validator};
+        use crate::Entry;
+        use chrono::Weekday;
+
+        let mut timetable = Timetable::new();
+        let staffed = timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP1), lesson("10A", "JSM", "H101"))
+                    .co_teacher(Teacher::new("ABC".to_string()).unwrap())
+                    .split_room("H102".parse().unwrap()),
+            )
+            .unwrap();
+        let other = timetable.insert(timeslot!(W1MP2), lesson("10B", "DEF", "H103"));
+
+        let optimiser = Optimiser::new().availability(StaffAvailability::new().teacher(
+            "ABC",
+            Availability::full().unavailable(Weekday::Mon, Period::First),
+        ));
+        let lessons = lessons(&timetable);
+        assert_eq!((lessons[0].id, lessons[1].id), (staffed, other));
+
+        // A co-teacher must also be available
+        assert!(!optimiser.available(&lessons[0]));
+        assert!(optimiser.available(&lessons[1]));
+
+        // Another lesson can use neither the co-teacher nor the split room
+        for (teacher, location) in [("ABC", "H104"), ("XYZ", "H102")] {
+            let mut lessons = lessons.clone();
+            lessons[1].slot = timeslot!(W1MP1);
+            lessons[1].teacher = Teacher::new(teacher.to_string()).ok();
+            lessons[1].location = location.parse().unwrap();
+
+            assert!(!fits(&lessons, 1));
+        }
+
+        let mut lessons = lessons;
+        lessons[1].slot = timeslot!(W1MP1);
+        assert!(fits(&lessons, 1));
+
+        // The optimised timetable remains without clashes
+        for seed in 0..10 {
+            let mut timetable = timetable.clone();
+            Optimiser::new().seed(seed).optimise(&mut timetable);
+
+            assert!(Validator::new()
+                .rule(NoClashes)
+                .validate(&timetable)
+                .is_valid());
+        }
+    }
+}
@@ -0,0 +1,422 @@
+//! Scheduling of parents' evening appointments.
+//!
+//! A parents' evening is divided into appointments of 5 to 10 minutes, each
+//! between a family and one of the teachers of their child. Teachers are
+//! seated at the tables of the rooms (or halls) used for the evening, and
+//! may only be available for part of it -- a [`Scheduler`] allocates an
+//! appointment to each [`Family`] with each teacher they have requested,
+//! such that no teacher or family has two appointments at once:
+//!
+//! ```
+//! use chrono::NaiveTime;
+//! use timetableau::parents_evening::{Family, Scheduler};
+//! use timetableau::{RangedU8, Teacher};
+//!
+//! let time = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+//! let teacher = |code: &str| Teacher::new(code.to_string()).unwrap();
+//!
+//! let scheduler = Scheduler::new(time(16, 0), time(19, 0), RangedU8::new(5).unwrap())
+//!     .room("Hall".parse().unwrap(), 20)
+//!     .teacher(teacher("ABC"), (time(16, 0), time(19, 0)))
+//!     .teacher(teacher("DEF"), (time(17, 0), time(19, 0)));
+//!
+//! let families = [Family::new("Smith")
+//!     .teacher(teacher("ABC"))
+//!     .teacher(teacher("DEF"))
+//!     .back_to_back(true)];
+//!
+//! let schedule = scheduler.schedule(&families).unwrap();
+//! let smith = schedule.for_family(0);
+//!
+//! assert_eq!(smith.len(), 2);
+//! // The earliest run of appointments ends as soon as DEF is available
+//! assert_eq!(smith[0].start, time(16, 55));
+//! assert_eq!(smith[1].start, time(17, 0));
+//! ```
+//!
+//! # Remarks
+//!
+//! Families preferring their appointments back-to-back are scheduled first
+//! (as their appointments are the most constrained), followed by the
+//! families requesting the most teachers -- should no consecutive run of
+//! appointments be found for a family preferring one, each of their
+//! appointments is scheduled at the earliest time available instead.
+
+use crate::{Location, RangedU8, Teacher};
+use chrono::{Duration, NaiveTime};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// A family attending a parents' evening.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Family {
+    /// The name of the family (e.g., `Smith`).
+    pub name: String,
+
+    /// The teachers with whom the family has requested an appointment.
+    pub teachers: Vec<Teacher>,
+
+    /// Whether the family would prefer their appointments to be consecutive
+    /// (i.e., to minimise the time spent waiting between them).
+    pub back_to_back: bool,
+}
+
+impl Family {
+    /// Creates a new `Family` which has not requested any appointments.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            teachers: Vec::new(),
+            back_to_back: false,
+        }
+    }
+
+    /// Requests an appointment with the `teacher` provided.
+    pub fn teacher(mut self, teacher: Teacher) -> Self {
+        self.teachers.push(teacher);
+        self
+    }
+
+    /// Sets whether the family would prefer their appointments to be
+    /// consecutive.
+    pub fn back_to_back(mut self, back_to_back: bool) -> Self {
+        self.back_to_back = back_to_back;
+        self
+    }
+}
+
+/// An appointment between a [`Family`] and a teacher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Appointment {
+    /// The index of the family (within the slice passed to
+    /// [`Scheduler::schedule`]).
+    pub family: usize,
+
+    /// The teacher with whom the appointment takes place.
+    pub teacher: Teacher,
+
+    /// The room in which the teacher is seated.
+    pub location: Location,
+
+    /// The time at which the appointment starts.
+    pub start: NaiveTime,
+
+    /// The time at which the appointment ends.
+    pub end: NaiveTime,
+}
+
+/// The appointments of a parents' evening, produced by a [`Scheduler`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schedule {
+    /// Every appointment (ordered by start time, then by teacher).
+    pub appointments: Vec<Appointment>,
+
+    /// The requested appointments which could not be scheduled, as
+    /// `(family, teacher)` pairs -- either the teacher is not attending, or
+    /// the teacher and the family have no free time in common.
+    pub unscheduled: Vec<(usize, Teacher)>,
+}
+
+impl Schedule {
+    /// Retrieves the appointments of the teacher with the staff `code`
+    /// provided (in chronological order).
+    pub fn for_teacher(&self, code: &str) -> Vec<&Appointment> {
+        self.appointments
+            .iter()
+            .filter(|appointment| appointment.teacher.code() == code)
+            .collect()
+    }
+
+    /// Retrieves the appointments of the `family` provided (in chronological
+    /// order) -- the family is identified by its index within the slice
+    /// passed to [`Scheduler::schedule`].
+    pub fn for_family(&self, family: usize) -> Vec<&Appointment> {
+        self.appointments
+            .iter()
+            .filter(|appointment| appointment.family == family)
+            .collect()
+    }
+}
+
+/// An error returned when the rooms of a parents' evening do not have
+/// enough tables to seat every teacher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unseated {
+    /// The teachers for whom no table is available.
+    pub teachers: Vec<Teacher>,
+}
+
+impl Display for Unseated {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let codes: Vec<&str> = self
+            .teachers
+            .iter()
+            .map(|teacher| teacher.code().as_str())
+            .collect();
+
+        write!(f, "no table is available for: {}", codes.join(", "))
+    }
+}
+
+impl Error for Unseated {}
+
+/// Schedules the appointments of a parents' evening.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scheduler {
+    start: NaiveTime,
+    end: NaiveTime,
+    length: Duration,
+    rooms: Vec<(Location, usize)>,
+    teachers: Vec<(Teacher, (NaiveTime, NaiveTime))>,
+}
+
+impl Scheduler {
+    /// Creates a new `Scheduler` for an evening running from `start` until
+    /// `end`, divided into appointments which are each `minutes` long.
+    ///
+    /// The scheduler has no rooms or teachers.
+    pub fn new(start: NaiveTime, end: NaiveTime, minutes: RangedU8<5, 10>) -> Self {
+        Self {
+            start,
+            end,
+            length: Duration::minutes(i64::from(minutes.get())),
+            rooms: Vec::new(),
+            teachers: Vec::new(),
+        }
+    }
+
+    /// Adds a room with the number of `tables` provided (i.e., the number
+    /// of teachers who can be seated in the room).
+    ///
+    /// Teachers are seated in the order in which they were added, filling
+    /// each room before the next (in the order in which the rooms were
+    /// added).
+    pub fn room(mut self, location: Location, tables: usize) -> Self {
+        self.rooms.push((location, tables));
+        self
+    }
+
+    /// Adds a teacher who is available for the `(start, end)` times provided
+    /// -- only appointments falling entirely within these times are given to
+    /// the teacher.
+    pub fn teacher(mut self, teacher: Teacher, available: (NaiveTime, NaiveTime)) -> Self {
+        self.teachers.push((teacher, available));
+        self
+    }
+
+    /// Schedules the appointments requested by the `families` provided.
+    ///
+    /// *See the [module](self) documentation for more information*.
+    ///
+    /// # Errors
+    ///
+    /// [`Unseated`] is returned should the rooms have fewer tables than there
+    /// are teachers.
+    pub fn schedule(&self, families: &[Family]) -> Result<Schedule, Unseated> {
+        // Seat each teacher at a table
+        let seats: Vec<Location> = self
+            .rooms
+            .iter()
+            .flat_map(|(location, tables)| std::iter::repeat_n(*location, *tables))
+            .take(self.teachers.len())
+            .collect();
+
+        if seats.len() < self.teachers.len() {
+            return Err(Unseated {
+                teachers: self.teachers[seats.len()..]
+                    .iter()
+                    .map(|(teacher, _)| teacher.clone())
+                    .collect(),
+            });
+        }
+
+        let slots = ((self.end - self.start).num_minutes() / self.length.num_minutes()).max(0);
+        let slots = slots as usize;
+        let time = |slot: usize| self.start + self.length * slot as i32;
+
+        // Whether each teacher is free during each slot
+        let mut free: Vec<Vec<bool>> = self
+            .teachers
+            .iter()
+            .map(|(_, (start, end))| {
+                (0..slots)
+                    .map(|slot| time(slot) >= *start && time(slot + 1) <= *end)
+                    .collect()
+            })
+            .collect();
+
+        // Schedule the most constrained families first
+        let mut order: Vec<usize> = (0..families.len()).collect();
+        order.sort_by_key(|&family| {
+            (
+                !families[family].back_to_back,
+                std::cmp::Reverse(families[family].teachers.len()),
+            )
+        });
+
+        let mut schedule = Schedule::default();
+        let mut booked: Vec<(usize, usize, usize)> = Vec::new();
+
+        for family in order {
+            let mut requested: Vec<usize> = Vec::new();
+
+            for teacher in &families[family].teachers {
+                match self.teachers.iter().position(|(other, _)| other == teacher) {
+                    Some(index) if !requested.contains(&index) => requested.push(index),
+                    Some(_) => {}
+                    None => schedule.unscheduled.push((family, teacher.clone())),
+                }
+            }
+
+            let mut busy = vec![false; slots];
+
+            if families[family].back_to_back && !requested.is_empty() {
+                let run = (0..(slots + 1).saturating_sub(requested.len())).find_map(|start| {
+                    consecutive(&free, &mut requested.clone(), start).map(|run| (start, run))
+                });
+
+                if let Some((start, run)) = run {
+                    for (slot, teacher) in (start..).zip(run) {
+                        free[teacher][slot] = false;
+                        booked.push((slot, teacher, family));
+                    }
+
+                    continue;
+                }
+            }
+
+            for teacher in requested {
+                match (0..slots).find(|&slot| free[teacher][slot] && !busy[slot]) {
+                    Some(slot) => {
+                        free[teacher][slot] = false;
+                        busy[slot] = true;
+                        booked.push((slot, teacher, family));
+                    }
+                    None => schedule
+                        .unscheduled
+                        .push((family, self.teachers[teacher].0.clone())),
+                }
+            }
+        }
+
+        booked.sort_unstable();
+        schedule.appointments = booked
+            .into_iter()
+            .map(|(slot, teacher, family)| Appointment {
+                family,
+                teacher: self.teachers[teacher].0.clone(),
+                location: seats[teacher],
+                start: time(slot),
+                end: time(slot + 1),
+            })
+            .collect();
+        schedule.unscheduled.sort();
+
+        Ok(schedule)
+    }
+}
+
+// Find an order in which the remaining teachers can each be given one of the
+// consecutive slots starting at the slot provided
+fn consecutive(free: &[Vec<bool>], remaining: &mut Vec<usize>, slot: usize) -> Option<Vec<usize>> {
+    if remaining.is_empty() {
+        return Some(Vec::new());
+    }
+
+    for i in 0..remaining.len() {
+        let teacher = remaining[i];
+
+        if !free[teacher][slot] {
+            continue;
+        }
+
+        remaining.swap_remove(i);
+
+        if let Some(mut run) = consecutive(free, remaining, slot + 1) {
+            run.insert(0, teacher);
+            return Some(run);
+        }
+
+        remaining.push(teacher);
+        let last = remaining.len() - 1;
+        remaining.swap(i, last);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn teacher(code: &str) -> Teacher {
+        Teacher::new(code.to_string()).unwrap()
+    }
+
+    #[test]
+    fn parents_evening_schedule() {
+        let scheduler = Scheduler::new(time(16, 0), time(16, 30), RangedU8::new(10).unwrap())
+            .room("H201".parse().unwrap(), 1)
+            .room("Hall".parse().unwrap(), 2)
+            .teacher(teacher("ABC"), (time(16, 0), time(16, 30)))
+            .teacher(teacher("DEF"), (time(16, 0), time(16, 30)))
+            .teacher(teacher("GHI"), (time(16, 10), time(16, 25)));
+
+        let families = [
+            Family::new("Jones").teacher(teacher("ABC")),
+            Family::new("Smith")
+                .teacher(teacher("ABC"))
+                .teacher(teacher("DEF"))
+                .teacher(teacher("XYZ"))
+                .back_to_back(true),
+            Family::new("Patel")
+                .teacher(teacher("GHI"))
+                .teacher(teacher("DEF")),
+            Family::new("Brown").teacher(teacher("GHI")),
+        ];
+
+        let schedule = scheduler.schedule(&families).unwrap();
+
+        // The back-to-back family is scheduled first, in consecutive slots
+        let smith: Vec<_> = schedule
+            .for_family(1)
+            .into_iter()
+            .map(|appointment| (appointment.teacher.code().as_str(), appointment.start))
+            .collect();
+        assert_eq!(smith, [("ABC", time(16, 0)), ("DEF", time(16, 10))]);
+
+        // GHI is only available for a single (whole) appointment
+        assert_eq!(schedule.for_teacher("GHI").len(), 1);
+        assert_eq!(
+            schedule.for_teacher("GHI")[0].location,
+            "Hall".parse().unwrap()
+        );
+        assert_eq!(schedule.for_teacher("ABC")[1].start, time(16, 10));
+        assert_eq!(
+            schedule.unscheduled,
+            [(1, teacher("XYZ")), (3, teacher("GHI"))]
+        );
+
+        // No family has two appointments at once
+        for family in 0..families.len() {
+            let appointments = schedule.for_family(family);
+
+            assert!(appointments
+                .windows(2)
+                .all(|pair| pair[0].end <= pair[1].start));
+        }
+
+        let error = Scheduler::new(time(16, 0), time(17, 0), RangedU8::new(5).unwrap())
+            .teacher(teacher("ABC"), (time(16, 0), time(17, 0)))
+            .schedule(&families)
+            .unwrap_err();
+
+        assert_eq!(error.teachers, [teacher("ABC")]);
+    }
+}
@@ -0,0 +1,702 @@
+//! Persistence of [`Timetable`]s in a versioned on-disk format.
+//!
+//! A timetable saved by one version of the crate must remain loadable by
+//! every later version (schools keep their saved timetables for years) -- as
+//! a result, each saved timetable records the version of the format in
+//! which it was saved, and [`load_any_version`] upgrades older versions to
+//! the [latest version](LATEST_VERSION) using explicit migration steps (e.g.,
+//! [`migrate_v1_to_v2`]):
+//!
+//! ```
+//! use timetableau::persist::{self, LATEST_VERSION};
+//! use timetableau::{timeslot, Activity, Timetable};
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(timeslot!(W1MP1), Activity::Registration);
+//!
+//! let saved = persist::save(&timetable);
+//! assert!(saved.starts_with(&format!("format = {}", LATEST_VERSION)));
+//!
+//! // Definitions written before the format was versioned are version 1
+//! let legacy = timetable.to_toml_string();
+//!
+//! assert_eq!(persist::load_any_version(&saved), Ok(timetable.clone()));
+//! assert_eq!(persist::load_any_version(&legacy), Ok(timetable));
+//! ```
+//!
+//! # Versions
+//!
+//! Every version of the format is a TOML document:
+//!
+//! 1. The [timetable definition](Timetable::from_toml_str) (i.e., the
+//!    activities of the timetable), without a `format` key or any activity
+//!    spanning multiple periods.
+//!
+//! 2. Introduces the `format = 2` key (which precedes any table) and the
+//!    room overrides of the timetable, as an array of inline tables:
+//!
+//!    ```toml
+//!    format = 2
+//!    overrides = [
+//!        { slot = "week1.mon.p3", start = "2023-09-04", end = "2023-09-08", room = "Hall" },
+//!    ]
+//!
+//!    [week1]
+//!    mon.p3 = { subject = "Ma", class = "10A/Ma1", room = "H201" }
+//!    ```
+//!
+//! 3. Introduces the optional `from` field of a room override, which
+//!    restricts the override to the lessons taking place in that room (*see
+//!    [`Timetable::override_location`]*):
+//!
+//!    ```toml
+//!    format = 3
+//!    overrides = [
+//!        { slot = "week1.mon.p3", start = "2023-09-04", end = "2023-09-04", room = "Sports Hall", from = "Field" },
+//!    ]
+//!    ```
+//!
+//! 4. Introduces the optional `periods` field of an activity, which is the
+//!    number of consecutive periods it spans (*see [`Entry::periods`]*), and
+//!    the optional `co_teachers` and `split_rooms` fields of a lesson (*see
+//!    [`Entry::co_teachers`] and [`Entry::split_rooms`]*):
+//!
+//!    ```toml
+//!    format = 4
+//!
+//!    [week1]
+//!    mon.p3 = { subject = "Sc", class = "10A/Sc1", room = "H201", periods = 2 }
+//!    tue.p1 = { activity = "Independent Study", periods = 2 }
+//!    wed.p2 = { subject = "PE", class = "10A", teacher = "ABC", room = "Field", co_teachers = ["DEF"], split_rooms = ["Sports Hall"] }
+//!    ```
+//!
+//! 5. Introduces the optional `rotation` field of a lesson, with the `start`
+//!    date of the rotation and its `stages` (*see [`Entry::rotation`]*):
+//!
+//!    ```toml
+//!    format = 5
+//!
+//!    [week1]
+//!    mon.p1 = { subject = "DT", class = "9A", room = "FH T1", rotation = { start = "2023-09-04", stages = [{ room = "FH T1" }, { teacher = "ABC", room = "FH T2" }] } }
+//!    ```
+//!
+//! A new version of the format must be accompanied by a new `VersionN`
+//! structure, a `migrate_vM_to_vN` step from the previous version, and a
+//! parser for the new version -- the parsers of earlier versions must never
+//! be changed.
+//!
+//! # Remarks
+//!
+//! Only the entries and the room overrides of a timetable are saved -- its
+//! history, its author, and the identifiers of its entries are not.
+
+use crate::definition::{activities, entries, location, slot};
+use crate::timeslot::slot_key;
+use crate::toml::{self, KeyValue, Spanned, TomlValue};
+use crate::{
+    Activity, Entry, ParseOptions, RoomOverride, TimeSlot, Timetable, TomlError, TomlErrorKind,
+};
+use chrono::NaiveDate;
+use std::fmt::Write;
+use std::ops::Range;
+
+/// The latest version of the format (i.e., the version in which timetables
+/// are [saved](save)).
+pub const LATEST_VERSION: i64 = 5;
+
+/// A timetable saved in version 1 of the format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version1 {
+    /// The activities of the timetable (in the order in which they were
+    /// defined).
+    pub entries: Vec<(TimeSlot, Activity)>,
+}
+
+/// A timetable saved in version 2 of the format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version2 {
+    /// The activities of the timetable (in the order in which they were
+    /// defined).
+    pub entries: Vec<(TimeSlot, Activity)>,
+
+    /// The room overrides of the timetable (in the order in which they were
+    /// recorded).
+    pub overrides: Vec<RoomOverride>,
+}
+
+/// A timetable saved in version 3 of the format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version3 {
+    /// The activities of the timetable (in the order in which they were
+    /// defined).
+    pub entries: Vec<(TimeSlot, Activity)>,
+
+    /// The room overrides of the timetable (in the order in which they were
+    /// recorded), including those restricted to a single room.
+    pub overrides: Vec<RoomOverride>,
+}
+
+/// A timetable saved in version 4 of the format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version4 {
+    /// The entries of the timetable (in the order in which they were
+    /// defined), including those spanning multiple periods, and the
+    /// team-taught and split lessons.
+    pub entries: Vec<Entry>,
+
+    /// The room overrides of the timetable (in the order in which they were
+    /// recorded).
+    pub overrides: Vec<RoomOverride>,
+}
+
+/// A timetable saved in version 5 of the format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version5 {
+    /// The entries of the timetable (in the order in which they were
+    /// defined), including the lessons which rotate between teachers and
+    /// rooms.
+    pub entries: Vec<Entry>,
+
+    /// The room overrides of the timetable (in the order in which they were
+    /// recorded).
+    pub overrides: Vec<RoomOverride>,
+}
+
+/// Migrates a timetable saved in version 1 of the format to version 2.
+///
+/// Version 1 could not record room overrides, so the migrated timetable has
+/// none.
+pub fn migrate_v1_to_v2(saved: Version1) -> Version2 {
+    Version2 {
+        entries: saved.entries,
+        overrides: Vec::new(),
+    }
+}
+
+/// Migrates a timetable saved in version 2 of the format to version 3.
+///
+/// Every override of version 2 moves every lesson of its timeslot, so the
+/// overrides are migrated unchanged.
+pub fn migrate_v2_to_v3(saved: Version2) -> Version3 {
+    Version3 {
+        entries: saved.entries,
+        overrides: saved.overrides,
+    }
+}
+
+/// Migrates a timetable saved in version 3 of the format to version 4.
+///
+/// Version 3 could not record activities spanning multiple periods, nor the
+/// co-teachers and split rooms of lessons, so every migrated entry spans a
+/// single period, and is taught by a single teacher in a single room.
+pub fn migrate_v3_to_v4(saved: Version3) -> Version4 {
+    Version4 {
+        entries: saved
+            .entries
+            .into_iter()
+            .map(|(slot, activity)| Entry::new(slot, activity))
+            .collect(),
+        overrides: saved.overrides,
+    }
+}
+
+/// Migrates a timetable saved in version 4 of the format to version 5.
+///
+/// Version 4 could not record the rotations of lessons, so no migrated
+/// entry rotates.
+pub fn migrate_v4_to_v5(saved: Version4) -> Version5 {
+    Version5 {
+        entries: saved.entries,
+        overrides: saved.overrides,
+    }
+}
+
+impl From<Version5> for Timetable {
+    fn from(saved: Version5) -> Self {
+        let mut timetable = Timetable::new();
+
+        // The spans (and the staffing) of the entries were checked as they
+        // were parsed
+        for entry in saved.entries {
+            timetable.push_entry(entry);
+        }
+
+        for room_override in saved.overrides {
+            let dates = room_override.start..=room_override.end;
+
+            match room_override.from {
+                Some(from) => timetable.override_location(
+                    room_override.slot,
+                    dates,
+                    from,
+                    room_override.location,
+                ),
+                None => timetable.override_room(room_override.slot, dates, room_override.location),
+            };
+        }
+
+        timetable
+    }
+}
+
+/// Loads a saved timetable, regardless of the version of the format in which
+/// it was saved.
+///
+/// *See the [module](self) documentation for more information*.
+///
+/// # Errors
+///
+/// A [`TomlError`] pointing at the first problem found within the saved
+/// timetable -- [`TomlErrorKind::UnsupportedVersion`] should it have been
+/// saved in an unknown version of the format (e.g., by a newer version of
+/// the crate).
+pub fn load_any_version(input: &str) -> Result<Timetable, TomlError> {
+    load_any_version_with(input, &ParseOptions::default())
+}
+
+/// Loads a saved timetable (regardless of the version of the format in
+/// which it was saved), using the `options` provided to parse room codes.
+///
+/// *See [`load_any_version`] for more information*.
+///
+/// # Errors
+///
+/// A [`TomlError`] pointing at the first problem found within the saved
+/// timetable -- should the `options` be [strict](ParseOptions::strict), this
+/// includes any room which is not in their registry.
+pub fn load_any_version_with(input: &str, options: &ParseOptions) -> Result<Timetable, TomlError> {
+    let mut pairs = toml::parse(input).map_err(|syntax| TomlError::syntax(input, syntax))?;
+    let mut version = (0..0, 1);
+
+    if let Some(index) = pairs
+        .iter()
+        .position(|(key, _)| matches!(&key[..], [(_, name)] if name == "format"))
+    {
+        let (_, (span, value)) = pairs.remove(index);
+
+        version = match value {
+            TomlValue::Integer(version) => (span, version),
+            _ => return Err(TomlError::new(input, span, TomlErrorKind::UnexpectedValue)),
+        };
+    }
+
+    let saved = match version {
+        (_, 1) => migrate_v4_to_v5(migrate_v3_to_v4(migrate_v2_to_v3(migrate_v1_to_v2(
+            parse_v1(input, pairs, options)?,
+        )))),
+        (_, 2) => migrate_v4_to_v5(migrate_v3_to_v4(migrate_v2_to_v3(parse_v2(
+            input, pairs, options,
+        )?))),
+        (_, 3) => migrate_v4_to_v5(migrate_v3_to_v4(parse_v3(input, pairs, options)?)),
+        (_, 4) => migrate_v4_to_v5(parse_v4(input, pairs, options)?),
+        (_, 5) => parse_v5(input, pairs, options)?,
+        (span, version) => {
+            return Err(TomlError::new(
+                input,
+                span,
+                TomlErrorKind::UnsupportedVersion(version),
+            ))
+        }
+    };
+
+    Ok(Timetable::from(saved))
+}
+
+/// Saves the `timetable` provided in the [latest version](LATEST_VERSION) of
+/// the format.
+///
+/// *See the [module](self) documentation for more information*.
+pub fn save(timetable: &Timetable) -> String {
+    let mut output = format!("format = {}\n", LATEST_VERSION);
+
+    if !timetable.overrides().is_empty() {
+        output.push_str("overrides = [\n");
+
+        for room_override in timetable.overrides() {
+            let _ = write!(
+                output,
+                "    {{ slot = \"{}\", start = \"{}\", end = \"{}\", room = \"{}\"",
+                slot_key(room_override.slot),
+                room_override.start.format("%Y-%m-%d"),
+                room_override.end.format("%Y-%m-%d"),
+                room_override.location,
+            );
+
+            if let Some(from) = room_override.from {
+                let _ = write!(output, ", from = \"{}\"", from);
+            }
+
+            output.push_str(" },\n");
+        }
+
+        output.push_str("]\n");
+    }
+
+    if !timetable.is_empty() {
+        output.push('\n');
+        output.push_str(&timetable.to_toml_string());
+    }
+
+    output
+}
+
+// Parse a timetable saved in version 1 of the format
+fn parse_v1(
+    input: &str,
+    pairs: Vec<KeyValue>,
+    options: &ParseOptions,
+) -> Result<Version1, TomlError> {
+    Ok(Version1 {
+        entries: activities(input, pairs, options)?,
+    })
+}
+
+// Parse a timetable saved in version 2 of the format
+fn parse_v2(
+    input: &str,
+    pairs: Vec<KeyValue>,
+    options: &ParseOptions,
+) -> Result<Version2, TomlError> {
+    // Version 2 is version 3 without restricted overrides
+    let (overrides, pairs) = parse_overrides(input, pairs, options, false)?;
+
+    Ok(Version2 {
+        entries: activities(input, pairs, options)?,
+        overrides,
+    })
+}
+
+// Parse a timetable saved in version 3 of the format
+fn parse_v3(
+    input: &str,
+    pairs: Vec<KeyValue>,
+    options: &ParseOptions,
+) -> Result<Version3, TomlError> {
+    let (overrides, pairs) = parse_overrides(input, pairs, options, true)?;
+
+    Ok(Version3 {
+        entries: activities(input, pairs, options)?,
+        overrides,
+    })
+}
+
+// Parse a timetable saved in version 4 of the format
+fn parse_v4(
+    input: &str,
+    pairs: Vec<KeyValue>,
+    options: &ParseOptions,
+) -> Result<Version4, TomlError> {
+    let (overrides, pairs) = parse_overrides(input, pairs, options, true)?;
+
+    Ok(Version4 {
+        entries: entries(input, pairs, options, false)?,
+        overrides,
+    })
+}
+
+// Parse a timetable saved in version 5 of the format
+fn parse_v5(
+    input: &str,
+    pairs: Vec<KeyValue>,
+    options: &ParseOptions,
+) -> Result<Version5, TomlError> {
+    let (overrides, pairs) = parse_overrides(input, pairs, options, true)?;
+
+    Ok(Version5 {
+        entries: entries(input, pairs, options, true)?,
+        overrides,
+    })
+}
+
+// Parse the room overrides of a saved timetable (`from` being whether the
+// overrides may be restricted to a single room), returning them alongside
+// the remaining key/value pairs (i.e., those of its entries)
+fn parse_overrides(
+    input: &str,
+    mut pairs: Vec<KeyValue>,
+    options: &ParseOptions,
+    from: bool,
+) -> Result<(Vec<RoomOverride>, Vec<KeyValue>), TomlError> {
+    let mut overrides = Vec::new();
+
+    if let Some(index) = pairs
+        .iter()
+        .position(|(key, _)| matches!(&key[..], [(_, name)] if name == "overrides"))
+    {
+        let (_, (span, value)) = pairs.remove(index);
+
+        let values = match value {
+            TomlValue::Array(values) => values,
+            _ => return Err(TomlError::new(input, span, TomlErrorKind::UnexpectedValue)),
+        };
+
+        for value in values {
+            overrides.push(room_override(input, value, options, from)?);
+        }
+    }
+
+    Ok((overrides, pairs))
+}
+
+// Parse a single room override of a saved timetable
+fn room_override(
+    input: &str,
+    (span, value): Spanned<TomlValue>,
+    options: &ParseOptions,
+    allow_from: bool,
+) -> Result<RoomOverride, TomlError> {
+    let error = |span: &Range<usize>, kind| TomlError::new(input, span.clone(), kind);
+
+    let members = match value {
+        TomlValue::Table(members) => members,
+        _ => return Err(error(&span, TomlErrorKind::UnexpectedValue)),
+    };
+
+    let mut slot_field = None;
+    let mut start = None;
+    let mut end = None;
+    let mut room = None;
+    let mut from = None;
+
+    for ((key_span, key), (value_span, value)) in members {
+        let field = match key.as_str() {
+            "slot" => &mut slot_field,
+            "start" => &mut start,
+            "end" => &mut end,
+            "room" => &mut room,
+            "from" if allow_from => &mut from,
+            _ => return Err(error(&key_span, TomlErrorKind::UnknownField(key))),
+        };
+
+        match value {
+            TomlValue::String(value) => *field = Some((value_span, value)),
+            _ => return Err(error(&value_span, TomlErrorKind::UnexpectedValue)),
+        }
+    }
+
+    let require = |field: Option<Spanned<String>>, name| {
+        field.ok_or_else(|| error(&span, TomlErrorKind::MissingField(name)))
+    };
+    let date = |(span, date): Spanned<String>| {
+        NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map_err(|_| error(&span, TomlErrorKind::InvalidDate(date)))
+    };
+
+    // The slot is written as a key (e.g., `week1.mon.p3`)
+    let (slot_span, slot_key) = require(slot_field, "slot")?;
+    let key: Vec<Spanned<String>> = slot_key
+        .split('.')
+        .map(|part| (slot_span.clone(), part.to_string()))
+        .collect();
+
+    let room_override = RoomOverride {
+        slot: slot(input, &key)?,
+        start: date(require(start, "start")?)?,
+        end: date(require(end, "end")?)?,
+        location: location(require(room, "room")?, options)
+            .map_err(|(span, kind)| error(&span, kind))?,
+        from: from
+            .map(|from| location(from, options).map_err(|(span, kind)| error(&span, kind)))
+            .transpose()?,
+    };
+
+    if room_override.end < room_override.start {
+        return Err(error(&span, TomlErrorKind::EmptyOverride));
+    }
+
+    Ok(room_override)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, Location, Rotation, Subject, Teacher};
+
+    #[test]
+    fn load_and_migrate_versions() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let hall: Location = "Hall".parse().unwrap();
+
+        let mut timetable = Timetable::new();
+        timetable.insert(
+            timeslot!(W2FP5),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("10A".to_string()).unwrap(),
+                teacher: None,
+                location: "H201".parse().unwrap(),
+            },
+        );
+        timetable.insert(timeslot!(W1MP1), Activity::Registration);
+        timetable.override_room(timeslot!(W2FP5), date(4)..=date(8), hall);
+        timetable.override_room(timeslot!(W1MP1), date(4)..=date(4), hall);
+        timetable.override_location(
+            timeslot!(W2FP5),
+            date(8)..=date(8),
+            "Field".parse().unwrap(),
+            "Sports Hall".parse().unwrap(),
+        );
+
+        let saved = save(&timetable);
+        let loaded = load_any_version(&saved).unwrap();
+
+        assert_eq!(loaded.overrides(), timetable.overrides());
+        assert_eq!(loaded.len(), 2);
+
+        // Version 1 definitions are migrated (without any overrides)
+        let legacy = load_any_version(&timetable.to_toml_string()).unwrap();
+
+        assert!(legacy.overrides().is_empty());
+        assert_eq!(
+            legacy.entries().map(|(_, entry)| entry).collect::<Vec<_>>(),
+            loaded.entries().map(|(_, entry)| entry).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            load_any_version("format = 1\n[week1]\nmon.p1 = 'Break'")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let error = load_any_version("format = 6\n").unwrap_err();
+
+        assert_eq!(error.kind, TomlErrorKind::UnsupportedVersion(6));
+        assert_eq!(error.column, 10);
+
+        let error = load_any_version(
+            "format = 2\noverrides = [{ slot = 'week1.mon.p1', start = '2023-09-08', \
+             end = '2023-09-04', room = 'Hall' }]",
+        )
+        .unwrap_err();
+
+        assert_eq!(error.kind, TomlErrorKind::EmptyOverride);
+        assert_eq!(error.line, 2);
+
+        // Overrides restricted to a single room were introduced by version 3
+        let error = load_any_version(
+            "format = 2\noverrides = [{ slot = 'week1.mon.p1', start = '2023-09-04', \
+             end = '2023-09-04', room = 'Hall', from = 'Field' }]",
+        )
+        .unwrap_err();
+
+        assert_eq!(error.kind, TomlErrorKind::UnknownField("from".to_string()));
+
+        // Activities spanning multiple periods were introduced by version 4
+        let error = load_any_version(
+            "format = 3\n[week1]\nmon.p1 = { subject = 'Sc', class = '10A', room = 'H201', \
+             periods = 2 }",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            TomlErrorKind::UnknownField("periods".to_string())
+        );
+    }
+
+    #[test]
+    fn save_spanning_entries() {
+        let mut timetable = Timetable::new();
+        let double = timetable
+            .insert_spanning(
+                timeslot!(W1MP3),
+                2,
+                Activity::Lesson {
+                    subject: Subject::new("Sc".to_string()).unwrap(),
+                    class: Class::new("10A".to_string()).unwrap(),
+                    teacher: None,
+                    location: "H201".parse().unwrap(),
+                },
+            )
+            .unwrap();
+        timetable
+            .insert_spanning(timeslot!(W2FP1), 2, Activity::SchoolStudy)
+            .unwrap();
+
+        let loaded = load_any_version(&save(&timetable)).unwrap();
+
+        assert_eq!(loaded, timetable);
+        assert_eq!(loaded.get(double).unwrap().periods.get(), 2);
+    }
+
+    #[test]
+    fn save_staffed_entries() {
+        let code = |code: &str| Teacher::new(code.to_string()).unwrap();
+        let mut timetable = Timetable::new();
+        let id = timetable
+            .insert_entry(
+                Entry::new(
+                    timeslot!(W1WP2),
+                    Activity::Lesson {
+                        subject: Subject::new("PE".to_string()).unwrap(),
+                        class: Class::new("10A".to_string()).unwrap(),
+                        teacher: Some(code("ABC")),
+                        location: "Field".parse().unwrap(),
+                    },
+                )
+                .co_teacher(code("DEF"))
+                .split_room("Sports Hall".parse().unwrap()),
+            )
+            .unwrap();
+
+        let loaded = load_any_version(&save(&timetable)).unwrap();
+        let entry = loaded.get(id).unwrap();
+
+        assert_eq!(loaded, timetable);
+        assert_eq!(entry.co_teachers, [code("DEF")]);
+        assert_eq!(entry.split_rooms, ["Sports Hall".parse().unwrap()]);
+
+        // Team-taught and split lessons were introduced by version 4
+        let error = load_any_version(
+            "format = 3\n[week1]\nmon.p1 = { subject = 'Sc', class = '10A', room = 'H201', \
+             split_rooms = ['H202'] }",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            TomlErrorKind::UnknownField("split_rooms".to_string())
+        );
+    }
+
+    #[test]
+    fn save_rotating_entries() {
+        let code = |code: &str| Teacher::new(code.to_string()).unwrap();
+        let rotation = Rotation::new(NaiveDate::from_ymd_opt(2023, 9, 4).unwrap())
+            .stage(None, "FH T1".parse().unwrap())
+            .stage(Some(code("ABC")), "FH T2".parse().unwrap());
+
+        let mut timetable = Timetable::new();
+        let id = timetable
+            .insert_entry(
+                Entry::new(
+                    timeslot!(W1MP1),
+                    Activity::Lesson {
+                        subject: Subject::new("DT".to_string()).unwrap(),
+                        class: Class::new("9A".to_string()).unwrap(),
+                        teacher: None,
+                        location: "FH T1".parse().unwrap(),
+                    },
+                )
+                .rotating(rotation.clone()),
+            )
+            .unwrap();
+
+        let loaded = load_any_version(&save(&timetable)).unwrap();
+
+        assert_eq!(loaded, timetable);
+        assert_eq!(loaded.get(id).unwrap().rotation, Some(rotation));
+
+        // Rotating lessons were introduced by version 5
+        let error = load_any_version(
+            "format = 4\n[week1]\nmon.p1 = { subject = 'DT', class = '9A', room = 'FH T1', \
+             rotation = { start = '2023-09-04', stages = [] } }",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            TomlErrorKind::UnknownField("rotation".to_string())
+        );
+    }
+}
@@ -0,0 +1,716 @@
+//! Proposals of lesson swaps, and their approval.
+//!
+//! Teachers frequently agree to swap the rooms (or periods) of their lessons
+//! -- rather than making the change themselves, a teacher submits a
+//! [`Proposal`] naming the lessons affected and the [`RequestedChange`],
+//! which a member of staff permitted to make the change (e.g., the cover
+//! manager) then approves or rejects. The hypothetical result of a proposal
+//! can be [checked](Proposal::check) against the rules of a [`Validator`]
+//! before it is decided upon, and an approved proposal is committed to the
+//! timetable atomically (*see [`Timetable::transaction`]*).
+//!
+//! Each [`Decision`] is accompanied by a [`Notification`] for every party
+//! affected by the proposal (i.e., its proposer, and the teachers of the
+//! lessons affected):
+//!
+//! ```
+//! use timetableau::access::{Role, Session};
+//! use timetableau::proposals::{Proposal, RequestedChange};
+//! use timetableau::validate::{NoClashes, Validator};
+//! use timetableau::{timeslot, Activity, Class, Subject, Teacher, Timetable};
+//!
+//! let lesson = |class: &str, teacher: &str, location: &str| Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new(class.to_string()).unwrap(),
+//!     teacher: Some(Teacher::new(teacher.to_string()).unwrap()),
+//!     location: location.parse().unwrap(),
+//! };
+//!
+//! let mut timetable = Timetable::new();
+//! let first = timetable.insert(timeslot!(W1MP1), lesson("10A", "JSM", "H201"));
+//! let second = timetable.insert(timeslot!(W1MP1), lesson("10B", "ABC", "H202"));
+//!
+//! let proposal = Proposal::new(
+//!     Teacher::new("JSM".to_string()).unwrap(),
+//!     RequestedChange::SwapRooms { first, second },
+//! );
+//!
+//! let validator = Validator::new().rule(NoClashes);
+//! let mut session = Session::new(&mut timetable, Role::CoverManager);
+//! let decision = proposal.approve(&mut session, &validator).unwrap();
+//! drop(session);
+//!
+//! assert_eq!(timetable.get(first).unwrap().activity.location(), Some(&"H202".parse().unwrap()));
+//! assert_eq!(decision.notifications.len(), 2);
+//! ```
+
+use crate::access::{AccessDenied, Permission, Session};
+use crate::swaps::Impact;
+use crate::timetable::span;
+use crate::validate::{Report, Validator};
+use crate::{Activity, EntryId, Location, Teacher, TimeSlot, Timetable, TimetableError};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+
+/// A change to the lessons of a timetable requested by a [`Proposal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestedChange {
+    /// The rooms of two lessons are exchanged.
+    SwapRooms {
+        /// The entry of the first lesson.
+        first: EntryId,
+
+        /// The entry of the second lesson.
+        second: EntryId,
+    },
+
+    /// The timeslots of two lessons are exchanged.
+    SwapPeriods {
+        /// The entry of the first lesson.
+        first: EntryId,
+
+        /// The entry of the second lesson.
+        second: EntryId,
+    },
+
+    /// A lesson is moved to another room.
+    Relocate {
+        /// The entry of the lesson.
+        entry: EntryId,
+
+        /// The room to which the lesson is moved.
+        to: Location,
+    },
+
+    /// A lesson is moved to another timeslot.
+    Move {
+        /// The entry of the lesson.
+        entry: EntryId,
+
+        /// The timeslot to which the lesson is moved.
+        to: TimeSlot,
+    },
+}
+
+impl RequestedChange {
+    /// Retrieves the entries of the lessons affected by the change.
+    pub fn lessons(&self) -> Vec<EntryId> {
+        match *self {
+            Self::SwapRooms { first, second } | Self::SwapPeriods { first, second } => {
+                vec![first, second]
+            }
+            Self::Relocate { entry, .. } | Self::Move { entry, .. } => vec![entry],
+        }
+    }
+
+    /// Retrieves the permission required to make the change (i.e.,
+    /// [`Permission::ChangeRooms`] for a change of room, or
+    /// [`Permission::EditStructure`] for a change of timeslot).
+    pub fn permission(&self) -> Permission {
+        match self {
+            Self::SwapRooms { .. } | Self::Relocate { .. } => Permission::ChangeRooms,
+            Self::SwapPeriods { .. } | Self::Move { .. } => Permission::EditStructure,
+        }
+    }
+
+    // Make the change to the timetable provided (whose lessons are known to
+    // exist), leaving it unchanged should a lesson not fit its new timeslot
+    fn apply(&self, timetable: &mut Timetable) -> Result<(), TimetableError> {
+        match *self {
+            Self::SwapRooms { first, second } => {
+                let location = timetable
+                    .get(first)
+                    .and_then(|entry| entry.activity.location());
+
+                if let Some(location) = location.copied() {
+                    if let Some(previous) = timetable.relocate(second, location) {
+                        timetable.relocate(first, previous);
+                    }
+                }
+            }
+            Self::SwapPeriods { first, second } => {
+                if let (Some(a), Some(b)) = (timetable.get(first), timetable.get(second)) {
+                    // Neither lesson is moved unless both fit their new
+                    // timeslots (e.g., a double lesson swapped with the final
+                    // period of the day)
+                    span(b.slot, a.periods.get())?;
+                    span(a.slot, b.periods.get())?;
+
+                    if let Some(previous) = timetable.move_entry(second, a.slot)? {
+                        timetable.move_entry(first, previous)?;
+                    }
+                }
+            }
+            Self::Relocate { entry, to } => {
+                timetable.relocate(entry, to);
+            }
+            Self::Move { entry, to } => {
+                timetable.move_entry(entry, to)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A proposal, submitted by a teacher, to change the lessons of a timetable.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proposal {
+    proposer: Teacher,
+    change: RequestedChange,
+    reason: Option<String>,
+}
+
+impl Proposal {
+    /// Creates a new `Proposal` of the `change` provided, submitted by the
+    /// `proposer`.
+    pub fn new(proposer: Teacher, change: RequestedChange) -> Self {
+        Self {
+            proposer,
+            change,
+            reason: None,
+        }
+    }
+
+    /// Sets the reason given for the proposal (e.g., `Practical lesson`).
+    pub fn reason(mut self, reason: String) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
+    /// Retrieves the teacher who submitted the `Proposal`.
+    pub fn proposer(&self) -> &Teacher {
+        &self.proposer
+    }
+
+    /// Retrieves the change requested by the `Proposal`.
+    pub fn change(&self) -> RequestedChange {
+        self.change
+    }
+
+    /// Retrieves the entries of the lessons affected by the `Proposal`.
+    pub fn lessons(&self) -> Vec<EntryId> {
+        self.change.lessons()
+    }
+
+    /// Determines the effect of the `Proposal` upon the rules of the
+    /// `validator`, should it be made to the `timetable` provided.
+    ///
+    /// # Remarks
+    ///
+    /// The change is made to a copy of the timetable -- the timetable itself
+    /// is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// * [`ProposalError::UnknownLesson`] is returned should any lesson
+    ///   affected by the proposal not be a lesson of the timetable.
+    ///
+    /// * [`ProposalError::Timetable`] is returned should a lesson not fit its
+    ///   new timeslot -- *see [`Timetable::move_entry`]*.
+    pub fn check(
+        &self,
+        timetable: &Timetable,
+        validator: &Validator,
+    ) -> Result<Impact, ProposalError> {
+        self.ensure_lessons(timetable)?;
+
+        let mut hypothetical = timetable.clone();
+        self.change
+            .apply(&mut hypothetical)
+            .map_err(ProposalError::Timetable)?;
+
+        let before = validator.validate(timetable).findings;
+        let after = validator.validate(&hypothetical).findings;
+
+        Ok(Impact {
+            resolved: before
+                .iter()
+                .filter(|finding| !after.contains(finding))
+                .cloned()
+                .collect(),
+            introduced: after
+                .into_iter()
+                .filter(|finding| !before.contains(finding))
+                .collect(),
+        })
+    }
+
+    /// Approves the `Proposal`, making its change to the timetable of the
+    /// `session` as a single transaction validated by the `validator`.
+    ///
+    /// # Returns
+    ///
+    /// The [`Decision`], with a notification for each affected party.
+    ///
+    /// # Errors
+    ///
+    /// - [`ProposalError::AccessDenied`] is returned should the role of the
+    ///   `session` not grant the [permission](RequestedChange::permission)
+    ///   required to make the change.
+    /// - [`ProposalError::UnknownLesson`] is returned should any lesson
+    ///   affected by the proposal not be a lesson of the timetable.
+    /// - [`ProposalError::Timetable`] is returned (and the timetable is left
+    ///   unchanged) should a lesson not fit its new timeslot -- *see
+    ///   [`Timetable::move_entry`]*.
+    /// - [`ProposalError::Invalid`] is returned (and the timetable is left
+    ///   unchanged) should the change introduce any error -- *see
+    ///   [`Timetable::transaction`]*.
+    pub fn approve(
+        self,
+        session: &mut Session,
+        validator: &Validator,
+    ) -> Result<Decision, ProposalError> {
+        session.authorise(self.change.permission())?;
+        self.ensure_lessons(session.timetable())?;
+
+        // The affected lessons are described as they were prior to the change
+        let notifications = self.notify(session.timetable(), "approved");
+
+        session
+            .timetable_mut()
+            .transaction(validator, |tx| self.change.apply(tx))
+            .map_err(ProposalError::Invalid)?
+            .map_err(ProposalError::Timetable)?;
+
+        Ok(Decision {
+            proposal: self,
+            approved: true,
+            notifications,
+        })
+    }
+
+    /// Rejects the `Proposal` for the `reason` provided, leaving the
+    /// `timetable` unchanged.
+    ///
+    /// # Returns
+    ///
+    /// The [`Decision`], with a notification for each affected party.
+    pub fn reject(self, timetable: &Timetable, reason: &str) -> Decision {
+        let notifications = self.notify(timetable, &format!("rejected ({})", reason));
+
+        Decision {
+            proposal: self,
+            approved: false,
+            notifications,
+        }
+    }
+
+    // Ensure each lesson affected by the proposal exists in the timetable
+    // (and, for a change of room, is a lesson with a room to change)
+    fn ensure_lessons(&self, timetable: &Timetable) -> Result<(), ProposalError> {
+        for id in self.lessons() {
+            let entry = timetable.get(id).ok_or(ProposalError::UnknownLesson(id))?;
+
+            if self.change.permission() == Permission::ChangeRooms
+                && !matches!(entry.activity, Activity::Lesson { .. })
+            {
+                return Err(ProposalError::NotALesson(id));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Notify the proposer, and the teachers of the affected lessons, of the
+    // outcome of the proposal
+    fn notify(&self, timetable: &Timetable, outcome: &str) -> Vec<Notification> {
+        let describe = |id: EntryId| match timetable.get(id) {
+            Some(entry) => match &entry.activity {
+                Activity::Lesson {
+                    subject,
+                    class,
+                    location,
+                    ..
+                } => format!(
+                    "{} ({}) in {} during {}",
+                    class,
+                    subject,
+                    location,
+                    describe_slot(entry.slot)
+                ),
+                _ => format!("the activity during {}", describe_slot(entry.slot)),
+            },
+            None => format!("lesson {}", id.get()),
+        };
+
+        let change = match self.change {
+            RequestedChange::SwapRooms { first, second } => {
+                format!(
+                    "swap the rooms of {} and {}",
+                    describe(first),
+                    describe(second)
+                )
+            }
+            RequestedChange::SwapPeriods { first, second } => {
+                format!(
+                    "swap the periods of {} and {}",
+                    describe(first),
+                    describe(second)
+                )
+            }
+            RequestedChange::Relocate { entry, to } => {
+                format!("move {} to {}", describe(entry), to)
+            }
+            RequestedChange::Move { entry, to } => {
+                format!("move {} to {}", describe(entry), describe_slot(to))
+            }
+        };
+
+        let mut message = format!("The proposal by {} to {}", self.proposer, change);
+
+        if let Some(reason) = &self.reason {
+            let _ = write!(message, " ({})", reason);
+        }
+
+        let _ = write!(message, " has been {}", outcome);
+
+        let mut recipients = vec![self.proposer.clone()];
+
+        // Every teacher of each lesson is notified (including co-teachers)
+        for entry in self
+            .lessons()
+            .into_iter()
+            .filter_map(|id| timetable.get(id))
+        {
+            for teacher in entry.teachers() {
+                if !recipients.contains(teacher) {
+                    recipients.push(teacher.clone());
+                }
+            }
+        }
+
+        recipients
+            .into_iter()
+            .map(|recipient| Notification {
+                recipient,
+                message: message.clone(),
+            })
+            .collect()
+    }
+}
+
+// Describe a timeslot (e.g., `period 1 of Mon (week 1)`)
+fn describe_slot(slot: TimeSlot) -> String {
+    format!(
+        "period {} of {} (week {})",
+        slot.period as u8 + 1,
+        slot.day,
+        slot.week as u8 + 1
+    )
+}
+
+/// The outcome of a [`Proposal`] which has been approved or rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decision {
+    /// The proposal which was decided upon.
+    pub proposal: Proposal,
+
+    /// Whether the proposal was approved (and its change made).
+    pub approved: bool,
+
+    /// The notifications of the parties affected by the proposal.
+    pub notifications: Vec<Notification>,
+}
+
+/// A notification of a [`Decision`], sent to an affected party.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// The member of staff to be notified.
+    pub recipient: Teacher,
+
+    /// The message of the notification (e.g., `The proposal by JSM to move
+    /// ... has been approved`).
+    pub message: String,
+}
+
+/// An error returned when a [`Proposal`] cannot be approved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProposalError {
+    /// The role of the session approving the proposal does not permit the
+    /// change.
+    AccessDenied(AccessDenied),
+
+    /// A lesson affected by the proposal is not a lesson of the timetable.
+    UnknownLesson(EntryId),
+
+    /// A change of room affects an entry which is not a lesson (and so has
+    /// no room to change).
+    NotALesson(EntryId),
+
+    /// A lesson affected by the proposal could not be moved to its new
+    /// timeslot (e.g., a double lesson which would span past the end of the
+    /// day).
+    Timetable(TimetableError),
+
+    /// The change would introduce errors into the timetable, which are
+    /// reported.
+    Invalid(Report),
+}
+
+impl From<AccessDenied> for ProposalError {
+    fn from(denied: AccessDenied) -> Self {
+        Self::AccessDenied(denied)
+    }
+}
+
+impl Display for ProposalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AccessDenied(denied) => denied.fmt(f),
+            Self::UnknownLesson(id) => write!(f, "the lesson {} does not exist", id.get()),
+            Self::NotALesson(id) => write!(f, "the entry {} is not a lesson", id.get()),
+            Self::Timetable(error) => error.fmt(f),
+            Self::Invalid(report) => write!(
+                f,
+                "the proposal would introduce {} error(s)",
+                report.findings.len()
+            ),
+        }
+    }
+}
+
+impl Error for ProposalError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::Role;
+    use crate::timeslot;
+    use crate::validate::NoClashes;
+    use crate::{Class, Entry, Subject};
+
+    #[test]
+    fn proposal_decided() {
+        let teacher = |code: &str| Teacher::new(code.to_string()).unwrap();
+        let lesson = |class: &str, code: &str, location: &str| Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Some(teacher(code)),
+            location: location.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        let first = timetable.insert(timeslot!(W1MP1), lesson("10A", "JSM", "H201"));
+        let second = timetable.insert(timeslot!(W1MP2), lesson("10B", "ABC", "H202"));
+        let third = timetable.insert(timeslot!(W1MP2), lesson("10C", "XYZ", "H203"));
+
+        let validator = Validator::new().rule(NoClashes);
+
+        // Moving to a free timeslot introduces nothing, whereas moving into
+        // an occupied room introduces a clash
+        let moved = Proposal::new(
+            teacher("JSM"),
+            RequestedChange::Move {
+                entry: first,
+                to: timeslot!(W1MP2),
+            },
+        );
+        assert_eq!(
+            moved
+                .check(&timetable, &validator)
+                .unwrap()
+                .introduced
+                .len(),
+            0
+        );
+        let clashing = Proposal::new(
+            teacher("JSM"),
+            RequestedChange::Relocate {
+                entry: second,
+                to: "H203".parse().unwrap(),
+            },
+        );
+        assert_eq!(
+            clashing
+                .check(&timetable, &validator)
+                .unwrap()
+                .introduced
+                .len(),
+            1
+        );
+
+        // A teacher may not approve a change of room
+        let mut session = Session::new(&mut timetable, Role::Teacher);
+        assert_eq!(
+            clashing.clone().approve(&mut session, &validator),
+            Err(ProposalError::AccessDenied(AccessDenied {
+                role: Role::Teacher,
+                permission: Permission::ChangeRooms
+            }))
+        );
+        drop(session);
+
+        // An invalid proposal is rolled back
+        let mut session = Session::new(&mut timetable, Role::CoverManager);
+        assert!(matches!(
+            clashing.approve(&mut session, &validator),
+            Err(ProposalError::Invalid(report)) if report.findings.len() == 1
+        ));
+        assert_eq!(session.timetable().history().len(), 3);
+
+        // The periods of the swapped lessons are exchanged
+        let swap = Proposal::new(
+            teacher("JSM"),
+            RequestedChange::SwapPeriods { first, second },
+        );
+        assert!(matches!(
+            swap.clone().approve(&mut session, &validator),
+            Err(ProposalError::AccessDenied(_))
+        ));
+        drop(session);
+
+        let mut session = Session::new(&mut timetable, Role::Admin);
+        let decision = swap.approve(&mut session, &validator).unwrap();
+        drop(session);
+
+        assert!(decision.approved);
+        assert_eq!(timetable.get(first).unwrap().slot, timeslot!(W1MP2));
+        assert_eq!(timetable.get(second).unwrap().slot, timeslot!(W1MP1));
+        assert_eq!(
+            decision
+                .notifications
+                .iter()
+                .map(|notification| notification.recipient.code().as_str())
+                .collect::<Vec<_>>(),
+            ["JSM", "ABC"]
+        );
+        assert_eq!(
+            decision.notifications[0].message,
+            "The proposal by JSM to swap the periods of 10A (Ma) in H201 during period 1 of Mon \
+             (week 1) and 10B (Ma) in H202 during period 2 of Mon (week 1) has been approved"
+        );
+
+        // A rejection leaves the timetable unchanged
+        let rejected = Proposal::new(
+            teacher("XYZ"),
+            RequestedChange::Relocate {
+                entry: third,
+                to: "H210".parse().unwrap(),
+            },
+        )
+        .reason("Practical lesson".to_string())
+        .reject(&timetable, "room booked for exams");
+
+        assert!(!rejected.approved);
+        assert_eq!(rejected.notifications.len(), 1);
+        assert!(rejected.notifications[0]
+            .message
+            .ends_with("to H210 (Practical lesson) has been rejected (room booked for exams)"));
+        assert_eq!(
+            timetable.get(third).unwrap().activity.location(),
+            Some(&"H203".parse().unwrap())
+        );
+
+        let unknown = Proposal::new(
+            teacher("JSM"),
+            RequestedChange::SwapRooms {
+                first,
+                second: EntryId(99),
+            },
+        );
+        assert!(matches!(
+            unknown.check(&timetable, &validator),
+            Err(ProposalError::UnknownLesson(_))
+        ));
+
+        // A double lesson cannot be swapped into the final period of the day
+        let double = timetable
+            .insert_spanning(timeslot!(W1TP3), 2, lesson("10D", "JSM", "H204"))
+            .unwrap();
+        let last = timetable.insert(timeslot!(W1TP5), lesson("10E", "ABC", "H205"));
+        let swap = Proposal::new(
+            teacher("JSM"),
+            RequestedChange::SwapPeriods {
+                first: double,
+                second: last,
+            },
+        );
+        let history = timetable.history().len();
+
+        let mut session = Session::new(&mut timetable, Role::Admin);
+        assert!(matches!(
+            swap.approve(&mut session, &validator),
+            Err(ProposalError::Timetable(TimetableError::InvalidSpan { .. }))
+        ));
+        drop(session);
+
+        assert_eq!(timetable.history().len(), history);
+        assert_eq!(timetable.get(double).unwrap().slot, timeslot!(W1TP3));
+        assert_eq!(timetable.get(last).unwrap().slot, timeslot!(W1TP5));
+
+        // A change of room cannot affect an entry which is not a lesson
+        let study = timetable.insert(timeslot!(W1WP1), Activity::SchoolStudy);
+        let relocate = Proposal::new(
+            teacher("JSM"),
+            RequestedChange::Relocate {
+                entry: study,
+                to: "H210".parse().unwrap(),
+            },
+        );
+        let swap = Proposal::new(
+            teacher("JSM"),
+            RequestedChange::SwapRooms {
+                first,
+                second: study,
+            },
+        );
+
+        assert_eq!(
+            relocate.check(&timetable, &validator),
+            Err(ProposalError::NotALesson(study))
+        );
+
+        let mut session = Session::new(&mut timetable, Role::Admin);
+        assert_eq!(
+            swap.approve(&mut session, &validator)
+                .unwrap_err()
+                .to_string(),
+            format!("the entry {} is not a lesson", study.get())
+        );
+        drop(session);
+
+        assert_eq!(timetable.history().len(), history + 1);
+    }
+
+    #[test]
+    fn proposal_notifies_co_teachers() {
+        let teacher = |code: &str| Teacher::new(code.to_string()).unwrap();
+        let lesson = Activity::Lesson {
+            subject: Subject::new("PE".to_string()).unwrap(),
+            class: Class::new("10A".to_string()).unwrap(),
+            teacher: Some(teacher("JSM")),
+            location: "Hall".parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        let id = timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP1), lesson)
+                    .co_teacher(teacher("ABC"))
+                    .co_teacher(teacher("JSM")),
+            )
+            .unwrap();
+
+        // Each teacher of the lesson is notified once (as is the proposer)
+        let decision = Proposal::new(
+            teacher("ABC"),
+            RequestedChange::Relocate {
+                entry: id,
+                to: "Sports Hall".parse().unwrap(),
+            },
+        )
+        .reject(&timetable, "room booked");
+
+        assert_eq!(
+            decision
+                .notifications
+                .iter()
+                .map(|notification| notification.recipient.code().as_str())
+                .collect::<Vec<_>>(),
+            ["ABC", "JSM"]
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use crate::{FearnhillRoom, HighfieldRoom, Location, Room, RoomKind};
+use chrono::Duration;
+
+// The distance between rooms at different schools (i.e., rooms which require
+// travelling between the sites)
+const CROSS_SITE: u32 = 1000;
+
+// The distance between different blocks (or sections) of the same school
+const CROSS_BLOCK: u32 = 50;
+
+// The distance between adjacent floors of the same block
+const FLOOR: u32 = 10;
+
+// The distance to (or from) a room whose position is not known
+const UNKNOWN: u32 = 100;
+
+/// The minimum times required to move between rooms (i.e., the passing
+/// times a school tolerates), which are consulted by every calculation of
+/// whether there is enough time to travel between lessons (*see
+/// [`Location::travel_time`]*).
+///
+/// ```
+/// use chrono::Duration;
+/// use timetableau::{Location, TransitPolicy};
+///
+/// let policy = TransitPolicy::new()
+///     .cross_site(Duration::minutes(20))
+///     .allowance(Duration::minutes(3));
+///
+/// let (from, to): (Location, Location) = ("H201".parse().unwrap(), "FH M1".parse().unwrap());
+///
+/// assert_eq!(from.travel_time(&to), Duration::minutes(15));
+/// assert_eq!(policy.travel_time(&from, &to), Duration::minutes(23));
+/// ```
+///
+/// # Remarks
+///
+/// By default, 2 minutes are required within a floor (or block), 5 minutes
+/// between blocks (or to a room whose position is not known), and 15
+/// minutes between the sites, without any allowance for accessibility
+/// needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitPolicy {
+    same_floor: Duration,
+    same_block: Duration,
+    cross_block: Duration,
+    cross_site: Duration,
+    allowance: Duration,
+}
+
+impl Default for TransitPolicy {
+    fn default() -> Self {
+        Self {
+            same_floor: Duration::minutes(2),
+            same_block: Duration::minutes(2),
+            cross_block: Duration::minutes(5),
+            cross_site: Duration::minutes(15),
+            allowance: Duration::zero(),
+        }
+    }
+}
+
+impl TransitPolicy {
+    /// Creates a new `TransitPolicy` with the default minimum times.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum time required to move between rooms on the same
+    /// floor of a block (or within a section of the Fearnhill school).
+    pub fn same_floor(mut self, minimum: Duration) -> Self {
+        self.same_floor = minimum;
+        self
+    }
+
+    /// Sets the minimum time required to move between floors of the same
+    /// block.
+    pub fn same_block(mut self, minimum: Duration) -> Self {
+        self.same_block = minimum;
+        self
+    }
+
+    /// Sets the minimum time required to move between blocks (or sections)
+    /// of the same school, or to (or from) a room whose position is not
+    /// known.
+    pub fn cross_block(mut self, minimum: Duration) -> Self {
+        self.cross_block = minimum;
+        self
+    }
+
+    /// Sets the minimum time required to travel between the sites (i.e.,
+    /// between the schools, or to an off-site location).
+    pub fn cross_site(mut self, minimum: Duration) -> Self {
+        self.cross_site = minimum;
+        self
+    }
+
+    /// Sets the extra time added to every journey (e.g., for a student who
+    /// must use the lifts) -- a policy with an allowance should be used for
+    /// the people with accessibility needs.
+    pub fn allowance(mut self, allowance: Duration) -> Self {
+        self.allowance = allowance;
+        self
+    }
+
+    /// Retrieves the minimum time required to travel between the sites
+    /// (including the allowance).
+    pub fn cross_site_time(&self) -> Duration {
+        self.cross_site + self.allowance
+    }
+
+    /// Determines the minimum time required to travel from the `from`
+    /// location to the `to` location (*see [`Location::distance`]*).
+    ///
+    /// No time is required to remain in the same room, or to travel to (or
+    /// from) a [remote](crate::Remote) lesson.
+    pub fn travel_time(&self, from: &Location, to: &Location) -> Duration {
+        if from == to
+            || [from, to]
+                .iter()
+                .any(|location| location.kind() == RoomKind::Remote)
+        {
+            return Duration::zero();
+        }
+
+        let distance = from.distance(to);
+        let minimum = if distance >= CROSS_SITE {
+            self.cross_site
+        } else if distance >= CROSS_BLOCK {
+            self.cross_block
+        } else if from.parts().floor == to.parts().floor {
+            self.same_floor
+        } else {
+            self.same_block
+        };
+
+        minimum + self.allowance
+    }
+}
+
+impl Location {
+    /// Estimates the distance between the `Location` and `other`.
+    ///
+    /// The distance is unitless, and is only intended to order rooms by
+    /// their proximity: rooms at the other school are always further than
+    /// rooms at the same school, rooms in another block (or section) are
+    /// further than rooms in the same block, and each floor climbed adds to
+    /// the distance (the Highfield blocks are connected at ground level).
+    /// Within a block or section, rooms with closer discriminators are
+    /// assumed to be closer.
+    pub fn distance(&self, other: &Location) -> u32 {
+        if self == other {
+            return 0;
+        }
+
+        match (self, other) {
+            // An off-site location is a site of its own, and a remote lesson
+            // is not at any site
+            _ if [self, other].iter().any(|location| {
+                matches!(location.kind(), RoomKind::OffSite | RoomKind::Remote)
+            }) =>
+            {
+                CROSS_SITE
+            }
+            (Self::Highfield(a), Self::Highfield(b)) => highfield_distance(a, b),
+            (Self::Fearnhill(a), Self::Fearnhill(b)) => fearnhill_distance(a, b),
+            _ => CROSS_SITE,
+        }
+    }
+
+    /// Estimates the time taken to travel from the `Location` to `other`
+    /// (*see [`Self::distance`]*), according to the default
+    /// [`TransitPolicy`].
+    ///
+    /// # Remarks
+    ///
+    /// The estimate is intentionally pessimistic: 2 minutes within a block
+    /// (or section), 5 minutes between blocks, and 15 minutes between the
+    /// sites. No time is required to travel to (or from) a
+    /// [remote](crate::Remote) lesson.
+    pub fn travel_time(&self, other: &Location) -> Duration {
+        TransitPolicy::default().travel_time(self, other)
+    }
+
+    /// Retrieves the candidate nearest to the `Location` (*see
+    /// [`Self::distance`]*) -- should multiple candidates be equally near,
+    /// the first is retrieved.
+    ///
+    /// This is intended for finding a free room to which a class can be
+    /// moved:
+    ///
+    /// ```
+    /// use timetableau::Location;
+    ///
+    /// let broken: Location = "H203".parse().unwrap();
+    /// let free = ["FH S1", "U101", "H204", "HG03"].map(|code| code.parse().unwrap());
+    ///
+    /// assert_eq!(broken.nearest(free), Some("H204".parse().unwrap()));
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if there are no candidates.
+    pub fn nearest<I>(&self, candidates: I) -> Option<Location>
+    where
+        I: IntoIterator<Item = Location>,
+    {
+        candidates
+            .into_iter()
+            .enumerate()
+            .min_by_key(|(index, candidate)| (self.distance(candidate), *index))
+            .map(|(_, candidate)| candidate)
+    }
+}
+
+// The position of a Highfield room: its block (`None` for the named rooms, the
+// outdoor spaces, and the temporary classrooms, which are not part of a
+// block), floor, and discriminator
+fn highfield_position(room: &HighfieldRoom) -> Option<(Option<u8>, u32, u32)> {
+    match room {
+        HighfieldRoom::Classroom {
+            block,
+            floor,
+            discriminator,
+        } => Some((
+            Some(*block as u8),
+            floor.number() as u32,
+            discriminator.get() as u32,
+        )),
+        HighfieldRoom::Hall
+        | HighfieldRoom::SportsHall
+        | HighfieldRoom::Field
+        | HighfieldRoom::AstroPitch
+        | HighfieldRoom::TennisCourts
+        | HighfieldRoom::Playground
+        | HighfieldRoom::Temporary(_) => Some((None, 0, 0)),
+        HighfieldRoom::OffSite(_) | HighfieldRoom::Remote(_) | HighfieldRoom::ToBeConfirmed => None,
+        #[cfg(feature = "other-rooms")]
+        HighfieldRoom::Other(_) => None,
+    }
+}
+
+fn highfield_distance(a: &HighfieldRoom, b: &HighfieldRoom) -> u32 {
+    match (highfield_position(a), highfield_position(b)) {
+        (Some((Some(block_a), floor_a, room_a)), Some((Some(block_b), floor_b, room_b)))
+            if block_a == block_b =>
+        {
+            FLOOR * floor_a.abs_diff(floor_b) + room_a.abs_diff(room_b)
+        }
+        // Travel down to the ground floor, across, and up again
+        (Some((_, floor_a, _)), Some((_, floor_b, _))) => CROSS_BLOCK + FLOOR * (floor_a + floor_b),
+        _ => UNKNOWN,
+    }
+}
+
+fn fearnhill_distance(a: &FearnhillRoom, b: &FearnhillRoom) -> u32 {
+    match (a, b) {
+        (
+            FearnhillRoom::Classroom {
+                section: section_a,
+                discriminator: room_a,
+            },
+            FearnhillRoom::Classroom {
+                section: section_b,
+                discriminator: room_b,
+            },
+        ) if section_a == section_b => room_a.get().abs_diff(room_b.get()) as u32,
+        (FearnhillRoom::ToBeConfirmed, _) | (_, FearnhillRoom::ToBeConfirmed) => UNKNOWN,
+        #[cfg(feature = "other-rooms")]
+        (FearnhillRoom::Other(_), _) | (_, FearnhillRoom::Other(_)) => UNKNOWN,
+        _ => CROSS_BLOCK,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(code: &str) -> Location {
+        code.parse().unwrap()
+    }
+
+    #[test]
+    fn distance_ordering() {
+        let origin = location("P105");
+
+        let mut rooms = ["FH M1", "H105", "PG05", "P107", "P105", "Hall"].map(location);
+        rooms.sort_by_key(|room| origin.distance(room));
+
+        assert_eq!(
+            rooms.map(|room| room.to_string()),
+            ["P105", "P107", "PG05", "Hall", "H105", "FH M1"]
+        );
+        assert_eq!(origin.nearest([]), None);
+        assert_eq!(
+            location("FH S2").nearest(["FH Gym", "FH S9", "FH M2"].map(location)),
+            Some(location("FH S9"))
+        );
+    }
+
+    #[test]
+    fn transit_policy_times() {
+        let policy = TransitPolicy::new()
+            .same_floor(Duration::minutes(1))
+            .same_block(Duration::minutes(3))
+            .cross_block(Duration::minutes(6))
+            .cross_site(Duration::minutes(20));
+        let time = |from: &str, to: &str| policy.travel_time(&location(from), &location(to));
+
+        assert_eq!(time("H201", "H201"), Duration::zero());
+        assert_eq!(time("H201", "H204"), Duration::minutes(1));
+        assert_eq!(time("H201", "H105"), Duration::minutes(3));
+        assert_eq!(time("H201", "P105"), Duration::minutes(6));
+        assert_eq!(time("H201", "FH M1"), Duration::minutes(20));
+        assert_eq!(time("H201", "FH Remote"), Duration::zero());
+
+        // The allowance is added to every journey (but not to remaining in
+        // the same room, or to a remote lesson)
+        let policy = policy.allowance(Duration::minutes(2));
+        let time = |from: &str, to: &str| policy.travel_time(&location(from), &location(to));
+
+        assert_eq!(time("H201", "H201"), Duration::zero());
+        assert_eq!(time("H201", "H204"), Duration::minutes(3));
+        assert_eq!(time("H201", "H105"), Duration::minutes(5));
+        assert_eq!(time("H201", "P105"), Duration::minutes(8));
+        assert_eq!(time("FH M1", "H201"), Duration::minutes(22));
+        assert_eq!(time("FH Remote", "FH M1"), Duration::zero());
+        assert_eq!(policy.cross_site_time(), Duration::minutes(22));
+    }
+}
@@ -0,0 +1,849 @@
+//! Queries of the lessons of a timetable.
+//!
+//! The lessons of a [`Timetable`] are queried by chaining the filters of
+//! [`Lessons`] (*see [`Timetable::lessons`]*). Questions phrased in natural
+//! language (e.g., from a helpdesk chatbot) can be [parsed](parse) into a
+//! [`Question`], which is executed against a timetable:
+//!
+//! ```
+//! use timetableau::query::{self, Intent};
+//! use timetableau::{timeslot, Activity, Class, Subject, Timetable};
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(
+//!     timeslot!(W2TP3),
+//!     Activity::Lesson {
+//!         subject: Subject::new("Maths".to_string()).unwrap(),
+//!         class: Class::new("10A".to_string()).unwrap(),
+//!         teacher: None,
+//!         location: "H201".parse().unwrap(),
+//!     },
+//! );
+//!
+//! let question = query::parse("Where is 10A maths on Tuesday week B?").unwrap();
+//! let lessons = question.execute(&timetable);
+//!
+//! assert_eq!(question.intent, Intent::Where);
+//! assert_eq!(lessons[0].1.activity.location(), Some(&"H201".parse().unwrap()));
+//! ```
+//!
+//! Mistyped subjects (e.g., `phyics`) and teachers referred to by name (e.g.,
+//! `Mr Smith`) are resolved should the question be parsed with a
+//! [`Resolver`] (*see [`parse_with`]*).
+
+use crate::fuzzy::{self, Resolver};
+use crate::timeslot::{parse_day, parse_period, parse_week};
+use crate::{
+    Activity, Change, Entry, EntryId, Location, Period, Room, RoomKind, School, TimeSlot,
+    Timetable, Week,
+};
+use chrono::Weekday;
+use std::collections::{btree_map, btree_set, BTreeSet};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+/// An iterator over the [lessons](Activity::Lesson) of a [`Timetable`] which
+/// match a set of filters.
+///
+/// Filters are added by chaining the methods of the query, and every filter
+/// must match for a lesson to be yielded -- for example, the following query
+/// yields JSM's lessons at Fearnhill on Tuesdays of week one:
+///
+/// ```
+/// # use timetableau::{School, Timetable, Week};
+/// # use chrono::Weekday;
+/// # let timetable = Timetable::new();
+/// let lessons = timetable
+///     .lessons()
+///     .on(Weekday::Tue)
+///     .in_week(Week::WeekOne)
+///     .at_school(School::Fearnhill)
+///     .taught_by("JSM");
+///
+/// for (id, entry) in lessons {
+///     println!("{:?}: {}", id, entry.activity);
+/// }
+/// ```
+///
+/// *See [`Timetable::lessons`] for more information*.
+///
+/// # Remarks
+///
+/// Should the query filter by a room, a teacher, or a class before it is
+/// iterated, only the lessons in the timetable's index of that room, teacher,
+/// or class are visited.
+#[derive(Debug, Clone)]
+pub struct Lessons<'a, L = Location> {
+    timetable: &'a Timetable<L>,
+    candidates: Candidates<'a, L>,
+    started: bool,
+    week: Option<Week>,
+    day: Option<Weekday>,
+    period: Option<Period>,
+    site: Option<&'a str>,
+    kind: Option<RoomKind>,
+    location: Option<L>,
+    subject: Option<&'a str>,
+    class: Option<&'a str>,
+    teacher: Option<&'a str>,
+}
+
+impl<'a, L: Room> Lessons<'a, L> {
+    /// Only yields lessons taking place on the `day` provided.
+    pub fn on(mut self, day: Weekday) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Only yields lessons taking place during the `week` provided.
+    pub fn in_week(mut self, week: Week) -> Self {
+        self.week = Some(week);
+        self
+    }
+
+    /// Only yields lessons taking place during the `period` provided.
+    pub fn in_period(mut self, period: Period) -> Self {
+        self.period = Some(period);
+        self
+    }
+
+    /// Only yields lessons taking place during the `slot` provided (i.e., in
+    /// its week, on its day, and during its period).
+    pub fn during(self, slot: TimeSlot) -> Self {
+        self.in_week(slot.week).on(slot.day).in_period(slot.period)
+    }
+
+    /// Only yields lessons taking place at the site provided (*see
+    /// [`Room::site`]*).
+    pub fn at_site(mut self, site: &'a str) -> Self {
+        self.site = Some(site);
+        self
+    }
+
+    /// Only yields lessons taking place in a room of the `kind` provided
+    /// (e.g., [`RoomKind::ToBeConfirmed`] for the lessons which still need
+    /// a room).
+    pub fn of_kind(mut self, kind: RoomKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Only yields lessons taking place in the `location` provided.
+    pub fn in_room(mut self, location: L) -> Self {
+        self.narrow(self.timetable.index.room(&location));
+        self.location = Some(location);
+        self
+    }
+
+    /// Only yields lessons of the subject with the `name` provided.
+    pub fn of_subject(mut self, name: &'a str) -> Self {
+        self.subject = Some(name);
+        self
+    }
+
+    /// Only yields lessons of the class with the `reference` provided.
+    pub fn for_class(mut self, reference: &'a str) -> Self {
+        self.narrow(self.timetable.index.class(reference));
+        self.class = Some(reference);
+        self
+    }
+
+    /// Only yields lessons taught by the teacher with the staff `code`
+    /// provided.
+    ///
+    /// Lessons whose teacher is unknown are never yielded by this filter.
+    pub fn taught_by(mut self, code: &'a str) -> Self {
+        self.narrow(self.timetable.index.teacher(code));
+        self.teacher = Some(code);
+        self
+    }
+
+    // Only visit the entries with the identifiers provided (should there be
+    // fewer than the entries which would otherwise be visited)
+    fn narrow(&mut self, ids: Option<&'a BTreeSet<EntryId>>) {
+        // Narrowing a partially consumed query would yield its lessons again
+        if self.started {
+            return;
+        }
+
+        self.candidates = match (ids, &self.candidates) {
+            (_, Candidates::Empty) | (None, _) => Candidates::Empty,
+            (Some(ids), Candidates::Indexed(current)) if current.len() <= ids.len() => {
+                return;
+            }
+            (Some(ids), _) => Candidates::Indexed(ids.iter()),
+        };
+    }
+
+    // Whether the entry provided matches every filter of the query
+    fn matches(&self, entry: &Entry<L>) -> bool {
+        let (subject, class) = match &entry.activity {
+            Activity::Lesson { subject, class, .. } => (subject, class),
+            _ => return false,
+        };
+
+        // A filter which has not been set matches every lesson
+        fn check<T: PartialEq>(filter: Option<T>, value: T) -> bool {
+            filter.is_none_or(|filter| filter == value)
+        }
+
+        // A lesson spanning multiple periods takes place during each of them,
+        // and a team-taught (or split) lesson is taught by each of its
+        // teachers (in each of its rooms)
+        check(self.week, entry.slot.week)
+            && check(self.day, entry.slot.day)
+            && self
+                .period
+                .is_none_or(|period| entry.slots().any(|slot| slot.period == period))
+            && entry.locations().any(|location| {
+                check(self.site, location.site())
+                    && check(self.kind, location.kind())
+                    && check(self.location.as_ref(), location)
+            })
+            && check(self.subject, subject.name().as_str())
+            && check(self.class, class.reference().as_str())
+            && self
+                .teacher
+                .is_none_or(|code| entry.teachers().any(|teacher| teacher.code() == code))
+    }
+}
+
+impl<'a> Lessons<'a> {
+    /// Only yields lessons taking place at the `school` provided.
+    pub fn at_school(self, school: School) -> Self {
+        self.at_site(school.name())
+    }
+}
+
+impl<'a, L: Room> Iterator for Lessons<'a, L> {
+    type Item = (EntryId, &'a Entry<L>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let timetable = self.timetable;
+        self.started = true;
+
+        loop {
+            let (id, entry) = match &mut self.candidates {
+                Candidates::All(entries) => entries.next().map(|(id, entry)| (*id, entry))?,
+                Candidates::Indexed(ids) => {
+                    let id = *ids.next()?;
+                    (id, timetable.entries.get(&id)?)
+                }
+                Candidates::Empty => return None,
+            };
+
+            if self.matches(entry) {
+                return Some((id, entry));
+            }
+        }
+    }
+}
+
+// The entries which a query visits
+#[derive(Debug, Clone)]
+enum Candidates<'a, L> {
+    // Every entry of the timetable
+    All(btree_map::Iter<'a, EntryId, Entry<L>>),
+
+    // The entries in an index of the timetable
+    Indexed(btree_set::Iter<'a, EntryId>),
+
+    // No entries (i.e., an index has no entries matching the query)
+    Empty,
+}
+
+impl<L: Room> Timetable<L> {
+    /// Queries the [lessons](Activity::Lesson) of the `Timetable` (in the
+    /// order in which they were inserted).
+    ///
+    /// Without any filters, every lesson is yielded -- *see [`Lessons`] for
+    /// more information*.
+    pub fn lessons(&self) -> Lessons<'_, L> {
+        Lessons {
+            timetable: self,
+            candidates: Candidates::All(self.entries.iter()),
+            started: false,
+            week: None,
+            day: None,
+            period: None,
+            site: None,
+            kind: None,
+            location: None,
+            subject: None,
+            class: None,
+            teacher: None,
+        }
+    }
+
+    /// Creates a view of the `Timetable` containing only the lessons taught
+    /// by the teacher with the staff `code` provided (*see
+    /// [`Self::view_for_room`] for more information*).
+    pub fn view_for_teacher(&self, code: &str) -> Timetable<L> {
+        self.view(self.lessons().taught_by(code))
+    }
+
+    /// Creates a view of the `Timetable` containing only the lessons taking
+    /// place in the `location` provided (e.g., such that the weekly sheet
+    /// of a room can be printed).
+    ///
+    /// A view is a `Timetable` in its own right, so it can be exported and
+    /// rendered like any other:
+    ///
+    /// ```
+    /// use timetableau::{timeslot, Activity, Class, Location, Subject, Timetable};
+    ///
+    /// let lesson = |subject: &str, room: &str| Activity::Lesson {
+    ///     subject: Subject::new(subject.to_string()).unwrap(),
+    ///     class: Class::new("10A".to_string()).unwrap(),
+    ///     teacher: None,
+    ///     location: room.parse().unwrap(),
+    /// };
+    ///
+    /// let mut timetable = Timetable::new();
+    /// timetable.insert(timeslot!(W1MP1), lesson("Ma", "H201"));
+    /// timetable.insert(timeslot!(W1MP2), lesson("En", "U101"));
+    ///
+    /// let sheet = timetable.view_for_room("H201".parse().unwrap());
+    ///
+    /// assert_eq!(sheet.len(), 1);
+    /// assert!(sheet.to_toml_string().contains("H201"));
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// The entries of a view retain their [`EntryId`]s, and the view retains
+    /// the [room overrides](Self::override_room) of the timeslots of its
+    /// entries -- its history, however, is empty (and changes made to the
+    /// view are not reflected in the `Timetable`).
+    ///
+    /// Lessons are included according to the rooms in which they are
+    /// scheduled (i.e., before any room overrides are applied).
+    pub fn view_for_room(&self, location: L) -> Timetable<L> {
+        self.view(self.lessons().in_room(location))
+    }
+
+    /// Creates a view of the `Timetable` containing only the lessons of the
+    /// class with the `reference` provided (*see [`Self::view_for_room`] for
+    /// more information*).
+    pub fn view_for_class(&self, reference: &str) -> Timetable<L> {
+        self.view(self.lessons().for_class(reference))
+    }
+
+    // Create a view containing only the lessons provided
+    fn view(&self, lessons: Lessons<'_, L>) -> Timetable<L> {
+        let mut view = Timetable::default();
+
+        for (id, entry) in lessons {
+            view.apply(&Change::Inserted {
+                id,
+                entry: entry.clone(),
+            });
+        }
+
+        view.overrides = Arc::new(
+            self.overrides
+                .iter()
+                .filter(|room_override| view.at(room_override.slot).next().is_some())
+                .cloned()
+                .collect(),
+        );
+        view.next_id = self.next_id;
+        view.author.clone_from(&self.author);
+        view.role = self.role;
+        view
+    }
+}
+
+/// What a [`Question`] asks about its lessons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Intent {
+    /// Where the lessons take place (e.g., `where is 10A maths`).
+    Where,
+
+    /// What the lessons are (e.g., `what's in H201 period 4`) -- this is the
+    /// intent of a question which does not start with a question word.
+    What,
+
+    /// When the lessons take place (e.g., `when does JSM teach 11B`).
+    When,
+
+    /// Who teaches the lessons (e.g., `who teaches 10A physics`).
+    Who,
+}
+
+/// A question about the lessons of a timetable (*see [`parse`]*).
+///
+/// Every criterion which is specified must match for a lesson to be an
+/// answer to the question.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Question {
+    /// What the question asks about its lessons.
+    pub intent: Intent,
+
+    /// The week of the lessons.
+    pub week: Option<Week>,
+
+    /// The day of the lessons.
+    pub day: Option<Weekday>,
+
+    /// The period of the lessons.
+    pub period: Option<Period>,
+
+    /// The room of the lessons.
+    pub location: Option<Location>,
+
+    /// The class of the lessons (matched case-insensitively, and including
+    /// the sets of the class -- e.g., `10A` matches `10A/Ma1`).
+    pub class: Option<String>,
+
+    /// The subject of the lessons (matched case-insensitively, and by prefix
+    /// -- e.g., `maths` matches `Ma`).
+    pub subject: Option<String>,
+
+    /// The staff code of the teacher of the lessons (matched
+    /// case-insensitively).
+    pub teacher: Option<String>,
+}
+
+impl Question {
+    /// Retrieves the lessons of the `timetable` provided which answer the
+    /// question (in the order in which they were inserted).
+    pub fn execute<'a>(&self, timetable: &'a Timetable) -> Vec<(EntryId, &'a Entry)> {
+        let mut lessons = timetable.lessons();
+
+        if let Some(week) = self.week {
+            lessons = lessons.in_week(week);
+        }
+        if let Some(day) = self.day {
+            lessons = lessons.on(day);
+        }
+        if let Some(period) = self.period {
+            lessons = lessons.in_period(period);
+        }
+        if let Some(location) = self.location {
+            lessons = lessons.in_room(location);
+        }
+
+        lessons
+            .filter(|(_, entry)| {
+                let Activity::Lesson {
+                    subject,
+                    class,
+                    teacher,
+                    ..
+                } = &entry.activity
+                else {
+                    return false;
+                };
+
+                let class_matches = self.class.as_deref().is_none_or(|reference| {
+                    let class = class.reference().to_ascii_lowercase();
+                    let reference = reference.to_ascii_lowercase();
+
+                    class == reference || class.starts_with(&format!("{}/", reference))
+                });
+
+                let subject_matches = self.subject.as_deref().is_none_or(|name| {
+                    let subject = subject.name().to_ascii_lowercase();
+                    let name = name.to_ascii_lowercase();
+
+                    subject.starts_with(&name) || name.starts_with(&subject)
+                });
+
+                let teacher_matches = self.teacher.as_deref().is_none_or(|code| {
+                    teacher
+                        .as_ref()
+                        .is_some_and(|teacher| teacher.code().eq_ignore_ascii_case(code))
+                });
+
+                class_matches && subject_matches && teacher_matches
+            })
+            .collect()
+    }
+}
+
+/// An error which prevents a question from being parsed (*see [`parse`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseQueryError {
+    /// The question does not specify any criteria (e.g., `where is`).
+    Empty,
+
+    /// The question specifies two different values of the same criterion
+    /// (e.g., two days) -- the criterion and both values are provided.
+    Ambiguous {
+        /// The criterion (e.g., `day`).
+        criterion: &'static str,
+
+        /// The first value of the criterion.
+        first: String,
+
+        /// The second value of the criterion.
+        second: String,
+    },
+
+    /// A part of the question could not be understood (e.g., `period 9`).
+    Unrecognised(String),
+}
+
+impl Display for ParseQueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("the question does not specify any lessons"),
+            Self::Ambiguous {
+                criterion,
+                first,
+                second,
+            } => write!(
+                f,
+                "the question specifies two {}s (`{}` and `{}`)",
+                criterion, first, second
+            ),
+            Self::Unrecognised(part) => write!(f, "`{}` could not be understood", part),
+        }
+    }
+}
+
+impl Error for ParseQueryError {}
+
+// The words of a question which do not specify a criterion
+const FILLER: &[&str] = &[
+    "a", "an", "and", "are", "at", "by", "class", "do", "does", "during", "for", "has", "have",
+    "i", "in", "is", "lesson", "lessons", "me", "my", "of", "on", "or", "room", "s", "show",
+    "take", "takes", "taught", "teach", "teaches", "the", "there", "which",
+];
+
+/// Parses a question about the lessons of a timetable phrased in natural
+/// language (e.g., `where is 10A maths on Tuesday week B`, or `what's in H201
+/// period 4 Friday`).
+///
+/// The following criteria are understood (in any order):
+///
+/// * A week (e.g., `week B`, `week 2`, or `W2`).
+/// * A day (e.g., `Tuesday`, `Tue`, or `Tues`).
+/// * A period (e.g., `period 4`, or `P4`).
+/// * A room (e.g., `H201` or `FH S12`).
+/// * A class, which starts with the number of its year group (e.g., `10A`).
+/// * A teacher, whose staff code is written in capitals (e.g., `JSM`).
+/// * A subject -- any other word which is not part of the phrasing of the
+///   question (e.g., `maths`).
+///
+/// The intent of the question is determined by its first word (e.g.,
+/// `where`) -- *see [`Intent`]*.
+///
+/// # Errors
+///
+/// A [`ParseQueryError`] should the question not specify any criteria,
+/// specify a criterion twice (e.g., `maths physics`), or contain a week or
+/// period which does not exist (e.g., `period 9`).
+pub fn parse(input: &str) -> Result<Question, ParseQueryError> {
+    parse_with(input, &Resolver::new())
+}
+
+/// Parses a question phrased in natural language (*see [`parse`]*),
+/// resolving its subject and teacher using the `resolver` provided.
+///
+/// A subject is resolved to the closest subject known to the resolver (e.g.,
+/// `phyics` to `Physics`), or is left unchanged should there be none. A
+/// teacher may also be referred to by their title and name (e.g., `Mr
+/// Smith`, or the mistyped `Mr Smth`), which is resolved to their staff
+/// code.
+///
+/// # Errors
+///
+/// A [`ParseQueryError`] as for [`parse`], or should a teacher referred to by
+/// name not be resolved.
+pub fn parse_with(input: &str, resolver: &Resolver) -> Result<Question, ParseQueryError> {
+    let words: Vec<String> = input
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '/')
+                .replace(['\'', '’'], "")
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let mut question = Question {
+        intent: Intent::What,
+        week: None,
+        day: None,
+        period: None,
+        location: None,
+        class: None,
+        subject: None,
+        teacher: None,
+    };
+
+    let mut index = 0;
+
+    if let Some(intent) = words
+        .first()
+        .and_then(|word| match word.to_ascii_lowercase().as_str() {
+            "where" | "wheres" => Some(Intent::Where),
+            "what" | "whats" => Some(Intent::What),
+            "when" | "whens" => Some(Intent::When),
+            "who" | "whos" => Some(Intent::Who),
+            _ => None,
+        })
+    {
+        question.intent = intent;
+        index = 1;
+    }
+
+    let mut empty = true;
+
+    while index < words.len() {
+        let word = &words[index];
+        let lower = word.to_ascii_lowercase();
+        let next = words.get(index + 1);
+
+        // The number of words consumed by the criterion
+        let mut consumed = 1;
+
+        if FILLER.contains(&lower.as_str()) {
+            index += 1;
+            continue;
+        }
+
+        empty = false;
+
+        if let Some(name) = next.filter(|_| fuzzy::is_title(&lower)) {
+            let part = format!("{} {}", word, name);
+            consumed = 2;
+
+            let code = resolver
+                .resolve_teacher(&part)
+                .ok_or(ParseQueryError::Unrecognised(part))?;
+            set(&mut question.teacher, code.to_string(), "teacher")?;
+        } else if lower == "week" || lower == "period" {
+            let Some(value) = next else {
+                return Err(ParseQueryError::Unrecognised(word.clone()));
+            };
+
+            let part = format!("{} {}", word, value);
+            consumed = 2;
+
+            if lower == "week" {
+                let week = parse_week(value).ok_or(ParseQueryError::Unrecognised(part))?;
+                set(&mut question.week, week, "week")?;
+            } else {
+                let period = parse_period(value).ok_or(ParseQueryError::Unrecognised(part))?;
+                set(&mut question.period, period, "period")?;
+            }
+        } else if let Some(week) = lower
+            .strip_prefix('w')
+            .filter(|number| number.len() == 1)
+            .and_then(parse_week)
+        {
+            set(&mut question.week, week, "week")?;
+        } else if let Some(period) = lower
+            .strip_prefix('p')
+            .filter(|number| number.chars().all(|c| c.is_ascii_digit()) && !number.is_empty())
+            .map(|number| parse_period(number).ok_or(ParseQueryError::Unrecognised(word.clone())))
+        {
+            set(&mut question.period, period?, "period")?;
+        } else if let Some(day) = parse_named_day(&lower) {
+            set(&mut question.day, day, "day")?;
+        } else if let Some((location, words)) = parse_location(word, next) {
+            consumed = words;
+            set(&mut question.location, location, "room")?;
+        } else if word.starts_with(|c: char| c.is_ascii_digit())
+            && word.contains(|c: char| c.is_ascii_alphabetic())
+        {
+            set(&mut question.class, word.clone(), "class")?;
+        } else if (2..=4).contains(&word.len()) && word.chars().all(|c| c.is_ascii_uppercase()) {
+            set(&mut question.teacher, word.clone(), "teacher")?;
+        } else if word.chars().all(|c| c.is_ascii_alphabetic()) {
+            let subject = resolver
+                .resolve_subject(&lower)
+                .map_or(lower, str::to_string);
+            set(&mut question.subject, subject, "subject")?;
+        } else {
+            return Err(ParseQueryError::Unrecognised(word.clone()));
+        }
+
+        index += consumed;
+    }
+
+    if empty {
+        return Err(ParseQueryError::Empty);
+    }
+
+    Ok(question)
+}
+
+// Set a criterion of a question, should it not already have a different value
+fn set<T: PartialEq + fmt::Debug>(
+    criterion: &mut Option<T>,
+    value: T,
+    name: &'static str,
+) -> Result<(), ParseQueryError> {
+    match criterion {
+        Some(first) if *first != value => Err(ParseQueryError::Ambiguous {
+            criterion: name,
+            first: format!("{:?}", first),
+            second: format!("{:?}", value),
+        }),
+        _ => {
+            *criterion = Some(value);
+            Ok(())
+        }
+    }
+}
+
+// Parse the name of an active day (the single letters of WDF notation are
+// not names, and would be mistaken for the words of a question)
+fn parse_named_day(word: &str) -> Option<Weekday> {
+    match word {
+        "tues" => Some(Weekday::Tue),
+        "thur" | "thurs" => Some(Weekday::Thu),
+        _ if word.len() >= 3 => parse_day(word),
+        _ => None,
+    }
+}
+
+// Parse a room from a word (or from a word and the word following it, e.g.,
+// `FH S12`), returning the number of words of the room
+fn parse_location(word: &str, next: Option<&String>) -> Option<(Location, usize)> {
+    let joined = next.and_then(|next| format!("{} {}", word, next).parse().ok());
+
+    match joined {
+        Some(location) => Some((location, 2)),
+        None => word
+            .parse()
+            .ok()
+            .filter(|location: &Location| location.kind() != RoomKind::ToBeConfirmed)
+            .map(|location| (location, 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, FearnhillRoom, Subject, Teacher};
+
+    fn lesson(subject: &str, teacher: Option<&str>, location: Location) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new(subject.to_string()).unwrap(),
+            class: Class::new("11A".to_string()).unwrap(),
+            teacher: teacher.map(|code| Teacher::new(code.to_string()).unwrap()),
+            location,
+        }
+    }
+
+    #[test]
+    fn lessons_filter() {
+        let gym = Location::Fearnhill(FearnhillRoom::Gym);
+        let hall = "Hall".parse().unwrap();
+
+        let mut timetable = Timetable::new();
+        let pe = timetable.insert(timeslot!(W1TP1), lesson("PE", Some("JSM"), gym));
+        timetable.insert(timeslot!(W1TP2), lesson("PE", None, gym));
+        timetable.insert(timeslot!(W1TP3), lesson("Ma", Some("JSM"), hall));
+        timetable.insert(timeslot!(W2TP1), lesson("PE", Some("JSM"), gym));
+        timetable.insert(timeslot!(W1TP4), Activity::HomeStudy);
+
+        let ids: Vec<EntryId> = timetable
+            .lessons()
+            .on(Weekday::Tue)
+            .in_week(Week::WeekOne)
+            .at_school(School::Fearnhill)
+            .taught_by("JSM")
+            .map(|(id, _)| id)
+            .collect();
+
+        assert_eq!(ids, vec![pe]);
+        assert_eq!(timetable.lessons().count(), 4);
+        assert_eq!(timetable.lessons().of_subject("PE").in_room(gym).count(), 3);
+        assert_eq!(timetable.lessons().during(timeslot!(W1TP3)).count(), 1);
+    }
+
+    #[test]
+    fn parse_questions() {
+        let hall: Location = "Hall".parse().unwrap();
+
+        let mut timetable = Timetable::new();
+        let history = timetable.insert(timeslot!(W2FP4), lesson("History", Some("JSM"), hall));
+        timetable.insert(timeslot!(W1FP4), lesson("History", Some("JSM"), hall));
+
+        let question = parse("What's in the Hall period 4 Friday week B?").unwrap();
+
+        assert_eq!(question.intent, Intent::What);
+        assert_eq!(question.location, Some(hall));
+        assert_eq!(
+            question
+                .execute(&timetable)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            [history]
+        );
+
+        let question = parse("where is 11a hist on fri").unwrap();
+
+        assert_eq!(question.intent, Intent::Where);
+        assert_eq!(question.execute(&timetable).len(), 2);
+        assert_eq!(
+            parse("who teaches FH S12 P2").map(|question| question.location),
+            Ok(Some(Location::Fearnhill("S12".parse().unwrap())))
+        );
+        assert_eq!(
+            parse("when does JSM teach").unwrap().teacher.as_deref(),
+            Some("JSM")
+        );
+
+        assert_eq!(parse("where is the"), Err(ParseQueryError::Empty));
+        assert_eq!(
+            parse("what's on period 9"),
+            Err(ParseQueryError::Unrecognised("period 9".to_string()))
+        );
+        assert!(matches!(
+            parse("where is maths physics"),
+            Err(ParseQueryError::Ambiguous {
+                criterion: "subject",
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse("Monday or Tuesday"),
+            Err(ParseQueryError::Ambiguous {
+                criterion: "day",
+                ..
+            })
+        ));
+
+        let resolver = Resolver::new()
+            .subjects(["Physics"])
+            .staff_member("JSM", "John Smith");
+        let question = parse_with("when does Mr Smth teach phyics", &resolver).unwrap();
+
+        assert_eq!(question.teacher.as_deref(), Some("JSM"));
+        assert_eq!(question.subject.as_deref(), Some("Physics"));
+        assert_eq!(
+            parse_with("who is Mrs Jones", &resolver),
+            Err(ParseQueryError::Unrecognised("Mrs Jones".to_string()))
+        );
+    }
+
+    #[test]
+    fn timetable_views() {
+        let gym = Location::Fearnhill(FearnhillRoom::Gym);
+        let hall = "Hall".parse().unwrap();
+        let start = chrono::NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+
+        let mut timetable = Timetable::new();
+        let pe = timetable.insert(timeslot!(W1TP1), lesson("PE", Some("JSM"), gym));
+        timetable.insert(timeslot!(W1TP2), lesson("Ma", Some("ABC"), hall));
+        timetable.override_room(timeslot!(W1TP1), start..=start, hall);
+        timetable.override_room(timeslot!(W1TP3), start..=start, hall);
+
+        let view = timetable.view_for_teacher("JSM");
+
+        assert_eq!(view.entries().map(|(id, _)| id).collect::<Vec<_>>(), [pe]);
+        assert_eq!(view.overrides().len(), 1);
+        assert!(view.history().is_empty());
+        assert_eq!(timetable.view_for_room(hall).len(), 1);
+        assert_eq!(timetable.view_for_class("11A").len(), 2);
+        assert!(timetable.view_for_class("10A").is_empty());
+    }
+}
@@ -0,0 +1,65 @@
+/// A seedable pseudo-random number generator (SplitMix64).
+///
+/// The generator is not cryptographically secure -- it exists such that
+/// randomised algorithms (e.g., the optimiser) are reproducible from a seed
+/// without depending on an external crate.
+#[derive(Debug, Clone)]
+pub(crate) struct Random(u64);
+
+impl Random {
+    /// Creates a new `Random` from the `seed` provided.
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Creates a new `Random` seeded from the system clock.
+    pub(crate) fn from_clock() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos() as u64);
+
+        Self(nanos)
+    }
+
+    /// Generates the next random `u64`.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Generates a random integer in the range `0..bound` (`bound` must not
+    /// be `0`).
+    pub(crate) fn below(&mut self, bound: usize) -> usize {
+        // The modulo bias is negligible for the bounds used by the crate
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Generates a random float in the range `0.0..1.0`.
+    pub(crate) fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_reproducible() {
+        let mut a = Random::new(42);
+        let mut b = Random::new(42);
+
+        for _ in 0..100 {
+            let value = a.below(10);
+
+            assert_eq!(value, b.below(10));
+            assert!(value < 10);
+            assert!((0.0..1.0).contains(&a.unit()));
+            b.unit();
+        }
+    }
+}
@@ -6,10 +6,20 @@ macro_rules! ranged_types {
             /// A ranged integer is an integer which must take a specific value within a
             /// range of possible values -- in this case, the value must be in the range
             /// `MIN..=MAX`.
+            ///
+            /// With the `serde` feature, a ranged integer is serialised as a plain
+            /// integer, and deserialising a value outside the range fails.
+            ///
+            /// # Remarks
+            ///
+            /// The value is stored as its (non-zero) offset from `MIN - 1`, such that
+            /// an [`Option`] of a ranged integer is the same size as the ranged integer
+            /// itself -- as a result, the range may not span every value of the
+            #[doc = concat!("`", stringify!($type), "` (which is rejected at compile time).")]
             $(#[$attr])*
-            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+            #[derive(Clone, Copy, PartialEq, Eq, Hash)]
             #[repr(transparent)] /* use the same representation as a normal type */
-            pub struct $name<const MIN: $type, const MAX: $type>($type);
+            pub struct $name<const MIN: $type, const MAX: $type>(::core::num::NonZero<$type>);
 
             impl<const MIN: $type, const MAX: $type> $name<MIN, MAX> {
                 #[doc = concat!("Creates a new `", stringify!($name), "<MIN, MAX>`.")]
@@ -25,19 +35,64 @@ macro_rules! ranged_types {
                 //     if they want to panic or they can do something else if they don't
                 //     want to panic)
                 pub const fn new(value: $type) -> Option<Self> {
+                    // A range spanning every value of the type would leave no value to
+                    // represent `None`
+                    const {
+                        assert!(
+                            (MAX as i128) - (MIN as i128) < (<$type>::MAX as i128) - (<$type>::MIN as i128),
+                            "the range of a ranged integer must not span its entire type",
+                        )
+                    };
+
                     // Ensure the value is within the range `MIN..=MAX`
-                    if value >= MIN && value <= MAX {
-                        // If the value is within the range, return Some(v)
-                        Some(Self(value))
-                    } else {
-                        // Otherwise, return None
-                        None
+                    if value < MIN || value > MAX {
+                        return None;
+                    }
+
+                    // The offset is never zero, as the range does not span the entire
+                    // type (the arithmetic wraps for signed types)
+                    match ::core::num::NonZero::new(value.wrapping_sub(MIN).wrapping_add(1)) {
+                        Some(offset) => Some(Self(offset)),
+                        None => None,
+                    }
+                }
+
+                #[doc = concat!("Creates a new `", stringify!($name), "<MIN, MAX>`.")]
+                ///
+                /// Unlike [`Self::new`], the error retains the offending value and the
+                /// bounds of the range (e.g., such that an importer can describe exactly
+                /// what was wrong).
+                ///
+                /// # Errors
+                ///
+                /// [`RangeError`](crate::RangeError) is returned should the `value`
+                /// provided be outside the range `MIN..=MAX`.
+                pub const fn try_new(value: $type) -> Result<Self, crate::RangeError> {
+                    match Self::new(value) {
+                        Some(ranged) => Ok(ranged),
+                        None => Err(crate::RangeError::new(value as i128, MIN as i128, MAX as i128)),
                     }
                 }
 
                 #[doc = concat!("Get the inner value of the `", stringify!($name), "`.")]
                 pub const fn get(self) -> $type {
-                    self.0
+                    self.0.get().wrapping_sub(1).wrapping_add(MIN)
+                }
+
+                #[doc = concat!("Creates an iterator over every `", stringify!($name), "<MIN, MAX>`")]
+                /// (i.e., every value in the range `MIN..=MAX`), in ascending order.
+                pub fn all() -> impl DoubleEndedIterator<Item = Self> + Clone {
+                    (MIN..=MAX).filter_map(Self::new)
+                }
+
+                #[doc = concat!("Creates an iterator over every `", stringify!($name), "<MIN, MAX>`")]
+                /// from `start` to `end` (inclusive), in ascending order -- unlike iterating
+                /// over a range of the inner type, the values yielded need not be converted
+                /// (fallibly) back into ranged integers.
+                ///
+                /// The iterator is empty should `start` be greater than `end`.
+                pub fn range(start: Self, end: Self) -> impl DoubleEndedIterator<Item = Self> + Clone {
+                    (start.get()..=end.get()).filter_map(Self::new)
                 }
             }
 
@@ -47,17 +102,75 @@ macro_rules! ranged_types {
                 }
             }
 
+            // The error retains the offending value (*see `try_new`*)
             impl<const MIN: $type, const MAX: $type> ::core::convert::TryFrom<$type> for $name<MIN, MAX> {
-                type Error = ();
+                type Error = crate::RangeError;
 
                 fn try_from(value: $type) -> ::core::result::Result<Self, Self::Error> {
-                    Self::new(value).ok_or(())
+                    Self::try_new(value)
+                }
+            }
+
+            impl<const MIN: $type, const MAX: $type> ::core::str::FromStr for $name<MIN, MAX> {
+                type Err = crate::ParseRangedError;
+
+                // Parse the value as the inner type, and then check its range
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    let value: $type = s.parse().map_err(crate::ParseRangedError::Invalid)?;
+
+                    Self::try_new(value).map_err(crate::ParseRangedError::Range)
                 }
             }
 
             impl<const MIN: $type, const MAX: $type> ::core::fmt::Display for $name<MIN, MAX> {
                 fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                    self.0.fmt(f)
+                    self.get().fmt(f)
+                }
+            }
+
+            // Format as the inner value (rather than the stored offset)
+            impl<const MIN: $type, const MAX: $type> ::core::fmt::Debug for $name<MIN, MAX> {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_tuple(stringify!($name)).field(&self.get()).finish()
+                }
+            }
+
+            // Order by the inner value (the stored offsets of signed types do not
+            // share its order)
+            impl<const MIN: $type, const MAX: $type> ::core::cmp::PartialOrd for $name<MIN, MAX> {
+                fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl<const MIN: $type, const MAX: $type> ::core::cmp::Ord for $name<MIN, MAX> {
+                fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                    self.get().cmp(&other.get())
+                }
+            }
+
+            // Serialise as the inner value (i.e., as a plain integer)
+            #[cfg(feature = "serde")]
+            impl<const MIN: $type, const MAX: $type> ::serde::Serialize for $name<MIN, MAX> {
+                fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                {
+                    ::serde::Serialize::serialize(&self.get(), serializer)
+                }
+            }
+
+            // Deserialise from a plain integer, which must be within the range
+            // `MIN..=MAX` (the error describes the value and the range otherwise)
+            #[cfg(feature = "serde")]
+            impl<'de, const MIN: $type, const MAX: $type> ::serde::Deserialize<'de> for $name<MIN, MAX> {
+                fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    let value = <$type as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                    Self::try_new(value).map_err(<D::Error as ::serde::de::Error>::custom)
                 }
             }
         )+
@@ -68,15 +181,136 @@ macro_rules! ranged_types {
 ranged_types!(
     RangedU8(u8);
 
+    /// # Examples
+    ///
+    /// A time of day in minutes (e.g., the start of a bell period):
+    ///
+    /// ```
+    /// use timetableau::RangedU16;
+    ///
+    /// type MinuteOfDay = RangedU16<0, 1439>;
+    ///
+    /// assert_eq!("525".parse::<MinuteOfDay>().map(MinuteOfDay::get), Ok(525));
+    /// assert!(MinuteOfDay::new(1440).is_none());
+    /// ```
+    RangedU16(u16);
+
+    /// # Examples
+    ///
+    /// A signed offset (e.g., the number of periods by which a lesson moves):
+    ///
+    /// ```
+    /// use timetableau::RangedI8;
+    ///
+    /// let offset = RangedI8::<-4, 4>::new(-2).unwrap();
+    ///
+    /// assert!(offset < RangedI8::new(1).unwrap());
+    /// ```
+    RangedI8(i8);
+
     // Hide these as they aren't used -- having them clutter up documentation
     // is unnecessary: the only reason ranged types were created was because
     // they are not currently in the standard library and pulling in an entire
     // crate for them is overkill
-    #[doc(hidden)] RangedU16(u16);
     #[doc(hidden)] RangedU32(u32);
     #[doc(hidden)] RangedU64(u64);
-    #[doc(hidden)] RangedI8(i8);
     #[doc(hidden)] RangedI16(i16);
     #[doc(hidden)] RangedI32(i32);
     #[doc(hidden)] RangedI64(i64);
 );
+
+// An `Option` of a ranged integer must use the niche of its offset
+const _: () = assert!(size_of::<Option<RangedU8<1, 99>>>() == size_of::<u8>());
+const _: () = assert!(size_of::<Option<RangedU16<0, 1439>>>() == size_of::<u16>());
+const _: () = assert!(size_of::<Option<RangedI8<-4, 4>>>() == size_of::<i8>());
+const _: () = assert!(size_of::<Option<RangedI64<-5, 5>>>() == size_of::<i64>());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParseRangedError, RangeError};
+
+    #[test]
+    fn ranged_try_new() {
+        assert_eq!(RangedU8::<1, 99>::try_new(42).map(RangedU8::get), Ok(42));
+        assert_eq!(
+            RangedU8::<1, 99>::try_new(0),
+            Err(RangeError::new(0, 1, 99))
+        );
+        assert_eq!(
+            RangedI8::<-5, 5>::try_new(-6).map_err(|error| error.to_string()),
+            Err("-6 is outside of the permitted range -5..=5".to_string())
+        );
+    }
+
+    #[test]
+    fn ranged_conversions() {
+        assert_eq!(
+            "42".parse::<RangedU8<1, 99>>(),
+            Ok(RangedU8::new(42).unwrap())
+        );
+        assert_eq!(
+            "100".parse::<RangedU8<1, 99>>(),
+            Err(ParseRangedError::Range(RangeError::new(100, 1, 99)))
+        );
+        assert!(matches!(
+            "4x".parse::<RangedU8<1, 99>>(),
+            Err(ParseRangedError::Invalid(_))
+        ));
+        assert_eq!(
+            RangedU8::<1, 99>::try_from(0),
+            Err(RangeError::new(0, 1, 99))
+        );
+        assert_eq!(format!("{:>3}", RangedU8::<1, 99>::new(7).unwrap()), "  7");
+    }
+
+    #[test]
+    fn ranged_offsets() {
+        let values = RangedI8::<-100, 100>::all();
+
+        // Signed values are ordered by value (rather than by their offsets)
+        assert!(values.clone().zip(values.skip(1)).all(|(a, b)| a < b));
+        assert_eq!(
+            RangedI8::<-100, 100>::new(-100).map(RangedI8::get),
+            Some(-100)
+        );
+        assert_eq!(RangedU8::<0, 254>::new(254).map(RangedU8::get), Some(254));
+        assert_eq!(
+            format!("{:?}", RangedU8::<1, 99>::new(7).unwrap()),
+            "RangedU8(7)"
+        );
+    }
+
+    #[test]
+    fn ranged_iteration() {
+        let values: Vec<u8> = RangedU8::<1, 9>::all().map(RangedU8::get).collect();
+
+        assert_eq!(values, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let start = RangedI8::<-5, 5>::new(-1).unwrap();
+        let end = RangedI8::<-5, 5>::new(2).unwrap();
+
+        assert_eq!(RangedI8::range(start, end).next_back(), Some(end));
+        assert_eq!(RangedI8::range(start, end).count(), 4);
+        assert_eq!(RangedI8::range(end, start).count(), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ranged_serde() {
+        use serde::de::value::{Error as ValueError, U8Deserializer};
+        use serde::de::IntoDeserializer;
+        use serde::Deserialize;
+
+        let deserialize = |value: u8| {
+            let deserializer: U8Deserializer<ValueError> = value.into_deserializer();
+            RangedU8::<1, 99>::deserialize(deserializer).map_err(|error| error.to_string())
+        };
+
+        assert_eq!(deserialize(42), Ok(RangedU8::new(42).unwrap()));
+        assert_eq!(
+            deserialize(100),
+            Err("100 is outside of the permitted range 1..=99".to_string())
+        );
+    }
+}
@@ -0,0 +1,640 @@
+use crate::{Location, ParseLocationError, ParseRegistryError, RoomMigration};
+use chrono::NaiveDate;
+use std::collections::{BTreeSet, HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::sync::{OnceLock, RwLock};
+
+// The curated database of the rooms which exist at the schools
+const KNOWN_ROOMS: &str = include_str!("../data/rooms.txt");
+
+// The database which replaces the curated database (if any)
+#[cfg(feature = "std")]
+static KNOWN: RwLock<Option<RoomRegistry>> = RwLock::new(None);
+
+/// A registry of the rooms which exist at the schools.
+///
+/// Every structurally valid room identifier (e.g., `H299`) can be parsed into
+/// a [`Location`], whether or not such a room exists -- a registry records
+/// which rooms do, such that a strict [`ParseOptions`] can reject the rest.
+///
+/// The crate ships a curated database of the rooms which exist (*see
+/// [`RoomRegistry::known`]*), which can be replaced at runtime (e.g., from a
+/// data file, should a room be built or demolished):
+///
+/// ```
+/// use timetableau::{Location, RoomRegistry};
+///
+/// let h201: Location = "H201".parse().unwrap();
+/// let h299: Location = "H299".parse().unwrap();
+///
+/// assert!(h201.exists());
+/// assert!(!h299.exists());
+///
+/// # #[cfg(feature = "std")]
+/// # {
+/// let registry = RoomRegistry::from_data("# Rooms\nH201\nH299\n").unwrap();
+/// RoomRegistry::set_known(Some(registry));
+///
+/// assert!(h299.exists());
+/// # }
+/// ```
+///
+/// A registry also records the facilities of its rooms (e.g., `Computers` or
+/// `Gas Taps`), against which the requirements of lessons are matched --
+/// *see [`Facilities`](crate::validate::Facilities)* -- and the fallback of
+/// each outdoor space (i.e., the room its lessons move to in wet weather --
+/// *see [`Timetable::fall_back`](crate::Timetable::fall_back)*), as well as
+/// the address of each [off-site location](crate::OffSite).
+///
+/// # Retired Rooms
+///
+/// A room which has been renamed or demolished remains registered, but may
+/// be marked as retired from a given date (*see [`RoomRegistry::retire`]*)
+/// -- parsers still accept the room (such that historical data can be read),
+/// whereas the [`RetiredRooms`](crate::validate::RetiredRooms) rule flags
+/// its use in current timetables:
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use timetableau::RoomRegistry;
+///
+/// let registry = RoomRegistry::from_data("U101 [retired 2025-08-01, replaced by U201]").unwrap();
+/// let u101 = "U101".parse().unwrap();
+/// let retirement = registry.retirement(&u101).unwrap();
+///
+/// assert!(registry.contains(&u101));
+/// assert_eq!(retirement.replacement, "U201".parse().ok());
+/// assert!(registry.is_retired(&u101, NaiveDate::from_ymd_opt(2025, 9, 1).unwrap()));
+/// assert_eq!(registry.migration().get(&u101), retirement.replacement);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoomRegistry {
+    rooms: HashSet<Location>,
+    facilities: HashMap<Location, BTreeSet<String>>,
+    fallbacks: HashMap<Location, Location>,
+    addresses: HashMap<Location, String>,
+    retirements: HashMap<Location, Retirement>,
+}
+
+/// The retirement of a room from a [`RoomRegistry`] -- *see
+/// [`RoomRegistry::retire`]*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Retirement {
+    /// The date from which the room is no longer in use.
+    pub from: NaiveDate,
+
+    /// The room which replaces the retired room (if any).
+    pub replacement: Option<Location>,
+}
+
+impl RoomRegistry {
+    /// Creates a new `RoomRegistry` without any rooms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the `location` provided.
+    ///
+    /// # Returns
+    ///
+    /// Whether the location was not already registered.
+    pub fn insert(&mut self, location: Location) -> bool {
+        self.rooms.insert(location)
+    }
+
+    /// Records that the `location` provided (which is registered should it
+    /// not already be) has the facility with the `name` provided (e.g.,
+    /// `Sprung Floor`).
+    pub fn add_facility(&mut self, location: Location, name: &str) {
+        self.rooms.insert(location);
+        self.facilities
+            .entry(location)
+            .or_default()
+            .insert(name.to_string());
+    }
+
+    /// Creates an iterator over the facilities of the `location` provided (in
+    /// alphabetical order).
+    pub fn facilities(&self, location: &Location) -> impl Iterator<Item = &str> {
+        self.facilities
+            .get(location)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Determines whether the `location` provided has the facility with the
+    /// `name` provided.
+    pub fn has_facility(&self, location: &Location, name: &str) -> bool {
+        self.facilities
+            .get(location)
+            .is_some_and(|facilities| facilities.contains(name))
+    }
+
+    /// Records that the lessons taking place in the `location` provided
+    /// (e.g., the field) move to `fallback` (e.g., the sports hall) should
+    /// the weather prevent them from taking place -- both rooms are
+    /// registered should they not already be.
+    pub fn set_fallback(&mut self, location: Location, fallback: Location) {
+        self.rooms.extend([location, fallback]);
+        self.fallbacks.insert(location, fallback);
+    }
+
+    /// Retrieves the fallback of the `location` provided (*see
+    /// [`Self::set_fallback`]*).
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the location has no fallback.
+    pub fn fallback(&self, location: &Location) -> Option<Location> {
+        self.fallbacks.get(location).copied()
+    }
+
+    /// Records the (postal) `address` of the `location` provided (e.g., of
+    /// an [off-site location](crate::OffSite), which carries only its name
+    /// and coordinates) -- the location is registered should it not already
+    /// be.
+    pub fn set_address(&mut self, location: Location, address: String) {
+        self.rooms.insert(location);
+        self.addresses.insert(location, address);
+    }
+
+    /// Retrieves the address of the `location` provided (*see
+    /// [`Self::set_address`]*).
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if no address has been recorded for the location.
+    pub fn address(&self, location: &Location) -> Option<&str> {
+        self.addresses.get(location).map(String::as_str)
+    }
+
+    /// Records that the `location` provided (which is registered should it
+    /// not already be) is retired `from` the date provided, and is replaced
+    /// by the `replacement` room (if any) -- the replacement is registered
+    /// should it not already be.
+    ///
+    /// # Remarks
+    ///
+    /// Retiring a room does not unregister it -- *see the [`RoomRegistry`]
+    /// documentation for more information*.
+    pub fn retire(&mut self, location: Location, from: NaiveDate, replacement: Option<Location>) {
+        self.rooms
+            .extend(Some(location).into_iter().chain(replacement));
+        self.retirements
+            .insert(location, Retirement { from, replacement });
+    }
+
+    /// Retrieves the retirement of the `location` provided (*see
+    /// [`Self::retire`]*).
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the location has not been retired.
+    pub fn retirement(&self, location: &Location) -> Option<Retirement> {
+        self.retirements.get(location).copied()
+    }
+
+    /// Determines whether the `location` provided has been retired on (or
+    /// before) the `date` provided.
+    pub fn is_retired(&self, location: &Location, date: NaiveDate) -> bool {
+        self.retirement(location)
+            .is_some_and(|retirement| retirement.from <= date)
+    }
+
+    /// Creates a [`RoomMigration`] renaming every retired room which has a
+    /// replacement to its replacement (e.g., such that the datasets stored
+    /// before a block was renumbered can be migrated).
+    pub fn migration(&self) -> RoomMigration {
+        self.retirements
+            .iter()
+            .filter_map(|(location, retirement)| Some((*location, retirement.replacement?)))
+            .collect()
+    }
+
+    /// Determines whether the `location` provided is registered.
+    pub fn contains(&self, location: &Location) -> bool {
+        self.rooms.contains(location)
+    }
+
+    /// Retrieves the number of registered rooms.
+    pub fn len(&self) -> usize {
+        self.rooms.len()
+    }
+
+    /// Determines whether no rooms are registered.
+    pub fn is_empty(&self) -> bool {
+        self.rooms.is_empty()
+    }
+
+    /// Creates an iterator over the registered rooms (in an arbitrary order).
+    pub fn iter(&self) -> impl Iterator<Item = &Location> {
+        self.rooms.iter()
+    }
+
+    /// Parses a database of rooms: one room identifier per line, optionally
+    /// followed by a colon and a comma-separated list of the room's
+    /// facilities (e.g., `FH S1: Gas Taps, Fume Cupboard`), and then by `->`
+    /// and the identifier of the room's [fallback](Self::set_fallback)
+    /// (e.g., `Field -> Sports Hall`) -- blank lines and lines starting with
+    /// `#` are ignored.
+    ///
+    /// A room identifier may also be followed by its
+    /// [retirement](Self::retire) in square brackets (e.g., `U101 [retired
+    /// 2025-08-01]`, or `U101 [retired 2025-08-01, replaced by U201]`).
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should a line not start with a structurally
+    /// valid room identifier, should its fallback or replacement not be one,
+    /// or should its retirement not be in the format above (in which case
+    /// the [code](ParseRegistryError::code) of the error is the retirement
+    /// in square brackets).
+    pub fn from_data(data: &str) -> Result<Self, ParseRegistryError> {
+        let mut registry = Self::new();
+
+        for (line, entry) in data
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        {
+            let parse = |code: &str| {
+                code.trim()
+                    .parse()
+                    .map_err(|_| ParseRegistryError::new(line, code.trim().to_string()))
+            };
+
+            let (entry, retirement) = match entry.split_once('[') {
+                Some((entry, retirement)) => {
+                    let invalid = || ParseRegistryError::new(line, format!("[{}", retirement));
+                    let retirement = retirement
+                        .strip_suffix(']')
+                        .and_then(|retirement| retirement.trim().strip_prefix("retired "))
+                        .ok_or_else(invalid)?;
+                    let (date, replacement) = match retirement.split_once(',') {
+                        Some((date, replacement)) => {
+                            let replacement = replacement
+                                .trim()
+                                .strip_prefix("replaced by ")
+                                .ok_or_else(invalid)?;
+
+                            (date, Some(parse(replacement)?))
+                        }
+                        None => (retirement, None),
+                    };
+                    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                        .map_err(|_| invalid())?;
+
+                    (entry, Some((date, replacement)))
+                }
+                None => (entry, None),
+            };
+            let (entry, fallback) = match entry.split_once("->") {
+                Some((entry, fallback)) => (entry, Some(parse(fallback)?)),
+                None => (entry, None),
+            };
+            let (code, facilities) = entry.split_once(':').unwrap_or((entry, ""));
+            let location = parse(code)?;
+
+            registry.insert(location);
+
+            if let Some((date, replacement)) = retirement {
+                registry.retire(location, date, replacement);
+            }
+
+            if let Some(fallback) = fallback {
+                registry.set_fallback(location, fallback);
+            }
+
+            for name in facilities.split(',').map(str::trim) {
+                if !name.is_empty() {
+                    registry.add_facility(location, name);
+                }
+            }
+        }
+
+        Ok(registry)
+    }
+
+    /// Retrieves the curated database of the rooms which exist at the
+    /// schools (as shipped with the crate).
+    pub fn curated() -> Self {
+        // The embedded database is checked by the crate's tests
+        Self::from_data(KNOWN_ROOMS).unwrap()
+    }
+
+    /// Retrieves the database of the rooms which exist at the schools: the
+    /// database provided to [`set_known`](Self::set_known), or the
+    /// [curated](Self::curated) database should none have been provided.
+    pub fn known() -> Self {
+        with_known(Self::clone)
+    }
+
+    /// Replaces the database of the rooms which exist at the schools (for
+    /// the entire process) -- [`None`] restores the curated database.
+    #[cfg(feature = "std")]
+    pub fn set_known(registry: Option<Self>) {
+        *KNOWN.write().unwrap_or_else(|error| error.into_inner()) = registry;
+    }
+}
+
+// Call `f` with the database of the rooms which exist at the schools
+#[cfg(feature = "std")]
+fn with_known<T>(f: impl FnOnce(&RoomRegistry) -> T) -> T {
+    static CURATED: OnceLock<RoomRegistry> = OnceLock::new();
+
+    let known = KNOWN.read().unwrap_or_else(|error| error.into_inner());
+
+    f(known
+        .as_ref()
+        .unwrap_or_else(|| CURATED.get_or_init(RoomRegistry::curated)))
+}
+
+// Call `f` with the database of the rooms which exist at the schools (which
+// cannot be replaced without the standard library's synchronisation
+// primitives)
+#[cfg(not(feature = "std"))]
+fn with_known<T>(f: impl FnOnce(&RoomRegistry) -> T) -> T {
+    f(&RoomRegistry::curated())
+}
+
+impl Location {
+    /// Determines whether the `Location` exists (i.e., whether it is in the
+    /// database of [known](RoomRegistry::known) rooms).
+    ///
+    /// Every structurally valid room identifier can be parsed into a
+    /// `Location` (e.g., `H999`), whether or not such a room has been built.
+    pub fn exists(&self) -> bool {
+        with_known(|registry| registry.contains(self))
+    }
+
+    /// Retrieves the fallback of the `Location` (*see
+    /// [`RoomRegistry::set_fallback`]*), as recorded in the database of
+    /// [known](RoomRegistry::known) rooms.
+    pub fn fallback(&self) -> Option<Location> {
+        with_known(|registry| registry.fallback(self))
+    }
+
+    /// Retrieves the facilities of the `Location` (in alphabetical order), as
+    /// recorded in the database of [known](RoomRegistry::known) rooms.
+    pub fn facilities(&self) -> Vec<String> {
+        with_known(|registry| registry.facilities(self).map(str::to_string).collect())
+    }
+
+    /// Retrieves the retirement of the `Location` (*see
+    /// [`RoomRegistry::retire`]*), as recorded in the database of
+    /// [known](RoomRegistry::known) rooms.
+    pub fn retirement(&self) -> Option<Retirement> {
+        with_known(|registry| registry.retirement(self))
+    }
+}
+
+impl FromIterator<Location> for RoomRegistry {
+    fn from_iter<I: IntoIterator<Item = Location>>(iter: I) -> Self {
+        Self {
+            rooms: iter.into_iter().collect(),
+            facilities: HashMap::new(),
+            fallbacks: HashMap::new(),
+            addresses: HashMap::new(),
+            retirements: HashMap::new(),
+        }
+    }
+}
+
+impl Extend<Location> for RoomRegistry {
+    fn extend<I: IntoIterator<Item = Location>>(&mut self, iter: I) {
+        self.rooms.extend(iter);
+    }
+}
+
+/// The options used by the crate's parsers and importers.
+///
+/// By default, parsing is lenient: any structurally valid room identifier is
+/// accepted (which suits tools cleaning up historic data). Parsing is strict
+/// when created with [`ParseOptions::strict`], rejecting any room which is
+/// not in the [`RoomRegistry`] provided (which suits systems booking rooms).
+/// Between the two, [`ParseOptions::flagging`] accepts every structurally
+/// valid room, but importers report any room which is not in the registry
+/// as an [`ImportIssue::NonexistentRoom`](crate::import::ImportIssue):
+///
+/// ```
+/// use timetableau::{Location, ParseOptions, RoomRegistry};
+///
+/// let registry: RoomRegistry = ["H201", "FH S13"]
+///     .into_iter()
+///     .map(|code| code.parse::<Location>().unwrap())
+///     .collect();
+///
+/// let lenient = ParseOptions::lenient();
+/// let strict = ParseOptions::strict(registry);
+///
+/// assert!(lenient.parse_location("H299").is_ok());
+/// assert!(strict.parse_location("H201").is_ok());
+/// assert!(strict.parse_location("H299").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    registry: Option<RoomRegistry>,
+    reject: bool,
+    #[cfg(feature = "other-rooms")]
+    other_rooms: bool,
+}
+
+impl ParseOptions {
+    /// Creates new lenient `ParseOptions` (i.e., any structurally valid room
+    /// identifier is accepted).
+    pub fn lenient() -> Self {
+        Self::default()
+    }
+
+    /// Creates new strict `ParseOptions` (i.e., only the rooms within the
+    /// `registry` are accepted).
+    pub fn strict(registry: RoomRegistry) -> Self {
+        Self {
+            registry: Some(registry),
+            reject: true,
+            #[cfg(feature = "other-rooms")]
+            other_rooms: false,
+        }
+    }
+
+    /// Creates new flagging `ParseOptions` (i.e., any structurally valid room
+    /// identifier is accepted, but the rooms which are not within the
+    /// `registry` are [flagged](Self::is_known) by the importers).
+    pub fn flagging(registry: RoomRegistry) -> Self {
+        Self {
+            reject: false,
+            ..Self::strict(registry)
+        }
+    }
+
+    /// Sets whether room identifiers which are not valid under either
+    /// school's room numbering scheme are parsed as an
+    /// [`OtherRoom`](crate::OtherRoom) (rather than rejected) -- *see
+    /// [`Location::parse_or_other`]*.
+    ///
+    /// Should the options also be strict, such rooms must still be in the
+    /// registry.
+    #[cfg(feature = "other-rooms")]
+    pub fn other_rooms(mut self, other_rooms: bool) -> Self {
+        self.other_rooms = other_rooms;
+        self
+    }
+
+    /// Determines whether the options are strict.
+    pub fn is_strict(&self) -> bool {
+        self.registry.is_some() && self.reject
+    }
+
+    /// Retrieves the registry of known rooms (or [`None`] should the options
+    /// be lenient).
+    pub fn registry(&self) -> Option<&RoomRegistry> {
+        self.registry.as_ref()
+    }
+
+    /// Determines whether the `location` provided is in the registry of the
+    /// options (always [`true`] should the options be lenient).
+    pub fn is_known(&self, location: &Location) -> bool {
+        self.registry
+            .as_ref()
+            .is_none_or(|registry| registry.contains(location))
+    }
+
+    /// Determines whether the `location` provided is accepted by the options.
+    pub fn accepts(&self, location: &Location) -> bool {
+        !self.reject || self.is_known(location)
+    }
+
+    /// Parses the room identifier `code` into a [`Location`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should the identifier not be structurally valid,
+    /// or (should the options be strict) should the room not be registered
+    /// -- *see [`ParseLocationError::unknown`]*.
+    pub fn parse_location(&self, code: &str) -> Result<Location, ParseLocationError> {
+        #[cfg(feature = "other-rooms")]
+        if self.other_rooms {
+            return self.check(Location::parse_or_other(code)?);
+        }
+
+        self.check(code.parse()?)
+    }
+
+    /// Checks that an already parsed `location` is accepted by the options.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should the options be strict and the room not
+    /// be registered.
+    pub fn check(&self, location: Location) -> Result<Location, ParseLocationError> {
+        if self.accepts(&location) {
+            Ok(location)
+        } else {
+            Err(ParseLocationError(Some(location)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_options_strictness() {
+        let registry: RoomRegistry = ["H201"].into_iter().map(|c| c.parse().unwrap()).collect();
+        let strict = ParseOptions::strict(registry);
+
+        assert!(!ParseOptions::default().is_strict());
+        assert!(strict.is_strict());
+        assert!(ParseOptions::lenient().parse_location("U199").is_ok());
+        assert!(strict.parse_location("H201").is_ok());
+
+        let error = strict.parse_location("U199").unwrap_err();
+
+        assert_eq!(error.unknown(), Some("U199".parse().unwrap()));
+        assert_eq!(error.to_string(), "unknown room `U199`");
+        assert_eq!(strict.parse_location("X1").unwrap_err().unknown(), None);
+    }
+
+    #[test]
+    fn curated_rooms_exist() {
+        let curated = RoomRegistry::curated();
+
+        assert!(curated.len() > 100);
+        assert!(ParseOptions::flagging(curated.clone()).accepts(&"H299".parse().unwrap()));
+        assert!(!ParseOptions::flagging(curated).is_known(&"H299".parse().unwrap()));
+
+        let error = RoomRegistry::from_data("H201\n\n# Closed\nH2O1").unwrap_err();
+
+        assert_eq!((error.line(), error.code()), (4, "H2O1"));
+
+        let lab = "FH S1".parse().unwrap();
+        let registry = RoomRegistry::from_data("FH S1: Gas Taps, Fume Cupboard\nH201:").unwrap();
+
+        assert_eq!(
+            registry.facilities(&lab).collect::<Vec<_>>(),
+            ["Fume Cupboard", "Gas Taps"]
+        );
+        assert!(registry.contains(&"H201".parse().unwrap()));
+        assert!(RoomRegistry::curated().has_facility(&lab, "Gas Taps"));
+
+        let field = "FH Field".parse().unwrap();
+        let registry = RoomRegistry::from_data("FH Field: Floodlights -> FH Gym").unwrap();
+
+        assert_eq!(registry.fallback(&field), "FH Gym".parse().ok());
+        assert!(registry.has_facility(&field, "Floodlights"));
+
+        let registry = RoomRegistry::from_data("U101: Projector [retired 2025-08-01]").unwrap();
+        let u101 = "U101".parse().unwrap();
+        let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+
+        assert!(registry.has_facility(&u101, "Projector"));
+        assert!(!registry.is_retired(&u101, date(7, 31)));
+        assert!(registry.is_retired(&u101, date(8, 1)));
+        assert!(registry.migration().is_empty());
+        assert_eq!(
+            RoomRegistry::from_data("U101 [retired 2025-08-32]")
+                .unwrap_err()
+                .code(),
+            "[retired 2025-08-32]"
+        );
+        assert_eq!(
+            RoomRegistry::from_data("U101 [retired 2025-08-01, replaced by U2O1]")
+                .unwrap_err()
+                .code(),
+            "U2O1"
+        );
+
+        let mut registry = RoomRegistry::new();
+        let pool = "@Stevenage Pool".parse().unwrap();
+
+        registry.set_address(pool, "Swimming Pool, Stevenage, SG1 1LZ".to_string());
+
+        assert!(registry.contains(&pool));
+        assert_eq!(
+            registry.address(&pool),
+            Some("Swimming Pool, Stevenage, SG1 1LZ")
+        );
+        assert_eq!(
+            RoomRegistry::from_data("Field -> Sport Hall")
+                .unwrap_err()
+                .code(),
+            "Sport Hall"
+        );
+    }
+
+    #[cfg(feature = "other-rooms")]
+    #[test]
+    fn parse_options_other_rooms() {
+        let options = ParseOptions::lenient().other_rooms(true);
+        let location = options.parse_location("FH OLDLAB").unwrap();
+
+        assert_eq!(location.to_string(), "FH OLDLAB");
+        assert_eq!(location.verbose_name(), "Fearnhill OLDLAB");
+        assert_eq!(u16::from(location), 0);
+        assert_eq!(options.parse_location("H201"), Ok("H201".parse().unwrap()));
+        assert!(ParseOptions::lenient().parse_location("OLDLAB").is_err());
+    }
+}
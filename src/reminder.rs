@@ -0,0 +1,240 @@
+use crate::{Activity, Occurrence, Room, Timetable, TransitPolicy, WeekCycle};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+// The default time (in minutes) before each lesson at which its reminder is
+// given
+const LEAD_MINUTES: i64 = 5;
+
+/// The lead times with which [`Timetable::reminders`] reminds students (or
+/// teachers) of their lessons.
+///
+/// # Remarks
+///
+/// By default, reminders are given 5 minutes before each lesson, and 5
+/// minutes plus the time required to travel between the sites (i.e., 20
+/// minutes with the default [`TransitPolicy`]) before a lesson at a
+/// different site to the previous lesson of the day, such that there is time
+/// to travel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReminderPolicy {
+    lead: Duration,
+    cross_site_lead: Option<Duration>,
+    transit: TransitPolicy,
+}
+
+impl ReminderPolicy {
+    /// Creates a new `ReminderPolicy` with the default lead times.
+    pub fn new() -> Self {
+        Self {
+            lead: Duration::minutes(LEAD_MINUTES),
+            cross_site_lead: None,
+            transit: TransitPolicy::default(),
+        }
+    }
+
+    /// Sets the time before each lesson at which its reminder is given.
+    pub fn lead(mut self, lead: Duration) -> Self {
+        self.lead = lead;
+        self
+    }
+
+    /// Sets the time before each lesson at a different site to the previous
+    /// lesson of the day at which its reminder is given.
+    pub fn cross_site_lead(mut self, cross_site_lead: Duration) -> Self {
+        self.cross_site_lead = Some(cross_site_lead);
+        self
+    }
+
+    /// Sets the policy whose time to travel between the sites determines the
+    /// default lead time of each lesson at a different site to the previous
+    /// lesson of the day (unless the [cross-site
+    /// lead](Self::cross_site_lead) is set).
+    pub fn transit(mut self, transit: TransitPolicy) -> Self {
+        self.transit = transit;
+        self
+    }
+
+    // The time before a lesson at a different site at which its reminder is
+    // given
+    fn cross_site(&self) -> Duration {
+        self.cross_site_lead
+            .unwrap_or(Duration::minutes(LEAD_MINUTES) + self.transit.cross_site_time())
+    }
+}
+
+impl Default for ReminderPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A reminder of an upcoming lesson (*see [`Timetable::reminders`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reminder {
+    /// The date and time at which the reminder should be given.
+    pub at: NaiveDateTime,
+
+    /// The date and time at which the lesson starts.
+    pub start: NaiveDateTime,
+
+    /// Whether the lesson takes place at a different site to the previous
+    /// lesson of the day (i.e., whether the cross-site lead time was used).
+    pub cross_site: bool,
+
+    /// The occurrence of the lesson.
+    pub occurrence: Occurrence,
+}
+
+impl Timetable {
+    /// Creates the [`Reminder`]s of every [lesson](Activity::Lesson) which
+    /// takes place on the `dates` provided, according to the `policy`
+    /// provided.
+    ///
+    /// The lessons are those which actually take place (*see
+    /// [`Self::occurrences`]*) -- no reminders are created for holidays,
+    /// weekends, or the periods suspended by a
+    /// [`DayException`](crate::DayException). The start of each lesson is
+    /// determined using the [`BellSchedule`](crate::BellSchedule) of its
+    /// school, and a lesson spanning multiple periods (e.g., a double) is
+    /// reminded once, before its first period.
+    ///
+    /// # Returns
+    ///
+    /// The reminders, ordered by the time at which they should be given.
+    pub fn reminders(
+        &self,
+        cycle: &WeekCycle,
+        dates: RangeInclusive<NaiveDate>,
+        policy: &ReminderPolicy,
+    ) -> Vec<Reminder> {
+        let mut reminders = Vec::new();
+
+        for date in dates
+            .start()
+            .iter_days()
+            .take_while(|date| date <= dates.end())
+        {
+            let schedules = cycle.calendar().bell_schedules_on(date);
+
+            // The site of the previous lesson of the day
+            let mut previous: Option<String> = None;
+            // The entries whose lessons have already been reminded today
+            let mut reminded = HashSet::new();
+
+            for occurrence in self.occurrences(cycle, date) {
+                let Activity::Lesson { location, .. } = &occurrence.activity else {
+                    continue;
+                };
+
+                // A lesson spanning multiple periods is reminded once, before
+                // its first period
+                if occurrence
+                    .entry
+                    .is_some_and(|entry| !reminded.insert(entry))
+                {
+                    continue;
+                }
+
+                let start = date.and_time(
+                    schedules
+                        .get(location.school())
+                        .start(occurrence.slot.period)
+                        .into(),
+                );
+                let cross_site = previous
+                    .as_deref()
+                    .is_some_and(|site| site != location.site());
+
+                previous = Some(location.site().to_string());
+
+                let lead = if cross_site {
+                    policy.cross_site()
+                } else {
+                    policy.lead
+                };
+
+                reminders.push(Reminder {
+                    at: start - lead,
+                    start,
+                    cross_site,
+                    occurrence,
+                });
+            }
+        }
+
+        reminders.sort_by_key(|reminder| reminder.at);
+        reminders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Subject, Term};
+    use chrono::NaiveTime;
+
+    #[test]
+    fn reminders_cross_site_and_holidays() {
+        let lesson = |room: &str| Activity::Lesson {
+            subject: Subject::new("Maths".to_string()).unwrap(),
+            class: Class::new("12A".to_string()).unwrap(),
+            teacher: None,
+            location: room.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1FP1), lesson("H201"));
+        timetable.insert(timeslot!(W1FP2), lesson("H202"));
+        timetable.insert(timeslot!(W1FP3), lesson("FH S12"));
+        timetable.insert(timeslot!(W1FP4), Activity::SchoolStudy);
+        timetable.insert(timeslot!(W2FP1), lesson("H201"));
+
+        let monday = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let friday = NaiveDate::from_ymd_opt(2023, 9, 8).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(monday, friday).unwrap()]);
+        let cycle = WeekCycle::new(monday, calendar).unwrap();
+
+        // The second week is a holiday, so only the first Friday is reminded
+        let reminders = timetable.reminders(
+            &cycle,
+            monday..=friday + Duration::days(7),
+            &ReminderPolicy::new().lead(Duration::minutes(10)),
+        );
+        let times: Vec<(NaiveTime, bool)> = reminders
+            .iter()
+            .map(|reminder| (reminder.at.time(), reminder.cross_site))
+            .collect();
+
+        let hm = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+
+        assert!(reminders
+            .iter()
+            .all(|reminder| reminder.at.date() == friday));
+        assert_eq!(
+            times,
+            [(hm(8, 40), false), (hm(9, 40), false), (hm(10, 45), true)]
+        );
+
+        // The cross-site lead time follows the transit policy
+        let reminders = timetable.reminders(
+            &cycle,
+            friday..=friday,
+            &ReminderPolicy::new().transit(TransitPolicy::new().cross_site(Duration::minutes(25))),
+        );
+        assert_eq!(reminders[2].at.time(), hm(10, 35));
+
+        // A double lesson is reminded once, before its first period
+        let mut timetable = Timetable::new();
+        let double = timetable
+            .insert_spanning(timeslot!(W1MP1), 2, lesson("H201"))
+            .unwrap();
+
+        let reminders = timetable.reminders(&cycle, monday..=monday, &ReminderPolicy::new());
+
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].at.time(), hm(8, 45));
+        assert_eq!(reminders[0].occurrence.entry, Some(double));
+    }
+}
@@ -0,0 +1,554 @@
+use crate::calendar::monday_of;
+use crate::WeekCycle;
+use crate::{Activity, Location, Occurrence, Period, Room, RoomKind, School, TimeOfDay};
+use crate::{Timetable, Week};
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use std::fmt::Write;
+
+// The width of the column of period labels, and of the column of each day
+const LABEL_WIDTH: usize = 6;
+const DAY_WIDTH: usize = 13;
+
+// The escape sequences used to mark the current slot (reverse video), dim the
+// slots which have passed, and reset the style
+const CURRENT: &str = "\x1b[7m";
+const PAST: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// A source of the current (local) date and time, used by
+/// [`Timetable::render_week_now`].
+///
+/// # Remarks
+///
+/// The crate implements `Clock` for [`SystemClock`] (the time of the system)
+/// and [`NaiveDateTime`] (a fixed time, e.g., for testing).
+pub trait Clock {
+    /// Retrieves the current local date and time.
+    fn now(&self) -> NaiveDateTime;
+}
+
+/// The [`Clock`] of the system (in the local timezone of the system).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
+
+impl Clock for NaiveDateTime {
+    fn now(&self) -> NaiveDateTime {
+        *self
+    }
+}
+
+// The state of a slot relative to the current time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Past,
+    Current,
+    Upcoming,
+}
+
+impl Timetable {
+    /// Renders the current week of the timetable as a grid which fits an
+    /// 80 by 24 terminal (e.g., for the message of the day of a shared
+    /// terminal).
+    ///
+    /// The current date and time are retrieved from the `clock` provided,
+    /// and the week is determined using the `cycle` provided. The activities
+    /// of each day are those which actually take place (*see
+    /// [`Self::occurrences`]*) -- the current slot is marked (with a `>`, in
+    /// reverse video), and the slots which have passed are dimmed.
+    ///
+    /// # Remarks
+    ///
+    /// The times of each slot are determined using the
+    /// [`BellSchedule`](crate::BellSchedule) of the school at which its
+    /// activity takes place (slots without a location use the Highfield
+    /// school's schedule), such that a lesson at Fearnhill is marked from
+    /// the time it starts at Fearnhill.
+    ///
+    /// Saturdays and Sundays belong to the week which precedes them (*see
+    /// [`WeekCycle::week_for`]*) -- a weekend renders the week which has
+    /// just passed, with every slot dimmed.
+    ///
+    /// The cells of an activity spanning multiple periods (e.g., a double
+    /// lesson) are merged, such that its name and room are only shown for
+    /// its first period.
+    pub fn render_week_now<C: Clock + ?Sized>(&self, cycle: &WeekCycle, clock: &C) -> String {
+        let now = clock.now();
+        let today = now.date();
+        let schedules = cycle.calendar().bell_schedules_on(today);
+
+        let dates: Vec<NaiveDate> = (0..5)
+            .map(|offset| monday_of(today) + Duration::days(offset))
+            .collect();
+        let occurrences: Vec<Vec<Occurrence>> = dates
+            .iter()
+            .map(|date| self.occurrences(cycle, *date))
+            .collect();
+
+        let mut output = match cycle.week_for(today) {
+            Some(Week::WeekOne) => String::from("Week 1"),
+            Some(Week::WeekTwo) => String::from("Week 2"),
+            None => String::from("Holiday"),
+        };
+
+        let _ = write!(output, " -- {}", now.format("%A %d %B, %H:%M"));
+
+        if let Some(period) = schedules.get(School::Highfield).period_at(now.time()) {
+            let _ = write!(output, " (period {})", period as usize + 1);
+        }
+
+        output.push('\n');
+        output.push_str(&pad("", LABEL_WIDTH));
+
+        for date in &dates {
+            let header = pad(&date.format(" %a %d %b").to_string(), DAY_WIDTH);
+
+            output.push('|');
+
+            if *date == today {
+                output.push_str(&styled(&header, CURRENT));
+            } else {
+                output.push_str(&header);
+            }
+        }
+
+        output.push('\n');
+
+        // Whether the first activity of a slot continues the activity of the
+        // previous period (i.e., whether its cell is merged with the cell
+        // above it)
+        let continues = |occurrences: &[Occurrence], period: Period| {
+            occurrences
+                .iter()
+                .find(|occurrence| occurrence.slot.period == period)
+                .and_then(|occurrence| self.get(occurrence.entry?))
+                .is_some_and(|entry| entry.slot.period < period)
+        };
+
+        for period in Period::ALL {
+            let label = match period {
+                Period::First => "",
+                Period::Third => "Break",
+                Period::Fifth => "Lunch",
+                _ => "-",
+            };
+            let merged: Vec<bool> = occurrences
+                .iter()
+                .map(|occurrences| continues(occurrences, period))
+                .collect();
+
+            output.push_str(&separator(label, &merged));
+
+            let start = schedules.get(School::Highfield).start(period);
+            let mut lines = [
+                pad(&format!("P{}", period as usize + 1), LABEL_WIDTH),
+                pad(&start.to_string(), LABEL_WIDTH),
+            ];
+
+            for ((date, occurrences), merged) in dates.iter().zip(&occurrences).zip(&merged) {
+                let slot: Vec<&Occurrence> = occurrences
+                    .iter()
+                    .filter(|occurrence| occurrence.slot.period == period)
+                    .collect();
+
+                let school = slot
+                    .first()
+                    .and_then(|occurrence| occurrence.activity.location())
+                    .map_or(School::Highfield, Location::school);
+                let (start, end) = cycle
+                    .calendar()
+                    .bell_schedules_on(*date)
+                    .get(school)
+                    .period(period)
+                    .to_naive();
+
+                let state = if *date < today || (*date == today && now.time() >= end) {
+                    State::Past
+                } else if *date == today && now.time() >= start {
+                    State::Current
+                } else {
+                    State::Upcoming
+                };
+
+                let marker = if state == State::Current { '>' } else { ' ' };
+                let (title, detail) = match slot.first() {
+                    Some(_) if *merged => (String::new(), String::new()),
+                    Some(occurrence) => describe(&occurrence.activity, slot.len() - 1),
+                    None => (String::new(), String::new()),
+                };
+
+                let cells = [
+                    pad(&format!("{}{}", marker, title), DAY_WIDTH),
+                    pad(&format!(" {}", detail), DAY_WIDTH),
+                ];
+
+                for (line, cell) in lines.iter_mut().zip(cells) {
+                    line.push('|');
+
+                    match state {
+                        State::Current => line.push_str(&styled(&cell, CURRENT)),
+                        State::Past if !slot.is_empty() => line.push_str(&styled(&cell, PAST)),
+                        _ => line.push_str(&cell),
+                    }
+                }
+            }
+
+            for line in lines {
+                output.push_str(&line);
+                output.push('\n');
+            }
+        }
+
+        output.push_str(&separator("", &[]));
+        output
+    }
+
+    /// Renders the activities which take place on the `date` provided as an
+    /// agenda (e.g., `P1 08:50 Maths – H201 – JSM`), for plain-text outputs
+    /// such as SMS messages, e-ink displays, and daily emails.
+    ///
+    /// The activities are those which actually take place (*see
+    /// [`Self::occurrences`]*), and their times are determined using the
+    /// [`BellSchedule`](crate::BellSchedule) of the school at which they take
+    /// place. Between consecutive lessons at different sites, or in
+    /// different blocks (or sections) of the same site, a hint describes
+    /// where to go next (e.g., `-> travel to Fearnhill (15 minutes)`).
+    ///
+    /// An activity spanning multiple periods is rendered once, with the range
+    /// of its periods (e.g., `P3-P4 11:10 Science – H201`).
+    pub fn render_day(&self, cycle: &WeekCycle, date: NaiveDate) -> String {
+        let schedules = cycle.calendar().bell_schedules_on(date);
+        let occurrences = self.occurrences(cycle, date);
+
+        let mut output = date.format("%A %d %B\n").to_string();
+
+        if occurrences.is_empty() {
+            output.push_str("No activities\n");
+            return output;
+        }
+
+        // Merge the occurrences of each entry during consecutive periods
+        let mut spans: Vec<(&Occurrence, Period)> = Vec::new();
+
+        for occurrence in &occurrences {
+            let span = spans.iter_mut().find(|(first, last)| {
+                first.entry.is_some()
+                    && first.entry == occurrence.entry
+                    && first.activity == occurrence.activity
+                    && last.next() == Some(occurrence.slot.period)
+            });
+
+            match span {
+                Some((_, last)) => *last = occurrence.slot.period,
+                None => spans.push((occurrence, occurrence.slot.period)),
+            }
+        }
+
+        // The location and the end of the latest lesson
+        let mut previous: Option<(Location, TimeOfDay)> = None;
+
+        for (occurrence, last) in spans {
+            let location = occurrence.activity.location().copied();
+            let school = location.map_or(School::Highfield, |location| location.school());
+            let start = schedules.get(school).start(occurrence.slot.period);
+            let end = schedules.get(school).end(last);
+
+            if let (Some((from, ended)), Some(to)) = (previous, location) {
+                if let Some(hint) = travel_hint(&from, &to, start - ended) {
+                    let _ = writeln!(output, "   -> {}", hint);
+                }
+            }
+
+            let _ = write!(output, "P{}", occurrence.slot.period as usize + 1);
+
+            if last != occurrence.slot.period {
+                let _ = write!(output, "-P{}", last as usize + 1);
+            }
+
+            let _ = write!(output, " {} ", start);
+
+            let _ = match &occurrence.activity {
+                Activity::Lesson {
+                    subject,
+                    teacher: Some(teacher),
+                    location,
+                    ..
+                } => writeln!(output, "{} – {} – {}", subject, location, teacher.code()),
+                Activity::Lesson {
+                    subject, location, ..
+                } => writeln!(output, "{} – {}", subject, location),
+                activity => writeln!(output, "{}", activity),
+            };
+
+            if let Some(location) = location {
+                previous = Some((location, end));
+            }
+        }
+
+        output
+    }
+}
+
+// Describe where to go between two consecutive lessons (should they be at
+// different sites, or in different areas of the same site)
+pub(crate) fn travel_hint(from: &Location, to: &Location, available: Duration) -> Option<String> {
+    if [from, to]
+        .iter()
+        .any(|location| location.kind() == RoomKind::Remote)
+    {
+        return None;
+    }
+
+    if from.site() != to.site() {
+        return Some(format!(
+            "travel to {} ({} minutes)",
+            to.site(),
+            available.num_minutes()
+        ));
+    }
+
+    let area = |location: &Location| {
+        let parts = location.parts();
+
+        parts
+            .block
+            .map(|block| format!("{} Block", block.full_name()))
+            .or_else(|| {
+                parts
+                    .section
+                    .map(|section| format!("{} section", section.full_name()))
+            })
+            .or_else(|| parts.named.map(str::to_string))
+    };
+
+    let destination = area(to)?;
+    (area(from).as_ref() != Some(&destination)).then(|| format!("walk to {}", destination))
+}
+
+// Describe an activity in two lines of a cell: its name (noting the number
+// of other activities of the slot), and its room and teacher
+fn describe(activity: &Activity, others: usize) -> (String, String) {
+    let width = DAY_WIDTH - 1;
+
+    let mut title = match activity {
+        Activity::Lesson { subject, .. } => subject.name().clone(),
+        Activity::Registration => String::from("Registration"),
+        Activity::Break => String::from("Break"),
+        Activity::SchoolStudy => String::from("Study"),
+        Activity::HomeStudy => String::from("Home study"),
+        Activity::Miscellaneous(name) => name.clone(),
+        Activity::Transit { .. } => String::from("Transit"),
+    };
+
+    if others > 0 {
+        let suffix = format!(" +{}", others);
+        let kept = width.saturating_sub(suffix.chars().count());
+
+        title = title.chars().take(kept).collect::<String>() + &suffix;
+    }
+
+    let detail = match activity {
+        Activity::Lesson {
+            teacher: Some(teacher),
+            location,
+            ..
+        } => {
+            let room = width
+                .saturating_sub(teacher.code().chars().count() + 1)
+                .max(4);
+            format!("{} {}", location.format_abbrev(room), teacher.code())
+        }
+        Activity::Lesson { location, .. } => location.format_abbrev(width),
+        Activity::Transit { to, .. } => format!("-> {}", to.format_abbrev(width - 3)),
+        _ => String::new(),
+    };
+
+    (title, detail)
+}
+
+// Truncate or pad the text provided to the width provided
+fn pad(text: &str, width: usize) -> String {
+    let mut padded: String = text.chars().take(width).collect();
+    let length = padded.chars().count();
+
+    padded.extend(std::iter::repeat_n(' ', width - length));
+    padded
+}
+
+// Wrap the text provided in the style provided
+fn styled(text: &str, style: &str) -> String {
+    format!("{}{}{}", style, text, RESET)
+}
+
+// Create the line separating two periods (labelled, e.g., with `Break`),
+// omitting the lines of the days whose cells are merged
+fn separator(label: &str, merged: &[bool]) -> String {
+    let mut line = pad(label, LABEL_WIDTH).replace(' ', "-");
+
+    for day in 0..5 {
+        let fill = if merged.get(day) == Some(&true) {
+            " "
+        } else {
+            "-"
+        };
+
+        line.push('+');
+        line.push_str(&fill.repeat(DAY_WIDTH));
+    }
+
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Subject, Teacher, Term};
+
+    #[test]
+    fn render_week_now_fits_terminal() {
+        let lesson = |subject: &str, room: &str| Activity::Lesson {
+            subject: Subject::new(subject.to_string()).unwrap(),
+            class: Class::new("12A".to_string()).unwrap(),
+            teacher: Teacher::new("JSM".to_string()).ok(),
+            location: room.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("Mathematics", "H201"));
+        timetable.insert(timeslot!(W1TP2), lesson("Physics", "FH S12"));
+        timetable.insert(timeslot!(W1TP3), lesson("Chemistry", "H202"));
+        timetable.insert(timeslot!(W1TP3), Activity::SchoolStudy);
+
+        let monday = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(
+            monday,
+            NaiveDate::from_ymd_opt(2023, 10, 20).unwrap(),
+        )
+        .unwrap()]);
+        let cycle = WeekCycle::new(monday, calendar).unwrap();
+
+        // Tuesday, during the second period at Fearnhill (09:45 to 10:45) --
+        // but before the second period at Highfield has started
+        let now = NaiveDate::from_ymd_opt(2023, 9, 5)
+            .unwrap()
+            .and_hms_opt(9, 47, 0)
+            .unwrap();
+        let output = timetable.render_week_now(&cycle, &now);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines.len() <= 24);
+        assert_eq!(lines[0], "Week 1 -- Tuesday 05 September, 09:47 (period 1)");
+
+        for line in &lines {
+            let visible = line
+                .replace(CURRENT, "")
+                .replace(PAST, "")
+                .replace(RESET, "");
+
+            assert!(visible.chars().count() <= 80, "{}", line);
+        }
+
+        // The lesson on Monday has passed, and the lesson at Fearnhill is in
+        // progress
+        let first = lines.iter().find(|line| line.starts_with("P1")).unwrap();
+        let second = lines.iter().find(|line| line.starts_with("P2")).unwrap();
+        let third = lines.iter().find(|line| line.starts_with("P3")).unwrap();
+
+        assert!(first.contains(&format!("{} Mathematics {}", PAST, RESET)));
+        assert!(second.contains(&format!("{}>Physics     {}", CURRENT, RESET)));
+        assert!(third.contains("| Chemistry +1|"));
+    }
+
+    #[test]
+    fn render_day_travel_hints() {
+        let lesson = |subject: &str, room: &str, teacher: Option<&str>| Activity::Lesson {
+            subject: Subject::new(subject.to_string()).unwrap(),
+            class: Class::new("12A".to_string()).unwrap(),
+            teacher: teacher.map(|code| Teacher::new(code.to_string()).unwrap()),
+            location: room.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("Maths", "H201", Some("JSM")));
+        timetable.insert(timeslot!(W1MP2), lesson("Maths", "H203", Some("JSM")));
+        timetable.insert(timeslot!(W1MP3), lesson("Physics", "U101", None));
+        timetable.insert(timeslot!(W1MP4), Activity::SchoolStudy);
+        timetable.insert(timeslot!(W1MP5), lesson("Music", "FH S12", Some("ABC")));
+
+        let monday = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(
+            monday,
+            NaiveDate::from_ymd_opt(2023, 10, 20).unwrap(),
+        )
+        .unwrap()]);
+        let cycle = WeekCycle::new(monday, calendar).unwrap();
+
+        assert_eq!(
+            timetable.render_day(&cycle, monday),
+            "Monday 04 September\n\
+             P1 08:50 Maths – H201 – JSM\n\
+             P2 09:50 Maths – H203 – JSM\n\
+             \x20  -> walk to Unwin Block\n\
+             P3 11:10 Physics – U101\n\
+             P4 12:10 Independent Study\n\
+             \x20  -> travel to Fearnhill (100 minutes)\n\
+             P5 13:50 Music – FH S12 – ABC\n"
+        );
+        assert_eq!(
+            timetable.render_day(&cycle, monday + Duration::days(5)),
+            "Saturday 09 September\nNo activities\n"
+        );
+    }
+
+    #[test]
+    fn render_merges_spans() {
+        let mut timetable = Timetable::new();
+        timetable
+            .insert_spanning(
+                timeslot!(W1MP3),
+                2,
+                Activity::Lesson {
+                    subject: Subject::new("Science".to_string()).unwrap(),
+                    class: Class::new("10A".to_string()).unwrap(),
+                    teacher: Teacher::new("JSM".to_string()).ok(),
+                    location: "H201".parse().unwrap(),
+                },
+            )
+            .unwrap();
+        timetable.insert(timeslot!(W1MP5), Activity::SchoolStudy);
+
+        let monday = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(
+            monday,
+            NaiveDate::from_ymd_opt(2023, 10, 20).unwrap(),
+        )
+        .unwrap()]);
+        let cycle = WeekCycle::new(monday, calendar).unwrap();
+
+        assert_eq!(
+            timetable.render_day(&cycle, monday),
+            "Monday 04 September\n\
+             P3-P4 11:10 Science – H201 – JSM\n\
+             P5 13:55 Independent Study\n"
+        );
+
+        // The line between the periods of the double lesson is omitted
+        let now = monday.and_hms_opt(8, 0, 0).unwrap();
+        let output = timetable.render_week_now(&cycle, &now);
+        let lines: Vec<&str> = output.lines().collect();
+        let fourth = lines
+            .iter()
+            .position(|line| line.starts_with("P4"))
+            .unwrap();
+
+        assert!(lines[fourth - 1].starts_with(&format!("------+{}+---", " ".repeat(DAY_WIDTH))));
+        assert!(lines[fourth].starts_with(&format!("P4    |{}|", " ".repeat(DAY_WIDTH))));
+        assert!(lines[fourth - 3].contains("| Science     |"));
+    }
+}
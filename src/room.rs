@@ -0,0 +1,290 @@
+use crate::{
+    FearnhillRoom, FearnhillSection, HighfieldBlock, HighfieldFloor, HighfieldRoom, Location,
+    School,
+};
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// The kind of a [`Room`], used by analyses which treat rooms differently
+/// depending upon what they are used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum RoomKind {
+    /// A classroom (i.e., a room in which most lessons take place).
+    Classroom,
+
+    /// A laboratory in which practical lessons can take place (i.e., a
+    /// classroom in the Science section of the Fearnhill school).
+    Lab,
+
+    /// A hall in which assemblies can be held.
+    Hall,
+
+    /// A room used for P.E. (e.g., a sports hall or a gym).
+    Sports,
+
+    /// A studio used for the performing arts (e.g., a dance studio).
+    Studio,
+
+    /// An outdoor space used for P.E. (e.g., a field or tennis courts).
+    Outdoor,
+
+    /// A location away from the sites of both schools (*see
+    /// [`OffSite`](crate::OffSite)*).
+    OffSite,
+
+    /// An online lesson, which occupies no room -- rooms of this kind never
+    /// clash (*see [`Remote`](crate::Remote)*).
+    Remote,
+
+    /// A room which is yet to be decided (e.g., in a draft timetable) --
+    /// rooms of this kind never clash, but are reported as still needed
+    /// (*see [`RoomsNeeded`](crate::validate::RoomsNeeded)*).
+    ToBeConfirmed,
+
+    /// Any other kind of room.
+    Other,
+}
+
+impl RoomKind {
+    /// Whether the lessons taking place in a room of the `RoomKind` occupy
+    /// it (i.e., whether two lessons in the same room during the same period
+    /// clash) -- neither [remote](Self::Remote) lessons nor lessons whose
+    /// room is [yet to be confirmed](Self::ToBeConfirmed) occupy a room.
+    pub fn is_occupied(self) -> bool {
+        !matches!(self, Self::Remote | Self::ToBeConfirmed)
+    }
+}
+
+/// A room in which a lesson can take place.
+///
+/// The timetabling machinery of the crate (e.g., a
+/// [`Timetable`](crate::Timetable), its [queries](crate::Lessons), and the
+/// [`NoClashes`](crate::validate::NoClashes) rule) is generic over the rooms
+/// it schedules, such that it can be reused by a school with its own room
+/// numbering scheme -- [`Location`] (the default) covers the rooms of the
+/// Highfield school and the Fearnhill school.
+///
+/// A room's identifier is produced by its [`Display`] implementation and
+/// parsed by its [`FromStr`] implementation (which must accept the
+/// identifier produced by `Display`).
+///
+/// # Remarks
+///
+/// The parts of the crate which depend upon the schedules of the Highfield
+/// school and the Fearnhill school (e.g., occurrences, travel times, and the
+/// exporters) remain specific to [`Location`].
+///
+/// ```
+/// use std::fmt::{self, Display, Formatter};
+/// use std::str::FromStr;
+/// use timetableau::{Room, RoomKind, Timetable};
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// struct BarclayRoom(u8);
+///
+/// impl Display for BarclayRoom {
+///     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+///         write!(f, "BA{}", self.0)
+///     }
+/// }
+///
+/// impl FromStr for BarclayRoom {
+///     type Err = ();
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         let number = s.strip_prefix("BA").ok_or(())?;
+///         number.parse().map(Self).map_err(|_| ())
+///     }
+/// }
+///
+/// impl Room for BarclayRoom {
+///     fn kind(&self) -> RoomKind {
+///         RoomKind::Classroom
+///     }
+///
+///     fn site(&self) -> &str {
+///         "Barclay"
+///     }
+/// }
+///
+/// let timetable = Timetable::<BarclayRoom>::default();
+///
+/// assert_eq!(timetable.lessons().at_site("Barclay").count(), 0);
+/// ```
+pub trait Room: Clone + Eq + Hash + Debug + Display + FromStr {
+    /// Retrieves the [`RoomKind`] of the room.
+    fn kind(&self) -> RoomKind;
+
+    /// Retrieves the name of the site (i.e., the school, or the
+    /// [off-site location](crate::OffSite)) at which the room is located.
+    fn site(&self) -> &str;
+}
+
+impl Room for HighfieldRoom {
+    fn kind(&self) -> RoomKind {
+        match self {
+            Self::Hall => RoomKind::Hall,
+            Self::SportsHall => RoomKind::Sports,
+            Self::Field | Self::AstroPitch | Self::TennisCourts | Self::Playground => {
+                RoomKind::Outdoor
+            }
+            Self::Classroom { .. } | Self::Temporary(_) => RoomKind::Classroom,
+            Self::OffSite(_) => RoomKind::OffSite,
+            Self::Remote(_) => RoomKind::Remote,
+            Self::ToBeConfirmed => RoomKind::ToBeConfirmed,
+            #[cfg(feature = "other-rooms")]
+            Self::Other(_) => RoomKind::Other,
+        }
+    }
+
+    fn site(&self) -> &str {
+        match self {
+            Self::OffSite(site) => site.name(),
+            _ => "Highfield",
+        }
+    }
+}
+
+impl Room for FearnhillRoom {
+    fn kind(&self) -> RoomKind {
+        match self {
+            Self::SportsHall | Self::Gym => RoomKind::Sports,
+            Self::DanceStudio | Self::DramaStudio => RoomKind::Studio,
+            Self::Field | Self::AstroPitch | Self::TennisCourts | Self::Playground => {
+                RoomKind::Outdoor
+            }
+            Self::Classroom {
+                section: FearnhillSection::Science,
+                ..
+            } => RoomKind::Lab,
+            Self::Classroom { .. } => RoomKind::Classroom,
+            Self::OffSite(_) => RoomKind::OffSite,
+            Self::Remote(_) => RoomKind::Remote,
+            Self::ToBeConfirmed => RoomKind::ToBeConfirmed,
+            #[cfg(feature = "other-rooms")]
+            Self::Other(_) => RoomKind::Other,
+        }
+    }
+
+    fn site(&self) -> &str {
+        match self {
+            Self::OffSite(site) => site.name(),
+            _ => "Fearnhill",
+        }
+    }
+}
+
+impl Room for Location {
+    fn kind(&self) -> RoomKind {
+        match self {
+            Self::Highfield(room) => room.kind(),
+            Self::Fearnhill(room) => room.kind(),
+        }
+    }
+
+    fn site(&self) -> &str {
+        match self {
+            Self::Highfield(room) => room.site(),
+            Self::Fearnhill(room) => room.site(),
+        }
+    }
+}
+
+impl Location {
+    /// Creates an iterator over every `Location` which can be represented by
+    /// the room numbering schemes (whether or not the room exists -- *see
+    /// [`LocationFilter::existing`]*), in the order of their packed
+    /// representations.
+    ///
+    /// The rooms can be searched using the combinators of
+    /// [`LocationFilter`]:
+    ///
+    /// ```
+    /// use timetableau::{HighfieldBlock, HighfieldFloor, Location, LocationFilter};
+    ///
+    /// let rooms: Vec<Location> = Location::all()
+    ///     .in_block(HighfieldBlock::Unwin)
+    ///     .on_floor(HighfieldFloor::Ground)
+    ///     .existing()
+    ///     .collect();
+    ///
+    /// assert_eq!(rooms.len(), 8);
+    /// ```
+    pub fn all() -> impl Iterator<Item = Location> {
+        (1..=u16::MAX).filter_map(|packed| Location::try_from(packed).ok())
+    }
+}
+
+/// Combinators which filter an iterator of [`Location`]s (e.g.,
+/// [`Location::all`]), such that room searches can be expressed without
+/// matching the room enumerations.
+pub trait LocationFilter: Iterator<Item = Location> + Sized {
+    /// Only yields the rooms at the `school` provided.
+    fn at(self, school: School) -> impl Iterator<Item = Location> {
+        self.filter(move |location| location.school() == school)
+    }
+
+    /// Only yields the rooms in the Highfield `block` provided.
+    fn in_block(self, block: HighfieldBlock) -> impl Iterator<Item = Location> {
+        self.filter(move |location| location.parts().block == Some(block))
+    }
+
+    /// Only yields the rooms in the Fearnhill `section` provided.
+    fn in_section(self, section: FearnhillSection) -> impl Iterator<Item = Location> {
+        self.filter(move |location| location.parts().section == Some(section))
+    }
+
+    /// Only yields the rooms on the Highfield `floor` provided.
+    fn on_floor(self, floor: HighfieldFloor) -> impl Iterator<Item = Location> {
+        self.filter(move |location| location.parts().floor == Some(floor))
+    }
+
+    /// Only yields the rooms of the `kind` provided.
+    fn of_kind(self, kind: RoomKind) -> impl Iterator<Item = Location> {
+        self.filter(move |location| location.kind() == kind)
+    }
+
+    /// Only yields the rooms which exist (*see [`Location::exists`]*).
+    fn existing(self) -> impl Iterator<Item = Location> {
+        self.filter(Location::exists)
+    }
+}
+
+impl<I> LocationFilter for I where I: Iterator<Item = Location> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn room_kind_and_site() {
+        let gym: Location = "FH Gym".parse().unwrap();
+        let classroom: Location = "H201".parse().unwrap();
+
+        assert_eq!(gym.kind(), RoomKind::Sports);
+        assert_eq!(gym.site(), "Fearnhill");
+        assert_eq!(classroom.kind(), RoomKind::Classroom);
+        assert_eq!(classroom.site(), "Highfield");
+    }
+
+    #[test]
+    fn location_filters() {
+        assert_eq!(Location::all().count(), 3 * 10 * 99 + 6 + 99 + 10 * 99 + 8);
+        assert_eq!(
+            Location::all()
+                .at(School::Fearnhill)
+                .of_kind(RoomKind::Lab)
+                .count(),
+            99
+        );
+        assert_eq!(
+            Location::all()
+                .in_section(FearnhillSection::Science)
+                .existing()
+                .count(),
+            14
+        );
+    }
+}
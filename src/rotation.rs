@@ -0,0 +1,192 @@
+use crate::{AcademicCalendar, Activity, Location, Teacher};
+use chrono::NaiveDate;
+
+/// The teacher and room with which a rotating lesson takes place during a
+/// single stage of its [`Rotation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotationStage<L = Location> {
+    /// The teacher of the lesson during the stage, or [`None`] should the
+    /// teacher not be known.
+    pub teacher: Option<Teacher>,
+
+    /// The location of the lesson during the stage.
+    pub location: L,
+}
+
+/// The rotation of a lesson between teachers and rooms (e.g., a half-termly
+/// carousel, in which the groups of DT and Art rotate between the workshops
+/// and the art studios).
+///
+/// A rotation consists of an ordered list of [`RotationStage`]s -- the first
+/// stage applies from the `start` of the rotation, and the lesson switches
+/// to the next stage at the start of each subsequent [`Term`](crate::Term)
+/// of the academic calendar (returning to the first stage after the last):
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use timetableau::{AcademicCalendar, Rotation, Teacher, Term};
+///
+/// let date = |month, day| NaiveDate::from_ymd_opt(2023, month, day).unwrap();
+/// let calendar = AcademicCalendar::new(vec![
+///     Term::new(date(9, 4), date(10, 20)).unwrap(),
+///     Term::new(date(10, 30), date(12, 19)).unwrap(),
+/// ]);
+///
+/// let rotation: Rotation = Rotation::new(date(9, 4))
+///     .stage(Some(Teacher::new("JSM".to_string()).unwrap()), "FH T1".parse().unwrap())
+///     .stage(Some(Teacher::new("ABC".to_string()).unwrap()), "FH T2".parse().unwrap());
+///
+/// assert_eq!(rotation.switches(&calendar).collect::<Vec<_>>(), [date(10, 30)]);
+///
+/// let stage = rotation.stage_on(&calendar, date(11, 6)).unwrap();
+/// assert_eq!(stage.location.to_string(), "FH T2");
+/// ```
+///
+/// A rotation is scheduled as a part of the [`Entry`](crate::Entry) of its
+/// lesson (*see [`Entry::rotation`](crate::Entry::rotation)*) -- the lesson
+/// of the entry's activity is the lesson as scheduled, whereas the lesson
+/// which actually takes place on a given date is the [effective
+/// activity](crate::Entry::activity_on) of the entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rotation<L = Location> {
+    /// The date from which the first stage applies.
+    pub start: NaiveDate,
+
+    /// The stages of the rotation (in the order in which they apply).
+    pub stages: Vec<RotationStage<L>>,
+}
+
+impl<L> Rotation<L> {
+    /// Creates a new `Rotation` starting on the `start` date provided, which
+    /// has no stages.
+    pub fn new(start: NaiveDate) -> Self {
+        Self {
+            start,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Adds a stage with the `teacher` and `location` provided to the end of
+    /// the `Rotation`.
+    pub fn stage(mut self, teacher: Option<Teacher>, location: L) -> Self {
+        self.stages.push(RotationStage { teacher, location });
+        self
+    }
+
+    /// Retrieves the dates on which the `Rotation` switches to its next stage
+    /// (i.e., the first day of each term of the `calendar` which starts after
+    /// the rotation), in chronological order.
+    pub fn switches<'a>(
+        &'a self,
+        calendar: &'a AcademicCalendar,
+    ) -> impl Iterator<Item = NaiveDate> + 'a {
+        calendar
+            .terms()
+            .iter()
+            .map(|term| term.start())
+            .filter(move |start| *start > self.start)
+    }
+
+    /// Retrieves the [`RotationStage`] which applies on the `date` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should the `Rotation` have no stages, or should the `date`
+    /// precede its start (in which case the lesson takes place as
+    /// scheduled).
+    pub fn stage_on(
+        &self,
+        calendar: &AcademicCalendar,
+        date: NaiveDate,
+    ) -> Option<&RotationStage<L>> {
+        if date < self.start || self.stages.is_empty() {
+            return None;
+        }
+
+        let switches = self.switches(calendar).filter(|switch| *switch <= date);
+
+        self.stages.get(switches.count() % self.stages.len())
+    }
+}
+
+impl<L: Clone> Rotation<L> {
+    // Apply the stage which applies on the date provided to the activity
+    // provided (should it be a lesson)
+    pub(crate) fn apply(
+        &self,
+        activity: &mut Activity<L>,
+        calendar: &AcademicCalendar,
+        date: NaiveDate,
+    ) {
+        let Some(stage) = self.stage_on(calendar, date) else {
+            return;
+        };
+
+        if let Activity::Lesson {
+            teacher, location, ..
+        } = activity
+        {
+            teacher.clone_from(&stage.teacher);
+            location.clone_from(&stage.location);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Term;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, month, day).unwrap()
+    }
+
+    #[test]
+    fn rotation_stages() {
+        let calendar = AcademicCalendar::new(vec![
+            Term::new(date(1, 4), date(2, 10)).unwrap(),
+            Term::new(date(2, 20), date(3, 31)).unwrap(),
+            Term::new(date(4, 17), date(5, 26)).unwrap(),
+            Term::new(date(6, 5), date(7, 21)).unwrap(),
+        ]);
+        let rotation: Rotation = Rotation::new(date(2, 20))
+            .stage(None, "FH T1".parse().unwrap())
+            .stage(None, "FH T2".parse().unwrap())
+            .stage(None, "FH T3".parse().unwrap());
+
+        let room = |month, day| {
+            rotation
+                .stage_on(&calendar, date(month, day))
+                .map(|stage| stage.location.to_string())
+        };
+
+        // The switches are the starts of the terms following the start
+        assert_eq!(
+            rotation.switches(&calendar).collect::<Vec<_>>(),
+            [date(4, 17), date(6, 5)]
+        );
+
+        assert_eq!(room(1, 9), None);
+        assert_eq!(room(2, 20).as_deref(), Some("FH T1"));
+        assert_eq!(room(4, 16).as_deref(), Some("FH T1"));
+        assert_eq!(room(4, 17).as_deref(), Some("FH T2"));
+        assert_eq!(room(7, 21).as_deref(), Some("FH T3"));
+
+        // The rotation returns to its first stage after its last
+        let mut autumn = calendar.clone();
+        autumn.add_term(Term::new(date(9, 4), date(10, 20)).unwrap());
+        assert_eq!(
+            rotation
+                .stage_on(&autumn, date(9, 4))
+                .unwrap()
+                .location
+                .to_string(),
+            "FH T1"
+        );
+
+        assert_eq!(
+            Rotation::<Location>::new(date(1, 4)).stage_on(&calendar, date(1, 9)),
+            None
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{Activity, Location, LocationParts, Period, Timetable};
+use std::collections::BTreeMap;
+
+// The first and last periods (and location) of a lesson
+type Lesson = (Period, Period, Location);
+
+/// The weights with which [`Timetable::score`] rewards (and penalises) the
+/// arrangement of the rooms of a timetable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoringWeights {
+    /// The reward for each class which stays in the same room from one
+    /// lesson to the next lesson of the same day.
+    pub same_room: i64,
+
+    /// The penalty for each teacher who moves to another block (or section,
+    /// or named room such as the hall) from one lesson to the next lesson of
+    /// the same day.
+    pub block_hop: i64,
+
+    /// The additional penalty for each teacher who moves to the other school
+    /// from one lesson to the next lesson of the same day.
+    pub cross_site: i64,
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        Self {
+            same_room: 1,
+            block_hop: 2,
+            cross_site: 10,
+        }
+    }
+}
+
+impl Timetable {
+    /// Scores the rooms of the `Timetable` using the `weights` provided, so
+    /// that candidate timetables can be compared numerically -- the higher
+    /// the score, the better the timetable.
+    ///
+    /// Consecutive lessons of a class in the same room are rewarded, and
+    /// consecutive lessons of a teacher in different blocks are penalised
+    /// (*see [`ScoringWeights`] for more information*):
+    ///
+    /// ```
+    /// use timetableau::{timeslot, Activity, Class, ScoringWeights, Subject, Teacher, Timetable};
+    ///
+    /// let lesson = |class: &str, room: &str| Activity::Lesson {
+    ///     subject: Subject::new("Ma".to_string()).unwrap(),
+    ///     class: Class::new(class.to_string()).unwrap(),
+    ///     teacher: Some(Teacher::new("ABC".to_string()).unwrap()),
+    ///     location: room.parse().unwrap(),
+    /// };
+    ///
+    /// let mut sticky = Timetable::new();
+    /// sticky.insert(timeslot!(W1MP1), lesson("10A", "H201"));
+    /// sticky.insert(timeslot!(W1MP2), lesson("10A", "H201"));
+    ///
+    /// let mut hopping = Timetable::new();
+    /// hopping.insert(timeslot!(W1MP1), lesson("10A", "H201"));
+    /// hopping.insert(timeslot!(W1MP2), lesson("10B", "U101"));
+    ///
+    /// let weights = ScoringWeights::default();
+    ///
+    /// assert!(sticky.score(&weights) > hopping.score(&weights));
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// Lessons are only consecutive should they take place during adjacent
+    /// periods of the same day -- a class which returns to the same room after
+    /// a free period is not rewarded.
+    pub fn score(&self, weights: &ScoringWeights) -> i64 {
+        // The lessons of each teacher and class on each day
+        let mut teachers: BTreeMap<(&str, usize), Vec<Lesson>> = BTreeMap::new();
+        let mut classes: BTreeMap<(&str, usize), Vec<Lesson>> = BTreeMap::new();
+
+        for (_, entry) in self.lessons() {
+            if let Activity::Lesson {
+                class, location, ..
+            } = &entry.activity
+            {
+                let day = entry.slot.index() / 5;
+                let last = entry.slots().last().unwrap_or(entry.slot).period;
+                let lesson = (entry.slot.period, last, *location);
+
+                // Co-teachers move between the lessons they teach as the
+                // teacher of the lesson does
+                let mut codes: Vec<&str> = entry
+                    .teachers()
+                    .map(|teacher| teacher.code().as_str())
+                    .collect();
+                codes.sort_unstable();
+                codes.dedup();
+
+                for code in codes {
+                    teachers.entry((code, day)).or_default().push(lesson);
+                }
+                classes
+                    .entry((class.reference(), day))
+                    .or_default()
+                    .push(lesson);
+            }
+        }
+
+        // The pairs of locations of consecutive lessons of a day (i.e., the
+        // lessons of adjacent periods, after the last period of a lesson
+        // spanning multiple periods)
+        let consecutive = |day: &mut Vec<Lesson>| {
+            day.sort_by_key(|(first, _, _)| *first);
+            day.windows(2)
+                .filter(|pair| pair[0].1.next() == Some(pair[1].0))
+                .map(|pair| (pair[0].2, pair[1].2))
+                .collect::<Vec<_>>()
+        };
+
+        let mut score = 0;
+
+        for day in classes.values_mut() {
+            for (from, to) in consecutive(day) {
+                score += i64::from(from == to) * weights.same_room;
+            }
+        }
+
+        for day in teachers.values_mut() {
+            for (from, to) in consecutive(day) {
+                score -= i64::from(block(&from) != block(&to)) * weights.block_hop;
+                score -= i64::from(from.school() != to.school()) * weights.cross_site;
+            }
+        }
+
+        score
+    }
+}
+
+// Retrieve the parts of a location which identify its block (a named room,
+// such as the hall, is considered a block of its own)
+fn block(location: &Location) -> LocationParts {
+    LocationParts {
+        floor: None,
+        discriminator: None,
+        ..location.parts()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, Entry, Subject, Teacher};
+
+    fn lesson(class: &str, teacher: &str, room: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Some(Teacher::new(teacher.to_string()).unwrap()),
+            location: room.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn room_stickiness_score() {
+        let weights = ScoringWeights {
+            same_room: 1,
+            block_hop: 10,
+            cross_site: 100,
+        };
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("10A", "ABC", "H201"));
+        timetable.insert(timeslot!(W1MP2), lesson("10A", "ABC", "H201"));
+        timetable.insert(timeslot!(W1MP3), lesson("10A", "ABC", "H102"));
+
+        // A free period separates the lessons, so neither counts (and moving
+        // floors within a block is not a block hop)
+        timetable.insert(timeslot!(W1MP5), lesson("10A", "ABC", "H202"));
+        assert_eq!(timetable.score(&weights), 1);
+
+        timetable.insert(timeslot!(W1TP1), lesson("11B", "ABC", "H101"));
+        timetable.insert(timeslot!(W1TP2), lesson("11C", "ABC", "U101"));
+        timetable.insert(timeslot!(W1TP3), lesson("11D", "ABC", "FH S13"));
+        timetable.insert(timeslot!(W1TP4), lesson("11E", "ABC", "FH S14"));
+        assert_eq!(timetable.score(&weights), 1 - 10 - 110);
+
+        // Other weights reorder the same timetable
+        assert_eq!(timetable.score(&ScoringWeights::default()), 1 - 2 - 12);
+
+        // The lesson following a double follows its second period
+        let mut timetable = Timetable::new();
+        timetable
+            .insert_spanning(timeslot!(W1MP3), 2, lesson("10A", "ABC", "H201"))
+            .unwrap();
+        timetable.insert(timeslot!(W1MP5), lesson("10A", "ABC", "U101"));
+        assert_eq!(timetable.score(&weights), -10);
+
+        // A co-teacher is penalised for moving to their next lesson
+        let mut timetable = Timetable::new();
+        timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP1), lesson("10A", "ABC", "H201"))
+                    .co_teacher(Teacher::new("DEF".to_string()).unwrap()),
+            )
+            .unwrap();
+        timetable.insert(timeslot!(W1MP2), lesson("10B", "DEF", "FH S13"));
+        assert_eq!(timetable.score(&weights), -110);
+    }
+}
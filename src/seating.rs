@@ -0,0 +1,489 @@
+//! Seating plans of classes within classrooms.
+//!
+//! A [`SeatingPlan`] describes the layout of the desks of a [`Location`] and
+//! the seat of each student of a class within it. Plans are saved in a small
+//! TOML format (*see [`SeatingPlan::to_toml_string`]*), such that they can be
+//! version-controlled alongside the timetable, and can be
+//! [rendered](SeatingPlan::render) as a printable layout:
+//!
+//! ```
+//! use timetableau::seating::{Seat, SeatingPlan};
+//! use timetableau::Class;
+//!
+//! let mut plan = SeatingPlan::new(
+//!     "H201".parse().unwrap(),
+//!     Class::new("10A".to_string()).unwrap(),
+//!     "##.##\n##.##",
+//! )
+//! .unwrap();
+//!
+//! plan.assign("A1".parse().unwrap(), "Alice").unwrap();
+//! plan.assign("B4".parse().unwrap(), "Bob").unwrap();
+//!
+//! assert_eq!(plan.desks(), 8);
+//! assert_eq!(plan.seat_of("Bob"), Some("B4".parse::<Seat>().unwrap()));
+//! assert_eq!(SeatingPlan::from_toml_str(&plan.to_toml_string()), Ok(plan));
+//! ```
+
+use crate::definition::{location, write_string};
+use crate::toml::{self, TomlValue};
+use crate::{Class, Location, ParseOptions, TomlError, TomlErrorKind};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+use std::str::FromStr;
+
+// The width (in characters) of each desk of a rendered layout
+const DESK_WIDTH: usize = 10;
+
+/// A seat of a [`SeatingPlan`], identified by its row (a letter, starting
+/// from `A` at the front of the room) and its column (a number, starting from
+/// `1` at the left of the room as seen from the front) -- e.g., `B3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Seat {
+    /// The row of the seat (starting from `0` at the front of the room).
+    pub row: u8,
+
+    /// The column of the seat (starting from `0` at the left of the room).
+    pub column: u8,
+}
+
+impl Display for Seat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", (b'A' + self.row) as char, self.column as u16 + 1)
+    }
+}
+
+impl FromStr for Seat {
+    type Err = SeatingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SeatingError::InvalidSeat(s.to_string());
+        let mut chars = s.chars();
+
+        let row = match chars.next() {
+            Some(row @ 'A'..='Z') => row as u8 - b'A',
+            _ => return Err(invalid()),
+        };
+        let column: u16 = chars.as_str().parse().map_err(|_| invalid())?;
+
+        if !(1..=256).contains(&column) {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            row,
+            column: (column - 1) as u8,
+        })
+    }
+}
+
+/// An error returned when a [`SeatingPlan`] cannot be constructed, or a
+/// student cannot be seated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeatingError {
+    /// The layout was empty, had more than 26 rows (or 256 columns), or
+    /// contained a character other than `#` (a desk) or `.` (a gap).
+    InvalidLayout,
+
+    /// The label of a seat was invalid, or there is no desk at the seat.
+    InvalidSeat(String),
+
+    /// Another student is already seated at the seat.
+    Occupied(Seat),
+
+    /// The student is already seated elsewhere.
+    AlreadySeated(String),
+
+    /// The layout has more desks than the room can seat.
+    OverCapacity {
+        /// The number of desks of the layout.
+        desks: usize,
+
+        /// The number of students the room can seat.
+        capacity: u32,
+    },
+}
+
+impl Display for SeatingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLayout => f.write_str("invalid desk layout"),
+            Self::InvalidSeat(seat) => write!(f, "invalid seat `{}`", seat),
+            Self::Occupied(seat) => write!(f, "seat {} is already occupied", seat),
+            Self::AlreadySeated(student) => write!(f, "{} is already seated", student),
+            Self::OverCapacity { desks, capacity } => write!(
+                f,
+                "the layout has {} desks, but the room can only seat {}",
+                desks, capacity
+            ),
+        }
+    }
+}
+
+impl Error for SeatingError {}
+
+/// The seating plan of a class within a classroom.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeatingPlan {
+    location: Location,
+    class: Class,
+    // Invariant: every row has the same number of columns
+    layout: Vec<Vec<bool>>,
+    seats: BTreeMap<Seat, String>,
+}
+
+impl SeatingPlan {
+    /// Creates a new `SeatingPlan` (with no students seated) for the `class`
+    /// in the `location` provided.
+    ///
+    /// The `layout` has one line per row of desks (starting from the front
+    /// of the room), in which each `#` is a desk and each `.` is a gap (e.g.,
+    /// an aisle) -- shorter rows are padded with gaps.
+    ///
+    /// # Errors
+    ///
+    /// [`SeatingError::InvalidLayout`] is returned should the `layout` be
+    /// empty, have more than 26 rows or 256 columns, or contain any other
+    /// character.
+    pub fn new(location: Location, class: Class, layout: &str) -> Result<Self, SeatingError> {
+        let mut rows = layout
+            .lines()
+            .map(|line| {
+                line.trim()
+                    .chars()
+                    .map(|c| match c {
+                        '#' => Ok(true),
+                        '.' => Ok(false),
+                        _ => Err(SeatingError::InvalidLayout),
+                    })
+                    .collect::<Result<Vec<bool>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        if columns == 0 || columns > 256 || rows.len() > 26 {
+            return Err(SeatingError::InvalidLayout);
+        }
+
+        for row in &mut rows {
+            row.resize(columns, false);
+        }
+
+        Ok(Self {
+            location,
+            class,
+            layout: rows,
+            seats: BTreeMap::new(),
+        })
+    }
+
+    /// Retrieves the room of the `SeatingPlan`.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Retrieves the class of the `SeatingPlan`.
+    pub fn class(&self) -> &Class {
+        &self.class
+    }
+
+    /// Retrieves the number of desks of the layout.
+    pub fn desks(&self) -> usize {
+        self.layout.iter().flatten().filter(|desk| **desk).count()
+    }
+
+    /// Whether there is a desk at the `seat` provided.
+    pub fn has_desk(&self, seat: Seat) -> bool {
+        self.layout
+            .get(seat.row as usize)
+            .and_then(|row| row.get(seat.column as usize))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Seats the `student` provided at the `seat` provided.
+    ///
+    /// # Errors
+    ///
+    /// [`SeatingError::InvalidSeat`] is returned should there be no desk at
+    /// the seat, [`SeatingError::Occupied`] should another student be seated
+    /// there, and [`SeatingError::AlreadySeated`] should the student be
+    /// seated elsewhere.
+    pub fn assign(&mut self, seat: Seat, student: &str) -> Result<(), SeatingError> {
+        if !self.has_desk(seat) {
+            return Err(SeatingError::InvalidSeat(seat.to_string()));
+        }
+        if self.seats.contains_key(&seat) {
+            return Err(SeatingError::Occupied(seat));
+        }
+        if self.seat_of(student).is_some() {
+            return Err(SeatingError::AlreadySeated(student.to_string()));
+        }
+
+        self.seats.insert(seat, student.to_string());
+        Ok(())
+    }
+
+    /// Removes the student seated at the `seat` provided, returning them.
+    pub fn unassign(&mut self, seat: Seat) -> Option<String> {
+        self.seats.remove(&seat)
+    }
+
+    /// Retrieves the student seated at the `seat` provided.
+    pub fn student_at(&self, seat: Seat) -> Option<&str> {
+        self.seats.get(&seat).map(String::as_str)
+    }
+
+    /// Retrieves the seat of the `student` provided.
+    pub fn seat_of(&self, student: &str) -> Option<Seat> {
+        self.seats
+            .iter()
+            .find(|(_, other)| *other == student)
+            .map(|(seat, _)| *seat)
+    }
+
+    /// Retrieves every seated student (ordered by seat, from the front left
+    /// of the room).
+    pub fn seats(&self) -> impl Iterator<Item = (Seat, &str)> + '_ {
+        self.seats
+            .iter()
+            .map(|(seat, student)| (*seat, student.as_str()))
+    }
+
+    /// Checks the layout against the `capacity` of the room (e.g., the
+    /// capacity known to a [`RoomCapacity`](crate::validate::RoomCapacity)
+    /// rule).
+    ///
+    /// # Errors
+    ///
+    /// [`SeatingError::OverCapacity`] is returned should the layout have more
+    /// desks than the room can seat.
+    pub fn check_capacity(&self, capacity: u32) -> Result<(), SeatingError> {
+        let desks = self.desks();
+
+        if desks as u64 > u64::from(capacity) {
+            return Err(SeatingError::OverCapacity { desks, capacity });
+        }
+
+        Ok(())
+    }
+
+    /// Renders the `SeatingPlan` as a printable layout, with the front of
+    /// the room at the top -- each desk shows the student seated at it (or
+    /// its seat label, should it be free), truncated to fit.
+    pub fn render(&self) -> String {
+        let width = self.layout[0].len() * (DESK_WIDTH + 3) + 2;
+        let mut output = format!("Seating plan: {} in {}\n", self.class, self.location);
+        let front = format!("{:^width$}", "FRONT", width = width);
+        output.push_str(front.trim_end());
+        output.push('\n');
+
+        for (row, desks) in self.layout.iter().enumerate() {
+            let _ = write!(output, "{}", (b'A' + row as u8) as char);
+
+            for (column, desk) in desks.iter().enumerate() {
+                let seat = Seat {
+                    row: row as u8,
+                    column: column as u8,
+                };
+
+                if *desk {
+                    let label = seat.to_string();
+                    let name = self.student_at(seat).unwrap_or(&label);
+                    let name: String = name.chars().take(DESK_WIDTH).collect();
+
+                    let _ = write!(output, " [{:<width$}]", name, width = DESK_WIDTH);
+                } else {
+                    let _ = write!(output, "{:width$}", "", width = DESK_WIDTH + 3);
+                }
+            }
+
+            output.truncate(output.trim_end().len());
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Writes the `SeatingPlan` in its TOML format:
+    ///
+    /// ```toml
+    /// room = "H201"
+    /// class = "10A"
+    /// layout = [
+    ///     "##.##",
+    ///     "##.##",
+    /// ]
+    ///
+    /// [seats]
+    /// A1 = "Alice"
+    /// B4 = "Bob"
+    /// ```
+    pub fn to_toml_string(&self) -> String {
+        let mut output = String::from("room = ");
+        write_string(&mut output, &self.location.to_string());
+        output.push_str("\nclass = ");
+        write_string(&mut output, self.class.reference());
+        output.push_str("\nlayout = [\n");
+
+        for row in &self.layout {
+            let row: String = row
+                .iter()
+                .map(|desk| if *desk { '#' } else { '.' })
+                .collect();
+            let _ = writeln!(output, "    \"{}\",", row);
+        }
+
+        output.push_str("]\n");
+
+        if !self.seats.is_empty() {
+            output.push_str("\n[seats]\n");
+
+            for (seat, student) in &self.seats {
+                let _ = write!(output, "{} = ", seat);
+                write_string(&mut output, student);
+                output.push('\n');
+            }
+        }
+
+        output
+    }
+
+    /// Parses a `SeatingPlan` from its TOML format (*see
+    /// [`Self::to_toml_string`]*).
+    ///
+    /// # Errors
+    ///
+    /// A [`TomlError`] pointing at the first problem found within the
+    /// `input` -- [`TomlErrorKind::InvalidSeat`] should a student be seated
+    /// at a seat without a desk (or be seated twice).
+    pub fn from_toml_str(input: &str) -> Result<Self, TomlError> {
+        let pairs = toml::parse(input).map_err(|syntax| TomlError::syntax(input, syntax))?;
+        let error = |span, kind| TomlError::new(input, span, kind);
+
+        let mut room = None;
+        let mut class = None;
+        let mut layout = None;
+        let mut seats = Vec::new();
+
+        for (key, (span, value)) in pairs {
+            let names: Vec<&str> = key.iter().map(|(_, name)| name.as_str()).collect();
+
+            match (&names[..], value) {
+                (["room"], TomlValue::String(value)) => room = Some((span, value)),
+                (["class"], TomlValue::String(value)) => class = Some((span, value)),
+                (["layout"], TomlValue::Array(rows)) => {
+                    let rows = rows
+                        .into_iter()
+                        .map(|(span, row)| match row {
+                            TomlValue::String(row) => Ok(row),
+                            _ => Err(error(span, TomlErrorKind::UnexpectedValue)),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    layout = Some((span, rows.join("\n")));
+                }
+                (["seats", seat], TomlValue::String(student)) => {
+                    seats.push((key[1].0.clone(), seat.to_string(), student));
+                }
+                (["room" | "class" | "layout"] | ["seats", _], _) => {
+                    return Err(error(span, TomlErrorKind::UnexpectedValue))
+                }
+                _ => {
+                    let (span, _) = &key[0];
+                    return Err(error(
+                        span.clone(),
+                        TomlErrorKind::UnknownField(names.join(".")),
+                    ));
+                }
+            }
+        }
+
+        let end = input.len()..input.len();
+        let room = room.ok_or_else(|| error(end.clone(), TomlErrorKind::MissingField("room")))?;
+        let (class_span, class) =
+            class.ok_or_else(|| error(end.clone(), TomlErrorKind::MissingField("class")))?;
+        let (layout_span, layout) =
+            layout.ok_or_else(|| error(end, TomlErrorKind::MissingField("layout")))?;
+
+        let location =
+            location(room, &ParseOptions::default()).map_err(|(span, kind)| error(span, kind))?;
+        let class = Class::new(class.clone())
+            .map_err(|_| error(class_span, TomlErrorKind::InvalidClass(class)))?;
+
+        let mut plan = Self::new(location, class, &layout)
+            .map_err(|_| error(layout_span, TomlErrorKind::UnexpectedValue))?;
+
+        for (span, seat, student) in seats {
+            seat.parse()
+                .and_then(|parsed| plan.assign(parsed, &student))
+                .map_err(|_| error(span, TomlErrorKind::InvalidSeat(seat)))?;
+        }
+
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seating_plan() {
+        let class = Class::new("10A".to_string()).unwrap();
+        let seat = |label: &str| label.parse::<Seat>().unwrap();
+
+        let mut plan = SeatingPlan::new("H201".parse().unwrap(), class.clone(), "#.#\n##").unwrap();
+
+        assert_eq!(plan.desks(), 4);
+        assert!(!plan.has_desk(seat("A2")));
+        assert!(!plan.has_desk(seat("B3")));
+        assert_eq!(plan.assign(seat("A1"), "Alexandra Smith"), Ok(()));
+        assert_eq!(
+            plan.assign(seat("A1"), "Bob"),
+            Err(SeatingError::Occupied(seat("A1")))
+        );
+        assert_eq!(
+            plan.assign(seat("B1"), "Alexandra Smith"),
+            Err(SeatingError::AlreadySeated("Alexandra Smith".to_string()))
+        );
+        assert_eq!(
+            plan.assign(seat("A2"), "Bob"),
+            Err(SeatingError::InvalidSeat("A2".to_string()))
+        );
+        assert_eq!(plan.assign(seat("B2"), "Bob"), Ok(()));
+
+        assert!(plan.check_capacity(4).is_ok());
+        assert_eq!(
+            plan.check_capacity(3),
+            Err(SeatingError::OverCapacity {
+                desks: 4,
+                capacity: 3
+            })
+        );
+
+        assert_eq!(
+            plan.render(),
+            "Seating plan: 10A in H201\n\
+             \x20                 FRONT\n\
+             A [Alexandra ]              [A3        ]\n\
+             B [B1        ] [Bob       ]\n"
+        );
+
+        assert_eq!(SeatingPlan::from_toml_str(&plan.to_toml_string()), Ok(plan));
+        assert!(SeatingPlan::new("H201".parse().unwrap(), class, "#x#").is_err());
+        assert!("A0".parse::<Seat>().is_err());
+
+        let error = SeatingPlan::from_toml_str(
+            "room = 'H201'\nclass = '10A'\nlayout = ['#.']\n[seats]\nA2 = 'Bob'",
+        )
+        .unwrap_err();
+
+        assert_eq!(error.kind, TomlErrorKind::InvalidSeat("A2".to_string()));
+        assert_eq!(error.line, 5);
+    }
+}
@@ -0,0 +1,337 @@
+//! Serialisation of [`Location`]s using serde.
+//!
+//! A `Location` serialises as its room identifier (e.g., `"FH S13"`) -- this
+//! is the representation intended for human-facing formats, and is also
+//! available as the [`code`] module. APIs whose payloads are described by a
+//! schema may prefer the [`structured`] module, which represents a location
+//! as an object of its [parts](crate::LocationParts):
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use timetableau::Location;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Booking {
+//!     // `"H201"`
+//!     #[serde(with = "timetableau::serialise::code")]
+//!     room: Location,
+//!
+//!     // `{ "school": "Highfield", "block": "Howard", "floor": 2,
+//!     // "discriminator": 1 }`
+//!     #[serde(with = "timetableau::serialise::structured")]
+//!     moved_to: Location,
+//! }
+//! ```
+//!
+//! Both representations deserialise to identical values.
+
+use crate::Location;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for Location {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Location {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Represents a [`Location`] as its room identifier (e.g., `"FH S13"`).
+///
+/// *See the [module](self) documentation for more information*.
+pub mod code {
+    use super::*;
+
+    /// Serialises the `location` as its room identifier.
+    ///
+    /// # Errors
+    ///
+    /// An error is only returned should the `serializer` fail.
+    pub fn serialize<S>(location: &Location, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        location.serialize(serializer)
+    }
+
+    /// Deserialises a `Location` from its room identifier.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should the value not be a valid room identifier.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Location, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Location::deserialize(deserializer)
+    }
+}
+
+/// Represents a [`Location`] as an object of its
+/// [parts](crate::LocationParts).
+///
+/// The object always contains a `school` (`"Highfield"` or `"Fearnhill"`),
+/// and the parts which apply to the room: a `block` or `section` (as its
+/// full name), a `floor` (as its number), a `discriminator`, the `named`
+/// room (e.g., `"Sports Hall"`), the `off_site` location (as its identifier
+/// without the `@` prefix, e.g., `"Stevenage Pool"`), or the `remote` lesson
+/// location (as its identifier, e.g., `"Remote (Teams)"`).
+///
+/// *See the [module](self) documentation for more information*.
+pub mod structured {
+    use super::*;
+    use crate::{HighfieldFloor, LocationParts, School};
+
+    // The names of the named rooms (at either school)
+    const NAMED: [&str; 10] = [
+        "Hall",
+        "Sports Hall",
+        "Gym",
+        "Dance Studio",
+        "Drama Studio",
+        "Field",
+        "Astro Pitch",
+        "Tennis Courts",
+        "Playground",
+        "TBC",
+    ];
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Location", deny_unknown_fields)]
+    struct Structured {
+        school: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        section: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        floor: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        discriminator: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        named: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        off_site: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        remote: Option<String>,
+    }
+
+    impl Structured {
+        // Convert the object into the parts of a location
+        fn parts(self) -> Option<LocationParts> {
+            let school = match self.school.as_str() {
+                "Highfield" => School::Highfield,
+                "Fearnhill" => School::Fearnhill,
+                _ => return None,
+            };
+
+            Some(LocationParts {
+                school,
+                block: self.block.map(|block| block.parse()).transpose().ok()?,
+                section: self
+                    .section
+                    .map(|section| section.parse())
+                    .transpose()
+                    .ok()?,
+                floor: self.floor.map(HighfieldFloor::try_from).transpose().ok()?,
+                discriminator: self.discriminator,
+                named: match self.named {
+                    Some(name) => Some(NAMED.into_iter().find(|named| *named == name)?),
+                    None => None,
+                },
+                off_site: self
+                    .off_site
+                    .map(|site| format!("@{}", site).parse())
+                    .transpose()
+                    .ok()?,
+                remote: self.remote.map(|remote| remote.parse()).transpose().ok()?,
+            })
+        }
+    }
+
+    /// Serialises the `location` as an object of its parts.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should the `serializer` fail, or should the
+    /// location not be representable by its parts (i.e., an
+    /// [`OtherRoom`](crate::OtherRoom)).
+    pub fn serialize<S>(location: &Location, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let parts = location.parts();
+
+        if Location::try_from(parts).is_err() {
+            return Err(serde::ser::Error::custom(format!(
+                "room `{}` cannot be represented by its parts",
+                location
+            )));
+        }
+
+        Structured {
+            school: parts.school.name().to_string(),
+            block: parts.block.map(|block| block.full_name().to_string()),
+            section: parts.section.map(|section| section.full_name().to_string()),
+            floor: parts.floor.map(HighfieldFloor::number),
+            discriminator: parts.discriminator,
+            named: parts.named.map(str::to_string),
+            off_site: parts.off_site.map(|site| site.to_string().split_off(1)),
+            remote: parts.remote.map(|remote| remote.to_string()),
+        }
+        .serialize(serializer)
+    }
+
+    /// Deserialises a `Location` from an object of its parts.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned should the object not describe a room (e.g., a
+    /// Highfield classroom without a floor).
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Location, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Structured::deserialize(deserializer)?
+            .parts()
+            .ok_or(crate::ParseLocationError(None))
+            .and_then(Location::try_from)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::value::{Error as ValueError, MapDeserializer, StrDeserializer};
+    use serde::de::{IntoDeserializer, Visitor};
+    use serde::forward_to_deserialize_any;
+
+    // A scalar value of a structured location (serde only provides
+    // deserializers for homogeneous maps)
+    #[derive(Clone, Copy)]
+    enum Value {
+        Str(&'static str),
+        Int(u8),
+    }
+
+    impl<'de> Deserializer<'de> for Value {
+        type Error = ValueError;
+
+        fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: Visitor<'de>,
+        {
+            match self {
+                Self::Str(value) => visitor.visit_borrowed_str(value),
+                Self::Int(value) => visitor.visit_u8(value),
+            }
+        }
+
+        fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: Visitor<'de>,
+        {
+            visitor.visit_some(self)
+        }
+
+        forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+            map struct enum identifier ignored_any
+        }
+    }
+
+    impl<'de> IntoDeserializer<'de, ValueError> for Value {
+        type Deserializer = Self;
+
+        fn into_deserializer(self) -> Self::Deserializer {
+            self
+        }
+    }
+
+    #[test]
+    fn representations_identical() {
+        let cases: [(&str, Vec<(&str, Value)>); 5] = [
+            (
+                "H201",
+                vec![
+                    ("school", Value::Str("Highfield")),
+                    ("block", Value::Str("Howard")),
+                    ("floor", Value::Int(2)),
+                    ("discriminator", Value::Int(1)),
+                ],
+            ),
+            (
+                "FH Mu2",
+                vec![
+                    ("school", Value::Str("Fearnhill")),
+                    ("section", Value::Str("Music")),
+                    ("discriminator", Value::Int(2)),
+                ],
+            ),
+            (
+                "FH Gym",
+                vec![
+                    ("school", Value::Str("Fearnhill")),
+                    ("named", Value::Str("Gym")),
+                ],
+            ),
+            (
+                "@Stevenage Pool (51.901200, -0.202100)",
+                vec![
+                    ("school", Value::Str("Highfield")),
+                    (
+                        "off_site",
+                        Value::Str("Stevenage Pool (51.901200, -0.202100)"),
+                    ),
+                ],
+            ),
+            (
+                "FH Remote (Teams)",
+                vec![
+                    ("school", Value::Str("Fearnhill")),
+                    ("remote", Value::Str("Remote (Teams)")),
+                ],
+            ),
+        ];
+
+        for (code, fields) in cases {
+            let deserializer: StrDeserializer<ValueError> = code.into_deserializer();
+            let from_code = code::deserialize(deserializer);
+            let from_structured =
+                structured::deserialize(MapDeserializer::<_, ValueError>::new(fields.into_iter()));
+
+            assert_eq!(from_code, Ok(code.parse::<Location>().unwrap()));
+            assert_eq!(from_structured, from_code);
+        }
+    }
+
+    #[test]
+    fn structured_invalid() {
+        // A Highfield classroom must have a floor
+        let fields = [
+            ("school", Value::Str("Highfield")),
+            ("block", Value::Str("Howard")),
+            ("discriminator", Value::Int(1)),
+        ];
+
+        assert!(
+            structured::deserialize(MapDeserializer::<_, ValueError>::new(fields.into_iter()))
+                .is_err()
+        );
+    }
+}
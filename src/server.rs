@@ -0,0 +1,506 @@
+//! A small, read-only HTTP API over a [`Dataset`].
+//!
+//! An [`Api`] answers the following requests (each of which responds with a
+//! JSON body), such that a small deployment can serve its timetable without
+//! writing a web layer around the crate:
+//!
+//! * `GET /timetable/<class>` -- the lessons of the class (e.g.,
+//!   `/timetable/10A%2FMa1`), ordered by their timeslots -- including the
+//!   number of periods each spans, and any co-teachers and split rooms.
+//! * `GET /rooms/free?slot=<slot>` -- the rooms of the dataset which are not
+//!   in use during the slot (e.g., `week1.mon.p3`), optionally only at a
+//!   single site (e.g., `&site=fearnhill`).
+//! * `GET /location/<code>` -- the details of a room (e.g., `/location/H201`).
+//!
+//! ```
+//! use timetableau::server::Api;
+//! use timetableau::{AcademicCalendar, Dataset, Timetable};
+//!
+//! let api = Api::new(Dataset::new(Timetable::new(), AcademicCalendar::default()));
+//! let response = api.handle("GET", "/location/FH%20S12");
+//!
+//! assert_eq!(response.status, 200);
+//! assert!(response.body.contains(r#""school":"Fearnhill""#));
+//! ```
+//!
+//! [`Api::serve`] answers the requests received by a [`TcpListener`] (one at
+//! a time) -- the API is intended to be placed behind the school's existing
+//! web server (e.g., to provide TLS). A connection which is idle for longer
+//! than the [timeout](Api::timeout), or whose request line or headers are
+//! too long, is closed such that it cannot hold up the other requests.
+
+use crate::json::write_string;
+use crate::timeslot::{parse_slot_key, slot_key};
+use crate::{Activity, Dataset, Location, School};
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+// The default time for which a connection may be idle before it is closed
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// The greatest number of bytes of a request line, and of the headers of a
+// request
+const MAX_REQUEST_LINE: u64 = 8 * 1024;
+const MAX_HEADERS: u64 = 16 * 1024;
+
+/// The response of the [`Api`] to a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiResponse {
+    /// The status code of the response (e.g., `200` or `404`).
+    pub status: u16,
+
+    /// The JSON body of the response -- an object with an `error` field
+    /// should the request have failed.
+    pub body: String,
+}
+
+impl ApiResponse {
+    // Create a response describing the error provided
+    fn error(status: u16, message: &str) -> Self {
+        let mut body = String::from("{\"error\":");
+        write_string(&mut body, message);
+        body.push('}');
+
+        Self { status, body }
+    }
+}
+
+/// A read-only HTTP API over a [`Dataset`].
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Api {
+    dataset: Dataset,
+    rooms: Vec<Location>,
+    timeout: Duration,
+}
+
+impl Api {
+    /// Creates a new `Api` serving the `dataset` provided.
+    ///
+    /// # Remarks
+    ///
+    /// The free rooms are chosen from the rooms referenced by the dataset
+    /// (*see [`Dataset::locations`]*) -- the dataset has no knowledge of
+    /// rooms which are never used.
+    pub fn new(dataset: Dataset) -> Self {
+        Self {
+            rooms: dataset.locations(),
+            dataset,
+            timeout: TIMEOUT,
+        }
+    }
+
+    /// Sets the time for which a connection may be idle whilst its request
+    /// is read (or its response written) before it is closed (10 seconds by
+    /// default) -- *see [`Self::serve`]*.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Retrieves the dataset served by the `Api`.
+    pub fn dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+
+    /// Answers a request with the `method` (e.g., `GET`) and the `target`
+    /// (i.e., the path and the query, e.g., `/rooms/free?slot=week1.mon.p1`)
+    /// provided.
+    pub fn handle(&self, method: &str, target: &str) -> ApiResponse {
+        if method != "GET" {
+            return ApiResponse::error(405, "only GET requests are supported");
+        }
+
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        let Some(segments) = segments
+            .into_iter()
+            .map(decode)
+            .collect::<Option<Vec<String>>>()
+        else {
+            return ApiResponse::error(400, "the path is not correctly percent-encoded");
+        };
+
+        match segments.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+            ["timetable", class] => self.timetable(class),
+            ["rooms", "free"] => self.free_rooms(query),
+            ["location", code] => self.location(code),
+            _ => ApiResponse::error(404, "no such endpoint"),
+        }
+    }
+
+    /// Answers each request received by the `listener` provided (one at a
+    /// time), until accepting a connection fails.
+    ///
+    /// As requests are answered one at a time, a connection is closed should
+    /// it be idle for longer than the [timeout](Self::timeout) -- a request
+    /// whose request line (or headers) exceed 8 KiB (or 16 KiB) is answered
+    /// with a `400` response without being read any further.
+    ///
+    /// # Errors
+    ///
+    /// The error which prevented a connection from being accepted -- errors
+    /// reading a request from (or writing a response to) an accepted
+    /// connection only close that connection.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        loop {
+            let (stream, _) = listener.accept()?;
+
+            // A connection which fails does not stop the server
+            let _ = self.respond(stream);
+        }
+    }
+
+    // Read a single request from a connection, and write its response
+    fn respond(&self, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        (&mut reader)
+            .take(MAX_REQUEST_LINE)
+            .read_line(&mut request_line)?;
+
+        // The headers of the request are disregarded (there is no body) --
+        // they end with an empty line
+        let mut headers = (&mut reader).take(MAX_HEADERS);
+        let mut header = String::new();
+        let mut complete = request_line.ends_with('\n');
+
+        while complete {
+            header.clear();
+            headers.read_line(&mut header)?;
+
+            if !header.ends_with('\n') {
+                complete = false;
+            } else if header.trim().is_empty() {
+                break;
+            }
+        }
+
+        let mut parts = request_line.split_whitespace();
+
+        let response = match (parts.next(), parts.next()) {
+            _ if !complete => ApiResponse::error(400, "the request is incomplete or too long"),
+            (Some(method), Some(target)) => self.handle(method, target),
+            _ => ApiResponse::error(400, "invalid request"),
+        };
+
+        let reason = match response.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            _ => "Method Not Allowed",
+        };
+
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            response.status,
+            reason,
+            response.body.len(),
+            response.body
+        )
+    }
+
+    // Respond with the lessons of a class
+    fn timetable(&self, class: &str) -> ApiResponse {
+        let mut lessons: Vec<_> = self
+            .dataset
+            .timetable
+            .lessons()
+            .for_class(class)
+            .map(|(_, entry)| entry)
+            .collect();
+
+        if lessons.is_empty() {
+            return ApiResponse::error(404, "no such class");
+        }
+
+        lessons.sort_by_key(|entry| entry.slot.index());
+
+        let mut body = String::from("[");
+
+        for (index, entry) in lessons.into_iter().enumerate() {
+            let Activity::Lesson {
+                subject,
+                teacher,
+                location,
+                ..
+            } = &entry.activity
+            else {
+                continue;
+            };
+
+            if index > 0 {
+                body.push(',');
+            }
+
+            body.push_str("{\"slot\":");
+            write_string(&mut body, &slot_key(entry.slot));
+            body.push_str(",\"subject\":");
+            write_string(&mut body, subject.name());
+            body.push_str(",\"teacher\":");
+
+            match teacher {
+                Some(teacher) => write_string(&mut body, teacher.code()),
+                None => body.push_str("null"),
+            }
+
+            body.push_str(",\"room\":");
+            write_string(&mut body, &location.to_string());
+            let _ = write!(body, ",\"periods\":{}", entry.periods);
+
+            body.push_str(",\"co_teachers\":");
+            write_strings(
+                &mut body,
+                entry.co_teachers.iter().map(|teacher| teacher.code()),
+            );
+            body.push_str(",\"split_rooms\":");
+            write_strings(
+                &mut body,
+                entry.split_rooms.iter().map(|room| room.to_string()),
+            );
+            body.push('}');
+        }
+
+        body.push(']');
+        ApiResponse { status: 200, body }
+    }
+
+    // Respond with the rooms which are not in use during a slot
+    fn free_rooms(&self, query: &str) -> ApiResponse {
+        let parameter = |name: &str| {
+            query
+                .split('&')
+                .find_map(|parameter| parameter.strip_prefix(name)?.strip_prefix('='))
+        };
+
+        let Some(slot) = parameter("slot").and_then(parse_slot_key) else {
+            return ApiResponse::error(400, "a valid `slot` is required (e.g., `week1.mon.p1`)");
+        };
+
+        let school = match parameter("site") {
+            None => None,
+            Some("highfield") => Some(School::Highfield),
+            Some("fearnhill") => Some(School::Fearnhill),
+            Some(_) => {
+                return ApiResponse::error(400, "the `site` must be `highfield` or `fearnhill`")
+            }
+        };
+
+        let used: Vec<Location> = self
+            .dataset
+            .timetable
+            .at(slot)
+            .flat_map(|(_, entry)| entry.locations().copied())
+            .collect();
+
+        let mut body = String::from("[");
+
+        let free = self.rooms.iter().filter(|location| {
+            !used.contains(location) && school.is_none_or(|school| location.school() == school)
+        });
+
+        for (index, location) in free.enumerate() {
+            if index > 0 {
+                body.push(',');
+            }
+
+            write_string(&mut body, &location.to_string());
+        }
+
+        body.push(']');
+        ApiResponse { status: 200, body }
+    }
+
+    // Respond with the details of a room
+    fn location(&self, code: &str) -> ApiResponse {
+        let Ok(location) = code.parse::<Location>() else {
+            return ApiResponse::error(404, "no such room");
+        };
+
+        let mut body = String::from("{\"code\":");
+        write_string(&mut body, &location.to_string());
+        body.push_str(",\"name\":");
+        write_string(&mut body, &location.verbose_name());
+        body.push_str(",\"school\":");
+        write_string(&mut body, location.school().name());
+        let _ = write!(body, ",\"exists\":{}}}", location.exists());
+
+        ApiResponse { status: 200, body }
+    }
+}
+
+// Write the strings provided as a JSON array
+fn write_strings<S: AsRef<str>>(output: &mut String, strings: impl Iterator<Item = S>) {
+    output.push('[');
+
+    for (index, string) in strings.enumerate() {
+        if index > 0 {
+            output.push(',');
+        }
+
+        write_string(output, string.as_ref());
+    }
+
+    output.push(']');
+}
+
+// Decode a percent-encoded segment of a path
+fn decode(segment: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut input = segment.bytes();
+
+    while let Some(byte) = input.next() {
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+
+        let mut digit = || char::from(input.next()?).to_digit(16);
+        let high = digit()?;
+        let low = digit()?;
+
+        bytes.push((high * 16 + low) as u8);
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Entry, Subject, Teacher, Timetable};
+    use std::io::Read;
+    use std::net::TcpStream;
+
+    fn api() -> Api {
+        let lesson = |class: &str, room: &str| Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Teacher::new("JSM".to_string()).ok(),
+            location: room.parse().unwrap(),
+        };
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1TP2), lesson("10A/Ma1", "FH S12"));
+        timetable.insert(timeslot!(W1MP1), lesson("10A/Ma1", "H201"));
+        timetable.insert(timeslot!(W1MP1), lesson("11B", "H202"));
+        timetable.insert(timeslot!(W1MP2), lesson("11B", "H202"));
+        timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1WP3), lesson("12C", "H202"))
+                    .co_teacher(Teacher::new("ABC".to_string()).unwrap())
+                    .split_room("H201".parse().unwrap()),
+            )
+            .unwrap();
+        timetable
+            .insert_spanning(timeslot!(W1WP1), 2, lesson("12C", "FH S12"))
+            .unwrap();
+
+        Api::new(Dataset::new(timetable, AcademicCalendar::default()))
+    }
+
+    #[test]
+    fn api_endpoints() {
+        let api = api();
+
+        let response = api.handle("GET", "/timetable/10A%2FMa1");
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.body,
+            r#"[{"slot":"week1.mon.p1","subject":"Ma","teacher":"JSM","room":"H201","periods":1,"co_teachers":[],"split_rooms":[]},{"slot":"week1.tue.p2","subject":"Ma","teacher":"JSM","room":"FH S12","periods":1,"co_teachers":[],"split_rooms":[]}]"#
+        );
+        assert_eq!(
+            api.handle("GET", "/timetable/12C").body,
+            r#"[{"slot":"week1.wed.p1","subject":"Ma","teacher":"JSM","room":"FH S12","periods":2,"co_teachers":[],"split_rooms":[]},{"slot":"week1.wed.p3","subject":"Ma","teacher":"JSM","room":"H202","periods":1,"co_teachers":["ABC"],"split_rooms":["H201"]}]"#
+        );
+
+        assert_eq!(
+            api.handle("GET", "/rooms/free?slot=week1.mon.p2").body,
+            r#"["FH S12","H201"]"#
+        );
+        assert_eq!(
+            api.handle("GET", "/rooms/free?site=highfield&slot=week1.mon.p1")
+                .body,
+            "[]"
+        );
+        assert_eq!(
+            api.handle("GET", "/location/H201").body,
+            r#"{"code":"H201","name":"Howard Block, Floor 2, Room 01","school":"Highfield","exists":true}"#
+        );
+
+        for (method, target, status) in [
+            ("GET", "/timetable/12Z", 404),
+            ("GET", "/rooms/free?slot=week3.mon.p1", 400),
+            ("GET", "/rooms/free?slot=week1.mon.p1&site=x", 400),
+            ("GET", "/location/H2001", 404),
+            ("GET", "/location/%ZZ", 400),
+            ("GET", "/", 404),
+            ("POST", "/location/H201", 405),
+        ] {
+            assert_eq!(api.handle(method, target).status, status, "{}", target);
+        }
+    }
+
+    #[test]
+    fn api_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || api().serve(listener));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"GET /location/Hall HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with(r#""school":"Highfield","exists":true}"#));
+    }
+
+    #[test]
+    fn api_serve_limits() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || api().timeout(Duration::from_millis(100)).serve(listener));
+
+        // An idle connection is closed, rather than blocking the next request
+        let _idle = TcpStream::connect(address).unwrap();
+
+        // A header line is not read without limit
+        let mut stream = TcpStream::connect(address).unwrap();
+        let request = format!(
+            "GET /location/Hall HTTP/1.1\r\nX: {}\r\n\r\n",
+            "a".repeat(20_000)
+        );
+        let _ = stream.write_all(request.as_bytes());
+
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response);
+
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+
+        // Headers end on an empty line
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"GET /location/Hall HTTP/1.1\nHost: localhost\n\n")
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    }
+}
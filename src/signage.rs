@@ -0,0 +1,578 @@
+//! Payloads encoding rooms for the signage on classroom doors.
+//!
+//! The QR codes on classroom doors encode a [`RoomPayload`] -- a compact,
+//! URI-style identifier of the room (e.g., `fy:loc/H201?v=1`), such that
+//! every app built on the crate which scans a code agrees upon the room it
+//! identifies:
+//!
+//! ```
+//! use timetableau::signage::RoomPayload;
+//!
+//! let payload = RoomPayload::new("FH S12".parse().unwrap());
+//! assert_eq!(payload.to_string(), "fy:loc/FH%20S12?v=1");
+//!
+//! let scanned: RoomPayload = "fy:loc/FH%20S12?v=1".parse().unwrap();
+//! assert_eq!(scanned.location(), payload.location());
+//! ```
+//!
+//! # Format
+//!
+//! A payload takes the form `fy:loc/<room>?v=<version>`, where:
+//!
+//! * `<room>` is the room identifier (*see the [`crate`] documentation*),
+//!   in which every byte other than an ASCII letter, an ASCII digit, `-`,
+//!   `.`, `_`, `~`, and `@` is percent-encoded (e.g., `FH%20S12`).
+//!
+//! * `<version>` is the [version](VERSION) of the format in which the
+//!   payload was encoded.
+//!
+//! Further query parameters (separated by `&`) may be introduced by later
+//! versions of the format -- parameters which are not understood are
+//! disregarded when decoding, such that the signs printed by newer apps
+//! remain readable by older apps, unless the version of the payload itself
+//! is newer than [`VERSION`].
+//!
+//! # Remarks
+//!
+//! The crate does not render QR codes -- the payload is intended to be
+//! passed to a QR code library (e.g., as a byte-mode segment).
+//!
+//! # NFC tags
+//!
+//! With the `nfc` feature enabled, the tap-to-check-in tags on classroom
+//! doors can be written as a [`RoomTag`] -- an NDEF message whose first
+//! record is a URI record of the room's payload, optionally followed by a
+//! URI record of the booking page of the room:
+//!
+//! ```
+//! # #[cfg(feature = "nfc")]
+//! # {
+//! use timetableau::signage::RoomTag;
+//!
+//! let tag = RoomTag::new("H201".parse().unwrap())
+//!     .with_booking_url("https://bookings.example.com/H201".to_string());
+//!
+//! assert_eq!(RoomTag::from_ndef(&tag.to_ndef()), Ok(tag));
+//! # }
+//! ```
+
+use crate::{Location, ParseLocationError};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+use std::str::FromStr;
+
+/// The latest version of the payload format (i.e., the version in which
+/// payloads are encoded).
+pub const VERSION: u32 = 1;
+
+// The prefix of every payload which identifies a room
+const PREFIX: &str = "fy:loc/";
+
+/// The reason a [`RoomPayload`] could not be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePayloadError {
+    /// The payload did not start with `fy:loc/`.
+    Scheme,
+
+    /// The room identifier was not correctly percent-encoded.
+    Encoding,
+
+    /// The room identifier was invalid.
+    Location(ParseLocationError),
+
+    /// The version parameter was missing or invalid.
+    Version,
+
+    /// The payload was encoded in a newer version of the format than
+    /// [`VERSION`].
+    UnsupportedVersion(u32),
+}
+
+impl Display for ParsePayloadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scheme => write!(f, "the payload does not start with `{}`", PREFIX),
+            Self::Encoding => f.write_str("the room identifier is not correctly percent-encoded"),
+            Self::Location(error) => Display::fmt(error, f),
+            Self::Version => f.write_str("the payload has no valid version"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "the payload is version {}, but only versions up to {} are supported",
+                version, VERSION
+            ),
+        }
+    }
+}
+
+impl Error for ParsePayloadError {}
+
+impl From<ParseLocationError> for ParsePayloadError {
+    fn from(error: ParseLocationError) -> Self {
+        Self::Location(error)
+    }
+}
+
+/// The payload of the QR code on the door of a room.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RoomPayload {
+    location: Location,
+    version: u32,
+}
+
+impl RoomPayload {
+    /// Creates a new `RoomPayload` identifying the `location` provided, in
+    /// the latest [version](VERSION) of the format.
+    pub fn new(location: Location) -> Self {
+        Self {
+            location,
+            version: VERSION,
+        }
+    }
+
+    /// Retrieves the room identified by the `RoomPayload`.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Retrieves the version of the format in which the `RoomPayload` was
+    /// encoded.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+impl Display for RoomPayload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(PREFIX)?;
+
+        for byte in self.location.to_string().bytes() {
+            if is_unreserved(byte) {
+                f.write_char(char::from(byte))?;
+            } else {
+                write!(f, "%{:02X}", byte)?;
+            }
+        }
+
+        write!(f, "?v={}", self.version)
+    }
+}
+
+impl FromStr for RoomPayload {
+    type Err = ParsePayloadError;
+
+    /// Decodes a payload (in the same format as produced by the `Display`
+    /// implementation of `RoomPayload`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix(PREFIX).ok_or(ParsePayloadError::Scheme)?;
+        let (room, query) = s.split_once('?').ok_or(ParsePayloadError::Version)?;
+
+        // Parameters introduced by later versions are disregarded
+        let version = query
+            .split('&')
+            .find_map(|parameter| parameter.strip_prefix("v="))
+            .ok_or(ParsePayloadError::Version)?;
+
+        if version.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParsePayloadError::Version);
+        }
+
+        let version: u32 = version.parse().map_err(|_| ParsePayloadError::Version)?;
+
+        match version {
+            0 => return Err(ParsePayloadError::Version),
+            VERSION => {}
+            _ => return Err(ParsePayloadError::UnsupportedVersion(version)),
+        }
+
+        Ok(Self {
+            location: percent_decode(room)?.parse()?,
+            version,
+        })
+    }
+}
+
+// Whether a byte of a room identifier can be written without being
+// percent-encoded
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b'@')
+}
+
+// Decode the percent-encoded room identifier of a payload
+fn percent_decode(s: &str) -> Result<String, ParsePayloadError> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut input = s.bytes();
+
+    while let Some(byte) = input.next() {
+        match byte {
+            b'%' => {
+                let mut digit = || {
+                    input
+                        .next()
+                        .and_then(|digit| char::from(digit).to_digit(16))
+                        .ok_or(ParsePayloadError::Encoding)
+                };
+
+                let high = digit()?;
+                let low = digit()?;
+
+                bytes.push((high * 16 + low) as u8);
+            }
+            byte if is_unreserved(byte) => bytes.push(byte),
+            _ => return Err(ParsePayloadError::Encoding),
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| ParsePayloadError::Encoding)
+}
+
+// The well-known URI prefixes of NDEF URI records (*see the NFC Forum URI
+// Record Type Definition*), indexed by their identifier codes -- the
+// prefixes are checked in order, so longer prefixes must come first
+#[cfg(feature = "nfc")]
+const URI_PREFIXES: [(u8, &str); 4] = [
+    (0x02, "https://www."),
+    (0x01, "http://www."),
+    (0x04, "https://"),
+    (0x03, "http://"),
+];
+
+// The header flags of an NDEF record
+#[cfg(feature = "nfc")]
+const MESSAGE_BEGIN: u8 = 0x80;
+#[cfg(feature = "nfc")]
+const MESSAGE_END: u8 = 0x40;
+#[cfg(feature = "nfc")]
+const CHUNKED: u8 = 0x20;
+#[cfg(feature = "nfc")]
+const SHORT_RECORD: u8 = 0x10;
+#[cfg(feature = "nfc")]
+const ID_LENGTH: u8 = 0x08;
+
+// The type name format of well-known record types (e.g., URI records)
+#[cfg(feature = "nfc")]
+const WELL_KNOWN: u8 = 0x01;
+
+/// The reason a [`RoomTag`] could not be decoded from an NDEF message.
+#[cfg(feature = "nfc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTagError {
+    /// The message ended in the middle of a record (or contained no
+    /// records).
+    Truncated,
+
+    /// A record was not a URI record (or was chunked), or the message
+    /// contained more than two records.
+    UnexpectedRecord,
+
+    /// A URI record used an identifier code which is not supported, or
+    /// was not valid UTF-8.
+    InvalidUri,
+
+    /// The payload of the room was invalid.
+    Payload(ParsePayloadError),
+}
+
+#[cfg(feature = "nfc")]
+impl Display for ParseTagError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => f.write_str("the NDEF message is truncated"),
+            Self::UnexpectedRecord => f.write_str("the NDEF message contains an unexpected record"),
+            Self::InvalidUri => f.write_str("the NDEF message contains an invalid URI record"),
+            Self::Payload(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "nfc")]
+impl Error for ParseTagError {}
+
+#[cfg(feature = "nfc")]
+impl From<ParsePayloadError> for ParseTagError {
+    fn from(error: ParsePayloadError) -> Self {
+        Self::Payload(error)
+    }
+}
+
+/// The contents of the NFC tag on the door of a room.
+///
+/// *See the [module](self) documentation for more information*.
+#[cfg(feature = "nfc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RoomTag {
+    payload: RoomPayload,
+    booking_url: Option<String>,
+}
+
+#[cfg(feature = "nfc")]
+impl RoomTag {
+    /// Creates a new `RoomTag` identifying the `location` provided, without
+    /// a booking URL.
+    pub fn new(location: Location) -> Self {
+        Self {
+            payload: RoomPayload::new(location),
+            booking_url: None,
+        }
+    }
+
+    /// Includes the URL of the booking page of the room in the `RoomTag`.
+    pub fn with_booking_url(mut self, url: String) -> Self {
+        self.booking_url = Some(url);
+        self
+    }
+
+    /// Retrieves the room identified by the `RoomTag`.
+    pub fn location(&self) -> Location {
+        self.payload.location()
+    }
+
+    /// Retrieves the payload of the room identified by the `RoomTag`.
+    pub fn payload(&self) -> RoomPayload {
+        self.payload
+    }
+
+    /// Retrieves the URL of the booking page of the room (if any).
+    pub fn booking_url(&self) -> Option<&str> {
+        self.booking_url.as_deref()
+    }
+
+    /// Encodes the `RoomTag` as an NDEF message, to be written to the tag.
+    ///
+    /// # Remarks
+    ///
+    /// The message consists of a URI record of the room's [payload](RoomPayload),
+    /// followed by a URI record of the booking URL (if any) -- records are
+    /// written as short records, unless their URI exceeds `254` bytes.
+    pub fn to_ndef(&self) -> Vec<u8> {
+        let payload = self.payload.to_string();
+        let uris: Vec<&str> = std::iter::once(payload.as_str())
+            .chain(self.booking_url.as_deref())
+            .collect();
+
+        let mut message = Vec::new();
+
+        for (index, uri) in uris.iter().enumerate() {
+            let (code, rest) = URI_PREFIXES
+                .iter()
+                .find_map(|(code, prefix)| Some((*code, uri.strip_prefix(prefix)?)))
+                .unwrap_or((0x00, uri));
+
+            let mut header = WELL_KNOWN;
+
+            if index == 0 {
+                header |= MESSAGE_BEGIN;
+            }
+            if index == uris.len() - 1 {
+                header |= MESSAGE_END;
+            }
+
+            // The payload of a URI record is its identifier code, followed by
+            // the remainder of the URI
+            let length = rest.len() + 1;
+
+            match u8::try_from(length) {
+                Ok(length) => {
+                    message.extend([header | SHORT_RECORD, 1, length]);
+                }
+                Err(_) => {
+                    message.extend([header, 1]);
+                    message.extend((length as u32).to_be_bytes());
+                }
+            }
+
+            message.push(b'U');
+            message.push(code);
+            message.extend(rest.bytes());
+        }
+
+        message
+    }
+
+    /// Decodes a `RoomTag` from an NDEF message (in the same format as
+    /// produced by [`Self::to_ndef`]).
+    ///
+    /// # Errors
+    ///
+    /// A [`ParseTagError`] should the message not consist of one or two
+    /// URI records, or should the first record not be a valid
+    /// [`RoomPayload`].
+    pub fn from_ndef(message: &[u8]) -> Result<Self, ParseTagError> {
+        let mut uris = Vec::new();
+        let mut rest = message;
+
+        loop {
+            let (uri, end, remainder) = read_uri_record(rest)?;
+            uris.push(uri);
+            rest = remainder;
+
+            if end {
+                break;
+            }
+        }
+
+        if !rest.is_empty() {
+            return Err(ParseTagError::UnexpectedRecord);
+        }
+
+        let mut uris = uris.into_iter();
+
+        let tag = Self {
+            payload: uris.next().ok_or(ParseTagError::Truncated)?.parse()?,
+            booking_url: uris.next(),
+        };
+
+        match uris.next() {
+            Some(_) => Err(ParseTagError::UnexpectedRecord),
+            None => Ok(tag),
+        }
+    }
+}
+
+// Read the URI record at the start of an NDEF message, returning its URI,
+// whether it is the last record of the message, and the remaining records
+#[cfg(feature = "nfc")]
+fn read_uri_record(message: &[u8]) -> Result<(String, bool, &[u8]), ParseTagError> {
+    fn take(message: &mut &[u8], count: usize) -> Result<Vec<u8>, ParseTagError> {
+        if message.len() < count {
+            return Err(ParseTagError::Truncated);
+        }
+
+        let (start, rest) = message.split_at(count);
+        *message = rest;
+        Ok(start.to_vec())
+    }
+
+    let mut message = message;
+    let header = take(&mut message, 1)?[0];
+
+    if header & 0x07 != WELL_KNOWN || header & CHUNKED != 0 {
+        return Err(ParseTagError::UnexpectedRecord);
+    }
+
+    let type_length = take(&mut message, 1)?[0] as usize;
+    let payload_length = if header & SHORT_RECORD != 0 {
+        take(&mut message, 1)?[0] as usize
+    } else {
+        let length = take(&mut message, 4)?;
+        u32::from_be_bytes([length[0], length[1], length[2], length[3]]) as usize
+    };
+    let id_length = if header & ID_LENGTH != 0 {
+        take(&mut message, 1)?[0] as usize
+    } else {
+        0
+    };
+
+    if take(&mut message, type_length)? != b"U" {
+        return Err(ParseTagError::UnexpectedRecord);
+    }
+
+    take(&mut message, id_length)?;
+
+    let payload = take(&mut message, payload_length)?;
+    let (code, rest) = payload.split_first().ok_or(ParseTagError::InvalidUri)?;
+
+    let prefix = match code {
+        0x00 => "",
+        _ => URI_PREFIXES
+            .iter()
+            .find(|(prefix_code, _)| prefix_code == code)
+            .map(|(_, prefix)| *prefix)
+            .ok_or(ParseTagError::InvalidUri)?,
+    };
+    let rest = std::str::from_utf8(rest).map_err(|_| ParseTagError::InvalidUri)?;
+
+    Ok((
+        format!("{}{}", prefix, rest),
+        header & MESSAGE_END != 0,
+        message,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn room_payload_round_trip() {
+        for room in [
+            "H201",
+            "FH Mu3",
+            "Sports Hall",
+            "FH TBC",
+            "@Museum (51.752000, -0.340000)",
+            "Remote (Teams)",
+        ] {
+            let payload = RoomPayload::new(room.parse().unwrap());
+            let encoded = payload.to_string();
+
+            assert!(encoded.bytes().all(|b| b.is_ascii_graphic()), "{}", encoded);
+            assert_eq!(encoded.parse(), Ok(payload));
+        }
+
+        let payload: RoomPayload = "fy:loc/H%32%30%31?x=y&v=1&z".parse().unwrap();
+        assert_eq!(payload.location(), "H201".parse().unwrap());
+        assert_eq!(payload.version(), 1);
+    }
+
+    #[cfg(feature = "nfc")]
+    #[test]
+    fn room_tag_ndef() {
+        let tag = RoomTag::new("FH S12".parse().unwrap());
+        let message = tag.to_ndef();
+
+        assert_eq!(&message[..4], [0xD1, 1, 20, b'U']);
+        assert_eq!(&message[4..], b"\x00fy:loc/FH%20S12?v=1");
+        assert_eq!(RoomTag::from_ndef(&message), Ok(tag.clone()));
+
+        let long = format!("https://www.example.com/{}", "a".repeat(300));
+        let tag = tag.with_booking_url(long.clone());
+        let message = tag.to_ndef();
+
+        assert_eq!(message[0], 0x91);
+        assert_eq!(message[24], 0x41);
+        assert_eq!(&message[30..32], [b'U', 0x02]);
+        assert_eq!(RoomTag::from_ndef(&message), Ok(tag));
+
+        assert_eq!(RoomTag::from_ndef(&[]), Err(ParseTagError::Truncated));
+        assert_eq!(
+            RoomTag::from_ndef(&message[..message.len() - 1]),
+            Err(ParseTagError::Truncated)
+        );
+        assert_eq!(
+            RoomTag::from_ndef(&[0xD2, 1, 1, b'U', 0]),
+            Err(ParseTagError::UnexpectedRecord)
+        );
+        assert_eq!(
+            RoomTag::from_ndef(&[0xD1, 1, 1, b'U', 0xFF]),
+            Err(ParseTagError::InvalidUri)
+        );
+        assert!(matches!(
+            RoomTag::from_ndef(&[0xD1, 1, 2, b'U', 0, b'x']),
+            Err(ParseTagError::Payload(ParsePayloadError::Scheme))
+        ));
+    }
+
+    #[test]
+    fn room_payload_errors() {
+        let parse = |s: &str| s.parse::<RoomPayload>().unwrap_err();
+
+        assert_eq!(parse("fy:room/H201?v=1"), ParsePayloadError::Scheme);
+        assert_eq!(parse("fy:loc/H201"), ParsePayloadError::Version);
+        assert_eq!(parse("fy:loc/H201?v="), ParsePayloadError::Version);
+        assert_eq!(parse("fy:loc/H201?v=0"), ParsePayloadError::Version);
+        assert_eq!(parse("fy:loc/H201?v=+1"), ParsePayloadError::Version);
+        assert_eq!(
+            parse("fy:loc/H201?v=2"),
+            ParsePayloadError::UnsupportedVersion(2)
+        );
+        assert_eq!(parse("fy:loc/FH S12?v=1"), ParsePayloadError::Encoding);
+        assert_eq!(parse("fy:loc/FH%2S12?v=1"), ParsePayloadError::Encoding);
+        assert_eq!(parse("fy:loc/%FF?v=1"), ParsePayloadError::Encoding);
+        assert!(matches!(
+            parse("fy:loc/H2001?v=1"),
+            ParsePayloadError::Location(_)
+        ));
+    }
+}
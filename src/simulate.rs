@@ -0,0 +1,415 @@
+//! Capacity planning: simulating whether the existing rooms could host the
+//! timetables of future years.
+//!
+//! A [`Scenario`] describes the demand of a future year -- the projected
+//! size of each cohort and the curriculum hours of each subject (*see
+//! [`Demand::cohort`]*). A [`Simulator`] lays the lessons of the scenario out
+//! across the two-week timetable, and checks whether the rooms of its
+//! [`Allocator`] could host them (*see [`Allocator::check`]*), highlighting
+//! the binding constraints (*see [`Shortfall`]*):
+//!
+//! ```
+//! use timetableau::allocate::{Allocator, Room};
+//! use timetableau::simulate::{Demand, Scenario, Simulator};
+//! use timetableau::Subject;
+//!
+//! let allocator = Allocator::new()
+//!     .room(Room::new("FH S1".parse().unwrap(), 30).equipment("Fume Cupboard"))
+//!     .room(Room::new("FH S2".parse().unwrap(), 30).equipment("Fume Cupboard"))
+//!     .room(Room::new("H201".parse().unwrap(), 30));
+//!
+//! let science = |students| {
+//!     Demand::cohort(Subject::new("Sc".to_string()).unwrap(), students, 30, 12)
+//!         .requires("Fume Cupboard")
+//! };
+//!
+//! let outcomes = Simulator::new(allocator).plan(&[
+//!     Scenario::new("2026/27").demand(science(240)),
+//!     Scenario::new("2027/28").demand(science(420)),
+//! ]);
+//!
+//! assert!(outcomes[0].feasible);
+//! assert_eq!(
+//!     outcomes[1].to_string(),
+//!     "2027/28: 2 more rooms with Fume Cupboard (seating 30) needed"
+//! );
+//! ```
+//!
+//! # Remarks
+//!
+//! The shortfalls are necessary conditions (i.e., each is a number of rooms
+//! which must be added in any timetable) -- the allocator additionally
+//! checks the placement of the simulated lessons, so a scenario may be
+//! infeasible without any shortfall (*see [`Outcome::conflicts`]*).
+
+use crate::allocate::{Allocator, Conflict, Request};
+use crate::{Class, RangedU8, Subject, TimeSlot, Timetable};
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+
+// The number of periods of the two-week timetable
+const PERIODS: u32 = 50;
+
+/// The lessons of a subject required by a [`Scenario`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Demand {
+    /// The subject of the lessons.
+    pub subject: Subject,
+
+    /// The number of classes taking the subject.
+    pub classes: u32,
+
+    /// The number of students in each class.
+    pub size: u32,
+
+    /// The number of periods of the subject each class is taught (over the
+    /// two-week timetable).
+    pub periods: u32,
+
+    /// The equipment the rooms of the lessons must have.
+    pub equipment: Vec<String>,
+}
+
+impl Demand {
+    /// Creates a new `Demand` of `classes` classes of `size` students, each
+    /// of which is taught `periods` periods of the `subject` (and which
+    /// require no equipment).
+    pub fn new(subject: Subject, classes: u32, size: u32, periods: u32) -> Self {
+        Self {
+            subject,
+            classes,
+            size,
+            periods,
+            equipment: Vec::new(),
+        }
+    }
+
+    /// Creates a new `Demand` for a cohort of `students` (e.g., a year
+    /// group's projected size), divided into the fewest classes of at most
+    /// `max_size` students, each of which is taught `periods` periods of the
+    /// `subject`.
+    pub fn cohort(subject: Subject, students: u32, max_size: u32, periods: u32) -> Self {
+        let classes = students.div_ceil(max_size.max(1));
+        let size = students.div_ceil(classes.max(1));
+
+        Self::new(subject, classes, size, periods)
+    }
+
+    /// Records that the lessons require the equipment with the `name`
+    /// provided (e.g., `Fume Cupboard` for science practicals).
+    pub fn requires(mut self, name: &str) -> Self {
+        self.equipment.push(name.to_string());
+        self
+    }
+
+    /// Calculates the number of lessons of the `Demand`.
+    pub fn lessons(&self) -> u32 {
+        self.classes * self.periods
+    }
+}
+
+/// The projected demand of a future year.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scenario {
+    /// The label of the scenario (e.g., `2027/28`).
+    pub label: String,
+
+    /// The demand of each subject.
+    pub demands: Vec<Demand>,
+}
+
+impl Scenario {
+    /// Creates a new `Scenario` (without any demand) with the `label`
+    /// provided.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            demands: Vec::new(),
+        }
+    }
+
+    /// Adds the `demand` of a subject to the `Scenario`.
+    pub fn demand(mut self, demand: Demand) -> Self {
+        self.demands.push(demand);
+        self
+    }
+}
+
+/// A binding constraint of a [`Scenario`]: more rooms with some equipment
+/// (and seating some number of students) are required than exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortfall {
+    /// The equipment the rooms must have (ordered by name).
+    pub equipment: Vec<String>,
+
+    /// The number of students the rooms must seat.
+    pub size: u32,
+
+    /// The number of such rooms required during the busiest period.
+    pub required: u32,
+
+    /// The number of such rooms which exist.
+    pub available: u32,
+}
+
+impl Shortfall {
+    /// Calculates the number of rooms which must be added.
+    pub fn rooms(&self) -> u32 {
+        self.required - self.available
+    }
+}
+
+impl Display for Shortfall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rooms = self.rooms();
+        write!(
+            f,
+            "{} more room{}",
+            rooms,
+            if rooms == 1 { "" } else { "s" }
+        )?;
+
+        if !self.equipment.is_empty() {
+            write!(f, " with {}", self.equipment.join(", "))?;
+        }
+
+        write!(f, " (seating {}) needed", self.size)
+    }
+}
+
+/// The result of simulating a [`Scenario`] (*see [`Simulator::simulate`]*).
+///
+/// The outcome is [displayed](Display) as the label of the scenario,
+/// followed by its shortfalls (or whether it is feasible).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The label of the scenario.
+    pub label: String,
+
+    /// Whether the existing rooms could host the lessons of the scenario.
+    pub feasible: bool,
+
+    /// The binding constraints of the scenario (ordered by their equipment,
+    /// and then by the size of their rooms).
+    pub shortfalls: Vec<Shortfall>,
+
+    /// The conflicts found by the allocator (i.e., the simulated lessons
+    /// for which no room could be allocated).
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.label)?;
+
+        if self.feasible {
+            return f.write_str("feasible");
+        }
+
+        if self.shortfalls.is_empty() {
+            return write!(f, "infeasible ({} conflicts)", self.conflicts.len());
+        }
+
+        for (i, shortfall) in self.shortfalls.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            Display::fmt(shortfall, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Simulates [`Scenario`]s against the rooms of an [`Allocator`].
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone)]
+pub struct Simulator {
+    allocator: Allocator,
+}
+
+impl Simulator {
+    /// Creates a new `Simulator` checking scenarios against the rooms (i.e.,
+    /// the capacities and equipment) of the `allocator` provided.
+    pub fn new(allocator: Allocator) -> Self {
+        Self { allocator }
+    }
+
+    /// Simulates the `scenario` provided.
+    ///
+    /// The lessons of the scenario are spread evenly across the periods of
+    /// the two-week timetable (with the most constrained lessons placed
+    /// first), and are then checked by the allocator.
+    pub fn simulate(&self, scenario: &Scenario) -> Outcome {
+        let requests = self.requests(scenario);
+        let conflicts = match self.allocator.check(&Timetable::new(), &requests) {
+            Ok(()) => Vec::new(),
+            Err(unsatisfiable) => unsatisfiable.conflicts,
+        };
+
+        Outcome {
+            label: scenario.label.clone(),
+            feasible: conflicts.is_empty(),
+            shortfalls: self.shortfalls(scenario),
+            conflicts,
+        }
+    }
+
+    /// Simulates each of the `scenarios` provided (e.g., the projections of
+    /// the next five years), in order.
+    pub fn plan(&self, scenarios: &[Scenario]) -> Vec<Outcome> {
+        scenarios
+            .iter()
+            .map(|scenario| self.simulate(scenario))
+            .collect()
+    }
+
+    // The requests of the lessons of the `scenario`, placed round-robin
+    fn requests(&self, scenario: &Scenario) -> Vec<Request> {
+        let mut demands: Vec<&Demand> = scenario.demands.iter().collect();
+        demands.sort_by_key(|demand| {
+            (
+                std::cmp::Reverse(demand.equipment.len()),
+                std::cmp::Reverse(demand.size),
+            )
+        });
+
+        let slots: Vec<TimeSlot> = RangedU8::all().map(TimeSlot::with_index).collect();
+        let mut requests = Vec::new();
+        let mut cursor = 0;
+
+        for demand in demands {
+            for class in 0..demand.classes {
+                let Ok(class) = Class::new(format!("{}/{}", demand.subject, class + 1)) else {
+                    continue;
+                };
+
+                for _ in 0..demand.periods {
+                    let slot = slots[cursor % slots.len()];
+                    cursor += 1;
+
+                    let mut request = Request::new(
+                        slot,
+                        demand.subject.clone(),
+                        class.clone(),
+                        None,
+                        demand.size,
+                    );
+                    request.equipment.clone_from(&demand.equipment);
+                    requests.push(request);
+                }
+            }
+        }
+
+        requests
+    }
+
+    // The rooms which must be added for each combination of equipment and
+    // size among the demands of the `scenario`
+    fn shortfalls(&self, scenario: &Scenario) -> Vec<Shortfall> {
+        let keys: BTreeSet<(Vec<String>, u32)> = scenario
+            .demands
+            .iter()
+            .map(|demand| (sorted(&demand.equipment), demand.size))
+            .collect();
+
+        keys.into_iter()
+            .filter_map(|(equipment, size)| {
+                // Every lesson requiring (at least) the equipment and seats
+                // must be held in such a room
+                let lessons: u32 = scenario
+                    .demands
+                    .iter()
+                    .filter(|demand| {
+                        demand.size >= size
+                            && equipment.iter().all(|name| demand.equipment.contains(name))
+                    })
+                    .map(Demand::lessons)
+                    .sum();
+                let available = self
+                    .allocator
+                    .rooms()
+                    .iter()
+                    .filter(|room| {
+                        room.capacity >= size
+                            && equipment.iter().all(|name| room.equipment.contains(name))
+                    })
+                    .count() as u32;
+
+                let required = lessons.div_ceil(PERIODS);
+
+                (required > available).then_some(Shortfall {
+                    equipment,
+                    size,
+                    required,
+                    available,
+                })
+            })
+            .collect()
+    }
+}
+
+// The `equipment` provided (ordered by name, and without duplicates)
+fn sorted(equipment: &[String]) -> Vec<String> {
+    let equipment: BTreeSet<&String> = equipment.iter().collect();
+    equipment.into_iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::allocate::Room;
+
+    #[test]
+    fn binding_constraints_found() {
+        let subject = |name: &str| Subject::new(name.to_string()).unwrap();
+        let simulator = Simulator::new(
+            Allocator::new()
+                .room(Room::new("FH S1".parse().unwrap(), 30).equipment("Fume Cupboard"))
+                .room(Room::new("FH S2".parse().unwrap(), 30).equipment("Fume Cupboard"))
+                .room(Room::new("H201".parse().unwrap(), 30))
+                .room(Room::new("H202".parse().unwrap(), 32)),
+        );
+
+        let demand = Demand::cohort(subject("En"), 250, 30, 8);
+        assert_eq!((demand.classes, demand.size), (9, 28));
+
+        let current = Scenario::new("2026/27")
+            .demand(Demand::cohort(subject("Sc"), 240, 30, 12).requires("Fume Cupboard"))
+            .demand(demand);
+        let outcome = simulator.simulate(&current);
+        assert!(outcome.feasible);
+        assert_eq!(outcome.to_string(), "2026/27: feasible");
+
+        // More scientists, and a class too large for every room
+        let future = Scenario::new("2027/28")
+            .demand(Demand::cohort(subject("Sc"), 330, 30, 12).requires("Fume Cupboard"))
+            .demand(Demand::new(subject("PE"), 1, 40, 4));
+        let outcome = simulator.simulate(&future);
+        assert!(!outcome.feasible);
+        assert_eq!(
+            outcome.shortfalls,
+            [
+                Shortfall {
+                    equipment: Vec::new(),
+                    size: 40,
+                    required: 1,
+                    available: 0,
+                },
+                Shortfall {
+                    equipment: vec!["Fume Cupboard".to_string()],
+                    size: 30,
+                    required: 3,
+                    available: 2,
+                },
+            ]
+        );
+        assert!(outcome
+            .conflicts
+            .iter()
+            .any(|conflict| matches!(conflict, Conflict::Capacity { .. })));
+    }
+}
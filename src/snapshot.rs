@@ -0,0 +1,143 @@
+use crate::index::Index;
+use crate::{Entry, EntryId, Location, Record, Room, RoomOverride, Timetable};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// An immutable snapshot of the state of a [`Timetable`] (*see
+/// [`Timetable::snapshot`]*).
+///
+/// A snapshot shares its entries, room overrides, and history with the
+/// timetable from which it was taken -- taking (or restoring) a snapshot
+/// never copies the lessons of the timetable. Should the timetable then be
+/// modified, the collections it modifies are copied once (i.e., the snapshot
+/// is unaffected by the modification), and are shared again once the
+/// snapshot is restored.
+#[derive(Debug, Clone)]
+pub struct Snapshot<L = Location> {
+    entries: Arc<BTreeMap<EntryId, Entry<L>>>,
+    next_id: u32,
+    overrides: Arc<Vec<RoomOverride<L>>>,
+    history: Arc<Vec<Record<L>>>,
+    index: Arc<Index<L>>,
+}
+
+impl<L> Snapshot<L> {
+    /// The number of entries in the timetable when the `Snapshot` was taken.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the timetable had no entries when the `Snapshot` was taken.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<L: Room> Timetable<L> {
+    /// Takes a [`Snapshot`] of the current state of the `Timetable` (e.g.,
+    /// before trying out changes in a "what-if" planning session), which can
+    /// later be [restored](Self::restore):
+    ///
+    /// ```
+    /// use timetableau::{timeslot, Activity, Timetable};
+    ///
+    /// let mut timetable = Timetable::new();
+    /// timetable.insert(timeslot!(W1MP1), Activity::Registration);
+    ///
+    /// let snapshot = timetable.snapshot();
+    ///
+    /// timetable.insert(timeslot!(W1MP2), Activity::Break);
+    /// assert_eq!(timetable.len(), 2);
+    ///
+    /// timetable.restore(&snapshot);
+    /// assert_eq!(timetable.len(), 1);
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// Taking a snapshot is a constant-time operation -- *see the [`Snapshot`]
+    /// documentation for more information*.
+    pub fn snapshot(&self) -> Snapshot<L> {
+        Snapshot {
+            entries: self.entries.clone(),
+            next_id: self.next_id,
+            overrides: self.overrides.clone(),
+            history: self.history.clone(),
+            index: self.index.clone(),
+        }
+    }
+
+    /// Restores the state of the `Timetable` recorded by the `snapshot`
+    /// provided (i.e., reverting every change made since it was taken).
+    ///
+    /// The same snapshot can be restored any number of times (e.g., to try
+    /// out several alternatives starting from the same timetable).
+    ///
+    /// # Remarks
+    ///
+    /// The [history](Self::history) of the timetable is also restored -- as
+    /// a result, restoring a snapshot is not itself recorded as a
+    /// [`Change`](crate::Change), and subscribers to the timetable are not
+    /// notified of it.
+    ///
+    /// Identifiers are never reused: entries inserted after restoring a
+    /// snapshot are not given the identifiers of entries which were inserted
+    /// (and then reverted) since it was taken.
+    pub fn restore(&mut self, snapshot: &Snapshot<L>) {
+        self.entries = snapshot.entries.clone();
+        self.next_id = self.next_id.max(snapshot.next_id);
+        self.overrides = snapshot.overrides.clone();
+        self.history = snapshot.history.clone();
+        self.index = snapshot.index.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Activity, Class, Subject};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn snapshot_restore_shares_state() {
+        let hall: Location = "Hall".parse().unwrap();
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+
+        let mut timetable = Timetable::new();
+        let id = timetable.insert(
+            timeslot!(W1MP1),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("10A".to_string()).unwrap(),
+                teacher: None,
+                location: "H201".parse().unwrap(),
+            },
+        );
+
+        let before = timetable.clone();
+        let snapshot = timetable.snapshot();
+
+        assert!(Arc::ptr_eq(&snapshot.entries, &timetable.entries));
+        assert_eq!(snapshot.len(), 1);
+
+        // Try out two alternatives from the same snapshot
+        timetable.relocate(id, hall);
+        timetable.override_room(timeslot!(W1MP1), date..=date, hall);
+        assert_eq!(snapshot.index.room(&hall), None);
+
+        timetable.restore(&snapshot);
+        assert_eq!(timetable, before);
+        assert_eq!(timetable.history().len(), 1);
+        assert_eq!(timetable.lessons().in_room(hall).count(), 0);
+
+        timetable.remove(id);
+        let inserted = timetable.insert(timeslot!(W1MP2), Activity::Break);
+
+        timetable.restore(&snapshot);
+        assert_eq!(timetable, before);
+        assert!(Arc::ptr_eq(&snapshot.entries, &timetable.entries));
+
+        // Identifiers of reverted entries are not reused
+        assert!(timetable.insert(timeslot!(W1MP2), Activity::Break) > inserted);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{Activity, Room, Timetable, Week, YearGroup};
+use std::collections::BTreeMap;
+
+/// The statistics of a single week of a student's timetable (*see
+/// [`StudentStats`]*).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WeekStats {
+    /// The number of periods without a lesson, on the days on which the
+    /// student has at least one lesson.
+    pub free_periods: usize,
+
+    /// The number of days on which the lessons either side of lunch (i.e.,
+    /// the fourth and fifth periods) take place at different sites, such
+    /// that part of lunch is spent travelling.
+    pub split_lunches: usize,
+
+    /// The largest number of consecutive periods with a lesson (breaks and
+    /// lunch do not interrupt a stretch).
+    pub longest_stretch: usize,
+
+    /// The number of times the student moves between sites from one lesson
+    /// to the next lesson of the same day.
+    pub site_transitions: usize,
+}
+
+/// The gap and contact-time statistics of a student's timetable, for each
+/// week of the two-week cycle.
+///
+/// *See [`Timetable::student_stats`] for more information*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StudentStats {
+    /// The statistics of each week (indexed by [`Week`], e.g.,
+    /// `weeks[Week::WeekTwo as usize]`).
+    pub weeks: [WeekStats; 2],
+}
+
+impl StudentStats {
+    /// Retrieves the statistics of the `week` provided.
+    pub fn week(&self, week: Week) -> &WeekStats {
+        &self.weeks[week as usize]
+    }
+}
+
+/// The statistics of a year group, averaged over its classes (*see
+/// [`Timetable::year_group_stats`]*).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct YearGroupStats {
+    /// The number of classes in the year group.
+    pub classes: usize,
+
+    /// The mean number of free periods per week.
+    pub free_periods: f64,
+
+    /// The mean number of split lunches per week.
+    pub split_lunches: f64,
+
+    /// The longest stretch of consecutive lessons of any class in the year
+    /// group.
+    pub longest_stretch: usize,
+
+    /// The mean number of site transitions per week.
+    pub site_transitions: f64,
+}
+
+impl<L: Room> Timetable<L> {
+    /// Calculates the gap and contact-time statistics (e.g., free periods
+    /// and site transitions) of a student -- either the lessons of the class
+    /// with the `reference` provided or, should the reference be [`None`],
+    /// every lesson of the timetable (i.e., the timetable is the timetable
+    /// of a single student).
+    ///
+    /// ```
+    /// use timetableau::{timeslot, Activity, Class, Subject, Timetable, Week};
+    ///
+    /// let lesson = |room: &str| Activity::Lesson {
+    ///     subject: Subject::new("Ma".to_string()).unwrap(),
+    ///     class: Class::new("10A".to_string()).unwrap(),
+    ///     teacher: None,
+    ///     location: room.parse().unwrap(),
+    /// };
+    ///
+    /// let mut timetable = Timetable::new();
+    /// timetable.insert(timeslot!(W1MP4), lesson("H201"));
+    /// timetable.insert(timeslot!(W1MP5), lesson("FH S13"));
+    ///
+    /// let stats = timetable.student_stats(Some("10A"));
+    /// let week = stats.week(Week::WeekOne);
+    ///
+    /// assert_eq!(week.free_periods, 3);
+    /// assert_eq!(week.split_lunches, 1);
+    /// assert_eq!(week.site_transitions, 1);
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// Several lessons during the same period (e.g., an option block) are
+    /// counted as a single period of contact time -- the first of them
+    /// (in the order in which they were inserted) determines the site at
+    /// which the student is taught.
+    pub fn student_stats(&self, reference: Option<&str>) -> StudentStats {
+        // The site of the student's lesson during each timeslot (if any)
+        let mut sites: [Option<&str>; 50] = [None; 50];
+
+        let lessons = match reference {
+            Some(reference) => self.lessons().for_class(reference),
+            None => self.lessons(),
+        };
+
+        for (_, entry) in lessons {
+            if let Activity::Lesson { location, .. } = &entry.activity {
+                // A lesson spanning several periods occupies each of them
+                for slot in entry.slots() {
+                    sites[slot.index()].get_or_insert(location.site());
+                }
+            }
+        }
+
+        let mut stats = StudentStats::default();
+
+        for (index, week) in stats.weeks.iter_mut().enumerate() {
+            for day in 0..5 {
+                let start = index * 25 + day * 5;
+                let periods = &sites[start..start + 5];
+
+                if periods.iter().all(Option::is_none) {
+                    continue;
+                }
+
+                week.free_periods += periods.iter().filter(|site| site.is_none()).count();
+
+                if let [.., Some(before), Some(after)] = periods {
+                    week.split_lunches += usize::from(before != after);
+                }
+
+                let mut stretch = 0;
+                let mut previous = None;
+
+                for site in periods {
+                    match site {
+                        Some(site) => {
+                            stretch += 1;
+                            week.longest_stretch = week.longest_stretch.max(stretch);
+
+                            if previous.is_some_and(|previous| previous != *site) {
+                                week.site_transitions += 1;
+                            }
+
+                            previous = Some(*site);
+                        }
+                        None => stretch = 0,
+                    }
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// Calculates the [statistics](Self::student_stats) of every class in the
+    /// timetable, and aggregates them by year group (e.g., for curriculum
+    /// review reports).
+    ///
+    /// The year group of a class is determined from its reference (*see
+    /// [`YearGroup::of_class`]*) -- classes whose references do not start
+    /// with a year group are disregarded.
+    pub fn year_group_stats(&self) -> BTreeMap<YearGroup, YearGroupStats> {
+        let mut classes: BTreeMap<YearGroup, Vec<&str>> = BTreeMap::new();
+
+        for (_, entry) in self.lessons() {
+            if let Activity::Lesson { class, .. } = &entry.activity {
+                if let Some(year) = YearGroup::of_class(class.reference()) {
+                    let references = classes.entry(year).or_default();
+
+                    if !references.contains(&class.reference().as_str()) {
+                        references.push(class.reference());
+                    }
+                }
+            }
+        }
+
+        classes
+            .into_iter()
+            .map(|(year, references)| {
+                let mut stats = YearGroupStats {
+                    classes: references.len(),
+                    ..YearGroupStats::default()
+                };
+
+                // Each class contributes two weeks to the means
+                let weeks = (references.len() * 2) as f64;
+
+                for reference in references {
+                    for week in self.student_stats(Some(reference)).weeks {
+                        stats.free_periods += week.free_periods as f64 / weeks;
+                        stats.split_lunches += week.split_lunches as f64 / weeks;
+                        stats.site_transitions += week.site_transitions as f64 / weeks;
+                        stats.longest_stretch = stats.longest_stretch.max(week.longest_stretch);
+                    }
+                }
+
+                (year, stats)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, Subject, TimeSlot};
+
+    fn lesson(class: &str, room: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: None,
+            location: room.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn student_and_year_group_stats() {
+        let mut timetable = Timetable::new();
+        let slots: [TimeSlot; 5] = [
+            timeslot!(W1MP1),
+            timeslot!(W1MP2),
+            timeslot!(W1MP3),
+            timeslot!(W1MP5),
+            timeslot!(W2FP3),
+        ];
+
+        for (slot, room) in slots
+            .into_iter()
+            .zip(["H201", "FH S13", "H201", "H201", "FH S13"])
+        {
+            timetable.insert(slot, lesson("10A", room));
+        }
+
+        // An option block taught at both sites during the same period
+        timetable.insert(timeslot!(W1MP1), lesson("10A", "FH S13"));
+        timetable.insert(timeslot!(W1TP1), lesson("Y10B", "H201"));
+        timetable.insert(timeslot!(W1TP1), lesson("Staff", "H201"));
+
+        let stats = timetable.student_stats(Some("10A"));
+
+        assert_eq!(
+            stats.week(Week::WeekOne),
+            &WeekStats {
+                free_periods: 1,
+                split_lunches: 0,
+                longest_stretch: 3,
+                site_transitions: 2,
+            }
+        );
+        assert_eq!(stats.week(Week::WeekTwo).free_periods, 4);
+        assert_eq!(
+            timetable
+                .student_stats(None)
+                .week(Week::WeekOne)
+                .free_periods,
+            5
+        );
+
+        let years = timetable.year_group_stats();
+
+        let ten = YearGroup::new(10).unwrap();
+
+        assert_eq!(years.keys().collect::<Vec<_>>(), [&ten]);
+        assert_eq!(years[&ten].classes, 2);
+        assert_eq!(years[&ten].longest_stretch, 3);
+        assert_eq!(years[&ten].free_periods, (1.0 + 4.0 + 4.0) / 4.0);
+        assert_eq!(years[&ten].site_transitions, 0.5);
+    }
+
+    #[test]
+    fn spanning_lessons_counted() {
+        let mut timetable = Timetable::new();
+
+        for slot in [
+            timeslot!(W1MP1),
+            timeslot!(W1MP2),
+            timeslot!(W1MP3),
+            timeslot!(W1MP5),
+        ] {
+            timetable.insert(slot, lesson("10A", "H201"));
+        }
+
+        // Lessons spanning every period of the Tuesday
+        for slot in [timeslot!(W1TP1), timeslot!(W1TP3)] {
+            timetable
+                .insert_spanning(slot, 2, lesson("10A", "H201"))
+                .unwrap();
+        }
+        timetable.insert(timeslot!(W1TP5), lesson("10A", "H201"));
+
+        let stats = timetable.student_stats(Some("10A"));
+
+        assert_eq!(stats.week(Week::WeekOne).free_periods, 1);
+        assert_eq!(stats.week(Week::WeekOne).longest_stretch, 5);
+    }
+}
@@ -0,0 +1,303 @@
+//! Suggestions of study spaces for the free periods of sixth-formers.
+//!
+//! A [`StudyPlanner`] knows the rooms in which students may study (e.g., the
+//! library, and any empty classroom), and uses the whole-school timetable
+//! (i.e., the utilisation of each room) to suggest the free rooms in which a
+//! student can spend each free period of their own timetable -- ranked by
+//! their proximity to the student's following lesson:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use timetableau::study::StudyPlanner;
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Subject, Term, Timetable, WeekCycle};
+//!
+//! let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+//! let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+//! let cycle = WeekCycle::new(date(4), calendar).unwrap();
+//!
+//! let lesson = |room: &str| Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new("12A".to_string()).unwrap(),
+//!     teacher: None,
+//!     location: room.parse().unwrap(),
+//! };
+//!
+//! // The student is free during the second period, before a lesson in H203
+//! let mut student = Timetable::new();
+//! student.insert(timeslot!(W1MP1), lesson("U101"));
+//! student.insert(timeslot!(W1MP3), lesson("H203"));
+//!
+//! // H204 is in use during the second period
+//! let mut school = student.clone();
+//! school.insert(timeslot!(W1MP2), lesson("H204"));
+//!
+//! let planner = StudyPlanner::without_classrooms(&school)
+//!     .space("H204".parse().unwrap())
+//!     .space("H202".parse().unwrap())
+//!     .space("FH S1".parse().unwrap());
+//! let suggestions = planner.suggest(&student, &cycle, date(4));
+//!
+//! assert_eq!(suggestions[0].slot, timeslot!(W1MP2));
+//! assert_eq!(suggestions[0].spaces[0], "H202".parse().unwrap());
+//! ```
+
+use crate::closures::{Closures, NoOccupancyData, Occupancy};
+use crate::{Activity, Location, LocationFilter, Period, RoomKind, TimeSlot, Timetable, WeekCycle};
+use chrono::NaiveDate;
+
+/// The study spaces suggested for a free period of a student (*see
+/// [`StudyPlanner::suggest`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StudySuggestion {
+    /// The timeslot of the free period.
+    pub slot: TimeSlot,
+
+    /// The location of the student's following lesson of the day (if any).
+    pub next: Option<Location>,
+
+    /// The free study spaces, nearest first.
+    pub spaces: Vec<Location>,
+}
+
+/// Suggests the study spaces in which students can spend their free
+/// periods.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone)]
+pub struct StudyPlanner<'a> {
+    school: &'a Timetable,
+    closures: Option<&'a Closures>,
+    spaces: Vec<Location>,
+    limit: usize,
+}
+
+impl<'a> StudyPlanner<'a> {
+    /// Creates a new `StudyPlanner` which suggests the empty classrooms of
+    /// either school, using the whole-`school` timetable provided to
+    /// determine which rooms are in use.
+    pub fn new(school: &'a Timetable) -> Self {
+        Self {
+            spaces: Location::all()
+                .of_kind(RoomKind::Classroom)
+                .existing()
+                .collect(),
+            ..Self::without_classrooms(school)
+        }
+    }
+
+    /// Creates a new `StudyPlanner` which only suggests the study spaces
+    /// added to it (*see [`Self::space`]*).
+    pub fn without_classrooms(school: &'a Timetable) -> Self {
+        Self {
+            school,
+            closures: None,
+            spaces: Vec::new(),
+            limit: 5,
+        }
+    }
+
+    /// Adds a designated study space (e.g., the library).
+    pub fn space(mut self, location: Location) -> Self {
+        if !self.spaces.contains(&location) {
+            self.spaces.push(location);
+        }
+
+        self
+    }
+
+    /// Sets the closures of the schools, such that closed rooms are not
+    /// suggested.
+    pub fn closures(mut self, closures: &'a Closures) -> Self {
+        self.closures = Some(closures);
+        self
+    }
+
+    /// Sets the largest number of study spaces suggested for each free
+    /// period (by default, `5`).
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Suggests the study spaces for each free period of the `student`'s
+    /// timetable on the `date` provided.
+    ///
+    /// A free period is a period of a school day without any activities, or
+    /// with [independent study](Activity::SchoolStudy), which is followed
+    /// (or preceded) by a lesson of the same day. The suggested spaces are
+    /// free (*see [`Closures::free_rooms`]*), and are ranked by their
+    /// [distance](Location::distance) to the following lesson (or, for the
+    /// free periods at the end of the day, to the preceding lesson).
+    pub fn suggest(
+        &self,
+        student: &Timetable,
+        cycle: &WeekCycle,
+        date: NaiveDate,
+    ) -> Vec<StudySuggestion> {
+        self.suggest_live(student, cycle, date, &NoOccupancyData)
+    }
+
+    /// Suggests the study spaces for each free period (*see
+    /// [`Self::suggest`]*), excluding any space which the `occupancy` data
+    /// reports as occupied (*see [`Closures::free_rooms_live`]*).
+    pub fn suggest_live<O>(
+        &self,
+        student: &Timetable,
+        cycle: &WeekCycle,
+        date: NaiveDate,
+        occupancy: &O,
+    ) -> Vec<StudySuggestion>
+    where
+        O: Occupancy + ?Sized,
+    {
+        let occurrences = student.occurrences(cycle, date);
+        let Some((week, day)) = cycle.day_for(date).filter(|_| !occurrences.is_empty()) else {
+            return Vec::new();
+        };
+
+        let none = Closures::new();
+        let closures = self.closures.unwrap_or(&none);
+
+        // The location of the lesson of each period (if any), and whether
+        // each period is free
+        let lessons: Vec<(Period, Option<Location>, bool)> = Period::ALL
+            .iter()
+            .map(|&period| {
+                let activities: Vec<&Activity> = occurrences
+                    .iter()
+                    .filter(|occurrence| occurrence.slot.period == period)
+                    .map(|occurrence| &occurrence.activity)
+                    .collect();
+
+                let location = activities
+                    .iter()
+                    .find_map(|activity| activity.location().copied());
+                let free = activities
+                    .iter()
+                    .all(|activity| **activity == Activity::SchoolStudy);
+
+                (period, location, free)
+            })
+            .collect();
+
+        let mut suggestions = Vec::new();
+
+        for (index, &(period, _, free)) in lessons.iter().enumerate() {
+            if !free {
+                continue;
+            }
+
+            let next = lessons[index + 1..]
+                .iter()
+                .find_map(|(_, location, _)| *location);
+            let Some(anchor) = next.or_else(|| {
+                lessons[..index]
+                    .iter()
+                    .rev()
+                    .find_map(|(_, location, _)| *location)
+            }) else {
+                continue;
+            };
+
+            let slot = TimeSlot { week, day, period };
+
+            let mut spaces = closures.free_rooms_live(
+                self.school,
+                cycle,
+                date,
+                slot,
+                self.spaces.iter().copied(),
+                occupancy,
+            );
+
+            // The sort is stable, so equidistant spaces remain in the order
+            // in which they were added
+            spaces.sort_by_key(|space| anchor.distance(space));
+            spaces.truncate(self.limit);
+
+            suggestions.push(StudySuggestion { slot, next, spaces });
+        }
+
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::closures::{Closure, ClosureScope};
+    use crate::{timeslot, AcademicCalendar, Class, Room, Subject, Term};
+    use std::collections::HashMap;
+
+    #[test]
+    fn study_suggestions() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 9, day).unwrap();
+        let calendar = AcademicCalendar::new(vec![Term::new(date(4), date(29)).unwrap()]);
+        let cycle = WeekCycle::new(date(4), calendar).unwrap();
+
+        let lesson = |room: &str| Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new("12A".to_string()).unwrap(),
+            teacher: None,
+            location: room.parse().unwrap(),
+        };
+
+        let mut student = Timetable::new();
+        student.insert(timeslot!(W1MP1), Activity::SchoolStudy);
+        student.insert(timeslot!(W1MP2), lesson("FH S12"));
+        student.insert(timeslot!(W1MP3), lesson("H201"));
+
+        let mut school = student.clone();
+        school.insert(timeslot!(W1MP4), lesson("H202"));
+
+        let mut closures = Closures::new();
+        closures.add(Closure {
+            scope: ClosureScope::Room("H203".parse().unwrap()),
+            start: date(4),
+            end: date(4),
+            reason: "Flooding".to_string(),
+        });
+
+        let planner = ["FH S13", "FH S11", "H203", "H204", "H202", "FH M1"]
+            .into_iter()
+            .fold(
+                StudyPlanner::without_classrooms(&school),
+                |planner, code| planner.space(code.parse().unwrap()),
+            )
+            .closures(&closures)
+            .limit(3);
+        let occupancy = HashMap::from([("FH S13".parse().unwrap(), true)]);
+        let suggestions = planner.suggest_live(&student, &cycle, date(4), &occupancy);
+
+        let spaces = |index: usize| -> Vec<String> {
+            suggestions[index]
+                .spaces
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        };
+
+        // The independent study is followed by a lesson at Fearnhill, and the
+        // last periods of the day follow a lesson in H201
+        assert_eq!(
+            suggestions.iter().map(|s| s.slot).collect::<Vec<_>>(),
+            [timeslot!(W1MP1), timeslot!(W1MP4), timeslot!(W1MP5)]
+        );
+        assert_eq!(suggestions[0].next, Some("FH S12".parse().unwrap()));
+        assert_eq!(spaces(0), ["FH S11", "FH M1", "H204"]);
+        assert_eq!(suggestions[1].next, None);
+        assert_eq!(spaces(1), ["H204", "FH S11", "FH M1"]);
+        assert_eq!(spaces(2), ["H202", "H204", "FH S11"]);
+
+        // Every existing classroom is a study space by default
+        assert!(
+            StudyPlanner::new(&school).suggest(&student, &cycle, date(4))[0]
+                .spaces
+                .iter()
+                .all(|space| space.kind() == RoomKind::Classroom)
+        );
+
+        // No suggestions are made for days without lessons
+        assert!(planner.suggest(&student, &cycle, date(5)).is_empty());
+    }
+}
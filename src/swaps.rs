@@ -0,0 +1,534 @@
+//! Suggestions of room changes which resolve clashes and closures.
+//!
+//! When a room is double-booked (e.g., a [finding](crate::validate::Finding)
+//! of the [`NoClashes`](crate::validate::NoClashes) rule) or closed (e.g., a
+//! lesson [affected](crate::closures::Affected) by a
+//! [`Closure`](crate::closures::Closure)), a [`SwapEngine`] suggests the
+//! changes of room which would resolve the [`Issue`] -- moving a lesson to a
+//! free room, or swapping the rooms of two lessons taking place at the same
+//! time (moving the other lesson on to a free room). Each [`Suggestion`] is scored (favouring rooms near to the original
+//! room, e.g., within the same block) and accompanied by its effect on the
+//! rules of a [`Validator`], such that a human can choose between them:
+//!
+//! ```
+//! use timetableau::swaps::{Issue, Resolution, SwapEngine};
+//! use timetableau::validate::{NoClashes, Rule};
+//! use timetableau::{timeslot, Activity, Class, Subject, Timetable};
+//!
+//! let lesson = |class: &str| Activity::Lesson {
+//!     subject: Subject::new("Ma".to_string()).unwrap(),
+//!     class: Class::new(class.to_string()).unwrap(),
+//!     teacher: None,
+//!     location: "H201".parse().unwrap(),
+//! };
+//!
+//! let mut timetable = Timetable::new();
+//! timetable.insert(timeslot!(W1MP1), lesson("10A"));
+//! let second = timetable.insert(timeslot!(W1MP1), lesson("10B"));
+//!
+//! let clash = NoClashes.check(&timetable).remove(0);
+//! let engine = SwapEngine::new(["H202", "FH M1"].map(|code| code.parse().unwrap()));
+//! let suggestions = engine.suggest_swaps(&timetable, &Issue::from(&clash));
+//!
+//! // Moving a lesson to the room next door is preferred
+//! assert!(matches!(
+//!     suggestions[0].resolution,
+//!     Resolution::Move { to, .. } if to == "H202".parse().unwrap()
+//! ));
+//! assert!(suggestions[0].impact.resolved.iter().any(|finding| finding.entries.contains(&second)));
+//! ```
+
+use crate::closures::{Affected, ClosureScope, Closures};
+use crate::validate::{Finding, NoClashes, Severity, Validator};
+use crate::{Entry, EntryId, Location, Room, Timetable};
+
+// The cost of each finding introduced by a suggestion (by severity), which
+// outweighs the distance between any two rooms of the same school
+const INFO: u32 = 0;
+const WARNING: u32 = 200;
+const ERROR: u32 = 2000;
+
+/// A problem with the rooms of a timetable which a [`SwapEngine`] can
+/// resolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// Lessons which share a room during the same timeslot (*see
+    /// [`NoClashes`]*).
+    Clash(Vec<EntryId>),
+
+    /// A lesson whose room is closed (*see [`Closures::affected`]*) -- each
+    /// room within the scope is considered closed.
+    Closed {
+        /// The entry of the affected lesson.
+        entry: EntryId,
+
+        /// The rooms which are closed.
+        scope: ClosureScope,
+    },
+}
+
+impl From<&Finding> for Issue {
+    fn from(finding: &Finding) -> Self {
+        Self::Clash(finding.entries.clone())
+    }
+}
+
+impl Issue {
+    /// Creates a new [`Issue::Closed`] from a lesson `affected` by one of the
+    /// `closures` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should the affected lesson have no entry (e.g., a suspension
+    /// of the timetable), or should its closure not be a part of the
+    /// `closures`.
+    pub fn closed(affected: &Affected, closures: &Closures) -> Option<Self> {
+        Some(Self::Closed {
+            entry: affected.occurrence.entry?,
+            scope: closures.closures().get(affected.closure)?.scope,
+        })
+    }
+
+    // Retrieve the entries of the lessons involved in the issue
+    fn entries(&self) -> Vec<EntryId> {
+        match self {
+            Self::Clash(entries) => entries.clone(),
+            Self::Closed { entry, .. } => vec![*entry],
+        }
+    }
+
+    // Whether the issue is resolved within the `timetable` provided
+    fn is_resolved(&self, timetable: &Timetable) -> bool {
+        match self {
+            Self::Clash(entries) => entries.iter().all(|id| {
+                let Some(entry) = timetable.get(*id) else {
+                    return true;
+                };
+
+                // No other lesson during any of its periods (including one
+                // spanning them, but started earlier) shares any of the rooms
+                // (including the rooms across which either class is split)
+                entry
+                    .locations()
+                    .filter(|location| location.kind().is_occupied())
+                    .all(|location| {
+                        !timetable.lessons().any(|(other, lesson)| {
+                            other != *id
+                                && lesson.slots().any(|slot| entry.covers(slot))
+                                && lesson.locations().any(|room| room == location)
+                        })
+                    })
+            }),
+            Self::Closed { entry, scope } => {
+                let Some(affected) = timetable.get(*entry) else {
+                    return true;
+                };
+
+                // No lesson during any of its periods (including one swapped
+                // into the closed room) remains within the scope
+                !timetable.lessons().any(|(_, entry)| {
+                    entry.slots().any(|slot| affected.covers(slot))
+                        && entry.locations().any(|location| scope.contains(location))
+                })
+            }
+        }
+    }
+}
+
+/// A change of room which would resolve an [`Issue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Move the lesson to a free room.
+    Move {
+        /// The entry of the lesson.
+        entry: EntryId,
+
+        /// The free room.
+        to: Location,
+    },
+
+    /// Swap the rooms of two lessons taking place during the same timeslot,
+    /// moving the other lesson on to a free room (e.g., a practical lesson
+    /// taking the laboratory of a lesson which does not need it).
+    Swap {
+        /// The entry of the lesson involved in the issue, which takes the
+        /// room of the other lesson.
+        first: EntryId,
+
+        /// The entry of the other lesson.
+        second: EntryId,
+
+        /// The free room to which the other lesson is moved.
+        to: Location,
+    },
+}
+
+impl Resolution {
+    /// Retrieves the entries of the lessons whose rooms would change.
+    pub fn entries(&self) -> Vec<EntryId> {
+        match self {
+            Self::Move { entry, .. } => vec![*entry],
+            Self::Swap { first, second, .. } => vec![*first, *second],
+        }
+    }
+
+    /// Applies the resolution to the `timetable` provided (through
+    /// [`Timetable::relocate`], such that it can be undone).
+    pub fn apply(&self, timetable: &mut Timetable) {
+        match *self {
+            Self::Move { entry, to } => {
+                timetable.relocate(entry, to);
+            }
+            Self::Swap { first, second, to } => {
+                if let Some(location) = timetable.relocate(second, to) {
+                    timetable.relocate(first, location);
+                }
+            }
+        }
+    }
+}
+
+/// The effect of a [`Resolution`] upon the rules of a [`Validator`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Impact {
+    /// The findings which the resolution would resolve.
+    pub resolved: Vec<Finding>,
+
+    /// The findings which the resolution would introduce (e.g., a class too
+    /// large for the room to which it is moved).
+    pub introduced: Vec<Finding>,
+}
+
+/// A suggested [`Resolution`] of an [`Issue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The change of room.
+    pub resolution: Resolution,
+
+    /// The score of the suggestion (lower is better): the distance between
+    /// the original and new room of each lesson moved, plus a penalty for
+    /// each finding introduced (*see [`Impact::introduced`]*).
+    pub score: u32,
+
+    /// The effect of the resolution upon the rules of the validator.
+    pub impact: Impact,
+}
+
+/// Suggests changes of room which resolve [`Issue`]s.
+///
+/// *See the [module](self) documentation for more information*.
+pub struct SwapEngine {
+    candidates: Vec<Location>,
+    validator: Validator,
+}
+
+impl SwapEngine {
+    /// Creates a new `SwapEngine` which can move lessons to any of the
+    /// `candidates` provided, and which validates its suggestions against
+    /// the [`NoClashes`] rule.
+    pub fn new<I>(candidates: I) -> Self
+    where
+        I: IntoIterator<Item = Location>,
+    {
+        Self {
+            candidates: candidates.into_iter().collect(),
+            validator: Validator::new().rule(NoClashes),
+        }
+    }
+
+    /// Sets the `validator` whose rules determine the
+    /// [`Impact`] of each suggestion (e.g., including a
+    /// [`RoomCapacity`](crate::validate::RoomCapacity) rule).
+    pub fn validator(mut self, validator: Validator) -> Self {
+        self.validator = validator;
+        self
+    }
+
+    /// Suggests the changes of room which would resolve the `issue` within
+    /// the `timetable` provided, ordered by their [score](Suggestion::score).
+    ///
+    /// Each lesson involved in the issue may be moved to any of the
+    /// candidates which is free during each of its periods (and which is not
+    /// closed by the issue), or may take the room of any other lesson taking
+    /// place during any of them (which is moved to such a candidate in turn).
+    /// Only the changes which would resolve the issue are suggested.
+    ///
+    /// # Remarks
+    ///
+    /// The suggestions are not applied to the timetable -- *see
+    /// [`Resolution::apply`]*. Clashes of teachers cannot be resolved by a
+    /// change of room, and so never have any suggestions.
+    pub fn suggest_swaps(&self, timetable: &Timetable, issue: &Issue) -> Vec<Suggestion> {
+        let before = self.validator.validate(timetable).findings;
+        let mut resolutions = Vec::new();
+
+        // A candidate is not free should it be any of the rooms of another
+        // lesson during any of the periods of an entry (including a lesson
+        // spanning them, but started earlier, and the rooms across which its
+        // class is split)
+        let is_free = |id: EntryId, entry: &Entry, candidate: &Location| {
+            entry.locations().all(|room| room != candidate)
+                && timetable.lessons().all(|(other, lesson)| {
+                    other == id
+                        || !lesson.slots().any(|slot| entry.covers(slot))
+                        || lesson.locations().all(|room| room != candidate)
+                })
+        };
+
+        for id in issue.entries() {
+            let Some(entry) = timetable.get(id) else {
+                continue;
+            };
+            let Some(location) = entry.activity.location() else {
+                continue;
+            };
+
+            let others: Vec<_> = timetable
+                .lessons()
+                .filter(|(other, lesson)| {
+                    *other != id && lesson.slots().any(|slot| entry.covers(slot))
+                })
+                .collect();
+
+            let free: Vec<Location> = self
+                .candidates
+                .iter()
+                .copied()
+                .filter(|candidate| is_free(id, entry, candidate))
+                .filter(|candidate| match issue {
+                    Issue::Closed { scope, .. } => !scope.contains(candidate),
+                    Issue::Clash(_) => true,
+                })
+                .collect();
+
+            resolutions.extend(
+                free.iter()
+                    .map(|to| Resolution::Move { entry: id, to: *to }),
+            );
+
+            // The other lesson may span periods besides those of the entry,
+            // during each of which the room to which it is moved must be free
+            for (other, lesson) in others.iter().filter(|(_, lesson)| {
+                lesson
+                    .activity
+                    .location()
+                    .is_some_and(|room| room != location)
+            }) {
+                resolutions.extend(free.iter().filter(|to| is_free(*other, lesson, to)).map(
+                    |to| Resolution::Swap {
+                        first: id,
+                        second: *other,
+                        to: *to,
+                    },
+                ));
+            }
+        }
+
+        let mut suggestions: Vec<Suggestion> = resolutions
+            .into_iter()
+            .filter_map(|resolution| {
+                let mut candidate = timetable.clone();
+                resolution.apply(&mut candidate);
+
+                if !issue.is_resolved(&candidate) {
+                    return None;
+                }
+
+                let after = self.validator.validate(&candidate).findings;
+                let impact = Impact {
+                    resolved: before
+                        .iter()
+                        .filter(|finding| !after.contains(finding))
+                        .cloned()
+                        .collect(),
+                    introduced: after
+                        .iter()
+                        .filter(|finding| !before.contains(finding))
+                        .cloned()
+                        .collect(),
+                };
+
+                let distance: u32 = resolution
+                    .entries()
+                    .into_iter()
+                    .filter_map(|id| {
+                        let from = timetable.get(id)?.activity.location()?;
+                        let to = candidate.get(id)?.activity.location()?;
+
+                        Some(from.distance(to))
+                    })
+                    .sum();
+                let penalty: u32 = impact
+                    .introduced
+                    .iter()
+                    .map(|finding| match finding.severity {
+                        Severity::Info => INFO,
+                        Severity::Warning => WARNING,
+                        Severity::Error => ERROR,
+                    })
+                    .sum();
+
+                Some(Suggestion {
+                    resolution,
+                    score: distance + penalty,
+                    impact,
+                })
+            })
+            .collect();
+
+        // The sort is stable, so suggestions of equal scores remain in the
+        // order of the entries and candidates
+        suggestions.sort_by_key(|suggestion| suggestion.score);
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Activity, Class, HighfieldBlock, Subject};
+
+    fn lesson(class: &str, room: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: None,
+            location: room.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn swaps_suggested() {
+        let mut timetable = Timetable::new();
+        let closed = timetable.insert(timeslot!(W1MP1), lesson("10A", "H201"));
+        let other = timetable.insert(timeslot!(W1MP1), lesson("10B", "FH M1"));
+        timetable.insert(timeslot!(W1MP2), lesson("10C", "H202"));
+
+        let candidates = ["H202", "H203", "P105"].map(|code| code.parse().unwrap());
+        let engine = SwapEngine::new(candidates);
+
+        // The Howard block is closed, so the lesson is moved to the nearest
+        // free room outside of it, or takes the room of the lesson at
+        // Fearnhill
+        let issue = Issue::Closed {
+            entry: closed,
+            scope: ClosureScope::Block(HighfieldBlock::Howard),
+        };
+        let suggestions = engine.suggest_swaps(&timetable, &issue);
+
+        assert_eq!(
+            suggestions
+                .iter()
+                .map(|suggestion| suggestion.resolution)
+                .collect::<Vec<_>>(),
+            [
+                Resolution::Move {
+                    entry: closed,
+                    to: "P105".parse().unwrap()
+                },
+                Resolution::Swap {
+                    first: closed,
+                    second: other,
+                    to: "P105".parse().unwrap()
+                },
+            ]
+        );
+        assert!(suggestions[0].score < suggestions[1].score);
+
+        // A clash is resolved by moving either lesson, and resolves the
+        // finding of the validator
+        let clash = timetable.insert(timeslot!(W1MP2), lesson("10D", "H202"));
+        let finding = crate::validate::Rule::check(&NoClashes, &timetable).remove(0);
+        let suggestions = engine.suggest_swaps(&timetable, &Issue::from(&finding));
+
+        assert_eq!(suggestions[0].impact.resolved, [finding]);
+        assert!(suggestions[0].impact.introduced.is_empty());
+        assert!(suggestions.iter().any(|suggestion| suggestion.resolution
+            == Resolution::Move {
+                entry: clash,
+                to: "H203".parse().unwrap()
+            }));
+
+        suggestions[0].resolution.apply(&mut timetable);
+
+        assert!(crate::validate::Rule::check(&NoClashes, &timetable).is_empty());
+    }
+
+    #[test]
+    fn split_rooms_occupied() {
+        let mut timetable = Timetable::new();
+        let split = timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP1), lesson("10A", "H201"))
+                    .split_room("H202".parse().unwrap()),
+            )
+            .unwrap();
+        let other = timetable.insert(timeslot!(W1MP1), lesson("10B", "H202"));
+        timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP1), lesson("10C", "H204"))
+                    .split_room("H203".parse().unwrap()),
+            )
+            .unwrap();
+
+        let engine = SwapEngine::new(["H202", "H203", "P105"].map(|code| code.parse().unwrap()));
+
+        // The clash is within a split room, and so is not yet resolved
+        let finding = crate::validate::Rule::check(&NoClashes, &timetable).remove(0);
+        let issue = Issue::from(&finding);
+        assert!(!issue.is_resolved(&timetable));
+
+        // Neither the split rooms of another lesson are free, nor does moving
+        // the split lesson from its own room resolve the clash
+        let suggestions = engine.suggest_swaps(&timetable, &issue);
+        assert!(!suggestions.is_empty());
+        assert!(suggestions.iter().all(|suggestion| !matches!(
+            suggestion.resolution,
+            Resolution::Move { to, .. } | Resolution::Swap { to, .. }
+                if to == "H203".parse().unwrap()
+        )));
+        assert!(suggestions.iter().all(|suggestion| !matches!(
+            suggestion.resolution,
+            Resolution::Move { entry, .. } if entry == split
+        )));
+        assert!(suggestions.iter().any(|suggestion| suggestion.resolution
+            == Resolution::Move {
+                entry: other,
+                to: "P105".parse().unwrap()
+            }));
+    }
+
+    #[test]
+    fn spanning_lessons_occupied() {
+        let mut timetable = Timetable::new();
+        timetable
+            .insert_spanning(timeslot!(W1MP3), 2, lesson("10A", "H203"))
+            .unwrap();
+        timetable.insert(timeslot!(W1MP4), lesson("10B", "H201"));
+        let clash = timetable.insert(timeslot!(W1MP4), lesson("10C", "H201"));
+
+        let engine = SwapEngine::new(["H202", "H203"].map(|code| code.parse().unwrap()));
+
+        // The double started during the third period, but still occupies its
+        // room during the fourth
+        let finding = crate::validate::Rule::check(&NoClashes, &timetable).remove(0);
+        let suggestions = engine.suggest_swaps(&timetable, &Issue::from(&finding));
+
+        assert!(suggestions.iter().all(|suggestion| !matches!(
+            suggestion.resolution,
+            Resolution::Move { to, .. } | Resolution::Swap { to, .. }
+                if to == "H203".parse().unwrap()
+        )));
+        assert!(suggestions.iter().any(|suggestion| suggestion.resolution
+            == Resolution::Move {
+                entry: clash,
+                to: "H202".parse().unwrap()
+            }));
+
+        // Nor is a clash with the later period of a double resolved
+        let issue = Issue::Clash(vec![clash]);
+        timetable.relocate(clash, "H203".parse().unwrap());
+
+        assert!(!issue.is_resolved(&timetable));
+    }
+}
@@ -0,0 +1,331 @@
+//! Conflict-free replication of draft timetables between devices.
+//!
+//! Deputy heads edit the draft timetable on laptops which are frequently
+//! offline -- each laptop holds a [`Replica`] of the timetable, which is
+//! edited locally and [merged](Replica::merge) with the replica of another
+//! laptop whenever the two can communicate (directly, or via a shared
+//! folder), without requiring a central server:
+//!
+//! ```
+//! use timetableau::sync::Replica;
+//! use timetableau::{timeslot, Activity, Timetable};
+//!
+//! let mut office = Replica::new("office");
+//! let registration = office.insert(timeslot!(W1MP1), Activity::Registration);
+//!
+//! // The laptop is given a copy of the draft before going offline
+//! let mut laptop = office.fork("laptop");
+//! laptop.move_entry(&registration, timeslot!(W1MP2)).unwrap();
+//! office.insert(timeslot!(W1TP1), Activity::Break);
+//!
+//! office.merge(&laptop);
+//! laptop.merge(&office);
+//!
+//! assert_eq!(office.timetable(), laptop.timetable());
+//! assert_eq!(office.entry(&registration).unwrap().slot, timeslot!(W1MP2));
+//! ```
+//!
+//! # Conflict resolution
+//!
+//! A replica is a map from the identifier of each entry (*see [`ElementId`]*)
+//! to its latest value (or to its removal), where each value is stamped with
+//! a Lamport timestamp (*see [`Stamp`]*). Merging two replicas keeps, for
+//! each entry, the value with the greatest stamp -- concurrent edits of the
+//! same entry are resolved in favour of the edit with the greater counter,
+//! and then in favour of the replica with the greater identifier, such that
+//! every replica converges to the same timetable regardless of the order in
+//! which they are merged.
+//!
+//! # Remarks
+//!
+//! Edits of different entries never conflict, even should they produce a
+//! clash (e.g., two lessons being moved into the same room at the same time
+//! on different laptops) -- the merged timetable should be checked by a
+//! [`Validator`](crate::validate::Validator) like any other draft.
+
+use crate::timetable::span;
+use crate::{Activity, Entry, Location, TimeSlot, Timetable, TimetableError};
+use std::collections::BTreeMap;
+
+/// A Lamport timestamp, which orders the edits made to the entries of
+/// [`Replica`]s.
+///
+/// Stamps are ordered by their counters, and then by the identifiers of the
+/// replicas which made the edits.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Stamp {
+    /// The logical time of the edit.
+    pub counter: u64,
+
+    /// The identifier of the replica which made the edit.
+    pub replica: String,
+}
+
+/// The identifier of an entry of a [`Replica`], which is unique across every
+/// replica.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ElementId {
+    /// The identifier of the replica into which the entry was inserted.
+    pub replica: String,
+
+    /// The number of entries inserted into the replica before the entry.
+    pub serial: u32,
+}
+
+/// The latest value of an entry of a [`Replica`] (i.e., [`None`] should the
+/// entry have been removed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Element {
+    stamp: Stamp,
+    entry: Option<Entry>,
+}
+
+/// A replica of a timetable which can be edited offline.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replica {
+    id: String,
+    clock: u64,
+    serial: u32,
+    elements: BTreeMap<ElementId, Element>,
+}
+
+impl Replica {
+    /// Creates a new, empty `Replica` with the identifier `id` (e.g., the
+    /// name of the device), which should be unique among the replicas with
+    /// which it is merged.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            clock: 0,
+            serial: 0,
+            elements: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a new `Replica` with the identifier `id` containing every
+    /// entry of the `timetable` provided (e.g., the draft timetable imported
+    /// from the school management system).
+    pub fn from_timetable(id: impl Into<String>, timetable: &Timetable) -> Self {
+        let mut replica = Self::new(id);
+
+        for (_, entry) in timetable.entries() {
+            replica.insert_entry(entry.clone());
+        }
+
+        replica
+    }
+
+    /// Creates a copy of the `Replica` with the identifier `id` (e.g., for a
+    /// laptop which is about to go offline).
+    pub fn fork(&self, id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            serial: 0,
+            ..self.clone()
+        }
+    }
+
+    /// Retrieves the identifier of the `Replica`.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Retrieves the entry with the identifier `id`, should it not have been
+    /// removed.
+    pub fn entry(&self, id: &ElementId) -> Option<&Entry> {
+        self.elements.get(id)?.entry.as_ref()
+    }
+
+    /// Creates an iterator over the entries of the `Replica` (ordered by
+    /// their identifiers).
+    pub fn entries(&self) -> impl Iterator<Item = (&ElementId, &Entry)> + '_ {
+        self.elements
+            .iter()
+            .filter_map(|(id, element)| Some((id, element.entry.as_ref()?)))
+    }
+
+    /// Materialises the `Replica` as a [`Timetable`], inserting its entries in
+    /// the order of their identifiers (such that merged replicas produce
+    /// equal timetables).
+    pub fn timetable(&self) -> Timetable {
+        let mut timetable = Timetable::new();
+
+        for (_, entry) in self.entries() {
+            timetable.push_entry(entry.clone());
+        }
+
+        timetable
+    }
+
+    /// Schedules an `activity` for the `slot` provided.
+    ///
+    /// # Returns
+    ///
+    /// The identifier of the newly created entry.
+    pub fn insert(&mut self, slot: TimeSlot, activity: Activity) -> ElementId {
+        self.insert_entry(Entry::new(slot, activity))
+    }
+
+    // Schedule an entry (retaining its span and staffing)
+    fn insert_entry(&mut self, entry: Entry) -> ElementId {
+        let id = ElementId {
+            replica: self.id.clone(),
+            serial: self.serial,
+        };
+
+        self.serial += 1;
+        self.write(id.clone(), Some(entry));
+        id
+    }
+
+    /// Removes the entry with the identifier `id`.
+    ///
+    /// # Returns
+    ///
+    /// Whether the `Replica` contained the entry.
+    pub fn remove(&mut self, id: &ElementId) -> bool {
+        if self.entry(id).is_none() {
+            return false;
+        }
+
+        self.write(id.clone(), None);
+        true
+    }
+
+    /// Moves the entry with the identifier `id` to the `slot` provided
+    /// (retaining the number of periods it spans).
+    ///
+    /// # Returns
+    ///
+    /// The slot in which the entry was previously scheduled, or [`None`] if
+    /// the `Replica` does not contain the entry.
+    ///
+    /// # Errors
+    ///
+    /// [`TimetableError::InvalidSpan`] is returned (and the entry is left
+    /// unchanged) should the span of the entry not fit the `slot` -- *see
+    /// [`Timetable::move_entry`]*.
+    pub fn move_entry(
+        &mut self,
+        id: &ElementId,
+        slot: TimeSlot,
+    ) -> Result<Option<TimeSlot>, TimetableError> {
+        let Some(entry) = self.entry(id) else {
+            return Ok(None);
+        };
+        let mut entry = entry.clone();
+
+        span(slot, entry.periods.get())?;
+
+        let from = std::mem::replace(&mut entry.slot, slot);
+
+        self.write(id.clone(), Some(entry));
+        Ok(Some(from))
+    }
+
+    /// Changes the [`Location`] of the lesson with the identifier `id`.
+    ///
+    /// # Returns
+    ///
+    /// The previous location of the lesson, or [`None`] if the `Replica` does
+    /// not contain a [lesson](Activity::Lesson) with the identifier `id`.
+    pub fn relocate(&mut self, id: &ElementId, location: Location) -> Option<Location> {
+        let mut entry = self.entry(id)?.clone();
+
+        let Activity::Lesson { location: room, .. } = &mut entry.activity else {
+            return None;
+        };
+        let from = std::mem::replace(room, location);
+
+        self.write(id.clone(), Some(entry));
+        Some(from)
+    }
+
+    /// Merges the `remote` replica into the `Replica`, keeping the latest
+    /// value of each entry (*see the [module](self#conflict-resolution)
+    /// documentation*).
+    ///
+    /// Merging is commutative, associative, and idempotent -- replicas which
+    /// have merged the same edits (in any order) contain the same entries.
+    pub fn merge(&mut self, remote: &Replica) {
+        for (id, element) in &remote.elements {
+            match self.elements.get(id) {
+                Some(local) if local.stamp >= element.stamp => {}
+                _ => {
+                    self.elements.insert(id.clone(), element.clone());
+                }
+            }
+        }
+
+        // Subsequent local edits supersede every edit which has been seen
+        self.clock = self.clock.max(remote.clock);
+
+        if remote.id == self.id {
+            self.serial = self.serial.max(remote.serial);
+        }
+    }
+
+    /// Records the `entry` (or removal) of the entry with the identifier
+    /// `id`, stamped after every edit the `Replica` has seen.
+    fn write(&mut self, id: ElementId, entry: Option<Entry>) {
+        self.clock += 1;
+
+        let stamp = Stamp {
+            counter: self.clock,
+            replica: self.id.clone(),
+        };
+        self.elements.insert(id, Element { stamp, entry });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, Subject};
+
+    #[test]
+    fn concurrent_edits_converge() {
+        let mut draft = Timetable::new();
+        draft.insert(
+            timeslot!(W1MP1),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("10A".to_string()).unwrap(),
+                teacher: None,
+                location: "H201".parse().unwrap(),
+            },
+        );
+
+        let office = Replica::from_timetable("office", &draft);
+        let lesson = office.entries().next().unwrap().0.clone();
+
+        let mut alice = office.fork("alice");
+        let mut bob = office.fork("bob");
+
+        // Both relocate the lesson concurrently -- bob's edit wins the tie
+        alice.relocate(&lesson, "H202".parse().unwrap());
+        bob.relocate(&lesson, "P105".parse().unwrap());
+        let added = alice.insert(timeslot!(W1MP2), Activity::Registration);
+        assert_eq!(added.replica, "alice");
+
+        let (mut first, mut second) = (alice.clone(), bob.clone());
+        first.merge(&bob);
+        second.merge(&alice);
+        assert_eq!(first.timetable(), second.timetable());
+        assert_eq!(
+            first.entry(&lesson).unwrap().activity.location(),
+            Some(&"P105".parse().unwrap())
+        );
+
+        // An edit made after a merge supersedes the edits it has seen
+        first.remove(&added);
+        second.merge(&first);
+        second.merge(&first);
+        assert!(second.entry(&added).is_none());
+        assert_eq!(second.timetable(), first.timetable());
+        assert_eq!(second.entries().count(), 1);
+        assert!(!second.remove(&added));
+    }
+}
@@ -0,0 +1,388 @@
+//! Hosting the datasets of several schools (i.e., tenants) in one process.
+//!
+//! The consortium back-end serves the Highfield school, the Fearnhill school,
+//! and the sixth form from a single process -- each tenant's state is held
+//! by a [`DatasetHandle`] (its dataset, its rooms, the students of its
+//! classes, and its caches), such that nothing is shared between tenants
+//! through process-wide state. [`Tenants`] contains every handle, and
+//! answers the queries which span tenants (e.g., the lessons of a sixth
+//! former taught at both schools):
+//!
+//! ```
+//! use timetableau::attendance::Roster;
+//! use timetableau::tenancy::{DatasetHandle, Tenants};
+//! use timetableau::{timeslot, AcademicCalendar, Activity, Class, Dataset, Subject, Timetable};
+//!
+//! let dataset = |slot, location: &str| {
+//!     let mut timetable = Timetable::new();
+//!     timetable.insert(slot, Activity::Lesson {
+//!         subject: Subject::new("Ma".to_string()).unwrap(),
+//!         class: Class::new("12A".to_string()).unwrap(),
+//!         teacher: None,
+//!         location: location.parse().unwrap(),
+//!     });
+//!     Dataset::new(timetable, AcademicCalendar::default())
+//! };
+//!
+//! let mut roster = Roster::new();
+//! roster.enrol("12A", "1001");
+//!
+//! let mut tenants = Tenants::new();
+//! tenants.insert(DatasetHandle::new("highfield", dataset(timeslot!(W1MP1), "H201")).roster(roster.clone()));
+//! tenants.insert(DatasetHandle::new("fearnhill", dataset(timeslot!(W1MP3), "FH M1")).roster(roster));
+//!
+//! assert_eq!(tenants.spanning_students(), vec!["1001"]);
+//!
+//! let lessons = tenants.lessons_of("1001");
+//! assert_eq!(lessons.iter().map(|lesson| lesson.tenant).collect::<Vec<_>>(), ["highfield", "fearnhill"]);
+//! ```
+//!
+//! # Remarks
+//!
+//! A handle never consults the process-wide database of
+//! [known](RoomRegistry::known) rooms (*see [`RoomRegistry::set_known`]*) --
+//! each handle has its own registry (the [curated](RoomRegistry::curated)
+//! database, unless another is provided), against which
+//! [`DatasetHandle::exists`] checks its rooms.
+
+use crate::attendance::Roster;
+use crate::cache::Cache;
+use crate::{Activity, Dataset, Entry, EntryId, Location, Room, RoomKind, RoomRegistry, TimeSlot};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+
+// The number of minutes for which the free rooms of a slot are cached
+const FREE_ROOMS_MINUTES: i64 = 5;
+
+/// The state of a single tenant (e.g., a school).
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone)]
+pub struct DatasetHandle {
+    tenant: String,
+    dataset: Dataset,
+    rooms: RoomRegistry,
+    roster: Roster,
+    free_rooms: Cache<TimeSlot, Vec<Location>>,
+}
+
+impl DatasetHandle {
+    /// Creates a new `DatasetHandle` of the `dataset` of the `tenant`
+    /// provided (e.g., `highfield`), with the curated registry of rooms and
+    /// no students.
+    pub fn new(tenant: &str, dataset: Dataset) -> Self {
+        Self {
+            tenant: tenant.to_string(),
+            dataset,
+            rooms: RoomRegistry::curated(),
+            roster: Roster::new(),
+            free_rooms: Cache::new(Duration::minutes(FREE_ROOMS_MINUTES)),
+        }
+    }
+
+    /// Sets the registry of the rooms of the tenant.
+    pub fn rooms(mut self, rooms: RoomRegistry) -> Self {
+        self.rooms = rooms;
+        self.free_rooms.clear();
+        self
+    }
+
+    /// Sets the students of the classes of the tenant.
+    pub fn roster(mut self, roster: Roster) -> Self {
+        self.roster = roster;
+        self
+    }
+
+    /// Retrieves the identifier of the tenant.
+    pub fn tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    /// Retrieves the dataset of the tenant.
+    pub fn dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+
+    /// Retrieves the registry of the rooms of the tenant.
+    pub fn registry(&self) -> &RoomRegistry {
+        &self.rooms
+    }
+
+    /// Retrieves the students of the classes of the tenant.
+    pub fn students(&self) -> &Roster {
+        &self.roster
+    }
+
+    /// Determines whether the `location` provided exists at the tenant
+    /// (i.e., whether it is in the tenant's registry of rooms).
+    pub fn exists(&self, location: &Location) -> bool {
+        self.rooms.contains(location)
+    }
+
+    /// Edits the dataset of the tenant, discarding the cached results which
+    /// are invalidated by the changes made to its timetable.
+    ///
+    /// # Returns
+    ///
+    /// The value returned by `f`.
+    pub fn edit<T>(&mut self, f: impl FnOnce(&mut Dataset) -> T) -> T {
+        let start = self.dataset.timetable.history().len();
+        let value = f(&mut self.dataset);
+
+        match self.dataset.timetable.history().get(start..) {
+            Some(records) => {
+                for record in records {
+                    self.free_rooms.notify(record);
+                }
+            }
+            // The timetable was replaced, so nothing cached can be trusted
+            None => self.free_rooms.clear(),
+        }
+
+        value
+    }
+
+    /// Retrieves the rooms of the tenant's registry which host no lesson
+    /// during the `slot` provided (ordered by their room codes), which are
+    /// cached until the slot is changed (or for 5 minutes from `now`).
+    pub fn free_rooms(&mut self, slot: TimeSlot, now: DateTime<Utc>) -> &[Location] {
+        let (dataset, rooms) = (&self.dataset, &self.rooms);
+
+        self.free_rooms.get_or_insert_with(slot, now, || {
+            let used: Vec<&Location> = dataset
+                .timetable
+                .at(slot)
+                .flat_map(|(_, entry)| entry.locations())
+                .collect();
+
+            let mut free: Vec<Location> = rooms
+                .iter()
+                .filter(|location| location.kind() != RoomKind::Remote)
+                .filter(|location| !used.contains(location))
+                .copied()
+                .collect();
+
+            free.sort_by_cached_key(Location::to_string);
+            free
+        })
+    }
+}
+
+/// A lesson of one of several [`Tenants`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TenantLesson<'a> {
+    /// The identifier of the tenant of the lesson.
+    pub tenant: &'a str,
+
+    /// The identifier of the lesson's entry in the tenant's timetable.
+    pub id: EntryId,
+
+    /// The lesson's entry.
+    pub entry: &'a Entry,
+}
+
+/// The [`DatasetHandle`]s of every tenant of a process.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Default)]
+pub struct Tenants {
+    handles: Vec<DatasetHandle>,
+}
+
+impl Tenants {
+    /// Creates a new `Tenants` without any tenants.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts the `handle` provided, replacing the handle of the same
+    /// tenant (should there be one).
+    ///
+    /// # Returns
+    ///
+    /// The replaced handle (if any).
+    pub fn insert(&mut self, handle: DatasetHandle) -> Option<DatasetHandle> {
+        match self
+            .handles
+            .iter_mut()
+            .find(|other| other.tenant == handle.tenant)
+        {
+            Some(other) => Some(std::mem::replace(other, handle)),
+            None => {
+                self.handles.push(handle);
+                None
+            }
+        }
+    }
+
+    /// Removes (and returns) the handle of the `tenant` provided.
+    pub fn remove(&mut self, tenant: &str) -> Option<DatasetHandle> {
+        let index = self
+            .handles
+            .iter()
+            .position(|handle| handle.tenant == tenant)?;
+        Some(self.handles.remove(index))
+    }
+
+    /// Retrieves the handle of the `tenant` provided.
+    pub fn get(&self, tenant: &str) -> Option<&DatasetHandle> {
+        self.handles.iter().find(|handle| handle.tenant == tenant)
+    }
+
+    /// Retrieves the handle of the `tenant` provided (e.g., to edit its
+    /// dataset).
+    pub fn get_mut(&mut self, tenant: &str) -> Option<&mut DatasetHandle> {
+        self.handles
+            .iter_mut()
+            .find(|handle| handle.tenant == tenant)
+    }
+
+    /// Creates an iterator over the handles (in the order in which their
+    /// tenants were inserted).
+    pub fn iter(&self) -> impl Iterator<Item = &DatasetHandle> + '_ {
+        self.handles.iter()
+    }
+
+    /// Retrieves the students enrolled in the classes of more than one
+    /// tenant (e.g., sixth formers taking a subject at the other school), in
+    /// alphabetical order.
+    pub fn spanning_students(&self) -> Vec<&str> {
+        let mut tenants: BTreeMap<&str, usize> = BTreeMap::new();
+
+        for handle in &self.handles {
+            for student in handle.roster.enrolled() {
+                *tenants.entry(student).or_default() += 1;
+            }
+        }
+
+        tenants
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(student, _)| student)
+            .collect()
+    }
+
+    /// Retrieves the lessons of the `student` provided across every tenant
+    /// (i.e., the lessons of the classes in which each tenant enrols them),
+    /// ordered by their timeslots and then by the order of their tenants.
+    pub fn lessons_of(&self, student: &str) -> Vec<TenantLesson<'_>> {
+        let mut lessons = Vec::new();
+
+        for handle in &self.handles {
+            let classes: BTreeSet<&str> = handle.roster.classes_of(student).collect();
+
+            for (id, entry) in handle.dataset.timetable.entries() {
+                let Activity::Lesson { class, .. } = &entry.activity else {
+                    continue;
+                };
+
+                if classes.contains(class.reference().as_str()) {
+                    lessons.push(TenantLesson {
+                        tenant: &handle.tenant,
+                        id,
+                        entry,
+                    });
+                }
+            }
+        }
+
+        // The sort is stable, so the tenants remain in order
+        lessons.sort_by_key(|lesson| lesson.entry.slot.index());
+        lessons
+    }
+
+    /// Retrieves the pairs of lessons of the `student` provided which take
+    /// place at different tenants during the same timeslot (i.e., clashes
+    /// which neither tenant can detect alone).
+    pub fn clashes_of(&self, student: &str) -> Vec<(TenantLesson<'_>, TenantLesson<'_>)> {
+        let lessons = self.lessons_of(student);
+        let mut clashes = Vec::new();
+
+        for (index, first) in lessons.iter().enumerate() {
+            for second in &lessons[index + 1..] {
+                let overlaps = second.entry.slots().any(|slot| first.entry.covers(slot));
+
+                if overlaps && second.tenant != first.tenant {
+                    clashes.push((*first, *second));
+                }
+            }
+        }
+
+        clashes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, Subject, Timetable};
+
+    #[test]
+    fn tenants_isolated() {
+        let lesson = |class: &str, location: &str| Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: None,
+            location: location.parse().unwrap(),
+        };
+
+        let rooms: RoomRegistry = ["H201", "H202"]
+            .map(|code| code.parse().unwrap())
+            .into_iter()
+            .collect();
+        let mut highfield = DatasetHandle::new(
+            "highfield",
+            Dataset::new(Timetable::new(), AcademicCalendar::default()),
+        )
+        .rooms(rooms);
+
+        let now = Utc::now();
+        assert_eq!(highfield.free_rooms(timeslot!(W1MP1), now).len(), 2);
+
+        // Edits invalidate the cache of the handle
+        highfield.edit(|dataset| {
+            dataset
+                .timetable
+                .insert(timeslot!(W1MP1), lesson("12A", "H201"))
+        });
+        assert_eq!(
+            highfield.free_rooms(timeslot!(W1MP1), now),
+            ["H202".parse().unwrap()]
+        );
+        assert!(!highfield.exists(&"H105".parse().unwrap()));
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("12B", "FH M1"));
+        timetable.insert(timeslot!(W1MP2), lesson("12B", "FH M1"));
+        let fearnhill = DatasetHandle::new(
+            "fearnhill",
+            Dataset::new(timetable, AcademicCalendar::default()),
+        );
+
+        let mut roster = Roster::new();
+        roster.enrol("12A", "1001");
+        roster.enrol("12A", "1002");
+        let highfield = highfield.roster(roster);
+
+        let mut roster = Roster::new();
+        roster.enrol("12B", "1001");
+        let fearnhill = fearnhill.roster(roster);
+
+        let mut tenants = Tenants::new();
+        assert!(tenants.insert(highfield).is_none());
+        assert!(tenants.insert(fearnhill.clone()).is_none());
+        assert!(tenants.insert(fearnhill).is_some());
+
+        assert_eq!(tenants.spanning_students(), vec!["1001"]);
+        assert_eq!(tenants.lessons_of("1001").len(), 3);
+        assert_eq!(tenants.lessons_of("1002").len(), 1);
+
+        let clashes = tenants.clashes_of("1001");
+        assert_eq!(clashes.len(), 1);
+        assert_eq!(
+            (clashes[0].0.tenant, clashes[0].1.tenant),
+            ("highfield", "fearnhill")
+        );
+        assert!(tenants.clashes_of("1002").is_empty());
+    }
+}
@@ -0,0 +1,315 @@
+//! Golden files for the exporters whose output is promised to be stable,
+//! such that third-party parsers of the formats (and refactors of the
+//! exporters) can be verified against the exact bytes produced.
+//!
+//! Every [`Golden`] is produced from the same inputs -- the canonical
+//! [`DATASET`] and the [`ROSTER`] of its students -- exported with fixed
+//! parameters (*see [`Golden::render`]*), and is bundled with the crate:
+//!
+//! | Golden          | Format | Exporter                                         |
+//! |-----------------|--------|--------------------------------------------------|
+//! | `dataset.json`  | JSON   | [`Dataset::to_json`]                             |
+//! | `timetable.ics` | ICS    | [`IcsExporter`]                                  |
+//! | `register.csv`  | CSV    | [`Register::to_csv`]                             |
+//! | `timetable.xml` | XML    | `XmlExporter` (only with the `xml` feature)      |
+//!
+//! A parser of one of the formats should parse the output of its golden and
+//! compare the result against the inputs, whereas a reimplementation of an
+//! exporter should [check](Golden::check) its output against the golden:
+//!
+//! ```
+//! use timetableau::testkit;
+//!
+//! for golden in testkit::goldens() {
+//!     // Our own exporters always reproduce their goldens
+//!     assert_eq!(golden.check(&golden.render()), Ok(()));
+//! }
+//!
+//! let golden = testkit::golden("register.csv").unwrap();
+//! let mismatch = golden.check("Date,Period\r\n").unwrap_err();
+//!
+//! assert_eq!(mismatch.line, 1);
+//! ```
+//!
+//! # Remarks
+//!
+//! The goldens only change should the output of an exporter change -- such a
+//! change is deliberate, and the goldens should be regenerated (using
+//! [`Golden::render`]) as a part of the same change.
+
+use crate::attendance::{Register, Roster};
+use crate::csv;
+use crate::export::ics::IcsExporter;
+use crate::{Dataset, WeekCycle};
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// The canonical [`Dataset`] from which every golden is produced.
+pub const DATASET: &str = include_str!("../data/golden/dataset.json");
+
+/// The students enrolled in each class of the [`DATASET`], as a CSV file
+/// with a `Class` and a `Student` column.
+pub const ROSTER: &str = include_str!("../data/golden/roster.csv");
+
+/// The format of a [`Golden`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// An iCalendar feed (as described by RFC 5545).
+    Ics,
+
+    /// Comma-separated values (as described by RFC 4180).
+    Csv,
+
+    /// A JSON document.
+    Json,
+
+    /// An XML document.
+    Xml,
+}
+
+impl Format {
+    /// Retrieves the file extension of the `Format` (e.g., `ics`).
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ics => "ics",
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Xml => "xml",
+        }
+    }
+}
+
+/// The expected output of an exporter for the [`DATASET`].
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, Copy)]
+pub struct Golden {
+    /// The file name of the golden (e.g., `timetable.ics`).
+    pub name: &'static str,
+
+    /// The format of the golden.
+    pub format: Format,
+
+    /// The exact output expected of the exporter.
+    pub output: &'static str,
+
+    render: fn(&Dataset, &Roster) -> String,
+}
+
+impl Golden {
+    /// Exports the [`DATASET`] (and [`ROSTER`]) using this crate's exporter
+    /// for the `Golden`.
+    ///
+    /// The exporters are given the following parameters:
+    ///
+    /// * The week cycle is anchored on Monday `2023-09-04`, and the dates
+    ///   exported are `2023-09-04` to `2023-09-08` (i.e., the first week
+    ///   one of the calendar).
+    ///
+    /// * The `DTSTAMP` of the ICS feed is `2023-09-01 00:00:00 UTC`, with
+    ///   alarms `5` minutes before same-site lessons and `15` minutes
+    ///   before cross-site lessons.
+    ///
+    /// * Every other parameter is the default of its exporter.
+    pub fn render(&self) -> String {
+        (self.render)(&dataset(), &roster())
+    }
+
+    /// Checks the `actual` output of an exporter against the `Golden`.
+    ///
+    /// # Errors
+    ///
+    /// [`Mismatch`] is returned (describing the first line which differs)
+    /// should the `actual` output not be byte-for-byte identical to the
+    /// output of the golden.
+    pub fn check(&self, actual: &str) -> Result<(), Mismatch> {
+        if actual == self.output {
+            return Ok(());
+        }
+
+        // Split on `\n` only, such that differing line endings are reported
+        let mut expected_lines = self.output.split('\n');
+        let mut actual_lines = actual.split('\n');
+        let mut line = 1;
+
+        loop {
+            match (expected_lines.next(), actual_lines.next()) {
+                (Some(expected), Some(actual)) if expected == actual => line += 1,
+                (expected, actual) => {
+                    return Err(Mismatch {
+                        golden: self.name,
+                        line,
+                        expected: expected.map(str::to_string),
+                        actual: actual.map(str::to_string),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// A difference between the output of an exporter and a [`Golden`] (*see
+/// [`Golden::check`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The name of the golden.
+    pub golden: &'static str,
+
+    /// The first line (starting from `1`) which differs.
+    pub line: usize,
+
+    /// The line of the golden (including any trailing `\r`), or [`None`]
+    /// should the output be longer than the golden.
+    pub expected: Option<String>,
+
+    /// The line of the output (including any trailing `\r`), or [`None`]
+    /// should the output be shorter than the golden.
+    pub actual: Option<String>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let line = |line: &Option<String>| match line {
+            Some(line) => format!("{:?}", line),
+            None => "the end of the output".to_string(),
+        };
+
+        write!(
+            f,
+            "line {} of `{}` differs: expected {}, found {}",
+            self.line,
+            self.golden,
+            line(&self.expected),
+            line(&self.actual)
+        )
+    }
+}
+
+impl Error for Mismatch {}
+
+/// Retrieves every [`Golden`] (ordered by the table of the [module](self)
+/// documentation).
+pub fn goldens() -> Vec<Golden> {
+    #[cfg_attr(not(feature = "xml"), allow(unused_mut))]
+    let mut goldens = vec![
+        Golden {
+            name: "dataset.json",
+            format: Format::Json,
+            output: DATASET,
+            render: |dataset, _| dataset.to_json(),
+        },
+        Golden {
+            name: "timetable.ics",
+            format: Format::Ics,
+            output: include_str!("../data/golden/timetable.ics"),
+            render: |dataset, _| {
+                IcsExporter::new()
+                    .stamp(Utc.with_ymd_and_hms(2023, 9, 1, 0, 0, 0).unwrap())
+                    .alarms(Some(Duration::minutes(5)), Some(Duration::minutes(15)))
+                    .export(&dataset.timetable, &cycle(dataset), dates())
+            },
+        },
+        Golden {
+            name: "register.csv",
+            format: Format::Csv,
+            output: include_str!("../data/golden/register.csv"),
+            render: |dataset, roster| {
+                Register::new(&dataset.timetable, &cycle(dataset), dates(), roster).to_csv()
+            },
+        },
+    ];
+
+    #[cfg(feature = "xml")]
+    goldens.push(Golden {
+        name: "timetable.xml",
+        format: Format::Xml,
+        output: include_str!("../data/golden/timetable.xml"),
+        render: |dataset, _| crate::export::xml::XmlExporter::new().export(&dataset.timetable),
+    });
+
+    goldens
+}
+
+/// Retrieves the [`Golden`] with the `name` provided (e.g., `timetable.ics`).
+pub fn golden(name: &str) -> Option<Golden> {
+    goldens().into_iter().find(|golden| golden.name == name)
+}
+
+/// Checks every exporter of this crate against its [`Golden`].
+///
+/// # Errors
+///
+/// [`Mismatch`] is returned for the first golden which is not reproduced
+/// exactly.
+pub fn conformance() -> Result<(), Mismatch> {
+    goldens()
+        .iter()
+        .try_for_each(|golden| golden.check(&golden.render()))
+}
+
+/// Parses the [`DATASET`].
+///
+/// # Panics
+///
+/// This function panics should the bundled dataset be invalid (which is
+/// prevented by the tests of this crate).
+pub fn dataset() -> Dataset {
+    Dataset::from_json(DATASET).expect("the golden dataset is valid")
+}
+
+/// Parses the [`ROSTER`].
+pub fn roster() -> Roster {
+    let mut roster = Roster::new();
+
+    for (_, fields) in csv::parse(ROSTER).iter().skip(1) {
+        if let [class, student] = fields.as_slice() {
+            roster.enrol(class, student);
+        }
+    }
+
+    roster
+}
+
+// The week cycle of the golden dataset
+fn cycle(dataset: &Dataset) -> WeekCycle {
+    WeekCycle::new(date(4), dataset.calendar.clone()).unwrap()
+}
+
+// The dates exported by the goldens
+fn dates() -> RangeInclusive<NaiveDate> {
+    date(4)..=date(8)
+}
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2023, 9, day).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn goldens_reproduced() {
+        // Regenerate with `TIMETABLEAU_BLESS=1 cargo test --all-features`
+        if std::env::var_os("TIMETABLEAU_BLESS").is_some() {
+            for golden in goldens() {
+                let path = format!("{}/data/golden/{}", env!("CARGO_MANIFEST_DIR"), golden.name);
+                fs::write(path, golden.render()).unwrap();
+            }
+        }
+
+        assert_eq!(conformance(), Ok(()));
+        assert_eq!(roster().students("10A"), ["100001", "100002"]);
+
+        let golden = golden("timetable.ics").unwrap();
+        let mismatch = golden
+            .check(&golden.output.replace("\r\n", "\n"))
+            .unwrap_err();
+
+        assert_eq!(mismatch.line, 1);
+        assert_eq!(mismatch.expected.as_deref(), Some("BEGIN:VCALENDAR\r"));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::RangedU8;
+use chrono::{Duration, NaiveTime, Timelike};
+use std::fmt::{self, Display, Formatter};
+use std::ops::Sub;
+
+/// A time of day, to the minute (e.g., the time at which a bell rings).
+///
+/// Unlike a [`NaiveTime`], a `TimeOfDay` has no seconds (or leap seconds) --
+/// the hours and minutes are [ranged integers](RangedU8), such that an
+/// invalid time cannot be represented:
+///
+/// ```
+/// use timetableau::TimeOfDay;
+///
+/// let time = TimeOfDay::new(8, 50).unwrap();
+///
+/// assert_eq!(time.to_string(), "08:50");
+/// assert_eq!(time.minutes(), 530);
+/// assert_eq!(TimeOfDay::new(24, 0), None);
+/// ```
+///
+/// # Remarks
+///
+/// Converting a [`NaiveTime`] into a `TimeOfDay` discards its seconds (i.e.,
+/// `08:50:59` becomes `08:50`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeOfDay {
+    hour: RangedU8<0, 23>,
+    minute: RangedU8<0, 59>,
+}
+
+impl TimeOfDay {
+    /// Creates a new `TimeOfDay`.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if the `hour` is in the range `0..=23` and the `minute` is in
+    /// the range `0..=59`, or [`None`] otherwise.
+    pub const fn new(hour: u8, minute: u8) -> Option<Self> {
+        match (RangedU8::new(hour), RangedU8::new(minute)) {
+            (Some(hour), Some(minute)) => Some(Self { hour, minute }),
+            _ => None,
+        }
+    }
+
+    /// Creates a new `TimeOfDay` from the number of `minutes` since midnight.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if the `minutes` are in the range `0..=1439`, or [`None`]
+    /// otherwise.
+    pub const fn from_minutes(minutes: u16) -> Option<Self> {
+        if minutes >= 24 * 60 {
+            return None;
+        }
+
+        Self::new((minutes / 60) as u8, (minutes % 60) as u8)
+    }
+
+    /// Retrieves the hour of the `TimeOfDay` (in the range `0..=23`).
+    pub const fn hour(self) -> u8 {
+        self.hour.get()
+    }
+
+    /// Retrieves the minute of the `TimeOfDay` (in the range `0..=59`).
+    pub const fn minute(self) -> u8 {
+        self.minute.get()
+    }
+
+    /// Retrieves the number of minutes since midnight of the `TimeOfDay`.
+    pub const fn minutes(self) -> u16 {
+        self.hour() as u16 * 60 + self.minute() as u16
+    }
+}
+
+impl Display for TimeOfDay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour(), self.minute())
+    }
+}
+
+// The time elapsed from the other time until this one (which is negative
+// should the other time be later)
+impl Sub for TimeOfDay {
+    type Output = Duration;
+
+    fn sub(self, other: Self) -> Duration {
+        Duration::minutes(self.minutes() as i64 - other.minutes() as i64)
+    }
+}
+
+impl From<NaiveTime> for TimeOfDay {
+    fn from(time: NaiveTime) -> Self {
+        // The hour and minute of a `NaiveTime` are always within range
+        Self::new(time.hour() as u8, time.minute() as u8).unwrap()
+    }
+}
+
+impl From<TimeOfDay> for NaiveTime {
+    fn from(time: TimeOfDay) -> Self {
+        NaiveTime::from_hms_opt(time.hour() as u32, time.minute() as u32, 0).unwrap()
+    }
+}
+
+/// The time range during which something takes place on a day (e.g., a
+/// [`Period`](crate::Period), or lunch).
+///
+/// A `PeriodTime` includes its `start` but **not** its `end` (e.g., a period
+/// from `08:50` to `09:50` does not contain `09:50`), such that consecutive
+/// periods neither overlap nor leave a gap between them:
+///
+/// ```
+/// use timetableau::{PeriodTime, TimeOfDay};
+///
+/// let hm = |hour, minute| TimeOfDay::new(hour, minute).unwrap();
+/// let first = PeriodTime::new(hm(8, 50), hm(9, 50));
+/// let second = PeriodTime::new(hm(9, 50), hm(10, 50));
+///
+/// assert_eq!(first.duration(), 60);
+/// assert!(first.contains(hm(9, 49)));
+/// assert!(!first.contains(hm(9, 50)));
+/// assert!(!first.overlaps(second));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PeriodTime {
+    /// The time at which the range starts (inclusive).
+    pub start: TimeOfDay,
+
+    /// The time at which the range ends (exclusive).
+    pub end: TimeOfDay,
+}
+
+impl PeriodTime {
+    /// Creates a new `PeriodTime` from `start` (inclusive) to `end`
+    /// (exclusive).
+    ///
+    /// # Remarks
+    ///
+    /// The range is not required to start before it ends -- such a range is
+    /// [empty](Self::is_empty).
+    pub const fn new(start: TimeOfDay, end: TimeOfDay) -> Self {
+        Self { start, end }
+    }
+
+    /// Retrieves the duration of the `PeriodTime` in minutes (which is `0`
+    /// should the range be empty).
+    pub const fn duration(self) -> u16 {
+        self.end.minutes().saturating_sub(self.start.minutes())
+    }
+
+    /// Whether the `PeriodTime` does not start before it ends (i.e., it
+    /// contains no times).
+    pub fn is_empty(self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Whether the `time` provided falls within the `PeriodTime`.
+    pub fn contains(self, time: TimeOfDay) -> bool {
+        time >= self.start && time < self.end
+    }
+
+    /// Whether the `PeriodTime` shares any time with the `other` one.
+    pub fn overlaps(self, other: PeriodTime) -> bool {
+        !self.is_empty() && !other.is_empty() && self.start < other.end && other.start < self.end
+    }
+
+    /// Retrieves the `(start, end)` times of the `PeriodTime` as
+    /// [`NaiveTime`]s (e.g., such that they can be combined with a date).
+    pub fn to_naive(self) -> (NaiveTime, NaiveTime) {
+        (self.start.into(), self.end.into())
+    }
+}
+
+impl Display for PeriodTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hm(hour: u8, minute: u8) -> TimeOfDay {
+        TimeOfDay::new(hour, minute).unwrap()
+    }
+
+    #[test]
+    fn period_time_ranges() {
+        let lunch = PeriodTime::new(hm(13, 10), hm(13, 55));
+
+        assert_eq!(lunch.duration(), 45);
+        assert_eq!(lunch.to_string(), "13:10-13:55");
+        assert!(lunch.contains(hm(13, 10)));
+        assert!(!lunch.contains(hm(13, 55)));
+
+        assert!(lunch.overlaps(PeriodTime::new(hm(13, 50), hm(14, 0))));
+        assert!(!lunch.overlaps(PeriodTime::new(hm(13, 55), hm(14, 55))));
+        assert!(!lunch.overlaps(PeriodTime::new(hm(13, 30), hm(13, 30))));
+
+        let empty = PeriodTime::new(hm(10, 0), hm(9, 0));
+        assert!(empty.is_empty());
+        assert_eq!(empty.duration(), 0);
+
+        assert_eq!(hm(9, 50) - hm(10, 5), Duration::minutes(-15));
+        assert_eq!(TimeOfDay::from_minutes(1439), Some(hm(23, 59)));
+        assert_eq!(TimeOfDay::from_minutes(1440), None);
+        assert_eq!(
+            TimeOfDay::from(NaiveTime::from_hms_opt(8, 50, 59).unwrap()),
+            hm(8, 50)
+        );
+    }
+}
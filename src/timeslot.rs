@@ -1,12 +1,16 @@
-use crate::RangedU8;
+use crate::{BellSchedule, RangedU8};
 use chrono::prelude::*;
 use num_traits::FromPrimitive;
 use std::fmt::Debug;
 
 /// The week of a alternating two-week timetable.
 ///
+/// A timetable which repeats every week only uses [`Week::WeekOne`] -- *see
+/// [`CycleLength`](crate::CycleLength) for the cycles which can be mapped
+/// onto the weeks of a timetable*.
+///
 /// *See the [`crate`] documentation for more information*.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Week {
     // Assign the variants integer values such that they can be cast into
     // integers (for mathematical purposes)
@@ -17,7 +21,7 @@ pub enum Week {
 /// A period for a day.
 ///
 /// *See the [`crate`] documentation for more information*.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Period {
     // Assign the variants integer values such that they can be cast into
     // integers (for mathematical purposes)
@@ -41,37 +45,37 @@ pub enum Period {
 }
 
 impl Period {
+    /// Every `Period` of a day in chronological order.
+    pub const ALL: [Period; 5] = [
+        Period::First,
+        Period::Second,
+        Period::Third,
+        Period::Fourth,
+        Period::Fifth,
+    ];
+
     /// Creates a new `Period` based on the `time` provided -- if the `time`
     /// provided corresponds to a `Period`, that `Period` will be returned,
     /// otherwise [`None`] will be returned.
     ///
     /// *See the [`crate`] documentation for more information*.
+    ///
+    /// # Remarks
+    ///
+    /// The times used are those of the Highfield school -- use
+    /// [`BellSchedule::period_at`] to use the times of another school.
     pub fn from_time(time: NaiveTime) -> Option<Self> {
-        // `time.hour() * 60 + time.minute()` calculates the number of
-        // minutes the time is into the day (i.e., the number of minutes
-        // since midnight) -- this is done such that we can easily match
-        // time ranges (i.e., whether a particular `time` occurs between
-        // two other times)
-        // Note: all the times in this match statement exclude the upper bound
-        match time.hour() * 60 + time.minute() {
-            // 8:50 to 9:50
-            530..=589 => Some(Period::First),
-
-            // 9:50 to 10:50
-            590..=649 => Some(Period::Second),
-
-            // 11:10 to 12:10
-            670..=729 => Some(Period::Third),
-
-            // 12:10 to 13:10
-            730..=789 => Some(Period::Fourth),
-
-            // 13:55 to 14:55
-            835..=894 => Some(Period::Fifth),
-
-            // No other times are allocated
-            _ => None,
-        }
+        BellSchedule::highfield().period_at(time)
+    }
+
+    /// Retrieves the `Period` which follows this one.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if a `Period` follows this one, or [`None`] if this is the
+    /// [`Period::Fifth`] (i.e., the last period of the day).
+    pub fn next(self) -> Option<Self> {
+        Self::ALL.get(self as usize + 1).copied()
     }
 }
 
@@ -99,7 +103,7 @@ impl Period {
 ///       timeslots created at an earlier time if that is reflected in the
 ///       timeslot's positions within the timetable (i.e., `I5W1MP1.index()` <
 ///       `I1W2FP5.index()`).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TimeSlot {
     pub week: Week,
     pub day: Weekday,
@@ -155,9 +159,10 @@ impl TimeSlot {
     /// # Remarks
     ///
     /// The reason why a `week` parameter is required is because the
-    /// `week` cannot be created using the `datetime` alone -- at the time
-    /// of writing, there is no known and reliable way to determine the week
-    /// based on the date alone.
+    /// `week` cannot be created using the `datetime` alone -- the week
+    /// depends on the academic calendar (e.g., holidays) of the school: use
+    /// [`WeekCycle::week_for`](crate::WeekCycle::week_for) to determine the
+    /// week of a particular date.
     pub fn from_datetime<Tz>(week: Week, datetime: DateTime<Tz>) -> Option<Self>
     where
         Tz: TimeZone,
@@ -192,6 +197,91 @@ impl TimeSlot {
     }
 }
 
+/// Parses a [`Week`] from the notations commonly used by school management
+/// systems (e.g., `1`, `W1`, `Week 1`, or `A` for week one).
+pub(crate) fn parse_week(s: &str) -> Option<Week> {
+    let s = s.trim().to_ascii_uppercase();
+    let s = s
+        .strip_prefix("WEEK")
+        .or_else(|| s.strip_prefix('W'))
+        .unwrap_or(&s)
+        .trim();
+
+    match s {
+        "1" | "A" => Some(Week::WeekOne),
+        "2" | "B" => Some(Week::WeekTwo),
+        _ => None,
+    }
+}
+
+/// Parses an active day from either its name (e.g., `Mon` or `Monday`) or
+/// its WDF notation letter (e.g., `R` for Thursday).
+///
+/// *See the [`crate`] documentation for more information about WDF
+/// notation*.
+pub(crate) fn parse_day(s: &str) -> Option<Weekday> {
+    let s = s.trim();
+
+    let day = match s.to_ascii_uppercase().as_str() {
+        "M" => Weekday::Mon,
+        "T" => Weekday::Tue,
+        "W" => Weekday::Wed,
+        "R" => Weekday::Thu,
+        "F" => Weekday::Fri,
+        _ => s.parse().ok()?,
+    };
+
+    // Saturdays and Sundays are inactive days
+    if matches!(day, Weekday::Sat | Weekday::Sun) {
+        None
+    } else {
+        Some(day)
+    }
+}
+
+/// Parses a [`Period`] from its number (e.g., `3` or `P3`).
+pub(crate) fn parse_period(s: &str) -> Option<Period> {
+    let s = s.trim();
+    let s = s
+        .strip_prefix('P')
+        .or_else(|| s.strip_prefix('p'))
+        .unwrap_or(s);
+
+    match s {
+        "1" => Some(Period::First),
+        "2" => Some(Period::Second),
+        "3" => Some(Period::Third),
+        "4" => Some(Period::Fourth),
+        "5" => Some(Period::Fifth),
+        _ => None,
+    }
+}
+
+/// Formats a [`TimeSlot`] as a key of the form `<week>.<day>.<period>`
+/// (e.g., `week1.mon.p3`), as used by the saved timetable formats.
+pub(crate) fn slot_key(slot: TimeSlot) -> String {
+    format!(
+        "week{}.{}.p{}",
+        slot.week as usize + 1,
+        slot.day.to_string().to_ascii_lowercase(),
+        slot.period as usize + 1
+    )
+}
+
+/// Parses a [`TimeSlot`] from a key of the form `<week>.<day>.<period>`
+/// (e.g., `week1.mon.p3`).
+pub(crate) fn parse_slot_key(s: &str) -> Option<TimeSlot> {
+    let mut parts = s.split('.');
+
+    let slot = TimeSlot {
+        week: parse_week(parts.next()?)?,
+        day: parse_day(parts.next()?)?,
+        period: parse_period(parts.next()?)?,
+    };
+
+    parts.next().is_none().then_some(slot)
+}
+
 /// Creates a [`TimeSlot`] from its `WDP` format.
 ///
 /// *See the [`crate`] documentation for more information*.
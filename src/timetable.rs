@@ -0,0 +1,1742 @@
+use crate::access::Role;
+use crate::index::Index;
+#[cfg(feature = "std")]
+use crate::observer::Subscribers;
+use crate::{
+    AcademicCalendar, Activity, Change, DayException, Location, Period, RangedU8, Record, Room,
+    RoomRegistry, Rotation, School, Teacher, TimeOfDay, TimeSlot, TimetableError, TransitPolicy,
+    WeekCycle,
+};
+use chrono::prelude::*;
+use chrono::Duration;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+/// The identifier of an [`Entry`] within a [`Timetable`].
+///
+/// Identifiers are assigned in the order in which entries are inserted into a
+/// timetable and are never reused (i.e., removing an entry will not cause its
+/// identifier to be assigned to another entry).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntryId(pub(crate) u32);
+
+impl EntryId {
+    /// Retrieves the inner value of the `EntryId`.
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
+/// An [`Activity`] which has been scheduled for a particular [`TimeSlot`].
+///
+/// # Spans
+///
+/// An activity may span multiple consecutive periods of the same day (e.g.,
+/// a double Science lesson) -- such an entry starts during its `slot`, and
+/// occupies each of its [`Self::slots`] (*see
+/// [`Timetable::insert_spanning`]*).
+///
+/// # Staffing
+///
+/// A lesson may be team-taught (i.e., by its own teacher and any number of
+/// [`Self::co_teachers`]), or split across multiple rooms (i.e., its own
+/// location and any number of [`Self::split_rooms`]) -- every teacher and
+/// every room of such a lesson is occupied by it (*see
+/// [`Timetable::insert_entry`]*).
+///
+/// # Rotations
+///
+/// A lesson may rotate between teachers and rooms during the year (*see
+/// [`Rotation`]*) -- its `activity` is then the lesson as scheduled, whereas
+/// [`Self::activity_on`] is the lesson which takes place on a given date.
+/// The [occurrences](Timetable::occurrences) (and therefore the exports) of
+/// a timetable are of the effective lessons, whereas its undated queries
+/// (e.g., [`Timetable::lessons`]) are of the scheduled lessons -- *see
+/// [`Timetable::effective_on`]* to query the lessons of a given date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry<L = Location> {
+    /// The timeslot during which the activity starts.
+    pub slot: TimeSlot,
+
+    /// The activity which takes place.
+    pub activity: Activity<L>,
+
+    /// The number of consecutive periods the activity spans (e.g., `2` for a
+    /// double lesson), starting from its `slot`.
+    ///
+    /// The span may extend across break and lunch (e.g., a triple lesson
+    /// from the third to the fifth period), but not past the final period of
+    /// the day.
+    pub periods: RangedU8<1, 5>,
+
+    /// The teachers of the lesson besides its own teacher (i.e., should the
+    /// lesson be team-taught).
+    pub co_teachers: Vec<Teacher>,
+
+    /// The rooms of the lesson besides its own location (i.e., should the
+    /// class be split across multiple rooms).
+    pub split_rooms: Vec<L>,
+
+    /// The rotation of the lesson between teachers and rooms (should it be
+    /// a part of a carousel).
+    pub rotation: Option<Rotation<L>>,
+}
+
+impl<L> Entry<L> {
+    /// Creates a new `Entry` of the `activity` provided, which spans a single
+    /// period.
+    pub fn new(slot: TimeSlot, activity: Activity<L>) -> Self {
+        Self {
+            slot,
+            activity,
+            periods: SINGLE,
+            co_teachers: Vec::new(),
+            split_rooms: Vec::new(),
+            rotation: None,
+        }
+    }
+
+    /// Adds a `teacher` to the lesson of the `Entry`, which is then
+    /// team-taught (*see [`Self::co_teachers`]*).
+    pub fn co_teacher(mut self, teacher: Teacher) -> Self {
+        self.co_teachers.push(teacher);
+        self
+    }
+
+    /// Adds a `room` to the lesson of the `Entry`, across which the class is
+    /// then split (*see [`Self::split_rooms`]*).
+    pub fn split_room(mut self, room: L) -> Self {
+        self.split_rooms.push(room);
+        self
+    }
+
+    /// Sets the `rotation` of the lesson of the `Entry` (*see
+    /// [`Self::rotation`]*).
+    pub fn rotating(mut self, rotation: Rotation<L>) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    // Whether the entry has co-teachers, split rooms, or a rotation
+    pub(crate) fn is_staffed(&self) -> bool {
+        !(self.co_teachers.is_empty() && self.split_rooms.is_empty()) || self.rotation.is_some()
+    }
+
+    /// Retrieves every [`Teacher`] of the `Entry` (i.e., the teacher of its
+    /// lesson, followed by its co-teachers).
+    pub fn teachers(&self) -> impl Iterator<Item = &Teacher> + '_ {
+        self.activity.teacher().into_iter().chain(&self.co_teachers)
+    }
+
+    /// Retrieves every location of the `Entry` (i.e., the location of its
+    /// lesson, followed by its split rooms).
+    pub fn locations(&self) -> impl Iterator<Item = &L> + '_ {
+        self.activity
+            .location()
+            .into_iter()
+            .chain(&self.split_rooms)
+    }
+
+    /// Retrieves each [`TimeSlot`] occupied by the `Entry` (in chronological
+    /// order), starting from its `slot`.
+    ///
+    /// # Remarks
+    ///
+    /// The periods of the span which would follow the final period of the
+    /// day (i.e., should the entry have been constructed too late in the
+    /// day, which [`Timetable::insert_entry`] and [`Timetable::move_entry`]
+    /// never permit) are disregarded.
+    pub fn slots(&self) -> impl Iterator<Item = TimeSlot> + '_ {
+        let TimeSlot { week, day, period } = self.slot;
+
+        Period::ALL
+            .into_iter()
+            .skip(period as usize)
+            .take(self.periods.get() as usize)
+            .map(move |period| TimeSlot { week, day, period })
+    }
+
+    /// Determines whether the `Entry` occupies the `slot` provided (i.e.,
+    /// whether the slot is one of its [`Self::slots`]).
+    pub fn covers(&self, slot: TimeSlot) -> bool {
+        self.slots().any(|covered| covered == slot)
+    }
+}
+
+impl<L: Clone> Entry<L> {
+    /// Retrieves the activity which takes place on the `date` provided (i.e.,
+    /// the stage of the entry's [`Self::rotation`] which applies on the date,
+    /// should it rotate, or its `activity` otherwise).
+    ///
+    /// # Remarks
+    ///
+    /// The [`RoomOverride`]s of the timetable are not applied -- *see
+    /// [`Timetable::occurrences`]*.
+    pub fn activity_on(&self, calendar: &AcademicCalendar, date: NaiveDate) -> Activity<L> {
+        let mut activity = self.activity.clone();
+
+        if let Some(rotation) = &self.rotation {
+            rotation.apply(&mut activity, calendar, date);
+        }
+
+        activity
+    }
+}
+
+/// The span of an entry which occupies a single period.
+pub(crate) const SINGLE: RangedU8<1, 5> = match RangedU8::new(1) {
+    Some(periods) => periods,
+    None => unreachable!(),
+};
+
+/// Checks that an entry starting during the `slot` provided can span the
+/// number of `periods` provided (i.e., that the span is at least one period
+/// long, and does not extend past the final period of the day).
+pub(crate) fn span(slot: TimeSlot, periods: u8) -> Result<RangedU8<1, 5>, TimetableError> {
+    RangedU8::new(periods)
+        .filter(|_| periods <= longest_span(slot.period))
+        .ok_or(TimetableError::InvalidSpan { slot, periods })
+}
+
+/// The greatest number of periods which an entry starting during the
+/// `period` provided can span (i.e., before the end of the day).
+pub(crate) fn longest_span(period: Period) -> u8 {
+    Period::Fifth as u8 - period as u8 + 1
+}
+
+/// An occurrence of an [`Activity`] on a specific date.
+///
+/// Whilst an [`Entry`] describes what should happen on every iteration of the
+/// timetable, an `Occurrence` describes what actually happens on a specific
+/// date (i.e., after any [`DayException`]s have been applied).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence {
+    /// The date on which the activity takes place.
+    pub date: NaiveDate,
+
+    /// The timeslot during which the activity takes place.
+    pub slot: TimeSlot,
+
+    /// The [`Entry`] from which the occurrence was produced.
+    ///
+    /// This is [`None`] if the activity does not originate from the timetable
+    /// (e.g., if the activity replaces a [suspended](DayException::Suspended)
+    /// period).
+    pub entry: Option<EntryId>,
+
+    /// The activity which takes place.
+    pub activity: Activity,
+}
+
+/// An [`Occurrence`] of a lesson, and its timing relative to a particular
+/// time (*see [`Timetable::current_lesson`] and
+/// [`Timetable::next_lesson`]*).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedLesson {
+    /// The occurrence of the lesson.
+    pub occurrence: Occurrence,
+
+    /// The date and time at which the lesson starts (according to the
+    /// [`BellSchedule`](crate::BellSchedule) of its school) -- for a lesson
+    /// spanning multiple periods, the start of its first period.
+    pub start: NaiveDateTime,
+
+    /// The date and time at which the lesson ends -- for a lesson spanning
+    /// multiple periods, the end of its last period.
+    pub end: NaiveDateTime,
+
+    /// The time remaining until the lesson starts (which is zero should the
+    /// lesson already be in progress).
+    pub starts_in: Duration,
+
+    /// The time remaining until the lesson ends.
+    pub ends_in: Duration,
+}
+
+impl TimedLesson {
+    /// Retrieves the number of whole minutes remaining until the lesson ends
+    /// (e.g., for a countdown).
+    pub fn minutes_remaining(&self) -> i64 {
+        self.ends_in.num_minutes()
+    }
+
+    /// Retrieves the latest time at which the lesson can be left whilst
+    /// still arriving at the `next` lesson as it starts (*see
+    /// [`Location::travel_time`] and [`Self::leave_by_with`]*).
+    pub fn leave_by(&self, next: &TimedLesson) -> NaiveDateTime {
+        self.leave_by_with(next, &TransitPolicy::default())
+    }
+
+    /// Retrieves the latest time at which the lesson can be left whilst
+    /// still arriving at the `next` lesson as it starts, according to the
+    /// `policy` provided (e.g., with an allowance for a student who must use
+    /// the lifts).
+    pub fn leave_by_with(&self, next: &TimedLesson, policy: &TransitPolicy) -> NaiveDateTime {
+        next.start - self.travel_time(next, policy)
+    }
+
+    /// Determines whether the gap between the end of the lesson and the
+    /// start of the `next` lesson is long enough to travel between their
+    /// locations (*see [`Location::travel_time`] and
+    /// [`Self::enough_time_to_reach_with`]*).
+    ///
+    /// # Remarks
+    ///
+    /// Should this be `false`, the lesson must be left before it ends (*see
+    /// [`Self::leave_by`]*) -- e.g., to warn "leave now to make FH S12".
+    pub fn enough_time_to_reach(&self, next: &TimedLesson) -> bool {
+        self.enough_time_to_reach_with(next, &TransitPolicy::default())
+    }
+
+    /// Determines whether the gap between the end of the lesson and the
+    /// start of the `next` lesson is long enough to travel between their
+    /// locations according to the `policy` provided -- *see
+    /// [`Self::enough_time_to_reach`] for more information*.
+    pub fn enough_time_to_reach_with(&self, next: &TimedLesson, policy: &TransitPolicy) -> bool {
+        self.leave_by_with(next, policy) >= self.end
+    }
+
+    // Estimate the time taken to travel to the next lesson
+    fn travel_time(&self, next: &TimedLesson, policy: &TransitPolicy) -> Duration {
+        match (
+            self.occurrence.activity.location(),
+            next.occurrence.activity.location(),
+        ) {
+            (Some(from), Some(to)) => policy.travel_time(from, to),
+            _ => Duration::zero(),
+        }
+    }
+
+    // Time the occurrence of a lesson relative to the time provided, from the
+    // first to the last period of its entry which take place on its date
+    // (i.e., the whole of a double lesson, among the `occurrences` of the
+    // date)
+    fn new(
+        cycle: &WeekCycle,
+        occurrences: &[Occurrence],
+        occurrence: Occurrence,
+        at: NaiveDateTime,
+    ) -> Option<Self> {
+        let school = occurrence.activity.location()?.school();
+        let schedule = cycle
+            .calendar()
+            .bell_schedules_on(occurrence.date)
+            .get(school);
+
+        let (first, last) = periods_of(occurrences, &occurrence);
+        let (start, _) = schedule.period(first).to_naive();
+        let (_, end) = schedule.period(last).to_naive();
+
+        let start = occurrence.date.and_time(start);
+        let end = occurrence.date.and_time(end);
+
+        Some(Self {
+            occurrence,
+            start,
+            end,
+            starts_in: (start - at).max(Duration::zero()),
+            ends_in: end - at,
+        })
+    }
+}
+
+// The first and last periods of the `occurrences` (which are ordered
+// chronologically) which were produced from the same entry as the
+// `occurrence` provided
+pub(crate) fn periods_of(occurrences: &[Occurrence], occurrence: &Occurrence) -> (Period, Period) {
+    let mut periods = occurrences
+        .iter()
+        .filter(|other| occurrence.entry.is_some() && other.entry == occurrence.entry)
+        .map(|other| other.slot.period);
+
+    let first = periods.next().unwrap_or(occurrence.slot.period);
+    let last = periods.next_back().unwrap_or(first);
+
+    (first, last)
+}
+
+/// A temporary change to the [`Location`] of the lessons taking place during a
+/// particular [`TimeSlot`] (e.g., due to wet weather, a broken projector, or
+/// building works).
+///
+/// *See [`Timetable::override_room`] for more information*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoomOverride<L = Location> {
+    /// The timeslot of the lessons which are moved.
+    pub slot: TimeSlot,
+
+    /// The first date on which the override takes effect.
+    pub start: NaiveDate,
+
+    /// The last date on which the override takes effect.
+    pub end: NaiveDate,
+
+    /// The location to which the lessons are moved.
+    pub location: L,
+
+    /// The location whose lessons are moved, or [`None`] should every lesson
+    /// of the timeslot be moved (*see [`Timetable::override_location`]*).
+    pub from: Option<L>,
+}
+
+impl<L: PartialEq> RoomOverride<L> {
+    /// Whether the override is in effect on the `date` provided.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        date >= self.start && date <= self.end
+    }
+
+    /// Whether the override moves the lessons taking place in the `location`
+    /// provided.
+    pub fn applies_to(&self, location: &L) -> bool {
+        self.from.as_ref().is_none_or(|from| from == location)
+    }
+}
+
+/// A timetable: a set of activities which take place on a recurring basis.
+///
+/// *See the [`crate`] documentation for more information*.
+///
+/// # Remarks
+///
+/// A `Timetable` does not prevent multiple activities from being scheduled
+/// for the same [`TimeSlot`] -- this allows the timetable of an entire school
+/// to be represented by a single `Timetable`.
+///
+/// Every mutation made to a `Timetable` is recorded in its
+/// [history](Self::history) -- *see the [`Change`] documentation for more
+/// information*.
+///
+/// A `Timetable` is generic over the [`Room`]s it schedules, defaulting to a
+/// [`Location`] (i.e., a room at the Highfield school or the Fearnhill
+/// school) -- for any other kind of room, create the timetable using
+/// [`Default`] (e.g., `Timetable::<MyRoom>::default()`).
+///
+/// The lessons of a `Timetable` are indexed by their rooms, teachers, and
+/// classes -- as a result, [queries](Self::lessons) filtering by any of these
+/// only visit the matching lessons (rather than every entry).
+#[derive(Debug, Clone)]
+pub struct Timetable<L = Location> {
+    pub(crate) entries: Arc<BTreeMap<EntryId, Entry<L>>>,
+    pub(crate) next_id: u32,
+    pub(crate) overrides: Arc<Vec<RoomOverride<L>>>,
+    pub(crate) history: Arc<Vec<Record<L>>>,
+    pub(crate) author: Option<String>,
+    pub(crate) role: Option<Role>,
+    pub(crate) index: Arc<Index<L>>,
+    #[cfg(feature = "std")]
+    pub(crate) subscribers: Subscribers<L>,
+}
+
+impl<L> Default for Timetable<L> {
+    fn default() -> Self {
+        Self {
+            entries: Arc::default(),
+            next_id: 0,
+            overrides: Arc::default(),
+            history: Arc::default(),
+            author: None,
+            role: None,
+            index: Arc::new(Index::default()),
+            #[cfg(feature = "std")]
+            subscribers: Subscribers::default(),
+        }
+    }
+}
+
+impl Timetable {
+    /// Creates a new, empty `Timetable`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<L: Room> Timetable<L> {
+    /// Schedules an `activity` for the `slot` provided.
+    ///
+    /// # Returns
+    ///
+    /// The identifier of the newly created [`Entry`].
+    pub fn insert(&mut self, slot: TimeSlot, activity: Activity<L>) -> EntryId {
+        self.push_entry(Entry::new(slot, activity))
+    }
+
+    /// Schedules an `activity` spanning the number of consecutive `periods`
+    /// provided (e.g., `2` for a double lesson), starting from the `slot`
+    /// provided.
+    ///
+    /// ```
+    /// use timetableau::{timeslot, Activity, Timetable};
+    ///
+    /// let mut timetable = Timetable::new();
+    /// let id = timetable
+    ///     .insert_spanning(timeslot!(W1MP3), 2, Activity::SchoolStudy)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(timetable.at(timeslot!(W1MP4)).next().unwrap().0, id);
+    ///
+    /// // A triple lesson may continue across lunch, but not past the end of
+    /// // the day
+    /// assert!(timetable.insert_spanning(timeslot!(W1TP3), 3, Activity::SchoolStudy).is_ok());
+    /// assert!(timetable.insert_spanning(timeslot!(W1WP4), 3, Activity::SchoolStudy).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`TimetableError::InvalidSpan`] is returned should the span not be
+    /// at least one period long, or should it extend past the final period of
+    /// the day.
+    pub fn insert_spanning(
+        &mut self,
+        slot: TimeSlot,
+        periods: u8,
+        activity: Activity<L>,
+    ) -> Result<EntryId, TimetableError> {
+        Ok(self.push_entry(Entry {
+            periods: span(slot, periods)?,
+            ..Entry::new(slot, activity)
+        }))
+    }
+
+    /// Schedules an `entry` (e.g., a team-taught lesson, or a lesson split
+    /// across multiple rooms).
+    ///
+    /// ```
+    /// use timetableau::{timeslot, Activity, Class, Entry, Subject, Teacher, Timetable};
+    ///
+    /// let lesson = Activity::Lesson {
+    ///     subject: Subject::new("Sc".to_string()).unwrap(),
+    ///     class: Class::new("10A".to_string()).unwrap(),
+    ///     teacher: Some(Teacher::new("JSM".to_string()).unwrap()),
+    ///     location: "H201".parse().unwrap(),
+    /// };
+    ///
+    /// let mut timetable = Timetable::new();
+    /// let entry = Entry::new(timeslot!(W1MP1), lesson)
+    ///     .co_teacher(Teacher::new("ABC".to_string()).unwrap())
+    ///     .split_room("H202".parse().unwrap());
+    /// let id = timetable.insert_entry(entry).unwrap();
+    ///
+    /// assert_eq!(timetable.lessons().taught_by("ABC").next().unwrap().0, id);
+    /// assert_eq!(timetable.lessons().in_room("H202".parse().unwrap()).next().unwrap().0, id);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`TimetableError::InvalidSpan`] is returned should the span of the
+    ///   entry extend past the final period of the day.
+    ///
+    /// * [`TimetableError::NotALesson`] is returned should the entry have
+    ///   co-teachers, split rooms, or a rotation, but not be a lesson.
+    pub fn insert_entry(&mut self, entry: Entry<L>) -> Result<EntryId, TimetableError> {
+        let slot = entry.slot;
+
+        span(slot, entry.periods.get())?;
+
+        if entry.is_staffed() && !matches!(entry.activity, Activity::Lesson { .. }) {
+            return Err(TimetableError::NotALesson { slot });
+        }
+
+        Ok(self.push_entry(entry))
+    }
+
+    // Schedule an entry (which has already been checked)
+    pub(crate) fn push_entry(&mut self, entry: Entry<L>) -> EntryId {
+        let id = EntryId(self.next_id);
+
+        self.commit(Change::Inserted { id, entry });
+        id
+    }
+
+    /// Removes (and returns) the [`Entry`] with the identifier `id`.
+    pub fn remove(&mut self, id: EntryId) -> Option<Entry<L>> {
+        let entry = self.get(id)?.clone();
+
+        self.commit(Change::Removed {
+            id,
+            entry: entry.clone(),
+        });
+        Some(entry)
+    }
+
+    /// Moves the [`Entry`] with the identifier `id` to the `slot` provided
+    /// (retaining the number of periods it spans).
+    ///
+    /// # Returns
+    ///
+    /// The slot in which the entry was previously scheduled, or [`None`] if
+    /// the timetable does not contain an entry with the identifier `id`.
+    ///
+    /// # Errors
+    ///
+    /// [`TimetableError::InvalidSpan`] is returned (and the entry is left
+    /// unchanged) should the span of the entry, starting from the `slot`,
+    /// extend past the final period of the day -- *see
+    /// [`Self::insert_entry`]*.
+    pub fn move_entry(
+        &mut self,
+        id: EntryId,
+        slot: TimeSlot,
+    ) -> Result<Option<TimeSlot>, TimetableError> {
+        let Some(entry) = self.get(id) else {
+            return Ok(None);
+        };
+        let from = entry.slot;
+
+        span(slot, entry.periods.get())?;
+
+        self.commit(Change::Moved { id, from, to: slot });
+        Ok(Some(from))
+    }
+
+    /// Changes the [`Location`] of the lesson with the identifier `id`.
+    ///
+    /// # Returns
+    ///
+    /// The previous location of the lesson, or [`None`] if the timetable
+    /// does not contain a [lesson](Activity::Lesson) with the identifier
+    /// `id`.
+    pub fn relocate(&mut self, id: EntryId, location: L) -> Option<L> {
+        let from = self.get(id)?.activity.location()?.clone();
+
+        self.commit(Change::Relocated {
+            id,
+            from: from.clone(),
+            to: location,
+        });
+        Some(from)
+    }
+
+    /// Retrieves the [`Entry`] with the identifier `id`.
+    pub fn get(&self, id: EntryId) -> Option<&Entry<L>> {
+        self.entries.get(&id)
+    }
+
+    /// The number of entries in the `Timetable`.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the `Timetable` has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Retrieves every [`Entry`] in the `Timetable` (in the order in which
+    /// they were inserted).
+    pub fn entries(&self) -> impl Iterator<Item = (EntryId, &Entry<L>)> + '_ {
+        self.entries.iter().map(|(id, entry)| (*id, entry))
+    }
+
+    /// Retrieves every [`Entry`] which occupies the `slot` provided
+    /// (including the entries which span it, but started earlier in the day
+    /// -- *see [`Entry::covers`]*).
+    pub fn at(&self, slot: TimeSlot) -> impl Iterator<Item = (EntryId, &Entry<L>)> + '_ {
+        self.entries().filter(move |(_, entry)| entry.covers(slot))
+    }
+
+    /// Temporarily moves the lessons taking place during the `slot` provided
+    /// to `location` for the range of `dates` provided.
+    ///
+    /// The base timetable is not modified -- the override is only applied to
+    /// the [`Occurrence`]s of lessons taking place during its validity
+    /// window. Should multiple overrides apply to the same lesson, the one
+    /// recorded last takes precedence.
+    ///
+    /// # Returns
+    ///
+    /// [`true`] if the override was recorded, or [`false`] if the range of
+    /// `dates` provided is empty.
+    pub fn override_room(
+        &mut self,
+        slot: TimeSlot,
+        dates: RangeInclusive<NaiveDate>,
+        location: L,
+    ) -> bool {
+        if dates.is_empty() {
+            return false;
+        }
+
+        self.commit(Change::OverrideAdded(RoomOverride {
+            slot,
+            start: *dates.start(),
+            end: *dates.end(),
+            location,
+            from: None,
+        }));
+        true
+    }
+
+    /// Temporarily moves the lessons taking place in the `from` location
+    /// during the `slot` provided to `location` for the range of `dates`
+    /// provided (e.g., moving the P.E. lessons on the field into the sports
+    /// hall should it rain), leaving the other lessons of the timeslot in
+    /// their rooms.
+    ///
+    /// *See [`Self::override_room`] for more information*.
+    ///
+    /// # Returns
+    ///
+    /// [`true`] if the override was recorded, or [`false`] if the range of
+    /// `dates` provided is empty.
+    pub fn override_location(
+        &mut self,
+        slot: TimeSlot,
+        dates: RangeInclusive<NaiveDate>,
+        from: L,
+        location: L,
+    ) -> bool {
+        if dates.is_empty() {
+            return false;
+        }
+
+        self.commit(Change::OverrideAdded(RoomOverride {
+            slot,
+            start: *dates.start(),
+            end: *dates.end(),
+            location,
+            from: Some(from),
+        }));
+        true
+    }
+
+    /// Retrieves every [`RoomOverride`] recorded for the `Timetable` (in the
+    /// order in which they were recorded).
+    pub fn overrides(&self) -> &[RoomOverride<L>] {
+        &self.overrides
+    }
+
+    /// Removes (and returns) the [`RoomOverride`] at position `index` of
+    /// [`Self::overrides`].
+    pub fn remove_override(&mut self, index: usize) -> Option<RoomOverride<L>> {
+        let room_override = self.overrides.get(index)?.clone();
+
+        self.commit(Change::OverrideRemoved {
+            index,
+            room_override: room_override.clone(),
+        });
+        Some(room_override)
+    }
+
+    /// Retrieves the [`Location`] to which the lessons taking place during
+    /// the `slot` provided have been moved on the `date` provided.
+    ///
+    /// Overrides which only move the lessons of a single location (*see
+    /// [`Self::override_location`]*) are disregarded -- *see
+    /// [`Self::overridden_location_of`]*.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if a [`RoomOverride`] is in effect, or [`None`] if it is not.
+    pub fn overridden_location(&self, slot: TimeSlot, date: NaiveDate) -> Option<L> {
+        self.overrides
+            .iter()
+            .rev()
+            .find(|room_override| {
+                room_override.slot == slot
+                    && room_override.contains(date)
+                    && room_override.from.is_none()
+            })
+            .map(|room_override| room_override.location.clone())
+    }
+
+    /// Retrieves the [`Location`] to which the lessons taking place in the
+    /// `location` provided during the `slot` provided have been moved on the
+    /// `date` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if a [`RoomOverride`] is in effect, or [`None`] if it is not.
+    pub fn overridden_location_of(
+        &self,
+        slot: TimeSlot,
+        date: NaiveDate,
+        location: &L,
+    ) -> Option<L> {
+        self.overrides
+            .iter()
+            .rev()
+            .find(|room_override| {
+                room_override.slot == slot
+                    && room_override.contains(date)
+                    && room_override.applies_to(location)
+            })
+            .map(|room_override| room_override.location.clone())
+    }
+
+    /// Creates a copy of the `Timetable` in which the lesson of each entry
+    /// which [rotates](Rotation) is replaced by its lesson on the `date`
+    /// provided (*see [`Entry::activity_on`]*), such that any query of the
+    /// timetable (e.g., the lessons taught by a teacher, or a
+    /// [validation](crate::validate)) describes the lessons of that date.
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use timetableau::{
+    ///     timeslot, AcademicCalendar, Activity, Class, Entry, Rotation, Subject, Teacher, Term,
+    ///     Timetable,
+    /// };
+    ///
+    /// let teacher = |code: &str| Some(Teacher::new(code.to_string()).unwrap());
+    /// let date = |month, day| NaiveDate::from_ymd_opt(2023, month, day).unwrap();
+    /// let calendar = AcademicCalendar::new(vec![
+    ///     Term::new(date(9, 4), date(10, 20)).unwrap(),
+    ///     Term::new(date(10, 30), date(12, 19)).unwrap(),
+    /// ]);
+    ///
+    /// let lesson = Activity::Lesson {
+    ///     subject: Subject::new("DT".to_string()).unwrap(),
+    ///     class: Class::new("8A".to_string()).unwrap(),
+    ///     teacher: teacher("JSM"),
+    ///     location: "FH T1".parse().unwrap(),
+    /// };
+    /// let rotation = Rotation::new(date(9, 4))
+    ///     .stage(teacher("JSM"), "FH T1".parse().unwrap())
+    ///     .stage(teacher("ABC"), "FH T2".parse().unwrap());
+    ///
+    /// let mut timetable = Timetable::new();
+    /// timetable
+    ///     .insert_entry(Entry::new(timeslot!(W1MP1), lesson).rotating(rotation))
+    ///     .unwrap();
+    ///
+    /// // The second stage applies from the start of the second half-term
+    /// let effective = timetable.effective_on(&calendar, date(11, 6));
+    ///
+    /// assert_eq!(timetable.lessons().taught_by("ABC").count(), 0);
+    /// assert_eq!(effective.lessons().taught_by("ABC").count(), 1);
+    /// ```
+    pub fn effective_on(&self, calendar: &AcademicCalendar, date: NaiveDate) -> Self {
+        let mut effective = self.clone();
+
+        for (id, entry) in self.entries() {
+            if entry.rotation.is_none() {
+                continue;
+            }
+
+            let activity = entry.activity_on(calendar, date);
+
+            if activity != entry.activity {
+                effective.apply(&Change::Inserted {
+                    id,
+                    entry: Entry {
+                        activity,
+                        ..entry.clone()
+                    },
+                });
+            }
+        }
+
+        effective
+    }
+}
+
+impl Timetable {
+    /// Moves the lessons taking place in each location which has a fallback
+    /// in the `registry` provided (e.g., the P.E. lessons on the field) to
+    /// that fallback (e.g., the sports hall) for the range of `dates`
+    /// provided -- for example, should rain be forecast.
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use timetableau::{timeslot, Activity, Class, RoomRegistry, Subject, Timetable};
+    ///
+    /// let mut timetable = Timetable::new();
+    /// timetable.insert(
+    ///     timeslot!(W1MP1),
+    ///     Activity::Lesson {
+    ///         subject: Subject::new("PE".to_string()).unwrap(),
+    ///         class: Class::new("10A".to_string()).unwrap(),
+    ///         teacher: None,
+    ///         location: "Field".parse().unwrap(),
+    ///     },
+    /// );
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+    ///
+    /// assert_eq!(timetable.fall_back(date..=date, &RoomRegistry::known()), 1);
+    /// assert_eq!(timetable.overrides()[0].location, "Sports Hall".parse().unwrap());
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// The number of [`RoomOverride`]s recorded (one per timeslot and
+    /// location, each restricted to the lessons of that location -- *see
+    /// [`Self::override_location`]*).
+    pub fn fall_back(
+        &mut self,
+        dates: RangeInclusive<NaiveDate>,
+        registry: &RoomRegistry,
+    ) -> usize {
+        if dates.is_empty() {
+            return 0;
+        }
+
+        let mut moves: Vec<(TimeSlot, Location, Location)> = Vec::new();
+
+        for (_, entry) in self.lessons() {
+            if let Activity::Lesson { location, .. } = &entry.activity {
+                if let Some(fallback) = registry.fallback(location) {
+                    let moved = (entry.slot, *location, fallback);
+
+                    if !moves.contains(&moved) {
+                        moves.push(moved);
+                    }
+                }
+            }
+        }
+
+        for (slot, from, fallback) in &moves {
+            self.override_location(*slot, dates.clone(), *from, *fallback);
+        }
+
+        moves.len()
+    }
+
+    /// Retrieves the [`Occurrence`]s which take place on the `date` provided.
+    ///
+    /// Any [`DayException`] recorded for the `date` (in the `cycle`'s
+    /// calendar) is applied -- for example, no occurrences are produced for
+    /// the periods following an [early closure](DayException::EarlyClosure).
+    /// Similarly, the teacher and location of any lesson which rotates are
+    /// those of the stage of its [`Rotation`] which applies on the `date`,
+    /// and the location of any lesson affected by a [`RoomOverride`] is
+    /// replaced by the location of that override.
+    ///
+    /// The occurrences are ordered chronologically -- an entry spanning
+    /// multiple periods produces an occurrence for each period it occupies
+    /// (each with the same [`Occurrence::entry`]).
+    pub fn occurrences(&self, cycle: &WeekCycle, date: NaiveDate) -> Vec<Occurrence> {
+        let calendar = cycle.calendar();
+
+        // No activities take place on days on which the school is closed
+        if !calendar.is_school_day(date) {
+            return Vec::new();
+        }
+
+        let (week, day) = match cycle.day_for(date) {
+            Some(day) => day,
+            None => return Vec::new(),
+        };
+
+        let mut occurrences = Vec::new();
+
+        for period in Period::ALL {
+            let slot = TimeSlot { week, day, period };
+
+            match calendar.exception(date) {
+                Some(DayException::EarlyClosure { last_period }) if period > *last_period => {}
+                Some(DayException::Suspended { periods, activity })
+                    if periods.contains(&period) =>
+                {
+                    occurrences.push(Occurrence {
+                        date,
+                        slot,
+                        entry: None,
+                        activity: activity.clone(),
+                    });
+                }
+                _ => occurrences.extend(self.at(slot).map(|(id, entry)| {
+                    let mut activity = entry.activity_on(calendar, date);
+
+                    if let Activity::Lesson { location, .. } = &mut activity {
+                        if let Some(moved_to) = self.overridden_location_of(slot, date, location) {
+                            *location = moved_to;
+                        }
+                    }
+
+                    Occurrence {
+                        date,
+                        slot,
+                        entry: Some(id),
+                        activity,
+                    }
+                })),
+            }
+        }
+
+        occurrences
+    }
+
+    /// Retrieves the [`Occurrence`]s of every activity taking place at the
+    /// `datetime` provided.
+    ///
+    /// The period during which each activity takes place is determined using
+    /// the [`BellSchedule`](crate::BellSchedule) of the school at which the
+    /// activity takes place (activities which do not take place at a
+    /// specific location use the Highfield school's schedule) -- *see
+    /// [`Self::occurrences`] for more information*.
+    pub fn activities_at<Tz>(&self, cycle: &WeekCycle, datetime: DateTime<Tz>) -> Vec<Occurrence>
+    where
+        Tz: TimeZone,
+    {
+        let schedules = cycle.calendar().bell_schedules_on(datetime.date_naive());
+        let time = TimeOfDay::from(datetime.time());
+
+        self.occurrences(cycle, datetime.date_naive())
+            .into_iter()
+            .filter(|occurrence| {
+                let school = occurrence
+                    .activity
+                    .location()
+                    .map_or(School::Highfield, Location::school);
+
+                schedules.get(school).period_at(time) == Some(occurrence.slot.period)
+            })
+            .collect()
+    }
+
+    /// Retrieves the [`Occurrence`] of the [lesson](Activity::Lesson) taking
+    /// place at the `datetime` provided.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if a lesson takes place at the `datetime`, or [`None`] if no
+    /// lesson takes place (e.g., during a break, a holiday, or a period
+    /// suspended by a [`DayException`]).
+    ///
+    /// # Remarks
+    ///
+    /// This function is intended for the timetable of a single student or
+    /// teacher -- should multiple lessons take place at the `datetime`, the
+    /// one inserted first is returned.
+    ///
+    /// The local time of the `datetime` (i.e., the time in its own time zone)
+    /// is compared against the bell schedules -- an instant should therefore
+    /// be converted into the time in [`London`](crate::London) (e.g.,
+    /// `Utc::now().with_timezone(&London)`), such that the correct lesson is
+    /// found either side of the clocks changing.
+    pub fn lesson_at<Tz>(&self, cycle: &WeekCycle, datetime: DateTime<Tz>) -> Option<Occurrence>
+    where
+        Tz: TimeZone,
+    {
+        self.activities_at(cycle, datetime)
+            .into_iter()
+            .find(|occurrence| matches!(occurrence.activity, Activity::Lesson { .. }))
+    }
+
+    /// Retrieves the [lesson](Activity::Lesson) taking place at the
+    /// `datetime` provided, and the time remaining until it ends (*see
+    /// [`TimedLesson::ends_in`]*).
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if a lesson takes place at the `datetime`, or [`None`] if no
+    /// lesson takes place (e.g., after the end of the day, at a weekend,
+    /// during a holiday, or during a period suspended by a
+    /// [`DayException`]) -- *see [`Self::lesson_at`] for more information*.
+    pub fn current_lesson<Tz>(
+        &self,
+        cycle: &WeekCycle,
+        datetime: DateTime<Tz>,
+    ) -> Option<TimedLesson>
+    where
+        Tz: TimeZone,
+    {
+        let at = datetime.naive_local();
+        let occurrence = self.lesson_at(cycle, datetime)?;
+        let occurrences = self.occurrences(cycle, occurrence.date);
+
+        TimedLesson::new(cycle, &occurrences, occurrence, at)
+    }
+
+    /// Retrieves the [lessons](Activity::Lesson) which take place on the
+    /// `date` provided (*see [`Self::occurrences`]*), timed relative to the
+    /// time `at` which they are retrieved (*see [`TimedLesson`]*).
+    ///
+    /// A lesson spanning multiple periods (e.g., a double lesson) is
+    /// retrieved once, from the start of its first period to the end of its
+    /// last period.
+    pub fn timed_lessons(
+        &self,
+        cycle: &WeekCycle,
+        date: NaiveDate,
+        at: NaiveDateTime,
+    ) -> Vec<TimedLesson> {
+        let occurrences = self.occurrences(cycle, date);
+
+        occurrences
+            .iter()
+            .filter(|occurrence| {
+                matches!(occurrence.activity, Activity::Lesson { .. })
+                    && periods_of(&occurrences, occurrence).0 == occurrence.slot.period
+            })
+            .filter_map(|occurrence| TimedLesson::new(cycle, &occurrences, occurrence.clone(), at))
+            .collect()
+    }
+
+    /// Retrieves the next [lesson](Activity::Lesson) to start after the
+    /// `datetime` provided, and the time remaining until it starts (*see
+    /// [`TimedLesson::starts_in`]*).
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if a lesson starts after the `datetime` (and before the end
+    /// of the last [`Term`](crate::Term) of the cycle's calendar), or
+    /// [`None`] otherwise.
+    ///
+    /// # Remarks
+    ///
+    /// A lesson which is already in progress at the `datetime` is **not**
+    /// the next lesson -- *see [`Self::current_lesson`] for more
+    /// information*. Weekends, holidays, and the periods suspended by a
+    /// [`DayException`] are skipped, such that the next lesson after the end
+    /// of a Friday is the first lesson of the following school day. Likewise,
+    /// the later periods of a lesson spanning multiple periods (e.g., the
+    /// second half of a double) do not start a lesson of their own.
+    pub fn next_lesson<Tz>(&self, cycle: &WeekCycle, datetime: DateTime<Tz>) -> Option<TimedLesson>
+    where
+        Tz: TimeZone,
+    {
+        let last = cycle
+            .calendar()
+            .terms()
+            .iter()
+            .map(|term| term.end())
+            .max()?;
+
+        let mut date = datetime.date_naive();
+
+        while date <= last {
+            let schedules = cycle.calendar().bell_schedules_on(date);
+            let occurrences = self.occurrences(cycle, date);
+            let next = occurrences
+                .iter()
+                .filter_map(|occurrence| {
+                    let location = occurrence.activity.location()?;
+
+                    // The later periods of a spanning lesson continue the
+                    // lesson, rather than starting another
+                    if periods_of(&occurrences, occurrence).0 != occurrence.slot.period {
+                        return None;
+                    }
+
+                    let start = schedules
+                        .get(location.school())
+                        .start(occurrence.slot.period);
+
+                    // Lessons on the first date must start after the time
+                    // provided
+                    (date != datetime.date_naive() || start > datetime.time().into())
+                        .then_some((start, occurrence))
+                })
+                .min_by_key(|(start, _)| *start);
+
+            if let Some((_, occurrence)) = next {
+                let occurrence = occurrence.clone();
+
+                return TimedLesson::new(cycle, &occurrences, occurrence, datetime.naive_local());
+            }
+
+            date = date.succ_opt()?;
+        }
+
+        None
+    }
+}
+
+// Two timetables are equal if they schedule the same activities (i.e., their
+// histories and authors are disregarded)
+impl<L: PartialEq> PartialEq for Timetable<L> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries && self.overrides == other.overrides
+    }
+}
+
+impl<L: Eq> Eq for Timetable<L> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, AcademicCalendar, Class, London, Subject, Term};
+    use crate::{BellSchedule, BellSchedules, PeriodTime};
+    use crate::{HighfieldBlock, HighfieldFloor, HighfieldRoom, RangedU8};
+
+    fn lesson(subject: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new(subject.to_string()).unwrap(),
+            class: Class::new("JSM".to_string()).unwrap(),
+            teacher: None,
+            location: Location::Highfield(HighfieldRoom::Classroom {
+                block: HighfieldBlock::Howard,
+                floor: HighfieldFloor::Level(RangedU8::new(2).unwrap()),
+                discriminator: RangedU8::new(1).unwrap(),
+            }),
+        }
+    }
+
+    fn cycle() -> WeekCycle {
+        let calendar = AcademicCalendar::new(vec![Term::new(
+            NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(),
+            NaiveDate::from_ymd_opt(2023, 10, 20).unwrap(),
+        )
+        .unwrap()]);
+
+        WeekCycle::new(NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(), calendar).unwrap()
+    }
+
+    fn timetable() -> Timetable {
+        let mut timetable = Timetable::new();
+
+        timetable.insert(timeslot!(W1MP1), lesson("Maths"));
+        timetable.insert(timeslot!(W1MP5), lesson("Physics"));
+        timetable.insert(timeslot!(W2MP1), lesson("History"));
+        timetable
+    }
+
+    #[test]
+    fn lesson_at_valid() {
+        let occurrence = timetable().lesson_at(
+            &cycle(),
+            Utc.with_ymd_and_hms(2023, 9, 11, 9, 0, 0).unwrap(),
+        );
+
+        assert_eq!(occurrence.unwrap().activity, lesson("History"));
+    }
+
+    #[test]
+    fn lesson_at_clock_change() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let subject = |cycle: &WeekCycle, utc: DateTime<Utc>| {
+            timetable()
+                .lesson_at(cycle, utc.with_timezone(&London))
+                .map(|occurrence| occurrence.activity)
+        };
+
+        // The clocks go back on Sunday 2023-10-29
+        let autumn = WeekCycle::new(
+            date(2023, 10, 16),
+            AcademicCalendar::new(vec![
+                Term::new(date(2023, 10, 16), date(2023, 11, 3)).unwrap()
+            ]),
+        )
+        .unwrap();
+
+        let utc = |month, day, hour, minute| {
+            Utc.with_ymd_and_hms(2023, month, day, hour, minute, 0)
+                .unwrap()
+        };
+        assert_eq!(subject(&autumn, utc(10, 16, 8, 0)), Some(lesson("Maths")));
+        assert_eq!(subject(&autumn, utc(10, 30, 8, 0)), None);
+        assert_eq!(subject(&autumn, utc(10, 30, 9, 0)), Some(lesson("Maths")));
+
+        // The clocks go forward on Sunday 2025-03-30
+        let spring = WeekCycle::new(
+            date(2025, 3, 24),
+            AcademicCalendar::new(vec![Term::new(date(2025, 3, 24), date(2025, 4, 4)).unwrap()]),
+        )
+        .unwrap();
+
+        let utc = |month, day, hour, minute| {
+            Utc.with_ymd_and_hms(2025, month, day, hour, minute, 0)
+                .unwrap()
+        };
+        assert_eq!(subject(&spring, utc(3, 24, 8, 50)), Some(lesson("Maths")));
+        assert_eq!(subject(&spring, utc(3, 31, 7, 50)), Some(lesson("History")));
+        assert_eq!(subject(&spring, utc(3, 31, 9, 0)), None);
+    }
+
+    #[test]
+    fn lesson_at_holiday() {
+        let occurrence = timetable().lesson_at(
+            &cycle(),
+            Utc.with_ymd_and_hms(2023, 10, 23, 9, 0, 0).unwrap(),
+        );
+
+        assert_eq!(occurrence, None);
+    }
+
+    #[test]
+    fn next_lesson_valid() {
+        let timetable = timetable();
+
+        let next = timetable
+            .next_lesson(&cycle(), Utc.with_ymd_and_hms(2023, 9, 4, 9, 0, 0).unwrap())
+            .unwrap();
+
+        assert_eq!(next.occurrence.activity, lesson("Physics"));
+        assert_eq!(next.starts_in, Duration::minutes(295));
+
+        let next = timetable
+            .next_lesson(
+                &cycle(),
+                Utc.with_ymd_and_hms(2023, 9, 4, 15, 0, 0).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            next.occurrence.date,
+            NaiveDate::from_ymd_opt(2023, 9, 11).unwrap()
+        );
+        assert_eq!(next.occurrence.activity, lesson("History"));
+        assert_eq!(
+            timetable.next_lesson(
+                &cycle(),
+                Utc.with_ymd_and_hms(2023, 10, 20, 15, 0, 0).unwrap()
+            ),
+            None
+        );
+
+        // A double lesson starts (and is timed) from its first period
+        let mut timetable = timetable;
+        let double = timetable
+            .insert_spanning(timeslot!(W1TP1), 2, lesson("Chemistry"))
+            .unwrap();
+
+        let next = timetable
+            .next_lesson(&cycle(), Utc.with_ymd_and_hms(2023, 9, 5, 8, 0, 0).unwrap())
+            .unwrap();
+        let date = NaiveDate::from_ymd_opt(2023, 9, 5).unwrap();
+
+        assert_eq!(next.occurrence.entry, Some(double));
+        assert_eq!(next.start, date.and_hms_opt(8, 50, 0).unwrap());
+        assert_eq!(next.end, date.and_hms_opt(10, 50, 0).unwrap());
+
+        // Whereas its second period does not start another lesson
+        let next = timetable
+            .next_lesson(&cycle(), Utc.with_ymd_and_hms(2023, 9, 5, 9, 0, 0).unwrap())
+            .unwrap();
+
+        assert_eq!(next.occurrence.activity, lesson("History"));
+    }
+
+    #[test]
+    fn current_lesson_remaining() {
+        let timetable = timetable();
+        let cycle = cycle();
+
+        let current = timetable
+            .current_lesson(&cycle, Utc.with_ymd_and_hms(2023, 9, 4, 9, 20, 0).unwrap())
+            .unwrap();
+
+        assert_eq!(current.occurrence.activity, lesson("Maths"));
+        assert_eq!(current.starts_in, Duration::zero());
+        assert_eq!(current.ends_in, Duration::minutes(30));
+
+        // The time remaining in a double lesson is until the end of its
+        // second period, during either period
+        let mut timetable = timetable;
+        timetable
+            .insert_spanning(timeslot!(W1TP1), 2, lesson("Chemistry"))
+            .unwrap();
+
+        for (hour, minute, remaining) in [(9, 20, 90), (10, 0, 50)] {
+            let datetime = Utc.with_ymd_and_hms(2023, 9, 5, hour, minute, 0).unwrap();
+            let current = timetable.current_lesson(&cycle, datetime).unwrap();
+
+            assert_eq!(current.occurrence.activity, lesson("Chemistry"));
+            assert_eq!(current.starts_in, Duration::zero());
+            assert_eq!(current.ends_in, Duration::minutes(remaining));
+        }
+
+        // Each lesson of a day is timed once, across the whole of its span
+        let tuesday = NaiveDate::from_ymd_opt(2023, 9, 5).unwrap();
+        timetable.insert(timeslot!(W1TP3), Activity::SchoolStudy);
+        let lessons =
+            timetable.timed_lessons(&cycle, tuesday, tuesday.and_hms_opt(10, 0, 0).unwrap());
+
+        assert_eq!(lessons.len(), 1);
+        assert_eq!(lessons[0].occurrence.slot, timeslot!(W1TP1));
+        assert_eq!(lessons[0].start, tuesday.and_hms_opt(8, 50, 0).unwrap());
+        assert_eq!(lessons[0].end, tuesday.and_hms_opt(10, 50, 0).unwrap());
+        assert_eq!(lessons[0].ends_in, Duration::minutes(50));
+
+        // There are no lessons after the end of the day, or at weekends
+        for (day, hour) in [(4, 15), (9, 9)] {
+            let datetime = Utc.with_ymd_and_hms(2023, 9, day, hour, 0, 0).unwrap();
+
+            assert_eq!(timetable.current_lesson(&cycle, datetime), None);
+        }
+
+        // The next lesson after a weekend is on the following Monday
+        let next = timetable
+            .next_lesson(&cycle, Utc.with_ymd_and_hms(2023, 9, 9, 12, 0, 0).unwrap())
+            .unwrap();
+
+        assert_eq!(next.occurrence.activity, lesson("History"));
+        assert_eq!(next.starts_in, Duration::hours(44) + Duration::minutes(50));
+    }
+
+    #[test]
+    fn enough_time_to_reach() {
+        let mut timetable = Timetable::new();
+        let fearnhill = Activity::Lesson {
+            subject: Subject::new("Music".to_string()).unwrap(),
+            class: Class::new("JSM".to_string()).unwrap(),
+            teacher: None,
+            location: "FH S12".parse().unwrap(),
+        };
+
+        timetable.insert(timeslot!(W1MP1), lesson("Maths"));
+        timetable.insert(timeslot!(W1MP2), lesson("Physics"));
+        timetable.insert(timeslot!(W1MP3), fearnhill);
+        timetable.insert(timeslot!(W1MP4), lesson("Chemistry"));
+
+        let cycle = cycle();
+        let at = |hour, minute| Utc.with_ymd_and_hms(2023, 9, 4, hour, minute, 0).unwrap();
+
+        let maths = timetable.current_lesson(&cycle, at(9, 30)).unwrap();
+        let physics = timetable.next_lesson(&cycle, at(9, 30)).unwrap();
+        let music = timetable.next_lesson(&cycle, at(10, 0)).unwrap();
+        let chemistry = timetable.next_lesson(&cycle, at(11, 30)).unwrap();
+
+        assert_eq!(maths.minutes_remaining(), 20);
+
+        // Consecutive lessons in the same room require no time to travel,
+        // break is just long enough to travel to Fearnhill, but the five
+        // minutes between the bells of the schools before lunch are not
+        assert!(maths.enough_time_to_reach(&physics));
+        assert!(physics.enough_time_to_reach(&music));
+        assert!(!music.enough_time_to_reach(&chemistry));
+        assert_eq!(
+            music.leave_by(&chemistry).time(),
+            NaiveTime::from_hms_opt(11, 55, 0).unwrap()
+        );
+
+        // A slower policy (e.g., for a student who must use the lifts) is
+        // consulted instead of the default one
+        let slower = TransitPolicy::new()
+            .cross_site(Duration::hours(1))
+            .allowance(Duration::minutes(3));
+
+        assert!(maths.enough_time_to_reach_with(&physics, &slower));
+        assert!(!physics.enough_time_to_reach_with(&music, &slower));
+        assert_eq!(
+            music.leave_by_with(&chemistry, &slower).time(),
+            NaiveTime::from_hms_opt(11, 7, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn occurrences_room_override() {
+        let mut timetable = timetable();
+        let hall = Location::Highfield(HighfieldRoom::Hall);
+        let start = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+
+        assert!(timetable.override_room(
+            timeslot!(W1MP1),
+            start..=NaiveDate::from_ymd_opt(2023, 9, 8).unwrap(),
+            hall,
+        ));
+
+        let cycle = cycle();
+        let moved = timetable.occurrences(&cycle, start);
+        let unmoved = timetable.occurrences(&cycle, NaiveDate::from_ymd_opt(2023, 9, 18).unwrap());
+
+        assert_eq!(moved[0].activity.location(), Some(&hall));
+        assert_eq!(unmoved[0].activity, lesson("Maths"));
+    }
+
+    #[test]
+    fn occurrences_fall_back() {
+        let mut timetable = timetable();
+        let field: Location = "Field".parse().unwrap();
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+
+        let mut pe = lesson("PE");
+        if let Activity::Lesson { location, .. } = &mut pe {
+            *location = field;
+        }
+        timetable.insert(timeslot!(W1MP1), pe);
+
+        assert_eq!(
+            timetable.fall_back(date..=date, &RoomRegistry::curated()),
+            1
+        );
+        assert_eq!(timetable.overridden_location(timeslot!(W1MP1), date), None);
+
+        let locations: Vec<String> = timetable
+            .occurrences(&cycle(), date)
+            .iter()
+            .filter(|occurrence| occurrence.slot == timeslot!(W1MP1))
+            .filter_map(|occurrence| occurrence.activity.location().map(Location::to_string))
+            .collect();
+
+        assert_eq!(locations, ["H201", "Sports Hall"]);
+    }
+
+    #[test]
+    fn occurrences_early_closure() {
+        let mut cycle = cycle();
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+
+        cycle.calendar_mut().add_exception(
+            date,
+            DayException::EarlyClosure {
+                last_period: Period::Fourth,
+            },
+        );
+
+        let occurrences = timetable().occurrences(&cycle, date);
+
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].activity, lesson("Maths"));
+    }
+
+    #[test]
+    fn lesson_at_schedule() {
+        let mut cycle = cycle();
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let at = |hour, minute| Utc.with_ymd_and_hms(2023, 9, 4, hour, minute, 0).unwrap();
+        let subject = |cycle: &WeekCycle, hour, minute| {
+            timetable()
+                .lesson_at(cycle, at(hour, minute))
+                .map(|occurrence| occurrence.activity)
+        };
+
+        assert_eq!(subject(&cycle, 9, 40), Some(lesson("Maths")));
+        assert_eq!(subject(&cycle, 12, 40), None);
+
+        // Forty-minute periods for a staff training afternoon
+        let range = |start: (u8, u8), end: (u8, u8)| {
+            PeriodTime::new(
+                TimeOfDay::new(start.0, start.1).unwrap(),
+                TimeOfDay::new(end.0, end.1).unwrap(),
+            )
+        };
+        let shortened = BellSchedule::new(
+            [
+                range((8, 50), (9, 30)),
+                range((9, 30), (10, 10)),
+                range((10, 30), (11, 10)),
+                range((11, 10), (11, 50)),
+                range((12, 30), (13, 10)),
+            ],
+            range((10, 10), (10, 30)),
+            range((11, 50), (12, 30)),
+        )
+        .unwrap();
+        let mut schedules = BellSchedules::default();
+        schedules.set(School::Highfield, shortened);
+
+        cycle
+            .calendar_mut()
+            .add_exception(date, DayException::Schedule(schedules));
+
+        assert_eq!(subject(&cycle, 9, 40), None);
+        assert_eq!(subject(&cycle, 12, 40), Some(lesson("Physics")));
+        assert_eq!(
+            timetable()
+                .current_lesson(&cycle, at(12, 40))
+                .map(|lesson| lesson.minutes_remaining()),
+            Some(30)
+        );
+    }
+
+    #[test]
+    fn occurrences_suspended() {
+        let mut cycle = cycle();
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let exam = Activity::Miscellaneous("Mock Exams".to_string());
+
+        cycle.calendar_mut().add_exception(
+            date,
+            DayException::Suspended {
+                periods: vec![Period::First, Period::Second],
+                activity: exam.clone(),
+            },
+        );
+
+        let occurrences = timetable().occurrences(&cycle, date);
+
+        assert_eq!(occurrences.len(), 3);
+        assert_eq!(occurrences[0].activity, exam);
+        assert_eq!(occurrences[0].entry, None);
+        assert_eq!(occurrences[1].activity, exam);
+        assert_eq!(occurrences[2].activity, lesson("Physics"));
+    }
+
+    #[test]
+    fn spanning_entries() {
+        let mut timetable = timetable();
+        let double = timetable
+            .insert_spanning(timeslot!(W1MP3), 2, lesson("Science"))
+            .unwrap();
+
+        assert_eq!(timetable.at(timeslot!(W1MP4)).next().unwrap().0, double);
+        assert_eq!(timetable.lessons().in_period(Period::Fourth).count(), 1);
+
+        // A span produces an occurrence for each of its periods
+        let mut cycle = cycle();
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let occurrences = timetable.occurrences(&cycle, date);
+
+        assert_eq!(occurrences.len(), 4);
+        assert_eq!(occurrences[1].entry, Some(double));
+        assert_eq!(occurrences[2].entry, Some(double));
+
+        cycle.calendar_mut().add_exception(
+            date,
+            DayException::EarlyClosure {
+                last_period: Period::Third,
+            },
+        );
+        assert_eq!(timetable.occurrences(&cycle, date).len(), 2);
+
+        // Spans may extend across break and lunch (e.g., triple P.E.), and
+        // occupy each of their periods
+        let triple = timetable
+            .insert_spanning(timeslot!(W1TP3), 3, lesson("PE"))
+            .unwrap();
+
+        assert_eq!(
+            timetable.get(triple).unwrap().slots().collect::<Vec<_>>(),
+            [timeslot!(W1TP3), timeslot!(W1TP4), timeslot!(W1TP5)]
+        );
+        assert_eq!(timetable.at(timeslot!(W1TP5)).next().unwrap().0, triple);
+        assert!(timetable
+            .insert_spanning(timeslot!(W1TP2), 2, lesson("PE"))
+            .is_ok());
+
+        // But not past the end of the day
+        let error = timetable
+            .insert_spanning(timeslot!(W1MP4), 3, lesson("PE"))
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "an activity starting in period 4 cannot span 3 periods (must be 1 to 2, as it \
+             cannot span past the end of the day)"
+        );
+        assert_eq!(
+            timetable.insert_spanning(timeslot!(W1WP1), 6, lesson("PE")),
+            Err(TimetableError::InvalidSpan {
+                slot: timeslot!(W1WP1),
+                periods: 6
+            })
+        );
+        assert!(timetable
+            .insert_spanning(timeslot!(W1TP5), 2, lesson("PE"))
+            .is_err());
+        assert!(timetable
+            .insert_spanning(timeslot!(W1MP1), 0, lesson("PE"))
+            .is_err());
+
+        let entry = Entry {
+            periods: RangedU8::new(3).unwrap(),
+            ..Entry::new(timeslot!(W1TP4), lesson("PE"))
+        };
+        assert!(matches!(
+            timetable.insert_entry(entry),
+            Err(TimetableError::InvalidSpan { .. })
+        ));
+
+        // Nor can an entry be moved such that its span would extend past the
+        // end of the day, which leaves it unchanged
+        assert_eq!(
+            timetable.move_entry(double, timeslot!(W1TP5)),
+            Err(TimetableError::InvalidSpan {
+                slot: timeslot!(W1TP5),
+                periods: 2
+            })
+        );
+        assert!(timetable.move_entry(triple, timeslot!(W1TP4)).is_err());
+        assert_eq!(timetable.get(triple).unwrap().slot, timeslot!(W1TP3));
+
+        let entry = timetable.get(double).unwrap();
+        assert_eq!(
+            entry.slots().collect::<Vec<_>>(),
+            [timeslot!(W1MP3), timeslot!(W1MP4)]
+        );
+
+        assert_eq!(
+            timetable.move_entry(double, timeslot!(W1TP1)),
+            Ok(Some(timeslot!(W1MP3)))
+        );
+        assert_eq!(
+            timetable.move_entry(EntryId(99), timeslot!(W1TP1)),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn staffed_entries() {
+        let mut timetable = timetable();
+        let split_room: Location = "H202".parse().unwrap();
+        let entry = Entry::new(timeslot!(W1TP1), lesson("Science"))
+            .co_teacher(Teacher::new("ABC".to_string()).unwrap())
+            .co_teacher(Teacher::new("DEF".to_string()).unwrap())
+            .split_room(split_room);
+        let shared = timetable.insert_entry(entry).unwrap();
+
+        // Every teacher teaches (and every room hosts) the lesson
+        let entry = timetable.get(shared).unwrap();
+        assert_eq!(
+            entry.teachers().map(Teacher::code).collect::<Vec<_>>(),
+            ["ABC", "DEF"]
+        );
+        assert_eq!(entry.locations().count(), 2);
+        assert_eq!(timetable.lessons().taught_by("DEF").count(), 1);
+        assert_eq!(timetable.lessons().in_room(split_room).count(), 1);
+        assert_eq!(timetable.lessons().count(), 4);
+
+        timetable.remove(shared);
+        assert_eq!(timetable.lessons().taught_by("ABC").count(), 0);
+        assert_eq!(timetable.lessons().in_room(split_room).count(), 0);
+
+        // Only lessons may be team-taught or split
+        let error = timetable
+            .insert_entry(Entry::new(timeslot!(W1TP2), Activity::Break).split_room(split_room))
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            TimetableError::NotALesson {
+                slot: timeslot!(W1TP2)
+            }
+        );
+    }
+
+    #[test]
+    fn rotating_entries() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2023, month, day).unwrap();
+        let teacher = |code: &str| Some(Teacher::new(code.to_string()).unwrap());
+        let mut cycle = cycle();
+        cycle
+            .calendar_mut()
+            .add_term(Term::new(date(10, 30), date(12, 19)).unwrap());
+
+        let workshop: Location = "FH T1".parse().unwrap();
+        let studio: Location = "FH T2".parse().unwrap();
+        let rotation = Rotation::new(date(9, 4))
+            .stage(teacher("JSM"), workshop)
+            .stage(teacher("ABC"), studio);
+
+        let mut timetable = Timetable::new();
+        let id = timetable
+            .insert_entry(Entry::new(timeslot!(W1MP1), lesson("DT")).rotating(rotation))
+            .unwrap();
+
+        // Each half-term has its own teacher and room
+        let first = timetable.occurrences(&cycle, date(9, 4));
+        let second = timetable.occurrences(&cycle, date(11, 6));
+
+        assert_eq!(first[0].activity.teacher(), teacher("JSM").as_ref());
+        assert_eq!(first[0].activity.location(), Some(&workshop));
+        assert_eq!(second[0].activity.teacher(), teacher("ABC").as_ref());
+        assert_eq!(second[0].activity.location(), Some(&studio));
+        assert_eq!(second[0].entry, Some(id));
+
+        // Room overrides apply to the room of the stage
+        let hall = Location::Highfield(HighfieldRoom::Hall);
+        timetable.override_location(timeslot!(W1MP1), date(11, 6)..=date(11, 6), studio, hall);
+        assert_eq!(
+            timetable.occurrences(&cycle, date(11, 6))[0]
+                .activity
+                .location(),
+            Some(&hall)
+        );
+
+        // Undated queries see the lesson as scheduled
+        let effective = timetable.effective_on(cycle.calendar(), date(11, 6));
+
+        assert_eq!(timetable.lessons().in_room(studio).count(), 0);
+        assert_eq!(effective.lessons().in_room(studio).count(), 1);
+        assert_eq!(effective.lessons().taught_by("ABC").next().unwrap().0, id);
+
+        // Only lessons may rotate
+        let rotation = Rotation::new(date(9, 4)).stage(None, studio);
+        assert!(timetable
+            .insert_entry(Entry::new(timeslot!(W1MP2), Activity::Break).rotating(rotation))
+            .is_err());
+    }
+}
@@ -0,0 +1,494 @@
+//! A minimal TOML (as described by [TOML v1.0.0]) parser.
+//!
+//! Only the subset of TOML required by the timetable definition format is
+//! supported -- tables (i.e., `[week1]`), dotted keys, basic and literal
+//! strings, integers, booleans, arrays, and inline tables. Every key and
+//! value is accompanied by its span (i.e., its byte range within the
+//! document), such that errors can point at the offending part of the
+//! document.
+//!
+//! [TOML v1.0.0]: https://toml.io/en/v1.0.0
+
+use std::ops::Range;
+
+// The deepest nesting of arrays and inline tables which is parsed -- deeper
+// documents are rejected (rather than overflowing the stack, as the parser is
+// recursive)
+const MAX_DEPTH: usize = 128;
+
+/// A value accompanied by its span within the document.
+pub(crate) type Spanned<T> = (Range<usize>, T);
+
+/// A TOML value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TomlValue {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Spanned<TomlValue>>),
+    Table(Vec<(Spanned<String>, Spanned<TomlValue>)>),
+}
+
+/// A key/value pair, where the key is the full path to the value (i.e.,
+/// including the name of the table in which the pair was defined).
+pub(crate) type KeyValue = (Vec<Spanned<String>>, Spanned<TomlValue>);
+
+/// An error returned when a TOML document cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SyntaxError {
+    /// The span of the document at which the error occurred.
+    pub(crate) span: Range<usize>,
+
+    /// A description of what was expected.
+    pub(crate) message: &'static str,
+}
+
+/// Parses the TOML document provided into its key/value pairs (in the order
+/// in which they appear in the document).
+pub(crate) fn parse(input: &str) -> Result<Vec<KeyValue>, SyntaxError> {
+    let mut parser = Parser {
+        input: input.as_bytes(),
+        position: 0,
+        depth: 0,
+    };
+
+    let mut table = Vec::new();
+    let mut pairs = Vec::new();
+
+    loop {
+        parser.whitespace();
+
+        match parser.peek() {
+            None => return Ok(pairs),
+            Some(b'\n') => parser.position += 1,
+            Some(b'#') => parser.comment(),
+            Some(b'[') => {
+                parser.position += 1;
+
+                if parser.peek() == Some(b'[') {
+                    return Err(parser.error("arrays of tables are not supported"));
+                }
+
+                parser.whitespace();
+                table = parser.key()?;
+                parser.whitespace();
+                parser.expect(b']', "expected `]`")?;
+                parser.end_of_line()?;
+            }
+            Some(_) => {
+                let mut key = table.clone();
+                key.extend(parser.key()?);
+
+                parser.whitespace();
+                parser.expect(b'=', "expected `=`")?;
+                parser.whitespace();
+
+                pairs.push((key, parser.value()?));
+                parser.end_of_line()?;
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    position: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &'static str) -> SyntaxError {
+        // Point at the offending character (or the end of the document)
+        let end = (self.position + 1).min(self.input.len());
+
+        SyntaxError {
+            span: self.position..end.max(self.position),
+            message,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    fn expect(&mut self, byte: u8, message: &'static str) -> Result<(), SyntaxError> {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    // Skip spaces and tabs (but not newlines, which are significant)
+    fn whitespace(&mut self) {
+        while let Some(b' ' | b'\t') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    // Skip spaces, tabs, newlines, and comments (as permitted within arrays)
+    fn blank(&mut self) {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\r' | b'\n') => self.position += 1,
+                Some(b'#') => self.comment(),
+                _ => return,
+            }
+        }
+    }
+
+    fn comment(&mut self) {
+        while !matches!(self.peek(), None | Some(b'\n')) {
+            self.position += 1;
+        }
+    }
+
+    // Nothing but whitespace or a comment may follow a key/value pair or a
+    // table header on the same line
+    fn end_of_line(&mut self) -> Result<(), SyntaxError> {
+        self.whitespace();
+
+        if self.peek() == Some(b'#') {
+            self.comment();
+        }
+        if self.peek() == Some(b'\r') {
+            self.position += 1;
+        }
+
+        match self.peek() {
+            None => Ok(()),
+            Some(b'\n') => {
+                self.position += 1;
+                Ok(())
+            }
+            _ => Err(self.error("expected a new line")),
+        }
+    }
+
+    // Parse a (possibly dotted) key
+    fn key(&mut self) -> Result<Vec<Spanned<String>>, SyntaxError> {
+        let mut parts = Vec::new();
+
+        loop {
+            let start = self.position;
+
+            let part = match self.peek() {
+                Some(b'"') => self.basic_string()?,
+                Some(b'\'') => self.literal_string()?,
+                _ => {
+                    while let Some(b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-') =
+                        self.peek()
+                    {
+                        self.position += 1;
+                    }
+
+                    if start == self.position {
+                        return Err(self.error("expected a key"));
+                    }
+
+                    // Bare keys only contain ASCII characters
+                    String::from_utf8_lossy(&self.input[start..self.position]).into_owned()
+                }
+            };
+
+            parts.push((start..self.position, part));
+            self.whitespace();
+
+            if self.peek() == Some(b'.') {
+                self.position += 1;
+                self.whitespace();
+            } else {
+                return Ok(parts);
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Spanned<TomlValue>, SyntaxError> {
+        let start = self.position;
+
+        let value = match self.peek() {
+            Some(b'"') => TomlValue::String(self.basic_string()?),
+            Some(b'\'') => TomlValue::String(self.literal_string()?),
+            Some(b'[') => self.nested(Self::array)?,
+            Some(b'{') => self.nested(Self::inline_table)?,
+            Some(b't') => self.literal("true", TomlValue::Boolean(true))?,
+            Some(b'f') => self.literal("false", TomlValue::Boolean(false))?,
+            Some(b'-' | b'+' | b'0'..=b'9') => self.integer()?,
+            _ => return Err(self.error("expected a value")),
+        };
+
+        Ok((start..self.position, value))
+    }
+
+    // Parse an array or inline table, one level deeper than the current value
+    fn nested(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<TomlValue, SyntaxError>,
+    ) -> Result<TomlValue, SyntaxError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("arrays and inline tables are nested too deeply"));
+        }
+
+        self.depth += 1;
+        let value = f(self);
+        self.depth -= 1;
+
+        value
+    }
+
+    fn literal(&mut self, literal: &str, value: TomlValue) -> Result<TomlValue, SyntaxError> {
+        if self.input[self.position..].starts_with(literal.as_bytes()) {
+            self.position += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a value"))
+        }
+    }
+
+    fn integer(&mut self) -> Result<TomlValue, SyntaxError> {
+        let start = self.position;
+
+        while let Some(b'-' | b'+' | b'_' | b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+
+        let digits: String = String::from_utf8_lossy(&self.input[start..self.position])
+            .chars()
+            .filter(|c| *c != '_')
+            .collect();
+
+        digits
+            .parse()
+            .map(TomlValue::Integer)
+            .map_err(|_| SyntaxError {
+                span: start..self.position,
+                message: "expected an integer",
+            })
+    }
+
+    fn array(&mut self) -> Result<TomlValue, SyntaxError> {
+        let mut values = Vec::new();
+
+        self.expect(b'[', "expected `[`")?;
+
+        loop {
+            self.blank();
+
+            if self.peek() == Some(b']') {
+                self.position += 1;
+                return Ok(TomlValue::Array(values));
+            }
+
+            values.push(self.value()?);
+            self.blank();
+
+            // A trailing comma is permitted
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {}
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<TomlValue, SyntaxError> {
+        let mut members = Vec::new();
+
+        self.expect(b'{', "expected `{`")?;
+        self.whitespace();
+
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(TomlValue::Table(members));
+        }
+
+        loop {
+            self.whitespace();
+
+            let key = self.key()?;
+
+            // Dotted keys within inline tables are not required by the
+            // timetable definition format
+            if key.len() != 1 {
+                return Err(SyntaxError {
+                    span: key[0].0.start..self.position,
+                    message: "dotted keys are not supported within inline tables",
+                });
+            }
+
+            self.whitespace();
+            self.expect(b'=', "expected `=`")?;
+            self.whitespace();
+
+            let value = self.value()?;
+
+            if members.iter().any(|((_, name), _)| *name == key[0].1) {
+                return Err(SyntaxError {
+                    span: key[0].0.clone(),
+                    message: "duplicate key",
+                });
+            }
+
+            members.push((key.into_iter().next().unwrap(), value));
+            self.whitespace();
+
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(TomlValue::Table(members));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String, SyntaxError> {
+        self.expect(b'"', "expected `\"`")?;
+
+        if self.input[self.position..].starts_with(b"\"\"") {
+            return Err(self.error("multi-line strings are not supported"));
+        }
+
+        let mut bytes = Vec::new();
+
+        loop {
+            match self.peek() {
+                None | Some(b'\n') => return Err(self.error("unterminated string")),
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    self.position += 1;
+
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => self.unicode_escape(4)?,
+                        Some(b'U') => self.unicode_escape(8)?,
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut buffer).as_bytes());
+                }
+                Some(byte) => bytes.push(byte),
+            }
+
+            self.position += 1;
+        }
+
+        self.position += 1;
+
+        // The input is a `str`, so any unescaped bytes are valid UTF-8
+        Ok(String::from_utf8(bytes).unwrap_or_default())
+    }
+
+    // Parse the hexadecimal digits of a `\u` or `\U` escape sequence (leaving
+    // the position on the final digit)
+    fn unicode_escape(&mut self, digits: usize) -> Result<char, SyntaxError> {
+        let start = self.position + 1;
+
+        let escaped = self
+            .input
+            .get(start..start + digits)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid escape sequence"))?;
+
+        self.position += digits;
+        Ok(escaped)
+    }
+
+    fn literal_string(&mut self) -> Result<String, SyntaxError> {
+        self.expect(b'\'', "expected `'`")?;
+
+        if self.input[self.position..].starts_with(b"''") {
+            return Err(self.error("multi-line strings are not supported"));
+        }
+
+        let start = self.position;
+
+        loop {
+            match self.peek() {
+                None | Some(b'\n') => return Err(self.error("unterminated string")),
+                Some(b'\'') => break,
+                Some(_) => self.position += 1,
+            }
+        }
+
+        let string = String::from_utf8_lossy(&self.input[start..self.position]).into_owned();
+
+        self.position += 1;
+        Ok(string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml_parse_valid() {
+        let pairs = parse(
+            "# A comment\n[week1]\nmon.p3 = { subject = 'Ma', room = \"H\\u0032\" } # Maths\n\ntue . \"p1\" = [1, true,\n]\n",
+        )
+        .unwrap();
+
+        let keys: Vec<Vec<&str>> = pairs
+            .iter()
+            .map(|(key, _)| key.iter().map(|(_, part)| part.as_str()).collect())
+            .collect();
+
+        assert_eq!(
+            keys,
+            vec![vec!["week1", "mon", "p3"], vec!["week1", "tue", "p1"]]
+        );
+        match &pairs[0].1 .1 {
+            TomlValue::Table(members) => {
+                assert_eq!(members[0].0 .1, "subject");
+                assert_eq!(members[1].1 .1, TomlValue::String("H2".to_string()));
+            }
+            _ => panic!("expected an inline table"),
+        }
+        assert_eq!(
+            pairs[1].1 .1,
+            TomlValue::Array(vec![
+                (89..90, TomlValue::Integer(1)),
+                (92..96, TomlValue::Boolean(true)),
+            ])
+        );
+    }
+
+    #[test]
+    fn toml_parse_invalid() {
+        let input = "[week1]\nmon.p3 = { subject = 'Ma' \n";
+
+        assert_eq!(
+            parse(input),
+            Err(SyntaxError {
+                span: 34..35,
+                message: "expected `,` or `}`",
+            })
+        );
+        assert!(parse("a = 1 b = 2").is_err());
+        assert!(parse("[[week1]]").is_err());
+
+        // Deeply nested values are rejected, rather than overflowing the stack
+        let nested = |depth| format!("x = {}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(
+            parse(&nested(MAX_DEPTH + 1)),
+            Err(SyntaxError {
+                span: 4 + MAX_DEPTH..5 + MAX_DEPTH,
+                message: "arrays and inline tables are nested too deeply",
+            })
+        );
+        assert!(parse(&format!("x = {}", "[".repeat(200_000))).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::undo::inverse;
+use crate::validate::{Report, Severity, Validator};
+use crate::{Room, Timetable};
+use std::sync::Arc;
+
+impl<L: Room> Timetable<L> {
+    /// Makes a batch of edits to the `Timetable` as a single transaction: the
+    /// edits made by `f` are validated as a whole (using the `validator`
+    /// provided), and are either committed together or rolled back together.
+    ///
+    /// The transaction is rolled back should the edits introduce any finding
+    /// with a severity of [`Severity::Error`] which the timetable did not
+    /// already have -- as a result, an importer cannot leave the timetable
+    /// half-updated, or in a clashing state:
+    ///
+    /// ```
+    /// use timetableau::validate::{NoClashes, Validator};
+    /// use timetableau::{timeslot, Activity, Class, Subject, Timetable};
+    ///
+    /// let lesson = |class: &str| Activity::Lesson {
+    ///     subject: Subject::new("Ma".to_string()).unwrap(),
+    ///     class: Class::new(class.to_string()).unwrap(),
+    ///     teacher: None,
+    ///     location: "H201".parse().unwrap(),
+    /// };
+    ///
+    /// let validator = Validator::new().rule(NoClashes);
+    /// let mut timetable = Timetable::new();
+    ///
+    /// let report = timetable
+    ///     .transaction(&validator, |tx| {
+    ///         tx.insert(timeslot!(W1MP1), lesson("10A"));
+    ///         tx.insert(timeslot!(W1MP1), lesson("10B"));
+    ///     })
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(report.findings[0].message, "H201 hosts 2 lessons at once");
+    /// assert!(timetable.is_empty());
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// The edits of a committed transaction are recorded in the timetable's
+    /// [history](Self::history) (and sent to its subscribers) once the
+    /// transaction has been committed -- the edits of a rolled back
+    /// transaction are not recorded at all.
+    ///
+    /// # Errors
+    ///
+    /// The transaction is rolled back, and a [`Report`] of the findings which
+    /// it introduced is returned, should the edits not be valid.
+    pub fn transaction<T>(
+        &mut self,
+        validator: &Validator<L>,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> Result<T, Report> {
+        let existing = validator.validate(self);
+        let start = self.history.len();
+
+        // Subscribers are only notified once the transaction is committed
+        #[cfg(feature = "std")]
+        let subscribers = std::mem::take(&mut self.subscribers);
+
+        let value = f(self);
+
+        #[cfg(feature = "std")]
+        {
+            self.subscribers = subscribers;
+        }
+
+        let introduced: Vec<_> = validator
+            .validate(self)
+            .findings
+            .into_iter()
+            .filter(|finding| {
+                finding.severity == Severity::Error && !existing.findings.contains(finding)
+            })
+            .collect();
+
+        if introduced.is_empty() {
+            #[cfg(feature = "std")]
+            for index in start..self.history.len() {
+                let record = self.history[index].clone();
+                self.subscribers.notify(&record);
+            }
+
+            return Ok(value);
+        }
+
+        // Reverse the edits (most recent first) without recording them
+        let records = Arc::make_mut(&mut self.history).split_off(start);
+
+        for record in records.iter().rev() {
+            for change in inverse(self, &record.change) {
+                self.apply(&change);
+            }
+        }
+
+        Err(Report {
+            findings: introduced,
+        })
+    }
+}
+
+// The tests observe the subscribers of the timetable, which require `std`
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::validate::{NoClashes, Validator};
+    use crate::{timeslot, Activity, Class, Location, Subject, Timetable};
+    use chrono::NaiveDate;
+
+    fn lesson(class: &str, location: &str) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Ma".to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: None,
+            location: location.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn transaction_commit_and_rollback() {
+        let validator = Validator::new().rule(NoClashes);
+        let hall: Location = "Hall".parse().unwrap();
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("10A", "H201"));
+        timetable.insert(timeslot!(W1MP1), lesson("10B", "H201"));
+
+        let receiver = timetable.subscribe();
+        let before = timetable.clone();
+
+        // The existing clash does not prevent unrelated edits
+        let id = timetable
+            .transaction(&validator, |tx| {
+                tx.insert(timeslot!(W1MP2), lesson("10C", "H201"))
+            })
+            .unwrap();
+
+        assert_eq!(receiver.try_iter().count(), 1);
+
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+        let report = timetable
+            .transaction(&validator, |tx| {
+                tx.move_entry(id, timeslot!(W1MP3)).unwrap();
+                tx.relocate(id, hall);
+                tx.override_room(timeslot!(W1MP3), date..=date, hall);
+                tx.insert(timeslot!(W1MP3), lesson("10D", "Hall"));
+            })
+            .unwrap_err();
+
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].message, "Hall hosts 2 lessons at once");
+        assert_eq!(timetable.len(), before.len() + 1);
+        assert_eq!(
+            timetable.get(id).unwrap().activity.location(),
+            Some(&"H201".parse().unwrap())
+        );
+        assert_eq!(timetable.get(id).unwrap().slot, timeslot!(W1MP2));
+        assert!(timetable.overrides().is_empty());
+        assert_eq!(timetable.history().len(), 3);
+        assert_eq!(receiver.try_iter().count(), 0);
+    }
+}
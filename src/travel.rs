@@ -0,0 +1,328 @@
+use crate::{
+    Activity, BellSchedules, EntryId, Location, Period, Room, RoomKind, TimeSlot, Timetable,
+    TransitPolicy, Week,
+};
+use chrono::{Duration, Weekday};
+
+/// A pair of consecutive lessons at different sites between which there is
+/// not enough time to travel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TravelIssue {
+    /// The lesson from which the student/teacher must depart.
+    pub from: EntryId,
+
+    /// The lesson at which the student/teacher must arrive.
+    pub to: EntryId,
+
+    /// The time between the end of the first lesson and the start of the
+    /// second lesson.
+    pub available: Duration,
+
+    /// The time required to travel between the two lessons.
+    pub required: Duration,
+}
+
+impl Timetable {
+    /// Finds every pair of consecutive lessons taking place at different
+    /// sites (i.e., different schools, or an [off-site
+    /// location](crate::OffSite) and any other site) between which there is
+    /// less than `travel_time` to travel.
+    ///
+    /// The time available between two lessons is calculated using the
+    /// [`BellSchedule`](crate::BellSchedule) of each lesson's school -- the
+    /// Highfield school and the Fearnhill school do not share bell times, so
+    /// the end of a lesson at one school does not necessarily coincide with
+    /// the start of the next period at the other. A lesson at an off-site
+    /// location follows the bell schedule of the school which holds it, and
+    /// [remote](crate::Remote) lessons are disregarded.
+    ///
+    /// # Remarks
+    ///
+    /// This check is intended for the timetable of a single student or
+    /// teacher -- every pair of lessons in consecutive periods is
+    /// considered, regardless of whether the same person attends both.
+    pub fn travel_issues(
+        &self,
+        schedules: &BellSchedules,
+        travel_time: Duration,
+    ) -> Vec<TravelIssue> {
+        let lessons = || {
+            self.entries()
+                .filter_map(|(id, entry)| match &entry.activity {
+                    // No time is required to travel to (or from) a remote lesson
+                    // A lesson spanning multiple periods is departed from
+                    // after its last period
+                    Activity::Lesson { location, .. } if location.kind() != RoomKind::Remote => {
+                        let last = entry.slots().last().unwrap_or(entry.slot);
+
+                        Some((id, entry.slot, last, location))
+                    }
+                    _ => None,
+                })
+        };
+
+        let mut issues = Vec::new();
+
+        for (from, _, from_slot, from_location) in lessons() {
+            let next = match from_slot.period.next() {
+                Some(next) => next,
+                None => continue,
+            };
+
+            for (to, to_slot, _, to_location) in lessons() {
+                // Only consider lessons in the following period at a
+                // different site
+                if to_slot.week != from_slot.week
+                    || to_slot.day != from_slot.day
+                    || to_slot.period != next
+                    || to_location.site() == from_location.site()
+                {
+                    continue;
+                }
+
+                let available = schedules.get(to_location.school()).start(next)
+                    - schedules.get(from_location.school()).end(from_slot.period);
+
+                if available < travel_time {
+                    issues.push(TravelIssue {
+                        from,
+                        to,
+                        available,
+                        required: travel_time,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Inserts a [`Transit`](Activity::Transit) into the free period
+    /// preceding each lesson which takes place at a different site to the
+    /// previous lesson of the day (e.g., a student walking from Highfield to
+    /// Fearnhill between the first and third periods), such that the
+    /// journey is shown instead of an unexplained free period.
+    ///
+    /// The duration of each transit is estimated by
+    /// [`Location::travel_time`] (*see [`Self::insert_transits_with`]*). No
+    /// transit is inserted should there not be
+    /// a free period (i.e., a period without any activities) between the
+    /// lessons -- *see [`Self::travel_issues`]* -- and [remote](crate::Remote)
+    /// lessons are disregarded.
+    ///
+    /// # Returns
+    ///
+    /// The identifiers of the transits inserted (inserting the transits of a
+    /// timetable a second time inserts none, as the free periods are then
+    /// occupied).
+    pub fn insert_transits(&mut self) -> Vec<EntryId> {
+        self.insert_transits_with(&TransitPolicy::default())
+    }
+
+    /// Inserts a [`Transit`](Activity::Transit) before each lesson at a
+    /// different site to the previous lesson of the day, estimating the
+    /// duration of each transit using the `policy` provided -- *see
+    /// [`Self::insert_transits`] for more information*.
+    pub fn insert_transits_with(&mut self, policy: &TransitPolicy) -> Vec<EntryId> {
+        let mut transits = Vec::new();
+
+        for week in [Week::WeekOne, Week::WeekTwo] {
+            for day in [
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ] {
+                let slot = |period| TimeSlot { week, day, period };
+
+                // The location of the previous lesson of the day, and the
+                // last free period since it
+                let mut previous: Option<Location> = None;
+                let mut free: Option<Period> = None;
+
+                for period in Period::ALL {
+                    let activities: Vec<&Activity> = self
+                        .at(slot(period))
+                        .map(|(_, entry)| &entry.activity)
+                        .collect();
+
+                    if activities.is_empty() {
+                        free = Some(period);
+                        continue;
+                    }
+
+                    // The journey has already been inserted
+                    if activities
+                        .iter()
+                        .any(|activity| matches!(activity, Activity::Transit { .. }))
+                    {
+                        free = None;
+                        continue;
+                    }
+
+                    let location = activities
+                        .into_iter()
+                        .filter_map(|activity| activity.location().copied())
+                        .find(|location| location.kind() != RoomKind::Remote);
+
+                    let Some(location) = location else {
+                        continue;
+                    };
+
+                    if let (Some(from), Some(free)) = (previous, free) {
+                        if from.site() != location.site() {
+                            transits.push(self.insert(
+                                slot(free),
+                                Activity::Transit {
+                                    from,
+                                    to: location,
+                                    duration: policy.travel_time(&from, &location),
+                                },
+                            ));
+                        }
+                    }
+
+                    previous = Some(location);
+                    free = None;
+                }
+            }
+        }
+
+        transits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, FearnhillRoom, HighfieldRoom, Location, Subject};
+
+    fn lesson(location: Location) -> Activity {
+        Activity::Lesson {
+            subject: Subject::new("Physics".to_string()).unwrap(),
+            class: Class::new("JSM".to_string()).unwrap(),
+            teacher: None,
+            location,
+        }
+    }
+
+    #[test]
+    fn travel_misaligned_schedules() {
+        let mut timetable = Timetable::new();
+
+        let from = timetable.insert(
+            timeslot!(W1MP1),
+            lesson(Location::Fearnhill(FearnhillRoom::Gym)),
+        );
+        let to = timetable.insert(
+            timeslot!(W1MP2),
+            lesson(Location::Highfield(HighfieldRoom::Hall)),
+        );
+
+        let issues = timetable.travel_issues(&BellSchedules::default(), Duration::minutes(15));
+
+        assert_eq!(
+            issues,
+            vec![TravelIssue {
+                from,
+                to,
+                available: Duration::minutes(5),
+                required: Duration::minutes(15),
+            }]
+        );
+    }
+
+    #[test]
+    fn travel_off_site() {
+        let mut timetable = Timetable::new();
+        let pool: Location = "@Stevenage Pool".parse().unwrap();
+
+        // Travelling to an off-site location from the school which holds the
+        // lesson still takes time
+        let from = timetable.insert(timeslot!(W1MP1), lesson("H201".parse().unwrap()));
+        let to = timetable.insert(timeslot!(W1MP2), lesson(pool));
+        timetable.insert(timeslot!(W1MP3), lesson(pool));
+
+        let issues = timetable.travel_issues(&BellSchedules::default(), Duration::minutes(15));
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].from, issues[0].to), (from, to));
+    }
+
+    #[test]
+    fn transits_inserted_into_free_periods() {
+        let gym = Location::Fearnhill(FearnhillRoom::Gym);
+        let hall = Location::Highfield(HighfieldRoom::Hall);
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson(hall));
+        timetable.insert(timeslot!(W1MP4), lesson(gym));
+        timetable.insert(timeslot!(W1MP5), lesson(hall));
+        timetable.insert(timeslot!(W1TP1), lesson(hall));
+        timetable.insert(timeslot!(W1TP3), lesson("H201".parse().unwrap()));
+
+        let transits = timetable.insert_transits();
+
+        // The transit occupies the free period preceding the lesson, and
+        // consecutive lessons have no free period to occupy
+        assert_eq!(transits.len(), 1);
+        assert_eq!(timetable.get(transits[0]).unwrap().slot, timeslot!(W1MP3));
+        assert_eq!(
+            timetable.get(transits[0]).unwrap().activity,
+            Activity::Transit {
+                from: hall,
+                to: gym,
+                duration: Duration::minutes(15),
+            }
+        );
+        assert!(timetable.insert_transits().is_empty());
+    }
+
+    #[test]
+    fn travel_over_break() {
+        let mut timetable = Timetable::new();
+
+        timetable.insert(
+            timeslot!(W1MP2),
+            lesson(Location::Fearnhill(FearnhillRoom::Gym)),
+        );
+        timetable.insert(
+            timeslot!(W1MP3),
+            lesson(Location::Highfield(HighfieldRoom::Hall)),
+        );
+
+        let issues = timetable.travel_issues(&BellSchedules::default(), Duration::minutes(15));
+
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn travel_after_double() {
+        let mut timetable = Timetable::new();
+
+        // The journey starts after the second period of the double
+        let from = timetable
+            .insert_spanning(
+                timeslot!(W1MP1),
+                2,
+                lesson(Location::Fearnhill(FearnhillRoom::Gym)),
+            )
+            .unwrap();
+        let to = timetable.insert(
+            timeslot!(W1MP3),
+            lesson(Location::Highfield(HighfieldRoom::Hall)),
+        );
+
+        let schedules = BellSchedules::default();
+        let issues = timetable.travel_issues(&schedules, Duration::minutes(60));
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].from, issues[0].to), (from, to));
+        assert_eq!(
+            issues[0].available,
+            schedules.get(crate::School::Highfield).start(Period::Third)
+                - schedules.get(crate::School::Fearnhill).end(Period::Second)
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{Activity, Change, EntryId, Location, Room, TimeSlot, Timetable, TimetableError};
+
+/// A [`Timetable`] whose edits can be undone and redone (e.g., by a graphical
+/// timetable editor).
+///
+/// Each edit is a group of mutations made to the timetable (*see
+/// [`EditStack::edit`]*) -- undoing an edit reverses every mutation of the
+/// group, such that compound edits (e.g., swapping two lessons) are undone in
+/// a single step:
+///
+/// ```
+/// use timetableau::{timeslot, Activity, EditStack, Timetable};
+///
+/// let mut stack = EditStack::new(Timetable::new());
+///
+/// let a = stack.insert(timeslot!(W1MP1), Activity::Registration);
+/// let b = stack.insert(timeslot!(W1MP2), Activity::Break);
+///
+/// // Swap the entries (as a single edit)
+/// stack.edit(|timetable| {
+///     timetable.move_entry(a, timeslot!(W1MP2)).unwrap();
+///     timetable.move_entry(b, timeslot!(W1MP1)).unwrap();
+/// });
+///
+/// assert!(stack.undo());
+/// assert_eq!(stack.timetable().get(a).unwrap().slot, timeslot!(W1MP1));
+///
+/// assert!(stack.redo());
+/// assert_eq!(stack.timetable().get(a).unwrap().slot, timeslot!(W1MP2));
+/// ```
+///
+/// # Remarks
+///
+/// Undoing (or redoing) an edit mutates the timetable, so the reversal is
+/// recorded in the timetable's [history](Timetable::history) (and sent to its
+/// subscribers) like any other change.
+#[derive(Debug, Clone)]
+pub struct EditStack<L = Location> {
+    timetable: Timetable<L>,
+    undo: Vec<Vec<Change<L>>>,
+    redo: Vec<Vec<Change<L>>>,
+}
+
+impl<L: Room> EditStack<L> {
+    /// Creates a new `EditStack` (without any edits to undo) around the
+    /// `timetable` provided.
+    pub fn new(timetable: Timetable<L>) -> Self {
+        Self {
+            timetable,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// Retrieves the timetable being edited.
+    pub fn timetable(&self) -> &Timetable<L> {
+        &self.timetable
+    }
+
+    /// Retrieves the timetable being edited (discarding its edits).
+    pub fn into_inner(self) -> Timetable<L> {
+        self.timetable
+    }
+
+    /// Makes an edit to the timetable: every mutation made by `f` is grouped
+    /// into a single edit, which is undone (or redone) in a single step.
+    ///
+    /// Making an edit discards the edits which could have been redone.
+    ///
+    /// # Returns
+    ///
+    /// The value returned by `f`.
+    pub fn edit<T>(&mut self, f: impl FnOnce(&mut Timetable<L>) -> T) -> T {
+        let start = self.timetable.history.len();
+        let value = f(&mut self.timetable);
+
+        let changes: Vec<Change<L>> = self.timetable.history[start..]
+            .iter()
+            .map(|record| record.change.clone())
+            .collect();
+
+        if !changes.is_empty() {
+            self.undo.push(changes);
+            self.redo.clear();
+        }
+
+        value
+    }
+
+    /// Schedules an `activity` for the `slot` provided (as a single edit) --
+    /// *see [`Timetable::insert`] for more information*.
+    pub fn insert(&mut self, slot: TimeSlot, activity: Activity<L>) -> EntryId {
+        self.edit(|timetable| timetable.insert(slot, activity))
+    }
+
+    /// Removes the entry with the identifier `id` (as a single edit) -- *see
+    /// [`Timetable::remove`] for more information*.
+    pub fn remove(&mut self, id: EntryId) -> bool {
+        self.edit(|timetable| timetable.remove(id).is_some())
+    }
+
+    /// Moves the entry with the identifier `id` to the `slot` provided (as a
+    /// single edit) -- *see [`Timetable::move_entry`] for more information*.
+    pub fn move_entry(
+        &mut self,
+        id: EntryId,
+        slot: TimeSlot,
+    ) -> Result<Option<TimeSlot>, TimetableError> {
+        self.edit(|timetable| timetable.move_entry(id, slot))
+    }
+
+    /// Changes the location of the lesson with the identifier `id` (as a
+    /// single edit) -- *see [`Timetable::relocate`] for more information*.
+    pub fn relocate(&mut self, id: EntryId, location: L) -> Option<L> {
+        self.edit(|timetable| timetable.relocate(id, location))
+    }
+
+    /// Whether there is an edit which can be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is an edit which can be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Undoes the most recent edit.
+    ///
+    /// # Returns
+    ///
+    /// [`false`] if there was no edit to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(changes) = self.undo.pop() else {
+            return false;
+        };
+
+        for change in changes.iter().rev() {
+            for inverse in inverse(&self.timetable, change) {
+                self.timetable.commit(inverse);
+            }
+        }
+
+        self.redo.push(changes);
+        true
+    }
+
+    /// Redoes the most recently undone edit.
+    ///
+    /// # Returns
+    ///
+    /// [`false`] if there was no edit to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(changes) = self.redo.pop() else {
+            return false;
+        };
+
+        // Every change records its new values, so the edit can be replayed
+        for change in &changes {
+            self.timetable.commit(change.clone());
+        }
+
+        self.undo.push(changes);
+        true
+    }
+}
+
+/// Retrieves the changes which reverse the `change` provided (which must be
+/// the most recent change made to the `timetable` which has not been
+/// reversed), in the order in which they must be made.
+pub(crate) fn inverse<L: Room>(timetable: &Timetable<L>, change: &Change<L>) -> Vec<Change<L>> {
+    match change {
+        Change::Inserted { id, entry } => vec![Change::Removed {
+            id: *id,
+            entry: entry.clone(),
+        }],
+        Change::Removed { id, entry } => vec![Change::Inserted {
+            id: *id,
+            entry: entry.clone(),
+        }],
+        Change::Moved { id, from, to } => vec![Change::Moved {
+            id: *id,
+            from: *to,
+            to: *from,
+        }],
+        Change::Relocated { id, from, to } => vec![Change::Relocated {
+            id: *id,
+            from: to.clone(),
+            to: from.clone(),
+        }],
+        // The override was recorded last, so it remains the last override
+        Change::OverrideAdded(room_override) => vec![Change::OverrideRemoved {
+            index: timetable.overrides().len() - 1,
+            room_override: room_override.clone(),
+        }],
+        // Overrides can only be recorded last, so the overrides which followed
+        // the removed override are removed and recorded again (preserving
+        // their precedence)
+        Change::OverrideRemoved {
+            index,
+            room_override,
+        } => {
+            let following = &timetable.overrides()[*index..];
+
+            let removed = following
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(offset, room_override)| Change::OverrideRemoved {
+                    index: index + offset,
+                    room_override: room_override.clone(),
+                });
+            let added = std::iter::once(room_override)
+                .chain(following)
+                .map(|room_override| Change::OverrideAdded(room_override.clone()));
+
+            removed.chain(added).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, Subject};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn undo_redo_edits() {
+        let h201: Location = "H201".parse().unwrap();
+        let hall: Location = "Hall".parse().unwrap();
+        let date = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+
+        let mut stack = EditStack::new(Timetable::new());
+
+        let id = stack.insert(
+            timeslot!(W1MP1),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("10A".to_string()).unwrap(),
+                teacher: None,
+                location: h201,
+            },
+        );
+        stack.edit(|timetable| {
+            timetable.override_room(timeslot!(W1MP1), date..=date, hall);
+            timetable.override_room(timeslot!(W1MP2), date..=date, hall);
+        });
+
+        let before = stack.timetable().clone();
+
+        stack.edit(|timetable| {
+            timetable.relocate(id, hall);
+            timetable.move_entry(id, timeslot!(W2FP5)).unwrap();
+            timetable.remove_override(0);
+        });
+        stack.remove(id);
+
+        assert!(stack.undo());
+        assert!(stack.undo());
+        assert_eq!(stack.timetable(), &before);
+        assert!(stack.can_redo());
+
+        assert!(stack.redo());
+        assert_eq!(stack.timetable().get(id).unwrap().slot, timeslot!(W2FP5));
+        assert_eq!(stack.timetable().overrides().len(), 1);
+
+        while stack.undo() {}
+
+        assert!(stack.timetable().is_empty());
+        assert!(stack.timetable().overrides().is_empty());
+
+        stack.redo();
+        stack.insert(timeslot!(W1MP2), Activity::Break);
+
+        assert!(!stack.can_redo());
+        assert_eq!(stack.timetable().len(), 2);
+    }
+}
@@ -0,0 +1,1949 @@
+//! Validation of [`Timetable`]s against a set of [`Rule`]s.
+//!
+//! Schools disagree as to what makes a timetable acceptable (e.g., one school
+//! may permit a teacher to teach five consecutive periods, whereas another
+//! may not) -- as a result, validation is performed by a [`Validator`]
+//! containing whichever rules a deployment wishes to enforce. Deployments
+//! with local policies can enforce them by implementing [`Rule`] themselves.
+//!
+//! ```
+//! use timetableau::validate::{MaxConsecutive, NoClashes, Validator};
+//! use timetableau::Timetable;
+//!
+//! let report = Validator::new()
+//!     .rule(NoClashes)
+//!     .rule(MaxConsecutive::new(4))
+//!     .validate(&Timetable::new());
+//!
+//! assert!(report.is_valid());
+//! ```
+
+use crate::accessibility::AccessibilityProfiles;
+use crate::allocate::Room;
+use crate::attendance::Roster;
+use crate::availability::StaffAvailability;
+use crate::json::write_string;
+use crate::{
+    Activity, BellSchedules, Change, Entry, EntryId, Location, RoomRegistry, Teacher, TimeSlot,
+    Timetable, TransitPolicy,
+};
+use chrono::{Duration, NaiveDate};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter, Write};
+
+/// The severity of a [`Finding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The finding is informational (i.e., it does not require any action).
+    Info,
+
+    /// The finding should be reviewed, but does not make the timetable
+    /// unusable.
+    Warning,
+
+    /// The finding makes the timetable unusable (e.g., a teacher is required
+    /// to be in two places at once).
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Info => f.write_str("info"),
+            Self::Warning => f.write_str("warning"),
+            Self::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A problem found by a [`Rule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// The name of the rule which produced the finding.
+    pub rule: &'static str,
+
+    /// The severity of the finding.
+    pub severity: Severity,
+
+    /// The entries involved in the finding.
+    pub entries: Vec<EntryId>,
+
+    /// A human-readable description of the finding.
+    pub message: String,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]: {}", self.severity, self.rule, self.message)
+    }
+}
+
+/// The result of validating a [`Timetable`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Every finding produced by the rules (in the order in which the rules
+    /// were added to the [`Validator`]).
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    /// Whether the timetable is valid (i.e., no finding has a severity of
+    /// [`Severity::Error`]).
+    pub fn is_valid(&self) -> bool {
+        self.severity() < Some(Severity::Error)
+    }
+
+    /// Retrieves the highest severity of the findings (or [`None`] should
+    /// there be no findings).
+    pub fn severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|finding| finding.severity).max()
+    }
+
+    /// Retrieves the findings with a severity of at least `severity`.
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &Finding> + '_ {
+        self.findings
+            .iter()
+            .filter(move |finding| finding.severity >= severity)
+    }
+
+    /// Converts the `Report` into a JSON document (e.g., for the checks of a
+    /// CI pipeline): an object containing whether the timetable is `valid`,
+    /// and its `findings` -- each with its `rule`, `severity`, `entries`, and
+    /// `message`.
+    pub fn to_json(&self) -> String {
+        let mut output = format!("{{\"valid\":{},\"findings\":[", self.is_valid());
+
+        for (index, finding) in self.findings.iter().enumerate() {
+            if index > 0 {
+                output.push(',');
+            }
+
+            output.push_str("{\"rule\":");
+            write_string(&mut output, finding.rule);
+            let _ = write!(
+                output,
+                ",\"severity\":\"{}\",\"entries\":[",
+                finding.severity
+            );
+
+            for (index, id) in finding.entries.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+
+                let _ = write!(output, "{}", id.get());
+            }
+
+            output.push_str("],\"message\":");
+            write_string(&mut output, &finding.message);
+            output.push('}');
+        }
+
+        output.push_str("]}");
+        output
+    }
+}
+
+/// A rule which a [`Timetable`] should satisfy.
+///
+/// Rules are generic over the [rooms](crate::Room) of the timetables they
+/// check, defaulting to a [`Location`] -- rules which do not depend upon the
+/// rooms of a particular school (e.g., [`NoClashes`]) can check any
+/// timetable.
+///
+/// *See the [module](self) documentation for more information*.
+pub trait Rule<L = Location> {
+    /// The name of the rule (e.g., `no-clashes`).
+    fn name(&self) -> &'static str;
+
+    /// Checks the `timetable` provided, returning a [`Finding`] for every
+    /// violation of the rule.
+    fn check(&self, timetable: &Timetable<L>) -> Vec<Finding>;
+}
+
+/// Validates [`Timetable`]s against a set of [`Rule`]s.
+///
+/// A new `Validator` contains no rules -- *see the [module](self)
+/// documentation for more information*.
+pub struct Validator<L = Location> {
+    rules: Vec<Box<dyn Rule<L>>>,
+}
+
+impl<L> Default for Validator<L> {
+    fn default() -> Self {
+        Self { rules: Vec::new() }
+    }
+}
+
+impl Validator {
+    /// Creates a new `Validator` with no rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<L> Validator<L> {
+    /// Adds a `rule` to the `Validator`.
+    pub fn rule<R>(mut self, rule: R) -> Self
+    where
+        R: Rule<L> + 'static,
+    {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Validates the `timetable` provided against every rule.
+    pub fn validate(&self, timetable: &Timetable<L>) -> Report {
+        Report {
+            findings: self
+                .rules
+                .iter()
+                .flat_map(|rule| rule.check(timetable))
+                .collect(),
+        }
+    }
+}
+
+/// Forbids teachers and rooms from being used by multiple lessons during the
+/// same [`TimeSlot`].
+///
+/// Each clash is reported as a [`Severity::Error`] -- rooms which are not
+/// [occupied](crate::RoomKind::is_occupied) by their lessons (i.e., online
+/// lessons, and rooms which are yet to be confirmed) never clash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoClashes;
+
+impl<L: crate::Room> Rule<L> for NoClashes {
+    fn name(&self) -> &'static str {
+        "no-clashes"
+    }
+
+    fn check(&self, timetable: &Timetable<L>) -> Vec<Finding> {
+        let mut usages: HashMap<(TimeSlot, Resource<L>), Vec<EntryId>> = HashMap::new();
+
+        // A lesson spanning multiple periods occupies its resources during
+        // each of them
+        for (id, entry) in timetable.lessons() {
+            for slot in entry.slots() {
+                for resource in Resource::of(entry) {
+                    usages.entry((slot, resource)).or_default().push(id);
+                }
+            }
+        }
+
+        Resource::clashes(
+            usages
+                .into_iter()
+                .filter(|(_, entries)| entries.len() > 1)
+                .map(|((_, resource), entries)| (resource, entries)),
+        )
+    }
+}
+
+/// Reports the lessons of a draft timetable whose rooms are yet to be
+/// confirmed (i.e., rooms of the
+/// [`RoomKind::ToBeConfirmed`](crate::RoomKind::ToBeConfirmed) kind, such as
+/// `TBC`), such that the rooms still needed can be found before the
+/// timetable is published.
+///
+/// Each such lesson is reported as a [`Severity::Warning`]:
+///
+/// ```
+/// use timetableau::validate::{RoomsNeeded, Validator};
+/// use timetableau::{timeslot, Activity, Class, Subject, Timetable};
+///
+/// let mut timetable = Timetable::new();
+/// timetable.insert(
+///     timeslot!(W1MP1),
+///     Activity::Lesson {
+///         subject: Subject::new("Ma".to_string()).unwrap(),
+///         class: Class::new("10A".to_string()).unwrap(),
+///         teacher: None,
+///         location: "TBC".parse().unwrap(),
+///     },
+/// );
+///
+/// let report = Validator::new().rule(RoomsNeeded).validate(&timetable);
+///
+/// assert_eq!(report.findings[0].message, "10A (Ma) still needs a room at Highfield");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RoomsNeeded;
+
+impl<L: crate::Room> Rule<L> for RoomsNeeded {
+    fn name(&self) -> &'static str {
+        "rooms-needed"
+    }
+
+    fn check(&self, timetable: &Timetable<L>) -> Vec<Finding> {
+        timetable
+            .lessons()
+            .of_kind(crate::RoomKind::ToBeConfirmed)
+            .filter_map(|(id, entry)| match &entry.activity {
+                Activity::Lesson {
+                    subject,
+                    class,
+                    location,
+                    ..
+                } => Some(Finding {
+                    rule: "rooms-needed",
+                    severity: Severity::Warning,
+                    entries: vec![id],
+                    message: format!(
+                        "{} ({}) still needs a room at {}",
+                        class,
+                        subject,
+                        location.site()
+                    ),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+// A teacher or a room which can only be used by one lesson at a time
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Resource<L> {
+    Teacher(String),
+    Room(L),
+}
+
+impl<L: crate::Room> Resource<L> {
+    // The resources used by the entry provided (i.e., each of its teachers
+    // and rooms, even should one be listed more than once)
+    fn of(entry: &Entry<L>) -> Vec<Resource<L>> {
+        let teachers = entry
+            .teachers()
+            .map(|teacher| Self::Teacher(teacher.code().clone()));
+        let rooms = entry.locations().cloned().filter_map(Self::room);
+        let mut resources = Vec::new();
+
+        for resource in teachers.chain(rooms) {
+            if !resources.contains(&resource) {
+                resources.push(resource);
+            }
+        }
+
+        resources
+    }
+
+    // The resource of the room provided (should its lessons occupy it)
+    fn room(location: L) -> Option<Self> {
+        location
+            .kind()
+            .is_occupied()
+            .then_some(Self::Room(location))
+    }
+
+    // Produce a clash of the resource between the entries provided
+    fn clash(&self, entries: Vec<EntryId>) -> Finding {
+        let message = match self {
+            Self::Teacher(code) => format!("{} teaches {} lessons at once", code, entries.len()),
+            Self::Room(room) => format!("{} hosts {} lessons at once", room, entries.len()),
+        };
+
+        Finding {
+            rule: "no-clashes",
+            severity: Severity::Error,
+            entries,
+            message,
+        }
+    }
+
+    // Produce the clashes of the resources provided, ordered by their
+    // entries (then teachers before rooms, then by their messages) for
+    // reproducibility, reporting lessons which clash during several periods
+    // once
+    fn clashes(clashes: impl Iterator<Item = (Self, Vec<EntryId>)>) -> Vec<Finding> {
+        let mut clashes: Vec<(bool, Finding)> = clashes
+            .map(|(resource, entries)| (matches!(resource, Self::Room(_)), resource.clash(entries)))
+            .collect();
+
+        clashes.sort_by(|(a_room, a), (b_room, b)| {
+            a.entries
+                .cmp(&b.entries)
+                .then(a_room.cmp(b_room))
+                .then_with(|| a.message.cmp(&b.message))
+        });
+
+        let mut findings: Vec<Finding> = clashes.into_iter().map(|(_, finding)| finding).collect();
+        findings.dedup();
+        findings
+    }
+
+    // Retrieve the lessons using the resource during the slot provided
+    fn lessons(&self, timetable: &Timetable<L>, slot: TimeSlot) -> Vec<EntryId> {
+        let lessons = match self {
+            Self::Teacher(code) => timetable.lessons().taught_by(code),
+            Self::Room(room) => timetable.lessons().in_room(room.clone()),
+        };
+
+        lessons.during(slot).map(|(id, _)| id).collect()
+    }
+}
+
+/// The clashes of a [`Timetable`] (*see [`NoClashes`]*), maintained as the
+/// timetable is edited.
+///
+/// Rather than checking the entire timetable after every edit, a
+/// `ValidationState` only re-checks the teachers and rooms used during the
+/// timeslots which an edit touched -- this allows interactive editors to
+/// report the clashes introduced (and resolved) by each edit immediately:
+///
+/// ```
+/// use timetableau::validate::ValidationState;
+/// use timetableau::{timeslot, Activity, Class, Subject, Timetable};
+///
+/// let lesson = |class: &str| Activity::Lesson {
+///     subject: Subject::new("Ma".to_string()).unwrap(),
+///     class: Class::new(class.to_string()).unwrap(),
+///     teacher: None,
+///     location: "H201".parse().unwrap(),
+/// };
+///
+/// let mut timetable = Timetable::new();
+/// timetable.insert(timeslot!(W1MP1), lesson("10A"));
+///
+/// let mut state = ValidationState::new(&timetable);
+///
+/// let id = timetable.insert(timeslot!(W1MP1), lesson("10B"));
+/// let issues = state.apply_edit(&timetable, &timetable.history().last().unwrap().change);
+///
+/// assert_eq!(issues.introduced.len(), 1);
+///
+/// timetable.move_entry(id, timeslot!(W1MP2)).unwrap();
+/// let issues = state.apply_edit(&timetable, &timetable.history().last().unwrap().change);
+///
+/// assert_eq!(issues.resolved.len(), 1);
+/// assert!(state.findings().is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValidationState<L = Location> {
+    clashes: HashMap<(TimeSlot, Resource<L>), Vec<EntryId>>,
+}
+
+/// The changes to the clashes of a [`Timetable`] caused by an edit.
+///
+/// *See [`ValidationState::apply_edit`] for more information*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NewIssues {
+    /// The clashes which the edit introduced (or changed -- e.g., a third
+    /// lesson joining an existing clash).
+    pub introduced: Vec<Finding>,
+
+    /// The clashes which the edit resolved.
+    pub resolved: Vec<Finding>,
+}
+
+impl NewIssues {
+    /// Whether the edit neither introduced nor resolved any clashes.
+    pub fn is_empty(&self) -> bool {
+        self.introduced.is_empty() && self.resolved.is_empty()
+    }
+}
+
+impl<L: crate::Room> ValidationState<L> {
+    /// Creates a new `ValidationState` by checking the entire `timetable`
+    /// provided.
+    pub fn new(timetable: &Timetable<L>) -> Self {
+        let mut clashes: HashMap<(TimeSlot, Resource<L>), Vec<EntryId>> = HashMap::new();
+
+        for (id, entry) in timetable.lessons() {
+            for slot in entry.slots() {
+                for resource in Resource::of(entry) {
+                    clashes.entry((slot, resource)).or_default().push(id);
+                }
+            }
+        }
+
+        clashes.retain(|_, entries| entries.len() > 1);
+        Self { clashes }
+    }
+
+    /// Updates the clashes following the `change` provided, which must have
+    /// already been applied to the `timetable` (e.g., the last change of its
+    /// [history](Timetable::history), or a change sent to a
+    /// [subscriber](Timetable::subscribe)).
+    ///
+    /// Only the teachers and rooms used by the entry which the change
+    /// affected are re-checked (during the timeslots which the change
+    /// touched).
+    pub fn apply_edit(&mut self, timetable: &Timetable<L>, change: &Change<L>) -> NewIssues {
+        let mut affected: Vec<(TimeSlot, Resource<L>)> = Vec::new();
+
+        match change {
+            Change::Inserted { entry, .. } | Change::Removed { entry, .. } => {
+                for slot in entry.slots() {
+                    affected.extend(
+                        Resource::of(entry)
+                            .into_iter()
+                            .map(|resource| (slot, resource)),
+                    );
+                }
+            }
+            Change::Moved { id, from, .. } => {
+                if let Some(entry) = timetable.get(*id) {
+                    // The periods spanned before the move
+                    let before = Entry {
+                        slot: *from,
+                        ..entry.clone()
+                    };
+
+                    for slot in before.slots().chain(entry.slots()) {
+                        affected.extend(
+                            Resource::of(entry)
+                                .into_iter()
+                                .map(|resource| (slot, resource)),
+                        );
+                    }
+                }
+            }
+            Change::Relocated { id, from, to } => {
+                if let Some(entry) = timetable.get(*id) {
+                    for slot in entry.slots() {
+                        for room in [from, to].into_iter().cloned().filter_map(Resource::room) {
+                            affected.push((slot, room));
+                        }
+                    }
+                }
+            }
+            // Room overrides do not change the base timetable
+            Change::OverrideAdded(_) | Change::OverrideRemoved { .. } => {}
+        }
+
+        let mut issues = NewIssues::default();
+
+        for key in affected {
+            let entries = key.1.lessons(timetable, key.0);
+            let previous = self.clashes.get(&key);
+
+            if previous == Some(&entries) || (previous.is_none() && entries.len() < 2) {
+                continue;
+            }
+
+            let previous = self.clashes.remove(&key);
+
+            if entries.len() > 1 {
+                issues.introduced.push(key.1.clash(entries.clone()));
+                self.clashes.insert(key, entries);
+            } else if let Some(previous) = previous {
+                issues.resolved.push(key.1.clash(previous));
+            }
+        }
+
+        issues
+    }
+
+    /// Retrieves every clash of the timetable (in the same order as
+    /// [`NoClashes`]).
+    pub fn findings(&self) -> Vec<Finding> {
+        Resource::clashes(
+            self.clashes
+                .iter()
+                .map(|((_, resource), entries)| (resource.clone(), entries.clone())),
+        )
+    }
+}
+
+/// Requires enough time to travel between consecutive lessons at different
+/// schools.
+///
+/// Each violation is reported as a [`Severity::Error`] -- *see
+/// [`Timetable::travel_issues`] for more information*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TravelTime {
+    schedules: BellSchedules,
+    travel_time: Duration,
+}
+
+impl TravelTime {
+    /// Creates a new `TravelTime` rule which requires `travel_time` between
+    /// consecutive lessons at different schools.
+    pub fn new(schedules: BellSchedules, travel_time: Duration) -> Self {
+        Self {
+            schedules,
+            travel_time,
+        }
+    }
+
+    /// Creates a new `TravelTime` rule which requires the time to travel
+    /// between the sites of the `policy` provided (*see
+    /// [`TransitPolicy::cross_site_time`]*).
+    pub fn with_policy(schedules: BellSchedules, policy: &TransitPolicy) -> Self {
+        Self::new(schedules, policy.cross_site_time())
+    }
+}
+
+impl Rule for TravelTime {
+    fn name(&self) -> &'static str {
+        "travel-time"
+    }
+
+    fn check(&self, timetable: &Timetable) -> Vec<Finding> {
+        timetable
+            .travel_issues(&self.schedules, self.travel_time)
+            .into_iter()
+            .map(|issue| Finding {
+                rule: self.name(),
+                severity: Severity::Error,
+                entries: vec![issue.from, issue.to],
+                message: format!(
+                    "{} minutes are available to travel between schools, but {} are required",
+                    issue.available.num_minutes(),
+                    issue.required.num_minutes()
+                ),
+            })
+            .collect()
+    }
+}
+
+/// A lesson whose class does not fit within the capacity of its room.
+///
+/// *See [`RoomCapacity::over_capacity`] for more information*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverCapacity {
+    /// The lesson which is over capacity.
+    pub entry: EntryId,
+
+    /// The room in which the lesson takes place (i.e., the first of its
+    /// rooms, should it be split across multiple rooms).
+    pub location: Location,
+
+    /// The number of students the lesson's rooms can seat (combined, should
+    /// it be split across multiple rooms).
+    pub capacity: u32,
+
+    /// The number of students in the lesson's class.
+    pub size: u32,
+
+    /// The severity of the assignment ([`Severity::Warning`] should the
+    /// excess be within the tolerance, and [`Severity::Error`] otherwise).
+    pub severity: Severity,
+}
+
+impl OverCapacity {
+    /// The number of students which exceed the room's capacity.
+    pub fn excess(&self) -> u32 {
+        self.size - self.capacity
+    }
+}
+
+/// Requires each room to be able to seat every member of the classes which
+/// use it.
+///
+/// Each violation is reported as a [`Severity::Error`], unless the excess is
+/// within the rule's [tolerance](Self::tolerance) (in which case it is
+/// reported as a [`Severity::Warning`]) -- lessons whose room capacity or
+/// class size is unknown are not checked. A class split across multiple
+/// rooms (*see [`Entry::split_rooms`]*) must fit within their combined
+/// capacity.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoomCapacity {
+    capacities: HashMap<Location, u32>,
+    class_sizes: HashMap<String, u32>,
+    tolerance: u32,
+}
+
+impl RoomCapacity {
+    /// Creates a new `RoomCapacity` rule with no known capacities or class
+    /// sizes (and no tolerance).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the number of students the `location` provided can seat.
+    pub fn capacity(mut self, location: Location, capacity: u32) -> Self {
+        self.capacities.insert(location, capacity);
+        self
+    }
+
+    /// Records the capacity of each of the `rooms` provided (e.g., the rooms
+    /// given to an [`Allocator`](crate::allocate::Allocator)).
+    pub fn rooms<'a, I>(mut self, rooms: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Room>,
+    {
+        self.capacities
+            .extend(rooms.into_iter().map(|room| (room.location, room.capacity)));
+        self
+    }
+
+    /// Records the number of students in the class with the `reference`
+    /// provided.
+    pub fn class_size(mut self, reference: &str, size: u32) -> Self {
+        self.class_sizes.insert(reference.to_string(), size);
+        self
+    }
+
+    /// Sets the number of students by which a class may exceed the capacity
+    /// of its room before the assignment is considered an error rather than
+    /// a warning.
+    pub fn tolerance(mut self, students: u32) -> Self {
+        self.tolerance = students;
+        self
+    }
+
+    /// Finds every lesson of the `timetable` whose class does not fit within
+    /// the capacity of its room (in the order in which the lessons were
+    /// inserted).
+    ///
+    /// Unlike [`Rule::check`], the assignments are returned as structured
+    /// data (e.g., such that they can be tabulated for a fire-safety audit).
+    pub fn over_capacity(&self, timetable: &Timetable) -> Vec<OverCapacity> {
+        timetable
+            .lessons()
+            .filter_map(|(id, entry)| {
+                let (class, location) = match &entry.activity {
+                    Activity::Lesson {
+                        class, location, ..
+                    } => (class, location),
+                    _ => return None,
+                };
+
+                let capacity = entry
+                    .locations()
+                    .map(|location| self.capacities.get(location))
+                    .sum::<Option<u32>>()?;
+                let size = *self.class_sizes.get(class.reference())?;
+
+                (size > capacity).then_some(OverCapacity {
+                    entry: id,
+                    location: *location,
+                    capacity,
+                    size,
+                    severity: if size - capacity <= self.tolerance {
+                        Severity::Warning
+                    } else {
+                        Severity::Error
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+impl Rule for RoomCapacity {
+    fn name(&self) -> &'static str {
+        "room-capacity"
+    }
+
+    fn check(&self, timetable: &Timetable) -> Vec<Finding> {
+        self.over_capacity(timetable)
+            .into_iter()
+            .map(|over| {
+                let entry = timetable.get(over.entry);
+                let class = entry
+                    .and_then(|entry| match &entry.activity {
+                        Activity::Lesson { class, .. } => Some(class.to_string()),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+                let rooms = entry
+                    .map(|entry| entry.locations().map(Location::to_string).collect())
+                    .unwrap_or_else(|| vec![over.location.to_string()]);
+
+                Finding {
+                    rule: self.name(),
+                    severity: over.severity,
+                    entries: vec![over.entry],
+                    message: format!(
+                        "{} ({} students) does not fit in {} ({} seats)",
+                        class,
+                        over.size,
+                        rooms.join(", "),
+                        over.capacity
+                    ),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Reserves laboratories for science practicals (i.e., lessons of a science
+/// subject).
+///
+/// Each non-science lesson taking place in a laboratory (including a
+/// laboratory amongst its split rooms) is reported as a [`Severity::Warning`]
+/// for each laboratory it uses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabsForPracticals {
+    labs: HashSet<Location>,
+    subjects: HashSet<String>,
+}
+
+impl LabsForPracticals {
+    /// Creates a new `LabsForPracticals` rule, where `labs` are the
+    /// laboratories and `subjects` are the names of the science subjects.
+    pub fn new<L, S>(labs: L, subjects: S) -> Self
+    where
+        L: IntoIterator<Item = Location>,
+        S: IntoIterator,
+        S::Item: Into<String>,
+    {
+        Self {
+            labs: labs.into_iter().collect(),
+            subjects: subjects.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Rule for LabsForPracticals {
+    fn name(&self) -> &'static str {
+        "labs-for-practicals"
+    }
+
+    fn check(&self, timetable: &Timetable) -> Vec<Finding> {
+        timetable
+            .lessons()
+            .flat_map(|(id, entry)| {
+                let subject = match &entry.activity {
+                    Activity::Lesson { subject, .. } if !self.subjects.contains(subject.name()) => {
+                        Some(subject)
+                    }
+                    _ => None,
+                };
+
+                subject.into_iter().flat_map(move |subject| {
+                    entry
+                        .locations()
+                        .filter(|location| self.labs.contains(location))
+                        .map(move |location| Finding {
+                            rule: self.name(),
+                            severity: Severity::Warning,
+                            entries: vec![id],
+                            message: format!(
+                                "{} is not a science subject, but uses {}",
+                                subject, location
+                            ),
+                        })
+                })
+            })
+            .collect()
+    }
+}
+
+/// A lesson whose room lacks a facility the lesson requires.
+///
+/// *See [`Facilities::unmet`] for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmetRequirement {
+    /// The lesson whose requirements are unmet.
+    pub entry: EntryId,
+
+    /// The room which lacks the facilities (i.e., the room in which the
+    /// lesson takes place, or one of its split rooms).
+    pub location: Location,
+
+    /// The facilities required by the lesson which the room lacks (in the
+    /// order in which they were required).
+    pub missing: Vec<String>,
+}
+
+/// Requires each lesson to take place in a room with the facilities it
+/// requires (e.g., a Dance lesson requires a sprung floor, and so is not
+/// taught in a science laboratory).
+///
+/// Requirements are declared for every lesson of a subject, or for a single
+/// lesson, and are matched against the facilities of each room recorded by a
+/// [`RoomRegistry`] (the [known](RoomRegistry::known) rooms, unless another
+/// registry is provided). Each lesson whose room lacks a required facility is
+/// reported as a [`Severity::Error`]:
+///
+/// ```
+/// use timetableau::validate::{Facilities, Rule};
+/// use timetableau::{timeslot, Activity, Class, Subject, Timetable};
+///
+/// let mut timetable = Timetable::new();
+/// timetable.insert(
+///     timeslot!(W1MP1),
+///     Activity::Lesson {
+///         subject: Subject::new("Da".to_string()).unwrap(),
+///         class: Class::new("9A".to_string()).unwrap(),
+///         teacher: None,
+///         location: "FH S1".parse().unwrap(),
+///     },
+/// );
+///
+/// let rule = Facilities::new().subject("Da", "Sprung Floor");
+///
+/// assert_eq!(
+///     rule.check(&timetable)[0].message,
+///     "Da requires Sprung Floor, which FH S1 lacks"
+/// );
+/// ```
+///
+/// # Remarks
+///
+/// The same facilities can be required of the rooms chosen by an
+/// [`Allocator`](crate::allocate::Allocator) -- *see
+/// [`Room::facilities`]*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Facilities {
+    registry: Option<RoomRegistry>,
+    subjects: HashMap<String, Vec<String>>,
+    lessons: HashMap<EntryId, Vec<String>>,
+}
+
+impl Facilities {
+    /// Creates a new `Facilities` rule without any requirements, matched
+    /// against the [known](RoomRegistry::known) rooms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the registry whose facilities the requirements are matched
+    /// against.
+    pub fn registry(mut self, registry: RoomRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Records that every lesson of the subject with the `name` provided
+    /// requires the facility with the `facility` name provided (e.g., `Ch`
+    /// requires `Gas Taps`).
+    pub fn subject(mut self, name: &str, facility: &str) -> Self {
+        self.subjects
+            .entry(name.to_string())
+            .or_default()
+            .push(facility.to_string());
+        self
+    }
+
+    /// Records that the lesson with the identifier `id` requires the facility
+    /// with the `facility` name provided (in addition to the requirements of
+    /// its subject).
+    pub fn lesson(mut self, id: EntryId, facility: &str) -> Self {
+        self.lessons
+            .entry(id)
+            .or_default()
+            .push(facility.to_string());
+        self
+    }
+
+    /// Finds every lesson of the `timetable` whose room lacks a facility the
+    /// lesson requires (in the order in which the lessons were inserted).
+    ///
+    /// Each of the rooms of a lesson split across multiple rooms must have
+    /// the facilities the lesson requires -- a requirement is returned for
+    /// each room which lacks any of them.
+    ///
+    /// Unlike [`Rule::check`], the lessons are returned as structured data
+    /// (e.g., such that they can be reallocated).
+    pub fn unmet(&self, timetable: &Timetable) -> Vec<UnmetRequirement> {
+        let known;
+        let registry = match &self.registry {
+            Some(registry) => registry,
+            None => {
+                known = RoomRegistry::known();
+                &known
+            }
+        };
+
+        let mut unmet = Vec::new();
+
+        for (id, entry) in timetable.lessons() {
+            let subject = match &entry.activity {
+                Activity::Lesson { subject, .. } => subject,
+                _ => continue,
+            };
+
+            for location in entry.locations() {
+                let mut missing: Vec<String> = Vec::new();
+
+                for facility in self
+                    .subjects
+                    .get(subject.name())
+                    .into_iter()
+                    .chain(self.lessons.get(&id))
+                    .flatten()
+                {
+                    if !registry.has_facility(location, facility) && !missing.contains(facility) {
+                        missing.push(facility.clone());
+                    }
+                }
+
+                if !missing.is_empty() {
+                    unmet.push(UnmetRequirement {
+                        entry: id,
+                        location: *location,
+                        missing,
+                    });
+                }
+            }
+        }
+
+        unmet
+    }
+}
+
+impl Rule for Facilities {
+    fn name(&self) -> &'static str {
+        "facilities"
+    }
+
+    fn check(&self, timetable: &Timetable) -> Vec<Finding> {
+        self.unmet(timetable)
+            .into_iter()
+            .map(|unmet| {
+                let subject = timetable
+                    .get(unmet.entry)
+                    .and_then(|entry| match &entry.activity {
+                        Activity::Lesson { subject, .. } => Some(subject.to_string()),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+
+                Finding {
+                    rule: self.name(),
+                    severity: Severity::Error,
+                    entries: vec![unmet.entry],
+                    message: format!(
+                        "{} requires {}, which {} lacks",
+                        subject,
+                        unmet.missing.join(", "),
+                        unmet.location
+                    ),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Flags the lessons which take place in rooms retired from the
+/// [`RoomRegistry`] (*see [`RoomRegistry::retire`]*) as of a given date.
+///
+/// Every location of a lesson is checked (i.e., its own location, its split
+/// rooms, and the locations of its rotation stages), and each lesson using a
+/// room which has been retired on (or before) the date is reported as a
+/// [`Severity::Warning`] -- with the room which replaces it (if any):
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use timetableau::validate::{RetiredRooms, Rule};
+/// use timetableau::{timeslot, Activity, Class, RoomRegistry, Subject, Timetable};
+///
+/// let mut timetable = Timetable::new();
+/// timetable.insert(
+///     timeslot!(W1MP1),
+///     Activity::Lesson {
+///         subject: Subject::new("Ma".to_string()).unwrap(),
+///         class: Class::new("10A".to_string()).unwrap(),
+///         teacher: None,
+///         location: "U101".parse().unwrap(),
+///     },
+/// );
+///
+/// let registry = RoomRegistry::from_data("U101 [retired 2025-08-01, replaced by U201]").unwrap();
+/// let rule = RetiredRooms::on(NaiveDate::from_ymd_opt(2025, 9, 1).unwrap()).registry(registry);
+///
+/// assert_eq!(
+///     rule.check(&timetable)[0].message,
+///     "10A (Ma) uses U101, which was retired on 2025-08-01 (replaced by U201)"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetiredRooms {
+    date: NaiveDate,
+    registry: Option<RoomRegistry>,
+}
+
+impl RetiredRooms {
+    /// Creates a new `RetiredRooms` rule flagging the rooms retired on (or
+    /// before) the `date` provided, matched against the
+    /// [known](RoomRegistry::known) rooms.
+    pub fn on(date: NaiveDate) -> Self {
+        Self {
+            date,
+            registry: None,
+        }
+    }
+
+    /// Sets the registry whose retirements the lessons are matched against.
+    pub fn registry(mut self, registry: RoomRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+}
+
+impl Rule for RetiredRooms {
+    fn name(&self) -> &'static str {
+        "retired-rooms"
+    }
+
+    fn check(&self, timetable: &Timetable) -> Vec<Finding> {
+        let known;
+        let registry = match &self.registry {
+            Some(registry) => registry,
+            None => {
+                known = RoomRegistry::known();
+                &known
+            }
+        };
+
+        let mut findings = Vec::new();
+
+        for (id, entry) in timetable.lessons() {
+            let Activity::Lesson { subject, class, .. } = &entry.activity else {
+                continue;
+            };
+            let stages = entry.rotation.iter().flat_map(|rotation| &rotation.stages);
+            let mut seen = HashSet::new();
+
+            for location in entry
+                .locations()
+                .chain(stages.map(|stage| &stage.location))
+                .filter(|location| seen.insert(**location))
+            {
+                let Some(retirement) = registry
+                    .retirement(location)
+                    .filter(|retirement| retirement.from <= self.date)
+                else {
+                    continue;
+                };
+
+                let mut message = format!(
+                    "{} ({}) uses {}, which was retired on {}",
+                    class, subject, location, retirement.from
+                );
+
+                if let Some(replacement) = retirement.replacement {
+                    let _ = write!(message, " (replaced by {})", replacement);
+                }
+
+                findings.push(Finding {
+                    rule: self.name(),
+                    severity: Severity::Warning,
+                    entries: vec![id],
+                    message,
+                });
+            }
+        }
+
+        findings
+    }
+}
+
+/// Forbids teachers from teaching outside of their [`Availability`] (e.g., a
+/// part-time teacher who does not work on Fridays).
+///
+/// Each lesson taught by a teacher (or co-teacher) who is unavailable during
+/// any of its timeslots is reported as a [`Severity::Error`] (once for each
+/// such teacher) -- teachers without a recorded availability are available
+/// during every timeslot.
+///
+/// [`Availability`]: crate::availability::Availability
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WithinAvailability {
+    staff: StaffAvailability,
+}
+
+impl WithinAvailability {
+    /// Creates a new `WithinAvailability` rule enforcing the availability of
+    /// the `staff` provided.
+    pub fn new(staff: StaffAvailability) -> Self {
+        Self { staff }
+    }
+}
+
+impl<L: crate::Room> Rule<L> for WithinAvailability {
+    fn name(&self) -> &'static str {
+        "within-availability"
+    }
+
+    fn check(&self, timetable: &Timetable<L>) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        for (id, entry) in timetable.lessons() {
+            let mut checked: Vec<&Teacher> = Vec::new();
+
+            // Every teacher (including co-teachers) must be available for the
+            // whole span of the lesson
+            for teacher in entry.teachers() {
+                if checked.contains(&teacher) {
+                    continue;
+                }
+                checked.push(teacher);
+
+                let Some(slot) = entry
+                    .slots()
+                    .find(|slot| !self.staff.is_available(teacher, *slot))
+                else {
+                    continue;
+                };
+
+                findings.push(Finding {
+                    rule: "within-availability",
+                    severity: Severity::Error,
+                    entries: vec![id],
+                    message: format!(
+                        "{} is unavailable during period {} of {} (week {})",
+                        teacher,
+                        slot.period as u8 + 1,
+                        slot.day,
+                        slot.week as u8 + 1
+                    ),
+                });
+            }
+        }
+
+        findings
+    }
+}
+
+/// Enforces the [accessibility profiles](crate::accessibility) of students.
+///
+/// Each lesson of a student who cannot access its room is reported as a
+/// [`Severity::Error`], and each pair of lessons in consecutive periods which
+/// leaves a student too little time to move between their rooms (i.e., the
+/// time available between the periods and the passing time of the
+/// [`TransitPolicy`], without their extra transition time) is reported as a
+/// [`Severity::Warning`] -- the students attending each lesson are those
+/// enrolled in its class by the [`Roster`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessibilityNeeds {
+    roster: Roster,
+    profiles: AccessibilityProfiles,
+    registry: RoomRegistry,
+    schedules: BellSchedules,
+    transit: TransitPolicy,
+}
+
+impl AccessibilityNeeds {
+    /// Creates a new `AccessibilityNeeds` rule enforcing the `profiles` of
+    /// the students of the `roster`, whose lessons follow the `schedules`
+    /// provided.
+    pub fn new(roster: Roster, profiles: AccessibilityProfiles, schedules: BellSchedules) -> Self {
+        Self {
+            roster,
+            profiles,
+            registry: RoomRegistry::new(),
+            schedules,
+            transit: TransitPolicy::default(),
+        }
+    }
+
+    /// Sets the registry recording the rooms which are reached by a lift
+    /// (*see [`LIFT`](crate::accessibility::LIFT)*) -- by default, only the
+    /// rooms on the ground floor are step-free.
+    pub fn registry(mut self, registry: RoomRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Sets the passing times tolerated by the school, to which the extra
+    /// transition time of each student is added.
+    pub fn transit(mut self, policy: TransitPolicy) -> Self {
+        self.transit = policy;
+        self
+    }
+}
+
+impl Rule for AccessibilityNeeds {
+    fn name(&self) -> &'static str {
+        "accessibility-needs"
+    }
+
+    fn check(&self, timetable: &Timetable) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        for (student, profile) in self.profiles.iter() {
+            let lessons: Vec<_> = timetable
+                .entries()
+                .filter_map(|(id, entry)| match &entry.activity {
+                    Activity::Lesson {
+                        class, location, ..
+                    } if self
+                        .roster
+                        .students(class.reference())
+                        .iter()
+                        .any(|other| other == student) =>
+                    {
+                        // A lesson spanning multiple periods is left after its
+                        // last period
+                        let last = entry.slots().last().unwrap_or(entry.slot);
+
+                        Some((id, entry.slot, last, location))
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            for (id, _, _, location) in &lessons {
+                if !profile.can_access(location, &self.registry) {
+                    findings.push(Finding {
+                        rule: self.name(),
+                        severity: Severity::Error,
+                        entries: vec![*id],
+                        message: format!("{} ({}) cannot access {}", student, profile, location),
+                    });
+                }
+            }
+
+            let extra = profile.extra_time();
+
+            if extra <= Duration::zero() {
+                continue;
+            }
+
+            for (from, _, from_slot, from_location) in &lessons {
+                let Some(next) = from_slot.period.next() else {
+                    continue;
+                };
+
+                for (to, to_slot, _, to_location) in &lessons {
+                    let passing = self.transit.travel_time(from_location, to_location);
+
+                    if to_slot.week != from_slot.week
+                        || to_slot.day != from_slot.day
+                        || to_slot.period != next
+                        || passing.is_zero()
+                    {
+                        continue;
+                    }
+
+                    let gap = self.schedules.get(to_location.school()).start(next)
+                        - self
+                            .schedules
+                            .get(from_location.school())
+                            .end(from_slot.period);
+                    let available = passing + gap;
+
+                    if available < passing + extra {
+                        findings.push(Finding {
+                            rule: self.name(),
+                            severity: Severity::Warning,
+                            entries: vec![*from, *to],
+                            message: format!(
+                                "{} needs {} minutes to move from {} to {}, but {} are available",
+                                student,
+                                (passing + extra).num_minutes(),
+                                from_location,
+                                to_location,
+                                available.num_minutes()
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+/// Limits the number of consecutive periods a teacher teaches.
+///
+/// Each run of more than `max` consecutive lessons taught by the same teacher
+/// is reported as a [`Severity::Warning`] -- periods separated by a break or
+/// lunch are still considered consecutive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxConsecutive {
+    max: usize,
+}
+
+impl MaxConsecutive {
+    /// Creates a new `MaxConsecutive` rule permitting at most `max`
+    /// consecutive teaching periods.
+    pub fn new(max: usize) -> Self {
+        Self { max }
+    }
+}
+
+impl<L: crate::Room> Rule<L> for MaxConsecutive {
+    fn name(&self) -> &'static str {
+        "max-consecutive"
+    }
+
+    fn check(&self, timetable: &Timetable<L>) -> Vec<Finding> {
+        // The periods taught by each teacher, including its co-teachers (a
+        // lesson spanning several periods occupies each of them)
+        let mut taught: HashMap<&Teacher, Vec<(TimeSlot, EntryId)>> = HashMap::new();
+
+        for (id, entry) in timetable.lessons() {
+            for teacher in entry.teachers() {
+                taught
+                    .entry(teacher)
+                    .or_default()
+                    .extend(entry.slots().map(|slot| (slot, id)));
+            }
+        }
+
+        let mut teachers: Vec<_> = taught.into_iter().collect();
+        teachers.sort_by_key(|(teacher, _)| *teacher);
+
+        let mut findings = Vec::new();
+
+        for (teacher, mut lessons) in teachers {
+            lessons.sort_by_key(|(slot, id)| (slot.index(), *id));
+
+            // Lessons taught during the same period (i.e., clashes) do not
+            // extend a run
+            lessons.dedup_by_key(|(slot, _)| *slot);
+
+            let mut run: Vec<(TimeSlot, EntryId)> = Vec::new();
+
+            // A trailing `None` ensures the final run is checked
+            for lesson in lessons.into_iter().map(Some).chain([None]) {
+                let continues = match (&lesson, run.last()) {
+                    (Some((slot, _)), Some((last, _))) => {
+                        slot.week == last.week
+                            && slot.day == last.day
+                            && Some(slot.period) == last.period.next()
+                    }
+                    _ => false,
+                };
+
+                if !continues {
+                    if run.len() > self.max {
+                        let mut entries: Vec<EntryId> = run.iter().map(|(_, id)| *id).collect();
+                        entries.dedup();
+
+                        findings.push(Finding {
+                            rule: "max-consecutive",
+                            severity: Severity::Warning,
+                            entries,
+                            message: format!(
+                                "{} teaches {} consecutive periods (at most {} are permitted)",
+                                teacher,
+                                run.len(),
+                                self.max
+                            ),
+                        });
+                    }
+
+                    run.clear();
+                }
+
+                run.extend(lesson);
+            }
+        }
+
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::availability::Availability;
+    use crate::{timeslot, Class, FearnhillRoom, HighfieldRoom, Period, Subject};
+
+    fn lesson<L>(subject: &str, class: &str, teacher: &str, location: L) -> Activity<L> {
+        Activity::Lesson {
+            subject: Subject::new(subject.to_string()).unwrap(),
+            class: Class::new(class.to_string()).unwrap(),
+            teacher: Teacher::new(teacher.to_string()).ok(),
+            location,
+        }
+    }
+
+    #[test]
+    fn validate_generic_rooms() {
+        let hall = HighfieldRoom::Hall;
+
+        let mut timetable = Timetable::<HighfieldRoom>::default();
+        let a = timetable.insert(timeslot!(W1MP1), lesson("Ma", "10A", "JSM", hall));
+        let b = timetable.insert(timeslot!(W1MP1), lesson("En", "10B", "ABC", hall));
+
+        let report = Validator::default().rule(NoClashes).validate(&timetable);
+
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].entries, vec![a, b]);
+        assert_eq!(report.findings[0].message, "Hall hosts 2 lessons at once");
+    }
+
+    #[test]
+    fn validate_remote_lessons() {
+        let remote: Location = "Remote (Teams)".parse().unwrap();
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("Ma", "10A", "JSM", remote));
+        let b = timetable.insert(timeslot!(W1MP2), lesson("En", "10B", "ABC", remote));
+
+        let mut state = ValidationState::new(&timetable);
+
+        // Remote lessons occupy no room, so only the teachers can clash
+        timetable.move_entry(b, timeslot!(W1MP1)).unwrap();
+        let issues = state.apply_edit(&timetable, &timetable.history().last().unwrap().change);
+
+        assert!(issues.is_empty());
+        assert!(NoClashes.check(&timetable).is_empty());
+    }
+
+    #[test]
+    fn validate_rooms_needed() {
+        let tbc: Location = "FH TBC".parse().unwrap();
+
+        let mut timetable = Timetable::new();
+        let a = timetable.insert(timeslot!(W1MP1), lesson("Ma", "10A", "JSM", tbc));
+        let b = timetable.insert(timeslot!(W1MP1), lesson("En", "10B", "ABC", tbc));
+        timetable.insert(
+            timeslot!(W1MP2),
+            lesson("Fr", "10C", "ABC", "H201".parse().unwrap()),
+        );
+
+        let report = Validator::new()
+            .rule(NoClashes)
+            .rule(RoomsNeeded)
+            .validate(&timetable);
+        let findings: Vec<(&str, Vec<EntryId>)> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.rule, finding.entries.clone()))
+            .collect();
+
+        // Lessons whose room is yet to be confirmed do not clash
+        assert_eq!(
+            findings,
+            vec![("rooms-needed", vec![a]), ("rooms-needed", vec![b])]
+        );
+        assert_eq!(
+            report.findings[1].message,
+            "10B (En) still needs a room at Fearnhill"
+        );
+    }
+
+    #[test]
+    fn validation_state_incremental() {
+        let hall = Location::Highfield(HighfieldRoom::Hall);
+        let lab: Location = "FH S1".parse().unwrap();
+
+        let mut timetable = Timetable::new();
+        timetable.insert(timeslot!(W1MP1), lesson("Ma", "10A", "JSM", hall));
+        let b = timetable.insert(timeslot!(W1MP2), lesson("En", "10B", "JSM", lab));
+
+        let mut state = ValidationState::new(&timetable);
+        let edit = |state: &mut ValidationState, timetable: &Timetable| {
+            state.apply_edit(timetable, &timetable.history().last().unwrap().change)
+        };
+
+        timetable.move_entry(b, timeslot!(W1MP1)).unwrap();
+        let issues = edit(&mut state, &timetable);
+
+        assert_eq!(issues.introduced.len(), 1);
+        assert_eq!(
+            issues.introduced[0].message,
+            "JSM teaches 2 lessons at once"
+        );
+
+        timetable.relocate(b, hall);
+        let issues = edit(&mut state, &timetable);
+
+        assert_eq!(issues.introduced[0].message, "Hall hosts 2 lessons at once");
+        assert!(issues.resolved.is_empty());
+
+        let c = timetable.insert(timeslot!(W1MP1), lesson("Fr", "10C", "JSM", lab));
+        let issues = edit(&mut state, &timetable);
+
+        assert_eq!(
+            issues.introduced[0].message,
+            "JSM teaches 3 lessons at once"
+        );
+        assert!(issues.resolved.is_empty());
+
+        timetable.remove(c);
+        edit(&mut state, &timetable);
+
+        assert_eq!(state.findings(), NoClashes.check(&timetable));
+        assert_eq!(state.findings().len(), 2);
+
+        timetable.move_entry(b, timeslot!(W2MP1)).unwrap();
+        let issues = edit(&mut state, &timetable);
+
+        assert_eq!(issues.resolved.len(), 2);
+        assert!(state.findings().is_empty());
+    }
+
+    #[test]
+    fn validate_spanning_lessons() {
+        let lab: Location = "FH S1".parse().unwrap();
+        let mut timetable = Timetable::new();
+
+        let double = timetable
+            .insert_spanning(timeslot!(W1MP1), 2, lesson("Sc", "10A", "JSM", lab))
+            .unwrap();
+        let single = timetable.insert(timeslot!(W1MP2), lesson("Sc", "10B", "ABC", lab));
+
+        // The second period of the double lesson is occupied
+        let findings = NoClashes.check(&timetable);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].entries, [double, single]);
+        assert_eq!(findings[0].message, "FH S1 hosts 2 lessons at once");
+
+        // Lessons which clash during both periods are reported once
+        timetable.remove(single);
+        timetable
+            .insert_spanning(timeslot!(W1MP1), 2, lesson("Sc", "10B", "JSM", lab))
+            .unwrap();
+
+        assert_eq!(NoClashes.check(&timetable).len(), 2);
+        assert_eq!(
+            ValidationState::new(&timetable).findings(),
+            NoClashes.check(&timetable)
+        );
+
+        // A double period followed by three single lessons is five periods
+        // in a row
+        let mut timetable = Timetable::new();
+        let double = timetable
+            .insert_spanning(timeslot!(W1TP1), 2, lesson("PE", "10A", "JSM", lab))
+            .unwrap();
+        let third = timetable.insert(timeslot!(W1TP3), lesson("Sc", "10D", "JSM", lab));
+        let fourth = timetable.insert(timeslot!(W1TP4), lesson("Sc", "10B", "JSM", lab));
+        let fifth = timetable.insert(timeslot!(W1TP5), lesson("Sc", "10C", "JSM", lab));
+
+        let findings = MaxConsecutive::new(3).check(&timetable);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].entries, [double, third, fourth, fifth]);
+        assert_eq!(
+            findings[0].message,
+            "JSM teaches 5 consecutive periods (at most 3 are permitted)"
+        );
+
+        // The teacher must be available for the whole span
+        let staff = StaffAvailability::new().teacher(
+            "JSM",
+            Availability::full().unavailable(chrono::Weekday::Tue, Period::Second),
+        );
+        let findings = WithinAvailability::new(staff).check(&timetable);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].entries, [double]);
+        assert_eq!(
+            findings[0].message,
+            "JSM is unavailable during period 2 of Tue (week 1)"
+        );
+    }
+
+    #[test]
+    fn validate_staffed_lessons() {
+        let lab: Location = "FH S1".parse().unwrap();
+        let prep: Location = "FH S2".parse().unwrap();
+        let abc = Teacher::new("ABC".to_string()).unwrap();
+        let mut timetable = Timetable::new();
+
+        let shared = timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP1), lesson("Sc", "10A", "JSM", lab))
+                    .co_teacher(abc.clone())
+                    .split_room(prep),
+            )
+            .unwrap();
+        let single = timetable.insert(timeslot!(W1MP1), lesson("Ma", "10B", "ABC", prep));
+
+        // The co-teacher and the split room are both in use
+        let findings = NoClashes.check(&timetable);
+        let messages: Vec<&str> = findings
+            .iter()
+            .map(|finding| finding.message.as_str())
+            .collect();
+
+        assert_eq!(
+            messages,
+            [
+                "ABC teaches 2 lessons at once",
+                "FH S2 hosts 2 lessons at once"
+            ]
+        );
+        assert_eq!(findings[0].entries, [shared, single]);
+
+        let mut state = ValidationState::new(&timetable);
+        assert_eq!(state.findings(), findings);
+
+        timetable.move_entry(single, timeslot!(W1MP2)).unwrap();
+        let issues = state.apply_edit(&timetable, &timetable.history().last().unwrap().change);
+
+        assert_eq!(issues.resolved.len(), 2);
+        assert!(NoClashes.check(&timetable).is_empty());
+
+        // A teacher listed twice does not clash with themselves
+        let twice = timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP3), lesson("Sc", "10C", "ABC", lab)).co_teacher(abc),
+            )
+            .unwrap();
+
+        assert!(NoClashes.check(&timetable).is_empty());
+
+        // A co-teacher's lessons count towards their consecutive periods
+        let findings = MaxConsecutive::new(1).check(&timetable);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].entries, [shared, single, twice]);
+        assert_eq!(
+            findings[0].message,
+            "ABC teaches 3 consecutive periods (at most 1 are permitted)"
+        );
+
+        // A co-teacher must also be available
+        let staff = StaffAvailability::new().teacher(
+            "ABC",
+            Availability::full().unavailable(chrono::Weekday::Mon, Period::First),
+        );
+        let findings = WithinAvailability::new(staff).check(&timetable);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].entries, [shared]);
+        assert_eq!(
+            findings[0].message,
+            "ABC is unavailable during period 1 of Mon (week 1)"
+        );
+    }
+
+    #[test]
+    fn validate_split_rooms() {
+        let h101: Location = "H101".parse().unwrap();
+        let h102: Location = "H102".parse().unwrap();
+        let lab: Location = "FH S1".parse().unwrap();
+        let studio: Location = "FH Dance Studio".parse().unwrap();
+        let mut timetable = Timetable::new();
+
+        let split = timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP1), lesson("Ma", "10A", "JSM", h101)).split_room(h102),
+            )
+            .unwrap();
+
+        // A class split across two rooms fits within their combined capacity
+        let rule = RoomCapacity::new()
+            .rooms(&[Room::new(h101, 30), Room::new(h102, 30)])
+            .class_size("10A", 60);
+
+        assert!(rule.over_capacity(&timetable).is_empty());
+
+        let rule = rule.class_size("10A", 70);
+        let over = rule.over_capacity(&timetable);
+
+        assert_eq!((over[0].entry, over[0].capacity), (split, 60));
+        assert_eq!(over[0].severity, Severity::Error);
+        assert_eq!(
+            rule.check(&timetable)[0].message,
+            "10A (70 students) does not fit in H101, H102 (60 seats)"
+        );
+
+        // A non-science lesson split into a laboratory uses the laboratory
+        let dance = timetable
+            .insert_entry(
+                Entry::new(timeslot!(W1MP2), lesson("Da", "9A", "ABC", studio)).split_room(lab),
+            )
+            .unwrap();
+        let findings = LabsForPracticals::new([lab], ["Ph"]).check(&timetable);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].entries, [dance]);
+        assert_eq!(
+            findings[0].message,
+            "Da is not a science subject, but uses FH S1"
+        );
+
+        // Each of the rooms must have the facilities the lesson requires
+        let registry = RoomRegistry::from_data("FH Dance Studio: Sprung Floor").unwrap();
+        let rule = Facilities::new()
+            .registry(registry)
+            .subject("Da", "Sprung Floor");
+
+        assert_eq!(
+            rule.unmet(&timetable),
+            vec![UnmetRequirement {
+                entry: dance,
+                location: lab,
+                missing: vec!["Sprung Floor".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_built_in_rules() {
+        let hall = Location::Highfield(HighfieldRoom::Hall);
+        let lab = "FH S1".parse().unwrap();
+        let gym = Location::Fearnhill(FearnhillRoom::Gym);
+
+        let mut timetable = Timetable::new();
+        let a = timetable.insert(timeslot!(W1MP1), lesson("Ma", "10A", "JSM", hall));
+        let b = timetable.insert(timeslot!(W1MP1), lesson("En", "10B", "JSM", hall));
+        let c = timetable.insert(timeslot!(W1MP2), lesson("Ma", "10A", "JSM", lab));
+        let d = timetable.insert(timeslot!(W1MP3), lesson("Ph", "10A", "JSM", lab));
+        timetable.insert(timeslot!(W1MP5), lesson("PE", "10C", "ABC", gym));
+
+        let report = Validator::new()
+            .rule(NoClashes)
+            .rule(RoomCapacity::new().capacity(hall, 20).class_size("10B", 30))
+            .rule(LabsForPracticals::new([lab], ["Ph", "Ch", "Bi"]))
+            .rule(MaxConsecutive::new(2))
+            .validate(&timetable);
+
+        let findings: Vec<(&str, Vec<EntryId>)> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.rule, finding.entries.clone()))
+            .collect();
+
+        assert_eq!(
+            findings,
+            vec![
+                ("no-clashes", vec![a, b]),
+                ("no-clashes", vec![a, b]),
+                ("room-capacity", vec![b]),
+                ("labs-for-practicals", vec![c]),
+                ("max-consecutive", vec![a, c, d]),
+            ]
+        );
+        assert!(!report.is_valid());
+        assert_eq!(report.at_least(Severity::Error).count(), 3);
+    }
+
+    #[test]
+    fn validate_room_capacity_tolerance() {
+        let h101 = "H101".parse().unwrap();
+        let h102 = "H102".parse().unwrap();
+
+        let mut timetable = Timetable::new();
+        let a = timetable.insert(timeslot!(W1MP1), lesson("Ma", "10A", "JSM", h101));
+        let b = timetable.insert(timeslot!(W1MP1), lesson("Ma", "10B", "ABC", h102));
+        timetable.insert(timeslot!(W1MP2), lesson("Ma", "10C", "ABC", h102));
+
+        let rule = RoomCapacity::new()
+            .rooms(&[Room::new(h101, 30), Room::new(h102, 20)])
+            .class_size("10A", 32)
+            .class_size("10B", 25)
+            .class_size("10C", 20)
+            .tolerance(2);
+
+        let over: Vec<(EntryId, u32, Severity)> = rule
+            .over_capacity(&timetable)
+            .iter()
+            .map(|over| (over.entry, over.excess(), over.severity))
+            .collect();
+
+        assert_eq!(
+            over,
+            vec![(a, 2, Severity::Warning), (b, 5, Severity::Error)]
+        );
+    }
+
+    #[test]
+    fn validate_facilities() {
+        let lab: Location = "FH S1".parse().unwrap();
+        let studio: Location = "FH Dance Studio".parse().unwrap();
+        let registry =
+            RoomRegistry::from_data("FH S1: Gas Taps\nFH Dance Studio: Sprung Floor").unwrap();
+
+        let mut timetable = Timetable::new();
+        let a = timetable.insert(timeslot!(W1MP1), lesson("Da", "9A", "JSM", lab));
+        timetable.insert(timeslot!(W1MP2), lesson("Da", "9A", "JSM", studio));
+        timetable.insert(timeslot!(W1MP3), lesson("Ch", "9A", "ABC", lab));
+        let b = timetable.insert(timeslot!(W1MP4), lesson("Ch", "9B", "ABC", lab));
+
+        let rule = Facilities::new()
+            .registry(registry.clone())
+            .subject("Da", "Sprung Floor")
+            .subject("Ch", "Gas Taps")
+            .lesson(a, "Sprung Floor")
+            .lesson(b, "Computers");
+
+        assert_eq!(
+            rule.unmet(&timetable),
+            vec![
+                UnmetRequirement {
+                    entry: a,
+                    location: lab,
+                    missing: vec!["Sprung Floor".to_string()],
+                },
+                UnmetRequirement {
+                    entry: b,
+                    location: lab,
+                    missing: vec!["Computers".to_string()],
+                },
+            ]
+        );
+        assert_eq!(
+            rule.check(&timetable)[1].message,
+            "Ch requires Computers, which FH S1 lacks"
+        );
+        assert!(Room::new(lab, 30)
+            .facilities(&registry)
+            .equipment
+            .contains("Gas Taps"));
+    }
+
+    #[test]
+    fn validate_travel_time() {
+        let mut timetable = Timetable::new();
+        timetable.insert(
+            timeslot!(W1MP1),
+            lesson("PE", "10C", "ABC", "FH Gym".parse().unwrap()),
+        );
+        timetable.insert(
+            timeslot!(W1MP2),
+            lesson("Ma", "10C", "ABC", "Hall".parse().unwrap()),
+        );
+
+        let report = Validator::new()
+            .rule(TravelTime::new(
+                BellSchedules::default(),
+                Duration::minutes(15),
+            ))
+            .validate(&timetable);
+
+        assert_eq!(report.severity(), Some(Severity::Error));
+        assert_eq!(
+            report.findings[0].message,
+            "5 minutes are available to travel between schools, but 15 are required"
+        );
+    }
+
+    #[test]
+    fn retired_rooms_flagged() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+        let registry = RoomRegistry::from_data(
+            "U101 [retired 2025-08-01, replaced by U201]\nU102 [retired 2025-09-01]\nH201",
+        )
+        .unwrap();
+
+        let mut timetable = Timetable::new();
+        timetable.insert(
+            timeslot!(W1MP1),
+            lesson("Ma", "10A", "JSM", "H201".parse().unwrap()),
+        );
+        let split = timetable
+            .insert_entry(
+                Entry::new(
+                    timeslot!(W1MP2),
+                    lesson("En", "10B", "ABC", "H201".parse().unwrap()),
+                )
+                .split_room("U101".parse().unwrap())
+                .rotating(
+                    crate::Rotation::new(date(1, 6))
+                        .stage(None, "U102".parse().unwrap())
+                        .stage(None, "U101".parse().unwrap()),
+                ),
+            )
+            .unwrap();
+
+        let messages = |month, day| {
+            RetiredRooms::on(date(month, day))
+                .registry(registry.clone())
+                .check(&timetable)
+                .into_iter()
+                .inspect(|finding| assert_eq!(finding.entries, [split]))
+                .map(|finding| finding.message)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(messages(7, 31).is_empty());
+        assert_eq!(
+            messages(8, 1),
+            ["10B (En) uses U101, which was retired on 2025-08-01 (replaced by U201)"]
+        );
+
+        // A room is only reported once per lesson
+        assert_eq!(
+            messages(9, 1)[1],
+            "10B (En) uses U102, which was retired on 2025-09-01"
+        );
+        assert_eq!(messages(9, 1).len(), 2);
+    }
+}
@@ -0,0 +1,389 @@
+//! JavaScript bindings (generated by [`wasm-bindgen`]) for the web timetable
+//! viewer.
+//!
+//! The bindings expose the room numbering schemes (i.e., parsing, formatting,
+//! and describing room identifiers), timetable loading (from the TOML
+//! definition format -- *see [`Timetable::from_toml_str`] for more
+//! information*), and the current/next lesson queries:
+//!
+//! ```js
+//! import { Location, Timetable, WeekCycle } from "timetableau";
+//!
+//! const room = new Location("H201");
+//! console.log(room.verboseName); // "Howard Block, Floor 2, Room 01"
+//!
+//! const cycle = new WeekCycle("2023-09-04");
+//! cycle.addTerm("2023-09-04", "2023-10-20");
+//!
+//! const timetable = Timetable.fromToml(definition);
+//! const lesson = timetable.nextLesson(cycle, "2023-09-04T09:00");
+//! ```
+//!
+//! Dates take the form `YYYY-MM-DD`, and date-times take the form
+//! `YYYY-MM-DDTHH:MM` (optionally followed by seconds) -- date-times are
+//! always in the school's local time (i.e., they do not contain a time zone).
+//!
+//! [`wasm-bindgen`]: https://rustwasm.github.io/docs/wasm-bindgen/
+
+use crate::timetable::periods_of;
+use crate::{
+    AcademicCalendar, Activity, Location, Period, Term, TimedLesson, Timetable, WeekCycle,
+};
+use chrono::prelude::*;
+use wasm_bindgen::prelude::*;
+
+/// Whether `code` is a valid room identifier (of either school).
+#[wasm_bindgen(js_name = isValidLocation)]
+pub fn is_valid_location(code: &str) -> bool {
+    code.parse::<Location>().is_ok()
+}
+
+/// Formats the room identifier `code` in its canonical form (i.e., the form
+/// produced by the Rust tooling).
+///
+/// # Errors
+///
+/// An error is thrown should `code` not be a valid room identifier.
+#[wasm_bindgen(js_name = formatLocation)]
+pub fn format_location(code: &str) -> Result<String, JsError> {
+    Ok(code.parse::<Location>()?.to_string())
+}
+
+/// A room of either the Highfield school or the Fearnhill school.
+///
+/// *See [`Location`] for more information*.
+#[wasm_bindgen(js_name = Location)]
+pub struct JsLocation(Location);
+
+#[wasm_bindgen(js_class = Location)]
+impl JsLocation {
+    /// Parses a room identifier (e.g., `H201` or `FH Gym`).
+    ///
+    /// # Errors
+    ///
+    /// An error is thrown should `code` not be a valid room identifier.
+    #[wasm_bindgen(constructor)]
+    pub fn new(code: &str) -> Result<JsLocation, JsError> {
+        Ok(Self(code.parse()?))
+    }
+
+    /// The verbose name of the room (e.g., `Howard Block, Floor 2, Room 01`).
+    #[wasm_bindgen(getter, js_name = verboseName)]
+    pub fn verbose_name(&self) -> String {
+        self.0.verbose_name()
+    }
+
+    /// The name of the school at which the room is located (i.e.,
+    /// `Highfield` or `Fearnhill`).
+    #[wasm_bindgen(getter)]
+    pub fn school(&self) -> String {
+        self.0.school().to_string()
+    }
+
+    /// The canonical room identifier (e.g., `H201`).
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// The cycle of the two-week alternating timetable.
+///
+/// *See [`WeekCycle`] for more information*.
+#[wasm_bindgen(js_name = WeekCycle)]
+pub struct JsWeekCycle(WeekCycle);
+
+#[wasm_bindgen(js_class = WeekCycle)]
+impl JsWeekCycle {
+    /// Creates a new cycle anchored from the Monday `anchor` (which must be
+    /// the start of week one) -- the cycle contains no terms until they are
+    /// [added](Self::add_term).
+    ///
+    /// # Errors
+    ///
+    /// An error is thrown should `anchor` not be a valid date, or should it
+    /// not be a Monday.
+    #[wasm_bindgen(constructor)]
+    pub fn new(anchor: &str) -> Result<JsWeekCycle, JsError> {
+        Ok(Self(WeekCycle::new(
+            date(anchor)?,
+            AcademicCalendar::default(),
+        )?))
+    }
+
+    /// Adds a term (from `start` to `end`, inclusive) to the cycle's
+    /// calendar.
+    ///
+    /// # Errors
+    ///
+    /// An error is thrown should either date be invalid, or should `start`
+    /// occur after `end`.
+    #[wasm_bindgen(js_name = addTerm)]
+    pub fn add_term(&mut self, start: &str, end: &str) -> Result<(), JsError> {
+        let term = Term::new(date(start)?, date(end)?)?;
+
+        self.0.calendar_mut().add_term(term);
+        Ok(())
+    }
+}
+
+/// A timetable.
+///
+/// *See [`Timetable`] for more information*.
+#[wasm_bindgen(js_name = Timetable)]
+pub struct JsTimetable(Timetable);
+
+#[wasm_bindgen(js_class = Timetable)]
+impl JsTimetable {
+    /// Loads a timetable from its TOML definition.
+    ///
+    /// # Errors
+    ///
+    /// An error (describing the line and column at fault) is thrown should
+    /// the definition be invalid.
+    #[wasm_bindgen(js_name = fromToml)]
+    pub fn from_toml(definition: &str) -> Result<JsTimetable, JsError> {
+        Ok(Self(Timetable::from_toml_str(definition)?))
+    }
+
+    /// Converts the timetable into its TOML definition.
+    #[wasm_bindgen(js_name = toToml)]
+    pub fn to_toml(&self) -> String {
+        self.0.to_toml_string()
+    }
+
+    /// The lesson taking place at the `datetime` provided (or `undefined`
+    /// should no lesson be taking place).
+    ///
+    /// # Errors
+    ///
+    /// An error is thrown should `datetime` be invalid.
+    #[wasm_bindgen(js_name = currentLesson)]
+    pub fn current_lesson(
+        &self,
+        cycle: &JsWeekCycle,
+        datetime: &str,
+    ) -> Result<Option<JsLesson>, JsError> {
+        let datetime = local(datetime)?;
+
+        Ok(self
+            .0
+            .current_lesson(&cycle.0, datetime)
+            .and_then(|lesson| JsLesson::new(&self.0, &cycle.0, lesson)))
+    }
+
+    /// The next lesson to start after the `datetime` provided (or
+    /// `undefined` should no lessons remain in the cycle's terms).
+    ///
+    /// # Errors
+    ///
+    /// An error is thrown should `datetime` be invalid.
+    #[wasm_bindgen(js_name = nextLesson)]
+    pub fn next_lesson(
+        &self,
+        cycle: &JsWeekCycle,
+        datetime: &str,
+    ) -> Result<Option<JsLesson>, JsError> {
+        let datetime = local(datetime)?;
+
+        Ok(self
+            .0
+            .next_lesson(&cycle.0, datetime)
+            .and_then(|lesson| JsLesson::new(&self.0, &cycle.0, lesson)))
+    }
+}
+
+/// A lesson taking place on a specific date.
+///
+/// *See [`Occurrence`](crate::Occurrence) and [`TimedLesson`] for more
+/// information*.
+#[wasm_bindgen(js_name = Lesson)]
+pub struct JsLesson {
+    date: NaiveDate,
+    first: Period,
+    last: Period,
+    start: NaiveTime,
+    end: NaiveTime,
+    subject: String,
+    class: String,
+    teacher: Option<String>,
+    location: Location,
+    co_teachers: Vec<String>,
+    split_rooms: Vec<Location>,
+}
+
+impl JsLesson {
+    fn new(timetable: &Timetable, cycle: &WeekCycle, lesson: TimedLesson) -> Option<Self> {
+        let TimedLesson {
+            occurrence,
+            start,
+            end,
+            ..
+        } = lesson;
+        let (first, last) = periods_of(&timetable.occurrences(cycle, occurrence.date), &occurrence);
+        let entry = occurrence.entry.and_then(|id| timetable.get(id));
+
+        let (subject, class, teacher, location) = match occurrence.activity {
+            Activity::Lesson {
+                subject,
+                class,
+                teacher,
+                location,
+            } => (subject, class, teacher, location),
+            _ => return None,
+        };
+
+        Some(Self {
+            date: occurrence.date,
+            first,
+            last,
+            start: start.time(),
+            end: end.time(),
+            subject: subject.name().clone(),
+            class: class.reference().clone(),
+            teacher: teacher.map(|teacher| teacher.code().clone()),
+            location,
+            co_teachers: entry.map_or_else(Vec::new, |entry| {
+                entry
+                    .co_teachers
+                    .iter()
+                    .map(|teacher| teacher.code().clone())
+                    .collect()
+            }),
+            split_rooms: entry.map_or_else(Vec::new, |entry| entry.split_rooms.clone()),
+        })
+    }
+}
+
+#[wasm_bindgen(js_class = Lesson)]
+impl JsLesson {
+    /// The date on which the lesson takes place (e.g., `2023-09-04`).
+    #[wasm_bindgen(getter)]
+    pub fn date(&self) -> String {
+        self.date.to_string()
+    }
+
+    /// The number of the period during which the lesson takes place (i.e.,
+    /// `1` to `5`) -- for a lesson spanning multiple periods (e.g., a double
+    /// lesson), its first period.
+    #[wasm_bindgen(getter)]
+    pub fn period(&self) -> u8 {
+        self.first as u8 + 1
+    }
+
+    /// The number of consecutive periods the lesson spans (e.g., `2` for a
+    /// double lesson).
+    #[wasm_bindgen(getter)]
+    pub fn periods(&self) -> u8 {
+        self.last as u8 - self.first as u8 + 1
+    }
+
+    /// The time at which the lesson starts (e.g., `08:50`) -- for a lesson
+    /// spanning multiple periods, the start of its first period.
+    #[wasm_bindgen(getter)]
+    pub fn start(&self) -> String {
+        self.start.format("%H:%M").to_string()
+    }
+
+    /// The time at which the lesson ends (e.g., `09:50`) -- for a lesson
+    /// spanning multiple periods, the end of its last period.
+    #[wasm_bindgen(getter)]
+    pub fn end(&self) -> String {
+        self.end.format("%H:%M").to_string()
+    }
+
+    /// The subject of the lesson.
+    #[wasm_bindgen(getter)]
+    pub fn subject(&self) -> String {
+        self.subject.clone()
+    }
+
+    /// The class of the lesson.
+    #[wasm_bindgen(getter)]
+    pub fn class(&self) -> String {
+        self.class.clone()
+    }
+
+    /// The staff code of the lesson's teacher (or `undefined` should the
+    /// teacher be unknown).
+    #[wasm_bindgen(getter)]
+    pub fn teacher(&self) -> Option<String> {
+        self.teacher.clone()
+    }
+
+    /// The room in which the lesson takes place.
+    #[wasm_bindgen(getter)]
+    pub fn location(&self) -> JsLocation {
+        JsLocation(self.location)
+    }
+
+    /// The staff codes of the lesson's teachers besides its own teacher
+    /// (should the lesson be team-taught).
+    #[wasm_bindgen(getter, js_name = coTeachers)]
+    pub fn co_teachers(&self) -> Vec<String> {
+        self.co_teachers.clone()
+    }
+
+    /// The rooms across which the lesson is split besides its own room.
+    #[wasm_bindgen(getter, js_name = splitRooms)]
+    pub fn split_rooms(&self) -> Vec<JsLocation> {
+        self.split_rooms.iter().copied().map(JsLocation).collect()
+    }
+}
+
+// Parse a date of the form `YYYY-MM-DD`
+fn date(s: &str) -> Result<NaiveDate, JsError> {
+    s.parse()
+        .map_err(|_| JsError::new(&format!("invalid date `{}`", s)))
+}
+
+// Parse a local date-time of the form `YYYY-MM-DDTHH:MM[:SS]` (the time zone
+// is irrelevant, as only the local date and time are used by the queries)
+fn local(s: &str) -> Result<DateTime<Utc>, JsError> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M"))
+        .map(|datetime| Utc.from_utc_datetime(&datetime))
+        .map_err(|_| JsError::new(&format!("invalid date-time `{}`", s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lessons_span_periods() {
+        let timetable = JsTimetable::from_toml(
+            "[week1]\n\
+             mon.p3 = { subject = 'Sc', class = '10A', teacher = 'JSM', room = 'H201', \
+             periods = 2, co_teachers = ['ABC'], split_rooms = ['H202'] }",
+        )
+        .unwrap();
+        let mut cycle = JsWeekCycle::new("2023-09-04").unwrap();
+        cycle.add_term("2023-09-04", "2023-10-20").unwrap();
+
+        // The whole of a double lesson is timed, from either of its periods
+        for datetime in ["2023-09-04T09:00", "2023-09-04T11:20", "2023-09-04T12:20"] {
+            let lesson = timetable
+                .next_lesson(&cycle, datetime)
+                .unwrap()
+                .or_else(|| timetable.current_lesson(&cycle, datetime).unwrap())
+                .unwrap();
+
+            assert_eq!(lesson.period(), 3);
+            assert_eq!(lesson.periods(), 2);
+            assert_eq!(
+                (lesson.start(), lesson.end()),
+                ("11:10".into(), "13:10".into())
+            );
+            assert_eq!(lesson.co_teachers(), ["ABC"]);
+            assert_eq!(
+                lesson
+                    .split_rooms()
+                    .iter()
+                    .map(JsLocation::to_js_string)
+                    .collect::<Vec<_>>(),
+                ["H202"]
+            );
+        }
+    }
+}
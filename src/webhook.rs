@@ -0,0 +1,630 @@
+//! Outbound webhooks notifying other systems of changes to a timetable.
+//!
+//! A [`Dispatcher`] POSTs each [`WebhookEvent`] (e.g., a lesson changing
+//! room) as a JSON body to every subscribed [`Webhook`], such that a
+//! notifier (e.g., for Teams or Slack) can react to changes without polling.
+//! Deliveries which fail are retried with an exponential backoff:
+//!
+//! ```
+//! use chrono::{Duration, TimeZone, Utc};
+//! use timetableau::webhook::{Dispatcher, Webhook, WebhookEvent, WebhookTransport};
+//! use timetableau::timeslot;
+//!
+//! struct Notifier;
+//!
+//! impl WebhookTransport for Notifier {
+//!     fn post(&self, url: &str, headers: &[(String, String)], body: &str) -> Result<u16, String> {
+//!         // A real transport would send the request (and its headers)
+//!         Ok(204)
+//!     }
+//! }
+//!
+//! let now = Utc.with_ymd_and_hms(2023, 9, 4, 8, 0, 0).unwrap();
+//!
+//! let mut dispatcher = Dispatcher::new().backoff(Duration::seconds(30));
+//! dispatcher.subscribe(Webhook::new(
+//!     "https://notifier.example/hooks/rooms".to_string(),
+//!     b"secret".to_vec(),
+//! ));
+//!
+//! dispatcher.enqueue(
+//!     &WebhookEvent::RoomChanged {
+//!         slot: timeslot!(W1MP1),
+//!         periods: 1,
+//!         class: Some("10A".to_string()),
+//!         from: Some("H201".parse().unwrap()),
+//!         to: "Hall".parse().unwrap(),
+//!     },
+//!     now,
+//! );
+//!
+//! assert_eq!(dispatcher.dispatch(&Notifier, now), 1);
+//! assert!(dispatcher.pending().is_empty());
+//! ```
+//!
+//! # Deliveries
+//!
+//! Each delivery is a `POST` with the following headers:
+//!
+//! * `Content-Type: application/json`
+//! * `X-Timetableau-Event` -- the name of the event (e.g., `room-changed`).
+//! * `X-Timetableau-Delivery` -- the identifier of the delivery, which is
+//!   the same for every attempt (such that retried deliveries can be
+//!   deduplicated).
+//! * `X-Timetableau-Signature` -- `sha256=` followed by the hexadecimal
+//!   HMAC-SHA-256 of the body, keyed by the secret of the webhook, which
+//!   allows the receiver to verify the sender (*see [`sign`]*).
+//!
+//! A delivery succeeds should the receiver respond with a `2xx` status code.
+
+use crate::closures::{Closure, ClosureScope};
+use crate::hmac::{hex, hmac_sha256};
+use crate::json::write_string;
+use crate::timeslot::slot_key;
+use crate::{Activity, Change, Location, Record, RoomOverride, Teacher, TimeSlot, Timetable};
+use chrono::{DateTime, Duration, Utc};
+
+/// A change to a timetable which is delivered to [`Webhook`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookEvent {
+    /// A lesson (or every lesson of a slot) was moved to a different room.
+    RoomChanged {
+        /// The slot of the lesson.
+        slot: TimeSlot,
+
+        /// The number of consecutive periods the lesson spans, from its slot
+        /// (e.g., `2` for a double lesson, which changes room for both).
+        periods: u8,
+
+        /// The class of the lesson (if known).
+        class: Option<String>,
+
+        /// The room from which the lesson was moved (if known).
+        from: Option<Location>,
+
+        /// The room to which the lesson was moved.
+        to: Location,
+    },
+
+    /// The lessons of a slot were moved to a different room for a range of
+    /// dates (*see [`RoomOverride`]*).
+    RoomOverridden(RoomOverride),
+
+    /// A teacher was assigned to cover a lesson.
+    CoverAssigned {
+        /// The slot of the lesson.
+        slot: TimeSlot,
+
+        /// The class of the lesson.
+        class: String,
+
+        /// The teacher covering the lesson.
+        teacher: Teacher,
+    },
+
+    /// A set of rooms was closed (*see [`Closure`]*).
+    RoomsClosed(Closure),
+}
+
+impl WebhookEvent {
+    /// Creates the event describing the change of the `record` provided,
+    /// should the change be delivered to webhooks (i.e., a lesson being
+    /// relocated, or a room override being added).
+    ///
+    /// The `timetable` is the timetable after the change was made, from
+    /// which the slot and class of a relocated lesson are retrieved.
+    pub fn from_record(record: &Record, timetable: &Timetable) -> Option<Self> {
+        match &record.change {
+            Change::Relocated { id, from, to } => {
+                let entry = timetable.get(*id)?;
+
+                let class = match &entry.activity {
+                    Activity::Lesson { class, .. } => Some(class.reference().to_string()),
+                    _ => None,
+                };
+
+                Some(Self::RoomChanged {
+                    slot: entry.slot,
+                    periods: entry.periods.get(),
+                    class,
+                    from: Some(*from),
+                    to: *to,
+                })
+            }
+            Change::OverrideAdded(room_override) => Some(Self::RoomOverridden(*room_override)),
+            _ => None,
+        }
+    }
+
+    /// Retrieves the name of the event (e.g., `room-changed`), which is sent
+    /// as the `X-Timetableau-Event` header of each delivery.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RoomChanged { .. } => "room-changed",
+            Self::RoomOverridden(_) => "room-overridden",
+            Self::CoverAssigned { .. } => "cover-assigned",
+            Self::RoomsClosed(_) => "rooms-closed",
+        }
+    }
+
+    /// Serialises the event as the JSON body of a delivery (e.g.,
+    /// `{"event":"room-changed","slot":"week1.mon.p1",...}`).
+    ///
+    /// # Remarks
+    ///
+    /// The scope of a closure is the prefix of the identifiers of the rooms
+    /// it closes (e.g., `H` for the Howard block, `H2` for its second floor,
+    /// or `FH S` for the Science section).
+    pub fn to_json(&self) -> String {
+        let mut output = String::from("{\"event\":");
+        write_string(&mut output, self.name());
+
+        let mut field = |name: &str, value: Option<&str>| {
+            output.push_str(",\"");
+            output.push_str(name);
+            output.push_str("\":");
+
+            match value {
+                Some(value) => write_string(&mut output, value),
+                None => output.push_str("null"),
+            }
+        };
+
+        match self {
+            Self::RoomChanged {
+                slot,
+                periods,
+                class,
+                from,
+                to,
+            } => {
+                field("slot", Some(&slot_key(*slot)));
+                field("class", class.as_deref());
+                field("from", from.map(|from| from.to_string()).as_deref());
+                field("to", Some(&to.to_string()));
+
+                output.push_str(",\"periods\":");
+                output.push_str(&periods.to_string());
+            }
+            Self::RoomOverridden(room_override) => {
+                field("slot", Some(&slot_key(room_override.slot)));
+                field("start", Some(&room_override.start.to_string()));
+                field("end", Some(&room_override.end.to_string()));
+                field(
+                    "from",
+                    room_override.from.map(|from| from.to_string()).as_deref(),
+                );
+                field("to", Some(&room_override.location.to_string()));
+            }
+            Self::CoverAssigned {
+                slot,
+                class,
+                teacher,
+            } => {
+                field("slot", Some(&slot_key(*slot)));
+                field("class", Some(class));
+                field("teacher", Some(teacher.code()));
+            }
+            Self::RoomsClosed(closure) => {
+                let scope = match closure.scope {
+                    ClosureScope::Room(location) => location.to_string(),
+                    ClosureScope::Block(block) => block.code().to_string(),
+                    ClosureScope::Floor(block, floor) => {
+                        format!("{}{}", block.code(), floor)
+                    }
+                    ClosureScope::Section(section) => format!("FH {}", section.code()),
+                };
+
+                field("scope", Some(&scope));
+                field("start", Some(&closure.start.to_string()));
+                field("end", Some(&closure.end.to_string()));
+                field("reason", Some(&closure.reason));
+            }
+        }
+
+        output.push('}');
+        output
+    }
+}
+
+/// An endpoint to which [`WebhookEvent`]s are delivered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    url: String,
+    secret: Vec<u8>,
+}
+
+impl Webhook {
+    /// Creates a new `Webhook` delivering to the `url` provided, whose
+    /// deliveries are signed using the `secret` provided.
+    pub fn new(url: String, secret: Vec<u8>) -> Self {
+        Self { url, secret }
+    }
+
+    /// Retrieves the URL to which the events are delivered.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// Calculates the signature of a delivery's `body` (i.e., the value of its
+/// `X-Timetableau-Signature` header), using the `secret` of its webhook.
+///
+/// Receivers verify a delivery by calculating the signature of its body
+/// themselves, and comparing it with the header.
+pub fn sign(secret: &[u8], body: &str) -> String {
+    format!("sha256={}", hex(&hmac_sha256(secret, body.as_bytes())))
+}
+
+/// The means by which a [`Dispatcher`] sends its deliveries (i.e., an HTTP
+/// client).
+pub trait WebhookTransport {
+    /// POSTs the `body` provided (with the `headers` provided) to the `url`
+    /// provided.
+    ///
+    /// # Returns
+    ///
+    /// The status code of the response.
+    ///
+    /// # Errors
+    ///
+    /// A description of the problem should no response have been received
+    /// (e.g., the receiver is unreachable) -- the delivery is retried.
+    fn post(&self, url: &str, headers: &[(String, String)], body: &str) -> Result<u16, String>;
+}
+
+/// A pending (or failed) delivery of a [`WebhookEvent`] to a [`Webhook`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivery {
+    /// The identifier of the delivery (*see the [module](self)
+    /// documentation*).
+    pub id: u64,
+
+    /// The URL of the webhook.
+    pub url: String,
+
+    /// The name of the event.
+    pub event: &'static str,
+
+    /// The JSON body of the delivery.
+    pub body: String,
+
+    /// The signature of the body.
+    pub signature: String,
+
+    /// The number of attempts which have been made.
+    pub attempts: u32,
+
+    /// The time at which the next attempt is due.
+    pub due: DateTime<Utc>,
+
+    /// The reason the latest attempt failed (if any).
+    pub error: Option<String>,
+}
+
+/// Delivers [`WebhookEvent`]s to subscribed [`Webhook`]s.
+///
+/// *See the [module](self) documentation for more information*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dispatcher {
+    webhooks: Vec<Webhook>,
+    pending: Vec<Delivery>,
+    failed: Vec<Delivery>,
+    max_attempts: u32,
+    backoff: Duration,
+    next_id: u64,
+}
+
+impl Dispatcher {
+    /// Creates a new `Dispatcher` without any webhooks, which attempts each
+    /// delivery `5` times, waiting `1` minute before the first retry.
+    pub fn new() -> Self {
+        Self {
+            webhooks: Vec::new(),
+            pending: Vec::new(),
+            failed: Vec::new(),
+            max_attempts: 5,
+            backoff: Duration::minutes(1),
+            next_id: 0,
+        }
+    }
+
+    /// Sets the number of attempts made for each delivery before it is
+    /// abandoned.
+    ///
+    /// # Panics
+    ///
+    /// Panics should `max_attempts` be `0`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        assert!(
+            max_attempts > 0,
+            "a delivery must be attempted at least once"
+        );
+
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the time waited before the first retry of a delivery -- the
+    /// time is doubled for each subsequent retry.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Subscribes the `webhook` provided to every subsequently enqueued
+    /// event.
+    pub fn subscribe(&mut self, webhook: Webhook) {
+        self.webhooks.push(webhook);
+    }
+
+    /// Retrieves the subscribed webhooks.
+    pub fn webhooks(&self) -> &[Webhook] {
+        &self.webhooks
+    }
+
+    /// Enqueues a delivery of the `event` provided to every subscribed
+    /// webhook, due at the time provided (i.e., `now`).
+    pub fn enqueue(&mut self, event: &WebhookEvent, now: DateTime<Utc>) {
+        let body = event.to_json();
+
+        for webhook in &self.webhooks {
+            self.pending.push(Delivery {
+                id: self.next_id,
+                url: webhook.url.clone(),
+                event: event.name(),
+                signature: sign(&webhook.secret, &body),
+                body: body.clone(),
+                attempts: 0,
+                due: now,
+                error: None,
+            });
+
+            self.next_id += 1;
+        }
+    }
+
+    /// Enqueues the event describing the change of the `record` provided
+    /// (*see [`WebhookEvent::from_record`]*), should it be delivered to
+    /// webhooks.
+    ///
+    /// # Returns
+    ///
+    /// `true` should an event have been enqueued.
+    pub fn enqueue_record(&mut self, record: &Record, timetable: &Timetable) -> bool {
+        match WebhookEvent::from_record(record, timetable) {
+            Some(event) => {
+                self.enqueue(&event, record.at);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Attempts every delivery which is due at the time provided (i.e.,
+    /// `now`), using the `transport` provided.
+    ///
+    /// # Returns
+    ///
+    /// The number of deliveries which succeeded.
+    ///
+    /// # Remarks
+    ///
+    /// A delivery which fails is retried once its backoff has elapsed (*see
+    /// [`Self::backoff`]*) -- a delivery which has failed every attempt is
+    /// moved to the [failed deliveries](Self::failed).
+    pub fn dispatch<T: WebhookTransport + ?Sized>(
+        &mut self,
+        transport: &T,
+        now: DateTime<Utc>,
+    ) -> usize {
+        let mut delivered = 0;
+        let mut pending = Vec::new();
+
+        for mut delivery in std::mem::take(&mut self.pending) {
+            if delivery.due > now {
+                pending.push(delivery);
+                continue;
+            }
+
+            let headers = [
+                ("Content-Type", "application/json"),
+                ("X-Timetableau-Event", delivery.event),
+                ("X-Timetableau-Delivery", &delivery.id.to_string()),
+                ("X-Timetableau-Signature", &delivery.signature),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+
+            delivery.attempts += 1;
+
+            let error = match transport.post(&delivery.url, &headers, &delivery.body) {
+                Ok(200..=299) => {
+                    delivered += 1;
+                    continue;
+                }
+                Ok(status) => format!("the receiver responded with status {}", status),
+                Err(error) => error,
+            };
+
+            delivery.error = Some(error);
+
+            if delivery.attempts >= self.max_attempts {
+                self.failed.push(delivery);
+            } else {
+                delivery.due = now + self.backoff * 2i32.pow(delivery.attempts - 1);
+                pending.push(delivery);
+            }
+        }
+
+        self.pending = pending;
+        delivered
+    }
+
+    /// Retrieves the deliveries which are yet to succeed (in the order in
+    /// which they were enqueued).
+    pub fn pending(&self) -> &[Delivery] {
+        &self.pending
+    }
+
+    /// Retrieves the deliveries which were abandoned after failing every
+    /// attempt (e.g., to be reported to an administrator).
+    pub fn failed(&self) -> &[Delivery] {
+        &self.failed
+    }
+}
+
+impl Default for Dispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{timeslot, Class, Subject};
+    use std::cell::RefCell;
+
+    // The URL, headers, and body of a delivery
+    type Post = (String, Vec<(String, String)>, String);
+
+    // A transport which records each delivery and responds with the next of
+    // its responses
+    struct Recorded {
+        posts: RefCell<Vec<Post>>,
+        responses: RefCell<Vec<Result<u16, String>>>,
+    }
+
+    impl WebhookTransport for Recorded {
+        fn post(&self, url: &str, headers: &[(String, String)], body: &str) -> Result<u16, String> {
+            self.posts
+                .borrow_mut()
+                .push((url.to_string(), headers.to_vec(), body.to_string()));
+            self.responses.borrow_mut().remove(0)
+        }
+    }
+
+    #[test]
+    fn dispatcher_retries_and_signs() {
+        let transport = Recorded {
+            posts: RefCell::new(Vec::new()),
+            responses: RefCell::new(vec![
+                Err("unreachable".to_string()),
+                Ok(200),
+                Ok(500),
+                Ok(503),
+            ]),
+        };
+
+        let mut timetable = Timetable::new();
+        let id = timetable.insert(
+            timeslot!(W1MP1),
+            Activity::Lesson {
+                subject: Subject::new("Ma".to_string()).unwrap(),
+                class: Class::new("10A".to_string()).unwrap(),
+                teacher: None,
+                location: "H201".parse().unwrap(),
+            },
+        );
+        timetable.relocate(id, "Hall".parse().unwrap());
+
+        // Events of records are due at the time of their change
+        let now = timetable.history()[1].at;
+
+        let mut dispatcher = Dispatcher::new()
+            .max_attempts(2)
+            .backoff(Duration::seconds(30));
+        dispatcher.subscribe(Webhook::new("https://a.example".to_string(), b"a".to_vec()));
+        dispatcher.subscribe(Webhook::new("https://b.example".to_string(), b"b".to_vec()));
+
+        assert!(!dispatcher.enqueue_record(&timetable.history()[0], &timetable));
+        assert!(dispatcher.enqueue_record(&timetable.history()[1], &timetable));
+
+        // The first webhook is unreachable, and is retried after the backoff
+        assert_eq!(dispatcher.dispatch(&transport, now), 1);
+        assert_eq!(dispatcher.pending().len(), 1);
+        assert_eq!(dispatcher.pending()[0].due, now + Duration::seconds(30));
+        assert_eq!(dispatcher.dispatch(&transport, now), 0);
+
+        dispatcher.enqueue(
+            &WebhookEvent::CoverAssigned {
+                slot: timeslot!(W1MP2),
+                class: "10A".to_string(),
+                teacher: Teacher::new("JSM".to_string()).unwrap(),
+            },
+            now + Duration::seconds(60),
+        );
+
+        assert_eq!(
+            dispatcher.dispatch(&transport, now + Duration::seconds(30)),
+            0
+        );
+        assert_eq!(dispatcher.failed().len(), 1);
+        assert_eq!(dispatcher.failed()[0].attempts, 2);
+        assert_eq!(
+            dispatcher.failed()[0].error.as_deref(),
+            Some("the receiver responded with status 500")
+        );
+
+        let posts = transport.posts.borrow();
+        let (url, headers, body) = &posts[0];
+
+        assert_eq!(url, "https://a.example");
+        assert_eq!(
+            body,
+            r#"{"event":"room-changed","slot":"week1.mon.p1","class":"10A","from":"H201","to":"Hall","periods":1}"#
+        );
+        assert_eq!(
+            headers[1],
+            (
+                "X-Timetableau-Event".to_string(),
+                "room-changed".to_string()
+            )
+        );
+        assert_eq!(headers[3].1, sign(b"a", body));
+        assert_eq!(posts[2].1[2], headers[2]);
+        assert_ne!(posts[1].1[3], headers[3]);
+    }
+
+    #[test]
+    fn webhook_event_json() {
+        let closure = Closure {
+            scope: ClosureScope::Section(crate::FearnhillSection::Science),
+            start: chrono::NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(),
+            end: chrono::NaiveDate::from_ymd_opt(2023, 9, 8).unwrap(),
+            reason: "Gas \"leak\"".to_string(),
+        };
+
+        assert_eq!(
+            WebhookEvent::RoomsClosed(closure).to_json(),
+            r#"{"event":"rooms-closed","scope":"FH S","start":"2023-09-04","end":"2023-09-08","reason":"Gas \"leak\""}"#
+        );
+
+        // A relocated double lesson changes room for both of its periods
+        let mut timetable = Timetable::new();
+        let id = timetable
+            .insert_spanning(
+                timeslot!(W1MP3),
+                2,
+                Activity::Lesson {
+                    subject: crate::Subject::new("Ma".to_string()).unwrap(),
+                    class: crate::Class::new("10A".to_string()).unwrap(),
+                    teacher: None,
+                    location: "H201".parse().unwrap(),
+                },
+            )
+            .unwrap();
+        timetable.relocate(id, "Hall".parse().unwrap());
+
+        assert_eq!(
+            WebhookEvent::from_record(&timetable.history()[1], &timetable)
+                .unwrap()
+                .to_json(),
+            r#"{"event":"room-changed","slot":"week1.mon.p3","class":"10A","from":"H201","to":"Hall","periods":2}"#
+        );
+        assert_eq!(
+            sign(b"key", "The quick brown fox jumps over the lazy dog"),
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{ParseYearGroupError, RangeError, RangedU8};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A year group of a secondary school or sixth form (i.e., from Year 7 to
+/// Year 13).
+///
+/// ```
+/// use timetableau::{KeyStage, YearGroup};
+///
+/// let year: YearGroup = "year 10".parse().unwrap();
+///
+/// assert_eq!(year, YearGroup::new(10).unwrap());
+/// assert_eq!(year.to_string(), "Y10");
+/// assert_eq!(year.key_stage(), KeyStage::Four);
+/// assert_eq!(YearGroup::of_class("10A/Ma1"), Some(year));
+/// ```
+///
+/// # Parsing
+///
+/// A year group is parsed (case-insensitively) from its number, optionally
+/// preceded by `Y` or `Year` (e.g., `10`, `Y10`, or `Year 10`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct YearGroup(RangedU8<7, 13>);
+
+impl YearGroup {
+    /// Creates a new `YearGroup`.
+    ///
+    /// # Returns
+    ///
+    /// [`Some`] if the `year` provided is in the range `7..=13`, or [`None`]
+    /// if it is not.
+    pub const fn new(year: u8) -> Option<Self> {
+        match RangedU8::new(year) {
+            Some(year) => Some(Self(year)),
+            None => None,
+        }
+    }
+
+    /// Creates a new `YearGroup`.
+    ///
+    /// # Errors
+    ///
+    /// [`RangeError`] is returned should the `year` provided be outside the
+    /// range `7..=13`.
+    pub const fn try_new(year: u8) -> Result<Self, RangeError> {
+        match RangedU8::try_new(year) {
+            Ok(year) => Ok(Self(year)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Retrieves the number of the year group (e.g., `10`).
+    pub const fn get(self) -> u8 {
+        self.0.get()
+    }
+
+    /// Creates an iterator over every `YearGroup`, in ascending order.
+    pub fn all() -> impl DoubleEndedIterator<Item = Self> + Clone {
+        RangedU8::all().map(Self)
+    }
+
+    /// Retrieves the [`KeyStage`] of the year group.
+    pub const fn key_stage(self) -> KeyStage {
+        match self.get() {
+            7..=9 => KeyStage::Three,
+            10 | 11 => KeyStage::Four,
+            _ => KeyStage::Five,
+        }
+    }
+
+    /// Determines the year group of the class with the `reference` provided
+    /// (i.e., the number at the start of the reference, optionally preceded
+    /// by a `Y`) -- for example, `10A/Ma1` and `Y10` are both in Year 10.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] should the reference not start with a year group (e.g.,
+    /// `Staff`).
+    pub fn of_class(reference: &str) -> Option<Self> {
+        let reference = reference.strip_prefix(['Y', 'y']).unwrap_or(reference);
+        let digits = reference
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(reference.len());
+
+        reference[..digits].parse().ok().and_then(Self::new)
+    }
+}
+
+impl From<YearGroup> for u8 {
+    fn from(year: YearGroup) -> Self {
+        year.get()
+    }
+}
+
+impl TryFrom<u8> for YearGroup {
+    type Error = RangeError;
+
+    fn try_from(year: u8) -> Result<Self, Self::Error> {
+        Self::try_new(year)
+    }
+}
+
+impl Display for YearGroup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Y{}", self.get())
+    }
+}
+
+impl FromStr for YearGroup {
+    type Err = ParseYearGroupError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        let number = lower
+            .strip_prefix("year")
+            .or_else(|| lower.strip_prefix('y'))
+            .unwrap_or(&lower)
+            .trim_start();
+
+        let year: u8 = number
+            .parse()
+            .map_err(|_| ParseYearGroupError::Invalid(s.to_string()))?;
+
+        Self::try_new(year).map_err(ParseYearGroupError::Range)
+    }
+}
+
+/// A key stage of the National Curriculum, grouping consecutive
+/// [`YearGroup`]s.
+///
+/// # Parsing
+///
+/// A key stage is parsed (case-insensitively) from `KS` or `Key Stage`,
+/// followed by its number (e.g., `KS4` or `Key Stage 4`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum KeyStage {
+    /// Key Stage 3 (Years 7 to 9).
+    Three,
+
+    /// Key Stage 4 (Years 10 and 11, i.e., GCSEs).
+    Four,
+
+    /// Key Stage 5 (Years 12 and 13, i.e., the sixth form).
+    Five,
+}
+
+impl KeyStage {
+    /// Every `KeyStage`, in ascending order.
+    pub const ALL: [KeyStage; 3] = [KeyStage::Three, KeyStage::Four, KeyStage::Five];
+
+    /// Retrieves the number of the key stage (e.g., `4`).
+    pub const fn number(self) -> u8 {
+        match self {
+            Self::Three => 3,
+            Self::Four => 4,
+            Self::Five => 5,
+        }
+    }
+
+    /// Retrieves the first year group of the key stage.
+    pub const fn first(self) -> YearGroup {
+        match self {
+            Self::Three => YearGroup(RangedU8::new(7).unwrap()),
+            Self::Four => YearGroup(RangedU8::new(10).unwrap()),
+            Self::Five => YearGroup(RangedU8::new(12).unwrap()),
+        }
+    }
+
+    /// Retrieves the last year group of the key stage.
+    pub const fn last(self) -> YearGroup {
+        match self {
+            Self::Three => YearGroup(RangedU8::new(9).unwrap()),
+            Self::Four => YearGroup(RangedU8::new(11).unwrap()),
+            Self::Five => YearGroup(RangedU8::new(13).unwrap()),
+        }
+    }
+
+    /// Creates an iterator over the year groups of the key stage, in
+    /// ascending order.
+    pub fn years(self) -> impl DoubleEndedIterator<Item = YearGroup> + Clone {
+        RangedU8::range(self.first().0, self.last().0).map(YearGroup)
+    }
+
+    /// Whether the `year` provided is in the key stage.
+    pub fn contains(self, year: YearGroup) -> bool {
+        year.key_stage() == self
+    }
+}
+
+impl From<YearGroup> for KeyStage {
+    fn from(year: YearGroup) -> Self {
+        year.key_stage()
+    }
+}
+
+impl Display for KeyStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "KS{}", self.number())
+    }
+}
+
+impl FromStr for KeyStage {
+    type Err = ParseYearGroupError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        let number = lower
+            .strip_prefix("key stage")
+            .or_else(|| lower.strip_prefix("ks"))
+            .ok_or_else(|| ParseYearGroupError::Invalid(s.to_string()))?
+            .trim_start();
+
+        Self::ALL
+            .into_iter()
+            .find(|stage| number == stage.number().to_string())
+            .ok_or_else(|| ParseYearGroupError::Invalid(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn year_groups_and_key_stages() {
+        let year = |number| YearGroup::new(number).unwrap();
+
+        assert_eq!("Y10".parse(), Ok(year(10)));
+        assert_eq!(" year 7".parse(), Ok(year(7)));
+        assert_eq!("13".parse(), Ok(year(13)));
+        assert_eq!(
+            "Y6".parse::<YearGroup>(),
+            Err(ParseYearGroupError::Range(RangeError::new(6, 7, 13)))
+        );
+        assert_eq!(
+            "Staff".parse::<YearGroup>(),
+            Err(ParseYearGroupError::Invalid("Staff".to_string()))
+        );
+
+        assert_eq!(YearGroup::of_class("y12B/Ph"), Some(year(12)));
+        assert_eq!(YearGroup::of_class("Staff"), None);
+        assert_eq!(YearGroup::of_class("5A"), None);
+        assert_eq!(YearGroup::all().count(), 7);
+
+        assert_eq!("KS5".parse(), Ok(KeyStage::Five));
+        assert_eq!("Key Stage 3".parse(), Ok(KeyStage::Three));
+        assert!("KS2".parse::<KeyStage>().is_err());
+        assert_eq!(
+            KeyStage::Four.years().collect::<Vec<_>>(),
+            [year(10), year(11)]
+        );
+        assert!(KeyStage::ALL
+            .iter()
+            .all(|stage| stage.years().all(|year| stage.contains(year))));
+        assert_eq!(KeyStage::from(year(9)).to_string(), "KS3");
+    }
+}
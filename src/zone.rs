@@ -0,0 +1,184 @@
+use chrono::prelude::*;
+use chrono::{Duration, MappedLocalTime};
+
+/// The time zone of both schools (i.e., `Europe/London`), which observes
+/// Greenwich Mean Time (`+00:00`) during the winter and British Summer Time
+/// (`+01:00`) during the summer.
+///
+/// The clocks go forward at `01:00` UTC on the last Sunday of March, and go
+/// back at `01:00` UTC on the last Sunday of October -- as a result, the
+/// local hour from `01:00` to `02:00` does not exist on the day the clocks go
+/// forward, and occurs twice on the day the clocks go back:
+///
+/// ```
+/// use chrono::{NaiveDate, TimeZone, Utc};
+/// use timetableau::London;
+///
+/// let local = NaiveDate::from_ymd_opt(2023, 10, 30)
+///     .unwrap()
+///     .and_hms_opt(9, 0, 0)
+///     .unwrap();
+///
+/// // The Monday after the clocks go back is observed in GMT
+/// assert_eq!(London.to_utc(local), Utc.with_ymd_and_hms(2023, 10, 30, 9, 0, 0).unwrap());
+///
+/// // Whereas the Friday before is observed in BST
+/// let instant = Utc.with_ymd_and_hms(2023, 10, 27, 8, 0, 0).unwrap();
+/// assert_eq!(instant.with_timezone(&London).to_string(), "2023-10-27 09:00:00 +01:00");
+/// ```
+///
+/// # Remarks
+///
+/// The rules are those in force since 1996 -- dates before then are treated
+/// as though the same rules applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct London;
+
+impl London {
+    /// Retrieves the instants (in UTC) at which the clocks go forward and go
+    /// back (in that order) during the `year` provided.
+    pub fn transitions(year: i32) -> (NaiveDateTime, NaiveDateTime) {
+        let at_one = |month| {
+            last_sunday(year, month)
+                .and_hms_opt(1, 0, 0)
+                .expect("01:00 is a valid time")
+        };
+
+        (at_one(3), at_one(10))
+    }
+
+    /// Converts a `local` date and time in London into UTC.
+    ///
+    /// # Remarks
+    ///
+    /// A local time occurring twice (as the clocks go back) is resolved to
+    /// its first occurrence (in BST), and a local time which does not exist
+    /// (as the clocks go forward) is resolved using GMT (e.g., `01:30` is
+    /// resolved to `01:30` UTC, which is `02:30` BST) -- such that every local
+    /// time can be converted.
+    pub fn to_utc(self, local: NaiveDateTime) -> DateTime<Utc> {
+        match self.from_local_datetime(&local) {
+            MappedLocalTime::Single(datetime) | MappedLocalTime::Ambiguous(datetime, _) => {
+                datetime.with_timezone(&Utc)
+            }
+            MappedLocalTime::None => Utc.from_utc_datetime(&local),
+        }
+    }
+}
+
+impl TimeZone for London {
+    type Offset = FixedOffset;
+
+    fn from_offset(_: &FixedOffset) -> Self {
+        London
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+        self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<FixedOffset> {
+        let (forward, back) = Self::transitions(local.year());
+        let hour = Duration::hours(1);
+
+        // The clocks go forward at 01:00 GMT (skipping to 02:00 BST), and go
+        // back at 02:00 BST (repeating from 01:00 GMT)
+        if *local >= forward && *local < forward + hour {
+            MappedLocalTime::None
+        } else if *local >= back && *local < back + hour {
+            MappedLocalTime::Ambiguous(summer(), winter())
+        } else if *local >= forward + hour && *local < back {
+            MappedLocalTime::Single(summer())
+        } else {
+            MappedLocalTime::Single(winter())
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+        self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        let (forward, back) = Self::transitions(utc.year());
+
+        if *utc >= forward && *utc < back {
+            summer()
+        } else {
+            winter()
+        }
+    }
+}
+
+// The offset of British Summer Time
+fn summer() -> FixedOffset {
+    FixedOffset::east_opt(3600).unwrap()
+}
+
+// The offset of Greenwich Mean Time
+fn winter() -> FixedOffset {
+    FixedOffset::east_opt(0).unwrap()
+}
+
+// Retrieves the last Sunday of the month provided
+fn last_sunday(year: i32, month: u32) -> NaiveDate {
+    // The last day of the month is the day before the first of the next month
+    let last = NaiveDate::from_ymd_opt(year, month + 1, 1).expect("the month is March or October")
+        - Duration::days(1);
+
+    last - Duration::days(last.weekday().num_days_from_sunday() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn london_transitions() {
+        assert_eq!(
+            London::transitions(2024),
+            (local(3, 31, 1, 0), local(10, 27, 1, 0))
+        );
+
+        // The hour skipped as the clocks go forward does not exist
+        assert_eq!(
+            London.offset_from_local_datetime(&local(3, 31, 1, 30)),
+            MappedLocalTime::None
+        );
+        assert_eq!(
+            London.to_utc(local(3, 31, 1, 30)),
+            Utc.from_utc_datetime(&local(3, 31, 1, 30))
+        );
+        assert_eq!(
+            London.to_utc(local(3, 31, 2, 0)),
+            Utc.from_utc_datetime(&local(3, 31, 1, 0))
+        );
+
+        // The hour repeated as the clocks go back occurs in BST first
+        assert_eq!(
+            London.offset_from_local_datetime(&local(10, 27, 1, 30)),
+            MappedLocalTime::Ambiguous(summer(), winter())
+        );
+        assert_eq!(
+            London.to_utc(local(10, 27, 1, 30)),
+            Utc.from_utc_datetime(&local(10, 27, 0, 30))
+        );
+        assert_eq!(
+            London.to_utc(local(10, 27, 2, 0)),
+            Utc.from_utc_datetime(&local(10, 27, 2, 0))
+        );
+
+        // Instants either side of the transitions
+        let at = |utc| London.offset_from_utc_datetime(&utc);
+        assert_eq!(at(local(3, 31, 0, 59)), winter());
+        assert_eq!(at(local(3, 31, 1, 0)), summer());
+        assert_eq!(at(local(10, 27, 0, 59)), summer());
+        assert_eq!(at(local(10, 27, 1, 0)), winter());
+    }
+}